@@ -1,5 +1,8 @@
+mod library_loader;
 mod main_args;
 mod run;
 mod sandbox;
 
+pub use library_loader::*;
+pub use main_args::MainArgs;
 pub use run::*;
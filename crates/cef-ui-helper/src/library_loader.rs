@@ -0,0 +1,104 @@
+use crate::main_args::{cef_main_args_t, MainArgs};
+use anyhow::{anyhow, Result};
+use libloading::{Library, Symbol};
+use std::{
+    env::current_exe,
+    ffi::{c_int, c_void},
+    fs::canonicalize,
+    path::{Path, PathBuf},
+    ptr::null_mut
+};
+use tracing::info;
+
+/// The path to the CEF framework library relative to the main app executable
+/// (<appname>.app/Contents/MacOS/<appname>).
+const MAIN_CEF_PATH: &str =
+    "../Frameworks/Chromium Embedded Framework.framework/Chromium Embedded Framework";
+
+/// The path to the CEF framework library relative to a helper executable
+/// (<appname>.app/Contents/Frameworks/<appname> Helper.app/Contents/MacOS/<appname> Helper).
+const HELPER_CEF_PATH: &str =
+    "../../../Chromium Embedded Framework.framework/Chromium Embedded Framework";
+
+/// Which kind of executable is loading the CEF framework. This determines
+/// where the framework is expected relative to the current executable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LibraryLoaderKind {
+    /// The main app executable.
+    Main,
+
+    /// A helper app executable.
+    Helper
+}
+
+/// On macOS the CEF framework cannot be linked into helper bundles and must be
+/// loaded at runtime. This locates the Chromium Embedded Framework bundle
+/// relative to the current executable and keeps it loaded until dropped.
+pub struct LibraryLoader {
+    path: PathBuf,
+    lib:  Library
+}
+
+impl LibraryLoader {
+    /// Locate and load the CEF framework for the given kind of executable.
+    pub fn new(kind: LibraryLoaderKind) -> Result<Self> {
+        let relative_path = match kind {
+            LibraryLoaderKind::Main => MAIN_CEF_PATH,
+            LibraryLoaderKind::Helper => HELPER_CEF_PATH
+        };
+
+        Self::load(&get_cef_path(relative_path)?)
+    }
+
+    /// Load the CEF framework from an explicit path.
+    pub fn load(path: &Path) -> Result<Self> {
+        let path = canonicalize(path)?;
+        let lib = unsafe { Library::new(&path)? };
+
+        info!("Loaded CEF framework: {}", path.display());
+
+        Ok(Self { path, lib })
+    }
+
+    /// The path of the loaded CEF framework library.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Execute a secondary process using the loaded framework's
+    /// cef_execute_process function, returning its exit code.
+    pub fn execute_process(&self, main_args: &MainArgs) -> Result<i32> {
+        unsafe {
+            let cef_execute_process: Symbol<
+                unsafe extern "C" fn(
+                    args: *const cef_main_args_t,
+                    *mut c_void,
+                    *mut c_void
+                ) -> c_int
+            > = self
+                .lib
+                .get(b"cef_execute_process")?;
+
+            Ok(cef_execute_process(main_args.as_raw(), null_mut(), null_mut()) as i32)
+        }
+    }
+
+    /// Unload the CEF framework.
+    pub fn close(self) -> Result<()> {
+        self.lib.close()?;
+
+        info!("Closed CEF library.");
+
+        Ok(())
+    }
+}
+
+/// Get the cef library path.
+fn get_cef_path(relative_path: &str) -> Result<PathBuf> {
+    let cef_path = current_exe()?
+        .parent()
+        .map(PathBuf::from)
+        .ok_or_else(|| anyhow!("Could not get parent directory"))?;
+
+    Ok(cef_path.join(relative_path))
+}
@@ -1,13 +1,13 @@
 use anyhow::Result;
 use cef_ui::{
-    App, AppCallbacks, Browser, BrowserHost, BrowserProcessHandler, BrowserSettings, Client,
-    ClientCallbacks, CommandLine, Context, ContextMenuHandler, ContextMenuHandlerCallbacks,
-    ContextMenuParams, DictionaryValue, EventFlags, Frame, KeyboardHandler, LifeSpanHandler,
-    LifeSpanHandlerCallbacks, LogSeverity, MainArgs, MenuCommandId, MenuModel, Point,
-    PopupFeatures, QuickMenuEditStateFlags, RenderHandler, RunContextMenuCallback,
-    RunQuickMenuCallback, Settings, Size, WindowInfo, WindowOpenDisposition
+    quit_message_loop, App, AppCallbacks, Browser, BrowserHost, BrowserSettings, Client,
+    ClientCallbacks, CommandLine, ConsoleLogger, Context, ContextMenuHandler,
+    ContextMenuHandlerCallbacks, ContextMenuParams, DictionaryValue, DisplayHandler, Frame,
+    LifeSpanHandler, LifeSpanHandlerCallbacks, LogSeverity, MainArgs, MenuModel, PopupFeatures,
+    Settings, WindowInfo, WindowOpenDisposition
 };
-use cef_ui_sys::cef_quit_message_loop;
+#[cfg(target_os = "macos")]
+use cef_ui::{init_application, set_default_main_menu};
 use std::{fs::create_dir_all, path::PathBuf, process::exit};
 use tracing::{error, info, level_filters::LevelFilter, subscriber::set_global_default, Level};
 use tracing_log::LogTracer;
@@ -30,54 +30,6 @@ impl ContextMenuHandlerCallbacks for MyContextMenuHandler {
             error!("{}", e);
         }
     }
-
-    fn run_context_menu(
-        &mut self,
-        browser: Browser,
-        frame: Frame,
-        params: ContextMenuParams,
-        model: MenuModel,
-        callback: RunContextMenuCallback
-    ) -> bool {
-        false
-    }
-
-    fn on_context_menu_command(
-        &mut self,
-        browser: Browser,
-        frame: Frame,
-        params: ContextMenuParams,
-        command_id: MenuCommandId,
-        event_flags: EventFlags
-    ) -> bool {
-        false
-    }
-
-    fn on_context_menu_dismissed(&mut self, browser: Browser, frame: Frame) {}
-
-    fn run_quick_menu(
-        &mut self,
-        browser: Browser,
-        frame: Frame,
-        location: &Point,
-        size: &Size,
-        edit_state_flags: QuickMenuEditStateFlags,
-        callback: RunQuickMenuCallback
-    ) -> bool {
-        false
-    }
-
-    fn on_quick_menu_command(
-        &mut self,
-        browser: Browser,
-        frame: Frame,
-        command_id: MenuCommandId,
-        event_flags: EventFlags
-    ) -> bool {
-        false
-    }
-
-    fn on_quick_menu_dismissed(&mut self, browser: Browser, frame: Frame) {}
 }
 
 /// Life span callbacks.
@@ -103,28 +55,11 @@ impl LifeSpanHandlerCallbacks for MyLifeSpanHandlerCallbacks {
         true
     }
 
-    fn on_before_dev_tools_popup(
-        &mut self,
-        browser: Browser,
-        window_info: &mut WindowInfo,
-        client: &mut Option<Client>,
-        settings: &mut BrowserSettings,
-        extra_info: &mut Option<DictionaryValue>,
-        use_default_window: &mut bool
-    ) {
-    }
-
-    fn on_after_created(&mut self, browser: Browser) {}
-
-    fn do_close(&mut self, browser: Browser) -> bool {
-        false
-    }
-
     fn on_before_close(&mut self, browser: Browser) {
         // If you have more than one browser open, you want to only
         // call this when the number of open browsers reaches zero.
-        unsafe {
-            cef_quit_message_loop();
+        if let Err(e) = quit_message_loop() {
+            error!("Failed to quit the message loop: {}", e);
         }
     }
 }
@@ -137,17 +72,13 @@ impl ClientCallbacks for MyClientCallbacks {
         Some(ContextMenuHandler::new(MyContextMenuHandler {}))
     }
 
-    fn get_keyboard_handler(&mut self) -> Option<KeyboardHandler> {
-        None
+    fn get_display_handler(&mut self) -> Option<DisplayHandler> {
+        Some(DisplayHandler::new(ConsoleLogger::new()))
     }
 
     fn get_life_span_handler(&mut self) -> Option<LifeSpanHandler> {
         Some(LifeSpanHandler::new(MyLifeSpanHandlerCallbacks {}))
     }
-
-    fn get_render_handler(&mut self) -> Option<RenderHandler> {
-        None
-    }
 }
 
 /// Application callbacks.
@@ -172,10 +103,6 @@ impl AppCallbacks for MyAppCallbacks {
             }
         }
     }
-
-    fn get_browser_process_handler(&mut self) -> Option<BrowserProcessHandler> {
-        None
-    }
 }
 
 fn main() {
@@ -219,8 +146,14 @@ fn try_main() -> Result<()> {
 
     // If this is a CEF subprocess, let it run and then
     // emit the proper exit code so CEF can clean up.
-    if let Some(code) = context.is_cef_subprocess() {
-        exit(code);
+    context.exit_if_subprocess();
+
+    // On macOS, CEF requires the shared application to implement
+    // CefAppProtocol, and key equivalents need a main menu.
+    #[cfg(target_os = "macos")]
+    {
+        init_application()?;
+        set_default_main_menu("cef-ui-simple")?;
     }
 
     // Initialize CEF.
@@ -248,7 +181,7 @@ fn try_main() -> Result<()> {
     info!("Running CEF message loop.");
 
     // Run the message loop.
-    context.run_message_loop();
+    context.run_message_loop()?;
 
     info!("Shutting down CEF.");
 
@@ -11,3 +11,44 @@ use crate::windows_x86_64::{HCURSOR, HWND, MSG};
 pub type cef_window_handle_t = HWND;
 pub type cef_cursor_handle_t = HCURSOR;
 pub type cef_event_handle_t = *mut MSG;
+
+// The cef_api_hash.h header is not part of the bindgen
+// input, so we must manually declare this function here.
+extern "C" {
+    /// Returns CEF API hashes for the libcef library. The returned string is owned
+    /// by the library and should not be freed. The |entry| parameter describes
+    /// which hash value will be returned:
+    /// 0 - CEF_API_HASH_PLATFORM
+    /// 1 - CEF_API_HASH_UNIVERSAL
+    /// 2 - CEF_COMMIT_HASH (from cef_version.h)
+    pub fn cef_api_hash(entry: ::std::os::raw::c_int) -> *const ::std::os::raw::c_char;
+}
+
+// The cef_app.h declaration is behind OS_WIN, which is not defined for
+// the bindgen input, so we must manually declare this function here.
+extern "C" {
+    /// Set to true (1) before calling Windows APIs like TrackPopupMenu that enter
+    /// a modal message loop. Set to false (0) after exiting the modal message
+    /// loop.
+    pub fn cef_set_osmodal_loop(osModalLoop: ::std::os::raw::c_int);
+}
+
+// The cef_dump_without_crashing_internal.h header is not part of the
+// bindgen input, so we must manually declare these functions here.
+extern "C" {
+    /// Crash reporting must be enabled to use these functions. See
+    /// cef_crash_reporting_enabled(). This function generates a crash dump
+    /// without crashing the process. Dumps are throttled to one every
+    /// |mseconds_between_dumps| per call site, which is identified by
+    /// |function_name|, |file_name| and |line_number|. Returns true (1) if the
+    /// dump was generated.
+    pub fn cef_dump_without_crashing(
+        mseconds_between_dumps: ::std::os::raw::c_longlong,
+        function_name: *const ::std::os::raw::c_char,
+        file_name: *const ::std::os::raw::c_char,
+        line_number: ::std::os::raw::c_int
+    ) -> ::std::os::raw::c_int;
+
+    /// Same as cef_dump_without_crashing, but without throttling.
+    pub fn cef_dump_without_crashing_unthrottled() -> ::std::os::raw::c_int;
+}
@@ -0,0 +1,175 @@
+#[cfg(feature = "wgpu")]
+use crate::{Rect, Result};
+#[cfg(feature = "wgpu")]
+use anyhow::anyhow;
+use std::ffi::c_void;
+
+/// The shared texture handle passed to RenderHandlerCallbacks::on_accelerated_paint.
+/// With this version of CEF it is an NT handle for a D3D11 Texture2D in
+/// B8G8R8A8 format, and accelerated painting is only supported on Windows.
+///
+/// The texture is only valid until on_accelerated_paint returns. CEF draws the
+/// next frame into it afterwards, so its contents must be copied before
+/// returning. With the wgpu feature, copy_to_texture does this for wgpu
+/// devices on the DX12 backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SharedTextureHandle(*mut c_void);
+
+impl SharedTextureHandle {
+    pub fn new(handle: *mut c_void) -> Self {
+        Self(handle)
+    }
+
+    /// Returns true if CEF did not provide a texture.
+    pub fn is_null(&self) -> bool {
+        self.0.is_null()
+    }
+
+    /// Returns the raw handle.
+    pub fn as_raw(&self) -> *mut c_void {
+        self.0
+    }
+}
+
+#[cfg(feature = "wgpu")]
+impl SharedTextureHandle {
+    /// Copies |dirty_rects| of the shared texture into |target| and waits until
+    /// the GPU has finished the copy, so that CEF can safely reuse the shared
+    /// texture once on_accelerated_paint returns. Call this from within
+    /// on_accelerated_paint. |target| must be a Bgra8Unorm texture with
+    /// COPY_DST usage and the size of the view in physical pixels, and |device|
+    /// must use the DX12 backend.
+    pub fn copy_to_texture(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        target: &wgpu::Texture,
+        dirty_rects: &[Rect]
+    ) -> Result<()> {
+        let size = target.size();
+        let source = self.import(device, size)?;
+        let bounds = Rect::new(0, 0, size.width as i32, size.height as i32);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("cef-ui accelerated paint")
+        });
+
+        for rect in dirty_rects {
+            let rect = rect.intersect(&bounds);
+
+            if rect.is_empty() {
+                continue;
+            }
+
+            let origin = wgpu::Origin3d {
+                x: rect.x as u32,
+                y: rect.y as u32,
+                z: 0
+            };
+
+            encoder.copy_texture_to_texture(
+                wgpu::ImageCopyTexture {
+                    texture: &source,
+                    mip_level: 0,
+                    origin,
+                    aspect: wgpu::TextureAspect::All
+                },
+                wgpu::ImageCopyTexture {
+                    texture: target,
+                    mip_level: 0,
+                    origin,
+                    aspect: wgpu::TextureAspect::All
+                },
+                wgpu::Extent3d {
+                    width:                 rect.width as u32,
+                    height:                rect.height as u32,
+                    depth_or_array_layers: 1
+                }
+            );
+        }
+
+        let index = queue.submit(Some(encoder.finish()));
+
+        // CEF draws the next frame into the shared texture as soon as
+        // on_accelerated_paint returns, so the copy must have completed.
+        device.poll(wgpu::Maintain::WaitForSubmissionIndex(index));
+
+        Ok(())
+    }
+
+    /// Wraps the shared texture as a Bgra8Unorm wgpu texture of |size| with
+    /// COPY_SRC and TEXTURE_BINDING usage. The texture must not be used after
+    /// on_accelerated_paint returns; see copy_to_texture.
+    pub fn import(&self, device: &wgpu::Device, size: wgpu::Extent3d) -> Result<wgpu::Texture> {
+        if self.is_null() {
+            return Err(anyhow!("CEF did not provide a shared texture.").into());
+        }
+
+        let descriptor = wgpu::TextureDescriptor {
+            label: Some("cef-ui shared texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Bgra8Unorm,
+            usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[]
+        };
+
+        import_shared_texture(self.0, device, &descriptor)
+    }
+}
+
+/// Opens the D3D11 shared texture |handle| on the DX12 device behind |device|.
+#[cfg(all(feature = "wgpu", target_os = "windows"))]
+fn import_shared_texture(
+    handle: *mut c_void,
+    device: &wgpu::Device,
+    descriptor: &wgpu::TextureDescriptor
+) -> Result<wgpu::Texture> {
+    use wgpu::hal::{api::Dx12, dx12};
+    use winapi::{shared::winerror::SUCCEEDED, um::d3d12::ID3D12Resource, Interface};
+
+    let resource = unsafe {
+        device.as_hal::<Dx12, _, _>(|device| {
+            let mut resource = d3d12::Resource::null();
+            let result = device?
+                .raw_device()
+                .OpenSharedHandle(handle as _, &ID3D12Resource::uuidof(), resource.mut_void());
+
+            match SUCCEEDED(result) {
+                true => Some(resource),
+                false => None
+            }
+        })
+    };
+
+    let Some(resource) = resource.flatten() else {
+        return Err(anyhow!(
+            "Failed to open the shared texture. The wgpu device must use the DX12 backend."
+        )
+        .into());
+    };
+
+    unsafe {
+        let texture = dx12::Device::texture_from_raw(
+            resource,
+            descriptor.format,
+            descriptor.dimension,
+            descriptor.size,
+            descriptor.mip_level_count,
+            descriptor.sample_count
+        );
+
+        Ok(device.create_texture_from_hal::<Dx12>(texture, descriptor))
+    }
+}
+
+/// CEF only supports accelerated painting on Windows.
+#[cfg(all(feature = "wgpu", not(target_os = "windows")))]
+fn import_shared_texture(
+    _handle: *mut c_void,
+    _device: &wgpu::Device,
+    _descriptor: &wgpu::TextureDescriptor
+) -> Result<wgpu::Texture> {
+    Err(anyhow!("Accelerated painting is only supported on Windows by this version of CEF.").into())
+}
@@ -0,0 +1,444 @@
+use crate::{
+    Browser, EventFlags, KeyCombo, KeyEvent, KeyEventType, KeyboardHandler,
+    KeyboardHandlerCallbacks, NativeEventHandle, Result
+};
+#[cfg(feature = "devtools")]
+use crate::{BrowserSettings, ClientBuilder, WindowInfo};
+use std::sync::Arc;
+use tracing::error;
+
+/// The amount by which ZoomIn and ZoomOut change the zoom level.
+const ZOOM_STEP: f64 = 0.5;
+
+/// The modifiers that take part in matching an accelerator. Lock keys and
+/// mouse buttons held during the key press are ignored.
+const ACCELERATOR_MODIFIERS: EventFlags = EventFlags::ShiftDown
+    .union(EventFlags::ControlDown)
+    .union(EventFlags::AltDown)
+    .union(EventFlags::CommandDown);
+
+type CustomAction = Arc<dyn Fn(&Browser) -> Result<()> + Send + Sync + 'static>;
+
+/// What an accelerator does to the browser that received the key press.
+#[derive(Clone)]
+pub enum AcceleratorAction {
+    /// Reloads the current page.
+    Reload,
+
+    /// Reloads the current page ignoring any cached data.
+    ReloadIgnoreCache,
+
+    /// Stops loading the page.
+    StopLoad,
+
+    /// Navigates backwards.
+    GoBack,
+
+    /// Navigates forwards.
+    GoForward,
+
+    /// Increases the zoom level.
+    ZoomIn,
+
+    /// Decreases the zoom level.
+    ZoomOut,
+
+    /// Resets the zoom level to the default.
+    ZoomReset,
+
+    /// Prints the current page.
+    Print,
+
+    /// Opens DevTools in its own window, or closes it if it is open.
+    #[cfg(feature = "devtools")]
+    ToggleDevTools,
+
+    /// Calls the closure.
+    Custom(CustomAction)
+}
+
+impl AcceleratorAction {
+    /// Runs the action on |browser|.
+    pub fn run(&self, browser: &Browser) -> Result<()> {
+        match self {
+            Self::Reload => browser.reload(),
+            Self::ReloadIgnoreCache => browser.reload_ignore_cache(),
+            Self::StopLoad => browser.stop_load(),
+            Self::GoBack => browser.go_back(),
+            Self::GoForward => browser.go_forward(),
+            Self::ZoomIn => zoom_by(browser, ZOOM_STEP),
+            Self::ZoomOut => zoom_by(browser, -ZOOM_STEP),
+            Self::ZoomReset => browser
+                .get_host()?
+                .set_zoom_level(0.0),
+            Self::Print => browser.get_host()?.print(),
+            #[cfg(feature = "devtools")]
+            Self::ToggleDevTools => toggle_dev_tools(browser),
+            Self::Custom(f) => f(browser)
+        }
+    }
+}
+
+/// A key combination bound to an action.
+#[derive(Clone)]
+struct Binding {
+    combo:  KeyCombo,
+    action: AcceleratorAction
+}
+
+impl Binding {
+    /// Returns true if the binding is triggered by |combo|.
+    fn matches(&self, combo: &KeyCombo) -> bool {
+        i32::from(self.combo.key_code) == i32::from(combo.key_code)
+            && (self.combo.modifiers & ACCELERATOR_MODIFIERS).bits()
+                == (combo.modifiers & ACCELERATOR_MODIFIERS).bits()
+    }
+}
+
+/// Maps key combinations to actions, so that shortcuts work the same way in
+/// every browser without parsing raw key events. Combinations use the syntax
+/// of KeyCombo, e.g. "CmdOrCtrl+R", which is Cmd+R on macOS and Ctrl+R
+/// elsewhere. Binding a combination again replaces its action.
+///
+/// Accelerators are matched in KeyboardHandlerCallbacks::on_pre_key_event, so
+/// they take precedence over the page. Return keyboard_handler from
+/// ClientCallbacks::get_keyboard_handler, or wrap an existing handler.
+#[derive(Clone, Default)]
+pub struct AcceleratorMap {
+    bindings: Vec<Binding>
+}
+
+impl AcceleratorMap {
+    /// An empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A map with the usual browser shortcuts of the current platform for
+    /// reloading, navigating, zooming, printing and DevTools.
+    pub fn with_defaults() -> Self {
+        let modifier = EventFlags::shortcut_key();
+        let mut map = Self::new();
+        let mut bind = |modifiers: EventFlags, key: &str, action: AcceleratorAction| {
+            // The keys are known, so they always parse.
+            if let Ok(combo) = key.parse::<KeyCombo>() {
+                map.bindings.push(Binding {
+                    combo: KeyCombo::new(combo.key_code, modifiers),
+                    action
+                });
+            }
+        };
+
+        bind(modifier, "R", AcceleratorAction::Reload);
+        bind(EventFlags::empty(), "F5", AcceleratorAction::Reload);
+        bind(
+            modifier | EventFlags::ShiftDown,
+            "R",
+            AcceleratorAction::ReloadIgnoreCache
+        );
+        bind(
+            EventFlags::ShiftDown,
+            "F5",
+            AcceleratorAction::ReloadIgnoreCache
+        );
+        bind(modifier, "=", AcceleratorAction::ZoomIn);
+        bind(
+            modifier | EventFlags::ShiftDown,
+            "=",
+            AcceleratorAction::ZoomIn
+        );
+        bind(modifier, "-", AcceleratorAction::ZoomOut);
+        bind(modifier, "0", AcceleratorAction::ZoomReset);
+        bind(modifier, "P", AcceleratorAction::Print);
+
+        match cfg!(target_os = "macos") {
+            true => {
+                bind(EventFlags::CommandDown, "[", AcceleratorAction::GoBack);
+                bind(EventFlags::CommandDown, "]", AcceleratorAction::GoForward);
+                bind(EventFlags::CommandDown, "Left", AcceleratorAction::GoBack);
+                bind(
+                    EventFlags::CommandDown,
+                    "Right",
+                    AcceleratorAction::GoForward
+                );
+            },
+            false => {
+                bind(EventFlags::AltDown, "Left", AcceleratorAction::GoBack);
+                bind(EventFlags::AltDown, "Right", AcceleratorAction::GoForward);
+            }
+        }
+
+        #[cfg(feature = "devtools")]
+        {
+            let inspect = match cfg!(target_os = "macos") {
+                true => EventFlags::CommandDown | EventFlags::AltDown,
+                false => EventFlags::ControlDown | EventFlags::ShiftDown
+            };
+
+            bind(
+                EventFlags::empty(),
+                "F12",
+                AcceleratorAction::ToggleDevTools
+            );
+            bind(inspect, "I", AcceleratorAction::ToggleDevTools);
+        }
+
+        map
+    }
+
+    /// Binds |combo|, e.g. "CmdOrCtrl+Shift+R", to |action|.
+    pub fn bind(self, combo: &str, action: AcceleratorAction) -> Result<Self> {
+        Ok(self.bind_combo(combo.parse()?, action))
+    }
+
+    /// Binds |combo| to |action|.
+    pub fn bind_combo(mut self, combo: KeyCombo, action: AcceleratorAction) -> Self {
+        self.bindings
+            .retain(|binding| !binding.matches(&combo));
+        self.bindings
+            .push(Binding { combo, action });
+        self
+    }
+
+    /// Binds |combo| to |f|, which is called on the UI thread.
+    pub fn on(
+        self,
+        combo: &str,
+        f: impl Fn(&Browser) -> Result<()> + Send + Sync + 'static
+    ) -> Result<Self> {
+        self.bind(combo, AcceleratorAction::Custom(Arc::new(f)))
+    }
+
+    /// Removes the binding of |combo|, so that the key press reaches the page.
+    pub fn unbind(mut self, combo: &str) -> Result<Self> {
+        let combo: KeyCombo = combo.parse()?;
+
+        self.bindings
+            .retain(|binding| !binding.matches(&combo));
+
+        Ok(self)
+    }
+
+    /// Returns the action bound to the key press |event|, if any. Only key down
+    /// events trigger accelerators.
+    pub fn action_for(&self, event: &KeyEvent) -> Option<&AcceleratorAction> {
+        match event.event_type {
+            KeyEventType::RawKeyDown | KeyEventType::KeyDown => {},
+            _ => return None
+        }
+
+        let combo = KeyCombo::new(event.windows_key_code, event.modifiers);
+
+        self.bindings
+            .iter()
+            .find(|binding| binding.matches(&combo))
+            .map(|binding| &binding.action)
+    }
+
+    /// Returns a keyboard handler that runs the accelerators.
+    pub fn keyboard_handler(self) -> KeyboardHandler {
+        self.wrap(NoopHandler)
+    }
+
+    /// Returns a keyboard handler that runs the accelerators and forwards every
+    /// other key event to |delegate|.
+    pub fn wrap<C: KeyboardHandlerCallbacks>(self, delegate: C) -> KeyboardHandler {
+        KeyboardHandler::new(AcceleratorHandler {
+            map: self,
+            delegate
+        })
+    }
+}
+
+/// Runs accelerators before forwarding key events to a delegate.
+struct AcceleratorHandler<C: KeyboardHandlerCallbacks> {
+    map:      AcceleratorMap,
+    delegate: C
+}
+
+impl<C: KeyboardHandlerCallbacks> KeyboardHandlerCallbacks for AcceleratorHandler<C> {
+    fn on_pre_key_event(
+        &mut self,
+        browser: Browser,
+        event: KeyEvent,
+        os_event: Option<NativeEventHandle>,
+        is_keyboard_shortcut: &mut bool
+    ) -> bool {
+        if let Some(action) = self.map.action_for(&event) {
+            if let Err(e) = action.run(&browser) {
+                error!("Failed to run accelerator: {}", e);
+            }
+
+            return true;
+        }
+
+        self.delegate
+            .on_pre_key_event(browser, event, os_event, is_keyboard_shortcut)
+    }
+
+    fn on_key_event(
+        &mut self,
+        browser: Browser,
+        event: KeyEvent,
+        os_event: Option<NativeEventHandle>
+    ) -> bool {
+        self.delegate
+            .on_key_event(browser, event, os_event)
+    }
+}
+
+/// A keyboard handler that leaves every event to the page.
+struct NoopHandler;
+
+impl KeyboardHandlerCallbacks for NoopHandler {}
+
+/// Changes the zoom level of |browser| by |delta|.
+fn zoom_by(browser: &Browser, delta: f64) -> Result<()> {
+    let host = browser.get_host()?;
+
+    host.set_zoom_level(host.get_zoom_level()? + delta)
+}
+
+/// Opens DevTools for |browser| in its own window, or closes it.
+#[cfg(feature = "devtools")]
+fn toggle_dev_tools(browser: &Browser) -> Result<()> {
+    let host = browser.get_host()?;
+
+    match host.has_dev_tools()? {
+        true => host.close_dev_tools(),
+        false => host.show_dev_tools(
+            &WindowInfo::new(),
+            ClientBuilder::new().build(),
+            &BrowserSettings::new(),
+            None
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WindowsKeyCode;
+
+    fn press(key_code: WindowsKeyCode, modifiers: EventFlags) -> KeyEvent {
+        KeyEvent::key_down(key_code).modifiers(modifiers)
+    }
+
+    #[test]
+    fn bind_and_match() {
+        let map = AcceleratorMap::new()
+            .bind("CmdOrCtrl+Shift+R", AcceleratorAction::ReloadIgnoreCache)
+            .unwrap();
+        let modifiers = EventFlags::shortcut_key() | EventFlags::ShiftDown;
+
+        assert!(matches!(
+            map.action_for(&press(WindowsKeyCode::R, modifiers)),
+            Some(AcceleratorAction::ReloadIgnoreCache)
+        ));
+        assert!(
+            map.action_for(&press(WindowsKeyCode::R, EventFlags::shortcut_key()))
+                .is_none()
+        );
+        assert!(
+            map.action_for(&press(WindowsKeyCode::T, modifiers))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn match_ignores_lock_keys() {
+        let map = AcceleratorMap::new()
+            .bind("Alt+Left", AcceleratorAction::GoBack)
+            .unwrap();
+        let modifiers = EventFlags::AltDown | EventFlags::CapsLockOn | EventFlags::NumLockOn;
+
+        assert!(matches!(
+            map.action_for(&press(WindowsKeyCode::Left, modifiers)),
+            Some(AcceleratorAction::GoBack)
+        ));
+    }
+
+    #[test]
+    fn match_key_down_only() {
+        let map = AcceleratorMap::new()
+            .bind("F5", AcceleratorAction::Reload)
+            .unwrap();
+
+        assert!(
+            map.action_for(&KeyEvent::key_up(WindowsKeyCode::F5))
+                .is_none()
+        );
+        assert!(
+            map.action_for(&KeyEvent::char('a' as u16))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn bind_replaces() {
+        let map = AcceleratorMap::new()
+            .bind("Ctrl++", AcceleratorAction::ZoomIn)
+            .unwrap()
+            .bind("Control++", AcceleratorAction::ZoomOut)
+            .unwrap();
+
+        assert_eq!(map.bindings.len(), 1);
+        assert!(matches!(
+            map.action_for(&press(WindowsKeyCode::OEMPlus, EventFlags::ControlDown)),
+            Some(AcceleratorAction::ZoomOut)
+        ));
+    }
+
+    #[test]
+    fn unbind() {
+        let map = AcceleratorMap::with_defaults()
+            .unbind("F5")
+            .unwrap();
+
+        assert!(
+            map.action_for(&press(WindowsKeyCode::F5, EventFlags::empty()))
+                .is_none()
+        );
+        assert!(matches!(
+            map.action_for(&press(WindowsKeyCode::R, EventFlags::shortcut_key())),
+            Some(AcceleratorAction::Reload)
+        ));
+    }
+
+    #[test]
+    fn bind_invalid() {
+        assert!(
+            AcceleratorMap::new()
+                .bind("Hyper+R", AcceleratorAction::Reload)
+                .is_err()
+        );
+        assert!(
+            AcceleratorMap::new()
+                .bind("Ctrl+F25", AcceleratorAction::Reload)
+                .is_err()
+        );
+        assert!(
+            AcceleratorMap::new()
+                .unbind("Ctrl+")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn defaults() {
+        let map = AcceleratorMap::with_defaults();
+
+        assert!(matches!(
+            map.action_for(&press(WindowsKeyCode::F5, EventFlags::empty())),
+            Some(AcceleratorAction::Reload)
+        ));
+        assert!(matches!(
+            map.action_for(&press(WindowsKeyCode::F5, EventFlags::ShiftDown)),
+            Some(AcceleratorAction::ReloadIgnoreCache)
+        ));
+        assert!(matches!(
+            map.action_for(&press(WindowsKeyCode::OEMMinus, EventFlags::shortcut_key())),
+            Some(AcceleratorAction::ZoomOut)
+        ));
+    }
+}
@@ -0,0 +1,502 @@
+use crate::{
+    AccessibilityHandler, AccessibilityHandlerCallbacks, DictionaryValue, ListValue, Rect, Result,
+    Value, ValueType
+};
+use anyhow::anyhow;
+use std::collections::HashMap;
+use tracing::error;
+
+/// Accessibility tree changes sent by the renderer process, as delivered to
+/// AccessibilityHandlerCallbacks::on_accessibility_tree_change. Node ids are
+/// only unique within a tree.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccessibilityTreeChange {
+    /// The id of the tree the updates and events apply to, if known.
+    pub tree_id: Option<String>,
+
+    /// Updates to apply to the tree, in order.
+    pub updates: Vec<AccessibilityTreeUpdate>,
+
+    /// Events that occurred, e.g. focus changes or value changes.
+    pub events: Vec<AccessibilityEvent>
+}
+
+impl AccessibilityTreeChange {
+    /// Parses the value passed to on_accessibility_tree_change.
+    pub fn from_value(value: &Value) -> Result<Self> {
+        let value = value
+            .get_dictionary()?
+            .ok_or_else(|| anyhow!("Accessibility tree change is not a dictionary."))?;
+
+        Ok(Self {
+            tree_id: get_optional_string(&value, "ax_tree_id")?,
+            updates: get_dictionaries(&value, "updates")?
+                .iter()
+                .map(AccessibilityTreeUpdate::from_dictionary)
+                .collect::<Result<_>>()?,
+            events:  get_dictionaries(&value, "events")?
+                .iter()
+                .map(AccessibilityEvent::from_dictionary)
+                .collect::<Result<_>>()?
+        })
+    }
+}
+
+/// An incremental update of an accessibility tree. |nodes| replace the nodes
+/// with the same ids; a node whose children change is sent in full.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccessibilityTreeUpdate {
+    /// Tree wide data, if it changed.
+    pub tree_data: Option<AccessibilityTreeData>,
+
+    /// The id of a node whose descendants must be removed before the update is
+    /// applied, if any.
+    pub node_id_to_clear: Option<i32>,
+
+    /// The id of the root node of the tree, if set.
+    pub root_id: Option<i32>,
+
+    /// New or changed nodes.
+    pub nodes: Vec<AccessibilityNode>
+}
+
+impl AccessibilityTreeUpdate {
+    fn from_dictionary(value: &DictionaryValue) -> Result<Self> {
+        let tree_data = match value.get_type("has_tree_data")? == ValueType::Bool
+            && value.get_bool("has_tree_data")?
+        {
+            true => value
+                .get_dictionary("tree_data")?
+                .map(|tree_data| AccessibilityTreeData::from_dictionary(&tree_data))
+                .transpose()?,
+            false => None
+        };
+
+        Ok(Self {
+            tree_data,
+            node_id_to_clear: get_optional_id(value, "node_id_to_clear")?,
+            root_id: get_optional_id(value, "root_id")?,
+            nodes: get_dictionaries(value, "nodes")?
+                .iter()
+                .map(AccessibilityNode::from_dictionary)
+                .collect::<Result<_>>()?
+        })
+    }
+}
+
+/// Data that applies to a whole accessibility tree, i.e. to a document.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccessibilityTreeData {
+    pub tree_id:              Option<String>,
+    pub parent_tree_id:       Option<String>,
+    pub focused_tree_id:      Option<String>,
+    pub title:                Option<String>,
+    pub url:                  Option<String>,
+    pub doctype:              Option<String>,
+    pub mimetype:             Option<String>,
+    pub loaded:               bool,
+    pub loading_progress:     f64,
+    pub focus_id:             Option<i32>,
+    pub sel_anchor_object_id: Option<i32>,
+    pub sel_anchor_offset:    Option<i32>,
+    pub sel_focus_object_id:  Option<i32>,
+    pub sel_focus_offset:     Option<i32>
+}
+
+impl AccessibilityTreeData {
+    fn from_dictionary(value: &DictionaryValue) -> Result<Self> {
+        Ok(Self {
+            tree_id:              get_optional_string(value, "tree_id")?,
+            parent_tree_id:       get_optional_string(value, "parent_tree_id")?,
+            focused_tree_id:      get_optional_string(value, "focused_tree_id")?,
+            title:                get_optional_string(value, "title")?,
+            url:                  get_optional_string(value, "url")?,
+            doctype:              get_optional_string(value, "doctype")?,
+            mimetype:             get_optional_string(value, "mimetype")?,
+            loaded:               get_optional_bool(value, "loaded")?.unwrap_or(false),
+            loading_progress:     get_optional_number(value, "loading_progress")?.unwrap_or(0.0),
+            focus_id:             get_optional_id(value, "focus_id")?,
+            sel_anchor_object_id: get_optional_id(value, "sel_anchor_object_id")?,
+            sel_anchor_offset:    get_optional_int(value, "sel_anchor_offset")?,
+            sel_focus_object_id:  get_optional_id(value, "sel_focus_object_id")?,
+            sel_focus_offset:     get_optional_int(value, "sel_focus_offset")?
+        })
+    }
+}
+
+/// A node of an accessibility tree.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccessibilityNode {
+    /// The id of the node, unique within its tree.
+    pub id: i32,
+
+    /// The role of the node in Chromium's naming, e.g. "button" or
+    /// "staticText".
+    pub role: String,
+
+    /// The states of the node, e.g. "focusable" or "invisible".
+    pub states: Vec<String>,
+
+    /// The location of the node.
+    pub bounds: AccessibilityBounds,
+
+    /// The ids of the children of the node, in order.
+    pub child_ids: Vec<i32>,
+
+    /// The actions the node supports, e.g. "focus" or "scrollToMakeVisible".
+    pub actions: Vec<String>,
+
+    /// All other attributes by their Chromium name, e.g. "name", "value" or
+    /// "description".
+    pub attributes: HashMap<String, AccessibilityAttribute>
+}
+
+impl AccessibilityNode {
+    fn from_dictionary(value: &DictionaryValue) -> Result<Self> {
+        Ok(Self {
+            id:         get_optional_int(value, "id")?
+                .ok_or_else(|| anyhow!("Accessibility node has no id."))?,
+            role:       get_optional_string(value, "role")?.unwrap_or_default(),
+            states:     get_string_list(value, "state")?,
+            bounds:     AccessibilityBounds::from_dictionary(value)?,
+            child_ids:  get_int_list(value, "child_ids")?,
+            actions:    get_string_list(value, "actions")?,
+            attributes: match value.get_dictionary("attributes")? {
+                Some(attributes) => parse_attributes(&attributes)?,
+                None => HashMap::new()
+            }
+        })
+    }
+
+    /// Returns the accessible name of the node, e.g. the label of a button.
+    pub fn name(&self) -> Option<&str> {
+        self.string_attribute("name")
+    }
+
+    /// Returns the value of the node, e.g. the text of a text field.
+    pub fn value(&self) -> Option<&str> {
+        self.string_attribute("value")
+    }
+
+    /// Returns the description of the node, e.g. a tooltip.
+    pub fn description(&self) -> Option<&str> {
+        self.string_attribute("description")
+    }
+
+    /// Returns true if the node has |state|.
+    pub fn has_state(&self, state: &str) -> bool {
+        self.states
+            .iter()
+            .any(|s| s == state)
+    }
+
+    /// Returns the string attribute |name|, if set.
+    pub fn string_attribute(&self, name: &str) -> Option<&str> {
+        match self.attributes.get(name) {
+            Some(AccessibilityAttribute::String(value)) => Some(value),
+            _ => None
+        }
+    }
+}
+
+/// The location of an accessibility node. The rectangle is relative to the
+/// node with |offset_container_id| or, if there is none, to the root of the
+/// tree, which is at the origin of the view.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccessibilityBounds {
+    /// The id of the node the rectangle is relative to, if any.
+    pub offset_container_id: Option<i32>,
+
+    pub x:      f64,
+    pub y:      f64,
+    pub width:  f64,
+    pub height: f64,
+
+    /// Chromium's string form of a transform applied to the rectangle, if it is
+    /// not the identity.
+    pub transform: Option<String>
+}
+
+impl AccessibilityBounds {
+    /// Parses the offset container, transform and rectangle, which CEF stores
+    /// under "location" for nodes and under "bounds" for location changes.
+    fn from_dictionary(value: &DictionaryValue) -> Result<Self> {
+        let rect = match value.get_dictionary("location")? {
+            Some(rect) => Some(rect),
+            None => value.get_dictionary("bounds")?
+        };
+        let mut bounds = Self {
+            offset_container_id: get_optional_id(value, "offset_container_id")?,
+            transform: get_optional_string(value, "transform")?,
+            ..Self::default()
+        };
+
+        if let Some(rect) = rect {
+            bounds.x = get_optional_number(&rect, "x")?.unwrap_or(0.0);
+            bounds.y = get_optional_number(&rect, "y")?.unwrap_or(0.0);
+            bounds.width = get_optional_number(&rect, "width")?.unwrap_or(0.0);
+            bounds.height = get_optional_number(&rect, "height")?.unwrap_or(0.0);
+        }
+
+        Ok(bounds)
+    }
+
+    /// Returns the rectangle rounded to whole pixels.
+    pub fn to_rect(&self) -> Rect {
+        Rect::new(
+            self.x.round() as i32,
+            self.y.round() as i32,
+            self.width.round() as i32,
+            self.height.round() as i32
+        )
+    }
+}
+
+/// The value of an accessibility node attribute.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AccessibilityAttribute {
+    Bool(bool),
+    Int(i32),
+    Double(f64),
+    String(String),
+    IntList(Vec<i32>),
+    StringList(Vec<String>)
+}
+
+/// An accessibility event, e.g. a focus change.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccessibilityEvent {
+    /// The id of the node the event is for.
+    pub id: i32,
+
+    /// The event type in Chromium's naming, e.g. "focus" or "valueChanged".
+    pub event_type: String,
+
+    /// What caused the event, e.g. "user" or "action", if known.
+    pub event_from: Option<String>,
+
+    /// The id of the action request that caused the event, if any.
+    pub action_request_id: Option<i32>
+}
+
+impl AccessibilityEvent {
+    fn from_dictionary(value: &DictionaryValue) -> Result<Self> {
+        Ok(Self {
+            id:                get_optional_int(value, "id")?.unwrap_or(-1),
+            event_type:        get_optional_string(value, "event_type")?.unwrap_or_default(),
+            event_from:        get_optional_string(value, "event_from")?,
+            action_request_id: get_optional_id(value, "action_request_id")?
+        })
+    }
+}
+
+/// A node that moved, as delivered to
+/// AccessibilityHandlerCallbacks::on_accessibility_location_change.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccessibilityLocationChange {
+    /// The id of the tree that contains the node, if known.
+    pub tree_id: Option<String>,
+
+    /// The id of the node.
+    pub id: i32,
+
+    /// The new location of the node.
+    pub bounds: AccessibilityBounds
+}
+
+impl AccessibilityLocationChange {
+    /// Parses the value passed to on_accessibility_location_change.
+    pub fn from_value(value: &Value) -> Result<Vec<Self>> {
+        let Some(list) = value.get_list()? else {
+            return Err(anyhow!("Accessibility location change is not a list.").into());
+        };
+
+        (0..list.len()?)
+            .filter_map(|index| list.get_dictionary(index).transpose())
+            .map(|change| {
+                let change = change?;
+                let bounds = match change.get_dictionary("new_location")? {
+                    Some(location) => AccessibilityBounds::from_dictionary(&location)?,
+                    None => AccessibilityBounds::default()
+                };
+
+                Ok(Self {
+                    tree_id: get_optional_string(&change, "ax_tree_id")?,
+                    id: get_optional_int(&change, "id")?.unwrap_or(-1),
+                    bounds
+                })
+            })
+            .collect()
+    }
+}
+
+/// Receives parsed accessibility changes. See AccessibilityHandler::new_typed.
+pub trait AccessibilityTreeCallbacks: Send + Sync + 'static {
+    /// Called with the accessibility tree changes sent by the renderer process.
+    fn on_tree_change(&mut self, _change: AccessibilityTreeChange) {}
+
+    /// Called with the nodes whose location changed.
+    fn on_location_change(&mut self, _changes: Vec<AccessibilityLocationChange>) {}
+}
+
+impl AccessibilityHandler {
+    /// Creates an accessibility handler that parses the values sent by CEF
+    /// before passing them to |delegate|. Values that fail to parse are logged
+    /// and dropped.
+    pub fn new_typed<C: AccessibilityTreeCallbacks>(delegate: C) -> Self {
+        Self::new(TypedAccessibilityHandler(delegate))
+    }
+}
+
+/// Parses CEF accessibility values for AccessibilityTreeCallbacks.
+struct TypedAccessibilityHandler<C: AccessibilityTreeCallbacks>(C);
+
+impl<C: AccessibilityTreeCallbacks> AccessibilityHandlerCallbacks for TypedAccessibilityHandler<C> {
+    fn on_accessibility_tree_change(&mut self, value: Value) {
+        match AccessibilityTreeChange::from_value(&value) {
+            Ok(change) => self.0.on_tree_change(change),
+            Err(e) => error!("Failed to parse accessibility tree change: {}", e)
+        }
+    }
+
+    fn on_accessibility_location_change(&mut self, value: Value) {
+        match AccessibilityLocationChange::from_value(&value) {
+            Ok(changes) => self.0.on_location_change(changes),
+            Err(e) => error!("Failed to parse accessibility location change: {}", e)
+        }
+    }
+}
+
+/// Returns the string at |key|, if any.
+fn get_optional_string(value: &DictionaryValue, key: &str) -> Result<Option<String>> {
+    match value.get_type(key)? {
+        ValueType::String => value.get_string(key),
+        _ => Ok(None)
+    }
+}
+
+/// Returns the bool at |key|, if any.
+fn get_optional_bool(value: &DictionaryValue, key: &str) -> Result<Option<bool>> {
+    match value.get_type(key)? {
+        ValueType::Bool => Ok(Some(value.get_bool(key)?)),
+        _ => Ok(None)
+    }
+}
+
+/// Returns the int at |key|, if any.
+fn get_optional_int(value: &DictionaryValue, key: &str) -> Result<Option<i32>> {
+    match value.get_type(key)? {
+        ValueType::Int => Ok(Some(value.get_int(key)?)),
+        _ => Ok(None)
+    }
+}
+
+/// Returns the node id at |key|, if any. Chromium uses ids of zero or less for
+/// no node.
+fn get_optional_id(value: &DictionaryValue, key: &str) -> Result<Option<i32>> {
+    Ok(get_optional_int(value, key)?.filter(|id| *id > 0))
+}
+
+/// Returns the int or double at |key| as a double, if any.
+fn get_optional_number(value: &DictionaryValue, key: &str) -> Result<Option<f64>> {
+    match value.get_type(key)? {
+        ValueType::Int => Ok(Some(value.get_int(key)? as f64)),
+        ValueType::Double => Ok(Some(value.get_double(key)?)),
+        _ => Ok(None)
+    }
+}
+
+/// Returns the dictionaries in the list at |key|.
+fn get_dictionaries(value: &DictionaryValue, key: &str) -> Result<Vec<DictionaryValue>> {
+    let Some(list) = value.get_list(key)? else {
+        return Ok(Vec::new());
+    };
+
+    let mut dictionaries = Vec::new();
+
+    for index in 0..list.len()? {
+        if let Some(dictionary) = list.get_dictionary(index)? {
+            dictionaries.push(dictionary);
+        }
+    }
+
+    Ok(dictionaries)
+}
+
+/// Returns the ints in the list at |key|.
+fn get_int_list(value: &DictionaryValue, key: &str) -> Result<Vec<i32>> {
+    match value.get_list(key)? {
+        Some(list) => list_to_ints(&list),
+        None => Ok(Vec::new())
+    }
+}
+
+/// Returns the strings in the list at |key|.
+fn get_string_list(value: &DictionaryValue, key: &str) -> Result<Vec<String>> {
+    match value.get_list(key)? {
+        Some(list) => list_to_strings(&list),
+        None => Ok(Vec::new())
+    }
+}
+
+/// Returns the ints in |list|, skipping other values.
+fn list_to_ints(list: &ListValue) -> Result<Vec<i32>> {
+    let mut ints = Vec::new();
+
+    for index in 0..list.len()? {
+        if list.get_type(index)? == ValueType::Int {
+            ints.push(list.get_int(index)?);
+        }
+    }
+
+    Ok(ints)
+}
+
+/// Returns the strings in |list|, skipping other values.
+fn list_to_strings(list: &ListValue) -> Result<Vec<String>> {
+    let mut strings = Vec::new();
+
+    for index in 0..list.len()? {
+        if list.get_type(index)? == ValueType::String {
+            strings.push(
+                list.get_string(index)?
+                    .unwrap_or_default()
+            );
+        }
+    }
+
+    Ok(strings)
+}
+
+/// Parses the attributes of a node. Lists are typed by their first element.
+fn parse_attributes(value: &DictionaryValue) -> Result<HashMap<String, AccessibilityAttribute>> {
+    let mut attributes = HashMap::new();
+
+    for key in value.get_keys()? {
+        let attribute = match value.get_type(&key)? {
+            ValueType::Bool => AccessibilityAttribute::Bool(value.get_bool(&key)?),
+            ValueType::Int => AccessibilityAttribute::Int(value.get_int(&key)?),
+            ValueType::Double => AccessibilityAttribute::Double(value.get_double(&key)?),
+            ValueType::String => AccessibilityAttribute::String(
+                value
+                    .get_string(&key)?
+                    .unwrap_or_default()
+            ),
+            ValueType::List => {
+                let Some(list) = value.get_list(&key)? else {
+                    continue;
+                };
+
+                match list.len()? > 0 && list.get_type(0)? == ValueType::String {
+                    true => AccessibilityAttribute::StringList(list_to_strings(&list)?),
+                    false => AccessibilityAttribute::IntList(list_to_ints(&list)?)
+                }
+            },
+            _ => continue
+        };
+
+        attributes.insert(key, attribute);
+    }
+
+    Ok(attributes)
+}
@@ -1,4 +1,4 @@
-use crate::{ref_counted_ptr, RefCountedPtr, Value, Wrappable, Wrapped};
+use crate::{ref_counted_ptr, unwind::catch_unwind_or, RefCountedPtr, Value, Wrappable, Wrapped};
 use cef_ui_sys::{cef_accessibility_handler_t, cef_value_t};
 use std::mem::zeroed;
 
@@ -8,11 +8,11 @@ use std::mem::zeroed;
 pub trait AccessibilityHandlerCallbacks: Send + Sync + 'static {
     /// Called after renderer process sends accessibility tree changes to the
     /// browser process.
-    fn on_accessibility_tree_change(&mut self, value: Value);
+    fn on_accessibility_tree_change(&mut self, _value: Value) {}
 
     /// Called after renderer process sends accessibility location changes to the
     /// browser process.
-    fn on_accessibility_location_change(&mut self, value: Value);
+    fn on_accessibility_location_change(&mut self, _value: Value) {}
 }
 
 // Implement this structure to receive accessibility notification when
@@ -27,11 +27,11 @@ impl AccessibilityHandler {
 }
 
 /// Translates CEF -> Rust callbacks.
-struct AccessibilityHandlerWrapper(Box<dyn AccessibilityHandlerCallbacks>);
+struct AccessibilityHandlerWrapper<C: AccessibilityHandlerCallbacks>(C);
 
-impl AccessibilityHandlerWrapper {
-    pub fn new<C: AccessibilityHandlerCallbacks>(delegate: C) -> Self {
-        Self(Box::new(delegate))
+impl<C: AccessibilityHandlerCallbacks> AccessibilityHandlerWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
     }
 
     /// Called after renderer process sends accessibility tree changes to the
@@ -40,11 +40,17 @@ impl AccessibilityHandlerWrapper {
         this: *mut cef_accessibility_handler_t,
         value: *mut cef_value_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let value = Value::from_ptr_unchecked(value);
+        catch_unwind_or(
+            "AccessibilityHandlerWrapper::c_on_accessibility_tree_change",
+            (),
+            || {
+                let this: &mut Self = Wrapped::wrappable(this);
+                let value = Value::from_ptr_unchecked(value);
 
-        this.0
-            .on_accessibility_tree_change(value);
+                this.0
+                    .on_accessibility_tree_change(value);
+            }
+        )
     }
 
     /// Called after renderer process sends accessibility location changes to the
@@ -53,15 +59,21 @@ impl AccessibilityHandlerWrapper {
         this: *mut cef_accessibility_handler_t,
         value: *mut cef_value_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let value = Value::from_ptr_unchecked(value);
+        catch_unwind_or(
+            "AccessibilityHandlerWrapper::c_on_accessibility_location_change",
+            (),
+            || {
+                let this: &mut Self = Wrapped::wrappable(this);
+                let value = Value::from_ptr_unchecked(value);
 
-        this.0
-            .on_accessibility_location_change(value);
+                this.0
+                    .on_accessibility_location_change(value);
+            }
+        )
     }
 }
 
-impl Wrappable for AccessibilityHandlerWrapper {
+impl<C: AccessibilityHandlerCallbacks> Wrappable for AccessibilityHandlerWrapper<C> {
     type Cef = cef_accessibility_handler_t;
 
     /// Converts this to a smart pointer.
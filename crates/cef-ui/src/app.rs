@@ -1,6 +1,7 @@
 use crate::{
-    ref_counted_ptr, BrowserProcessHandler, CefString, CommandLine, RefCountedPtr, Wrappable,
-    Wrapped
+    callbacks_builder, ref_counted_ptr, unwind::catch_unwind_or, BrowserProcessHandler, CefString,
+    CommandLine, RefCountedPtr, RenderProcessHandler, RenderProcessHandlerCallbacks,
+    ResourceBundleHandler, SchemeRegistrar, Wrappable, Wrapped
 };
 use cef_ui_sys::{
     cef_app_t, cef_browser_process_handler_t, cef_command_line_t, cef_render_process_handler_t,
@@ -8,6 +9,11 @@ use cef_ui_sys::{
 };
 use std::{mem::zeroed, ptr::null_mut};
 
+/// The render process handler used when the app does not provide one.
+struct DefaultRenderProcessHandler;
+
+impl RenderProcessHandlerCallbacks for DefaultRenderProcessHandler {}
+
 /// Implement this structure to provide handler implementations. Methods will be
 /// called by the process and/or thread indicated.
 pub trait AppCallbacks: Send + Sync + 'static {
@@ -23,34 +29,37 @@ pub trait AppCallbacks: Send + Sync + 'static {
     /// behavior including crashes.
     fn on_before_command_line_processing(
         &mut self,
-        process_type: Option<&str>,
-        command_line: Option<CommandLine>
-    );
-
-    // TODO: Fix this!
+        _process_type: Option<&str>,
+        _command_line: Option<CommandLine>
+    ) {
+    }
 
-    // /// Provides an opportunity to register custom schemes. Do not keep a
-    // /// reference to the |registrar| object. This function is called on the main
-    // /// thread for each process and the registered schemes should be the same
-    // /// across all processes.
-    // fn on_register_custom_schemes(&mut self, registrar: SchemeRegistrar);
+    /// Provides an opportunity to register custom schemes. Do not keep a
+    /// reference to the |registrar| object. This function is called on the main
+    /// thread for each process and the registered schemes should be the same
+    /// across all processes.
+    fn on_register_custom_schemes(&mut self, _registrar: &SchemeRegistrar) {}
 
-    // /// Return the handler for resource bundle events. If
-    // /// cef_settings_t.pack_loading_disabled is true (1) a handler must be
-    // /// returned. If no handler is returned resources will be loaded from pack
-    // /// files. This function is called by the browser and render processes on
-    // /// multiple threads.
-    // fn get_resource_bundle_handler(&mut self) -> Option<ResourceBundleHandler>;
+    /// Return the handler for resource bundle events. If
+    /// cef_settings_t.pack_loading_disabled is true (1) a handler must be
+    /// returned. If no handler is returned resources will be loaded from pack
+    /// files. This function is called by the browser and render processes on
+    /// multiple threads.
+    fn get_resource_bundle_handler(&mut self) -> Option<ResourceBundleHandler> {
+        None
+    }
 
     /// Return the handler for functionality specific to the browser process. This
     /// function is called on multiple threads in the browser process.
-    fn get_browser_process_handler(&mut self) -> Option<BrowserProcessHandler>;
-
-    // TODO: Fix this!
+    fn get_browser_process_handler(&mut self) -> Option<BrowserProcessHandler> {
+        None
+    }
 
-    // /// Return the handler for functionality specific to the render process. This
-    // /// function is called on the render process main thread.
-    // fn get_render_process_handler(&mut self) -> Option<RenderProcessHandler>;
+    /// Return the handler for functionality specific to the render process. This
+    /// function is called on the render process main thread.
+    fn get_render_process_handler(&mut self) -> Option<RenderProcessHandler> {
+        None
+    }
 }
 
 // Implement this structure to provide handler implementations. Methods will be
@@ -63,16 +72,27 @@ impl App {
     }
 }
 
-/// Translates CEF -> Rust callbacks.
-struct AppWrapper(Box<dyn AppCallbacks>);
+callbacks_builder! {
+    /// Builds a App from closures. Callbacks that are not set fall back to the
+    /// default AppCallbacks behavior.
+    AppBuilder: AppCallbacks => App {
+        fn on_before_command_line_processing(
+            process_type: Option<&str>,
+            command_line: Option<CommandLine>
+        );
+        fn on_register_custom_schemes(registrar: &SchemeRegistrar);
+        fn get_resource_bundle_handler() -> Option<ResourceBundleHandler> = None;
+        fn get_browser_process_handler() -> Option<BrowserProcessHandler> = None;
+        fn get_render_process_handler() -> Option<RenderProcessHandler> = None;
+    }
+}
 
-// TODO: Remove this!
+/// Translates CEF -> Rust callbacks.
+struct AppWrapper<C: AppCallbacks>(C);
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-impl AppWrapper {
-    pub fn new<C: AppCallbacks>(delegate: C) -> Self {
-        Self(Box::new(delegate))
+impl<C: AppCallbacks> AppWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
     }
 
     /// Provides an opportunity to view and/or modify command-line arguments
@@ -90,17 +110,22 @@ impl AppWrapper {
         process_type: *const cef_string_t,
         command_line: *mut cef_command_line_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let process_type: Option<String> = CefString::from_ptr(process_type).map(|s| s.into());
-        let process_type = process_type.as_deref();
-        let command_line = CommandLine::from_ptr(command_line);
-
-        this.0
-            .on_before_command_line_processing(process_type, command_line);
+        catch_unwind_or(
+            "AppWrapper::c_on_before_command_line_processing",
+            (),
+            || {
+                let this: &mut Self = Wrapped::wrappable(this);
+                let process_type: Option<String> =
+                    CefString::from_ptr(process_type).map(|s| s.into());
+                let process_type = process_type.as_deref();
+                let command_line = CommandLine::from_ptr(command_line);
+
+                this.0
+                    .on_before_command_line_processing(process_type, command_line);
+            }
+        )
     }
 
-    // TODO: Fix this!
-
     /// Provides an opportunity to register custom schemes. Do not keep a
     /// reference to the |registrar| object. This function is called on the main
     /// thread for each process and the registered schemes should be the same
@@ -109,7 +134,14 @@ impl AppWrapper {
         this: *mut cef_app_t,
         registrar: *mut cef_scheme_registrar_t
     ) {
-        todo!();
+        catch_unwind_or("AppWrapper::c_on_register_custom_schemes", (), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+
+            if let Some(registrar) = SchemeRegistrar::from_ptr(registrar) {
+                this.0
+                    .on_register_custom_schemes(&registrar);
+            }
+        })
     }
 
     /// Return the handler for resource bundle events. If
@@ -120,7 +152,18 @@ impl AppWrapper {
     unsafe extern "C" fn c_get_resource_bundle_handler(
         this: *mut cef_app_t
     ) -> *mut cef_resource_bundle_handler_t {
-        todo!()
+        catch_unwind_or(
+            "AppWrapper::c_get_resource_bundle_handler",
+            null_mut(),
+            || {
+                let this: &mut Self = Wrapped::wrappable(this);
+
+                this.0
+                    .get_resource_bundle_handler()
+                    .map(|handler| handler.into_raw())
+                    .unwrap_or_else(null_mut)
+            }
+        )
     }
 
     /// Return the handler for functionality specific to the browser process. This
@@ -128,12 +171,18 @@ impl AppWrapper {
     unsafe extern "C" fn c_get_browser_process_handler(
         this: *mut cef_app_t
     ) -> *mut cef_browser_process_handler_t {
-        let this: &mut Self = Wrapped::wrappable(this);
-
-        this.0
-            .get_browser_process_handler()
-            .map(|handler| handler.into_raw())
-            .unwrap_or_else(null_mut)
+        catch_unwind_or(
+            "AppWrapper::c_get_browser_process_handler",
+            null_mut(),
+            || {
+                let this: &mut Self = Wrapped::wrappable(this);
+
+                this.0
+                    .get_browser_process_handler()
+                    .map(|handler| handler.into_raw())
+                    .unwrap_or_else(null_mut)
+            }
+        )
     }
 
     /// Return the handler for functionality specific to the render process. This
@@ -141,11 +190,24 @@ impl AppWrapper {
     unsafe extern "C" fn c_get_render_process_handler(
         this: *mut cef_app_t
     ) -> *mut cef_render_process_handler_t {
-        todo!()
+        catch_unwind_or(
+            "AppWrapper::c_get_render_process_handler",
+            null_mut(),
+            || {
+                let this: &mut Self = Wrapped::wrappable(this);
+
+                // A handler is always returned so that functions bound with
+                // Browser::bind_function work without any render process code.
+                this.0
+                    .get_render_process_handler()
+                    .unwrap_or_else(|| RenderProcessHandler::new(DefaultRenderProcessHandler))
+                    .into_raw()
+            }
+        )
     }
 }
 
-impl Wrappable for AppWrapper {
+impl<C: AppCallbacks> Wrappable for AppWrapper<C> {
     type Cef = cef_app_t;
 
     /// Converts this to a smart pointer.
@@ -154,12 +216,11 @@ impl Wrappable for AppWrapper {
             cef_app_t {
                 base: unsafe { zeroed() },
 
-                // TODO: Fix this!
                 on_before_command_line_processing: Some(Self::c_on_before_command_line_processing),
-                on_register_custom_schemes:        None,
-                get_resource_bundle_handler:       None,
+                on_register_custom_schemes:        Some(Self::c_on_register_custom_schemes),
+                get_resource_bundle_handler:       Some(Self::c_get_resource_bundle_handler),
                 get_browser_process_handler:       Some(Self::c_get_browser_process_handler),
-                get_render_process_handler:        None
+                get_render_process_handler:        Some(Self::c_get_render_process_handler)
             },
             self
         )
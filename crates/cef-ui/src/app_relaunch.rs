@@ -0,0 +1,103 @@
+use crate::{CommandLine, Result};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf}
+};
+
+/// The launch arguments of a second app instance that were forwarded to the
+/// already running instance. Only a single app instance may run for a given
+/// Settings::root_cache_path, so a relaunch exits early and the running
+/// instance receives
+/// BrowserProcessHandlerCallbacks::on_already_running_app_relaunch instead.
+/// The CommandLine passed there must not be kept, so this copies what it needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppRelaunch {
+    program:           String,
+    switches:          HashMap<String, Option<String>>,
+    arguments:         Vec<String>,
+    current_directory: PathBuf
+}
+
+impl AppRelaunch {
+    /// Copies the relaunch |command_line| and the |current_directory| of the
+    /// new instance.
+    pub fn new(command_line: &CommandLine, current_directory: &str) -> Result<Self> {
+        Ok(Self {
+            program:           command_line.get_program()?,
+            switches:          command_line.get_switches()?,
+            arguments:         command_line.get_arguments()?,
+            current_directory: PathBuf::from(current_directory)
+        })
+    }
+
+    /// The program of the new instance.
+    pub fn program(&self) -> &str {
+        &self.program
+    }
+
+    /// The working directory of the new instance.
+    pub fn current_directory(&self) -> &Path {
+        &self.current_directory
+    }
+
+    /// True if the new instance was launched with |name|.
+    pub fn has_switch(&self, name: &str) -> bool {
+        self.switches
+            .contains_key(name)
+    }
+
+    /// The value of switch |name|, if it was passed with a value.
+    pub fn get_switch_value(&self, name: &str) -> Option<&str> {
+        self.switches
+            .get(name)
+            .and_then(|value| value.as_deref())
+    }
+
+    /// All switches of the new instance.
+    pub fn switches(&self) -> &HashMap<String, Option<String>> {
+        &self.switches
+    }
+
+    /// The remaining (non-switch) arguments of the new instance.
+    pub fn arguments(&self) -> &[String] {
+        &self.arguments
+    }
+
+    /// The arguments that are URLs, i.e. that start with a scheme such as
+    /// "https:" or "about:". These are typically opened in a new tab or window.
+    pub fn urls(&self) -> Vec<&str> {
+        self.arguments
+            .iter()
+            .filter(|argument| is_url(argument))
+            .map(|argument| argument.as_str())
+            .collect()
+    }
+
+    /// The arguments that are not URLs, as paths resolved against the working
+    /// directory of the new instance (not that of the running instance).
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.arguments
+            .iter()
+            .filter(|argument| !is_url(argument))
+            .map(|argument| self.current_directory.join(argument))
+            .collect()
+    }
+}
+
+/// Returns true if |argument| starts with a URL scheme. Single letter schemes
+/// are treated as Windows drive letters.
+fn is_url(argument: &str) -> bool {
+    match argument.split_once(':') {
+        Some((scheme, _)) => {
+            scheme.len() > 1
+                && scheme
+                    .chars()
+                    .next()
+                    .map_or(false, |c| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        },
+        None => false
+    }
+}
@@ -0,0 +1,605 @@
+use crate::{
+    callbacks_builder, debug_assert_currently_on, ref_counted_ptr, unwind::catch_unwind_or,
+    Browser, CefString, RefCountedPtr, ThreadId, Wrappable, Wrapped
+};
+use cef_ui_sys::{
+    cef_audio_handler_t, cef_audio_parameters_t, cef_browser_t, cef_channel_layout_t,
+    cef_string_t
+};
+use std::{
+    collections::HashMap,
+    ffi::c_int,
+    mem::zeroed,
+    slice::from_raw_parts,
+    time::Duration
+};
+
+/// Enumerates the various representations of the ordering of audio channels.
+/// Must be kept synchronized with media::ChannelLayout from Chromium.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChannelLayout {
+    None,
+    Unsupported,
+
+    /// Front C
+    Mono,
+
+    /// Front L, Front R
+    Stereo,
+
+    /// Front L, Front R, Back C
+    Layout2_1,
+
+    /// Front L, Front R, Front C
+    Surround,
+
+    /// Front L, Front R, Front C, Back C
+    Layout4_0,
+
+    /// Front L, Front R, Side L, Side R
+    Layout2_2,
+
+    /// Front L, Front R, Back L, Back R
+    Quad,
+
+    /// Front L, Front R, Front C, Side L, Side R
+    Layout5_0,
+
+    /// Front L, Front R, Front C, LFE, Side L, Side R
+    Layout5_1,
+
+    /// Front L, Front R, Front C, Back L, Back R
+    Layout5_0Back,
+
+    /// Front L, Front R, Front C, LFE, Back L, Back R
+    Layout5_1Back,
+
+    /// Front L, Front R, Front C, Side L, Side R, Back L, Back R
+    Layout7_0,
+
+    /// Front L, Front R, Front C, LFE, Side L, Side R, Back L, Back R
+    Layout7_1,
+
+    /// Front L, Front R, Front C, LFE, Side L, Side R, Front LofC, Front RofC
+    Layout7_1Wide,
+
+    /// Stereo L, Stereo R
+    StereoDownmix,
+
+    /// Stereo L, Stereo R, LFE
+    Layout2Point1,
+
+    /// Stereo L, Stereo R, Front C, LFE
+    Layout3_1,
+
+    /// Stereo L, Stereo R, Front C, Rear C, LFE
+    Layout4_1,
+
+    /// Stereo L, Stereo R, Front C, Side L, Side R, Back C
+    Layout6_0,
+
+    /// Stereo L, Stereo R, Side L, Side R, Front LofC, Front RofC
+    Layout6_0Front,
+
+    /// Stereo L, Stereo R, Front C, Rear L, Rear R, Rear C
+    Hexagonal,
+
+    /// Stereo L, Stereo R, Front C, LFE, Side L, Side R, Rear Center
+    Layout6_1,
+
+    /// Stereo L, Stereo R, Front C, LFE, Back L, Back R, Rear Center
+    Layout6_1Back,
+
+    /// Stereo L, Stereo R, Side L, Side R, Front LofC, Front RofC, LFE
+    Layout6_1Front,
+
+    /// Front L, Front R, Front C, Side L, Side R, Front LofC, Front RofC
+    Layout7_0Front,
+
+    /// Front L, Front R, Front C, LFE, Back L, Back R, Front LofC, Front RofC
+    Layout7_1WideBack,
+
+    /// Front L, Front R, Front C, Side L, Side R, Rear L, Back R, Back C.
+    Octagonal,
+
+    /// Channels are not explicitly mapped to speakers.
+    Discrete,
+
+    /// Front L, Front R, Front C. Front C contains the keyboard mic audio. This
+    /// layout is only intended for input for WebRTC. The Front C channel
+    /// is stripped away in the WebRTC audio input pipeline and never seen
+    /// outside of that.
+    StereoAndKeyboardMic,
+
+    /// Front L, Front R, Side L, Side R, LFE
+    Layout4_1QuadSide,
+
+    /// Actual channel layout is specified in the bitstream and the actual channel
+    /// count is unknown at Chromium media pipeline level (useful for audio
+    /// pass-through mode).
+    Bitstream,
+
+    /// Front L, Front R, Front C, LFE, Side L, Side R,
+    /// Front Height L, Front Height R, Rear Height L, Rear Height R
+    /// Will be represented as six channels (5.1) due to eight channel limit
+    /// kMaxConcurrentChannels
+    Layout5_1_4Downmix
+}
+
+impl ChannelLayout {
+    /// Returns the number of channels in the layout, or None if the layout does
+    /// not determine it (None, Unsupported, Discrete and Bitstream).
+    pub fn channel_count(&self) -> Option<usize> {
+        match self {
+            ChannelLayout::None => None,
+            ChannelLayout::Unsupported => None,
+            ChannelLayout::Mono => Some(1),
+            ChannelLayout::Stereo => Some(2),
+            ChannelLayout::Layout2_1 => Some(3),
+            ChannelLayout::Surround => Some(3),
+            ChannelLayout::Layout4_0 => Some(4),
+            ChannelLayout::Layout2_2 => Some(4),
+            ChannelLayout::Quad => Some(4),
+            ChannelLayout::Layout5_0 => Some(5),
+            ChannelLayout::Layout5_1 => Some(6),
+            ChannelLayout::Layout5_0Back => Some(5),
+            ChannelLayout::Layout5_1Back => Some(6),
+            ChannelLayout::Layout7_0 => Some(7),
+            ChannelLayout::Layout7_1 => Some(8),
+            ChannelLayout::Layout7_1Wide => Some(8),
+            ChannelLayout::StereoDownmix => Some(2),
+            ChannelLayout::Layout2Point1 => Some(3),
+            ChannelLayout::Layout3_1 => Some(4),
+            ChannelLayout::Layout4_1 => Some(5),
+            ChannelLayout::Layout6_0 => Some(6),
+            ChannelLayout::Layout6_0Front => Some(6),
+            ChannelLayout::Hexagonal => Some(6),
+            ChannelLayout::Layout6_1 => Some(7),
+            ChannelLayout::Layout6_1Back => Some(7),
+            ChannelLayout::Layout6_1Front => Some(7),
+            ChannelLayout::Layout7_0Front => Some(7),
+            ChannelLayout::Layout7_1WideBack => Some(8),
+            ChannelLayout::Octagonal => Some(8),
+            ChannelLayout::Discrete => None,
+            ChannelLayout::StereoAndKeyboardMic => Some(3),
+            ChannelLayout::Layout4_1QuadSide => Some(5),
+            ChannelLayout::Bitstream => None,
+            ChannelLayout::Layout5_1_4Downmix => Some(6)
+        }
+    }
+}
+
+impl From<cef_channel_layout_t> for ChannelLayout {
+    fn from(value: cef_channel_layout_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_channel_layout_t> for ChannelLayout {
+    fn from(value: &cef_channel_layout_t) -> Self {
+        match value {
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_NONE => ChannelLayout::None,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_UNSUPPORTED => ChannelLayout::Unsupported,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_MONO => ChannelLayout::Mono,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_STEREO => ChannelLayout::Stereo,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_2_1 => ChannelLayout::Layout2_1,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_SURROUND => ChannelLayout::Surround,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_4_0 => ChannelLayout::Layout4_0,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_2_2 => ChannelLayout::Layout2_2,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_QUAD => ChannelLayout::Quad,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_5_0 => ChannelLayout::Layout5_0,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_5_1 => ChannelLayout::Layout5_1,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_5_0_BACK => ChannelLayout::Layout5_0Back,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_5_1_BACK => ChannelLayout::Layout5_1Back,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_7_0 => ChannelLayout::Layout7_0,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_7_1 => ChannelLayout::Layout7_1,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_7_1_WIDE => ChannelLayout::Layout7_1Wide,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_STEREO_DOWNMIX => {
+                ChannelLayout::StereoDownmix
+            },
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_2POINT1 => ChannelLayout::Layout2Point1,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_3_1 => ChannelLayout::Layout3_1,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_4_1 => ChannelLayout::Layout4_1,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_6_0 => ChannelLayout::Layout6_0,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_6_0_FRONT => ChannelLayout::Layout6_0Front,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_HEXAGONAL => ChannelLayout::Hexagonal,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_6_1 => ChannelLayout::Layout6_1,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_6_1_BACK => ChannelLayout::Layout6_1Back,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_6_1_FRONT => ChannelLayout::Layout6_1Front,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_7_0_FRONT => ChannelLayout::Layout7_0Front,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_7_1_WIDE_BACK => {
+                ChannelLayout::Layout7_1WideBack
+            },
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_OCTAGONAL => ChannelLayout::Octagonal,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_DISCRETE => ChannelLayout::Discrete,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_STEREO_AND_KEYBOARD_MIC => {
+                ChannelLayout::StereoAndKeyboardMic
+            },
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_4_1_QUAD_SIDE => {
+                ChannelLayout::Layout4_1QuadSide
+            },
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_BITSTREAM => ChannelLayout::Bitstream,
+            cef_channel_layout_t::CEF_CHANNEL_LAYOUT_5_1_4_DOWNMIX => {
+                ChannelLayout::Layout5_1_4Downmix
+            },
+        }
+    }
+}
+
+impl From<ChannelLayout> for cef_channel_layout_t {
+    fn from(value: ChannelLayout) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&ChannelLayout> for cef_channel_layout_t {
+    fn from(value: &ChannelLayout) -> Self {
+        match value {
+            ChannelLayout::None => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_NONE,
+            ChannelLayout::Unsupported => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_UNSUPPORTED,
+            ChannelLayout::Mono => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_MONO,
+            ChannelLayout::Stereo => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_STEREO,
+            ChannelLayout::Layout2_1 => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_2_1,
+            ChannelLayout::Surround => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_SURROUND,
+            ChannelLayout::Layout4_0 => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_4_0,
+            ChannelLayout::Layout2_2 => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_2_2,
+            ChannelLayout::Quad => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_QUAD,
+            ChannelLayout::Layout5_0 => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_5_0,
+            ChannelLayout::Layout5_1 => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_5_1,
+            ChannelLayout::Layout5_0Back => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_5_0_BACK,
+            ChannelLayout::Layout5_1Back => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_5_1_BACK,
+            ChannelLayout::Layout7_0 => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_7_0,
+            ChannelLayout::Layout7_1 => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_7_1,
+            ChannelLayout::Layout7_1Wide => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_7_1_WIDE,
+            ChannelLayout::StereoDownmix => {
+                cef_channel_layout_t::CEF_CHANNEL_LAYOUT_STEREO_DOWNMIX
+            },
+            ChannelLayout::Layout2Point1 => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_2POINT1,
+            ChannelLayout::Layout3_1 => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_3_1,
+            ChannelLayout::Layout4_1 => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_4_1,
+            ChannelLayout::Layout6_0 => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_6_0,
+            ChannelLayout::Layout6_0Front => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_6_0_FRONT,
+            ChannelLayout::Hexagonal => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_HEXAGONAL,
+            ChannelLayout::Layout6_1 => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_6_1,
+            ChannelLayout::Layout6_1Back => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_6_1_BACK,
+            ChannelLayout::Layout6_1Front => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_6_1_FRONT,
+            ChannelLayout::Layout7_0Front => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_7_0_FRONT,
+            ChannelLayout::Layout7_1WideBack => {
+                cef_channel_layout_t::CEF_CHANNEL_LAYOUT_7_1_WIDE_BACK
+            },
+            ChannelLayout::Octagonal => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_OCTAGONAL,
+            ChannelLayout::Discrete => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_DISCRETE,
+            ChannelLayout::StereoAndKeyboardMic => {
+                cef_channel_layout_t::CEF_CHANNEL_LAYOUT_STEREO_AND_KEYBOARD_MIC
+            },
+            ChannelLayout::Layout4_1QuadSide => {
+                cef_channel_layout_t::CEF_CHANNEL_LAYOUT_4_1_QUAD_SIDE
+            },
+            ChannelLayout::Bitstream => cef_channel_layout_t::CEF_CHANNEL_LAYOUT_BITSTREAM,
+            ChannelLayout::Layout5_1_4Downmix => {
+                cef_channel_layout_t::CEF_CHANNEL_LAYOUT_5_1_4_DOWNMIX
+            },
+        }
+    }
+}
+
+/// Structure representing the audio parameters for setting up the audio
+/// handler.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AudioParameters {
+    /// Layout of the audio channels.
+    pub channel_layout: ChannelLayout,
+
+    /// Sample rate in Hz.
+    pub sample_rate: i32,
+
+    /// Number of frames per buffer, i.e. per call to on_audio_stream_packet.
+    pub frames_per_buffer: i32
+}
+
+impl AudioParameters {
+    pub fn new(channel_layout: ChannelLayout, sample_rate: i32, frames_per_buffer: i32) -> Self {
+        Self {
+            channel_layout,
+            sample_rate,
+            frames_per_buffer
+        }
+    }
+
+    /// Returns the duration of audio in one buffer.
+    pub fn buffer_duration(&self) -> Duration {
+        match self.sample_rate > 0 {
+            true => Duration::from_secs_f64(
+                self.frames_per_buffer.max(0) as f64 / self.sample_rate as f64
+            ),
+            false => Duration::ZERO
+        }
+    }
+}
+
+impl Default for AudioParameters {
+    /// The parameters CEF uses unless get_audio_parameters changes them: stereo
+    /// at 48 kHz with 1024 frames per buffer.
+    fn default() -> Self {
+        Self::new(ChannelLayout::Stereo, 48000, 1024)
+    }
+}
+
+impl From<cef_audio_parameters_t> for AudioParameters {
+    fn from(value: cef_audio_parameters_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_audio_parameters_t> for AudioParameters {
+    fn from(value: &cef_audio_parameters_t) -> Self {
+        Self::new(
+            value.channel_layout.into(),
+            value.sample_rate,
+            value.frames_per_buffer
+        )
+    }
+}
+
+impl From<AudioParameters> for cef_audio_parameters_t {
+    fn from(value: AudioParameters) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&AudioParameters> for cef_audio_parameters_t {
+    fn from(value: &AudioParameters) -> Self {
+        Self {
+            channel_layout:    value.channel_layout.into(),
+            sample_rate:       value.sample_rate,
+            frames_per_buffer: value.frames_per_buffer
+        }
+    }
+}
+
+/// Implement this structure to handle audio events.
+pub trait AudioHandlerCallbacks: Send + Sync + 'static {
+    /// Called on the UI thread to allow configuration of audio stream parameters.
+    /// Return true (1) to proceed with audio stream capture, or false (0) to
+    /// cancel it. All members of |params| can optionally be configured here, but
+    /// they are also pre-filled with some sensible defaults.
+    fn get_audio_parameters(&mut self, _browser: Browser, _params: &mut AudioParameters) -> bool {
+        true
+    }
+
+    /// Called on a browser audio capture thread when the browser starts
+    /// streaming audio. on_audio_stream_stopped will always be called after
+    /// on_audio_stream_started; both functions may be called multiple times for
+    /// the same browser. |params| contains the audio parameters like sample rate
+    /// and channel layout. |channels| is the number of channels.
+    fn on_audio_stream_started(
+        &mut self,
+        _browser: Browser,
+        _params: AudioParameters,
+        _channels: i32
+    ) {
+    }
+
+    /// Called on the audio stream thread when a PCM packet is received for the
+    /// stream. |data| is an array representing the raw PCM data as a floating
+    /// point type, i.e. 4-byte value(s), with one slice of |frames| samples per
+    /// channel. |pts| is the presentation timestamp (in milliseconds since the
+    /// Unix Epoch) and represents the time at which the decompressed packet
+    /// should be presented to the user. Based on |frames| and the
+    /// |channel_layout| value passed to on_audio_stream_started you can
+    /// calculate the size of the |data| array in bytes.
+    fn on_audio_stream_packet(&mut self, _browser: Browser, _data: &[&[f32]], _pts: i64) {}
+
+    /// Called on the UI thread when the stream has stopped. on_audio_stream_stopped
+    /// will always be called after on_audio_stream_started; both functions may be
+    /// called multiple times for the same stream.
+    fn on_audio_stream_stopped(&mut self, _browser: Browser) {}
+
+    /// Called on the UI or audio stream thread when an error occurred. During the
+    /// stream creation phase this callback will be called on the UI thread while
+    /// in the capturing phase it will be called on the audio stream thread. The
+    /// stream will be stopped immediately.
+    fn on_audio_stream_error(&mut self, _browser: Browser, _message: &str) {}
+}
+
+// Implement this structure to handle audio events.
+ref_counted_ptr!(AudioHandler, cef_audio_handler_t);
+
+impl AudioHandler {
+    pub fn new<C: AudioHandlerCallbacks>(delegate: C) -> Self {
+        Self(AudioHandlerWrapper::new(delegate).wrap())
+    }
+}
+
+callbacks_builder! {
+    /// Builds an AudioHandler from closures. Callbacks that are not set fall
+    /// back to the default AudioHandlerCallbacks behavior.
+    AudioHandlerBuilder: AudioHandlerCallbacks => AudioHandler {
+        fn get_audio_parameters(browser: Browser, params: &mut AudioParameters) -> bool = true;
+        fn on_audio_stream_started(browser: Browser, params: AudioParameters, channels: i32);
+        fn on_audio_stream_packet(browser: Browser, data: &[&[f32]], pts: i64);
+        fn on_audio_stream_stopped(browser: Browser);
+        fn on_audio_stream_error(browser: Browser, message: &str);
+    }
+}
+
+/// Translates CEF -> Rust callbacks.
+struct AudioHandlerWrapper<C: AudioHandlerCallbacks> {
+    delegate: C,
+
+    /// The channel count of each browser's stream, by browser identifier, as
+    /// on_audio_stream_packet does not pass it.
+    channels: HashMap<i32, usize>
+}
+
+impl<C: AudioHandlerCallbacks> AudioHandlerWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self {
+            delegate,
+            channels: HashMap::new()
+        }
+    }
+
+    /// Called on the UI thread to allow configuration of audio stream parameters.
+    /// Return true (1) to proceed with audio stream capture, or false (0) to
+    /// cancel it. All members of |params| can optionally be configured here, but
+    /// they are also pre-filled with some sensible defaults.
+    unsafe extern "C" fn c_get_audio_parameters(
+        this: *mut cef_audio_handler_t,
+        browser: *mut cef_browser_t,
+        params: *mut cef_audio_parameters_t
+    ) -> c_int {
+        catch_unwind_or("AudioHandlerWrapper::c_get_audio_parameters", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let mut local_params = AudioParameters::from(&*params);
+
+            let ret = this
+                .delegate
+                .get_audio_parameters(browser, &mut local_params);
+
+            *params = local_params.into();
+
+            ret as c_int
+        })
+    }
+
+    /// Called on a browser audio capture thread when the browser starts
+    /// streaming audio. OnAudioStreamStopped will always be called after
+    /// OnAudioStreamStarted; both functions may be called multiple times for the
+    /// same browser. |params| contains the audio parameters like sample rate and
+    /// channel layout. |channels| is the number of channels.
+    unsafe extern "C" fn c_on_audio_stream_started(
+        this: *mut cef_audio_handler_t,
+        browser: *mut cef_browser_t,
+        params: *const cef_audio_parameters_t,
+        channels: c_int
+    ) {
+        catch_unwind_or(
+            "AudioHandlerWrapper::c_on_audio_stream_started",
+            (),
+            || {
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+                let params = AudioParameters::from(&*params);
+
+                if let Ok(identifier) = browser.get_identifier() {
+                    this.channels
+                        .insert(identifier, channels.max(0) as usize);
+                }
+
+                this.delegate
+                    .on_audio_stream_started(browser, params, channels);
+            }
+        )
+    }
+
+    /// Called on the audio stream thread when a PCM packet is received for the
+    /// stream. |data| is an array representing the raw PCM data as a floating
+    /// point type, i.e. 4-byte value(s). |frames| is the number of frames in the
+    /// PCM packet. |pts| is the presentation timestamp (in milliseconds since the
+    /// Unix Epoch) and represents the time at which the decompressed packet
+    /// should be presented to the user. Based on |frames| and the
+    /// |channel_layout| value passed to OnAudioStreamStarted you can calculate
+    /// the size of the |data| array in bytes.
+    unsafe extern "C" fn c_on_audio_stream_packet(
+        this: *mut cef_audio_handler_t,
+        browser: *mut cef_browser_t,
+        data: *mut *const f32,
+        frames: c_int,
+        pts: i64
+    ) {
+        catch_unwind_or("AudioHandlerWrapper::c_on_audio_stream_packet", (), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let channels = browser
+                .get_identifier()
+                .ok()
+                .and_then(|identifier| this.channels.get(&identifier))
+                .copied()
+                .unwrap_or(0);
+            let data: Vec<&[f32]> = match data.is_null() || frames <= 0 {
+                true => Vec::new(),
+                false => from_raw_parts(data, channels)
+                    .iter()
+                    .map(|channel| from_raw_parts(*channel, frames as usize))
+                    .collect()
+            };
+
+            this.delegate
+                .on_audio_stream_packet(browser, &data, pts);
+        })
+    }
+
+    /// Called on the UI thread when the stream has stopped. OnAudioSteamStopped
+    /// will always be called after OnAudioStreamStarted; both functions may be
+    /// called multiple times for the same stream.
+    unsafe extern "C" fn c_on_audio_stream_stopped(
+        this: *mut cef_audio_handler_t,
+        browser: *mut cef_browser_t
+    ) {
+        catch_unwind_or(
+            "AudioHandlerWrapper::c_on_audio_stream_stopped",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+
+                if let Ok(identifier) = browser.get_identifier() {
+                    this.channels.remove(&identifier);
+                }
+
+                this.delegate
+                    .on_audio_stream_stopped(browser);
+            }
+        )
+    }
+
+    /// Called on the UI or audio stream thread when an error occurred. During the
+    /// stream creation phase this callback will be called on the UI thread while
+    /// in the capturing phase it will be called on the audio stream thread. The
+    /// stream will be stopped immediately.
+    unsafe extern "C" fn c_on_audio_stream_error(
+        this: *mut cef_audio_handler_t,
+        browser: *mut cef_browser_t,
+        message: *const cef_string_t
+    ) {
+        catch_unwind_or("AudioHandlerWrapper::c_on_audio_stream_error", (), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let message: String = CefString::from_ptr(message)
+                .map(|message| message.into())
+                .unwrap_or_default();
+
+            if let Ok(identifier) = browser.get_identifier() {
+                this.channels.remove(&identifier);
+            }
+
+            this.delegate
+                .on_audio_stream_error(browser, &message);
+        })
+    }
+}
+
+impl<C: AudioHandlerCallbacks> Wrappable for AudioHandlerWrapper<C> {
+    type Cef = cef_audio_handler_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<cef_audio_handler_t> {
+        RefCountedPtr::wrap(
+            cef_audio_handler_t {
+                base:                    unsafe { zeroed() },
+                get_audio_parameters:    Some(Self::c_get_audio_parameters),
+                on_audio_stream_started: Some(Self::c_on_audio_stream_started),
+                on_audio_stream_packet:  Some(Self::c_on_audio_stream_packet),
+                on_audio_stream_stopped: Some(Self::c_on_audio_stream_stopped),
+                on_audio_stream_error:   Some(Self::c_on_audio_stream_error)
+            },
+            self
+        )
+    }
+}
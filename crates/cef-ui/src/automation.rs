@@ -0,0 +1,388 @@
+use crate::{
+    Browser, BrowserHost, ClickCounter, Error, EventFlags, KeyEvent, KeyEventType,
+    MouseButtonType, MouseEvent, Point, Result, WindowsKeyCode
+};
+use anyhow::anyhow;
+use std::str::FromStr;
+
+/// A key together with the modifiers that are held while it is pressed, e.g.
+/// "Ctrl+Shift+A", "Enter" or "CmdOrCtrl+C". Modifiers are Ctrl (or Control),
+/// Shift, Alt (or Option), Cmd (or Command, Meta, Super) and CmdOrCtrl, which
+/// is Cmd on macOS and Ctrl elsewhere. Keys are single characters, F1 through
+/// F24 and the names Enter, Tab, Space, Backspace, Delete, Insert, Escape,
+/// Home, End, PageUp, PageDown, Up, Down, Left and Right.
+#[derive(Debug, Clone, Copy)]
+pub struct KeyCombo {
+    pub modifiers: EventFlags,
+    pub key_code:  WindowsKeyCode,
+
+    /// The character the key types without modifiers, if any.
+    pub character: Option<char>
+}
+
+impl KeyCombo {
+    /// A combo of |key_code| and |modifiers|.
+    pub fn new(key_code: WindowsKeyCode, modifiers: EventFlags) -> Self {
+        Self {
+            modifiers,
+            key_code,
+            character: None
+        }
+    }
+
+    /// Returns the key code and unmodified character of |key|, if it is known.
+    fn parse_key(key: &str) -> Option<(WindowsKeyCode, Option<char>)> {
+        let mut chars = key.chars();
+
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            // Shift is part of the combo, so letters are typed in lower case.
+            return key_code_of(c).map(|(key_code, _)| (key_code, Some(c.to_ascii_lowercase())));
+        }
+
+        let key = key.to_ascii_lowercase();
+        let named = match key.as_str() {
+            "enter" | "return" => (WindowsKeyCode::Return, Some('\r')),
+            "tab" => (WindowsKeyCode::Tab, None),
+            "space" => (WindowsKeyCode::Space, Some(' ')),
+            "backspace" => (WindowsKeyCode::Back, None),
+            "delete" | "del" => (WindowsKeyCode::Delete, None),
+            "insert" => (WindowsKeyCode::Insert, None),
+            "escape" | "esc" => (WindowsKeyCode::Escape, None),
+            "home" => (WindowsKeyCode::Home, None),
+            "end" => (WindowsKeyCode::End, None),
+            "pageup" => (WindowsKeyCode::Prior, None),
+            "pagedown" => (WindowsKeyCode::Next, None),
+            "up" | "arrowup" => (WindowsKeyCode::Up, None),
+            "down" | "arrowdown" => (WindowsKeyCode::Down, None),
+            "left" | "arrowleft" => (WindowsKeyCode::Left, None),
+            "right" | "arrowright" => (WindowsKeyCode::Right, None),
+            _ => {
+                let number: i32 = key
+                    .strip_prefix('f')?
+                    .parse()
+                    .ok()?;
+
+                match (1..=24).contains(&number) {
+                    true => (
+                        WindowsKeyCode::from(i32::from(WindowsKeyCode::F1) + number - 1),
+                        None
+                    ),
+                    false => return None
+                }
+            }
+        };
+
+        Some(named)
+    }
+}
+
+impl FromStr for KeyCombo {
+    type Err = Error;
+
+    fn from_str(combo: &str) -> Result<Self> {
+        let invalid = || Error::from(anyhow!("Invalid key combination: {}", combo));
+
+        // The key itself may be "+", as in "Ctrl++".
+        let (modifiers, key) = match combo.strip_suffix("++") {
+            Some(modifiers) => (modifiers, "+"),
+            None => combo
+                .rsplit_once('+')
+                .unwrap_or(("", combo))
+        };
+
+        let mut flags = EventFlags::empty();
+
+        for modifier in modifiers
+            .split('+')
+            .map(str::trim)
+            .filter(|modifier| !modifier.is_empty())
+        {
+            flags |= match modifier
+                .to_ascii_lowercase()
+                .as_str()
+            {
+                "ctrl" | "control" => EventFlags::ControlDown,
+                "shift" => EventFlags::ShiftDown,
+                "alt" | "option" => EventFlags::AltDown,
+                "cmd" | "command" | "meta" | "super" => EventFlags::CommandDown,
+                "cmdorctrl" | "commandorcontrol" => EventFlags::shortcut_key(),
+                _ => return Err(invalid())
+            };
+        }
+
+        let (key_code, character) = Self::parse_key(key.trim()).ok_or_else(invalid)?;
+
+        Ok(Self {
+            modifiers: flags,
+            key_code,
+            character
+        })
+    }
+}
+
+/// Synthesizes user input for a browser, e.g. to drive an embedded UI in
+/// end-to-end tests. Every action sends the complete sequence of events a user
+/// would produce: clicks move the mouse before pressing and releasing the
+/// button and count repeated clicks as double and triple clicks, key presses
+/// hold their modifiers down around the key, and typed text produces key down,
+/// character and key up events.
+///
+/// Events are sent to the browser's view regardless of window focus, so
+/// windowless browsers can be automated offscreen. Coordinates are relative to
+/// the upper-left corner of the view.
+pub struct Automation {
+    host:   BrowserHost,
+    x:      i32,
+    y:      i32,
+    clicks: ClickCounter
+}
+
+impl Automation {
+    pub fn new(browser: &Browser) -> Result<Self> {
+        Ok(Self {
+            host:   browser.get_host()?,
+            x:      0,
+            y:      0,
+            clicks: ClickCounter::new()
+        })
+    }
+
+    /// Moves the mouse to |x|, |y|.
+    pub fn move_to(&mut self, x: i32, y: i32) -> Result<()> {
+        self.x = x;
+        self.y = y;
+
+        self.host
+            .send_mouse_move_event(&self.mouse_event(EventFlags::empty()), false)
+    }
+
+    /// Clicks the left mouse button at |x|, |y|.
+    pub fn click(&mut self, x: i32, y: i32) -> Result<()> {
+        self.click_with(x, y, MouseButtonType::Left, EventFlags::empty())
+    }
+
+    /// Double clicks the left mouse button at |x|, |y|.
+    pub fn double_click(&mut self, x: i32, y: i32) -> Result<()> {
+        self.clicks.reset();
+        self.click(x, y)?;
+        self.click(x, y)
+    }
+
+    /// Clicks |button| at |x|, |y| while |modifiers| are held. Clicks in quick
+    /// succession at the same place count as double and triple clicks.
+    pub fn click_with(
+        &mut self,
+        x: i32,
+        y: i32,
+        button: MouseButtonType,
+        modifiers: EventFlags
+    ) -> Result<()> {
+        self.move_to(x, y)?;
+
+        let count = self
+            .clicks
+            .press(button, Point::new(x, y));
+
+        self.host
+            .send_mouse_click(&self.mouse_event(modifiers), button, count)
+    }
+
+    /// Scrolls by |delta_x|, |delta_y| pixels at the current mouse position.
+    /// Positive values scroll right and down.
+    pub fn scroll(&mut self, delta_x: i32, delta_y: i32) -> Result<()> {
+        self.host
+            .send_mouse_wheel_event(&self.mouse_event(EventFlags::empty()), -delta_x, -delta_y)
+    }
+
+    /// Types |text| into the focused element.
+    pub fn type_text(&mut self, text: &str) -> Result<()> {
+        for c in text.chars() {
+            match c {
+                '\n' => self.press_combo(&KeyCombo {
+                    modifiers: EventFlags::empty(),
+                    key_code:  WindowsKeyCode::Return,
+                    character: Some('\r')
+                })?,
+                '\t' => {
+                    self.press_combo(&KeyCombo::new(WindowsKeyCode::Tab, EventFlags::empty()))?
+                },
+                c => self.type_char(c)?
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Presses and releases |combo|, e.g. "Ctrl+A" or "Enter". See KeyCombo
+    /// for the syntax.
+    pub fn press(&mut self, combo: &str) -> Result<()> {
+        self.press_combo(&combo.parse()?)
+    }
+
+    /// Presses and releases |combo|.
+    pub fn press_combo(&mut self, combo: &KeyCombo) -> Result<()> {
+        let modifier_keys = modifier_keys(combo.modifiers);
+        let mut held = EventFlags::empty();
+
+        for (flag, key_code) in &modifier_keys {
+            held |= *flag;
+            self.send_key(KeyEventType::RawKeyDown, *key_code, held, 0)?;
+        }
+
+        self.send_key(KeyEventType::RawKeyDown, combo.key_code, held, 0)?;
+
+        // Shortcuts do not type characters.
+        let shortcut = combo
+            .modifiers
+            .intersects(EventFlags::ControlDown | EventFlags::AltDown | EventFlags::CommandDown);
+
+        if let (Some(c), false) = (combo.character, shortcut) {
+            let c = match held.contains(EventFlags::ShiftDown) {
+                true => c.to_ascii_uppercase(),
+                false => c
+            };
+
+            self.send_chars(c, combo.key_code, held)?;
+        }
+
+        self.send_key(KeyEventType::KeyUp, combo.key_code, held, 0)?;
+
+        for (flag, key_code) in modifier_keys.iter().rev() {
+            held.remove(*flag);
+            self.send_key(KeyEventType::KeyUp, *key_code, held, 0)?;
+        }
+
+        Ok(())
+    }
+
+    /// Types a single character.
+    fn type_char(&mut self, c: char) -> Result<()> {
+        let (key_code, modifiers) =
+            key_code_of(c).unwrap_or((WindowsKeyCode::Unknown, EventFlags::empty()));
+
+        if modifiers.contains(EventFlags::ShiftDown) {
+            self.send_key(
+                KeyEventType::RawKeyDown,
+                WindowsKeyCode::Shift,
+                modifiers,
+                0
+            )?;
+        }
+
+        self.send_key(KeyEventType::RawKeyDown, key_code, modifiers, 0)?;
+        self.send_chars(c, key_code, modifiers)?;
+        self.send_key(KeyEventType::KeyUp, key_code, modifiers, 0)?;
+
+        if modifiers.contains(EventFlags::ShiftDown) {
+            self.send_key(
+                KeyEventType::KeyUp,
+                WindowsKeyCode::Shift,
+                EventFlags::empty(),
+                0
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Sends the character events for |c|, one per UTF-16 code unit.
+    fn send_chars(&self, c: char, key_code: WindowsKeyCode, modifiers: EventFlags) -> Result<()> {
+        let mut units = [0; 2];
+
+        for unit in c.encode_utf16(&mut units) {
+            self.send_key(KeyEventType::Char, key_code, modifiers, *unit)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sends a single key event.
+    fn send_key(
+        &self,
+        event_type: KeyEventType,
+        key_code: WindowsKeyCode,
+        modifiers: EventFlags,
+        character: u16
+    ) -> Result<()> {
+        let key_code = match event_type {
+            // Character events carry the character in windows_key_code.
+            KeyEventType::Char => WindowsKeyCode::from(character as i32),
+            _ => key_code
+        };
+
+        self.host.send_key_event(KeyEvent {
+            event_type,
+            modifiers,
+            windows_key_code: key_code,
+            native_key_code: 0,
+            is_system_key: false,
+            character,
+            unmodified_character: character,
+            focus_on_editable_field: false
+        })
+    }
+
+    /// Returns a mouse event at the current position.
+    fn mouse_event(&self, modifiers: EventFlags) -> MouseEvent {
+        MouseEvent::new(self.x, self.y).modifiers(modifiers)
+    }
+}
+
+/// Returns the keys of |modifiers| in the order they are pressed.
+fn modifier_keys(modifiers: EventFlags) -> Vec<(EventFlags, WindowsKeyCode)> {
+    [
+        (EventFlags::ControlDown, WindowsKeyCode::Control),
+        (EventFlags::AltDown, WindowsKeyCode::Menu),
+        (EventFlags::ShiftDown, WindowsKeyCode::Shift),
+        (EventFlags::CommandDown, WindowsKeyCode::LWin)
+    ]
+    .into_iter()
+    .filter(|(flag, _)| modifiers.contains(*flag))
+    .collect()
+}
+
+/// Returns the key code of the US keyboard key that types |c|, and whether
+/// Shift must be held.
+fn key_code_of(c: char) -> Option<(WindowsKeyCode, EventFlags)> {
+    let shift = EventFlags::ShiftDown;
+    let none = EventFlags::empty();
+
+    let key = match c {
+        'a'..='z' => (WindowsKeyCode::from(c.to_ascii_uppercase()), none),
+        'A'..='Z' => (WindowsKeyCode::from(c), shift),
+        '0'..='9' => (WindowsKeyCode::from(c), none),
+        ' ' => (WindowsKeyCode::Space, none),
+        '-' => (WindowsKeyCode::OEMMinus, none),
+        '_' => (WindowsKeyCode::OEMMinus, shift),
+        '=' => (WindowsKeyCode::OEMPlus, none),
+        '+' => (WindowsKeyCode::OEMPlus, shift),
+        ',' => (WindowsKeyCode::OEMComma, none),
+        '<' => (WindowsKeyCode::OEMComma, shift),
+        '.' => (WindowsKeyCode::OEMPeriod, none),
+        '>' => (WindowsKeyCode::OEMPeriod, shift),
+        ';' => (WindowsKeyCode::OEM1, none),
+        ':' => (WindowsKeyCode::OEM1, shift),
+        '/' => (WindowsKeyCode::OEM2, none),
+        '?' => (WindowsKeyCode::OEM2, shift),
+        '`' => (WindowsKeyCode::OEM3, none),
+        '~' => (WindowsKeyCode::OEM3, shift),
+        '[' => (WindowsKeyCode::OEM4, none),
+        '{' => (WindowsKeyCode::OEM4, shift),
+        '\\' => (WindowsKeyCode::OEM5, none),
+        '|' => (WindowsKeyCode::OEM5, shift),
+        ']' => (WindowsKeyCode::OEM6, none),
+        '}' => (WindowsKeyCode::OEM6, shift),
+        '\'' => (WindowsKeyCode::OEM7, none),
+        '"' => (WindowsKeyCode::OEM7, shift),
+        _ => {
+            let digit = ")!@#$%^&*(".find(c)?;
+
+            (
+                WindowsKeyCode::from(i32::from(WindowsKeyCode::Key0) + digit as i32),
+                shift
+            )
+        }
+    };
+
+    Some(key)
+}
@@ -0,0 +1,612 @@
+use crate::{
+    Browser, CefStr, Frame, FunctionScope, ProcessId, ProcessMessage, Result,
+    SharedProcessMessageBuilder, V8Context, V8Handler, V8HandlerCallbacks, V8PropertyAttribute,
+    V8Value
+};
+use anyhow::anyhow;
+use bytes::Bytes;
+use parking_lot::Mutex;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    ptr::copy_nonoverlapping,
+    slice::from_raw_parts,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc
+    }
+};
+
+/// Sent from the renderer when a process first sees a browser, asking whether
+/// a binary bridge is attached to it.
+const SYNC_MESSAGE: &str = "cef_ui.binary_bridge.sync";
+
+/// Sent to the renderer with the scope of the binary bridge attached to a
+/// browser.
+const ENABLE_MESSAGE: &str = "cef_ui.binary_bridge.enable";
+
+/// The prefix of binary message names. The channel name follows it, because
+/// shared memory messages have no argument list.
+const MESSAGE_PREFIX: &str = "cef_ui.binary:";
+
+/// The size of the length header at the start of the shared memory region.
+/// Regions can't be empty and may be larger than requested, so the payload
+/// length is stored explicitly.
+const HEADER_SIZE: usize = size_of::<u64>();
+
+/// The global that holds the native send function until the bootstrap script
+/// has captured it.
+const NATIVE_SEND: &str = "__cefUiSendBinary";
+
+/// The global that holds the JavaScript dispatch function.
+const DISPATCH_FUNCTION: &str = "__cefUiDispatchBinary";
+
+/// Defines cefSendBinary, cefOnBinary and cefOffBinary on the global object.
+const BOOTSTRAP_SCRIPT: &str = r#"(function () {
+  const send = globalThis.__cefUiSendBinary;
+  const listeners = new Map();
+  delete globalThis.__cefUiSendBinary;
+  const define = (name, value) => Object.defineProperty(globalThis, name, { value });
+  define("cefSendBinary", (channel, data) => {
+    if (ArrayBuffer.isView(data)) {
+      data = data.buffer.slice(data.byteOffset, data.byteOffset + data.byteLength);
+    }
+    if (!(data instanceof ArrayBuffer)) {
+      throw new TypeError("cefSendBinary expects an ArrayBuffer or a typed array.");
+    }
+    send(String(channel), data);
+  });
+  define("cefOnBinary", (channel, callback) => {
+    channel = String(channel);
+    if (!listeners.has(channel)) listeners.set(channel, new Set());
+    listeners.get(channel).add(callback);
+    return () => globalThis.cefOffBinary(channel, callback);
+  });
+  define("cefOffBinary", (channel, callback) => {
+    const set = listeners.get(String(channel));
+    if (set) set.delete(callback);
+  });
+  define("__cefUiDispatchBinary", (channel, data) => {
+    for (const callback of [...(listeners.get(channel) || [])]) {
+      try { callback(data); } catch (e) { console.error(e); }
+    }
+  });
+})();"#;
+
+/// Binary data sent from JavaScript with cefSendBinary(channel, data).
+#[derive(Clone)]
+pub struct BinaryMessage {
+    /// The browser that sent the data.
+    pub browser: Browser,
+
+    /// The frame that sent the data. Use BinaryBridge::send_to_frame to reply.
+    pub frame: Frame,
+
+    /// The channel name.
+    pub channel: String,
+
+    /// The data.
+    pub data: Bytes
+}
+
+/// Identifies a listener registered with BinaryBridge::on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BinaryBridgeSubscription(u64);
+
+type Listener = Arc<dyn Fn(&BinaryMessage) + Send + Sync + 'static>;
+type Listeners = HashMap<String, Vec<(BinaryBridgeSubscription, Listener)>>;
+
+/// Binary bridges attached to browsers in the browser process, by browser
+/// identifier.
+static ATTACHED: Mutex<BTreeMap<i32, (FunctionScope, BinaryBridge)>> = Mutex::new(BTreeMap::new());
+
+/// Browser process side of a channel for binary data between Rust and
+/// JavaScript. Data sent from Rust arrives in JavaScript as an ArrayBuffer
+/// passed to the callbacks registered with cefOnBinary(channel, callback), and
+/// JavaScript sends an ArrayBuffer or typed array with
+/// cefSendBinary(channel, data). The data travels in shared memory process
+/// messages, so it is never encoded as a string.
+///
+/// Attach the bridge to a browser with attach. No render process code is
+/// needed beyond returning a RenderProcessHandler from
+/// AppCallbacks::get_render_process_handler, which the crate does by default.
+/// Cloning is cheap and all clones share the same listeners.
+#[derive(Clone, Default)]
+pub struct BinaryBridge {
+    listeners: Arc<Mutex<Listeners>>,
+    next_id:   Arc<AtomicU64>
+}
+
+impl BinaryBridge {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Defines cefSendBinary, cefOnBinary and cefOffBinary in the frames of
+    /// |browser| selected by |scope| and routes the data they send to this
+    /// bridge. A browser has at most one binary bridge; attaching another one
+    /// replaces it, and frames that are no longer in scope keep the functions
+    /// but their data is dropped. The bridge is detached when the browser is
+    /// closed, provided the client has a LifeSpanHandler.
+    pub fn attach(&self, browser: &Browser, scope: FunctionScope) -> Result<()> {
+        ATTACHED
+            .lock()
+            .insert(browser.get_identifier()?, (scope, self.clone()));
+
+        // Every render process of the browser learns about the bridge, but
+        // only installs it in the frames that are in scope.
+        for identifier in browser.get_frame_identifiers()? {
+            if let Some(frame) = browser.get_frame_by_identifier(identifier)? {
+                send_enable_message(&frame, scope)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Calls |listener| whenever JavaScript sends data on |channel|. Listeners
+    /// are called on the browser process UI thread.
+    pub fn on(
+        &self,
+        channel: &str,
+        listener: impl Fn(&BinaryMessage) + Send + Sync + 'static
+    ) -> BinaryBridgeSubscription {
+        let subscription = BinaryBridgeSubscription(
+            self.next_id
+                .fetch_add(1, Ordering::Relaxed)
+        );
+
+        self.listeners
+            .lock()
+            .entry(channel.to_string())
+            .or_default()
+            .push((subscription, Arc::new(listener)));
+
+        subscription
+    }
+
+    /// Removes a listener registered with on.
+    pub fn off(&self, subscription: BinaryBridgeSubscription) {
+        let mut listeners = self.listeners.lock();
+
+        for entries in listeners.values_mut() {
+            entries.retain(|(id, _)| *id != subscription);
+        }
+
+        listeners.retain(|_, entries| !entries.is_empty());
+    }
+
+    /// Sends |data| to the JavaScript listeners for |channel| in |frame|. Fails
+    /// if |frame| is not in the scope of the binary bridge attached to its
+    /// browser.
+    pub fn send_to_frame(&self, frame: &Frame, channel: &str, data: &[u8]) -> Result<()> {
+        let browser = frame.get_browser()?;
+
+        match attached_scope(&browser)?.contains(&browser, frame)? {
+            true => frame.send_process_message(ProcessId::Renderer, create_message(channel, data)?),
+            false => Err(anyhow!("The frame is not in the scope of the binary bridge.").into())
+        }
+    }
+
+    /// Sends |data| to the JavaScript listeners for |channel| in every frame of
+    /// |browser| in the scope of its binary bridge.
+    pub fn send(&self, browser: &Browser, channel: &str, data: &[u8]) -> Result<()> {
+        let scope = attached_scope(browser)?;
+
+        for identifier in browser.get_frame_identifiers()? {
+            let Some(frame) = browser.get_frame_by_identifier(identifier)? else {
+                continue;
+            };
+
+            if scope.contains(browser, &frame)? {
+                frame.send_process_message(ProcessId::Renderer, create_message(channel, data)?)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Calls the listeners for |message|.
+    fn dispatch(&self, message: &BinaryMessage) {
+        // Listeners are cloned so that they may subscribe or unsubscribe.
+        let listeners: Vec<Listener> = self
+            .listeners
+            .lock()
+            .get(&message.channel)
+            .map(|entries| {
+                entries
+                    .iter()
+                    .map(|(_, listener)| listener.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        for listener in listeners {
+            listener(message);
+        }
+    }
+}
+
+/// Returns the scope of the binary bridge attached to |browser|.
+fn attached_scope(browser: &Browser) -> Result<FunctionScope> {
+    ATTACHED
+        .lock()
+        .get(&browser.get_identifier()?)
+        .map(|(scope, _)| *scope)
+        .ok_or_else(|| anyhow!("No binary bridge is attached to the browser.").into())
+}
+
+/// Detaches the binary bridge attached to |browser|, if any.
+pub(crate) fn release_browser_binary_bridge(browser: &Browser) {
+    if let Ok(identifier) = browser.get_identifier() {
+        ATTACHED.lock().remove(&identifier);
+    }
+}
+
+/// Handles binary bridge messages sent to the browser process. Returns true if
+/// |message| was handled.
+pub(crate) fn on_browser_process_message(
+    browser: &Browser,
+    frame: &Frame,
+    source_process: ProcessId,
+    message: &ProcessMessage
+) -> bool {
+    if source_process != ProcessId::Renderer {
+        return false;
+    }
+
+    if let Some(channel) = channel_name(message) {
+        let _ = receive_data(browser, frame, channel, message);
+
+        return true;
+    }
+
+    match message.get_name().ok().as_deref() {
+        Some(SYNC_MESSAGE) => {
+            if let Ok(scope) = attached_scope(browser) {
+                let _ = send_enable_message(frame, scope);
+            }
+
+            true
+        },
+        _ => false
+    }
+}
+
+/// Passes data sent by JavaScript on |channel| to the binary bridge attached
+/// to |browser|.
+fn receive_data(
+    browser: &Browser,
+    frame: &Frame,
+    channel: String,
+    message: &ProcessMessage
+) -> Result<()> {
+    // The bridge is cloned so that listeners may attach or detach bridges.
+    let Some((scope, bridge)) = ATTACHED
+        .lock()
+        .get(&browser.get_identifier()?)
+        .cloned()
+    else {
+        return Ok(());
+    };
+
+    // The render process only installs cefSendBinary in frames that are in
+    // scope, but a compromised render process could still send the data.
+    if !scope.contains(browser, frame)? {
+        return Ok(());
+    }
+
+    bridge.dispatch(&BinaryMessage {
+        browser: browser.clone(),
+        frame: frame.clone(),
+        channel,
+        data: read_message(message, Bytes::copy_from_slice)?
+    });
+
+    Ok(())
+}
+
+/// Tells the render process for |frame| that a binary bridge is attached in
+/// |scope|.
+fn send_enable_message(frame: &Frame, scope: FunctionScope) -> Result<()> {
+    let message = ProcessMessage::new(ENABLE_MESSAGE);
+
+    if let Some(arguments) = message.get_argument_list()? {
+        arguments.set_int(0, scope.into())?;
+    }
+
+    frame.send_process_message(ProcessId::Renderer, message)
+}
+
+thread_local! {
+    /// The scope of the binary bridge attached to each browser known to this
+    /// render process, by browser identifier. None until the browser process
+    /// has attached a binary bridge.
+    static RENDERER_SCOPES: RefCell<HashMap<i32, Option<FunctionScope>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Installs the binary bridge in |context| if one is attached to |browser| and
+/// its scope contains |frame|. The first context of a browser in this process
+/// asks the browser process for the scope.
+pub(crate) fn on_context_created(browser: &Browser, frame: &Frame, context: &V8Context) {
+    let Ok(identifier) = browser.get_identifier() else {
+        return;
+    };
+
+    let scope = RENDERER_SCOPES.with_borrow_mut(|scopes| match scopes.get(&identifier) {
+        Some(scope) => Some(*scope),
+        None => {
+            scopes.insert(identifier, None);
+
+            None
+        }
+    });
+
+    match scope {
+        Some(Some(scope)) => {
+            if scope
+                .contains(browser, frame)
+                .unwrap_or(false)
+            {
+                let _ = install(context);
+            }
+        },
+        Some(None) => {},
+        None => {
+            let _ =
+                frame.send_process_message(ProcessId::Browser, ProcessMessage::new(SYNC_MESSAGE));
+        }
+    }
+}
+
+/// Forgets the binary bridge scope of |browser|.
+pub(crate) fn on_browser_destroyed(browser: &Browser) {
+    if let Ok(identifier) = browser.get_identifier() {
+        RENDERER_SCOPES.with_borrow_mut(|scopes| scopes.remove(&identifier));
+    }
+}
+
+/// Handles binary bridge messages sent to the render process. Returns true if
+/// |message| was handled.
+pub(crate) fn on_renderer_process_message(
+    browser: &Browser,
+    frame: &Frame,
+    source_process: ProcessId,
+    message: &ProcessMessage
+) -> bool {
+    if source_process != ProcessId::Browser {
+        return false;
+    }
+
+    if let Some(channel) = channel_name(message) {
+        let _ = dispatch_in_renderer(browser, frame, &channel, message);
+
+        return true;
+    }
+
+    match message.get_name().ok().as_deref() {
+        Some(ENABLE_MESSAGE) => {
+            let _ = enable_in_renderer(browser, message);
+
+            true
+        },
+        _ => false
+    }
+}
+
+/// Records the scope of the binary bridge attached to |browser| and installs
+/// it in the frames of |browser| in this process that are in scope.
+fn enable_in_renderer(browser: &Browser, message: &ProcessMessage) -> Result<()> {
+    let Some(arguments) = message.get_argument_list()? else {
+        return Ok(());
+    };
+
+    let scope = FunctionScope::from(arguments.get_int(0)?);
+    let identifier = browser.get_identifier()?;
+    let previous = RENDERER_SCOPES.with_borrow_mut(|scopes| scopes.insert(identifier, Some(scope)));
+
+    if previous == Some(Some(scope)) {
+        return Ok(());
+    }
+
+    for frame_identifier in browser.get_frame_identifiers()? {
+        let Some(frame) = browser.get_frame_by_identifier(frame_identifier)? else {
+            continue;
+        };
+
+        if !scope.contains(browser, &frame)? {
+            continue;
+        }
+
+        if let Some(context) = frame.get_v8_context()? {
+            install(&context)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Calls the JavaScript listeners for |channel| in |frame| with data sent by
+/// the browser process.
+fn dispatch_in_renderer(
+    browser: &Browser,
+    frame: &Frame,
+    channel: &str,
+    message: &ProcessMessage
+) -> Result<()> {
+    let identifier = browser.get_identifier()?;
+    let Some(scope) = RENDERER_SCOPES.with_borrow(|scopes| {
+        scopes
+            .get(&identifier)
+            .copied()
+            .flatten()
+    }) else {
+        return Ok(());
+    };
+
+    if !scope.contains(browser, frame)? {
+        return Ok(());
+    }
+
+    // The frame may have navigated away and lost its context.
+    let Some(context) = frame.get_v8_context()? else {
+        return Ok(());
+    };
+
+    // The region may be mapped read-only, so the data is copied into memory
+    // that JavaScript is allowed to write to.
+    let data = read_message(message, <[u8]>::to_vec)?;
+
+    if !context.enter()? {
+        return Err(anyhow!("Failed to enter V8 context.").into());
+    }
+
+    let result = context
+        .get_global()
+        .and_then(|global| global.get_value_by_key(DISPATCH_FUNCTION))
+        .and_then(|dispatch| {
+            dispatch.ok_or_else(|| {
+                anyhow!("The binary bridge is not installed in this context.").into()
+            })
+        })
+        .and_then(|dispatch| {
+            let arguments = [
+                V8Value::new_string(channel)?,
+                V8Value::new_array_buffer(data)?
+            ];
+
+            dispatch.execute_function(None, &arguments)
+        });
+
+    context.exit()?;
+    result.map(|_| ())
+}
+
+/// Defines cefSendBinary, cefOnBinary and cefOffBinary in |context| unless
+/// they already exist.
+fn install(context: &V8Context) -> Result<()> {
+    if !context.enter()? {
+        return Err(anyhow!("Failed to enter V8 context.").into());
+    }
+
+    let result = context
+        .get_global()
+        .and_then(|global| {
+            if global.has_value_by_key(DISPATCH_FUNCTION)? {
+                return Ok(());
+            }
+
+            let send = V8Value::new_function(NATIVE_SEND, V8Handler::new(NativeSend))?;
+
+            global.set_value_by_key(NATIVE_SEND, send, V8PropertyAttribute::DontEnum)?;
+            context
+                .eval(BOOTSTRAP_SCRIPT, "cef-ui://binary-bridge.js", 1)
+                .map(|_| ())
+        });
+
+    context.exit()?;
+    result
+}
+
+/// The native function behind cefSendBinary. Sends data to the browser process.
+struct NativeSend;
+
+impl V8HandlerCallbacks for NativeSend {
+    fn execute(
+        &mut self,
+        _name: CefStr<'_>,
+        _object: V8Value,
+        arguments: Vec<V8Value>
+    ) -> anyhow::Result<Option<V8Value>> {
+        let (Some(channel), Some(data)) = (arguments.first(), arguments.get(1)) else {
+            return Err(anyhow!(
+                "cefSendBinary expects a channel and an ArrayBuffer."
+            ));
+        };
+
+        if !data.is_array_buffer()? {
+            return Err(anyhow!("cefSendBinary expects an ArrayBuffer."));
+        }
+
+        let frame = V8Context::get_current_context()
+            .map(|context| context.get_frame())
+            .transpose()?
+            .flatten()
+            .ok_or_else(|| anyhow!("cefSendBinary must be called from a frame."))?;
+
+        // The bytes are copied straight from the backing store into the shared
+        // memory region.
+        let length = data.get_array_buffer_byte_length()?;
+        let bytes = match length {
+            0 => &[][..],
+            _ => unsafe { from_raw_parts(data.get_array_buffer_data()? as *const u8, length) }
+        };
+
+        frame.send_process_message(
+            ProcessId::Browser,
+            create_message(&channel.get_string_value()?, bytes)?
+        )?;
+
+        Ok(None)
+    }
+}
+
+/// Returns the channel name if |message| is a binary message.
+fn channel_name(message: &ProcessMessage) -> Option<String> {
+    message
+        .get_name()
+        .ok()?
+        .strip_prefix(MESSAGE_PREFIX)
+        .map(|channel| channel.to_string())
+}
+
+/// Creates a shared memory process message that carries |data|.
+fn create_message(channel: &str, data: &[u8]) -> Result<ProcessMessage> {
+    let builder = SharedProcessMessageBuilder::new(
+        &format!("{}{}", MESSAGE_PREFIX, channel),
+        HEADER_SIZE + data.len()
+    );
+    let memory = builder.memory()? as *mut u8;
+
+    if !builder.is_valid()? || memory.is_null() || builder.size()? < HEADER_SIZE + data.len() {
+        return Err(anyhow!("Failed to allocate {} bytes of shared memory.", data.len()).into());
+    }
+
+    unsafe {
+        let length = (data.len() as u64).to_le_bytes();
+
+        copy_nonoverlapping(length.as_ptr(), memory, HEADER_SIZE);
+        copy_nonoverlapping(data.as_ptr(), memory.add(HEADER_SIZE), data.len());
+    }
+
+    builder
+        .build()?
+        .ok_or_else(|| anyhow!("Failed to build the shared memory message.").into())
+}
+
+/// Reads the data of a binary message, passing it to |f| while the shared
+/// memory is mapped.
+fn read_message<T>(message: &ProcessMessage, f: impl FnOnce(&[u8]) -> T) -> Result<T> {
+    let region = message
+        .get_shared_memory_region()?
+        .ok_or_else(|| anyhow!("The binary message has no shared memory region."))?;
+    let size = region.size()?;
+    let memory = region.memory()? as *const u8;
+
+    if !region.is_valid()? || memory.is_null() || size < HEADER_SIZE {
+        return Err(anyhow!("The binary message has an invalid shared memory region.").into());
+    }
+
+    let bytes = unsafe { from_raw_parts(memory, size) };
+    let mut length = [0; HEADER_SIZE];
+
+    length.copy_from_slice(&bytes[..HEADER_SIZE]);
+
+    let length = u64::from_le_bytes(length) as usize;
+
+    match bytes.get(HEADER_SIZE..HEADER_SIZE.saturating_add(length)) {
+        Some(data) => Ok(f(data)),
+        None => Err(anyhow!("The binary message length exceeds its shared memory region.").into())
+    }
+}
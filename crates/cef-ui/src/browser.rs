@@ -1,17 +1,26 @@
+#[cfg(feature = "extensions")]
+use crate::Extension;
 use crate::{
-    free_cef_string, ref_counted_ptr, try_c, CefString, CefStringList, Client, Color, CommandId,
-    CompositionUnderline, DictionaryValue, DragData, DragOperations, Extension, Frame, KeyEvent,
-    MouseButtonType, MouseEvent, NativeWindowHandle, NavigationEntry, NavigationEntryVisitor,
-    PaintElementType, Point, Range, RequestContext, Size, State, TouchEvent, WindowInfo,
-    WindowOpenDisposition, ZoomCommand
+    free_cef_string, ref_counted_ptr, try_c, AcceptFilter, CefString, CefStringList, Client, Color,
+    CommandId, CompositionUnderline, DictionaryValue, DragData, DragOperations, EventFlags,
+    FileDialogMode, Frame, KeyEvent, MouseButtonType, MouseEvent, NativeWindowHandle,
+    NavigationEntry, NavigationEntryVisitor, PaintElementType, Range, RequestContext, Result,
+    RunFileDialogCallback, RuntimeStyle, Size, State, TouchEvent, WindowInfo, WindowOpenDisposition,
+    WindowsKeyCode, ZoomCommand
 };
-use anyhow::Result;
+#[cfg(feature = "devtools")]
+use crate::{DevToolsMessageObserver, Point, Registration};
+use anyhow::anyhow;
+#[cfg(feature = "devtools")]
+use cef_ui_sys::cef_point_t;
 use cef_ui_sys::{
     cef_browser_host_create_browser_sync, cef_browser_host_t, cef_browser_settings_t,
-    cef_browser_t, cef_composition_underline_t, cef_point_t, cef_range_t, cef_string_t
+    cef_browser_t, cef_composition_underline_t, cef_range_t, cef_state_t, cef_string_t
 };
+#[cfg(feature = "devtools")]
+use std::ffi::c_void;
 use std::{
-    ffi::{c_int, c_void},
+    ffi::c_int,
     mem::{size_of, zeroed},
     ptr::{null, null_mut}
 };
@@ -57,9 +66,18 @@ impl BrowserSettings {
     /// the browser cannot generate frames at the requested rate. The minimum
     /// value is 1 and the maximum value is 60 (default 30). This value can also
     /// be changed dynamically via CefBrowserHost::SetWindowlessFrameRate.
-    pub fn windowless_frame_rate(mut self, windowless_frame_rate: i32) -> Self {
+    pub fn windowless_frame_rate(mut self, windowless_frame_rate: i32) -> Result<Self> {
+        if !(1..=60).contains(&windowless_frame_rate) {
+            return Err(anyhow!(
+                "Windowless frame rate must be between 1 and 60, got {}.",
+                windowless_frame_rate
+            )
+            .into());
+        }
+
         self.0.windowless_frame_rate = windowless_frame_rate as c_int;
-        self
+
+        Ok(self)
     }
 
     /// Set standard font family.
@@ -237,9 +255,18 @@ impl BrowserSettings {
     /// CefSettings.background_color value will be used. If the alpha component is
     /// fully transparent for a windowless (off-screen) browser then transparent
     /// painting will be enabled.
-    pub fn background_color(mut self, background_color: &Color) -> Self {
+    pub fn background_color(mut self, background_color: &Color) -> Result<Self> {
+        if background_color.a != 0x00 && background_color.a != 0xFF {
+            return Err(anyhow!(
+                "Background color alpha must be fully opaque or fully transparent, got {:?}.",
+                background_color
+            )
+            .into());
+        }
+
         self.0.background_color = background_color.into();
-        self
+
+        Ok(self)
     }
 
     /// Controls whether the Chrome status bubble will be used. Only supported
@@ -257,17 +284,44 @@ impl BrowserSettings {
         self
     }
 
+    /// Checks for settings that are ignored by |runtime_style|, such as the
+    /// Chrome bubbles with the Alloy runtime.
+    pub fn validate_for(&self, runtime_style: RuntimeStyle) -> Result<()> {
+        let chrome_bubbles = [self.0.chrome_status_bubble, self.0.chrome_zoom_bubble];
+
+        if runtime_style == RuntimeStyle::Alloy
+            && chrome_bubbles
+                .iter()
+                .any(|state| *state != cef_state_t::STATE_DEFAULT)
+        {
+            return Err(
+                anyhow!("The Chrome bubbles are only supported with the Chrome runtime.").into()
+            );
+        }
+
+        Ok(())
+    }
+
     /// Converts to the raw cef type.
     pub fn as_raw(&self) -> &cef_browser_settings_t {
         &self.0
     }
 
-    /// Tries to assign a String to a cef_string_t.
+    /// Tries to assign a String to a cef_string_t, freeing any
+    /// previously assigned value.
     fn set_string(s: &String, cef: &mut cef_string_t) {
+        free_cef_string(cef);
+
         *cef = CefString::new(s.as_str()).into_raw();
     }
 }
 
+impl Default for BrowserSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Drop for BrowserSettings {
     fn drop(&mut self) {
         free_cef_string(&mut self.0.standard_font_family);
@@ -559,30 +613,43 @@ impl BrowserHost {
         })
     }
 
-    // TODO: Fix this!
+    /// Call to run a file chooser dialog. Only a single file chooser dialog may
+    /// be pending at any given time. |mode| represents the type of dialog to
+    /// display. |title| to the title to be used for the dialog and may be None to
+    /// show the default title ("Open" or "Save" depending on the mode).
+    /// |default_file_path| is the path with optional directory and/or file name
+    /// component that will be initially selected in the dialog. |accept_filters|
+    /// are used to restrict the selectable file types. |callback| will be
+    /// executed after the dialog is dismissed or immediately if another dialog is
+    /// already pending. The dialog will be initiated asynchronously on the UI
+    /// thread.
+    pub fn run_file_dialog(
+        &self,
+        mode: FileDialogMode,
+        title: Option<&str>,
+        default_file_path: Option<&str>,
+        accept_filters: &[AcceptFilter],
+        callback: RunFileDialogCallback
+    ) -> Result<()> {
+        try_c!(self, run_file_dialog, {
+            let title = title.map(CefString::new);
+            let default_file_path = default_file_path.map(CefString::new);
+            let mut accept_filters = CefStringList::from(accept_filters);
 
-    // ///
-    // /// Call to run a file chooser dialog. Only a single file chooser dialog may
-    // /// be pending at any given time. |mode| represents the type of dialog to
-    // /// display. |title| to the title to be used for the dialog and may be NULL to
-    // /// show the default title ("Open" or "Save" depending on the mode).
-    // /// |default_file_path| is the path with optional directory and/or file name
-    // /// component that will be initially selected in the dialog. |accept_filters|
-    // /// are used to restrict the selectable file types and may any combination of
-    // /// (a) valid lower-cased MIME types (e.g. "text/*" or "image/*"), (b)
-    // /// individual file extensions (e.g. ".txt" or ".png"), or (c) combined
-    // /// description and file extension delimited using "|" and ";" (e.g. "Image
-    // /// Types|.png;.gif;.jpg"). |callback| will be executed after the dialog is
-    // /// dismissed or immediately if another dialog is already pending. The dialog
-    // /// will be initiated asynchronously on the UI thread.
-    // ///
-    // void(CEF_CALLBACK* run_file_dialog)(
-    // struct _cef_browser_host_t* self,
-    // cef_file_dialog_mode_t mode,
-    // const cef_string_t* title,
-    // const cef_string_t* default_file_path,
-    // cef_string_list_t accept_filters,
-    // struct _cef_run_file_dialog_callback_t* callback);
+            Ok(run_file_dialog(
+                self.as_ptr(),
+                mode.into(),
+                title
+                    .as_ref()
+                    .map_or(null(), |s| s.as_ptr()),
+                default_file_path
+                    .as_ref()
+                    .map_or(null(), |s| s.as_ptr()),
+                accept_filters.as_mut_ptr(),
+                callback.into_raw()
+            ))
+        })
+    }
 
     /// Download the file at |url| using cef_download_handler_t.
     pub fn start_download(&self, url: &str) -> Result<()> {
@@ -677,6 +744,7 @@ impl BrowserHost {
     /// |inspect_element_at| is non-NULL then the element at the specified (x,y)
     /// location will be inspected. The |windowInfo| parameter will be ignored if
     /// this browser is wrapped in a cef_browser_view_t.
+    #[cfg(feature = "devtools")]
     pub fn show_dev_tools(
         &self,
         window_info: &WindowInfo,
@@ -702,6 +770,7 @@ impl BrowserHost {
     }
 
     /// Explicitly close the associated DevTools browser, if any.
+    #[cfg(feature = "devtools")]
     pub fn close_dev_tools(&self) -> Result<()> {
         try_c!(self, close_dev_tools, {
             Ok(close_dev_tools(self.as_ptr()))
@@ -710,6 +779,7 @@ impl BrowserHost {
 
     /// Returns true (1) if this browser currently has an associated DevTools
     /// browser. Must be called on the browser process UI thread.
+    #[cfg(feature = "devtools")]
     pub fn has_dev_tools(&self) -> Result<bool> {
         try_c!(self, has_dev_tools, {
             Ok(has_dev_tools(self.as_ptr()) != 0)
@@ -747,6 +817,7 @@ impl BrowserHost {
     /// Communication with the DevTools front-end (when displayed) can be logged
     /// for development purposes by passing the `--devtools-protocol-log-
     /// file=<path>` command-line flag.
+    #[cfg(feature = "devtools")]
     pub fn send_dev_tools_message(&self, message: &[u8]) -> Result<bool> {
         try_c!(self, send_dev_tools_message, {
             Ok(send_dev_tools_message(
@@ -768,12 +839,13 @@ impl BrowserHost {
     /// and the message was successfully submitted for validation, otherwise 0.
     /// See the SendDevToolsMessage documentation for additional usage
     /// information.
+    #[cfg(feature = "devtools")]
     pub fn execute_dev_tools_method(
         &self,
         message_id: i32,
         method: &str,
         params: Option<DictionaryValue>
-    ) -> Result<bool> {
+    ) -> Result<i32> {
         try_c!(self, execute_dev_tools_method, {
             let method = CefString::new(method);
 
@@ -784,21 +856,26 @@ impl BrowserHost {
                 params
                     .map(|params| params.into_raw())
                     .unwrap_or(null_mut())
-            ) != 0)
+            ))
         })
     }
 
-    // TODO: Fix this!
-
-    // ///
-    // /// Add an observer for DevTools protocol messages (function results and
-    // /// events). The observer will remain registered until the returned
-    // /// Registration object is destroyed. See the SendDevToolsMessage
-    // /// documentation for additional usage information.
-    // ///
-    // struct _cef_registration_t*(CEF_CALLBACK* add_dev_tools_message_observer)(
-    // struct _cef_browser_host_t* self,
-    // struct _cef_dev_tools_message_observer_t* observer);
+    /// Add an observer for DevTools protocol messages (function results and
+    /// events). The observer will remain registered until the returned
+    /// Registration object is destroyed. See the SendDevToolsMessage
+    /// documentation for additional usage information.
+    #[cfg(feature = "devtools")]
+    pub fn add_dev_tools_message_observer(
+        &self,
+        observer: DevToolsMessageObserver
+    ) -> Result<Option<Registration>> {
+        try_c!(self, add_dev_tools_message_observer, {
+            Ok(Registration::from_ptr(add_dev_tools_message_observer(
+                self.as_ptr(),
+                observer.into_raw()
+            )))
+        })
+    }
 
     /// Retrieve a snapshot of current navigation entries as values sent to the
     /// specified visitor. If |current_only| is true (1) only the current
@@ -897,6 +974,25 @@ impl BrowserHost {
         })
     }
 
+    /// Send a complete key press to the browser: a key down event, a character
+    /// event for each UTF-16 code unit of |text| and a key up event. |text| is
+    /// usually the character the key produces, or empty for keys such as
+    /// arrows and function keys.
+    pub fn send_key_press(
+        &self,
+        windows_key_code: WindowsKeyCode,
+        modifiers: EventFlags,
+        text: &str
+    ) -> Result<()> {
+        self.send_key_event(KeyEvent::key_down(windows_key_code).modifiers(modifiers))?;
+
+        for character in text.encode_utf16() {
+            self.send_key_event(KeyEvent::char(character).modifiers(modifiers))?;
+        }
+
+        self.send_key_event(KeyEvent::key_up(windows_key_code).modifiers(modifiers))
+    }
+
     /// Send a mouse click event to the browser. The |x| and |y| coordinates are
     /// relative to the upper-left corner of the view.
     pub fn send_mouse_click_event(
@@ -917,6 +1013,22 @@ impl BrowserHost {
         })
     }
 
+    /// Send a complete click of |mouse_button| to the browser: a mouse down event
+    /// with the button held followed by a mouse up event. |click_count| is 2
+    /// for the second click of a double click and so on; see ClickCounter.
+    pub fn send_mouse_click(
+        &self,
+        event: &MouseEvent,
+        mouse_button: MouseButtonType,
+        click_count: i32
+    ) -> Result<()> {
+        let pressed = event.button(mouse_button);
+        let released = event.modifiers(event.modifiers - mouse_button.event_flag());
+
+        self.send_mouse_click_event(&pressed, mouse_button, false, click_count)?;
+        self.send_mouse_click_event(&released, mouse_button, true, click_count)
+    }
+
     /// Send a mouse move event to the browser. The |x| and |y| coordinates are
     /// relative to the upper-left corner of the view.
     pub fn send_mouse_move_event(&self, event: &MouseEvent, mouse_leave: bool) -> Result<()> {
@@ -1259,6 +1371,7 @@ impl BrowserHost {
     /// Enable notifications of auto resize via
     /// cef_display_handler_t::OnAutoResize. Notifications are disabled by
     /// default. |min_size| and |max_size| define the range of allowed sizes.
+    /// See DisplayHandlerCallbacks::on_auto_resize.
     pub fn set_auto_resize_enabled(
         &self,
         enabled: bool,
@@ -1277,6 +1390,7 @@ impl BrowserHost {
 
     /// Returns the extension hosted in this browser or NULL if no extension is
     /// hosted. See cef_request_context_t::LoadExtension for details.
+    #[cfg(feature = "extensions")]
     pub fn get_extension(&self) -> Result<Option<Extension>> {
         try_c!(self, get_extension, {
             Ok(Extension::from_ptr(get_extension(self.as_ptr())))
@@ -1286,6 +1400,7 @@ impl BrowserHost {
     /// Returns true (1) if this browser is hosting an extension background
     /// script. Background hosts do not have a window and are not displayable. See
     /// cef_request_context_t::LoadExtension for details.
+    #[cfg(feature = "extensions")]
     pub fn is_background_host(&self) -> Result<bool> {
         try_c!(self, is_background_host, {
             Ok(is_background_host(self.as_ptr()) != 0)
@@ -1385,6 +1500,7 @@ impl BrowserHost {
     /// |extra_info| parameter provides an opportunity to specify extra information
     /// specific to the created browser that will be passed to
     /// cef_render_process_handler_t::on_browser_created() in the render process.
+    /// Returns None if the browser could not be created.
     pub fn create_browser_sync(
         window_info: &WindowInfo,
         client: Client,
@@ -1392,7 +1508,7 @@ impl BrowserHost {
         settings: &BrowserSettings,
         extra_info: Option<DictionaryValue>,
         request_context: Option<RequestContext>
-    ) -> Browser {
+    ) -> Option<Browser> {
         unsafe {
             let url = CefString::new(url);
             let extra_info = extra_info
@@ -1402,7 +1518,7 @@ impl BrowserHost {
                 .map(|request_context| request_context.into_raw())
                 .unwrap_or_else(null_mut);
 
-            Browser::from_ptr_unchecked(cef_browser_host_create_browser_sync(
+            Browser::from_ptr(cef_browser_host_create_browser_sync(
                 window_info.as_raw(),
                 client.into_raw(),
                 url.as_ptr(),
@@ -1,5 +1,6 @@
 use crate::{
-    ref_counted_ptr, CefString, Client, CommandLine, RefCountedPtr, Value, Wrappable, Wrapped
+    callbacks_builder, debug_assert_currently_on, ref_counted_ptr, unwind::catch_unwind_or,
+    CefString, Client, CommandLine, RefCountedPtr, ThreadId, Value, Wrappable, Wrapped
 };
 use cef_ui_sys::{
     cef_browser_process_handler_t, cef_client_t, cef_command_line_t, cef_preference_registrar_t,
@@ -114,20 +115,21 @@ pub trait BrowserProcessHandlerCallbacks: Send + Sync + 'static {
     /// on the browser process UI thread.
     fn on_register_custom_preferences(
         &mut self,
-        preferences_type: PreferencesType,
-        registrar: &mut PreferenceRegistrar
-    );
+        _preferences_type: PreferencesType,
+        _registrar: &mut PreferenceRegistrar
+    ) {
+    }
 
     /// Called on the browser process UI thread immediately after the CEF context
     /// has been initialized.
-    fn on_context_initialized(&mut self);
+    fn on_context_initialized(&mut self) {}
 
     /// Called before a child process is launched. Will be called on the browser
     /// process UI thread when launching a render process and on the browser
     /// process IO thread when launching a GPU process. Provides an opportunity to
     /// modify the child process command line. Do not keep a reference to
     /// |command_line| outside of this function.
-    fn on_before_child_process_launch(&mut self, command_line: CommandLine);
+    fn on_before_child_process_launch(&mut self, _command_line: CommandLine) {}
 
     /// Implement this function to provide app-specific behavior when an already
     /// running app is relaunched with the same CefSettings.root_cache_path value.
@@ -144,12 +146,17 @@ pub trait BrowserProcessHandlerCallbacks: Send + Sync + 'static {
     /// therefore check the cef_initialize() return value for early exit before
     /// proceeding.
     ///
+    /// Use AppRelaunch::new to copy the URLs, paths and switches of the new
+    /// instance, e.g. to open them in a new tab of an existing window.
+    ///
     /// This function will be called on the browser process UI thread.
     fn on_already_running_app_relaunch(
         &mut self,
-        command_line: CommandLine,
-        current_directory: &str
-    ) -> bool;
+        _command_line: CommandLine,
+        _current_directory: &str
+    ) -> bool {
+        false
+    }
 
     /// Called from any thread when work has been scheduled for the browser
     /// process main (UI) thread. This callback is used in combination with
@@ -161,15 +168,18 @@ pub trait BrowserProcessHandlerCallbacks: Send + Sync + 'static {
     /// |delay_ms| is the requested delay in milliseconds. If |delay_ms| is <= 0
     /// then the call should happen reasonably soon. If |delay_ms| is > 0 then the
     /// call should be scheduled to happen after the specified delay and any
-    /// currently pending scheduled call should be cancelled.
-    fn on_schedule_message_pump_work(&mut self, delay_ms: i64);
+    /// currently pending scheduled call should be cancelled. See MessagePump for
+    /// a ready-made adapter that can be driven from an existing event loop.
+    fn on_schedule_message_pump_work(&mut self, _delay_ms: i64) {}
 
     /// Return the default client for use with a newly created browser window. If
     /// null is returned the browser will be unmanaged (no callbacks will be
     /// executed for that browser) and application shutdown will be blocked until
     /// the browser window is closed manually. This function is currently only
     /// used with the chrome runtime.
-    fn get_default_client(&mut self) -> Option<Client>;
+    fn get_default_client(&mut self) -> Option<Client> {
+        None
+    }
 }
 
 // Structure used to implement browser process callbacks. The functions of this
@@ -183,12 +193,31 @@ impl BrowserProcessHandler {
     }
 }
 
+callbacks_builder! {
+    /// Builds a BrowserProcessHandler from closures. Callbacks that are not set
+    /// fall back to the default BrowserProcessHandlerCallbacks behavior.
+    BrowserProcessHandlerBuilder: BrowserProcessHandlerCallbacks => BrowserProcessHandler {
+        fn on_register_custom_preferences(
+            preferences_type: PreferencesType,
+            registrar: &mut PreferenceRegistrar
+        );
+        fn on_context_initialized();
+        fn on_before_child_process_launch(command_line: CommandLine);
+        fn on_already_running_app_relaunch(
+            command_line: CommandLine,
+            current_directory: &str
+        ) -> bool = false;
+        fn on_schedule_message_pump_work(delay_ms: i64);
+        fn get_default_client() -> Option<Client> = None;
+    }
+}
+
 /// Translates CEF -> Rust callbacks.
-struct BrowserProcessHandlerWrapper(Box<dyn BrowserProcessHandlerCallbacks>);
+struct BrowserProcessHandlerWrapper<C: BrowserProcessHandlerCallbacks>(C);
 
-impl BrowserProcessHandlerWrapper {
-    pub fn new<C: BrowserProcessHandlerCallbacks>(delegate: C) -> Self {
-        Self(Box::new(delegate))
+impl<C: BrowserProcessHandlerCallbacks> BrowserProcessHandlerWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
     }
 
     /// Provides an opportunity to register custom preferences prior to global and
@@ -216,19 +245,35 @@ impl BrowserProcessHandlerWrapper {
         preferences_type: cef_preferences_type_t,
         registrar: *mut cef_preference_registrar_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let mut registrar = PreferenceRegistrar::from_ptr_unchecked(registrar);
-
-        this.0
-            .on_register_custom_preferences(preferences_type.into(), &mut registrar);
+        catch_unwind_or(
+            "BrowserProcessHandlerWrapper::c_on_register_custom_preferences",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let mut registrar = PreferenceRegistrar::from_ptr_unchecked(registrar);
+
+                this.0
+                    .on_register_custom_preferences(preferences_type.into(), &mut registrar);
+            }
+        )
     }
 
     /// Called on the browser process UI thread immediately after the CEF context
     /// has been initialized.
     unsafe extern "C" fn c_on_context_initialized(this: *mut cef_browser_process_handler_t) {
-        let this: &mut Self = Wrapped::wrappable(this);
+        catch_unwind_or(
+            "BrowserProcessHandlerWrapper::c_on_context_initialized",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
 
-        this.0.on_context_initialized();
+                this.0.on_context_initialized();
+            }
+        )
     }
 
     /// Called before a child process is launched. Will be called on the browser
@@ -240,11 +285,17 @@ impl BrowserProcessHandlerWrapper {
         this: *mut cef_browser_process_handler_t,
         command_line: *mut cef_command_line_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let command_line = CommandLine::from_ptr_unchecked(command_line);
-
-        this.0
-            .on_before_child_process_launch(command_line);
+        catch_unwind_or(
+            "BrowserProcessHandlerWrapper::c_on_before_child_process_launch",
+            (),
+            || {
+                let this: &mut Self = Wrapped::wrappable(this);
+                let command_line = CommandLine::from_ptr_unchecked(command_line);
+
+                this.0
+                    .on_before_child_process_launch(command_line);
+            }
+        )
     }
 
     /// Implement this function to provide app-specific behavior when an already
@@ -268,12 +319,22 @@ impl BrowserProcessHandlerWrapper {
         command_line: *mut cef_command_line_t,
         current_directory: *const cef_string_t
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let command_line = CommandLine::from_ptr_unchecked(command_line);
-        let current_directory: String = CefString::from_ptr_unchecked(current_directory).into();
-
-        this.0
-            .on_already_running_app_relaunch(command_line, &current_directory) as c_int
+        catch_unwind_or(
+            "BrowserProcessHandlerWrapper::c_on_already_running_app_relaunch",
+            0,
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let command_line = CommandLine::from_ptr_unchecked(command_line);
+                let current_directory: String =
+                    CefString::from_ptr_unchecked(current_directory).into();
+
+                this.0
+                    .on_already_running_app_relaunch(command_line, &current_directory)
+                    as c_int
+            }
+        )
     }
 
     /// Called from any thread when work has been scheduled for the browser
@@ -291,10 +352,16 @@ impl BrowserProcessHandlerWrapper {
         this: *mut cef_browser_process_handler_t,
         delay_ms: i64
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-
-        this.0
-            .on_schedule_message_pump_work(delay_ms);
+        catch_unwind_or(
+            "BrowserProcessHandlerWrapper::c_on_schedule_message_pump_work",
+            (),
+            || {
+                let this: &mut Self = Wrapped::wrappable(this);
+
+                this.0
+                    .on_schedule_message_pump_work(delay_ms);
+            }
+        )
     }
 
     /// Return the default client for use with a newly created browser window. If
@@ -305,16 +372,22 @@ impl BrowserProcessHandlerWrapper {
     unsafe extern "C" fn c_get_default_client(
         this: *mut cef_browser_process_handler_t
     ) -> *mut cef_client_t {
-        let this: &mut Self = Wrapped::wrappable(this);
-
-        this.0
-            .get_default_client()
-            .map(|client| client.into_raw())
-            .unwrap_or_else(null_mut)
+        catch_unwind_or(
+            "BrowserProcessHandlerWrapper::c_get_default_client",
+            null_mut(),
+            || {
+                let this: &mut Self = Wrapped::wrappable(this);
+
+                this.0
+                    .get_default_client()
+                    .map(|client| client.into_raw())
+                    .unwrap_or_else(null_mut)
+            }
+        )
     }
 }
 
-impl Wrappable for BrowserProcessHandlerWrapper {
+impl<C: BrowserProcessHandlerCallbacks> Wrappable for BrowserProcessHandlerWrapper<C> {
     type Cef = cef_browser_process_handler_t;
 
     /// Converts this to a smart pointer.
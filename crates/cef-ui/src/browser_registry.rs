@@ -0,0 +1,183 @@
+use crate::{Browser, BrowserHost, Result};
+use anyhow::anyhow;
+use parking_lot::Mutex;
+use std::{
+    any::{Any, TypeId},
+    collections::{BTreeMap, HashMap},
+    sync::Arc
+};
+
+type UserData = Arc<dyn Any + Send + Sync + 'static>;
+
+/// A live browser and the state attached to it.
+struct RegisteredBrowser {
+    browser:   Browser,
+    user_data: HashMap<TypeId, UserData>,
+    closing:   bool
+}
+
+impl RegisteredBrowser {
+    fn new(browser: Browser) -> Self {
+        Self {
+            browser,
+            user_data: HashMap::new(),
+            closing: false
+        }
+    }
+}
+
+/// The outcome of asking a browser to close.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CloseStatus {
+    /// The close has been requested and the 'onbeforeunload' handler, if any,
+    /// may still prompt the user and cancel it. The top-level window must stay
+    /// open; the window will be asked to close again once the close proceeds.
+    Pending,
+
+    /// The browser has closed and the top-level window may be destroyed.
+    Closed
+}
+
+impl CloseStatus {
+    /// Returns true if the top-level window that hosts the browser may close.
+    pub fn can_close_window(&self) -> bool {
+        match self {
+            CloseStatus::Pending => false,
+            CloseStatus::Closed => true
+        }
+    }
+}
+
+/// Live browsers in the browser process, by browser identifier.
+static BROWSERS: Mutex<BTreeMap<i32, RegisteredBrowser>> = Mutex::new(BTreeMap::new());
+
+impl Browser {
+    /// Returns the live browser with |identifier|, e.g. to route a process
+    /// message or DevTools event back to the browser it belongs to. Browsers
+    /// are registered when they are created and removed when they are closed,
+    /// provided the client has a LifeSpanHandler.
+    pub fn from_identifier(identifier: i32) -> Option<Browser> {
+        BROWSERS
+            .lock()
+            .get(&identifier)
+            .map(|registered| registered.browser.clone())
+    }
+
+    /// Returns every live browser, ordered by identifier.
+    pub fn get_all() -> Vec<Browser> {
+        BROWSERS
+            .lock()
+            .values()
+            .map(|registered| registered.browser.clone())
+            .collect()
+    }
+
+    /// Returns true if the close of this browser was allowed by its
+    /// 'onbeforeunload' handler (or forced) and LifeSpanHandlerCallbacks::do_close
+    /// has been called. From then on the close can no longer be canceled, so a
+    /// top-level window that received a close notification may proceed.
+    pub fn is_ready_to_be_closed(&self) -> Result<bool> {
+        let closing = BROWSERS
+            .lock()
+            .get(&self.get_identifier()?)
+            .map_or(false, |registered| registered.closing);
+
+        Ok(closing)
+    }
+
+    /// Attaches |data| to this browser, replacing any previous value of the
+    /// same type. The data is dropped when the browser is closed. Returns an
+    /// error if the browser is not registered, i.e. if it has already closed
+    /// or the client has no LifeSpanHandler.
+    pub fn set_user_data<T: Any + Send + Sync + 'static>(&self, data: T) -> Result<()> {
+        let identifier = self.get_identifier()?;
+
+        match BROWSERS.lock().get_mut(&identifier) {
+            Some(registered) => {
+                registered
+                    .user_data
+                    .insert(TypeId::of::<T>(), Arc::new(data));
+
+                Ok(())
+            },
+            None => Err(anyhow!("Browser {} is not registered.", identifier).into())
+        }
+    }
+
+    /// Returns the data of type T attached to this browser, if any.
+    pub fn get_user_data<T: Any + Send + Sync + 'static>(&self) -> Result<Option<Arc<T>>> {
+        let data = BROWSERS
+            .lock()
+            .get(&self.get_identifier()?)
+            .and_then(|registered| {
+                registered
+                    .user_data
+                    .get(&TypeId::of::<T>())
+                    .cloned()
+            });
+
+        Ok(data.and_then(|data| data.downcast::<T>().ok()))
+    }
+
+    /// Detaches the data of type T from this browser and returns it.
+    pub fn take_user_data<T: Any + Send + Sync + 'static>(&self) -> Result<Option<Arc<T>>> {
+        let data = BROWSERS
+            .lock()
+            .get_mut(&self.get_identifier()?)
+            .and_then(|registered| {
+                registered
+                    .user_data
+                    .remove(&TypeId::of::<T>())
+            });
+
+        Ok(data.and_then(|data| data.downcast::<T>().ok()))
+    }
+}
+
+impl BrowserHost {
+    /// Call this from the close handler of the top-level window that hosts the
+    /// browser (e.g. WindowDelegateCallbacks::can_close, WM_CLOSE or a winit
+    /// CloseRequested event) and only let the window close if the result is
+    /// CloseStatus::Closed. The first call starts the close and fires the
+    /// 'onbeforeunload' event, which may prompt the user; if the user approves,
+    /// the window receives another close notification and this then returns
+    /// CloseStatus::Closed. Must be called on the browser process UI thread.
+    pub fn request_close(&self) -> Result<CloseStatus> {
+        match self.try_close_browser()? {
+            true => Ok(CloseStatus::Closed),
+            false => Ok(CloseStatus::Pending)
+        }
+    }
+}
+
+/// Adds |browser| to the registry.
+pub(crate) fn register_browser(browser: &Browser) {
+    if let Ok(identifier) = browser.get_identifier() {
+        BROWSERS
+            .lock()
+            .entry(identifier)
+            .or_insert_with(|| RegisteredBrowser::new(browser.clone()));
+    }
+}
+
+/// Records that do_close has been called for |browser|.
+pub(crate) fn mark_browser_closing(browser: &Browser) {
+    if let Ok(identifier) = browser.get_identifier() {
+        if let Some(registered) = BROWSERS.lock().get_mut(&identifier) {
+            registered.closing = true;
+        }
+    }
+}
+
+/// Removes |browser| and its user data from the registry.
+pub(crate) fn unregister_browser(browser: &Browser) {
+    let Ok(identifier) = browser.get_identifier() else {
+        return;
+    };
+
+    // The user data is dropped without the lock, as its destructors may use
+    // the registry.
+    let registered = BROWSERS.lock().remove(&identifier);
+
+    drop(registered);
+}
@@ -0,0 +1,167 @@
+use crate::{BrowserHost, CompletionCallback, DeleteCookiesCallback, RequestContext, Result};
+use anyhow::anyhow;
+use bitflags::bitflags;
+use parking_lot::Mutex;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc
+};
+
+bitflags! {
+    /// The kinds of browsing data cleared by RequestContext::clear_browsing_data.
+    #[allow(non_upper_case_globals)]
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct BrowsingDataTypes: u32 {
+        /// All cookies, via the context's cookie manager.
+        const Cookies = 1 << 0;
+
+        /// The HTTP cache. Requires a browser that uses the request context.
+        const HttpCache = 1 << 1;
+
+        /// HTTP authentication credentials added by GetAuthCredentials.
+        const HttpAuthCredentials = 1 << 2;
+
+        /// Certificate exceptions added by on_certificate_error.
+        const CertificateExceptions = 1 << 3;
+
+        /// All active and idle connections.
+        const Connections = 1 << 4;
+
+        /// Authentication state kept by the network stack: HTTP authentication
+        /// credentials, certificate exceptions and the connections that may
+        /// still carry them.
+        const NetworkState = Self::HttpAuthCredentials.bits()
+            | Self::CertificateExceptions.bits()
+            | Self::Connections.bits();
+
+        /// Everything above.
+        const All = Self::Cookies.bits()
+            | Self::HttpCache.bits()
+            | Self::HttpAuthCredentials.bits()
+            | Self::CertificateExceptions.bits()
+            | Self::Connections.bits();
+    }
+}
+
+/// Runs a function once every pending operation has completed.
+struct Completion {
+    remaining: AtomicUsize,
+    f:         Mutex<Option<Box<dyn FnOnce() + Send + 'static>>>
+}
+
+impl Completion {
+    /// The returned completion waits for one extra arrival, so that it can't
+    /// finish while operations are still being started.
+    fn new(f: impl FnOnce() + Send + 'static) -> Arc<Self> {
+        Arc::new(Self {
+            remaining: AtomicUsize::new(1),
+            f:         Mutex::new(Some(Box::new(f)))
+        })
+    }
+
+    /// Registers a pending operation and returns the function that completes it.
+    fn add(self: &Arc<Self>) -> impl FnOnce() + Send + 'static {
+        let this = self.clone();
+
+        self.remaining
+            .fetch_add(1, Ordering::AcqRel);
+
+        move || this.arrive()
+    }
+
+    /// Completes a pending operation.
+    fn arrive(&self) {
+        if self
+            .remaining
+            .fetch_sub(1, Ordering::AcqRel)
+            == 1
+        {
+            if let Some(f) = self.f.lock().take() {
+                f();
+            }
+        }
+    }
+}
+
+impl RequestContext {
+    /// Clears the browsing data selected by |data_types| and calls |f| on the UI
+    /// thread once everything has been cleared. Clearing the HTTP cache uses the
+    /// DevTools protocol, so |browser| must be a browser that uses this request
+    /// context; it is unused otherwise. The HTTP cache is cleared asynchronously
+    /// and is not waited for.
+    pub fn clear_browsing_data(
+        &self,
+        data_types: BrowsingDataTypes,
+        browser: Option<&BrowserHost>,
+        f: impl FnOnce() + Send + 'static
+    ) -> Result<()> {
+        if data_types.contains(BrowsingDataTypes::HttpCache) && browser.is_none() {
+            return Err(anyhow!("Clearing the HTTP cache requires a browser.").into());
+        }
+
+        let completion = Completion::new(f);
+
+        if data_types.contains(BrowsingDataTypes::Cookies) {
+            let cookie_manager = self
+                .get_cookie_manager(None)?
+                .ok_or_else(|| anyhow!("The request context has no cookie manager."))?;
+            let done = completion.add();
+
+            if !cookie_manager.delete_cookies(
+                None,
+                None,
+                Some(DeleteCookiesCallback::new(move |_| done()))
+            )? {
+                return Err(anyhow!("Failed to delete cookies.").into());
+            }
+        }
+
+        if data_types.contains(BrowsingDataTypes::HttpCache) {
+            if let Some(browser) = browser {
+                clear_http_cache(browser)?;
+            }
+        }
+
+        if data_types.contains(BrowsingDataTypes::HttpAuthCredentials) {
+            self.clear_http_auth_credentials(Some(CompletionCallback::new(completion.add())))?;
+        }
+
+        if data_types.contains(BrowsingDataTypes::CertificateExceptions) {
+            self.clear_certificate_exceptions(Some(CompletionCallback::new(completion.add())))?;
+        }
+
+        if data_types.contains(BrowsingDataTypes::Connections) {
+            self.close_all_connections(Some(CompletionCallback::new(completion.add())))?;
+        }
+
+        completion.arrive();
+
+        Ok(())
+    }
+}
+
+impl RequestContext {
+    /// Forgets HTTP authentication credentials and certificate exceptions and
+    /// closes all connections, then calls |f| on the UI thread. Use this to
+    /// sign out of every site that relied on HTTP authentication or client
+    /// certificates, or to start over after a network change; connections are
+    /// closed so that the cleared state is not reused by an open socket.
+    pub fn reset_network_state(&self, f: impl FnOnce() + Send + 'static) -> Result<()> {
+        self.clear_browsing_data(BrowsingDataTypes::NetworkState, None, f)
+    }
+}
+
+/// Clears the HTTP cache of the request context used by |browser|.
+#[cfg(feature = "devtools")]
+fn clear_http_cache(browser: &BrowserHost) -> Result<()> {
+    match browser.execute_dev_tools_method(0, "Network.clearBrowserCache", None)? {
+        0 => Err(anyhow!("Failed to clear the HTTP cache.").into()),
+        _ => Ok(())
+    }
+}
+
+/// Clears the HTTP cache of the request context used by |browser|.
+#[cfg(not(feature = "devtools"))]
+fn clear_http_cache(_browser: &BrowserHost) -> Result<()> {
+    Err(anyhow!("Clearing the HTTP cache requires the devtools feature.").into())
+}
@@ -1,5 +1,7 @@
-use crate::{ref_counted_ptr, try_c, CefString, RefCountedPtr, Wrappable, Wrapped};
-use anyhow::Result;
+use crate::{
+    ref_counted_ptr, try_c, unwind::catch_unwind_or, CefString, RefCountedPtr, Result, Wrappable,
+    Wrapped
+};
 use cef_ui_sys::{cef_auth_callback_t, cef_callback_t, cef_completion_callback_t};
 use parking_lot::Mutex;
 use std::mem::zeroed;
@@ -39,11 +41,13 @@ impl CompletionCallbackWrapper {
 
     /// Method that will be called once the task is complete.
     unsafe extern "C" fn c_on_complete(this: *mut cef_completion_callback_t) {
-        let this: &Self = Wrapped::wrappable(this);
+        catch_unwind_or("CompletionCallbackWrapper::c_on_complete", (), || {
+            let this: &Self = Wrapped::wrappable(this);
 
-        if let Some(f) = this.0.lock().take() {
-            f();
-        }
+            if let Some(f) = this.0.lock().take() {
+                f();
+            }
+        })
     }
 }
 
@@ -1,24 +1,32 @@
+#[cfg(feature = "v8")]
+use crate::{binary_bridge, event_bus, function_binding};
+#[cfg(feature = "osr")]
+use crate::RenderHandler;
 use crate::{
-    keyboard_handler::KeyboardHandler, ref_counted_ptr, ContextMenuHandler, LifeSpanHandler,
-    RefCountedPtr, RenderHandler, Wrappable, Wrapped
+    callbacks_builder, keyboard_handler::KeyboardHandler, ref_counted_ptr, unwind::catch_unwind_or,
+    AudioHandler, Browser, ContextMenuHandler, DialogHandler, DisplayHandler, DownloadHandler,
+    DragHandler, Frame, JsDialogHandler, LifeSpanHandler, LoadHandler, PermissionHandler, ProcessId,
+    ProcessMessage, RefCountedPtr, RequestHandler, Wrappable, Wrapped
 };
+#[cfg(feature = "osr")]
+use cef_ui_sys::cef_render_handler_t;
 use cef_ui_sys::{
-    cef_audio_handler_t, cef_browser_t, cef_client_t, cef_command_handler_t,
-    cef_context_menu_handler_t, cef_dialog_handler_t, cef_display_handler_t,
-    cef_download_handler_t, cef_drag_handler_t, cef_find_handler_t, cef_focus_handler_t,
-    cef_frame_handler_t, cef_frame_t, cef_jsdialog_handler_t, cef_keyboard_handler_t,
-    cef_life_span_handler_t, cef_load_handler_t, cef_permission_handler_t, cef_print_handler_t,
-    cef_process_id_t, cef_process_message_t, cef_render_handler_t, cef_request_handler_t
+    cef_audio_handler_t, cef_browser_t, cef_client_t, cef_context_menu_handler_t,
+    cef_dialog_handler_t, cef_display_handler_t, cef_download_handler_t, cef_drag_handler_t,
+    cef_frame_t, cef_jsdialog_handler_t, cef_keyboard_handler_t, cef_life_span_handler_t,
+    cef_load_handler_t, cef_permission_handler_t, cef_process_id_t, cef_process_message_t,
+    cef_request_handler_t
 };
 use std::{ffi::c_int, mem::zeroed, ptr::null_mut};
 
 /// Implement this structure to provide handler implementations.
 pub trait ClientCallbacks: Send + Sync + 'static {
-    // TODO: Fix this!
+    /// Return the handler for audio rendering events.
+    fn get_audio_handler(&mut self) -> Option<AudioHandler> {
+        None
+    }
 
-    // /// Return the handler for audio rendering events.
-    // struct _cef_audio_handler_t*(CEF_CALLBACK* get_audio_handler)(
-    // struct _cef_client_t* self);
+    // TODO: Fix this!
 
     // /// Return the handler for commands. If no handler is provided the default
     // /// implementation will be used.
@@ -27,25 +35,31 @@ pub trait ClientCallbacks: Send + Sync + 'static {
 
     /// Return the handler for context menus. If no handler is provided the
     /// default implementation will be used.
-    fn get_context_menu_handler(&mut self) -> Option<ContextMenuHandler>;
+    fn get_context_menu_handler(&mut self) -> Option<ContextMenuHandler> {
+        None
+    }
 
-    // /// Return the handler for dialogs. If no handler is provided the default
-    // /// implementation will be used.
-    // struct _cef_dialog_handler_t*(CEF_CALLBACK* get_dialog_handler)(
-    // struct _cef_client_t* self);
+    /// Return the handler for dialogs. If no handler is provided the default
+    /// implementation will be used.
+    fn get_dialog_handler(&mut self) -> Option<DialogHandler> {
+        None
+    }
 
-    // /// Return the handler for browser display state events.
-    // struct _cef_display_handler_t*(CEF_CALLBACK* get_display_handler)(
-    // struct _cef_client_t* self);
+    /// Return the handler for browser display state events.
+    fn get_display_handler(&mut self) -> Option<DisplayHandler> {
+        None
+    }
 
-    // /// Return the handler for download events. If no handler is returned
-    // /// downloads will not be allowed.
-    // struct _cef_download_handler_t*(CEF_CALLBACK* get_download_handler)(
-    // struct _cef_client_t* self);
+    /// Return the handler for download events. If no handler is returned
+    /// downloads will not be allowed.
+    fn get_download_handler(&mut self) -> Option<DownloadHandler> {
+        None
+    }
 
-    // /// Return the handler for drag events.
-    // struct _cef_drag_handler_t*(CEF_CALLBACK* get_drag_handler)(
-    // struct _cef_client_t* self);
+    /// Return the handler for drag events.
+    fn get_drag_handler(&mut self) -> Option<DragHandler> {
+        None
+    }
 
     // /// Return the handler for find result events.
     // struct _cef_find_handler_t*(CEF_CALLBACK* get_find_handler)(
@@ -61,24 +75,31 @@ pub trait ClientCallbacks: Send + Sync + 'static {
     // struct _cef_frame_handler_t*(CEF_CALLBACK* get_frame_handler)(
     // struct _cef_client_t* self);
 
-    // /// Return the handler for permission requests.
-    // struct _cef_permission_handler_t*(CEF_CALLBACK* get_permission_handler)(
-    // struct _cef_client_t* self);
+    /// Return the handler for permission requests.
+    fn get_permission_handler(&mut self) -> Option<PermissionHandler> {
+        None
+    }
 
-    // /// Return the handler for JavaScript dialogs. If no handler is provided the
-    // /// default implementation will be used.
-    // struct _cef_jsdialog_handler_t*(CEF_CALLBACK* get_jsdialog_handler)(
-    // struct _cef_client_t* self);
+    /// Return the handler for JavaScript dialogs. If no handler is provided the
+    /// default implementation will be used.
+    fn get_jsdialog_handler(&mut self) -> Option<JsDialogHandler> {
+        None
+    }
 
     /// Return the handler for keyboard events.
-    fn get_keyboard_handler(&mut self) -> Option<KeyboardHandler>;
+    fn get_keyboard_handler(&mut self) -> Option<KeyboardHandler> {
+        None
+    }
 
     /// Return the handler for browser life span events.
-    fn get_life_span_handler(&mut self) -> Option<LifeSpanHandler>;
+    fn get_life_span_handler(&mut self) -> Option<LifeSpanHandler> {
+        None
+    }
 
-    // /// Return the handler for browser load status events.
-    // struct _cef_load_handler_t*(CEF_CALLBACK* get_load_handler)(
-    // struct _cef_client_t* self);
+    /// Return the handler for browser load status events.
+    fn get_load_handler(&mut self) -> Option<LoadHandler> {
+        None
+    }
 
     // /// Return the handler for printing on Linux. If a print handler is not
     // /// provided then printing will not be supported on the Linux platform.
@@ -86,21 +107,28 @@ pub trait ClientCallbacks: Send + Sync + 'static {
     // struct _cef_client_t* self);
 
     /// Return the handler for off-screen rendering events.
-    fn get_render_handler(&mut self) -> Option<RenderHandler>;
+    #[cfg(feature = "osr")]
+    fn get_render_handler(&mut self) -> Option<RenderHandler> {
+        None
+    }
 
-    // /// Return the handler for browser request events.
-    // struct _cef_request_handler_t*(CEF_CALLBACK* get_request_handler)(
-    // struct _cef_client_t* self);
+    /// Return the handler for browser request events.
+    fn get_request_handler(&mut self) -> Option<RequestHandler> {
+        None
+    }
 
-    // /// Called when a new message is received from a different process. Return
-    // /// true (1) if the message was handled or false (0) otherwise.  It is safe to
-    // /// keep a reference to |message| outside of this callback.
-    // int(CEF_CALLBACK* on_process_message_received)(
-    // struct _cef_client_t* self,
-    // struct _cef_browser_t* browser,
-    // struct _cef_frame_t* frame,
-    // cef_process_id_t source_process,
-    // struct _cef_process_message_t* message);
+    /// Called when a new message is received from a different process. Return
+    /// true (1) if the message was handled or false (0) otherwise. It is safe to
+    /// keep a reference to |message| outside of this callback.
+    fn on_process_message_received(
+        &mut self,
+        _browser: Browser,
+        _frame: Frame,
+        _source_process: ProcessId,
+        _message: ProcessMessage
+    ) -> bool {
+        false
+    }
 }
 
 // Implement this structure to provide handler implementations.
@@ -112,29 +140,55 @@ impl Client {
     }
 }
 
+callbacks_builder! {
+    /// Builds a Client from closures. Callbacks that are not set fall back to
+    /// the default ClientCallbacks behavior.
+    ClientBuilder: ClientCallbacks => Client {
+        fn get_audio_handler() -> Option<AudioHandler> = None;
+        fn get_context_menu_handler() -> Option<ContextMenuHandler> = None;
+        fn get_dialog_handler() -> Option<DialogHandler> = None;
+        fn get_display_handler() -> Option<DisplayHandler> = None;
+        fn get_download_handler() -> Option<DownloadHandler> = None;
+        fn get_drag_handler() -> Option<DragHandler> = None;
+        fn get_jsdialog_handler() -> Option<JsDialogHandler> = None;
+        fn get_permission_handler() -> Option<PermissionHandler> = None;
+        fn get_keyboard_handler() -> Option<KeyboardHandler> = None;
+        fn get_life_span_handler() -> Option<LifeSpanHandler> = None;
+        fn get_load_handler() -> Option<LoadHandler> = None;
+        #[cfg(feature = "osr")]
+        fn get_render_handler() -> Option<RenderHandler> = None;
+        fn get_request_handler() -> Option<RequestHandler> = None;
+        fn on_process_message_received(
+            browser: Browser,
+            frame: Frame,
+            source_process: ProcessId,
+            message: ProcessMessage
+        ) -> bool = false;
+    }
+}
+
 /// Translates CEF -> Rust callbacks.
-struct ClientWrapper(Box<dyn ClientCallbacks>);
+struct ClientWrapper<C: ClientCallbacks>(C);
 
 // TODO: Remove this!
 
 #[allow(dead_code)]
 #[allow(unused_variables)]
-impl ClientWrapper {
-    pub fn new<C: ClientCallbacks>(delegate: C) -> Self {
-        Self(Box::new(delegate))
+impl<C: ClientCallbacks> ClientWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
     }
 
     /// Return the handler for audio rendering events.
     unsafe extern "C" fn c_get_audio_handler(this: *mut cef_client_t) -> *mut cef_audio_handler_t {
-        todo!()
-    }
-
-    /// Return the handler for commands. If no handler is provided the default
-    /// implementation will be used.
-    unsafe extern "C" fn c_get_command_handler(
-        this: *mut cef_client_t
-    ) -> *mut cef_command_handler_t {
-        todo!()
+        catch_unwind_or("ClientWrapper::c_get_audio_handler", null_mut(), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+
+            this.0
+                .get_audio_handler()
+                .map(|handler| handler.into_raw())
+                .unwrap_or(null_mut())
+        })
     }
 
     /// Return the handler for context menus. If no handler is provided the
@@ -142,12 +196,18 @@ impl ClientWrapper {
     unsafe extern "C" fn c_get_context_menu_handler(
         this: *mut cef_client_t
     ) -> *mut cef_context_menu_handler_t {
-        let this: &mut Self = Wrapped::wrappable(this);
-
-        this.0
-            .get_context_menu_handler()
-            .map(|handler| handler.into_raw())
-            .unwrap_or(null_mut())
+        catch_unwind_or(
+            "ClientWrapper::c_get_context_menu_handler",
+            null_mut(),
+            || {
+                let this: &mut Self = Wrapped::wrappable(this);
+
+                this.0
+                    .get_context_menu_handler()
+                    .map(|handler| handler.into_raw())
+                    .unwrap_or(null_mut())
+            }
+        )
     }
 
     /// Return the handler for dialogs. If no handler is provided the default
@@ -155,14 +215,32 @@ impl ClientWrapper {
     unsafe extern "C" fn c_get_dialog_handler(
         this: *mut cef_client_t
     ) -> *mut cef_dialog_handler_t {
-        todo!()
+        catch_unwind_or(
+            "ClientWrapper::c_get_dialog_handler",
+            null_mut(),
+            || {
+                let this: &mut Self = Wrapped::wrappable(this);
+
+                this.0
+                    .get_dialog_handler()
+                    .map(|handler| handler.into_raw())
+                    .unwrap_or(null_mut())
+            }
+        )
     }
 
     /// Return the handler for browser display state events.
     unsafe extern "C" fn c_get_display_handler(
         this: *mut cef_client_t
     ) -> *mut cef_display_handler_t {
-        todo!()
+        catch_unwind_or("ClientWrapper::c_get_display_handler", null_mut(), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+
+            this.0
+                .get_display_handler()
+                .map(|handler| handler.into_raw())
+                .unwrap_or(null_mut())
+        })
     }
 
     /// Return the handler for download events. If no handler is returned
@@ -170,36 +248,44 @@ impl ClientWrapper {
     unsafe extern "C" fn c_get_download_handler(
         this: *mut cef_client_t
     ) -> *mut cef_download_handler_t {
-        todo!()
+        catch_unwind_or("ClientWrapper::c_get_download_handler", null_mut(), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+
+            this.0
+                .get_download_handler()
+                .map(|handler| handler.into_raw())
+                .unwrap_or(null_mut())
+        })
     }
 
     /// Return the handler for drag events.
     unsafe extern "C" fn c_get_drag_handler(this: *mut cef_client_t) -> *mut cef_drag_handler_t {
-        todo!()
-    }
-
-    /// Return the handler for find result events.
-    unsafe extern "C" fn c_get_find_handler(this: *mut cef_client_t) -> *mut cef_find_handler_t {
-        todo!()
-    }
-
-    /// Return the handler for focus events.
-    unsafe extern "C" fn c_get_focus_handler(this: *mut cef_client_t) -> *mut cef_focus_handler_t {
-        todo!()
-    }
-
-    /// Return the handler for events related to cef_frame_t lifespan. This
-    /// function will be called once during cef_browser_t creation and the result
-    /// will be cached for performance reasons.
-    unsafe extern "C" fn c_get_frame_handler(this: *mut cef_client_t) -> *mut cef_frame_handler_t {
-        todo!()
+        catch_unwind_or("ClientWrapper::c_get_drag_handler", null_mut(), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+
+            this.0
+                .get_drag_handler()
+                .map(|handler| handler.into_raw())
+                .unwrap_or(null_mut())
+        })
     }
 
     /// Return the handler for permission requests.
     unsafe extern "C" fn c_get_permission_handler(
         this: *mut cef_client_t
     ) -> *mut cef_permission_handler_t {
-        todo!()
+        catch_unwind_or(
+            "ClientWrapper::c_get_permission_handler",
+            null_mut(),
+            || {
+                let this: &mut Self = Wrapped::wrappable(this);
+
+                this.0
+                    .get_permission_handler()
+                    .map(|handler| handler.into_raw())
+                    .unwrap_or(null_mut())
+            }
+        )
     }
 
     /// Return the handler for JavaScript dialogs. If no handler is provided the
@@ -207,61 +293,87 @@ impl ClientWrapper {
     unsafe extern "C" fn c_get_jsdialog_handler(
         this: *mut cef_client_t
     ) -> *mut cef_jsdialog_handler_t {
-        todo!()
+        catch_unwind_or(
+            "ClientWrapper::c_get_jsdialog_handler",
+            null_mut(),
+            || {
+                let this: &mut Self = Wrapped::wrappable(this);
+
+                this.0
+                    .get_jsdialog_handler()
+                    .map(|handler| handler.into_raw())
+                    .unwrap_or(null_mut())
+            }
+        )
     }
 
     /// Return the handler for keyboard events.
     unsafe extern "C" fn c_get_keyboard_handler(
         this: *mut cef_client_t
     ) -> *mut cef_keyboard_handler_t {
-        let this: &mut Self = Wrapped::wrappable(this);
-
-        this.0
-            .get_keyboard_handler()
-            .map(|handler| handler.into_raw())
-            .unwrap_or(null_mut())
+        catch_unwind_or("ClientWrapper::c_get_keyboard_handler", null_mut(), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+
+            this.0
+                .get_keyboard_handler()
+                .map(|handler| handler.into_raw())
+                .unwrap_or(null_mut())
+        })
     }
 
     /// Return the handler for browser life span events.
     unsafe extern "C" fn c_get_life_span_handler(
         this: *mut cef_client_t
     ) -> *mut cef_life_span_handler_t {
-        let this: &mut Self = Wrapped::wrappable(this);
-
-        this.0
-            .get_life_span_handler()
-            .map(|handler| handler.into_raw())
-            .unwrap_or(null_mut())
+        catch_unwind_or("ClientWrapper::c_get_life_span_handler", null_mut(), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+
+            this.0
+                .get_life_span_handler()
+                .map(|handler| handler.into_raw())
+                .unwrap_or(null_mut())
+        })
     }
 
     /// Return the handler for browser load status events.
     unsafe extern "C" fn c_get_load_handler(this: *mut cef_client_t) -> *mut cef_load_handler_t {
-        todo!()
-    }
-
-    /// Return the handler for printing on Linux. If a print handler is not
-    /// provided then printing will not be supported on the Linux platform.
-    unsafe extern "C" fn c_get_print_handler(this: *mut cef_client_t) -> *mut cef_print_handler_t {
-        todo!()
+        catch_unwind_or("ClientWrapper::c_get_load_handler", null_mut(), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+
+            this.0
+                .get_load_handler()
+                .map(|handler| handler.into_raw())
+                .unwrap_or(null_mut())
+        })
     }
 
     /// Return the handler for off-screen rendering events.
+    #[cfg(feature = "osr")]
     unsafe extern "C" fn c_get_render_handler(
         this: *mut cef_client_t
     ) -> *mut cef_render_handler_t {
-        let this: &mut Self = Wrapped::wrappable(this);
-
-        this.0
-            .get_render_handler()
-            .map(|handler| handler.into_raw())
-            .unwrap_or(null_mut())
+        catch_unwind_or("ClientWrapper::c_get_render_handler", null_mut(), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+
+            this.0
+                .get_render_handler()
+                .map(|handler| handler.into_raw())
+                .unwrap_or(null_mut())
+        })
     }
 
     /// Return the handler for browser request events.
     unsafe extern "C" fn c_get_request_handler(
         this: *mut cef_client_t
     ) -> *mut cef_request_handler_t {
-        todo!()
+        catch_unwind_or("ClientWrapper::c_get_request_handler", null_mut(), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+
+            this.0
+                .get_request_handler()
+                .map(|handler| handler.into_raw())
+                .unwrap_or(null_mut())
+        })
     }
 
     /// Called when a new message is received from a different process. Return
@@ -274,11 +386,40 @@ impl ClientWrapper {
         source_process: cef_process_id_t,
         message: *mut cef_process_message_t
     ) -> c_int {
-        todo!()
+        catch_unwind_or("ClientWrapper::c_process_message_received", 0, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let frame = Frame::from_ptr_unchecked(frame);
+            let message = ProcessMessage::from_ptr_unchecked(message);
+
+            #[cfg(feature = "v8")]
+            if function_binding::on_browser_process_message(
+                &browser,
+                &frame,
+                source_process.into(),
+                &message
+            ) || event_bus::on_browser_process_message(
+                &browser,
+                &frame,
+                source_process.into(),
+                &message
+            ) || binary_bridge::on_browser_process_message(
+                &browser,
+                &frame,
+                source_process.into(),
+                &message
+            ) {
+                return 1;
+            }
+
+            this.0
+                .on_process_message_received(browser, frame, source_process.into(), message)
+                as c_int
+        })
     }
 }
 
-impl Wrappable for ClientWrapper {
+impl<C: ClientCallbacks> Wrappable for ClientWrapper<C> {
     type Cef = cef_client_t;
 
     /// Converts this to a smart pointer.
@@ -287,26 +428,36 @@ impl Wrappable for ClientWrapper {
             cef_client_t {
                 base: unsafe { zeroed() },
 
-                // TODO: Fix this!
-                get_audio_handler:           None,
-                get_command_handler:         None,
-                get_context_menu_handler:    Some(Self::c_get_context_menu_handler),
-                get_dialog_handler:          None,
-                get_display_handler:         None,
-                get_download_handler:        None,
-                get_drag_handler:            None,
-                get_find_handler:            None,
-                get_focus_handler:           None,
-                get_frame_handler:           None,
-                get_permission_handler:      None,
-                get_jsdialog_handler:        None,
-                get_keyboard_handler:        Some(Self::c_get_keyboard_handler),
-                get_life_span_handler:       Some(Self::c_get_life_span_handler),
-                get_load_handler:            None,
-                get_print_handler:           None,
-                get_render_handler:          Some(Self::c_get_render_handler),
-                get_request_handler:         None,
-                on_process_message_received: None
+                get_audio_handler:                               Some(Self::c_get_audio_handler),
+                get_command_handler:                             None,
+                get_context_menu_handler:                        Some(
+                    Self::c_get_context_menu_handler
+                ),
+                get_dialog_handler:                              Some(Self::c_get_dialog_handler),
+                get_display_handler:                             Some(Self::c_get_display_handler),
+                get_download_handler:                            Some(Self::c_get_download_handler),
+                get_drag_handler:                                Some(Self::c_get_drag_handler),
+                get_find_handler:                                None,
+                get_focus_handler:                               None,
+                get_frame_handler:                               None,
+                get_permission_handler:                          Some(
+                    Self::c_get_permission_handler
+                ),
+                get_jsdialog_handler:                            Some(Self::c_get_jsdialog_handler),
+                get_keyboard_handler:                            Some(Self::c_get_keyboard_handler),
+                get_life_span_handler:                           Some(
+                    Self::c_get_life_span_handler
+                ),
+                get_load_handler:                                Some(Self::c_get_load_handler),
+                get_print_handler:                               None,
+                #[cfg(feature = "osr")]
+                get_render_handler:                              Some(Self::c_get_render_handler),
+                #[cfg(not(feature = "osr"))]
+                get_render_handler:                              None,
+                get_request_handler:                             Some(Self::c_get_request_handler),
+                on_process_message_received:                     Some(
+                    Self::c_process_message_received
+                )
             },
             self
         )
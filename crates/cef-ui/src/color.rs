@@ -27,6 +27,20 @@ impl Color {
         b: 0
     };
 
+    /// The fully transparent color. As a background color it enables
+    /// transparent painting for windowless browsers.
+    pub const TRANSPARENT: Self = Self {
+        a: 0,
+        r: 0,
+        g: 0,
+        b: 0
+    };
+
+    /// Returns a value with the specified component values.
+    pub const fn argb(a: u8, r: u8, g: u8, b: u8) -> Self {
+        Self { a, r, g, b }
+    }
+
     /// Returns a value with the specified component values in range 0.0 to 1.0.
     pub fn rgba(alpha: f32, red: f32, green: f32, blue: f32) -> Self {
         debug_assert!(alpha >= 0.0 && alpha <= 1.0);
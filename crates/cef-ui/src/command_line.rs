@@ -1,5 +1,4 @@
-use crate::{ref_counted_ptr, try_c, CefString, CefStringList, CefStringMap};
-use anyhow::Result;
+use crate::{ref_counted_ptr, try_c, CefString, CefStringList, CefStringMap, Result};
 use cef_ui_sys::{cef_command_line_create, cef_command_line_get_global, cef_command_line_t};
 use std::{collections::HashMap, ptr::null_mut};
 
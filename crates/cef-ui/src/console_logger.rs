@@ -0,0 +1,61 @@
+use crate::{Browser, CefStr, DisplayHandlerCallbacks, LogSeverity};
+use tracing::{debug, error, info, warn};
+
+/// A display handler that forwards page console messages to `tracing` under
+/// the `cef_ui::console` target, so that JavaScript console output shows up
+/// alongside the application's own logs. Install it with
+/// `DisplayHandler::new(ConsoleLogger::new())`. Records reach the `log` crate
+/// through the usual `tracing` bridges.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ConsoleLogger {
+    suppress_default_output: bool
+}
+
+impl ConsoleLogger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// If true, console messages are not also written to CEF's own console
+    /// output after they have been logged. Defaults to false.
+    pub fn suppress_default_output(mut self, suppress: bool) -> Self {
+        self.suppress_default_output = suppress;
+        self
+    }
+}
+
+impl DisplayHandlerCallbacks for ConsoleLogger {
+    fn on_console_message(
+        &mut self,
+        _browser: Browser,
+        level: LogSeverity,
+        message: CefStr<'_>,
+        source: CefStr<'_>,
+        line: i32
+    ) -> bool {
+        log_console_message(level, message, source, line);
+
+        self.suppress_default_output
+    }
+}
+
+/// Emits a page console message as a `tracing` event with the `cef_ui::console`
+/// target. Use this from your own DisplayHandlerCallbacks::on_console_message
+/// if you need to handle other display events as well.
+pub fn log_console_message(level: LogSeverity, message: CefStr<'_>, source: CefStr<'_>, line: i32) {
+    match level {
+        LogSeverity::Verbose => {
+            debug!(target: "cef_ui::console", source = %source, line, "{}", message)
+        },
+        LogSeverity::Default | LogSeverity::Info => {
+            info!(target: "cef_ui::console", source = %source, line, "{}", message)
+        },
+        LogSeverity::Warning => {
+            warn!(target: "cef_ui::console", source = %source, line, "{}", message)
+        },
+        LogSeverity::Error | LogSeverity::Fatal => {
+            error!(target: "cef_ui::console", source = %source, line, "{}", message)
+        },
+        LogSeverity::Disable => {}
+    }
+}
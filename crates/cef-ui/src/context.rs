@@ -1,10 +1,20 @@
-use crate::{App, MainArgs, Settings};
-use anyhow::{anyhow, Result};
+use crate::{
+    currently_on, is_subprocess, verify_version, App, Error, MainArgs, Result, Settings, ThreadId
+};
+use anyhow::anyhow;
 use cef_ui_sys::{
     cef_do_message_loop_work, cef_execute_process, cef_initialize, cef_quit_message_loop,
     cef_run_message_loop, cef_shutdown
 };
-use std::{ffi::c_void, ptr::null_mut};
+use std::{
+    ffi::c_void,
+    process::exit,
+    ptr::null_mut,
+    sync::atomic::{AtomicBool, Ordering}
+};
+
+/// Set while cef_run_message_loop is running.
+static MESSAGE_LOOP_RUNNING: AtomicBool = AtomicBool::new(false);
 
 pub struct Context {
     pub main_args:            MainArgs,
@@ -55,6 +65,22 @@ impl Context {
         }
     }
 
+    /// Convenience entry point for single-binary applications where the same
+    /// executable serves as both the browser process and its render, GPU and
+    /// utility helpers. If the command line contains a "--type=" switch this
+    /// runs the secondary process via cef_execute_process and then exits the
+    /// current process with its exit code, so it never returns. For the browser
+    /// process it returns immediately and initialization can continue.
+    pub fn exit_if_subprocess(&self) {
+        if !is_subprocess() {
+            return;
+        }
+
+        if let Some(code) = self.is_cef_subprocess() {
+            exit(code);
+        }
+    }
+
     /// This function should be called on the main application thread to initialize
     /// the CEF browser process. The |application| parameter may be NULL. Returns
     /// true (1) if initialization succeeds. Returns false (0) if initialization
@@ -64,6 +90,10 @@ impl Context {
     /// |windows_sandbox_info| parameter is only used on Windows and may be NULL
     /// (see cef_sandbox_win.h for details).
     pub fn initialize(&self) -> Result<()> {
+        verify_version()?;
+
+        self.settings.validate()?;
+
         match unsafe {
             cef_initialize(
                 self.main_args.as_raw(),
@@ -76,7 +106,7 @@ impl Context {
             ) != 0
         } {
             true => Ok(()),
-            false => Err(anyhow!("Failed to initialize CEF."))
+            false => Err(anyhow!("Failed to initialize CEF.").into())
         }
     }
 
@@ -85,16 +115,34 @@ impl Context {
     /// usage. This function should only be called on the main application thread
     /// and only if cef_initialize() is called with a
     /// cef_settings_t.multi_threaded_message_loop value of false (0). This function
-    /// will block until a quit message is received by the system.
-    pub fn run_message_loop(&self) {
+    /// will block until a quit message is received by the system. Returns an
+    /// error if not called on the UI thread or if the message loop is
+    /// multi-threaded, externally pumped or already running.
+    pub fn run_message_loop(&self) -> Result<()> {
+        self.ensure_on_message_loop_thread()?;
+
+        if self
+            .settings
+            .is_external_message_pump()
+        {
+            return Err(anyhow!(
+                "The message loop cannot be run when external_message_pump is enabled; use do_message_loop_work instead."
+            )
+            .into());
+        }
+
+        let _running = RunningMessageLoop::enter()?;
+
         unsafe { cef_run_message_loop() };
+
+        Ok(())
     }
 
     /// Quit the CEF message loop that was started by calling
     /// cef_run_message_loop(). This function should only be called on the main
     /// application thread and only if cef_run_message_loop() was used.
-    pub fn quit_message_loop(&self) {
-        unsafe { cef_quit_message_loop() };
+    pub fn quit_message_loop(&self) -> Result<()> {
+        quit_message_loop()
     }
 
     /// This function should be called on the main application thread to shut down
@@ -116,9 +164,71 @@ impl Context {
     /// facilitate the scheduling process. This function should only be called on
     /// the main application thread and only if cef_initialize() is called with a
     /// cef_settings_t.multi_threaded_message_loop value of false (0). This function
-    /// will not block.
-    pub fn do_message_loop_work(&self) {
+    /// will not block. Returns Error::WrongThread otherwise.
+    pub fn do_message_loop_work(&self) -> Result<()> {
+        self.ensure_on_message_loop_thread()?;
+
         unsafe { cef_do_message_loop_work() };
+
+        Ok(())
+    }
+
+    /// Returns true if CEF was configured to run its message loop on a
+    /// separate thread. In that case the application keeps running its own
+    /// UI loop and browser process UI thread callbacks arrive on CEF's thread.
+    pub fn is_multi_threaded_message_loop(&self) -> bool {
+        self.settings
+            .is_multi_threaded_message_loop()
+    }
+
+    /// The message loop may only be driven manually from the main application
+    /// thread, which is the UI thread unless CEF owns the message loop. With
+    /// multi_threaded_message_loop enabled no application thread qualifies.
+    fn ensure_on_message_loop_thread(&self) -> Result<()> {
+        match !self.is_multi_threaded_message_loop() && currently_on(ThreadId::Ui) {
+            true => Ok(()),
+            false => Err(Error::WrongThread(ThreadId::Ui))
+        }
+    }
+}
+
+/// Marks the message loop as running for as long as it is alive, including
+/// when cef_run_message_loop unwinds.
+struct RunningMessageLoop;
+
+impl RunningMessageLoop {
+    fn enter() -> Result<Self> {
+        match MESSAGE_LOOP_RUNNING.swap(true, Ordering::SeqCst) {
+            true => Err(anyhow!("The message loop is already running.").into()),
+            false => Ok(Self)
+        }
+    }
+}
+
+impl Drop for RunningMessageLoop {
+    fn drop(&mut self) {
+        MESSAGE_LOOP_RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Returns true while Context::run_message_loop is running.
+pub fn is_message_loop_running() -> bool {
+    MESSAGE_LOOP_RUNNING.load(Ordering::SeqCst)
+}
+
+/// Quit the CEF message loop that was started by calling
+/// Context::run_message_loop, e.g. from LifeSpanHandlerCallbacks::on_before_close
+/// when the last browser closes. This function should only be called on the
+/// main application thread. Returns an error if the message loop is not
+/// running.
+pub fn quit_message_loop() -> Result<()> {
+    match is_message_loop_running() {
+        true => {
+            unsafe { cef_quit_message_loop() };
+
+            Ok(())
+        },
+        false => Err(anyhow!("The message loop is not running.").into())
     }
 }
 
@@ -1,8 +1,8 @@
 use crate::{
-    ref_counted_ptr, try_c, Browser, CefString, CefStringList, Color, EventFlags, Frame, Point,
-    RefCountedPtr, Size, Wrappable, Wrapped
+    callbacks_builder, debug_assert_currently_on, ref_counted_ptr, try_c, unwind::catch_unwind_or,
+    Browser, CefString, CefStringList, Color, EventFlags, Frame, Point, RefCountedPtr, Result,
+    Size, ThreadId, Wrappable, Wrapped
 };
-use anyhow::Result;
 use bitflags::bitflags;
 use cef_ui_sys::{
     cef_browser_t, cef_color_t, cef_context_menu_edit_state_flags_t,
@@ -38,20 +38,21 @@ use cef_ui_sys::{
     cef_context_menu_type_flags_t_CM_TYPEFLAG_MEDIA,
     cef_context_menu_type_flags_t_CM_TYPEFLAG_NONE, cef_context_menu_type_flags_t_CM_TYPEFLAG_PAGE,
     cef_context_menu_type_flags_t_CM_TYPEFLAG_SELECTION, cef_event_flags_t, cef_frame_t,
-    cef_menu_color_type_t, cef_menu_id_t, cef_menu_item_type_t, cef_menu_model_t, cef_point_t,
-    cef_quick_menu_edit_state_flags_t, cef_quick_menu_edit_state_flags_t_QM_EDITFLAG_CAN_COPY,
+    cef_menu_color_type_t, cef_menu_id_t, cef_menu_item_type_t, cef_menu_model_create,
+    cef_menu_model_delegate_t, cef_menu_model_t, cef_point_t, cef_quick_menu_edit_state_flags_t,
+    cef_quick_menu_edit_state_flags_t_QM_EDITFLAG_CAN_COPY,
     cef_quick_menu_edit_state_flags_t_QM_EDITFLAG_CAN_CUT,
     cef_quick_menu_edit_state_flags_t_QM_EDITFLAG_CAN_ELLIPSIS,
     cef_quick_menu_edit_state_flags_t_QM_EDITFLAG_CAN_PASTE,
     cef_quick_menu_edit_state_flags_t_QM_EDITFLAG_NONE, cef_run_context_menu_callback_t,
-    cef_run_quick_menu_callback_t, cef_size_t
+    cef_run_quick_menu_callback_t, cef_size_t, cef_string_t
 };
 use std::{ffi::c_int, mem::zeroed, ptr::null};
 
 bitflags! {
     /// Supported context menu type flags.
     #[allow(non_upper_case_globals)]
-    #[derive(Default, Clone, Copy)]
+    #[derive(Debug, Default, Clone, Copy)]
     pub struct ContextMenuTypeFlags: cef_context_menu_type_flags_t {
         /// No node is selected.
         const None = cef_context_menu_type_flags_t_CM_TYPEFLAG_NONE;
@@ -137,12 +138,78 @@ impl From<&QuickMenuEditStateFlags> for cef_quick_menu_edit_state_flags_t {
     }
 }
 
+impl QuickMenuEditStateFlags {
+    /// Returns the commands the quick menu should offer, in display order.
+    pub fn commands(&self) -> Vec<QuickMenuCommand> {
+        QuickMenuCommand::ALL
+            .into_iter()
+            .filter(|command| self.contains(command.edit_state_flag()))
+            .collect()
+    }
+}
+
+/// A command of the quick menu shown for touch text selection in windowless
+/// browsers. CEF identifies quick menu commands by their edit state flag, so
+/// these are the command ids passed to RunQuickMenuCallback::cont and
+/// on_quick_menu_command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum QuickMenuCommand {
+    Cut,
+    Copy,
+    Paste,
+
+    /// Show the full context menu for the selection.
+    Ellipsis
+}
+
+impl QuickMenuCommand {
+    /// Every command, in display order.
+    pub const ALL: [QuickMenuCommand; 4] = [
+        QuickMenuCommand::Cut,
+        QuickMenuCommand::Copy,
+        QuickMenuCommand::Paste,
+        QuickMenuCommand::Ellipsis
+    ];
+
+    /// Returns the edit state flag that enables this command.
+    pub fn edit_state_flag(&self) -> QuickMenuEditStateFlags {
+        match self {
+            QuickMenuCommand::Cut => QuickMenuEditStateFlags::CanCut,
+            QuickMenuCommand::Copy => QuickMenuEditStateFlags::CanCopy,
+            QuickMenuCommand::Paste => QuickMenuEditStateFlags::CanPaste,
+            QuickMenuCommand::Ellipsis => QuickMenuEditStateFlags::CanEllipsis
+        }
+    }
+
+    /// Returns the command id that selects this command.
+    pub fn command_id(&self) -> MenuCommandId {
+        MenuCommandId::new(self.edit_state_flag().bits() as i32)
+    }
+
+    /// Returns the command selected by |command_id|, if any.
+    pub fn from_command_id(command_id: &MenuCommandId) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|command| command.edit_state_flag().bits() as i32 == command_id.0)
+    }
+
+    /// Returns an English label for the command.
+    pub fn label(&self) -> &'static str {
+        match self {
+            QuickMenuCommand::Cut => "Cut",
+            QuickMenuCommand::Copy => "Copy",
+            QuickMenuCommand::Paste => "Paste",
+            QuickMenuCommand::Ellipsis => "\u{2026}"
+        }
+    }
+}
+
 bitflags! {
     /// Supported context menu media state bit flags. These constants match their
     /// equivalents in Chromium's ContextMenuData::MediaFlags and should not be
     /// renumbered.
     #[allow(non_upper_case_globals)]
-    #[derive(Default, Clone, Copy)]
+    #[derive(Debug, Default, Clone, Copy)]
     pub struct ContextMenuMediaStateFlags: cef_context_menu_media_state_flags_t {
         const None = cef_context_menu_media_state_flags_t_CM_MEDIAFLAG_NONE;
         const InError = cef_context_menu_media_state_flags_t_CM_MEDIAFLAG_IN_ERROR;
@@ -190,7 +257,7 @@ bitflags! {
     /// equivalents in Chromium's ContextMenuDataEditFlags and should not be
     /// renumbered.
     #[allow(non_upper_case_globals)]
-    #[derive(Default, Clone, Copy)]
+    #[derive(Debug, Default, Clone, Copy)]
     pub struct ContextMenuEditStateFlags: cef_context_menu_edit_state_flags_t {
         const None = cef_context_menu_edit_state_flags_t_CM_EDITFLAG_NONE;
         const CanUndo = cef_context_menu_edit_state_flags_t_CM_EDITFLAG_CAN_UNDO;
@@ -312,6 +379,40 @@ impl ContextMenuParams {
         try_c!(self, get_ycoord, { Ok(get_ycoord(self.as_ptr()) as i32) })
     }
 
+    /// Returns the point where the context menu was invoked, relative to the
+    /// associated RenderView's origin.
+    pub fn get_location(&self) -> Result<Point> {
+        Ok(Point::new(self.get_xcoord()?, self.get_ycoord()?))
+    }
+
+    /// Copies the parameters into a ContextMenuInfo, which unlike this object
+    /// may be kept and used after the context menu callback returns.
+    pub fn to_info(&self) -> Result<ContextMenuInfo> {
+        Ok(ContextMenuInfo {
+            location:               self.get_location()?,
+            type_flags:             self.get_type_flags()?,
+            link_url:               non_empty(self.get_link_url()?),
+            unfiltered_link_url:    non_empty(self.get_unfiltered_link_url()?),
+            source_url:             non_empty(self.get_source_url()?),
+            has_image_contents:     self.has_image_contents()?,
+            title_text:             non_empty(self.get_title_text()?),
+            page_url:               self.get_page_url()?,
+            frame_url:              self.get_frame_url()?,
+            frame_charset:          self.get_frame_charset()?,
+            media_type:             self.get_media_type()?,
+            media_state_flags:      self.get_media_state_flags()?,
+            selection_text:         non_empty(self.get_selection_text()?),
+            misspelled_word:        non_empty(self.get_misspelled_word()?),
+            dictionary_suggestions: self
+                .get_dictionary_suggestions()?
+                .unwrap_or_default(),
+            is_editable:            self.is_editable()?,
+            is_spell_check_enabled: self.is_spell_check_enabled()?,
+            edit_state_flags:       self.get_edit_state_flags()?,
+            is_custom_menu:         self.is_custom_menu()?
+        })
+    }
+
     /// Returns flags representing the type of node that the context menu was
     /// invoked on.
     pub fn get_type_flags(&self) -> Result<ContextMenuTypeFlags> {
@@ -326,7 +427,9 @@ impl ContextMenuParams {
         try_c!(self, get_link_url, {
             let s = get_link_url(self.as_ptr());
 
-            Ok(CefString::from_userfree_ptr_unchecked(s).into())
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
         })
     }
 
@@ -336,7 +439,9 @@ impl ContextMenuParams {
         try_c!(self, get_unfiltered_link_url, {
             let s = get_unfiltered_link_url(self.as_ptr());
 
-            Ok(CefString::from_userfree_ptr_unchecked(s).into())
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
         })
     }
 
@@ -347,7 +452,9 @@ impl ContextMenuParams {
         try_c!(self, get_source_url, {
             let s = get_source_url(self.as_ptr());
 
-            Ok(CefString::from_userfree_ptr_unchecked(s).into())
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
         })
     }
 
@@ -365,7 +472,9 @@ impl ContextMenuParams {
         try_c!(self, get_title_text, {
             let s = get_title_text(self.as_ptr());
 
-            Ok(CefString::from_userfree_ptr_unchecked(s).into())
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
         })
     }
 
@@ -375,7 +484,9 @@ impl ContextMenuParams {
         try_c!(self, get_page_url, {
             let s = get_page_url(self.as_ptr());
 
-            Ok(CefString::from_userfree_ptr_unchecked(s).into())
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
         })
     }
 
@@ -384,7 +495,9 @@ impl ContextMenuParams {
         try_c!(self, get_frame_url, {
             let s = get_frame_url(self.as_ptr());
 
-            Ok(CefString::from_userfree_ptr_unchecked(s).into())
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
         })
     }
 
@@ -394,7 +507,9 @@ impl ContextMenuParams {
         try_c!(self, get_frame_charset, {
             let s = get_frame_charset(self.as_ptr());
 
-            Ok(CefString::from_userfree_ptr_unchecked(s).into())
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
         })
     }
 
@@ -419,7 +534,9 @@ impl ContextMenuParams {
         try_c!(self, get_selection_text, {
             let s = get_selection_text(self.as_ptr());
 
-            Ok(CefString::from_userfree_ptr_unchecked(s).into())
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
         })
     }
 
@@ -429,7 +546,9 @@ impl ContextMenuParams {
         try_c!(self, get_misspelled_word, {
             let s = get_misspelled_word(self.as_ptr());
 
-            Ok(CefString::from_userfree_ptr_unchecked(s).into())
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
         })
     }
 
@@ -477,6 +596,96 @@ impl ContextMenuParams {
     }
 }
 
+/// An owned copy of ContextMenuParams. Empty strings are None.
+#[derive(Clone, Debug)]
+pub struct ContextMenuInfo {
+    /// Where the context menu was invoked, relative to the associated
+    /// RenderView's origin.
+    pub location: Point,
+
+    /// The type of node that the context menu was invoked on.
+    pub type_flags: ContextMenuTypeFlags,
+
+    /// The URL of the link that encloses the node, if any.
+    pub link_url: Option<String>,
+
+    /// The link URL to be used ONLY for "copy link address", if any.
+    pub unfiltered_link_url: Option<String>,
+
+    /// The source URL of an img, audio or video element, if any.
+    pub source_url: Option<String>,
+
+    /// True if the node is an image which has non-NULL contents.
+    pub has_image_contents: bool,
+
+    /// The title text, or the alt text of an image.
+    pub title_text: Option<String>,
+
+    /// The URL of the top level page.
+    pub page_url: String,
+
+    /// The URL of the subframe.
+    pub frame_url: String,
+
+    /// The character encoding of the subframe.
+    pub frame_charset: String,
+
+    /// The type of context node.
+    pub media_type: ContextMenuMediaType,
+
+    /// The actions supported by the media element, if any.
+    pub media_state_flags: ContextMenuMediaStateFlags,
+
+    /// The selected text, if any.
+    pub selection_text: Option<String>,
+
+    /// The misspelled word, if any.
+    pub misspelled_word: Option<String>,
+
+    /// Spell check suggestions for the misspelled word.
+    pub dictionary_suggestions: Vec<String>,
+
+    /// True if the node is editable.
+    pub is_editable: bool,
+
+    /// True if the node is editable and spell-check is enabled.
+    pub is_spell_check_enabled: bool,
+
+    /// The actions supported by the editable node, if any.
+    pub edit_state_flags: ContextMenuEditStateFlags,
+
+    /// True if the menu contains items specified by the renderer process.
+    pub is_custom_menu: bool
+}
+
+impl ContextMenuInfo {
+    /// Returns true if the menu was invoked on a link.
+    pub fn is_link(&self) -> bool {
+        self.type_flags
+            .contains(ContextMenuTypeFlags::Link)
+    }
+
+    /// Returns true if the menu was invoked on a selection.
+    pub fn has_selection(&self) -> bool {
+        self.type_flags
+            .contains(ContextMenuTypeFlags::Selection)
+    }
+
+    /// Returns true if the menu was invoked on an image, video, audio or other
+    /// media element.
+    pub fn is_media(&self) -> bool {
+        self.media_type != ContextMenuMediaType::None
+    }
+}
+
+/// Returns None for an empty string.
+fn non_empty(s: String) -> Option<String> {
+    match s.is_empty() {
+        true => None,
+        false => Some(s)
+    }
+}
+
 /// Supported menu item types.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum MenuItemType {
@@ -763,6 +972,11 @@ impl From<&MenuId> for MenuCommandId {
 ref_counted_ptr!(MenuModel, cef_menu_model_t);
 
 impl MenuModel {
+    /// Create a new MenuModel with the specified |delegate|.
+    pub fn create(delegate: MenuModelDelegate) -> Option<Self> {
+        unsafe { Self::from_ptr(cef_menu_model_create(delegate.into_raw())) }
+    }
+
     /// Returns true (1) if this menu is a submenu.
     pub fn is_sub_menu(&self) -> Result<bool> {
         try_c!(self, is_sub_menu, { Ok(is_sub_menu(self.as_ptr()) != 0) })
@@ -1360,6 +1574,235 @@ impl MenuModel {
     }
 }
 
+/// Implement this structure to handle menu model events. The functions of this
+/// structure will be called on the browser process UI thread unless otherwise
+/// indicated.
+pub trait MenuModelDelegateCallbacks: Send + Sync + 'static {
+    /// Perform the action associated with the specified |command_id| and
+    /// optional |event_flags|.
+    fn execute_command(
+        &mut self,
+        _menu_model: MenuModel,
+        _command_id: MenuCommandId,
+        _event_flags: EventFlags
+    ) {
+    }
+
+    /// Called when the user moves the mouse outside the menu and over the owning
+    /// window.
+    fn mouse_outside_menu(&mut self, _menu_model: MenuModel, _screen_point: &Point) {}
+
+    /// Called on unhandled open submenu keyboard commands. |is_rtl| will be true
+    /// (1) if the menu is displaying a right-to-left language.
+    fn unhandled_open_submenu(&mut self, _menu_model: MenuModel, _is_rtl: bool) {}
+
+    /// Called on unhandled close submenu keyboard commands. |is_rtl| will be true
+    /// (1) if the menu is displaying a right-to-left language.
+    fn unhandled_close_submenu(&mut self, _menu_model: MenuModel, _is_rtl: bool) {}
+
+    /// The menu is about to show.
+    fn menu_will_show(&mut self, _menu_model: MenuModel) {}
+
+    /// The menu has closed.
+    fn menu_closed(&mut self, _menu_model: MenuModel) {}
+
+    /// Optionally modify a menu item label. Return Some(label) if |label| was
+    /// modified.
+    fn format_label(&mut self, _menu_model: MenuModel, _label: &str) -> Option<String> {
+        None
+    }
+}
+
+// Implement this structure to handle menu model events. The functions of this
+// structure will be called on the browser process UI thread unless otherwise
+// indicated.
+ref_counted_ptr!(MenuModelDelegate, cef_menu_model_delegate_t);
+
+impl MenuModelDelegate {
+    pub fn new<C: MenuModelDelegateCallbacks>(delegate: C) -> Self {
+        Self(MenuModelDelegateWrapper::new(delegate).wrap())
+    }
+}
+
+/// Translates CEF -> Rust callbacks.
+struct MenuModelDelegateWrapper<C: MenuModelDelegateCallbacks>(C);
+
+impl<C: MenuModelDelegateCallbacks> MenuModelDelegateWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
+    }
+
+    /// Perform the action associated with the specified |command_id| and
+    /// optional |event_flags|.
+    unsafe extern "C" fn c_execute_command(
+        this: *mut cef_menu_model_delegate_t,
+        menu_model: *mut cef_menu_model_t,
+        command_id: c_int,
+        event_flags: cef_event_flags_t
+    ) {
+        catch_unwind_or("MenuModelDelegateWrapper::c_execute_command", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let menu_model = MenuModel::from_ptr_unchecked(menu_model);
+            let command_id = MenuCommandId::new(command_id);
+            let event_flags = event_flags.into();
+
+            this.0
+                .execute_command(menu_model, command_id, event_flags);
+        })
+    }
+
+    /// Called when the user moves the mouse outside the menu and over the owning
+    /// window.
+    unsafe extern "C" fn c_mouse_outside_menu(
+        this: *mut cef_menu_model_delegate_t,
+        menu_model: *mut cef_menu_model_t,
+        screen_point: *const cef_point_t
+    ) {
+        catch_unwind_or("MenuModelDelegateWrapper::c_mouse_outside_menu", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let menu_model = MenuModel::from_ptr_unchecked(menu_model);
+            let screen_point: Point = (&*screen_point).into();
+
+            this.0
+                .mouse_outside_menu(menu_model, &screen_point);
+        })
+    }
+
+    /// Called on unhandled open submenu keyboard commands. |is_rtl| will be true
+    /// (1) if the menu is displaying a right-to-left language.
+    unsafe extern "C" fn c_unhandled_open_submenu(
+        this: *mut cef_menu_model_delegate_t,
+        menu_model: *mut cef_menu_model_t,
+        is_rtl: c_int
+    ) {
+        catch_unwind_or(
+            "MenuModelDelegateWrapper::c_unhandled_open_submenu",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let menu_model = MenuModel::from_ptr_unchecked(menu_model);
+
+                this.0
+                    .unhandled_open_submenu(menu_model, is_rtl != 0);
+            }
+        )
+    }
+
+    /// Called on unhandled close submenu keyboard commands. |is_rtl| will be true
+    /// (1) if the menu is displaying a right-to-left language.
+    unsafe extern "C" fn c_unhandled_close_submenu(
+        this: *mut cef_menu_model_delegate_t,
+        menu_model: *mut cef_menu_model_t,
+        is_rtl: c_int
+    ) {
+        catch_unwind_or(
+            "MenuModelDelegateWrapper::c_unhandled_close_submenu",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let menu_model = MenuModel::from_ptr_unchecked(menu_model);
+
+                this.0
+                    .unhandled_close_submenu(menu_model, is_rtl != 0);
+            }
+        )
+    }
+
+    /// The menu is about to show.
+    unsafe extern "C" fn c_menu_will_show(
+        this: *mut cef_menu_model_delegate_t,
+        menu_model: *mut cef_menu_model_t
+    ) {
+        catch_unwind_or("MenuModelDelegateWrapper::c_menu_will_show", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let menu_model = MenuModel::from_ptr_unchecked(menu_model);
+
+            this.0.menu_will_show(menu_model);
+        })
+    }
+
+    /// The menu has closed.
+    unsafe extern "C" fn c_menu_closed(
+        this: *mut cef_menu_model_delegate_t,
+        menu_model: *mut cef_menu_model_t
+    ) {
+        catch_unwind_or("MenuModelDelegateWrapper::c_menu_closed", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let menu_model = MenuModel::from_ptr_unchecked(menu_model);
+
+            this.0.menu_closed(menu_model);
+        })
+    }
+
+    /// Optionally modify a menu item label. Return true (1) if |label| was
+    /// modified.
+    unsafe extern "C" fn c_format_label(
+        this: *mut cef_menu_model_delegate_t,
+        menu_model: *mut cef_menu_model_t,
+        label: *mut cef_string_t
+    ) -> c_int {
+        catch_unwind_or("MenuModelDelegateWrapper::c_format_label", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let menu_model = MenuModel::from_ptr_unchecked(menu_model);
+
+            match CefString::from_ptr_mut(label) {
+                Some(label) => {
+                    let current: String = (&*label).into();
+
+                    match this
+                        .0
+                        .format_label(menu_model, &current)
+                    {
+                        Some(value) => {
+                            label.set(&value);
+
+                            1
+                        },
+                        None => 0
+                    }
+                },
+                None => 0
+            }
+        })
+    }
+}
+
+impl<C: MenuModelDelegateCallbacks> Wrappable for MenuModelDelegateWrapper<C> {
+    type Cef = cef_menu_model_delegate_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<cef_menu_model_delegate_t> {
+        RefCountedPtr::wrap(
+            cef_menu_model_delegate_t {
+                base: unsafe { zeroed() },
+
+                execute_command:         Some(Self::c_execute_command),
+                mouse_outside_menu:      Some(Self::c_mouse_outside_menu),
+                unhandled_open_submenu:  Some(Self::c_unhandled_open_submenu),
+                unhandled_close_submenu: Some(Self::c_unhandled_close_submenu),
+                menu_will_show:          Some(Self::c_menu_will_show),
+                menu_closed:             Some(Self::c_menu_closed),
+                format_label:            Some(Self::c_format_label)
+            },
+            self
+        )
+    }
+}
+
 // Callback structure used for continuation of custom context menu display.
 ref_counted_ptr!(RunContextMenuCallback, cef_run_context_menu_callback_t);
 
@@ -1390,6 +1833,11 @@ impl RunQuickMenuCallback {
         })
     }
 
+    /// Complete quick menu display by selecting |command|.
+    pub fn select(&self, command: QuickMenuCommand) -> Result<()> {
+        self.cont(command.command_id(), EventFlags::empty())
+    }
+
     /// Cancel quick menu display.
     pub fn cancel(&self) -> Result<()> {
         try_c!(self, cancel, { Ok(cancel(self.as_ptr())) })
@@ -1406,11 +1854,12 @@ pub trait ContextMenuHandlerCallbacks: Send + Sync + 'static {
     /// |model| outside of this callback.
     fn on_before_context_menu(
         &mut self,
-        browser: Browser,
-        frame: Frame,
-        params: ContextMenuParams,
-        model: MenuModel
-    );
+        _browser: Browser,
+        _frame: Frame,
+        _params: ContextMenuParams,
+        _model: MenuModel
+    ) {
+    }
 
     /// Called to allow custom display of the context menu. |params| provides
     /// information about the context menu state. |model| contains the context
@@ -1420,12 +1869,14 @@ pub trait ContextMenuHandlerCallbacks: Send + Sync + 'static {
     /// keep references to |params| or |model| outside of this callback.
     fn run_context_menu(
         &mut self,
-        browser: Browser,
-        frame: Frame,
-        params: ContextMenuParams,
-        model: MenuModel,
-        callback: RunContextMenuCallback
-    ) -> bool;
+        _browser: Browser,
+        _frame: Frame,
+        _params: ContextMenuParams,
+        _model: MenuModel,
+        _callback: RunContextMenuCallback
+    ) -> bool {
+        false
+    }
 
     /// Called to execute a command selected from the context menu. Return true
     /// (1) if the command was handled or false (0) for the default
@@ -1436,16 +1887,18 @@ pub trait ContextMenuHandlerCallbacks: Send + Sync + 'static {
     /// reference to |params| outside of this callback.
     fn on_context_menu_command(
         &mut self,
-        browser: Browser,
-        frame: Frame,
-        params: ContextMenuParams,
-        command_id: MenuCommandId,
-        event_flags: EventFlags
-    ) -> bool;
+        _browser: Browser,
+        _frame: Frame,
+        _params: ContextMenuParams,
+        _command_id: MenuCommandId,
+        _event_flags: EventFlags
+    ) -> bool {
+        false
+    }
 
     /// Called when the context menu is dismissed irregardless of whether the menu
     /// was canceled or a command was selected.
-    fn on_context_menu_dismissed(&mut self, browser: Browser, frame: Frame);
+    fn on_context_menu_dismissed(&mut self, _browser: Browser, _frame: Frame) {}
 
     /// Called to allow custom display of the quick menu for a windowless browser.
     /// |location| is the top left corner of the selected region. |size| is the
@@ -1453,32 +1906,38 @@ pub trait ContextMenuHandlerCallbacks: Send + Sync + 'static {
     /// that represent the state of the quick menu. Return true (1) if the menu
     /// will be handled and execute |callback| either synchronously or
     /// asynchronously with the selected command ID. Return false (0) to cancel
-    /// the menu.
+    /// the menu. QuickMenuEditStateFlags::commands returns the commands to show
+    /// and RunQuickMenuCallback::select selects one of them.
     fn run_quick_menu(
         &mut self,
-        browser: Browser,
-        frame: Frame,
-        location: &Point,
-        size: &Size,
-        edit_state_flags: QuickMenuEditStateFlags,
-        callback: RunQuickMenuCallback
-    ) -> bool;
+        _browser: Browser,
+        _frame: Frame,
+        _location: &Point,
+        _size: &Size,
+        _edit_state_flags: QuickMenuEditStateFlags,
+        _callback: RunQuickMenuCallback
+    ) -> bool {
+        false
+    }
 
     /// Called to execute a command selected from the quick menu for a windowless
     /// browser. Return true (1) if the command was handled or false (0) for the
     /// default implementation. See cef_menu_id_t for command IDs that have
-    /// default implementations.
+    /// default implementations. QuickMenuCommand::from_command_id maps
+    /// |command_id| to the quick menu command.
     fn on_quick_menu_command(
         &mut self,
-        browser: Browser,
-        frame: Frame,
-        command_id: MenuCommandId,
-        event_flags: EventFlags
-    ) -> bool;
+        _browser: Browser,
+        _frame: Frame,
+        _command_id: MenuCommandId,
+        _event_flags: EventFlags
+    ) -> bool {
+        false
+    }
 
     /// Called when the quick menu for a windowless browser is dismissed
     /// irregardless of whether the menu was canceled or a command was selected.
-    fn on_quick_menu_dismissed(&mut self, browser: Browser, frame: Frame);
+    fn on_quick_menu_dismissed(&mut self, _browser: Browser, _frame: Frame) {}
 }
 
 // Implement this structure to handle context menu events. The functions of
@@ -1491,12 +1950,55 @@ impl ContextMenuHandler {
     }
 }
 
+callbacks_builder! {
+    /// Builds a ContextMenuHandler from closures. Callbacks that are not set
+    /// fall back to the default ContextMenuHandlerCallbacks behavior.
+    ContextMenuHandlerBuilder: ContextMenuHandlerCallbacks => ContextMenuHandler {
+        fn on_before_context_menu(
+            browser: Browser,
+            frame: Frame,
+            params: ContextMenuParams,
+            model: MenuModel
+        );
+        fn run_context_menu(
+            browser: Browser,
+            frame: Frame,
+            params: ContextMenuParams,
+            model: MenuModel,
+            callback: RunContextMenuCallback
+        ) -> bool = false;
+        fn on_context_menu_command(
+            browser: Browser,
+            frame: Frame,
+            params: ContextMenuParams,
+            command_id: MenuCommandId,
+            event_flags: EventFlags
+        ) -> bool = false;
+        fn on_context_menu_dismissed(browser: Browser, frame: Frame);
+        fn run_quick_menu(
+            browser: Browser,
+            frame: Frame,
+            location: &Point,
+            size: &Size,
+            edit_state_flags: QuickMenuEditStateFlags,
+            callback: RunQuickMenuCallback
+        ) -> bool = false;
+        fn on_quick_menu_command(
+            browser: Browser,
+            frame: Frame,
+            command_id: MenuCommandId,
+            event_flags: EventFlags
+        ) -> bool = false;
+        fn on_quick_menu_dismissed(browser: Browser, frame: Frame);
+    }
+}
+
 /// Translates CEF -> Rust callbacks.
-struct ContextMenuHandlerWrapper(Box<dyn ContextMenuHandlerCallbacks>);
+struct ContextMenuHandlerWrapper<C: ContextMenuHandlerCallbacks>(C);
 
-impl ContextMenuHandlerWrapper {
-    pub fn new<C: ContextMenuHandlerCallbacks>(delegate: C) -> Self {
-        Self(Box::new(delegate))
+impl<C: ContextMenuHandlerCallbacks> ContextMenuHandlerWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
     }
 
     /// Called before a context menu is displayed. |params| provides information
@@ -1511,14 +2013,22 @@ impl ContextMenuHandlerWrapper {
         params: *mut cef_context_menu_params_t,
         model: *mut cef_menu_model_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let frame = Frame::from_ptr_unchecked(frame);
-        let params = ContextMenuParams::from_ptr_unchecked(params);
-        let model = MenuModel::from_ptr_unchecked(model);
-
-        this.0
-            .on_before_context_menu(browser, frame, params, model);
+        catch_unwind_or(
+            "ContextMenuHandlerWrapper::c_on_before_context_menu",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+                let frame = Frame::from_ptr_unchecked(frame);
+                let params = ContextMenuParams::from_ptr_unchecked(params);
+                let model = MenuModel::from_ptr_unchecked(model);
+
+                this.0
+                    .on_before_context_menu(browser, frame, params, model);
+            }
+        )
     }
 
     /// Called to allow custom display of the context menu. |params| provides
@@ -1535,15 +2045,19 @@ impl ContextMenuHandlerWrapper {
         model: *mut cef_menu_model_t,
         callback: *mut cef_run_context_menu_callback_t
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let frame = Frame::from_ptr_unchecked(frame);
-        let params = ContextMenuParams::from_ptr_unchecked(params);
-        let model = MenuModel::from_ptr_unchecked(model);
-        let callback = RunContextMenuCallback::from_ptr_unchecked(callback);
+        catch_unwind_or("ContextMenuHandlerWrapper::c_run_context_menu", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let frame = Frame::from_ptr_unchecked(frame);
+            let params = ContextMenuParams::from_ptr_unchecked(params);
+            let model = MenuModel::from_ptr_unchecked(model);
+            let callback = RunContextMenuCallback::from_ptr_unchecked(callback);
 
-        this.0
-            .run_context_menu(browser, frame, params, model, callback) as c_int
+            this.0
+                .run_context_menu(browser, frame, params, model, callback) as c_int
+        })
     }
 
     /// Called to execute a command selected from the context menu. Return true
@@ -1561,16 +2075,24 @@ impl ContextMenuHandlerWrapper {
         command_id: c_int,
         event_flags: cef_event_flags_t
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let frame = Frame::from_ptr_unchecked(frame);
-        let params = ContextMenuParams::from_ptr_unchecked(params);
-        let command_id = MenuCommandId::new(command_id);
-        let event_flags = event_flags.into();
-
-        this.0
-            .on_context_menu_command(browser, frame, params, command_id, event_flags)
-            as c_int
+        catch_unwind_or(
+            "ContextMenuHandlerWrapper::c_on_context_menu_command",
+            0,
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+                let frame = Frame::from_ptr_unchecked(frame);
+                let params = ContextMenuParams::from_ptr_unchecked(params);
+                let command_id = MenuCommandId::new(command_id);
+                let event_flags = event_flags.into();
+
+                this.0
+                    .on_context_menu_command(browser, frame, params, command_id, event_flags)
+                    as c_int
+            }
+        )
     }
 
     /// Called when the context menu is dismissed irregardless of whether the menu
@@ -1580,12 +2102,20 @@ impl ContextMenuHandlerWrapper {
         browser: *mut cef_browser_t,
         frame: *mut cef_frame_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let frame = Frame::from_ptr_unchecked(frame);
-
-        this.0
-            .on_context_menu_dismissed(browser, frame)
+        catch_unwind_or(
+            "ContextMenuHandlerWrapper::c_on_context_menu_dismissed",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+                let frame = Frame::from_ptr_unchecked(frame);
+
+                this.0
+                    .on_context_menu_dismissed(browser, frame)
+            }
+        )
     }
 
     /// Called to allow custom display of the quick menu for a windowless browser.
@@ -1604,17 +2134,21 @@ impl ContextMenuHandlerWrapper {
         edit_state_flags: cef_quick_menu_edit_state_flags_t,
         callback: *mut cef_run_quick_menu_callback_t
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let frame = Frame::from_ptr_unchecked(frame);
-        let location = (*location).into();
-        let size = (*size).into();
-        let edit_state_flags = edit_state_flags.into();
-        let callback = RunQuickMenuCallback::from_ptr_unchecked(callback);
+        catch_unwind_or("ContextMenuHandlerWrapper::c_run_quick_menu", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let frame = Frame::from_ptr_unchecked(frame);
+            let location = (*location).into();
+            let size = (*size).into();
+            let edit_state_flags = edit_state_flags.into();
+            let callback = RunQuickMenuCallback::from_ptr_unchecked(callback);
 
-        this.0
-            .run_quick_menu(browser, frame, &location, &size, edit_state_flags, callback)
-            as c_int
+            this.0
+                .run_quick_menu(browser, frame, &location, &size, edit_state_flags, callback)
+                as c_int
+        })
     }
 
     /// Called to execute a command selected from the quick menu for a windowless
@@ -1628,14 +2162,23 @@ impl ContextMenuHandlerWrapper {
         command_id: c_int,
         event_flags: cef_event_flags_t
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let frame = Frame::from_ptr_unchecked(frame);
-        let command_id = MenuCommandId::new(command_id);
-        let event_flags = event_flags.into();
-
-        this.0
-            .on_quick_menu_command(browser, frame, command_id, event_flags) as c_int
+        catch_unwind_or(
+            "ContextMenuHandlerWrapper::c_on_quick_menu_command",
+            0,
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+                let frame = Frame::from_ptr_unchecked(frame);
+                let command_id = MenuCommandId::new(command_id);
+                let event_flags = event_flags.into();
+
+                this.0
+                    .on_quick_menu_command(browser, frame, command_id, event_flags)
+                    as c_int
+            }
+        )
     }
 
     /// Called when the quick menu for a windowless browser is dismissed
@@ -1645,16 +2188,24 @@ impl ContextMenuHandlerWrapper {
         browser: *mut cef_browser_t,
         frame: *mut cef_frame_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let frame = Frame::from_ptr_unchecked(frame);
-
-        this.0
-            .on_quick_menu_dismissed(browser, frame)
+        catch_unwind_or(
+            "ContextMenuHandlerWrapper::c_on_quick_menu_dismissed",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+                let frame = Frame::from_ptr_unchecked(frame);
+
+                this.0
+                    .on_quick_menu_dismissed(browser, frame)
+            }
+        )
     }
 }
 
-impl Wrappable for ContextMenuHandlerWrapper {
+impl<C: ContextMenuHandlerCallbacks> Wrappable for ContextMenuHandlerWrapper<C> {
     type Cef = cef_context_menu_handler_t;
 
     /// Converts this to a smart pointer.
@@ -0,0 +1,133 @@
+use crate::{
+    ref_counted_ptr, try_c, unwind::catch_unwind_or, CefString, CompletionCallback, RefCountedPtr,
+    Result, Wrappable, Wrapped
+};
+use cef_ui_sys::{
+    cef_cookie_manager_get_global_manager, cef_cookie_manager_t, cef_delete_cookies_callback_t
+};
+use parking_lot::Mutex;
+use std::{
+    ffi::c_int,
+    mem::zeroed,
+    ptr::{null, null_mut}
+};
+
+// Structure to implement to be notified of asynchronous completion via
+// cef_cookie_manager_t::delete_cookies().
+ref_counted_ptr!(DeleteCookiesCallback, cef_delete_cookies_callback_t);
+
+impl DeleteCookiesCallback {
+    pub fn new(f: impl FnOnce(usize) + Send + 'static) -> Self {
+        Self(DeleteCookiesCallbackWrapper::new(f).wrap())
+    }
+}
+
+/// Translates CEF -> Rust callbacks.
+struct DeleteCookiesCallbackWrapper(Mutex<Option<Box<dyn FnOnce(usize) + Send + 'static>>>);
+
+impl DeleteCookiesCallbackWrapper {
+    pub fn new(f: impl FnOnce(usize) + Send + 'static) -> Self {
+        Self(Mutex::new(Some(Box::new(f))))
+    }
+
+    /// Method that will be called upon completion. |num_deleted| will be the
+    /// number of cookies that were deleted.
+    unsafe extern "C" fn c_on_complete(
+        this: *mut cef_delete_cookies_callback_t,
+        num_deleted: c_int
+    ) {
+        catch_unwind_or("DeleteCookiesCallbackWrapper::c_on_complete", (), || {
+            let this: &Self = Wrapped::wrappable(this);
+
+            if let Some(f) = this.0.lock().take() {
+                f(num_deleted.max(0) as usize);
+            }
+        })
+    }
+}
+
+impl Wrappable for DeleteCookiesCallbackWrapper {
+    type Cef = cef_delete_cookies_callback_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<Self::Cef> {
+        RefCountedPtr::wrap(
+            cef_delete_cookies_callback_t {
+                base:        unsafe { zeroed() },
+                on_complete: Some(Self::c_on_complete)
+            },
+            self
+        )
+    }
+}
+
+// Structure used for managing cookies. The functions of this structure may be
+// called on any thread unless otherwise indicated.
+ref_counted_ptr!(CookieManager, cef_cookie_manager_t);
+
+impl CookieManager {
+    /// Returns the global cookie manager. By default data will be stored at
+    /// cef_settings_t.cache_path if specified or in memory otherwise. If
+    /// |callback| is non-NULL it will be executed asnychronously on the UI
+    /// thread after the manager's storage has been initialized. Using this
+    /// function is equivalent to calling
+    /// RequestContext::get_global().get_cookie_manager().
+    pub fn get_global(callback: Option<CompletionCallback>) -> Option<Self> {
+        unsafe {
+            Self::from_ptr(cef_cookie_manager_get_global_manager(
+                callback
+                    .map(|callback| callback.into_raw())
+                    .unwrap_or_else(null_mut)
+            ))
+        }
+    }
+
+    /// Delete all cookies that match the specified parameters. If both |url| and
+    /// |cookie_name| values are specified all host and domain cookies matching
+    /// both will be deleted. If only |url| is specified all host cookies (but not
+    /// domain cookies) irrespective of path will be deleted. If |url| is None all
+    /// cookies for all hosts and domains will be deleted. If |callback| is
+    /// non-NULL it will be executed asnychronously on the UI thread after the
+    /// cookies have been deleted. Returns false (0) if a non-NULL invalid URL is
+    /// specified or if cookies cannot be accessed. Cookies can alternately be
+    /// deleted using the Visit*Cookies() functions.
+    pub fn delete_cookies(
+        &self,
+        url: Option<&str>,
+        cookie_name: Option<&str>,
+        callback: Option<DeleteCookiesCallback>
+    ) -> Result<bool> {
+        try_c!(self, delete_cookies, {
+            let url = url.map(CefString::new);
+            let cookie_name = cookie_name.map(CefString::new);
+
+            Ok(delete_cookies(
+                self.as_ptr(),
+                url.as_ref()
+                    .map(|url| url.as_ptr())
+                    .unwrap_or_else(null),
+                cookie_name
+                    .as_ref()
+                    .map(|cookie_name| cookie_name.as_ptr())
+                    .unwrap_or_else(null),
+                callback
+                    .map(|callback| callback.into_raw())
+                    .unwrap_or_else(null_mut)
+            ) != 0)
+        })
+    }
+
+    /// Flush the backing store (if any) to disk. If |callback| is non-NULL it
+    /// will be executed asnychronously on the UI thread after the flush is
+    /// complete. Returns false (0) if cookies cannot be accessed.
+    pub fn flush_store(&self, callback: Option<CompletionCallback>) -> Result<bool> {
+        try_c!(self, flush_store, {
+            Ok(flush_store(
+                self.as_ptr(),
+                callback
+                    .map(|callback| callback.into_raw())
+                    .unwrap_or_else(null_mut)
+            ) != 0)
+        })
+    }
+}
@@ -0,0 +1,462 @@
+use crate::{CefString, Result};
+use anyhow::anyhow;
+use cef_ui_sys::{
+    cef_crash_reporting_enabled, cef_dump_without_crashing, cef_dump_without_crashing_unthrottled,
+    cef_set_crash_key_value
+};
+use std::{
+    ffi::{c_int, c_longlong, CString},
+    fmt::Write as _,
+    fs::write,
+    panic::Location,
+    path::Path,
+    time::Duration
+};
+
+/// The name of the crash reporter configuration file. On Windows and Linux it
+/// must be placed next to the main application executable. On macOS it must be
+/// placed in the top-level app bundle Resources directory (e.g.
+/// "<appname>.app/Contents/Resources").
+pub const CRASH_REPORTER_CFG: &str = "crash_reporter.cfg";
+
+/// Crash reporting is configured using an INI-style config file named
+/// "crash_reporter.cfg". This function will return true (1) if crash reporting
+/// is enabled.
+pub fn crash_reporting_enabled() -> bool {
+    unsafe { cef_crash_reporting_enabled() != 0 }
+}
+
+/// Sets or clears a specific key-value pair from the crash metadata. The key
+/// must be registered in the [CrashKeys] section of crash_reporter.cfg and the
+/// value will be truncated to the registered size. An empty value clears the
+/// key.
+pub fn set_crash_key_value(key: &str, value: &str) {
+    let key = CefString::new(key);
+    let value = CefString::new(value);
+
+    unsafe { cef_set_crash_key_value(key.as_ptr(), value.as_ptr()) };
+}
+
+/// Generates a crash dump of the current process without crashing it, e.g. to
+/// report an unexpected state in production. Dumps from the same call site are
+/// throttled to one per |min_interval|. Returns true if a dump was generated,
+/// which requires crash reporting to be enabled.
+#[track_caller]
+pub fn dump_without_crashing(min_interval: Duration) -> bool {
+    if !crash_reporting_enabled() {
+        return false;
+    }
+
+    let location = Location::caller();
+    let function_name = CString::default();
+    let file_name = CString::new(location.file()).unwrap_or_default();
+
+    unsafe {
+        cef_dump_without_crashing(
+            min_interval.as_millis() as c_longlong,
+            function_name.as_ptr(),
+            file_name.as_ptr(),
+            location.line() as c_int
+        ) != 0
+    }
+}
+
+/// Like dump_without_crashing, but without throttling. Returns true if a dump
+/// was generated, which requires crash reporting to be enabled.
+pub fn dump_without_crashing_unthrottled() -> bool {
+    match crash_reporting_enabled() {
+        true => unsafe { cef_dump_without_crashing_unthrottled() != 0 },
+        false => false
+    }
+}
+
+/// A crash key registered with CrashReporterConfig::register_crash_key. Crash
+/// keys can be set in any process, so both browser and renderer crashes can
+/// carry application context, e.g. the current route or user action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrashKey {
+    name: &'static str,
+    size: CrashKeySize
+}
+
+impl CrashKey {
+    pub const fn new(name: &'static str, size: CrashKeySize) -> Self {
+        Self { name, size }
+    }
+
+    /// The name of the key in the [CrashKeys] section of crash_reporter.cfg.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The maximum size of the value.
+    pub fn size(&self) -> CrashKeySize {
+        self.size
+    }
+
+    /// Sets the value, truncated to the maximum size on a character boundary.
+    pub fn set(&self, value: &str) {
+        set_crash_key_value(self.name, truncate(value, self.size.max_len()));
+    }
+
+    /// Clears the value.
+    pub fn clear(&self) {
+        set_crash_key_value(self.name, "");
+    }
+
+    /// Sets the value until the returned guard is dropped.
+    pub fn scoped(&self, value: &str) -> ScopedCrashKey {
+        self.set(value);
+
+        ScopedCrashKey(*self)
+    }
+}
+
+/// Clears a crash key when dropped. See CrashKey::scoped.
+#[must_use]
+#[derive(Debug)]
+pub struct ScopedCrashKey(CrashKey);
+
+impl Drop for ScopedCrashKey {
+    fn drop(&mut self) {
+        self.0.clear();
+    }
+}
+
+/// Truncates |value| to at most |max_len| bytes without splitting a character.
+fn truncate(value: &str, max_len: usize) -> &str {
+    match value.len() <= max_len {
+        true => value,
+        false => {
+            let end = (0..=max_len)
+                .rev()
+                .find(|i| value.is_char_boundary(*i))
+                .unwrap_or(0);
+
+            &value[..end]
+        }
+    }
+}
+
+/// The maximum size of a crash key value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrashKeySize {
+    /// Up to 64 bytes.
+    Small,
+
+    /// Up to 256 bytes.
+    Medium,
+
+    /// Up to 1024 bytes.
+    Large
+}
+
+impl CrashKeySize {
+    /// The maximum length of a value in bytes.
+    pub fn max_len(&self) -> usize {
+        match self {
+            CrashKeySize::Small => 64,
+            CrashKeySize::Medium => 256,
+            CrashKeySize::Large => 1024
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            CrashKeySize::Small => "small",
+            CrashKeySize::Medium => "medium",
+            CrashKeySize::Large => "large"
+        }
+    }
+}
+
+/// Builder for the contents of crash_reporter.cfg. Crash reporting is only
+/// enabled if this file exists when the application starts and |server_url|
+/// is specified (or crash dumps are only stored locally if it is not).
+#[derive(Debug, Default, Clone)]
+pub struct CrashReporterConfig {
+    product_name:                     Option<String>,
+    product_version:                  Option<String>,
+    app_name:                         Option<String>,
+    external_handler:                 Option<String>,
+    browser_crash_forwarding_enabled: Option<bool>,
+    server_url:                       Option<String>,
+    rate_limit_enabled:               Option<bool>,
+    max_uploads_per_day:              Option<u32>,
+    max_database_size_in_mb:          Option<u32>,
+    max_database_age_in_days:         Option<u32>,
+    crash_keys:                       Vec<(String, CrashKeySize)>
+}
+
+impl CrashReporterConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The product name reported to the crash server. Defaults to "cef".
+    pub fn product_name(mut self, value: &str) -> Self {
+        self.product_name = Some(value.to_string());
+        self
+    }
+
+    /// The product version reported to the crash server. Defaults to the CEF
+    /// version.
+    pub fn product_version(mut self, value: &str) -> Self {
+        self.product_version = Some(value.to_string());
+        self
+    }
+
+    /// Windows and Linux only. The name of the application directory used to
+    /// store crash dumps. Defaults to "CEF".
+    pub fn app_name(mut self, value: &str) -> Self {
+        self.app_name = Some(value.to_string());
+        self
+    }
+
+    /// Windows only. Name of an external crash handler executable, relative to
+    /// the main executable, to use instead of the main executable.
+    pub fn external_handler(mut self, value: &str) -> Self {
+        self.external_handler = Some(value.to_string());
+        self
+    }
+
+    /// macOS only. If true, browser process crashes are forwarded to the
+    /// system crash reporter.
+    pub fn browser_crash_forwarding_enabled(mut self, value: bool) -> Self {
+        self.browser_crash_forwarding_enabled = Some(value);
+        self
+    }
+
+    /// The crash server upload URL. Crash dumps are only stored locally if
+    /// this is not specified.
+    pub fn server_url(mut self, value: &str) -> Self {
+        self.server_url = Some(value.to_string());
+        self
+    }
+
+    /// If true, the number of crash dump uploads is limited. Defaults to true.
+    pub fn rate_limit_enabled(mut self, value: bool) -> Self {
+        self.rate_limit_enabled = Some(value);
+        self
+    }
+
+    /// The maximum number of crash dump uploads per day when rate limiting is
+    /// enabled. Defaults to 5.
+    pub fn max_uploads_per_day(mut self, value: u32) -> Self {
+        self.max_uploads_per_day = Some(value);
+        self
+    }
+
+    /// The maximum size of the crash dump database in megabytes. Defaults to 20.
+    pub fn max_database_size_in_mb(mut self, value: u32) -> Self {
+        self.max_database_size_in_mb = Some(value);
+        self
+    }
+
+    /// The maximum age of crash dumps in days. Defaults to 5.
+    pub fn max_database_age_in_days(mut self, value: u32) -> Self {
+        self.max_database_age_in_days = Some(value);
+        self
+    }
+
+    /// Registers a crash key that can later be set with set_crash_key_value.
+    /// Key names must be unique and may only contain ASCII letters, digits,
+    /// dashes and underscores.
+    pub fn crash_key(mut self, name: &str, size: CrashKeySize) -> Result<Self> {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(anyhow!("Invalid crash key name: {}", name).into());
+        }
+
+        if self
+            .crash_keys
+            .iter()
+            .any(|(key, _)| key == name)
+        {
+            return Err(anyhow!("Duplicate crash key name: {}", name).into());
+        }
+
+        self.crash_keys
+            .push((name.to_string(), size));
+
+        Ok(self)
+    }
+
+    /// Registers |key| so that it can be set at runtime with CrashKey::set.
+    pub fn register_crash_key(self, key: &CrashKey) -> Result<Self> {
+        self.crash_key(key.name, key.size)
+    }
+
+    /// Renders the configuration in the INI format expected by CEF.
+    pub fn to_ini(&self) -> String {
+        let mut ini = String::from("[Config]\n");
+
+        let entries = [
+            ("ProductName", self.product_name.clone()),
+            ("ProductVersion", self.product_version.clone()),
+            ("AppName", self.app_name.clone()),
+            ("ExternalHandler", self.external_handler.clone()),
+            (
+                "BrowserCrashForwardingEnabled",
+                self.browser_crash_forwarding_enabled
+                    .map(|v| v.to_string())
+            ),
+            ("ServerURL", self.server_url.clone()),
+            (
+                "RateLimitEnabled",
+                self.rate_limit_enabled
+                    .map(|v| v.to_string())
+            ),
+            (
+                "MaxUploadsPerDay",
+                self.max_uploads_per_day
+                    .map(|v| v.to_string())
+            ),
+            (
+                "MaxDatabaseSizeInMb",
+                self.max_database_size_in_mb
+                    .map(|v| v.to_string())
+            ),
+            (
+                "MaxDatabaseAgeInDays",
+                self.max_database_age_in_days
+                    .map(|v| v.to_string())
+            )
+        ];
+
+        for (key, value) in entries {
+            if let Some(value) = value {
+                let _ = writeln!(ini, "{}={}", key, value);
+            }
+        }
+
+        if !self.crash_keys.is_empty() {
+            ini.push_str("\n[CrashKeys]\n");
+
+            for (name, size) in &self.crash_keys {
+                let _ = writeln!(ini, "{}={}", name, size.as_str());
+            }
+        }
+
+        ini
+    }
+
+    /// Writes crash_reporter.cfg into |dir|. See CRASH_REPORTER_CFG for where
+    /// the file must be placed on each platform.
+    pub fn write_to_dir(&self, dir: &Path) -> Result<()> {
+        write(dir.join(CRASH_REPORTER_CFG), self.to_ini())?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_ini_empty() {
+        assert_eq!(CrashReporterConfig::new().to_ini(), "[Config]\n");
+    }
+
+    #[test]
+    fn to_ini_all_entries() {
+        let ini = CrashReporterConfig::new()
+            .product_name("app")
+            .product_version("1.2.3")
+            .app_name("App")
+            .external_handler("crash_handler.exe")
+            .browser_crash_forwarding_enabled(true)
+            .server_url("https://crash.example.com/submit")
+            .rate_limit_enabled(false)
+            .max_uploads_per_day(10)
+            .max_database_size_in_mb(30)
+            .max_database_age_in_days(7)
+            .crash_key("route", CrashKeySize::Medium)
+            .unwrap()
+            .register_crash_key(&CrashKey::new("user_action", CrashKeySize::Small))
+            .unwrap()
+            .crash_key("state-dump", CrashKeySize::Large)
+            .unwrap()
+            .to_ini();
+
+        assert_eq!(
+            ini,
+            "[Config]\n\
+             ProductName=app\n\
+             ProductVersion=1.2.3\n\
+             AppName=App\n\
+             ExternalHandler=crash_handler.exe\n\
+             BrowserCrashForwardingEnabled=true\n\
+             ServerURL=https://crash.example.com/submit\n\
+             RateLimitEnabled=false\n\
+             MaxUploadsPerDay=10\n\
+             MaxDatabaseSizeInMb=30\n\
+             MaxDatabaseAgeInDays=7\n\
+             \n\
+             [CrashKeys]\n\
+             route=medium\n\
+             user_action=small\n\
+             state-dump=large\n"
+        );
+    }
+
+    #[test]
+    fn to_ini_skips_unset_entries() {
+        let ini = CrashReporterConfig::new()
+            .server_url("https://crash.example.com/submit")
+            .max_uploads_per_day(0)
+            .to_ini();
+
+        assert_eq!(
+            ini,
+            "[Config]\n\
+             ServerURL=https://crash.example.com/submit\n\
+             MaxUploadsPerDay=0\n"
+        );
+    }
+
+    #[test]
+    fn crash_key_invalid() {
+        let config = CrashReporterConfig::new();
+
+        assert!(
+            config
+                .clone()
+                .crash_key("", CrashKeySize::Small)
+                .is_err()
+        );
+        assert!(
+            config
+                .clone()
+                .crash_key("a=b", CrashKeySize::Small)
+                .is_err()
+        );
+        assert!(
+            config
+                .clone()
+                .crash_key("a b", CrashKeySize::Small)
+                .is_err()
+        );
+        assert!(
+            config
+                .crash_key("route", CrashKeySize::Small)
+                .unwrap()
+                .crash_key("route", CrashKeySize::Large)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn truncate_on_char_boundary() {
+        assert_eq!(truncate("hello", 10), "hello");
+        assert_eq!(truncate("hello", 5), "hello");
+        assert_eq!(truncate("hello", 3), "hel");
+        assert_eq!(truncate("héllo", 2), "h");
+        assert_eq!(truncate("héllo", 3), "hé");
+        assert_eq!(truncate("€", 2), "");
+    }
+}
@@ -0,0 +1,366 @@
+use crate::{
+    register_scheme_handler_factory, Browser, Callback, EmbeddedAssets, ErrorCode, Frame, Request,
+    RequestContext, ResourceHandler, ResourceHandlerCallbacks, ResourceReadBuffer,
+    ResourceReadCallback, Response, Result, SchemeHandlerFactory, SchemeHandlerFactoryCallbacks,
+    UrlRequest, UrlRequestClient, UrlRequestClientCallbacks, UrlRequestStatus
+};
+use bytes::{Buf, Bytes};
+use parking_lot::{Mutex, RwLock};
+use std::{collections::VecDeque, sync::Arc};
+use tracing::error;
+
+/// Serves a custom scheme from embedded assets or, in development, by proxying
+/// requests to a local dev server (e.g. Vite on http://localhost:5173). Only
+/// the origin changes, so app://localhost/some/page.html is fetched from
+/// http://localhost:5173/some/page.html and the application keeps the same URLs
+/// in development and production. The dev server can be switched on and off at
+/// runtime with set_dev_server, and reloading the page picks up the change.
+/// Clones share the same state.
+///
+/// The page still connects to the dev server directly for hot reload, so the
+/// dev server must be told where to accept that connection, e.g. with Vite's
+/// server.hmr.host and server.hmr.clientPort options. Request bodies are not
+/// proxied.
+#[derive(Clone)]
+pub struct DevServerProxy {
+    assets:     Arc<EmbeddedAssets>,
+    dev_server: Arc<RwLock<Option<String>>>
+}
+
+impl DevServerProxy {
+    /// Serves |assets| until a dev server is set.
+    pub fn new(assets: EmbeddedAssets) -> Self {
+        Self {
+            assets:     Arc::new(assets),
+            dev_server: Arc::default()
+        }
+    }
+
+    /// Proxies requests to the origin |dev_server|, e.g. http://localhost:5173,
+    /// or serves the embedded assets again if it is None.
+    pub fn set_dev_server(&self, dev_server: Option<&str>) {
+        *self.dev_server.write() = dev_server.map(|dev_server| {
+            dev_server
+                .trim_end_matches('/')
+                .to_string()
+        });
+    }
+
+    /// Returns the dev server requests are proxied to, if any.
+    pub fn get_dev_server(&self) -> Option<String> {
+        self.dev_server.read().clone()
+    }
+
+    /// Converts this to a SchemeHandlerFactory.
+    pub fn factory(&self) -> SchemeHandlerFactory {
+        SchemeHandlerFactory::new(self.clone())
+    }
+
+    /// Registers this as the handler of |scheme_name| and |domain_name| with
+    /// the global request context. Returns false if an error occurs.
+    pub fn register(&self, scheme_name: &str, domain_name: Option<&str>) -> bool {
+        register_scheme_handler_factory(scheme_name, domain_name, Some(self.factory()))
+    }
+
+    /// Registers this as the handler of |scheme_name| and |domain_name| with
+    /// |request_context| only, so that other contexts can serve the same
+    /// scheme differently. Returns false if an error occurs.
+    pub fn register_with(
+        &self,
+        request_context: &RequestContext,
+        scheme_name: &str,
+        domain_name: Option<&str>
+    ) -> Result<bool> {
+        request_context.register_scheme_handler_factory(
+            scheme_name,
+            domain_name,
+            Some(self.factory())
+        )
+    }
+
+    /// Returns a handler that serves |request|. This can also be used from
+    /// ResourceRequestHandlerCallbacks::get_resource_handler.
+    pub fn handle(&self, request: &Request) -> ResourceHandler {
+        match self.get_dev_server() {
+            Some(dev_server) => ResourceHandler::new(ProxyResponse {
+                dev_server,
+                state: Arc::default()
+            }),
+            None => self.assets.handle(request)
+        }
+    }
+}
+
+impl SchemeHandlerFactoryCallbacks for DevServerProxy {
+    fn create(
+        &mut self,
+        _browser: Option<Browser>,
+        _frame: Option<Frame>,
+        _scheme_name: &str,
+        request: Request
+    ) -> Option<ResourceHandler> {
+        Some(self.handle(&request))
+    }
+}
+
+/// The state shared between a proxied response and its URL request. The URL
+/// request references the state through its client, so it is only kept while
+/// it is in flight.
+#[derive(Default)]
+struct ProxyState {
+    request:  Option<UrlRequest>,
+    open:     Option<Callback>,
+    response: Option<Response>,
+    chunks:   VecDeque<Bytes>,
+    complete: bool,
+    error:    Option<ErrorCode>,
+    pending:  Option<(ResourceReadBuffer, ResourceReadCallback)>
+}
+
+impl ProxyState {
+    /// Copies the next available data into |buffer|. Returns None if the read
+    /// must wait for more data, Some(0) once the response is complete and the
+    /// negative error code if the request failed.
+    fn fill(&mut self, buffer: &mut ResourceReadBuffer) -> Option<i32> {
+        while let Some(chunk) = self.chunks.front_mut() {
+            let count = buffer.write(chunk);
+
+            chunk.advance(count);
+
+            if chunk.is_empty() {
+                self.chunks.pop_front();
+            }
+
+            if count > 0 {
+                return Some(count as i32);
+            }
+        }
+
+        match (self.complete, self.error) {
+            (true, Some(error)) => Some(error.code()),
+            (true, None) => Some(0),
+            (false, _) => None
+        }
+    }
+
+    /// Called when the response headers are known or the request failed.
+    /// Returns the callback that continues opening the response.
+    fn on_response(&mut self, request: &UrlRequest) -> Option<Callback> {
+        let open = self.open.take()?;
+
+        self.response = match self.error {
+            Some(_) => None,
+            None => request
+                .get_response()
+                .ok()
+                .flatten()
+        };
+
+        Some(open)
+    }
+
+    /// Completes the pending read, if there is one and data is available.
+    /// Returns the callback to execute and the number of bytes read, or the
+    /// negative error code.
+    fn resume(&mut self) -> Option<(ResourceReadCallback, i32)> {
+        let (mut buffer, callback) = self.pending.take()?;
+
+        match self.fill(&mut buffer) {
+            Some(count) => Some((callback, count)),
+            None => {
+                self.pending = Some((buffer, callback));
+
+                None
+            }
+        }
+    }
+}
+
+/// Proxies a single request to the dev server.
+struct ProxyResponse {
+    dev_server: String,
+    state:      Arc<Mutex<ProxyState>>
+}
+
+impl ProxyResponse {
+    /// Starts fetching |request| from the dev server.
+    fn start(&self, request: &Request) -> Result<UrlRequest> {
+        let url = request.get_url()?;
+        let path = url
+            .split_once("://")
+            .and_then(|(_, rest)| {
+                rest.find('/')
+                    .map(|index| &rest[index..])
+            })
+            .unwrap_or("/");
+        let proxied = Request::new();
+
+        proxied.set_url(&format!("{}{}", self.dev_server, path))?;
+        proxied.set_method(&request.get_method()?)?;
+        proxied.set_header_map(&request.get_header_map()?)?;
+
+        Ok(UrlRequest::new(
+            proxied,
+            UrlRequestClient::new(ProxyClient(self.state.clone())),
+            None
+        ))
+    }
+}
+
+impl ResourceHandlerCallbacks for ProxyResponse {
+    fn open(&mut self, request: Request, handle_request: &mut bool, callback: Callback) -> bool {
+        self.state.lock().open = Some(callback);
+
+        match self.start(&request) {
+            Ok(request) => {
+                let mut state = self.state.lock();
+
+                // Keeping a request that already completed would leak it and
+                // the state through the reference cycle.
+                if !state.complete {
+                    state.request = Some(request);
+                }
+
+                // Opening continues once the dev server responds.
+                *handle_request = false;
+
+                true
+            },
+            Err(e) => {
+                error!("Failed to proxy request to dev server: {}", e);
+
+                *handle_request = true;
+
+                false
+            }
+        }
+    }
+
+    fn get_response_headers(
+        &mut self,
+        response: Response,
+        response_length: &mut i64,
+        _redirect_url: &mut Option<String>
+    ) {
+        let (upstream, error) = {
+            let mut state = self.state.lock();
+
+            (state.response.take(), state.error)
+        };
+        let result = match (upstream, error) {
+            (Some(upstream), None) => copy_response(&upstream, &response),
+            (_, error) => response.set_error(error.unwrap_or(ErrorCode::ConnectionRefused))
+        };
+
+        if let Err(e) = result {
+            error!("Failed to set dev server response headers: {}", e);
+        }
+
+        // The body may have been decoded, so the upstream length is not used.
+        *response_length = -1;
+    }
+
+    fn read(
+        &mut self,
+        mut data_out: ResourceReadBuffer,
+        bytes_read: &mut i32,
+        callback: ResourceReadCallback
+    ) -> bool {
+        let mut state = self.state.lock();
+
+        match state.fill(&mut data_out) {
+            Some(count) => {
+                *bytes_read = count;
+
+                count > 0
+            },
+            None => {
+                *bytes_read = 0;
+                state.pending = Some((data_out, callback));
+
+                true
+            }
+        }
+    }
+
+    fn cancel(&mut self) {
+        let request = {
+            let mut state = self.state.lock();
+
+            state.pending = None;
+            state.request.take()
+        };
+
+        if let Some(request) = request {
+            let _ = request.cancel();
+        }
+    }
+}
+
+/// Receives the dev server response.
+struct ProxyClient(Arc<Mutex<ProxyState>>);
+
+impl ProxyClient {
+    /// Executes the callbacks returned by the state, outside of its lock.
+    fn continue_with(open: Option<Callback>, read: Option<(ResourceReadCallback, i32)>) {
+        if let Some(open) = open {
+            let _ = open.cont();
+        }
+
+        if let Some((callback, count)) = read {
+            let _ = callback.cont(count);
+        }
+    }
+}
+
+impl UrlRequestClientCallbacks for ProxyClient {
+    fn on_request_complete(&mut self, request: UrlRequest) {
+        let (open, read) = {
+            let mut state = self.0.lock();
+
+            state.complete = true;
+            state.request = None;
+            state.error = request_error(&request);
+
+            (state.on_response(&request), state.resume())
+        };
+
+        Self::continue_with(open, read);
+    }
+
+    fn on_download_data(&mut self, request: UrlRequest, data: &[u8]) {
+        let (open, read) = {
+            let mut state = self.0.lock();
+
+            state
+                .chunks
+                .push_back(Bytes::copy_from_slice(data));
+
+            (state.on_response(&request), state.resume())
+        };
+
+        Self::continue_with(open, read);
+    }
+}
+
+/// Returns the error |request| failed with, or None if it succeeded.
+fn request_error(request: &UrlRequest) -> Option<ErrorCode> {
+    match request.get_request_status() {
+        Ok(UrlRequestStatus::Success) => None,
+        _ => match request.get_request_error() {
+            Ok(ErrorCode::None) | Err(_) => Some(ErrorCode::Failed),
+            Ok(error) => Some(error)
+        }
+    }
+}
+
+/// Copies the status and headers of |from| to |to|.
+fn copy_response(from: &Response, to: &Response) -> Result<()> {
+    to.set_status(from.get_status()?)?;
+    to.set_status_text(&from.get_status_text()?)?;
+    to.set_mime_type(&from.get_mime_type()?)?;
+    to.set_charset(&from.get_charset()?)?;
+    to.set_header_map(&from.get_header_map()?)?;
+
+    Ok(())
+}
@@ -0,0 +1,201 @@
+#[cfg(any(feature = "osr", feature = "views"))]
+use crate::Client;
+#[cfg(feature = "views")]
+use crate::{
+    Browser, BrowserView, BrowserViewDelegate, BrowserViewDelegateCallbacks, ChromeToolbarType,
+    GestureCommand, Panel, Size, View, ViewDelegateCallbacks
+};
+use crate::{
+    BrowserHost, BrowserSettings, ClientBuilder, NativeWindowHandle, Point, Rect, Result,
+    WindowInfo
+};
+#[cfg(feature = "views")]
+use tracing::error;
+
+impl BrowserHost {
+    /// Open DevTools as a child of |parent|, a native window (HWND, NSView or
+    /// X11 window) owned by the application, at |bounds| in the coordinates of
+    /// |parent|. This allows the inspector to be docked next to the inspected
+    /// page. The application is responsible for resizing the DevTools window
+    /// along with |parent|. If |inspect_element_at| is set the element at that
+    /// location will be inspected.
+    pub fn show_dev_tools_as_child(
+        &self,
+        parent: NativeWindowHandle,
+        bounds: &Rect,
+        inspect_element_at: Option<Point>
+    ) -> Result<()> {
+        self.show_dev_tools(
+            &WindowInfo::as_child(parent, bounds),
+            ClientBuilder::new().build(),
+            &BrowserSettings::new(),
+            inspect_element_at
+        )
+    }
+
+    /// Open DevTools using windowless (off-screen) rendering, so that it can be
+    /// composited into the application's own UI like any other windowless
+    /// browser. |client| must provide a RenderHandler that receives the
+    /// DevTools frames, and input is forwarded through the DevTools browser's
+    /// host. |parent|, if any, is used to identify monitor info and to act as
+    /// the parent window for dialogs and context menus.
+    #[cfg(feature = "osr")]
+    pub fn show_dev_tools_windowless(
+        &self,
+        parent: Option<NativeWindowHandle>,
+        client: Client,
+        settings: &BrowserSettings,
+        inspect_element_at: Option<Point>
+    ) -> Result<()> {
+        self.show_dev_tools(
+            &WindowInfo::as_windowless(parent),
+            client,
+            settings,
+            inspect_element_at
+        )
+    }
+}
+
+/// A BrowserViewDelegateCallbacks implementation that adds the DevTools
+/// BrowserView of the inspected browser to |panel| instead of a window of its
+/// own, so that DevTools can be docked in a Views layout. Use it as the
+/// delegate of the inspected BrowserView and open DevTools with show_dev_tools
+/// and an empty WindowInfo. All other callbacks are forwarded to |delegate|.
+#[cfg(feature = "views")]
+pub struct DevToolsDock<C: BrowserViewDelegateCallbacks> {
+    delegate: C,
+    panel:    Panel
+}
+
+#[cfg(feature = "views")]
+impl<C: BrowserViewDelegateCallbacks> DevToolsDock<C> {
+    pub fn new(panel: Panel, delegate: C) -> Self {
+        Self { delegate, panel }
+    }
+
+    /// Converts this to a BrowserViewDelegate.
+    pub fn build(self) -> BrowserViewDelegate {
+        BrowserViewDelegate::new(self)
+    }
+
+    /// Adds |browser_view| to the panel.
+    fn dock(&self, browser_view: &BrowserView) -> Result<()> {
+        self.panel
+            .add_child_view(browser_view.as_view())?;
+        self.panel.layout()
+    }
+}
+
+#[cfg(feature = "views")]
+impl<C: BrowserViewDelegateCallbacks> ViewDelegateCallbacks for DevToolsDock<C> {
+    fn get_preferred_size(&mut self, view: View) -> Size {
+        self.delegate
+            .get_preferred_size(view)
+    }
+
+    fn get_minimum_size(&mut self, view: View) -> Size {
+        self.delegate.get_minimum_size(view)
+    }
+
+    fn get_maximum_size(&mut self, view: View) -> Size {
+        self.delegate.get_maximum_size(view)
+    }
+
+    fn get_height_for_width(&mut self, view: View, width: i32) -> i32 {
+        self.delegate
+            .get_height_for_width(view, width)
+    }
+
+    fn on_parent_view_changed(&mut self, view: View, added: bool, parent: View) {
+        self.delegate
+            .on_parent_view_changed(view, added, parent);
+    }
+
+    fn on_child_view_changed(&mut self, view: View, added: bool, child: View) {
+        self.delegate
+            .on_child_view_changed(view, added, child);
+    }
+
+    fn on_window_changed(&mut self, view: View, added: bool) {
+        self.delegate
+            .on_window_changed(view, added);
+    }
+
+    fn on_layout_changed(&mut self, view: View, new_bounds: &Rect) {
+        self.delegate
+            .on_layout_changed(view, new_bounds);
+    }
+
+    fn on_focus(&mut self, view: View) {
+        self.delegate.on_focus(view);
+    }
+
+    fn on_blur(&mut self, view: View) {
+        self.delegate.on_blur(view);
+    }
+}
+
+#[cfg(feature = "views")]
+impl<C: BrowserViewDelegateCallbacks> BrowserViewDelegateCallbacks for DevToolsDock<C> {
+    fn on_browser_created(&mut self, browser_view: BrowserView, browser: Browser) {
+        self.delegate
+            .on_browser_created(browser_view, browser);
+    }
+
+    fn on_browser_destroyed(&mut self, browser_view: BrowserView, browser: Browser) {
+        self.delegate
+            .on_browser_destroyed(browser_view, browser);
+    }
+
+    fn get_delegate_for_popup_browser_view(
+        &mut self,
+        browser_view: BrowserView,
+        settings: &BrowserSettings,
+        client: Client,
+        is_devtools: bool
+    ) -> Option<BrowserViewDelegate> {
+        self.delegate
+            .get_delegate_for_popup_browser_view(browser_view, settings, client, is_devtools)
+    }
+
+    fn on_popup_browser_view_created(
+        &mut self,
+        browser_view: BrowserView,
+        popup_browser_view: BrowserView,
+        is_devtools: bool
+    ) -> bool {
+        if !is_devtools {
+            return self
+                .delegate
+                .on_popup_browser_view_created(browser_view, popup_browser_view, is_devtools);
+        }
+
+        match self.dock(&popup_browser_view) {
+            Ok(()) => true,
+            Err(e) => {
+                error!("Failed to dock DevTools: {}", e);
+
+                false
+            }
+        }
+    }
+
+    fn get_chrome_toolbar_type(&mut self, browser_view: BrowserView) -> ChromeToolbarType {
+        self.delegate
+            .get_chrome_toolbar_type(browser_view)
+    }
+
+    fn use_frameless_window_for_picture_in_picture(&mut self, browser_view: BrowserView) -> bool {
+        self.delegate
+            .use_frameless_window_for_picture_in_picture(browser_view)
+    }
+
+    fn on_gesture_command(
+        &mut self,
+        browser_view: BrowserView,
+        gesture_command: GestureCommand
+    ) -> bool {
+        self.delegate
+            .on_gesture_command(browser_view, gesture_command)
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{BrowserHost, PaintElementType, Point, Rect, Result, ScreenInfo, Size};
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc
+};
+
+/// The device scale factor of a windowless browser, shared between the
+/// application and its RenderHandlerCallbacks. Views are laid out in view
+/// coordinates (device-independent pixels) and painted in pixels, so returning
+/// screen_info from RenderHandlerCallbacks::get_screen_info makes CEF paint at
+/// the display's native resolution instead of being scaled up and blurry.
+/// Clones share the same factor.
+#[derive(Debug, Clone)]
+pub struct DeviceScaleFactor(Arc<AtomicU32>);
+
+impl DeviceScaleFactor {
+    /// A scale factor of |factor| physical pixels per view pixel. Values that
+    /// are not positive are treated as 1.0.
+    pub fn new(factor: f32) -> Self {
+        Self(Arc::new(AtomicU32::new(sanitize(factor).to_bits())))
+    }
+
+    /// Returns the current scale factor.
+    pub fn get(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    /// Changes the scale factor at runtime, e.g. when the window moves to a
+    /// display with a different density, and tells the browser of |host| to
+    /// query its screen info again and repaint. Does nothing if the factor did
+    /// not change.
+    pub fn set(&self, host: &BrowserHost, factor: f32) -> Result<()> {
+        let factor = sanitize(factor);
+        let previous = f32::from_bits(
+            self.0
+                .swap(factor.to_bits(), Ordering::Relaxed)
+        );
+
+        if previous == factor {
+            return Ok(());
+        }
+
+        host.notify_screen_info_changed()?;
+        host.was_resized()?;
+        host.invalidate(PaintElementType::View)
+    }
+
+    /// Returns screen info with the current scale factor for a screen covering
+    /// |rect| in view coordinates.
+    pub fn screen_info(&self, rect: Rect) -> ScreenInfo {
+        ScreenInfo::new(rect).device_scale_factor(self.get())
+    }
+
+    /// Converts |point| from view coordinates to pixels.
+    pub fn to_pixel_point(&self, point: &Point) -> Point {
+        let factor = self.get();
+
+        Point::new(
+            (point.x as f32 * factor).round() as i32,
+            (point.y as f32 * factor).round() as i32
+        )
+    }
+
+    /// Converts |point| from pixels to view coordinates.
+    pub fn to_view_point(&self, point: &Point) -> Point {
+        let factor = self.get();
+
+        Point::new(
+            (point.x as f32 / factor).round() as i32,
+            (point.y as f32 / factor).round() as i32
+        )
+    }
+
+    /// Converts |size| from view coordinates to pixels, e.g. to size the
+    /// buffer that on_paint fills.
+    pub fn to_pixel_size(&self, size: &Size) -> Size {
+        let factor = self.get();
+
+        Size::new(
+            (size.width as f32 * factor).ceil() as i32,
+            (size.height as f32 * factor).ceil() as i32
+        )
+    }
+
+    /// Converts |size| from pixels to view coordinates.
+    pub fn to_view_size(&self, size: &Size) -> Size {
+        let factor = self.get();
+
+        Size::new(
+            (size.width as f32 / factor).ceil() as i32,
+            (size.height as f32 / factor).ceil() as i32
+        )
+    }
+
+    /// Converts |rect| from view coordinates to the smallest pixel rectangle
+    /// that contains it.
+    pub fn to_pixel_rect(&self, rect: &Rect) -> Rect {
+        scale_rect(rect, self.get())
+    }
+
+    /// Converts |rect| from pixels, e.g. a dirty rectangle passed to on_paint,
+    /// to the smallest rectangle in view coordinates that contains it.
+    pub fn to_view_rect(&self, rect: &Rect) -> Rect {
+        scale_rect(rect, 1.0 / self.get())
+    }
+}
+
+impl Default for DeviceScaleFactor {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+/// Replaces scale factors that cannot be used with 1.0.
+fn sanitize(factor: f32) -> f32 {
+    match factor.is_finite() && factor > 0.0 {
+        true => factor,
+        false => 1.0
+    }
+}
+
+/// Scales |rect| by |factor|, rounding outwards.
+fn scale_rect(rect: &Rect, factor: f32) -> Rect {
+    let x = (rect.x as f32 * factor).floor() as i32;
+    let y = (rect.y as f32 * factor).floor() as i32;
+    let right = ((rect.x + rect.width) as f32 * factor).ceil() as i32;
+    let bottom = ((rect.y + rect.height) as f32 * factor).ceil() as i32;
+
+    Rect::new(x, y, right - x, bottom - y)
+}
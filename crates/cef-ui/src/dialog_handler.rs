@@ -0,0 +1,409 @@
+use crate::{
+    callbacks_builder, debug_assert_currently_on, ref_counted_ptr, try_c, unwind::catch_unwind_or,
+    Browser, CefString, CefStringList, RefCountedPtr, Result, ThreadId, Wrappable, Wrapped
+};
+use cef_ui_sys::{
+    cef_browser_t, cef_dialog_handler_t, cef_file_dialog_callback_t, cef_file_dialog_mode_t,
+    cef_run_file_dialog_callback_t, cef_string_list_t, cef_string_t
+};
+use parking_lot::Mutex;
+use std::{
+    ffi::c_int,
+    fmt::{self, Display, Formatter},
+    mem::zeroed
+};
+
+/// Supported file dialog modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileDialogMode {
+    /// Requires that the file exists before allowing the user to pick it.
+    Open,
+
+    /// Like Open, but allows picking multiple files to open.
+    OpenMultiple,
+
+    /// Like Open, but selects a folder to open.
+    OpenFolder,
+
+    /// Allows picking a nonexistent file, and prompts to overwrite if the file
+    /// already exists.
+    Save
+}
+
+impl FileDialogMode {
+    /// Returns true if the dialog may return more than one path.
+    pub fn allows_multiple(&self) -> bool {
+        matches!(self, FileDialogMode::OpenMultiple)
+    }
+}
+
+impl From<cef_file_dialog_mode_t> for FileDialogMode {
+    fn from(value: cef_file_dialog_mode_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_file_dialog_mode_t> for FileDialogMode {
+    fn from(value: &cef_file_dialog_mode_t) -> Self {
+        match value {
+            cef_file_dialog_mode_t::FILE_DIALOG_OPEN => FileDialogMode::Open,
+            cef_file_dialog_mode_t::FILE_DIALOG_OPEN_MULTIPLE => FileDialogMode::OpenMultiple,
+            cef_file_dialog_mode_t::FILE_DIALOG_OPEN_FOLDER => FileDialogMode::OpenFolder,
+            cef_file_dialog_mode_t::FILE_DIALOG_SAVE => FileDialogMode::Save
+        }
+    }
+}
+
+impl From<FileDialogMode> for cef_file_dialog_mode_t {
+    fn from(value: FileDialogMode) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&FileDialogMode> for cef_file_dialog_mode_t {
+    fn from(value: &FileDialogMode) -> Self {
+        match value {
+            FileDialogMode::Open => cef_file_dialog_mode_t::FILE_DIALOG_OPEN,
+            FileDialogMode::OpenMultiple => cef_file_dialog_mode_t::FILE_DIALOG_OPEN_MULTIPLE,
+            FileDialogMode::OpenFolder => cef_file_dialog_mode_t::FILE_DIALOG_OPEN_FOLDER,
+            FileDialogMode::Save => cef_file_dialog_mode_t::FILE_DIALOG_SAVE
+        }
+    }
+}
+
+/// Restricts the file types that can be selected in a file dialog. CEF passes
+/// these around as strings; this is the structured form of each variant.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AcceptFilter {
+    /// A lower-cased MIME type, e.g. "text/*" or "image/png".
+    MimeType(String),
+
+    /// A single file extension including the leading dot, e.g. ".txt".
+    Extension(String),
+
+    /// A description shown to the user together with the extensions it
+    /// covers, e.g. "Image Types|.png;.gif;.jpg".
+    Described {
+        description: String,
+        extensions:  Vec<String>
+    }
+}
+
+impl AcceptFilter {
+    /// Creates a MIME type filter. The MIME type is lower-cased.
+    pub fn mime_type(mime_type: &str) -> Self {
+        AcceptFilter::MimeType(mime_type.to_lowercase())
+    }
+
+    /// Creates an extension filter. A leading dot is added if missing.
+    pub fn extension(extension: &str) -> Self {
+        AcceptFilter::Extension(normalize_extension(extension))
+    }
+
+    /// Creates a filter with a |description| for |extensions|. A leading dot
+    /// is added to each extension if missing.
+    pub fn described(description: &str, extensions: &[&str]) -> Self {
+        AcceptFilter::Described {
+            description: description.to_string(),
+            extensions:  extensions
+                .iter()
+                .map(|extension| normalize_extension(extension))
+                .collect()
+        }
+    }
+
+    /// Parses a filter in the string form that CEF uses.
+    pub fn parse(filter: &str) -> Self {
+        let filter = filter.trim();
+
+        if let Some((description, extensions)) = filter.split_once('|') {
+            return AcceptFilter::Described {
+                description: description.to_string(),
+                extensions:  extensions
+                    .split(';')
+                    .map(str::trim)
+                    .filter(|extension| !extension.is_empty())
+                    .map(normalize_extension)
+                    .collect()
+            };
+        }
+
+        match filter.contains('/') {
+            true => AcceptFilter::MimeType(filter.to_string()),
+            false => AcceptFilter::Extension(normalize_extension(filter))
+        }
+    }
+
+    /// Parses every filter in |filters|.
+    pub fn parse_list(filters: &CefStringList) -> Vec<Self> {
+        filters
+            .iter()
+            .map(|filter| Self::parse(&String::from(filter)))
+            .collect()
+    }
+
+    /// Returns true if a file at |path| with MIME type |mime_type| passes this
+    /// filter. MIME types may end in a "/*" wildcard.
+    pub fn matches(&self, path: &str, mime_type: Option<&str>) -> bool {
+        let path = path.to_lowercase();
+
+        match self {
+            AcceptFilter::MimeType(filter) => mime_type.map_or(false, |mime_type| {
+                let mime_type = mime_type.to_lowercase();
+
+                match filter.strip_suffix("/*") {
+                    Some(prefix) => mime_type
+                        .split_once('/')
+                        .map_or(false, |(kind, _)| kind == prefix),
+                    None => mime_type == *filter
+                }
+            }),
+            AcceptFilter::Extension(extension) => path.ends_with(extension),
+            AcceptFilter::Described { extensions, .. } => extensions
+                .iter()
+                .any(|extension| path.ends_with(extension))
+        }
+    }
+}
+
+impl Display for AcceptFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AcceptFilter::MimeType(mime_type) => write!(f, "{}", mime_type),
+            AcceptFilter::Extension(extension) => write!(f, "{}", extension),
+            AcceptFilter::Described {
+                description,
+                extensions
+            } => write!(f, "{}|{}", description, extensions.join(";"))
+        }
+    }
+}
+
+impl From<&str> for AcceptFilter {
+    fn from(value: &str) -> Self {
+        Self::parse(value)
+    }
+}
+
+impl From<&[AcceptFilter]> for CefStringList {
+    fn from(value: &[AcceptFilter]) -> Self {
+        value
+            .iter()
+            .map(|filter| filter.to_string())
+            .collect()
+    }
+}
+
+/// Lower-cases |extension| and adds a leading dot if it is missing.
+fn normalize_extension(extension: &str) -> String {
+    let extension = extension.to_lowercase();
+
+    match extension.starts_with('.') {
+        true => extension,
+        false => format!(".{}", extension)
+    }
+}
+
+// Callback structure for asynchronous continuation of file dialog requests.
+ref_counted_ptr!(FileDialogCallback, cef_file_dialog_callback_t);
+
+impl FileDialogCallback {
+    /// Continue the file selection. |file_paths| should be a single value or a
+    /// list of values depending on the dialog mode. An NULL |file_paths| value
+    /// is treated the same as calling cancel().
+    pub fn cont<S: AsRef<str>>(&self, file_paths: &[S]) -> Result<()> {
+        try_c!(self, cont, {
+            let mut file_paths: CefStringList = file_paths
+                .iter()
+                .map(|path| path.as_ref())
+                .collect();
+
+            Ok(cont(self.as_ptr(), file_paths.as_mut_ptr()))
+        })
+    }
+
+    /// Cancel the file selection.
+    pub fn cancel(&self) -> Result<()> {
+        try_c!(self, cancel, { Ok(cancel(self.as_ptr())) })
+    }
+}
+
+// Callback structure for BrowserHost::run_file_dialog. The methods of this
+// structure will be called on the browser process UI thread.
+ref_counted_ptr!(RunFileDialogCallback, cef_run_file_dialog_callback_t);
+
+impl RunFileDialogCallback {
+    /// |f| is called with the selected paths, or with an empty vector if the
+    /// dialog was cancelled.
+    pub fn new(f: impl FnOnce(Vec<String>) + Send + 'static) -> Self {
+        Self(RunFileDialogCallbackWrapper::new(f).wrap())
+    }
+}
+
+/// Translates CEF -> Rust callbacks.
+struct RunFileDialogCallbackWrapper(Mutex<Option<Box<dyn FnOnce(Vec<String>) + Send + 'static>>>);
+
+impl RunFileDialogCallbackWrapper {
+    pub fn new(f: impl FnOnce(Vec<String>) + Send + 'static) -> Self {
+        Self(Mutex::new(Some(Box::new(f))))
+    }
+
+    /// Called asynchronously after the file dialog is dismissed. |file_paths|
+    /// will be a single value or a list of values depending on the dialog mode.
+    /// If the selection was cancelled |file_paths| will be NULL.
+    unsafe extern "C" fn c_on_file_dialog_dismissed(
+        this: *mut cef_run_file_dialog_callback_t,
+        file_paths: cef_string_list_t
+    ) {
+        catch_unwind_or(
+            "RunFileDialogCallbackWrapper::c_on_file_dialog_dismissed",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &Self = Wrapped::wrappable(this);
+                let file_paths =
+                    CefStringList::from_ptr(file_paths).map_or(Vec::new(), |s| s.into());
+
+                if let Some(f) = this.0.lock().take() {
+                    f(file_paths);
+                }
+            }
+        )
+    }
+}
+
+impl Wrappable for RunFileDialogCallbackWrapper {
+    type Cef = cef_run_file_dialog_callback_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<Self::Cef> {
+        RefCountedPtr::wrap(
+            cef_run_file_dialog_callback_t {
+                base:                     unsafe { zeroed() },
+                on_file_dialog_dismissed: Some(Self::c_on_file_dialog_dismissed)
+            },
+            self
+        )
+    }
+}
+
+/// Implement this structure to handle dialog events. The functions of this
+/// structure will be called on the browser process UI thread.
+pub trait DialogHandlerCallbacks: Send + Sync + 'static {
+    /// Called to run a file chooser dialog. |mode| represents the type of dialog
+    /// to display. |title| to the title to be used for the dialog and may be NULL
+    /// to show the default title ("Open" or "Save" depending on the mode).
+    /// |default_file_path| is the path with optional directory and/or file name
+    /// component that should be initially selected in the dialog.
+    /// |accept_filters| are used to restrict the selectable file types. To
+    /// display a custom dialog return true (1) and execute |callback| either
+    /// inline or at a later time. To display the default dialog return false
+    /// (0).
+    fn on_file_dialog(
+        &mut self,
+        _browser: Browser,
+        _mode: FileDialogMode,
+        _title: Option<&str>,
+        _default_file_path: Option<&str>,
+        _accept_filters: &[AcceptFilter],
+        _callback: FileDialogCallback
+    ) -> bool {
+        false
+    }
+}
+
+// Implement this structure to handle dialog events. The functions of this
+// structure will be called on the browser process UI thread.
+ref_counted_ptr!(DialogHandler, cef_dialog_handler_t);
+
+impl DialogHandler {
+    pub fn new<C: DialogHandlerCallbacks>(delegate: C) -> Self {
+        Self(DialogHandlerWrapper::new(delegate).wrap())
+    }
+}
+
+callbacks_builder! {
+    /// Builds a DialogHandler from closures. Callbacks that are not set fall
+    /// back to the default DialogHandlerCallbacks behavior.
+    DialogHandlerBuilder: DialogHandlerCallbacks => DialogHandler {
+        fn on_file_dialog(
+            browser: Browser,
+            mode: FileDialogMode,
+            title: Option<&str>,
+            default_file_path: Option<&str>,
+            accept_filters: &[AcceptFilter],
+            callback: FileDialogCallback
+        ) -> bool = false;
+    }
+}
+
+/// Translates CEF -> Rust callbacks.
+struct DialogHandlerWrapper<C: DialogHandlerCallbacks>(C);
+
+impl<C: DialogHandlerCallbacks> DialogHandlerWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
+    }
+
+    /// Called to run a file chooser dialog. |mode| represents the type of dialog
+    /// to display. |title| to the title to be used for the dialog and may be NULL
+    /// to show the default title ("Open" or "Save" depending on the mode).
+    /// |default_file_path| is the path with optional directory and/or file name
+    /// component that should be initially selected in the dialog.
+    /// |accept_filters| are used to restrict the selectable file types. To
+    /// display a custom dialog return true (1) and execute |callback| either
+    /// inline or at a later time. To display the default dialog return false
+    /// (0).
+    unsafe extern "C" fn c_on_file_dialog(
+        this: *mut cef_dialog_handler_t,
+        browser: *mut cef_browser_t,
+        mode: cef_file_dialog_mode_t,
+        title: *const cef_string_t,
+        default_file_path: *const cef_string_t,
+        accept_filters: cef_string_list_t,
+        callback: *mut cef_file_dialog_callback_t
+    ) -> c_int {
+        catch_unwind_or("DialogHandlerWrapper::c_on_file_dialog", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let title: Option<String> = CefString::from_ptr(title).map(|s| s.into());
+            let default_file_path: Option<String> =
+                CefString::from_ptr(default_file_path).map(|s| s.into());
+            let accept_filters = CefStringList::from_ptr(accept_filters)
+                .map_or(Vec::new(), AcceptFilter::parse_list);
+            let callback = FileDialogCallback::from_ptr_unchecked(callback);
+
+            this.0
+                .on_file_dialog(
+                    browser,
+                    mode.into(),
+                    title
+                        .as_deref()
+                        .filter(|s| !s.is_empty()),
+                    default_file_path
+                        .as_deref()
+                        .filter(|s| !s.is_empty()),
+                    &accept_filters,
+                    callback
+                ) as c_int
+        })
+    }
+}
+
+impl<C: DialogHandlerCallbacks> Wrappable for DialogHandlerWrapper<C> {
+    type Cef = cef_dialog_handler_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<cef_dialog_handler_t> {
+        RefCountedPtr::wrap(
+            cef_dialog_handler_t {
+                base:           unsafe { zeroed() },
+                on_file_dialog: Some(Self::c_on_file_dialog)
+            },
+            self
+        )
+    }
+}
@@ -0,0 +1,653 @@
+use crate::{
+    callbacks_builder, debug_assert_currently_on, ref_counted_ptr, unwind::catch_unwind_or,
+    Browser, CefStr, CefString, CefStringList, Frame, LogSeverity, NativeCursorHandle, Point,
+    RefCountedPtr, Size, ThreadId, Wrappable, Wrapped
+};
+use cef_ui_sys::{
+    cef_browser_t, cef_cursor_handle_t, cef_cursor_info_t, cef_cursor_type_t,
+    cef_display_handler_t, cef_frame_t, cef_log_severity_t, cef_size_t, cef_string_list_t,
+    cef_string_t
+};
+use std::{ffi::c_int, mem::zeroed, slice::from_raw_parts};
+
+/// Cursor type values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursorType {
+    Pointer,
+    Cross,
+    Hand,
+    IBeam,
+    Wait,
+    Help,
+    EastResize,
+    NorthResize,
+    NorthEastResize,
+    NorthWestResize,
+    SouthResize,
+    SouthEastResize,
+    SouthWestResize,
+    WestResize,
+    NorthSouthResize,
+    EastWestResize,
+    NorthEastSouthWestResize,
+    NorthWestSouthEastResize,
+    ColumnResize,
+    RowResize,
+    MiddlePanning,
+    EastPanning,
+    NorthPanning,
+    NorthEastPanning,
+    NorthWestPanning,
+    SouthPanning,
+    SouthEastPanning,
+    SouthWestPanning,
+    WestPanning,
+    Move,
+    VerticalText,
+    Cell,
+    ContextMenu,
+    Alias,
+    Progress,
+    NoDrop,
+    Copy,
+    None,
+    NotAllowed,
+    ZoomIn,
+    ZoomOut,
+    Grab,
+    Grabbing,
+    MiddlePanningVertical,
+    MiddlePanningHorizontal,
+    /// A cursor defined by the page, described by CustomCursorInfo.
+    Custom,
+    DndNone,
+    DndMove,
+    DndCopy,
+    DndLink
+}
+
+impl From<cef_cursor_type_t> for CursorType {
+    fn from(value: cef_cursor_type_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_cursor_type_t> for CursorType {
+    fn from(value: &cef_cursor_type_t) -> Self {
+        match value {
+            cef_cursor_type_t::CT_POINTER => Self::Pointer,
+            cef_cursor_type_t::CT_CROSS => Self::Cross,
+            cef_cursor_type_t::CT_HAND => Self::Hand,
+            cef_cursor_type_t::CT_IBEAM => Self::IBeam,
+            cef_cursor_type_t::CT_WAIT => Self::Wait,
+            cef_cursor_type_t::CT_HELP => Self::Help,
+            cef_cursor_type_t::CT_EASTRESIZE => Self::EastResize,
+            cef_cursor_type_t::CT_NORTHRESIZE => Self::NorthResize,
+            cef_cursor_type_t::CT_NORTHEASTRESIZE => Self::NorthEastResize,
+            cef_cursor_type_t::CT_NORTHWESTRESIZE => Self::NorthWestResize,
+            cef_cursor_type_t::CT_SOUTHRESIZE => Self::SouthResize,
+            cef_cursor_type_t::CT_SOUTHEASTRESIZE => Self::SouthEastResize,
+            cef_cursor_type_t::CT_SOUTHWESTRESIZE => Self::SouthWestResize,
+            cef_cursor_type_t::CT_WESTRESIZE => Self::WestResize,
+            cef_cursor_type_t::CT_NORTHSOUTHRESIZE => Self::NorthSouthResize,
+            cef_cursor_type_t::CT_EASTWESTRESIZE => Self::EastWestResize,
+            cef_cursor_type_t::CT_NORTHEASTSOUTHWESTRESIZE => Self::NorthEastSouthWestResize,
+            cef_cursor_type_t::CT_NORTHWESTSOUTHEASTRESIZE => Self::NorthWestSouthEastResize,
+            cef_cursor_type_t::CT_COLUMNRESIZE => Self::ColumnResize,
+            cef_cursor_type_t::CT_ROWRESIZE => Self::RowResize,
+            cef_cursor_type_t::CT_MIDDLEPANNING => Self::MiddlePanning,
+            cef_cursor_type_t::CT_EASTPANNING => Self::EastPanning,
+            cef_cursor_type_t::CT_NORTHPANNING => Self::NorthPanning,
+            cef_cursor_type_t::CT_NORTHEASTPANNING => Self::NorthEastPanning,
+            cef_cursor_type_t::CT_NORTHWESTPANNING => Self::NorthWestPanning,
+            cef_cursor_type_t::CT_SOUTHPANNING => Self::SouthPanning,
+            cef_cursor_type_t::CT_SOUTHEASTPANNING => Self::SouthEastPanning,
+            cef_cursor_type_t::CT_SOUTHWESTPANNING => Self::SouthWestPanning,
+            cef_cursor_type_t::CT_WESTPANNING => Self::WestPanning,
+            cef_cursor_type_t::CT_MOVE => Self::Move,
+            cef_cursor_type_t::CT_VERTICALTEXT => Self::VerticalText,
+            cef_cursor_type_t::CT_CELL => Self::Cell,
+            cef_cursor_type_t::CT_CONTEXTMENU => Self::ContextMenu,
+            cef_cursor_type_t::CT_ALIAS => Self::Alias,
+            cef_cursor_type_t::CT_PROGRESS => Self::Progress,
+            cef_cursor_type_t::CT_NODROP => Self::NoDrop,
+            cef_cursor_type_t::CT_COPY => Self::Copy,
+            cef_cursor_type_t::CT_NONE => Self::None,
+            cef_cursor_type_t::CT_NOTALLOWED => Self::NotAllowed,
+            cef_cursor_type_t::CT_ZOOMIN => Self::ZoomIn,
+            cef_cursor_type_t::CT_ZOOMOUT => Self::ZoomOut,
+            cef_cursor_type_t::CT_GRAB => Self::Grab,
+            cef_cursor_type_t::CT_GRABBING => Self::Grabbing,
+            cef_cursor_type_t::CT_MIDDLE_PANNING_VERTICAL => Self::MiddlePanningVertical,
+            cef_cursor_type_t::CT_MIDDLE_PANNING_HORIZONTAL => Self::MiddlePanningHorizontal,
+            cef_cursor_type_t::CT_CUSTOM => Self::Custom,
+            cef_cursor_type_t::CT_DND_NONE => Self::DndNone,
+            cef_cursor_type_t::CT_DND_MOVE => Self::DndMove,
+            cef_cursor_type_t::CT_DND_COPY => Self::DndCopy,
+            cef_cursor_type_t::CT_DND_LINK => Self::DndLink
+        }
+    }
+}
+
+impl From<CursorType> for cef_cursor_type_t {
+    fn from(value: CursorType) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&CursorType> for cef_cursor_type_t {
+    fn from(value: &CursorType) -> Self {
+        match value {
+            CursorType::Pointer => cef_cursor_type_t::CT_POINTER,
+            CursorType::Cross => cef_cursor_type_t::CT_CROSS,
+            CursorType::Hand => cef_cursor_type_t::CT_HAND,
+            CursorType::IBeam => cef_cursor_type_t::CT_IBEAM,
+            CursorType::Wait => cef_cursor_type_t::CT_WAIT,
+            CursorType::Help => cef_cursor_type_t::CT_HELP,
+            CursorType::EastResize => cef_cursor_type_t::CT_EASTRESIZE,
+            CursorType::NorthResize => cef_cursor_type_t::CT_NORTHRESIZE,
+            CursorType::NorthEastResize => cef_cursor_type_t::CT_NORTHEASTRESIZE,
+            CursorType::NorthWestResize => cef_cursor_type_t::CT_NORTHWESTRESIZE,
+            CursorType::SouthResize => cef_cursor_type_t::CT_SOUTHRESIZE,
+            CursorType::SouthEastResize => cef_cursor_type_t::CT_SOUTHEASTRESIZE,
+            CursorType::SouthWestResize => cef_cursor_type_t::CT_SOUTHWESTRESIZE,
+            CursorType::WestResize => cef_cursor_type_t::CT_WESTRESIZE,
+            CursorType::NorthSouthResize => cef_cursor_type_t::CT_NORTHSOUTHRESIZE,
+            CursorType::EastWestResize => cef_cursor_type_t::CT_EASTWESTRESIZE,
+            CursorType::NorthEastSouthWestResize => cef_cursor_type_t::CT_NORTHEASTSOUTHWESTRESIZE,
+            CursorType::NorthWestSouthEastResize => cef_cursor_type_t::CT_NORTHWESTSOUTHEASTRESIZE,
+            CursorType::ColumnResize => cef_cursor_type_t::CT_COLUMNRESIZE,
+            CursorType::RowResize => cef_cursor_type_t::CT_ROWRESIZE,
+            CursorType::MiddlePanning => cef_cursor_type_t::CT_MIDDLEPANNING,
+            CursorType::EastPanning => cef_cursor_type_t::CT_EASTPANNING,
+            CursorType::NorthPanning => cef_cursor_type_t::CT_NORTHPANNING,
+            CursorType::NorthEastPanning => cef_cursor_type_t::CT_NORTHEASTPANNING,
+            CursorType::NorthWestPanning => cef_cursor_type_t::CT_NORTHWESTPANNING,
+            CursorType::SouthPanning => cef_cursor_type_t::CT_SOUTHPANNING,
+            CursorType::SouthEastPanning => cef_cursor_type_t::CT_SOUTHEASTPANNING,
+            CursorType::SouthWestPanning => cef_cursor_type_t::CT_SOUTHWESTPANNING,
+            CursorType::WestPanning => cef_cursor_type_t::CT_WESTPANNING,
+            CursorType::Move => cef_cursor_type_t::CT_MOVE,
+            CursorType::VerticalText => cef_cursor_type_t::CT_VERTICALTEXT,
+            CursorType::Cell => cef_cursor_type_t::CT_CELL,
+            CursorType::ContextMenu => cef_cursor_type_t::CT_CONTEXTMENU,
+            CursorType::Alias => cef_cursor_type_t::CT_ALIAS,
+            CursorType::Progress => cef_cursor_type_t::CT_PROGRESS,
+            CursorType::NoDrop => cef_cursor_type_t::CT_NODROP,
+            CursorType::Copy => cef_cursor_type_t::CT_COPY,
+            CursorType::None => cef_cursor_type_t::CT_NONE,
+            CursorType::NotAllowed => cef_cursor_type_t::CT_NOTALLOWED,
+            CursorType::ZoomIn => cef_cursor_type_t::CT_ZOOMIN,
+            CursorType::ZoomOut => cef_cursor_type_t::CT_ZOOMOUT,
+            CursorType::Grab => cef_cursor_type_t::CT_GRAB,
+            CursorType::Grabbing => cef_cursor_type_t::CT_GRABBING,
+            CursorType::MiddlePanningVertical => cef_cursor_type_t::CT_MIDDLE_PANNING_VERTICAL,
+            CursorType::MiddlePanningHorizontal => cef_cursor_type_t::CT_MIDDLE_PANNING_HORIZONTAL,
+            CursorType::Custom => cef_cursor_type_t::CT_CUSTOM,
+            CursorType::DndNone => cef_cursor_type_t::CT_DND_NONE,
+            CursorType::DndMove => cef_cursor_type_t::CT_DND_MOVE,
+            CursorType::DndCopy => cef_cursor_type_t::CT_DND_COPY,
+            CursorType::DndLink => cef_cursor_type_t::CT_DND_LINK
+        }
+    }
+}
+
+/// Structure representing cursor information. |buffer| will be
+/// |size.width|*|size.height|*4 bytes in size and represents a BGRA image with
+/// an upper-left origin.
+#[derive(Debug, Clone, Copy)]
+pub struct CustomCursorInfo<'a> {
+    pub hotspot:            Point,
+    pub image_scale_factor: f32,
+    pub buffer:             &'a [u8],
+    pub size:               Size
+}
+
+impl<'a> CustomCursorInfo<'a> {
+    /// Convert from a pointer, which may be null.
+    pub(crate) fn from_ptr(ptr: *const cef_cursor_info_t) -> Option<Self> {
+        let info = unsafe { ptr.as_ref()? };
+        let size: Size = info.size.into();
+        let len = size.width.max(0) as usize * size.height.max(0) as usize * 4;
+        let buffer = match info.buffer.is_null() {
+            true => &[][..],
+            false => unsafe { from_raw_parts(info.buffer as *const u8, len) }
+        };
+
+        Some(Self {
+            hotspot: info.hotspot.into(),
+            image_scale_factor: info.image_scale_factor,
+            buffer,
+            size
+        })
+    }
+}
+
+/// Implement this structure to handle events related to browser display state.
+/// The functions of this structure will be called on the UI thread.
+pub trait DisplayHandlerCallbacks: Send + Sync + 'static {
+    /// Called when a frame's address has changed.
+    fn on_address_change(&mut self, _browser: Browser, _frame: Frame, _url: &str) {}
+
+    /// Called when the page title changes.
+    fn on_title_change(&mut self, _browser: Browser, _title: &str) {}
+
+    /// Called when the page icon changes.
+    fn on_favicon_url_change(&mut self, _browser: Browser, _icon_urls: Vec<String>) {}
+
+    /// Called when web content in the page has toggled fullscreen mode. If
+    /// |fullscreen| is true (1) the content will automatically be sized to fill
+    /// the browser content area. If |fullscreen| is false (0) the content will
+    /// automatically return to its original size and position. With the Alloy
+    /// runtime the client is responsible for triggering the fullscreen
+    /// transition (for example, by calling cef_window_t::SetFullscreen when using
+    /// Views). With the Chrome runtime the fullscreen transition will be
+    /// triggered automatically. The cef_window_delegate_t::OnWindowFullscreenTransition
+    /// function will be called during the fullscreen transition for
+    /// notification purposes.
+    fn on_fullscreen_mode_change(&mut self, _browser: Browser, _fullscreen: bool) {}
+
+    /// Called when the browser is about to display a tooltip. |text| contains the
+    /// text that will be displayed in the tooltip. To handle the display of the
+    /// tooltip yourself return true (1). Otherwise, you can optionally modify
+    /// |text| and then return false (0) to allow the browser to display the
+    /// tooltip. When window rendering is disabled the application is responsible
+    /// for drawing tooltips and the return value is ignored.
+    fn on_tooltip(&mut self, _browser: Browser, _text: &mut String) -> bool {
+        false
+    }
+
+    /// Called when the browser receives a status message. |value| contains the
+    /// text that will be displayed in the status message.
+    fn on_status_message(&mut self, _browser: Browser, _value: &str) {}
+
+    /// Called to display a console message. Return true (1) to stop the message
+    /// from being output to the console.
+    fn on_console_message(
+        &mut self,
+        _browser: Browser,
+        _level: LogSeverity,
+        _message: CefStr<'_>,
+        _source: CefStr<'_>,
+        _line: i32
+    ) -> bool {
+        false
+    }
+
+    /// Called when auto-resize is enabled via
+    /// cef_browser_host_t::SetAutoResizeEnabled and the contents have auto-
+    /// resized. |new_size| will be the desired size in view coordinates. Return
+    /// true (1) if the resize was handled or false (0) for default handling.
+    /// Windowless browsers should resize their view to |new_size| and call
+    /// cef_browser_host_t::WasResized.
+    fn on_auto_resize(&mut self, _browser: Browser, _new_size: &Size) -> bool {
+        false
+    }
+
+    /// Called when the overall page loading progress has changed. |progress|
+    /// ranges from 0.0 to 1.0.
+    fn on_loading_progress_change(&mut self, _browser: Browser, _progress: f64) {}
+
+    /// Called when the browser's cursor has changed. If |cursor_type| is
+    /// CursorType::Custom then |custom_cursor_info| will be populated with the
+    /// custom cursor information. |cursor| is the native cursor, if any. Return
+    /// true (1) if the cursor change was handled or false (0) for default
+    /// handling. Windowless browsers never change the cursor themselves, so
+    /// their host must apply the cursor to its own window here.
+    fn on_cursor_change(
+        &mut self,
+        _browser: Browser,
+        _cursor: Option<NativeCursorHandle>,
+        _cursor_type: CursorType,
+        _custom_cursor_info: Option<CustomCursorInfo<'_>>
+    ) -> bool {
+        false
+    }
+
+    /// Called when the browser's access to an audio and/or video source has
+    /// changed.
+    fn on_media_access_change(
+        &mut self,
+        _browser: Browser,
+        _has_video_access: bool,
+        _has_audio_access: bool
+    ) {
+    }
+}
+
+// Implement this structure to handle events related to browser display state.
+// The functions of this structure will be called on the UI thread.
+ref_counted_ptr!(DisplayHandler, cef_display_handler_t);
+
+impl DisplayHandler {
+    pub fn new<C: DisplayHandlerCallbacks>(delegate: C) -> Self {
+        Self(DisplayHandlerWrapper::new(delegate).wrap())
+    }
+}
+
+callbacks_builder! {
+    /// Builds a DisplayHandler from closures. Callbacks that are not set fall
+    /// back to the default DisplayHandlerCallbacks behavior.
+    DisplayHandlerBuilder: DisplayHandlerCallbacks => DisplayHandler {
+        fn on_address_change(browser: Browser, frame: Frame, url: &str);
+        fn on_title_change(browser: Browser, title: &str);
+        fn on_favicon_url_change(browser: Browser, icon_urls: Vec<String>);
+        fn on_fullscreen_mode_change(browser: Browser, fullscreen: bool);
+        fn on_tooltip(browser: Browser, text: &mut String) -> bool = false;
+        fn on_status_message(browser: Browser, value: &str);
+        fn on_console_message(
+            browser: Browser,
+            level: LogSeverity,
+            message: CefStr<'_>,
+            source: CefStr<'_>,
+            line: i32
+        ) -> bool = false;
+        fn on_auto_resize(browser: Browser, new_size: &Size) -> bool = false;
+        fn on_loading_progress_change(browser: Browser, progress: f64);
+        fn on_cursor_change(
+            browser: Browser,
+            cursor: Option<NativeCursorHandle>,
+            cursor_type: CursorType,
+            custom_cursor_info: Option<CustomCursorInfo<'_>>
+        ) -> bool = false;
+        fn on_media_access_change(
+            browser: Browser,
+            has_video_access: bool,
+            has_audio_access: bool
+        );
+    }
+}
+
+/// Translates CEF -> Rust callbacks.
+struct DisplayHandlerWrapper<C: DisplayHandlerCallbacks>(C);
+
+impl<C: DisplayHandlerCallbacks> DisplayHandlerWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
+    }
+
+    /// Called when a frame's address has changed.
+    unsafe extern "C" fn c_on_address_change(
+        this: *mut cef_display_handler_t,
+        browser: *mut cef_browser_t,
+        frame: *mut cef_frame_t,
+        url: *const cef_string_t
+    ) {
+        catch_unwind_or("DisplayHandlerWrapper::c_on_address_change", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let frame = Frame::from_ptr_unchecked(frame);
+            let url: String = CefString::from_ptr_unchecked(url).into();
+
+            this.0
+                .on_address_change(browser, frame, &url);
+        })
+    }
+
+    /// Called when the page title changes.
+    unsafe extern "C" fn c_on_title_change(
+        this: *mut cef_display_handler_t,
+        browser: *mut cef_browser_t,
+        title: *const cef_string_t
+    ) {
+        catch_unwind_or("DisplayHandlerWrapper::c_on_title_change", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let title: String = CefString::from_ptr(title)
+                .map(|title| title.into())
+                .unwrap_or_default();
+
+            this.0
+                .on_title_change(browser, &title);
+        })
+    }
+
+    /// Called when the page icon changes.
+    unsafe extern "C" fn c_on_favicon_urlchange(
+        this: *mut cef_display_handler_t,
+        browser: *mut cef_browser_t,
+        icon_urls: cef_string_list_t
+    ) {
+        catch_unwind_or("DisplayHandlerWrapper::c_on_favicon_urlchange", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let icon_urls = CefStringList::from_ptr(icon_urls).map_or(Vec::new(), |s| s.into());
+
+            this.0
+                .on_favicon_url_change(browser, icon_urls);
+        })
+    }
+
+    /// Called when web content in the page has toggled fullscreen mode.
+    unsafe extern "C" fn c_on_fullscreen_mode_change(
+        this: *mut cef_display_handler_t,
+        browser: *mut cef_browser_t,
+        fullscreen: c_int
+    ) {
+        catch_unwind_or(
+            "DisplayHandlerWrapper::c_on_fullscreen_mode_change",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+
+                this.0
+                    .on_fullscreen_mode_change(browser, fullscreen != 0);
+            }
+        )
+    }
+
+    /// Called when the browser is about to display a tooltip. |text| contains the
+    /// text that will be displayed in the tooltip. To handle the display of the
+    /// tooltip yourself return true (1). Otherwise, you can optionally modify
+    /// |text| and then return false (0) to allow the browser to display the
+    /// tooltip. When window rendering is disabled the application is responsible
+    /// for drawing tooltips and the return value is ignored.
+    unsafe extern "C" fn c_on_tooltip(
+        this: *mut cef_display_handler_t,
+        browser: *mut cef_browser_t,
+        text: *mut cef_string_t
+    ) -> c_int {
+        catch_unwind_or("DisplayHandlerWrapper::c_on_tooltip", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+
+            match CefString::from_ptr_mut(text) {
+                Some(text) => {
+                    let current: String = (&*text).into();
+                    let mut value = current.clone();
+                    let handled = this
+                        .0
+                        .on_tooltip(browser, &mut value);
+
+                    if value != current {
+                        text.set(&value);
+                    }
+
+                    handled as c_int
+                },
+                None => {
+                    let mut value = String::new();
+
+                    this.0
+                        .on_tooltip(browser, &mut value) as c_int
+                }
+            }
+        })
+    }
+
+    /// Called when the browser receives a status message. |value| contains the
+    /// text that will be displayed in the status message.
+    unsafe extern "C" fn c_on_status_message(
+        this: *mut cef_display_handler_t,
+        browser: *mut cef_browser_t,
+        value: *const cef_string_t
+    ) {
+        catch_unwind_or("DisplayHandlerWrapper::c_on_status_message", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let value: String = CefString::from_ptr(value)
+                .map(|value| value.into())
+                .unwrap_or_default();
+
+            this.0
+                .on_status_message(browser, &value);
+        })
+    }
+
+    /// Called to display a console message. Return true (1) to stop the message
+    /// from being output to the console.
+    unsafe extern "C" fn c_on_console_message(
+        this: *mut cef_display_handler_t,
+        browser: *mut cef_browser_t,
+        level: cef_log_severity_t,
+        message: *const cef_string_t,
+        source: *const cef_string_t,
+        line: c_int
+    ) -> c_int {
+        catch_unwind_or("DisplayHandlerWrapper::c_on_console_message", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let message = CefStr::from_ptr(message).unwrap_or_default();
+            let source = CefStr::from_ptr(source).unwrap_or_default();
+
+            this.0
+                .on_console_message(browser, level.into(), message, source, line)
+                as c_int
+        })
+    }
+
+    /// Called when auto-resize is enabled via
+    /// cef_browser_host_t::SetAutoResizeEnabled and the contents have auto-
+    /// resized. |new_size| will be the desired size in view coordinates. Return
+    /// true (1) if the resize was handled or false (0) for default handling.
+    unsafe extern "C" fn c_on_auto_resize(
+        this: *mut cef_display_handler_t,
+        browser: *mut cef_browser_t,
+        new_size: *const cef_size_t
+    ) -> c_int {
+        catch_unwind_or("DisplayHandlerWrapper::c_on_auto_resize", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let new_size: Size = (&*new_size).into();
+
+            this.0
+                .on_auto_resize(browser, &new_size) as c_int
+        })
+    }
+
+    /// Called when the overall page loading progress has changed. |progress|
+    /// ranges from 0.0 to 1.0.
+    unsafe extern "C" fn c_on_loading_progress_change(
+        this: *mut cef_display_handler_t,
+        browser: *mut cef_browser_t,
+        progress: f64
+    ) {
+        catch_unwind_or(
+            "DisplayHandlerWrapper::c_on_loading_progress_change",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+
+                this.0
+                    .on_loading_progress_change(browser, progress);
+            }
+        )
+    }
+
+    /// Called when the browser's cursor has changed.
+    unsafe extern "C" fn c_on_cursor_change(
+        this: *mut cef_display_handler_t,
+        browser: *mut cef_browser_t,
+        cursor: cef_cursor_handle_t,
+        cursor_type: cef_cursor_type_t,
+        custom_cursor_info: *const cef_cursor_info_t
+    ) -> c_int {
+        catch_unwind_or("DisplayHandlerWrapper::c_on_cursor_change", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let cursor = NativeCursorHandle::try_from(cursor).ok();
+            let custom_cursor_info = match cursor_type {
+                cef_cursor_type_t::CT_CUSTOM => CustomCursorInfo::from_ptr(custom_cursor_info),
+                _ => None
+            };
+
+            this.0
+                .on_cursor_change(browser, cursor, cursor_type.into(), custom_cursor_info)
+                as c_int
+        })
+    }
+
+    /// Called when the browser's access to an audio and/or video source has
+    /// changed.
+    unsafe extern "C" fn c_on_media_access_change(
+        this: *mut cef_display_handler_t,
+        browser: *mut cef_browser_t,
+        has_video_access: c_int,
+        has_audio_access: c_int
+    ) {
+        catch_unwind_or(
+            "DisplayHandlerWrapper::c_on_media_access_change",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+
+                this.0.on_media_access_change(
+                    browser,
+                    has_video_access != 0,
+                    has_audio_access != 0
+                );
+            }
+        )
+    }
+}
+
+impl<C: DisplayHandlerCallbacks> Wrappable for DisplayHandlerWrapper<C> {
+    type Cef = cef_display_handler_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<cef_display_handler_t> {
+        RefCountedPtr::wrap(
+            cef_display_handler_t {
+                base: unsafe { zeroed() },
+
+                on_address_change:          Some(Self::c_on_address_change),
+                on_title_change:            Some(Self::c_on_title_change),
+                on_favicon_urlchange:       Some(Self::c_on_favicon_urlchange),
+                on_fullscreen_mode_change:  Some(Self::c_on_fullscreen_mode_change),
+                on_tooltip:                 Some(Self::c_on_tooltip),
+                on_status_message:          Some(Self::c_on_status_message),
+                on_console_message:         Some(Self::c_on_console_message),
+                on_auto_resize:             Some(Self::c_on_auto_resize),
+                on_loading_progress_change: Some(Self::c_on_loading_progress_change),
+                on_cursor_change:           Some(Self::c_on_cursor_change),
+                on_media_access_change:     Some(Self::c_on_media_access_change)
+            },
+            self
+        )
+    }
+}
@@ -0,0 +1,437 @@
+use crate::{
+    BeforeDownloadCallback, Browser, DownloadHandler, DownloadHandlerCallbacks,
+    DownloadInterruptReason, DownloadItem, DownloadItemCallback, Result
+};
+use anyhow::anyhow;
+use futures_core::Stream;
+use parking_lot::Mutex;
+use std::{
+    collections::HashMap,
+    future::Future,
+    mem,
+    path::PathBuf,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Waker}
+};
+use tracing::error;
+
+/// A snapshot of the progress of a download.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DownloadProgress {
+    /// The number of received bytes.
+    pub received_bytes: i64,
+
+    /// The total number of bytes, if the server reported it.
+    pub total_bytes: Option<i64>,
+
+    /// The rough percent complete, if the total number of bytes is known.
+    pub percent_complete: Option<u8>,
+
+    /// A simple speed estimate in bytes/s.
+    pub current_speed: i64,
+
+    /// The full path to the downloading file. Empty until the path is known.
+    pub full_path: PathBuf
+}
+
+/// How a download finished.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DownloadOutcome {
+    /// The file was written to the path.
+    Complete(PathBuf),
+
+    /// The download was canceled, by DownloadHandle::cancel or otherwise.
+    Canceled,
+
+    /// The download failed.
+    Interrupted(DownloadInterruptReason)
+}
+
+/// An action requested before CEF provided a DownloadItemCallback.
+#[derive(Debug, Clone, Copy)]
+enum DownloadAction {
+    Pause,
+    Resume,
+    Cancel
+}
+
+impl DownloadAction {
+    fn run(self, callback: &DownloadItemCallback) -> Result<()> {
+        match self {
+            Self::Pause => callback.pause(),
+            Self::Resume => callback.resume(),
+            Self::Cancel => callback.cancel()
+        }
+    }
+}
+
+/// The state shared between a download's handle, streams and futures.
+#[derive(Default)]
+struct DownloadState {
+    progress: DownloadProgress,
+    version:  u64,
+    outcome:  Option<DownloadOutcome>,
+    callback: Option<DownloadItemCallback>,
+    pending:  Option<DownloadAction>,
+    wakers:   Vec<Waker>
+}
+
+impl DownloadState {
+    /// Records an update of |item| and wakes every waiting stream and future.
+    fn update(&mut self, item: &DownloadItem) -> Result<()> {
+        let total_bytes = item.get_total_bytes()?;
+
+        self.progress = DownloadProgress {
+            received_bytes:   item.get_received_bytes()?,
+            total_bytes:      (total_bytes > 0).then_some(total_bytes),
+            percent_complete: item.get_percent_complete()?,
+            current_speed:    item.get_current_speed()?,
+            full_path:        PathBuf::from(item.get_full_path()?)
+        };
+        self.version += 1;
+
+        if item.is_complete()? {
+            self.outcome = Some(DownloadOutcome::Complete(self.progress.full_path.clone()));
+        } else if item.is_canceled()? {
+            self.outcome = Some(DownloadOutcome::Canceled);
+        } else if item.is_interrupted()? {
+            self.outcome = Some(DownloadOutcome::Interrupted(item.get_interrupt_reason()?));
+        }
+
+        self.wake();
+
+        Ok(())
+    }
+
+    /// Returns the callback to run |action| with, or defers |action| to the
+    /// next update if CEF has not provided a callback yet.
+    fn request(&mut self, action: DownloadAction) -> Result<Option<DownloadItemCallback>> {
+        if self.outcome.is_some() {
+            return Err(anyhow!("The download has already finished.").into());
+        }
+
+        if self.callback.is_none() {
+            self.pending = Some(action);
+        }
+
+        Ok(self.callback.clone())
+    }
+
+    fn wake(&mut self) {
+        for waker in mem::take(&mut self.wakers) {
+            waker.wake();
+        }
+    }
+
+    /// Registers |cx| to be woken on the next update.
+    fn register(&mut self, cx: &Context<'_>) {
+        if !self
+            .wakers
+            .iter()
+            .any(|waker| waker.will_wake(cx.waker()))
+        {
+            self.wakers.push(cx.waker().clone());
+        }
+    }
+}
+
+/// A download accepted by DownloadManager. Clones refer to the same download.
+/// The handle may be used and awaited from any thread.
+#[derive(Clone)]
+pub struct DownloadHandle {
+    id:             u32,
+    url:            String,
+    suggested_name: String,
+    state:          Arc<Mutex<DownloadState>>
+}
+
+impl DownloadHandle {
+    /// Returns the unique identifier of the download.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the URL.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the suggested file name.
+    pub fn suggested_name(&self) -> &str {
+        &self.suggested_name
+    }
+
+    /// Returns the most recent progress.
+    pub fn current_progress(&self) -> DownloadProgress {
+        self.state.lock().progress.clone()
+    }
+
+    /// Returns how the download finished, or None while it is in progress.
+    pub fn outcome(&self) -> Option<DownloadOutcome> {
+        self.state.lock().outcome.clone()
+    }
+
+    /// Returns a stream of progress updates that ends when the download
+    /// finishes. A stream that is polled less often than CEF reports progress
+    /// skips to the most recent update rather than buffering every one.
+    pub fn progress(&self) -> impl Stream<Item = DownloadProgress> + Send + 'static {
+        DownloadProgressStream {
+            state: self.state.clone(),
+            seen:  0
+        }
+    }
+
+    /// Returns a future that resolves with how the download finished.
+    pub fn completion(&self) -> impl Future<Output = DownloadOutcome> + Send + 'static {
+        DownloadCompletion {
+            state: self.state.clone()
+        }
+    }
+
+    /// Pauses the download.
+    pub fn pause(&self) -> Result<()> {
+        self.request(DownloadAction::Pause)
+    }
+
+    /// Resumes a paused download.
+    pub fn resume(&self) -> Result<()> {
+        self.request(DownloadAction::Resume)
+    }
+
+    /// Cancels the download. The completion resolves with Canceled once CEF
+    /// reports the cancellation.
+    pub fn cancel(&self) -> Result<()> {
+        self.request(DownloadAction::Cancel)
+    }
+
+    /// Runs |action| without the lock, as CEF may report the resulting update
+    /// synchronously.
+    fn request(&self, action: DownloadAction) -> Result<()> {
+        let callback = self.state.lock().request(action)?;
+
+        match callback {
+            Some(callback) => action.run(&callback),
+            None => Ok(())
+        }
+    }
+}
+
+/// The stream returned by DownloadHandle::progress.
+struct DownloadProgressStream {
+    state: Arc<Mutex<DownloadState>>,
+    seen:  u64
+}
+
+impl Stream for DownloadProgressStream {
+    type Item = DownloadProgress;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let state = self.state.clone();
+        let mut state = state.lock();
+
+        if state.version > self.seen {
+            self.seen = state.version;
+
+            return Poll::Ready(Some(state.progress.clone()));
+        }
+
+        match state.outcome {
+            Some(_) => Poll::Ready(None),
+            None => {
+                state.register(cx);
+
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// The future returned by DownloadHandle::completion.
+struct DownloadCompletion {
+    state: Arc<Mutex<DownloadState>>
+}
+
+impl Future for DownloadCompletion {
+    type Output = DownloadOutcome;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock();
+
+        match state.outcome.clone() {
+            Some(outcome) => Poll::Ready(outcome),
+            None => {
+                state.register(cx);
+
+                Poll::Pending
+            }
+        }
+    }
+}
+
+type OnDownload = Box<dyn FnMut(DownloadHandle) + Send + Sync + 'static>;
+
+#[derive(Default)]
+struct ManagerState {
+    directory:   Option<PathBuf>,
+    show_dialog: bool,
+    on_download: Option<OnDownload>,
+    downloads:   HashMap<u32, Arc<Mutex<DownloadState>>>
+}
+
+/// Accepts downloads and exposes each one as a DownloadHandle. Downloads are
+/// saved under the directory set with directory, or else with the suggested
+/// name in the default temp directory. Clones share the same downloads.
+///
+/// Return download_handler from ClientCallbacks::get_download_handler.
+#[derive(Clone, Default)]
+pub struct DownloadManager(Arc<Mutex<ManagerState>>);
+
+impl DownloadManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Saves downloads under |directory| with their suggested names.
+    pub fn directory(self, directory: impl Into<PathBuf>) -> Self {
+        self.0.lock().directory = Some(directory.into());
+        self
+    }
+
+    /// If true, the default "Save As" dialog is shown for each download.
+    pub fn show_dialog(self, show_dialog: bool) -> Self {
+        self.0.lock().show_dialog = show_dialog;
+        self
+    }
+
+    /// Calls |f| on the UI thread with the handle of each accepted download.
+    pub fn on_download(self, f: impl FnMut(DownloadHandle) + Send + Sync + 'static) -> Self {
+        self.0.lock().on_download = Some(Box::new(f));
+        self
+    }
+
+    /// Returns a download handler that accepts downloads.
+    pub fn download_handler(&self) -> DownloadHandler {
+        DownloadHandler::new(self.clone())
+    }
+
+    /// Accepts |download_item| and returns its handle.
+    fn accept(
+        &self,
+        download_item: &DownloadItem,
+        suggested_name: &str,
+        callback: &BeforeDownloadCallback
+    ) -> Result<DownloadHandle> {
+        let (path, show_dialog) = {
+            let state = self.0.lock();
+            let path = state
+                .directory
+                .as_ref()
+                .map(|directory| directory.join(suggested_name));
+
+            (path, state.show_dialog)
+        };
+        let handle = DownloadHandle {
+            id:             download_item.get_id()?,
+            url:            download_item.get_url()?,
+            suggested_name: suggested_name.to_string(),
+            state:          Arc::new(Mutex::new(DownloadState::default()))
+        };
+
+        self.0
+            .lock()
+            .downloads
+            .insert(handle.id, handle.state.clone());
+
+        callback.cont(path.as_deref(), show_dialog)?;
+
+        Ok(handle)
+    }
+}
+
+impl DownloadHandlerCallbacks for DownloadManager {
+    fn on_before_download(
+        &mut self,
+        _browser: Browser,
+        download_item: DownloadItem,
+        suggested_name: &str,
+        callback: BeforeDownloadCallback
+    ) {
+        let handle = match self.accept(&download_item, suggested_name, &callback) {
+            Ok(handle) => handle,
+            Err(e) => {
+                error!("Failed to accept download: {}", e);
+
+                return;
+            }
+        };
+
+        // The callback is called without the lock, so that it may configure
+        // the manager.
+        let on_download = self.0.lock().on_download.take();
+
+        if let Some(mut on_download) = on_download {
+            on_download(handle);
+
+            let mut state = self.0.lock();
+
+            if state.on_download.is_none() {
+                state.on_download = Some(on_download);
+            }
+        }
+    }
+
+    fn on_download_updated(
+        &mut self,
+        _browser: Browser,
+        download_item: DownloadItem,
+        callback: DownloadItemCallback
+    ) {
+        let id = match download_item.get_id() {
+            Ok(id) => id,
+            Err(e) => {
+                error!("Failed to get download ID: {}", e);
+
+                return;
+            }
+        };
+        let Some(download) = self
+            .0
+            .lock()
+            .downloads
+            .get(&id)
+            .cloned()
+        else {
+            return;
+        };
+        let pending = {
+            let mut download = download.lock();
+
+            if let Err(e) = download.update(&download_item) {
+                error!("Failed to update download: {}", e);
+            }
+
+            match download.outcome {
+                Some(_) => {
+                    download.callback = None;
+                    download.pending = None;
+                },
+                None => download.callback = Some(callback.clone())
+            }
+
+            download.pending.take()
+        };
+
+        if download.lock().outcome.is_some() {
+            self.0.lock().downloads.remove(&id);
+        }
+
+        // Actions requested before the first update run without the lock, as
+        // CEF may report the resulting update synchronously.
+        if let Some(action) = pending {
+            if let Err(e) = action.run(&callback) {
+                error!("Failed to update download: {}", e);
+            }
+        }
+    }
+}
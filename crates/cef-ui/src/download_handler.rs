@@ -0,0 +1,552 @@
+use crate::{
+    callbacks_builder, debug_assert_currently_on, ref_counted_ptr, try_c, unwind::catch_unwind_or,
+    Browser, CefString, CefTime, RefCountedPtr, Result, ThreadId, Wrappable, Wrapped
+};
+use cef_ui_sys::{
+    cef_before_download_callback_t, cef_browser_t, cef_download_handler_t,
+    cef_download_interrupt_reason_t, cef_download_item_callback_t, cef_download_item_t,
+    cef_string_t
+};
+use chrono::{DateTime, Utc};
+use std::{ffi::c_int, mem::zeroed, path::Path};
+
+/// Download interrupt reasons. Should be kept in sync with Chromium's
+/// download::DownloadInterruptReason type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DownloadInterruptReason {
+    None,
+
+    /// Generic file operation failure.
+    FileFailed,
+
+    /// The file cannot be accessed due to security restrictions.
+    FileAccessDenied,
+
+    /// There is not enough room on the drive.
+    FileNoSpace,
+
+    /// The directory or file name is too long.
+    FileNameTooLong,
+
+    /// The file is too large for the file system to handle.
+    FileTooLarge,
+
+    /// The file contains a virus.
+    FileVirusInfected,
+
+    /// The file was in use. Too many files are opened at once. We have run out of
+    /// memory.
+    FileTransientError,
+
+    /// The file was blocked due to local policy.
+    FileBlocked,
+
+    /// An attempt to check the safety of the download failed due to unexpected
+    /// reasons. See http://crbug.com/153212.
+    FileSecurityCheckFailed,
+
+    /// An attempt was made to seek past the end of a file in opening
+    /// a file (as part of resuming a previously interrupted download).
+    FileTooShort,
+
+    /// The partial file didn't match the expected hash.
+    FileHashMismatch,
+
+    /// The source and the target of the download were the same.
+    FileSameAsSource,
+
+    /// Generic network failure.
+    NetworkFailed,
+
+    /// The network operation timed out.
+    NetworkTimeout,
+
+    /// The network connection has been lost.
+    NetworkDisconnected,
+
+    /// The server has gone down.
+    NetworkServerDown,
+
+    /// The network request was invalid. This may be due to the original URL or a
+    /// redirected URL:
+    /// - Having an unsupported scheme.
+    /// - Being an invalid URL.
+    /// - Being disallowed by policy.
+    NetworkInvalidRequest,
+
+    /// The server indicates that the operation has failed (generic).
+    ServerFailed,
+
+    /// The server does not support range requests.
+    ServerNoRange,
+
+    /// The server does not have the requested data.
+    ServerBadContent,
+
+    /// Server didn't authorize access to resource.
+    ServerUnauthorized,
+
+    /// Server certificate problem.
+    ServerCertProblem,
+
+    /// Server access forbidden.
+    ServerForbidden,
+
+    /// Unexpected server response. This might indicate that the responding
+    /// server may not be the intended server.
+    ServerUnreachable,
+
+    /// The server sent fewer bytes than the content-length header. It may
+    /// indicate that the connection was closed prematurely, or the
+    /// Content-Length header was invalid. The download is only interrupted if
+    /// strong validators are present. Otherwise, it is treated as finished.
+    ServerContentLengthMismatch,
+
+    /// An unexpected cross-origin redirect happened.
+    ServerCrossOriginRedirect,
+
+    /// The user canceled the download.
+    UserCanceled,
+
+    /// The user shut down the browser.
+    UserShutdown,
+
+    /// The browser crashed.
+    Crash
+}
+
+impl From<cef_download_interrupt_reason_t> for DownloadInterruptReason {
+    fn from(value: cef_download_interrupt_reason_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_download_interrupt_reason_t> for DownloadInterruptReason {
+    fn from(value: &cef_download_interrupt_reason_t) -> Self {
+        use cef_download_interrupt_reason_t::*;
+
+        match value {
+            CEF_DOWNLOAD_INTERRUPT_REASON_NONE => Self::None,
+            CEF_DOWNLOAD_INTERRUPT_REASON_FILE_FAILED => Self::FileFailed,
+            CEF_DOWNLOAD_INTERRUPT_REASON_FILE_ACCESS_DENIED => Self::FileAccessDenied,
+            CEF_DOWNLOAD_INTERRUPT_REASON_FILE_NO_SPACE => Self::FileNoSpace,
+            CEF_DOWNLOAD_INTERRUPT_REASON_FILE_NAME_TOO_LONG => Self::FileNameTooLong,
+            CEF_DOWNLOAD_INTERRUPT_REASON_FILE_TOO_LARGE => Self::FileTooLarge,
+            CEF_DOWNLOAD_INTERRUPT_REASON_FILE_VIRUS_INFECTED => Self::FileVirusInfected,
+            CEF_DOWNLOAD_INTERRUPT_REASON_FILE_TRANSIENT_ERROR => Self::FileTransientError,
+            CEF_DOWNLOAD_INTERRUPT_REASON_FILE_BLOCKED => Self::FileBlocked,
+            CEF_DOWNLOAD_INTERRUPT_REASON_FILE_SECURITY_CHECK_FAILED => {
+                Self::FileSecurityCheckFailed
+            },
+            CEF_DOWNLOAD_INTERRUPT_REASON_FILE_TOO_SHORT => Self::FileTooShort,
+            CEF_DOWNLOAD_INTERRUPT_REASON_FILE_HASH_MISMATCH => Self::FileHashMismatch,
+            CEF_DOWNLOAD_INTERRUPT_REASON_FILE_SAME_AS_SOURCE => Self::FileSameAsSource,
+            CEF_DOWNLOAD_INTERRUPT_REASON_NETWORK_FAILED => Self::NetworkFailed,
+            CEF_DOWNLOAD_INTERRUPT_REASON_NETWORK_TIMEOUT => Self::NetworkTimeout,
+            CEF_DOWNLOAD_INTERRUPT_REASON_NETWORK_DISCONNECTED => Self::NetworkDisconnected,
+            CEF_DOWNLOAD_INTERRUPT_REASON_NETWORK_SERVER_DOWN => Self::NetworkServerDown,
+            CEF_DOWNLOAD_INTERRUPT_REASON_NETWORK_INVALID_REQUEST => Self::NetworkInvalidRequest,
+            CEF_DOWNLOAD_INTERRUPT_REASON_SERVER_FAILED => Self::ServerFailed,
+            CEF_DOWNLOAD_INTERRUPT_REASON_SERVER_NO_RANGE => Self::ServerNoRange,
+            CEF_DOWNLOAD_INTERRUPT_REASON_SERVER_BAD_CONTENT => Self::ServerBadContent,
+            CEF_DOWNLOAD_INTERRUPT_REASON_SERVER_UNAUTHORIZED => Self::ServerUnauthorized,
+            CEF_DOWNLOAD_INTERRUPT_REASON_SERVER_CERT_PROBLEM => Self::ServerCertProblem,
+            CEF_DOWNLOAD_INTERRUPT_REASON_SERVER_FORBIDDEN => Self::ServerForbidden,
+            CEF_DOWNLOAD_INTERRUPT_REASON_SERVER_UNREACHABLE => Self::ServerUnreachable,
+            CEF_DOWNLOAD_INTERRUPT_REASON_SERVER_CONTENT_LENGTH_MISMATCH => {
+                Self::ServerContentLengthMismatch
+            },
+            CEF_DOWNLOAD_INTERRUPT_REASON_SERVER_CROSS_ORIGIN_REDIRECT => {
+                Self::ServerCrossOriginRedirect
+            },
+            CEF_DOWNLOAD_INTERRUPT_REASON_USER_CANCELED => Self::UserCanceled,
+            CEF_DOWNLOAD_INTERRUPT_REASON_USER_SHUTDOWN => Self::UserShutdown,
+            CEF_DOWNLOAD_INTERRUPT_REASON_CRASH => Self::Crash
+        }
+    }
+}
+
+// Structure used to represent a download item.
+ref_counted_ptr!(DownloadItem, cef_download_item_t);
+
+impl DownloadItem {
+    /// Returns true (1) if this object is valid. Do not call any other functions
+    /// if this function returns false (0).
+    pub fn is_valid(&self) -> Result<bool> {
+        try_c!(self, is_valid, { Ok(is_valid(self.as_ptr()) != 0) })
+    }
+
+    /// Returns true (1) if the download is in progress.
+    pub fn is_in_progress(&self) -> Result<bool> {
+        try_c!(self, is_in_progress, {
+            Ok(is_in_progress(self.as_ptr()) != 0)
+        })
+    }
+
+    /// Returns true (1) if the download is complete.
+    pub fn is_complete(&self) -> Result<bool> {
+        try_c!(self, is_complete, { Ok(is_complete(self.as_ptr()) != 0) })
+    }
+
+    /// Returns true (1) if the download has been canceled.
+    pub fn is_canceled(&self) -> Result<bool> {
+        try_c!(self, is_canceled, { Ok(is_canceled(self.as_ptr()) != 0) })
+    }
+
+    /// Returns true (1) if the download has been interrupted.
+    pub fn is_interrupted(&self) -> Result<bool> {
+        try_c!(self, is_interrupted, {
+            Ok(is_interrupted(self.as_ptr()) != 0)
+        })
+    }
+
+    /// Returns the most recent interrupt reason.
+    pub fn get_interrupt_reason(&self) -> Result<DownloadInterruptReason> {
+        try_c!(self, get_interrupt_reason, {
+            Ok(get_interrupt_reason(self.as_ptr()).into())
+        })
+    }
+
+    /// Returns a simple speed estimate in bytes/s.
+    pub fn get_current_speed(&self) -> Result<i64> {
+        try_c!(self, get_current_speed, {
+            Ok(get_current_speed(self.as_ptr()))
+        })
+    }
+
+    /// Returns the rough percent complete or -1 if the receive total size is
+    /// unknown.
+    pub fn get_percent_complete(&self) -> Result<Option<u8>> {
+        try_c!(self, get_percent_complete, {
+            let percent = get_percent_complete(self.as_ptr());
+
+            Ok(match percent {
+                -1 => None,
+                percent => Some(percent.clamp(0, 100) as u8)
+            })
+        })
+    }
+
+    /// Returns the total number of bytes.
+    pub fn get_total_bytes(&self) -> Result<i64> {
+        try_c!(self, get_total_bytes, {
+            Ok(get_total_bytes(self.as_ptr()))
+        })
+    }
+
+    /// Returns the number of received bytes.
+    pub fn get_received_bytes(&self) -> Result<i64> {
+        try_c!(self, get_received_bytes, {
+            Ok(get_received_bytes(self.as_ptr()))
+        })
+    }
+
+    /// Returns the time that the download started.
+    pub fn get_start_time(&self) -> Result<Option<DateTime<Utc>>> {
+        try_c!(self, get_start_time, {
+            let base_time = get_start_time(self.as_ptr());
+
+            Ok(CefTime::from_basetime(base_time).map(CefTime::into))
+        })
+    }
+
+    /// Returns the time that the download ended.
+    pub fn get_end_time(&self) -> Result<Option<DateTime<Utc>>> {
+        try_c!(self, get_end_time, {
+            let base_time = get_end_time(self.as_ptr());
+
+            Ok(CefTime::from_basetime(base_time).map(CefTime::into))
+        })
+    }
+
+    /// Returns the full path to the downloaded or downloading file.
+    pub fn get_full_path(&self) -> Result<String> {
+        try_c!(self, get_full_path, {
+            let s = get_full_path(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
+        })
+    }
+
+    /// Returns the unique identifier for this download.
+    pub fn get_id(&self) -> Result<u32> {
+        try_c!(self, get_id, { Ok(get_id(self.as_ptr())) })
+    }
+
+    /// Returns the URL.
+    pub fn get_url(&self) -> Result<String> {
+        try_c!(self, get_url, {
+            let s = get_url(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
+        })
+    }
+
+    /// Returns the original URL before any redirections.
+    pub fn get_original_url(&self) -> Result<String> {
+        try_c!(self, get_original_url, {
+            let s = get_original_url(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
+        })
+    }
+
+    /// Returns the suggested file name.
+    pub fn get_suggested_file_name(&self) -> Result<String> {
+        try_c!(self, get_suggested_file_name, {
+            let s = get_suggested_file_name(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
+        })
+    }
+
+    /// Returns the content disposition.
+    pub fn get_content_disposition(&self) -> Result<String> {
+        try_c!(self, get_content_disposition, {
+            let s = get_content_disposition(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
+        })
+    }
+
+    /// Returns the mime type.
+    pub fn get_mime_type(&self) -> Result<String> {
+        try_c!(self, get_mime_type, {
+            let s = get_mime_type(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
+        })
+    }
+}
+
+// Callback structure used to asynchronously continue a download.
+ref_counted_ptr!(BeforeDownloadCallback, cef_before_download_callback_t);
+
+impl BeforeDownloadCallback {
+    /// Call to continue the download. Set |download_path| to the full file path
+    /// for the download including the file name or leave blank to use the
+    /// suggested name and the default temp directory. Set |show_dialog| to true
+    /// (1) if you do wish to show the default "Save As" dialog.
+    pub fn cont(&self, download_path: Option<&Path>, show_dialog: bool) -> Result<()> {
+        try_c!(self, cont, {
+            let download_path = download_path.map(|path| CefString::new(&path.to_string_lossy()));
+            let download_path = download_path
+                .as_ref()
+                .map(|path| path.as_ptr())
+                .unwrap_or(std::ptr::null());
+
+            cont(self.as_ptr(), download_path, show_dialog as c_int);
+
+            Ok(())
+        })
+    }
+}
+
+// Callback structure used to asynchronously cancel a download.
+ref_counted_ptr!(DownloadItemCallback, cef_download_item_callback_t);
+
+impl DownloadItemCallback {
+    /// Call to cancel the download.
+    pub fn cancel(&self) -> Result<()> {
+        try_c!(self, cancel, {
+            cancel(self.as_ptr());
+
+            Ok(())
+        })
+    }
+
+    /// Call to pause the download.
+    pub fn pause(&self) -> Result<()> {
+        try_c!(self, pause, {
+            pause(self.as_ptr());
+
+            Ok(())
+        })
+    }
+
+    /// Call to resume the download.
+    pub fn resume(&self) -> Result<()> {
+        try_c!(self, resume, {
+            resume(self.as_ptr());
+
+            Ok(())
+        })
+    }
+}
+
+/// Structure used to handle file downloads. The functions of this structure
+/// will called on the browser process UI thread.
+pub trait DownloadHandlerCallbacks: Send + Sync + 'static {
+    /// Called before a download begins in response to a user-initiated action
+    /// (e.g. alt + link click or link click that returns a `Content-Disposition:
+    /// attachment` response from the server). |url| is the target download URL
+    /// and |request_method| is the target function (GET, POST, etc). Return true
+    /// (1) to proceed with the download or false (0) to cancel the download.
+    fn can_download(&mut self, _browser: Browser, _url: &str, _request_method: &str) -> bool {
+        true
+    }
+
+    /// Called before a download begins. |suggested_name| is the suggested name
+    /// for the download file. By default the download will be canceled. Execute
+    /// |callback| either asynchronously or in this function to continue the
+    /// download if desired. Do not keep a reference to |download_item| outside
+    /// of this function.
+    fn on_before_download(
+        &mut self,
+        browser: Browser,
+        download_item: DownloadItem,
+        suggested_name: &str,
+        callback: BeforeDownloadCallback
+    );
+
+    /// Called when a download's status or progress information has been
+    /// updated. This may be called multiple times before and after
+    /// on_before_download(). Execute |callback| either asynchronously or in this
+    /// function to cancel the download if desired. Do not keep a reference to
+    /// |download_item| outside of this function.
+    fn on_download_updated(
+        &mut self,
+        _browser: Browser,
+        _download_item: DownloadItem,
+        _callback: DownloadItemCallback
+    ) {
+    }
+}
+
+// Structure used to handle file downloads. The functions of this structure
+// will called on the browser process UI thread.
+ref_counted_ptr!(DownloadHandler, cef_download_handler_t);
+
+impl DownloadHandler {
+    pub fn new<C: DownloadHandlerCallbacks>(delegate: C) -> Self {
+        Self(DownloadHandlerWrapper::new(delegate).wrap())
+    }
+}
+
+callbacks_builder! {
+    /// Builds a DownloadHandler from closures. Callbacks that are not set fall
+    /// back to the default DownloadHandlerCallbacks behavior, and downloads are
+    /// canceled if on_before_download is not set.
+    DownloadHandlerBuilder: DownloadHandlerCallbacks => DownloadHandler {
+        fn can_download(browser: Browser, url: &str, request_method: &str) -> bool = true;
+        fn on_before_download(
+            browser: Browser,
+            download_item: DownloadItem,
+            suggested_name: &str,
+            callback: BeforeDownloadCallback
+        );
+        fn on_download_updated(
+            browser: Browser,
+            download_item: DownloadItem,
+            callback: DownloadItemCallback
+        );
+    }
+}
+
+/// Translates CEF -> Rust callbacks.
+struct DownloadHandlerWrapper<C: DownloadHandlerCallbacks>(C);
+
+impl<C: DownloadHandlerCallbacks> DownloadHandlerWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
+    }
+
+    /// Called before a download begins in response to a user-initiated action.
+    unsafe extern "C" fn c_can_download(
+        this: *mut cef_download_handler_t,
+        browser: *mut cef_browser_t,
+        url: *const cef_string_t,
+        request_method: *const cef_string_t
+    ) -> c_int {
+        catch_unwind_or("DownloadHandlerWrapper::c_can_download", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let url: String = CefString::from_ptr(url)
+                .map(|url| url.into())
+                .unwrap_or_default();
+            let request_method: String = CefString::from_ptr(request_method)
+                .map(|request_method| request_method.into())
+                .unwrap_or_default();
+
+            this.0
+                .can_download(browser, &url, &request_method) as c_int
+        })
+    }
+
+    /// Called before a download begins.
+    unsafe extern "C" fn c_on_before_download(
+        this: *mut cef_download_handler_t,
+        browser: *mut cef_browser_t,
+        download_item: *mut cef_download_item_t,
+        suggested_name: *const cef_string_t,
+        callback: *mut cef_before_download_callback_t
+    ) {
+        catch_unwind_or("DownloadHandlerWrapper::c_on_before_download", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let download_item = DownloadItem::from_ptr_unchecked(download_item);
+            let suggested_name: String = CefString::from_ptr(suggested_name)
+                .map(|suggested_name| suggested_name.into())
+                .unwrap_or_default();
+            let callback = BeforeDownloadCallback::from_ptr_unchecked(callback);
+
+            this.0
+                .on_before_download(browser, download_item, &suggested_name, callback);
+        })
+    }
+
+    /// Called when a download's status or progress information has been
+    /// updated.
+    unsafe extern "C" fn c_on_download_updated(
+        this: *mut cef_download_handler_t,
+        browser: *mut cef_browser_t,
+        download_item: *mut cef_download_item_t,
+        callback: *mut cef_download_item_callback_t
+    ) {
+        catch_unwind_or("DownloadHandlerWrapper::c_on_download_updated", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let download_item = DownloadItem::from_ptr_unchecked(download_item);
+            let callback = DownloadItemCallback::from_ptr_unchecked(callback);
+
+            this.0
+                .on_download_updated(browser, download_item, callback);
+        })
+    }
+}
+
+impl<C: DownloadHandlerCallbacks> Wrappable for DownloadHandlerWrapper<C> {
+    type Cef = cef_download_handler_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<cef_download_handler_t> {
+        RefCountedPtr::wrap(
+            cef_download_handler_t {
+                base: unsafe { zeroed() },
+
+                can_download:        Some(Self::c_can_download),
+                on_before_download:  Some(Self::c_on_before_download),
+                on_download_updated: Some(Self::c_on_download_updated)
+            },
+            self
+        )
+    }
+}
@@ -1,8 +1,10 @@
-use crate::{ref_counted_ptr, try_c, CefString, CefStringList, Point};
-use anyhow::Result;
+use crate::{
+    ref_counted_ptr, try_c, AlphaType, CefString, CefStringList, ColorType, EncodedImage, Image,
+    Point, Rect, Result
+};
 use bitflags::bitflags;
 use cef_ui_sys::{
-    cef_drag_data_create, cef_drag_data_t, cef_drag_operations_mask_t,
+    cef_drag_data_create, cef_drag_data_t, cef_drag_operations_mask_t, cef_draggable_region_t,
     cef_drag_operations_mask_t_DRAG_OPERATION_COPY,
     cef_drag_operations_mask_t_DRAG_OPERATION_DELETE,
     cef_drag_operations_mask_t_DRAG_OPERATION_EVERY,
@@ -11,13 +13,14 @@ use cef_ui_sys::{
     cef_drag_operations_mask_t_DRAG_OPERATION_NONE,
     cef_drag_operations_mask_t_DRAG_OPERATION_PRIVATE
 };
+use std::ffi::c_int;
 
 bitflags! {
     /// "Verb" of a drag-and-drop operation as negotiated between the source and
     /// destination. These constants match their equivalents in WebCore's
     /// DragActions.h and should not be renumbered.
     #[allow(non_upper_case_globals)]
-    #[derive(Default, Clone, Copy)]
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
     pub struct DragOperations: cef_drag_operations_mask_t {
         const None = cef_drag_operations_mask_t_DRAG_OPERATION_NONE;
         const Copy = cef_drag_operations_mask_t_DRAG_OPERATION_COPY;
@@ -54,6 +57,64 @@ impl From<&DragOperations> for cef_drag_operations_mask_t {
     }
 }
 
+impl DragOperations {
+    /// Returns the single operation a drop target should report for a drag
+    /// that allows these operations, preferring copy over move over link, as
+    /// platform drag and drop does when no modifier keys are held.
+    pub fn preferred(&self) -> DragOperations {
+        [
+            DragOperations::Copy,
+            DragOperations::Move,
+            DragOperations::Link,
+            DragOperations::Generic,
+            DragOperations::Private
+        ]
+        .into_iter()
+        .find(|operation| self.contains(*operation))
+        .unwrap_or(DragOperations::None)
+    }
+}
+
+/// Structure representing a draggable region.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DraggableRegion {
+    /// Bounds of the region.
+    pub bounds: Rect,
+
+    /// True (1) this this region is draggable and false (0) otherwise.
+    pub draggable: bool
+}
+
+impl From<cef_draggable_region_t> for DraggableRegion {
+    fn from(value: cef_draggable_region_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_draggable_region_t> for DraggableRegion {
+    fn from(value: &cef_draggable_region_t) -> Self {
+        Self {
+            bounds:    value.bounds.into(),
+            draggable: value.draggable != 0
+        }
+    }
+}
+
+impl From<DraggableRegion> for cef_draggable_region_t {
+    fn from(value: DraggableRegion) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&DraggableRegion> for cef_draggable_region_t {
+    fn from(value: &DraggableRegion) -> Self {
+        Self {
+            bounds:    value.bounds.into(),
+            draggable: value.draggable as c_int
+        }
+    }
+}
+
 // Structure used to represent drag data. The functions of this structure may
 // be called on any thread.
 ref_counted_ptr!(DragData, cef_drag_data_t);
@@ -274,13 +335,13 @@ impl DragData {
         })
     }
 
-    // TODO: Fix this!
-
-    //     ///
-    //     /// Get the image representation of drag data. May return NULL if no image
-    //     /// representation is available.
-    //     ///
-    //     struct _cef_image_t*(CEF_CALLBACK* get_image)(struct _cef_drag_data_t* self);
+    /// Get the image representation of drag data. May return NULL if no image
+    /// representation is available.
+    pub fn get_image(&self) -> Result<Option<Image>> {
+        try_c!(self, get_image, {
+            Ok(Image::from_ptr(get_image(self.as_ptr())))
+        })
+    }
 
     /// Get the image hotspot (drag start location relative to image dimensions).
     pub fn get_image_hotspot(&self) -> Result<Point> {
@@ -293,4 +354,50 @@ impl DragData {
     pub fn has_image(&self) -> Result<bool> {
         try_c!(self, has_image, { Ok(has_image(self.as_ptr()) != 0) })
     }
+
+    /// Returns the drag image as premultiplied BGRA pixels at the
+    /// representation closest to |scale_factor|, together with its hotspot, so
+    /// that a windowless browser can draw drag feedback under the cursor.
+    /// Returns None if the drag has no image.
+    pub fn get_drag_image(&self, scale_factor: f32) -> Result<Option<DragImage>> {
+        if !self.has_image()? {
+            return Ok(None);
+        }
+
+        let Some(image) = self.get_image()? else {
+            return Ok(None);
+        };
+        let Some(bitmap) =
+            image.get_as_bitmap(scale_factor, ColorType::Bgra8888, AlphaType::Premultiplied)?
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(DragImage {
+            bitmap,
+            hotspot: self.get_image_hotspot()?
+        }))
+    }
+}
+
+/// The image shown while dragging. See DragData::get_drag_image.
+pub struct DragImage {
+    /// The image as premultiplied BGRA pixels.
+    pub bitmap: EncodedImage,
+
+    /// The drag start location relative to the image, i.e. the point of the
+    /// image that should be under the cursor.
+    pub hotspot: Point
+}
+
+impl DragImage {
+    /// Returns where to draw the image for a cursor at |cursor|.
+    pub fn bounds_at(&self, cursor: &Point) -> Rect {
+        Rect::new(
+            cursor.x - self.hotspot.x,
+            cursor.y - self.hotspot.y,
+            self.bitmap.pixel_width,
+            self.bitmap.pixel_height
+        )
+    }
 }
@@ -0,0 +1,151 @@
+use crate::{
+    callbacks_builder, debug_assert_currently_on, ref_counted_ptr, unwind::catch_unwind_or,
+    Browser, DragData, DragOperations, DraggableRegion, Frame, RefCountedPtr, ThreadId, Wrappable,
+    Wrapped
+};
+use cef_ui_sys::{
+    cef_browser_t, cef_drag_data_t, cef_drag_handler_t, cef_drag_operations_mask_t,
+    cef_draggable_region_t, cef_frame_t
+};
+use std::{ffi::c_int, mem::zeroed, slice::from_raw_parts};
+
+/// Implement this structure to handle events related to dragging. The functions
+/// of this structure will be called on the UI thread.
+pub trait DragHandlerCallbacks: Send + Sync + 'static {
+    /// Called when an external drag event enters the browser window. |dragData|
+    /// contains the drag event data and |mask| represents the type of drag
+    /// operation. Return false (0) for default drag handling behavior or true (1)
+    /// to cancel the drag event.
+    fn on_drag_enter(
+        &mut self,
+        _browser: Browser,
+        _drag_data: DragData,
+        _mask: DragOperations
+    ) -> bool {
+        false
+    }
+
+    /// Called whenever draggable regions for the browser window change. These
+    /// can be specified using the '-webkit-app-region: drag/no-drag' CSS-
+    /// property. If draggable regions are never defined in a document this
+    /// function will also never be called. If the last draggable region is
+    /// removed from a document this function will be called with an NULL
+    /// vector.
+    fn on_draggable_regions_changed(
+        &mut self,
+        _browser: Browser,
+        _frame: Frame,
+        _regions: &[DraggableRegion]
+    ) {
+    }
+}
+
+// Implement this structure to handle events related to dragging. The functions
+// of this structure will be called on the UI thread.
+ref_counted_ptr!(DragHandler, cef_drag_handler_t);
+
+impl DragHandler {
+    pub fn new<C: DragHandlerCallbacks>(delegate: C) -> Self {
+        Self(DragHandlerWrapper::new(delegate).wrap())
+    }
+}
+
+callbacks_builder! {
+    /// Builds a DragHandler from closures. Callbacks that are not set fall back
+    /// to the default DragHandlerCallbacks behavior.
+    DragHandlerBuilder: DragHandlerCallbacks => DragHandler {
+        fn on_drag_enter(
+            browser: Browser,
+            drag_data: DragData,
+            mask: DragOperations
+        ) -> bool = false;
+        fn on_draggable_regions_changed(
+            browser: Browser,
+            frame: Frame,
+            regions: &[DraggableRegion]
+        );
+    }
+}
+
+/// Translates CEF -> Rust callbacks.
+struct DragHandlerWrapper<C: DragHandlerCallbacks>(C);
+
+impl<C: DragHandlerCallbacks> DragHandlerWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
+    }
+
+    /// Called when an external drag event enters the browser window. |dragData|
+    /// contains the drag event data and |mask| represents the type of drag
+    /// operation. Return false (0) for default drag handling behavior or true (1)
+    /// to cancel the drag event.
+    unsafe extern "C" fn c_on_drag_enter(
+        this: *mut cef_drag_handler_t,
+        browser: *mut cef_browser_t,
+        drag_data: *mut cef_drag_data_t,
+        mask: cef_drag_operations_mask_t
+    ) -> c_int {
+        catch_unwind_or("DragHandlerWrapper::c_on_drag_enter", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let drag_data = DragData::from_ptr_unchecked(drag_data);
+
+            this.0
+                .on_drag_enter(browser, drag_data, mask.into()) as c_int
+        })
+    }
+
+    /// Called whenever draggable regions for the browser window change. These
+    /// can be specified using the '-webkit-app-region: drag/no-drag' CSS-
+    /// property. If draggable regions are never defined in a document this
+    /// function will also never be called. If the last draggable region is
+    /// removed from a document this function will be called with an NULL
+    /// vector.
+    unsafe extern "C" fn c_on_draggable_regions_changed(
+        this: *mut cef_drag_handler_t,
+        browser: *mut cef_browser_t,
+        frame: *mut cef_frame_t,
+        regions_count: usize,
+        regions: *const cef_draggable_region_t
+    ) {
+        catch_unwind_or(
+            "DragHandlerWrapper::c_on_draggable_regions_changed",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+                let frame = Frame::from_ptr_unchecked(frame);
+                let regions: Vec<DraggableRegion> = match regions.is_null() {
+                    true => Vec::new(),
+                    false => from_raw_parts(regions, regions_count)
+                        .iter()
+                        .map(DraggableRegion::from)
+                        .collect()
+                };
+
+                this.0
+                    .on_draggable_regions_changed(browser, frame, &regions);
+            }
+        )
+    }
+}
+
+impl<C: DragHandlerCallbacks> Wrappable for DragHandlerWrapper<C> {
+    type Cef = cef_drag_handler_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<cef_drag_handler_t> {
+        RefCountedPtr::wrap(
+            cef_drag_handler_t {
+                base:                         unsafe { zeroed() },
+                on_drag_enter:                Some(Self::c_on_drag_enter),
+                on_draggable_regions_changed: Some(Self::c_on_draggable_regions_changed)
+            },
+            self
+        )
+    }
+}
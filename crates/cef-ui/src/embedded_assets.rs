@@ -0,0 +1,426 @@
+use crate::{
+    get_mime_type, register_scheme_handler_factory, Browser, Frame, Request, RequestContext,
+    ResourceHandler, Result, SchemeHandlerFactory, SchemeHandlerFactoryCallbacks,
+    StreamResourceHandler
+};
+use bytes::Bytes;
+use parking_lot::Mutex;
+use std::{
+    borrow::Cow,
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::Arc
+};
+use tracing::error;
+
+/// The MIME type used when the extension is unknown.
+const DEFAULT_MIME_TYPE: &str = "application/octet-stream";
+
+/// A bundle of files compiled into the executable, e.g. with include_dir! or
+/// rust-embed. Closures can be used as bundles, so both work without extra
+/// glue:
+///
+/// ```ignore
+/// static DIST: Dir = include_dir!("$CARGO_MANIFEST_DIR/dist");
+/// let assets = EmbeddedAssets::new(|path: &str| {
+///     DIST.get_file(path).map(|file| Cow::Borrowed(file.contents()))
+/// });
+///
+/// let assets = EmbeddedAssets::new(|path: &str| Dist::get(path).map(|file| file.data));
+/// ```
+pub trait AssetBundle: Send + Sync + 'static {
+    /// Returns the contents of the file at |path|. The path is relative to the
+    /// root of the bundle and has no leading slash.
+    fn get(&self, path: &str) -> Option<Cow<'static, [u8]>>;
+}
+
+impl<F> AssetBundle for F
+where
+    F: Fn(&str) -> Option<Cow<'static, [u8]>> + Send + Sync + 'static
+{
+    fn get(&self, path: &str) -> Option<Cow<'static, [u8]>> {
+        self(path)
+    }
+}
+
+impl AssetBundle for &'static [(&'static str, &'static [u8])] {
+    fn get(&self, path: &str) -> Option<Cow<'static, [u8]>> {
+        self.iter()
+            .find(|(name, _)| *name == path)
+            .map(|(_, data)| Cow::Borrowed(*data))
+    }
+}
+
+/// A scheme handler factory that serves files from an AssetBundle, so that an
+/// application (e.g. a single page app) can ship inside the executable:
+///
+/// ```ignore
+/// // In AppCallbacks::on_register_custom_schemes, in every process:
+/// registrar.add_custom_scheme("app", SchemeOptions::Standard | SchemeOptions::Secure)?;
+///
+/// // In the browser process, once the context is initialized:
+/// EmbeddedAssets::new(bundle).register("app", Some("localhost"));
+/// ```
+///
+/// The MIME type is derived from the file extension. Responses carry an ETag,
+/// and If-None-Match requests for unchanged files are answered with 304.
+/// Single byte ranges are supported, so media elements can seek.
+pub struct EmbeddedAssets {
+    bundle:       Arc<dyn AssetBundle>,
+    index:        String,
+    spa_fallback: bool,
+    headers:      Vec<(String, String)>,
+    etags:        Arc<Mutex<HashMap<String, String>>>
+}
+
+impl EmbeddedAssets {
+    pub fn new(bundle: impl AssetBundle) -> Self {
+        Self {
+            bundle:       Arc::new(bundle),
+            index:        String::from("index.html"),
+            spa_fallback: false,
+            headers:      Vec::new(),
+            etags:        Arc::default()
+        }
+    }
+
+    /// The file served for directory paths, index.html by default.
+    pub fn index(mut self, index: &str) -> Self {
+        self.index = index
+            .trim_start_matches('/')
+            .to_string();
+        self
+    }
+
+    /// If true, paths that do not exist and have no file extension are served
+    /// the index file, so that client-side routes survive a reload.
+    pub fn spa_fallback(mut self, spa_fallback: bool) -> Self {
+        self.spa_fallback = spa_fallback;
+        self
+    }
+
+    /// Adds a header to every response, e.g. Content-Security-Policy.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers
+            .push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Converts this to a SchemeHandlerFactory.
+    pub fn factory(self) -> SchemeHandlerFactory {
+        SchemeHandlerFactory::new(self)
+    }
+
+    /// Registers this as the handler of |scheme_name| and |domain_name| with
+    /// the global request context. Returns false if an error occurs.
+    pub fn register(self, scheme_name: &str, domain_name: Option<&str>) -> bool {
+        register_scheme_handler_factory(scheme_name, domain_name, Some(self.factory()))
+    }
+
+    /// Registers this as the handler of |scheme_name| and |domain_name| with
+    /// |request_context| only, so that other contexts can serve the same
+    /// scheme differently. Returns false if an error occurs.
+    pub fn register_with(
+        self,
+        request_context: &RequestContext,
+        scheme_name: &str,
+        domain_name: Option<&str>
+    ) -> Result<bool> {
+        request_context.register_scheme_handler_factory(
+            scheme_name,
+            domain_name,
+            Some(self.factory())
+        )
+    }
+
+    /// Returns a handler that serves |request|. This can also be used from
+    /// ResourceRequestHandlerCallbacks::get_resource_handler.
+    pub fn handle(&self, request: &Request) -> ResourceHandler {
+        match request.get_url() {
+            Ok(url) => self.serve(request, &url_path(&url)),
+            Err(e) => {
+                error!("Failed to get embedded asset URL: {}", e);
+
+                status_response(500, "Internal Server Error")
+            }
+        }
+    }
+
+    /// Serves the file at |path|.
+    fn serve(&self, request: &Request, path: &str) -> ResourceHandler {
+        let mut path = match path.is_empty() || path.ends_with('/') {
+            true => format!("{}{}", path, self.index),
+            false => path.to_string()
+        };
+
+        let mut data = match path
+            .split('/')
+            .any(|segment| segment == "..")
+        {
+            true => None,
+            false => self.bundle.get(&path)
+        };
+
+        if data.is_none() && self.spa_fallback && !file_name(&path).contains('.') {
+            path = self.index.clone();
+            data = self.bundle.get(&path);
+        }
+
+        let Some(data) = data else {
+            return status_response(404, "Not Found");
+        };
+
+        let data = match data {
+            Cow::Borrowed(data) => Bytes::from_static(data),
+            Cow::Owned(data) => Bytes::from(data)
+        };
+        let etag = self.etag(&path, &data);
+        let header = |name: &str| {
+            request
+                .get_header_by_name(name)
+                .ok()
+                .flatten()
+                .filter(|value| !value.is_empty())
+        };
+
+        if header("If-None-Match").is_some_and(|value| value == etag) {
+            return self
+                .headers(StreamResourceHandler::from_bytes(Bytes::new()), &etag)
+                .status(304)
+                .status_text("Not Modified")
+                .build();
+        }
+
+        let length = data.len();
+        // Invalid ranges are ignored and multiple ranges are not supported, so
+        // in both cases the whole body is served.
+        let range = header("Range").and_then(|range| parse_range(&range, length));
+        let response = match range {
+            None => StreamResourceHandler::from_bytes(data),
+            Some(ByteRange::Satisfiable(start, end)) => {
+                StreamResourceHandler::from_bytes(data.slice(start..=end))
+                    .status(206)
+                    .status_text("Partial Content")
+                    .header(
+                        "Content-Range",
+                        &format!("bytes {}-{}/{}", start, end, length)
+                    )
+            },
+            Some(ByteRange::Unsatisfiable) => StreamResourceHandler::from_bytes(Bytes::new())
+                .status(416)
+                .status_text("Range Not Satisfiable")
+                .header("Content-Range", &format!("bytes */{}", length))
+        };
+
+        self.headers(response, &etag)
+            .mime_type(&mime_type(&path))
+            .build()
+    }
+
+    /// Adds the common response headers.
+    fn headers(&self, response: StreamResourceHandler, etag: &str) -> StreamResourceHandler {
+        self.headers.iter().fold(
+            response
+                .header("Accept-Ranges", "bytes")
+                .header("ETag", etag),
+            |response, (name, value)| response.header(name, value)
+        )
+    }
+
+    /// Returns the ETag of the file at |path|. ETags are cached, since the
+    /// bundle does not change while the application runs.
+    fn etag(&self, path: &str, data: &[u8]) -> String {
+        self.etags
+            .lock()
+            .entry(path.to_string())
+            .or_insert_with(|| {
+                let mut hasher = DefaultHasher::new();
+
+                data.hash(&mut hasher);
+
+                format!("\"{:016x}\"", hasher.finish())
+            })
+            .clone()
+    }
+}
+
+impl SchemeHandlerFactoryCallbacks for EmbeddedAssets {
+    fn create(
+        &mut self,
+        _browser: Option<Browser>,
+        _frame: Option<Frame>,
+        _scheme_name: &str,
+        request: Request
+    ) -> Option<ResourceHandler> {
+        Some(self.handle(&request))
+    }
+}
+
+/// Returns a plain text response with |status|.
+fn status_response(status: i32, status_text: &str) -> ResourceHandler {
+    StreamResourceHandler::from_bytes(status_text.to_string())
+        .status(status)
+        .status_text(status_text)
+        .mime_type("text/plain")
+        .build()
+}
+
+/// Returns the decoded path of |url| without the scheme, host, query and
+/// fragment, and without a leading slash.
+fn url_path(url: &str) -> String {
+    let url = url
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default();
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest
+            .split_once('/')
+            .map(|(_, path)| path)
+            .unwrap_or_default(),
+        None => url
+            .split_once(':')
+            .map(|(_, path)| path.trim_start_matches('/'))
+            .unwrap_or(url)
+    };
+
+    percent_decode(path)
+}
+
+/// Decodes %XX escapes in |value|.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escaped = match bytes[i] {
+            b'%' => value
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None
+        };
+
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            },
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Returns the last segment of |path|.
+fn file_name(path: &str) -> &str {
+    path.rsplit('/')
+        .next()
+        .unwrap_or(path)
+}
+
+/// Returns the MIME type for the extension of |path|.
+fn mime_type(path: &str) -> String {
+    file_name(path)
+        .rsplit_once('.')
+        .and_then(|(_, extension)| get_mime_type(extension))
+        .unwrap_or_else(|| DEFAULT_MIME_TYPE.to_string())
+}
+
+/// A byte range requested with the Range header.
+#[derive(Debug, PartialEq, Eq)]
+enum ByteRange {
+    /// The inclusive bounds of a range within the body.
+    Satisfiable(usize, usize),
+
+    /// A valid range that lies outside of the body.
+    Unsatisfiable
+}
+
+/// Parses a single "bytes=start-end" range for a body of |length| bytes.
+/// Returns None if |range| is not a valid single byte range, in which case
+/// the header must be ignored.
+fn parse_range(range: &str, length: usize) -> Option<ByteRange> {
+    let (start, end) = range
+        .trim()
+        .strip_prefix("bytes=")?
+        .split_once('-')?;
+    let (start, end) = (start.trim(), end.trim());
+
+    let range = match (start.is_empty(), end.is_empty()) {
+        // A suffix range: the last |end| bytes.
+        (true, false) => match parse_position(end)? {
+            0 => None,
+            suffix => Some((length - suffix.min(length), length.saturating_sub(1)))
+        },
+        (false, true) => Some((parse_position(start)?, length.saturating_sub(1))),
+        (false, false) => {
+            let (start, end) = (parse_position(start)?, parse_position(end)?);
+
+            if end < start {
+                return None;
+            }
+
+            Some((start, end.min(length.saturating_sub(1))))
+        },
+        (true, true) => return None
+    };
+
+    match range {
+        Some((start, end)) if start < length => Some(ByteRange::Satisfiable(start, end)),
+        _ => Some(ByteRange::Unsatisfiable)
+    }
+}
+
+/// Parses a byte position of a range. Positions past usize::MAX are clamped,
+/// as they are past the end of any body anyway.
+fn parse_position(value: &str) -> Option<usize> {
+    match !value.is_empty() && value.bytes().all(|c| c.is_ascii_digit()) {
+        true => Some(value.parse().unwrap_or(usize::MAX)),
+        false => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_range_satisfiable() {
+        assert_eq!(parse_range("bytes=0-9", 100), Some(ByteRange::Satisfiable(0, 9)));
+        assert_eq!(parse_range(" bytes=10 - 19 ", 100), Some(ByteRange::Satisfiable(10, 19)));
+        assert_eq!(parse_range("bytes=90-", 100), Some(ByteRange::Satisfiable(90, 99)));
+        assert_eq!(parse_range("bytes=-10", 100), Some(ByteRange::Satisfiable(90, 99)));
+        assert_eq!(parse_range("bytes=-500", 100), Some(ByteRange::Satisfiable(0, 99)));
+        assert_eq!(parse_range("bytes=50-500", 100), Some(ByteRange::Satisfiable(50, 99)));
+        assert_eq!(parse_range("bytes=99-99", 100), Some(ByteRange::Satisfiable(99, 99)));
+    }
+
+    #[test]
+    fn parse_range_unsatisfiable() {
+        assert_eq!(parse_range("bytes=100-", 100), Some(ByteRange::Unsatisfiable));
+        assert_eq!(parse_range("bytes=100-200", 100), Some(ByteRange::Unsatisfiable));
+        assert_eq!(parse_range("bytes=-0", 100), Some(ByteRange::Unsatisfiable));
+        assert_eq!(parse_range("bytes=0-", 0), Some(ByteRange::Unsatisfiable));
+        assert_eq!(parse_range("bytes=-10", 0), Some(ByteRange::Unsatisfiable));
+        assert_eq!(
+            parse_range("bytes=99999999999999999999999-", 100),
+            Some(ByteRange::Unsatisfiable)
+        );
+    }
+
+    #[test]
+    fn parse_range_invalid() {
+        assert_eq!(parse_range("", 100), None);
+        assert_eq!(parse_range("bytes=", 100), None);
+        assert_eq!(parse_range("bytes=-", 100), None);
+        assert_eq!(parse_range("bytes=abc-", 100), None);
+        assert_eq!(parse_range("bytes=+1-2", 100), None);
+        assert_eq!(parse_range("bytes=20-10", 100), None);
+        assert_eq!(parse_range("bytes=0-9,20-29", 100), None);
+        assert_eq!(parse_range("items=0-9", 100), None);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{ErrorCode, ThreadId};
+use std::{
+    any::type_name,
+    ffi::NulError,
+    fmt::{Display, Formatter}
+};
+
+/// A specialized Result type for fallible cef-ui operations.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// The ways in which a cef-ui operation can fail. This implements
+/// std::error::Error, so it converts into anyhow::Error with `?`.
+#[derive(Debug)]
+pub enum Error {
+    /// A CEF structure did not provide the function pointer for a method.
+    NullFunctionPointer {
+        type_name: &'static str,
+        method:    &'static str
+    },
+
+    /// A null or otherwise unusable native handle was encountered.
+    InvalidHandle(&'static str),
+
+    /// An operation was attempted on a thread that is not allowed to perform it.
+    WrongThread(ThreadId),
+
+    /// JavaScript code threw an exception.
+    V8Exception {
+        message:              String,
+        script_resource_name: String,
+        line_number:          i32
+    },
+
+    /// CEF reported a network or loading error.
+    Cef(ErrorCode),
+
+    /// An I/O operation failed.
+    Io(std::io::Error),
+
+    /// Any other failure.
+    Other(anyhow::Error)
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NullFunctionPointer { type_name, method } => {
+                write!(f, "Missing function pointer: {}::{}", type_name, method)
+            },
+            Error::InvalidHandle(name) => write!(f, "Invalid handle: {}", name),
+            Error::WrongThread(thread_id) => {
+                write!(f, "Expected to be called on the {:?} thread.", thread_id)
+            },
+            Error::V8Exception {
+                message,
+                script_resource_name,
+                line_number
+            } => write!(
+                f,
+                "Uncaught JavaScript exception at {}:{}: {}",
+                script_resource_name, line_number, message
+            ),
+            Error::Cef(code) => write!(f, "CEF error: {:?}", code),
+            Error::Io(e) => Display::fmt(e, f),
+            Error::Other(e) => Display::fmt(e, f)
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => e.source(),
+            Error::Other(e) => e.source(),
+            _ => None
+        }
+    }
+}
+
+impl From<anyhow::Error> for Error {
+    fn from(value: anyhow::Error) -> Self {
+        Error::Other(value)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(value: std::io::Error) -> Self {
+        Error::Io(value)
+    }
+}
+
+impl From<NulError> for Error {
+    fn from(value: NulError) -> Self {
+        Error::Other(value.into())
+    }
+}
+
+impl From<ErrorCode> for Error {
+    fn from(value: ErrorCode) -> Self {
+        Error::Cef(value)
+    }
+}
+
+/// Returns the unqualified name of the type of |value|. Used by try_c! to name
+/// the structure that is missing a function pointer.
+#[doc(hidden)]
+pub fn short_type_name<T: ?Sized>(_value: &T) -> &'static str {
+    let name = type_name::<T>().trim_start_matches('&');
+
+    name.rsplit("::")
+        .next()
+        .unwrap_or(name)
+}
@@ -0,0 +1,538 @@
+use crate::{
+    Browser, CefStr, Frame, FunctionScope, ProcessId, ProcessMessage, Result, V8Context, V8Handler,
+    V8HandlerCallbacks, V8PropertyAttribute, V8Value
+};
+use anyhow::anyhow;
+use parking_lot::Mutex;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc
+    }
+};
+
+/// Sent from the renderer when a process first sees a browser, asking whether
+/// an event bus is attached to it.
+const SYNC_MESSAGE: &str = "cef_ui.event_bus.sync";
+
+/// Sent to the renderer with the scope of the event bus attached to a browser.
+const ENABLE_MESSAGE: &str = "cef_ui.event_bus.enable";
+
+/// The process message sent from the renderer when JavaScript calls cefEmit.
+const EMIT_MESSAGE: &str = "cef_ui.event_bus.emit";
+
+/// The process message sent to the renderer when Rust emits an event.
+const DISPATCH_MESSAGE: &str = "cef_ui.event_bus.dispatch";
+
+/// The global that holds the native emit function until the bootstrap script
+/// has captured it.
+const NATIVE_EMIT: &str = "__cefUiEmit";
+
+/// The global that holds the JavaScript dispatch function.
+const DISPATCH_FUNCTION: &str = "__cefUiDispatch";
+
+/// Defines cefEmit, cefOn and cefOff on the global object. Listeners live in
+/// JavaScript, so they are released together with their V8 context.
+const BOOTSTRAP_SCRIPT: &str = r#"(function () {
+  const emit = globalThis.__cefUiEmit;
+  const listeners = new Map();
+  delete globalThis.__cefUiEmit;
+  const define = (name, value) => Object.defineProperty(globalThis, name, { value });
+  define("cefEmit", (name, payload) =>
+    emit(String(name), JSON.stringify(payload === undefined ? null : payload)));
+  define("cefOn", (name, callback) => {
+    name = String(name);
+    if (!listeners.has(name)) listeners.set(name, new Set());
+    listeners.get(name).add(callback);
+    return () => globalThis.cefOff(name, callback);
+  });
+  define("cefOff", (name, callback) => {
+    const set = listeners.get(String(name));
+    if (set) set.delete(callback);
+  });
+  define("__cefUiDispatch", (name, json) => {
+    const payload = JSON.parse(json);
+    for (const callback of [...(listeners.get(name) || [])]) {
+      try { callback(payload); } catch (e) { console.error(e); }
+    }
+  });
+})();"#;
+
+/// An event emitted from JavaScript with cefEmit(name, payload).
+#[derive(Clone)]
+pub struct EventBusEvent {
+    /// The browser that emitted the event.
+    pub browser: Browser,
+
+    /// The frame that emitted the event. Use EventBus::emit_to_frame to reply.
+    pub frame: Frame,
+
+    /// The event name.
+    pub name: String,
+
+    /// The payload serialized as JSON.
+    pub payload: String
+}
+
+/// Identifies a listener registered with EventBus::on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EventBusSubscription(u64);
+
+type Listener = Arc<dyn Fn(&EventBusEvent) + Send + Sync + 'static>;
+type Listeners = HashMap<String, Vec<(EventBusSubscription, Listener)>>;
+
+/// Event buses attached to browsers in the browser process, by browser
+/// identifier.
+static ATTACHED: Mutex<BTreeMap<i32, (FunctionScope, EventBus)>> = Mutex::new(BTreeMap::new());
+
+/// Browser process side of a JavaScript <-> Rust event bus built on process
+/// messages. JavaScript emits events with cefEmit(name, payload) and subscribes
+/// with cefOn(name, callback), which returns a function that unsubscribes.
+/// Payloads are exchanged as JSON.
+///
+/// Attach the bus to a browser with attach. No render process code is needed
+/// beyond returning a RenderProcessHandler from
+/// AppCallbacks::get_render_process_handler, which the crate does by default.
+/// Cloning is cheap and all clones share the same listeners.
+#[derive(Clone, Default)]
+pub struct EventBus {
+    listeners: Arc<Mutex<Listeners>>,
+    next_id:   Arc<AtomicU64>
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Defines cefEmit, cefOn and cefOff in the frames of |browser| selected by
+    /// |scope| and routes their events to this bus. A browser has at most one
+    /// event bus; attaching another one replaces it, and frames that are no
+    /// longer in scope keep the functions but their events are dropped. The
+    /// bus is detached when the browser is closed, provided the client has a
+    /// LifeSpanHandler.
+    pub fn attach(&self, browser: &Browser, scope: FunctionScope) -> Result<()> {
+        ATTACHED
+            .lock()
+            .insert(browser.get_identifier()?, (scope, self.clone()));
+
+        // Every render process of the browser learns about the bus, but only
+        // installs it in the frames that are in scope.
+        for identifier in browser.get_frame_identifiers()? {
+            if let Some(frame) = browser.get_frame_by_identifier(identifier)? {
+                send_enable_message(&frame, scope)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Calls |listener| whenever JavaScript emits the event |name|. Listeners
+    /// are called on the browser process UI thread.
+    pub fn on(
+        &self,
+        name: &str,
+        listener: impl Fn(&EventBusEvent) + Send + Sync + 'static
+    ) -> EventBusSubscription {
+        let subscription = EventBusSubscription(
+            self.next_id
+                .fetch_add(1, Ordering::Relaxed)
+        );
+
+        self.listeners
+            .lock()
+            .entry(name.to_string())
+            .or_default()
+            .push((subscription, Arc::new(listener)));
+
+        subscription
+    }
+
+    /// Removes a listener registered with on.
+    pub fn off(&self, subscription: EventBusSubscription) {
+        let mut listeners = self.listeners.lock();
+
+        for entries in listeners.values_mut() {
+            entries.retain(|(id, _)| *id != subscription);
+        }
+
+        listeners.retain(|_, entries| !entries.is_empty());
+    }
+
+    /// Emits the event |name| to the JavaScript listeners of |frame|. |payload|
+    /// must be valid JSON. Fails if |frame| is not in the scope of the event
+    /// bus attached to its browser.
+    pub fn emit_to_frame(&self, frame: &Frame, name: &str, payload: &str) -> Result<()> {
+        let browser = frame.get_browser()?;
+
+        match attached_scope(&browser)?.contains(&browser, frame)? {
+            true => frame.send_process_message(
+                ProcessId::Renderer,
+                create_message(DISPATCH_MESSAGE, name, payload)?
+            ),
+            false => Err(anyhow!("The frame is not in the scope of the event bus.").into())
+        }
+    }
+
+    /// Emits the event |name| to the JavaScript listeners of every frame of
+    /// |browser| in the scope of its event bus. |payload| must be valid JSON.
+    pub fn emit(&self, browser: &Browser, name: &str, payload: &str) -> Result<()> {
+        let scope = attached_scope(browser)?;
+
+        for identifier in browser.get_frame_identifiers()? {
+            let Some(frame) = browser.get_frame_by_identifier(identifier)? else {
+                continue;
+            };
+
+            if scope.contains(browser, &frame)? {
+                frame.send_process_message(
+                    ProcessId::Renderer,
+                    create_message(DISPATCH_MESSAGE, name, payload)?
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Calls the listeners for |event|.
+    fn dispatch(&self, event: &EventBusEvent) {
+        // Listeners are cloned so that they may subscribe or unsubscribe.
+        let listeners: Vec<Listener> = self
+            .listeners
+            .lock()
+            .get(&event.name)
+            .map(|entries| {
+                entries
+                    .iter()
+                    .map(|(_, listener)| listener.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        for listener in listeners {
+            listener(event);
+        }
+    }
+}
+
+/// Returns the scope of the event bus attached to |browser|.
+fn attached_scope(browser: &Browser) -> Result<FunctionScope> {
+    ATTACHED
+        .lock()
+        .get(&browser.get_identifier()?)
+        .map(|(scope, _)| *scope)
+        .ok_or_else(|| anyhow!("No event bus is attached to the browser.").into())
+}
+
+/// Detaches the event bus attached to |browser|, if any.
+pub(crate) fn release_browser_event_bus(browser: &Browser) {
+    if let Ok(identifier) = browser.get_identifier() {
+        ATTACHED.lock().remove(&identifier);
+    }
+}
+
+/// Handles event bus messages sent to the browser process. Returns true if
+/// |message| was handled.
+pub(crate) fn on_browser_process_message(
+    browser: &Browser,
+    frame: &Frame,
+    source_process: ProcessId,
+    message: &ProcessMessage
+) -> bool {
+    if source_process != ProcessId::Renderer {
+        return false;
+    }
+
+    match message.get_name().ok().as_deref() {
+        Some(SYNC_MESSAGE) => {
+            if let Ok(scope) = attached_scope(browser) {
+                let _ = send_enable_message(frame, scope);
+            }
+
+            true
+        },
+        Some(EMIT_MESSAGE) => {
+            let _ = receive_event(browser, frame, message);
+
+            true
+        },
+        _ => false
+    }
+}
+
+/// Passes an event emitted by JavaScript to the event bus attached to
+/// |browser|.
+fn receive_event(browser: &Browser, frame: &Frame, message: &ProcessMessage) -> Result<()> {
+    let Some((name, payload)) = parse_message(message) else {
+        return Ok(());
+    };
+
+    // The bus is cloned so that listeners may attach or detach buses.
+    let Some((scope, bus)) = ATTACHED
+        .lock()
+        .get(&browser.get_identifier()?)
+        .cloned()
+    else {
+        return Ok(());
+    };
+
+    // The render process only installs cefEmit in frames that are in scope,
+    // but a compromised render process could still send the event.
+    if !scope.contains(browser, frame)? {
+        return Ok(());
+    }
+
+    bus.dispatch(&EventBusEvent {
+        browser: browser.clone(),
+        frame: frame.clone(),
+        name,
+        payload
+    });
+
+    Ok(())
+}
+
+/// Tells the render process for |frame| that an event bus is attached in
+/// |scope|.
+fn send_enable_message(frame: &Frame, scope: FunctionScope) -> Result<()> {
+    let message = ProcessMessage::new(ENABLE_MESSAGE);
+
+    if let Some(arguments) = message.get_argument_list()? {
+        arguments.set_int(0, scope.into())?;
+    }
+
+    frame.send_process_message(ProcessId::Renderer, message)
+}
+
+/// Creates an event process message.
+fn create_message(message_name: &str, name: &str, payload: &str) -> Result<ProcessMessage> {
+    let message = ProcessMessage::new(message_name);
+
+    if let Some(arguments) = message.get_argument_list()? {
+        arguments.set_string(0, name)?;
+        arguments.set_string(1, payload)?;
+    }
+
+    Ok(message)
+}
+
+/// Returns the event name and payload of an event process message.
+fn parse_message(message: &ProcessMessage) -> Option<(String, String)> {
+    let arguments = message.get_argument_list().ok()??;
+    let name = arguments.get_string(0).ok()??;
+    let payload = arguments
+        .get_string(1)
+        .ok()?
+        .unwrap_or_else(|| "null".to_string());
+
+    Some((name, payload))
+}
+
+thread_local! {
+    /// The scope of the event bus attached to each browser known to this
+    /// render process, by browser identifier. None until the browser process
+    /// has attached an event bus.
+    static RENDERER_SCOPES: RefCell<HashMap<i32, Option<FunctionScope>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Installs the event bus in |context| if one is attached to |browser| and its
+/// scope contains |frame|. The first context of a browser in this process asks
+/// the browser process for the scope.
+pub(crate) fn on_context_created(browser: &Browser, frame: &Frame, context: &V8Context) {
+    let Ok(identifier) = browser.get_identifier() else {
+        return;
+    };
+
+    let scope = RENDERER_SCOPES.with_borrow_mut(|scopes| match scopes.get(&identifier) {
+        Some(scope) => Some(*scope),
+        None => {
+            scopes.insert(identifier, None);
+
+            None
+        }
+    });
+
+    match scope {
+        Some(Some(scope)) => {
+            if scope
+                .contains(browser, frame)
+                .unwrap_or(false)
+            {
+                let _ = install(context);
+            }
+        },
+        Some(None) => {},
+        None => {
+            let _ =
+                frame.send_process_message(ProcessId::Browser, ProcessMessage::new(SYNC_MESSAGE));
+        }
+    }
+}
+
+/// Forgets the event bus scope of |browser|.
+pub(crate) fn on_browser_destroyed(browser: &Browser) {
+    if let Ok(identifier) = browser.get_identifier() {
+        RENDERER_SCOPES.with_borrow_mut(|scopes| scopes.remove(&identifier));
+    }
+}
+
+/// Handles event bus messages sent to the render process. Returns true if
+/// |message| was handled.
+pub(crate) fn on_renderer_process_message(
+    browser: &Browser,
+    frame: &Frame,
+    source_process: ProcessId,
+    message: &ProcessMessage
+) -> bool {
+    if source_process != ProcessId::Browser {
+        return false;
+    }
+
+    match message.get_name().ok().as_deref() {
+        Some(ENABLE_MESSAGE) => {
+            let _ = enable_in_renderer(browser, message);
+
+            true
+        },
+        Some(DISPATCH_MESSAGE) => {
+            let _ = dispatch_in_renderer(browser, frame, message);
+
+            true
+        },
+        _ => false
+    }
+}
+
+/// Records the scope of the event bus attached to |browser| and installs it in
+/// the frames of |browser| in this process that are in scope.
+fn enable_in_renderer(browser: &Browser, message: &ProcessMessage) -> Result<()> {
+    let Some(arguments) = message.get_argument_list()? else {
+        return Ok(());
+    };
+
+    let scope = FunctionScope::from(arguments.get_int(0)?);
+    let identifier = browser.get_identifier()?;
+    let previous = RENDERER_SCOPES.with_borrow_mut(|scopes| scopes.insert(identifier, Some(scope)));
+
+    if previous == Some(Some(scope)) {
+        return Ok(());
+    }
+
+    for frame_identifier in browser.get_frame_identifiers()? {
+        let Some(frame) = browser.get_frame_by_identifier(frame_identifier)? else {
+            continue;
+        };
+
+        if !scope.contains(browser, &frame)? {
+            continue;
+        }
+
+        if let Some(context) = frame.get_v8_context()? {
+            install(&context)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Calls the JavaScript listeners of |frame| for an event sent by the browser
+/// process.
+fn dispatch_in_renderer(browser: &Browser, frame: &Frame, message: &ProcessMessage) -> Result<()> {
+    let Some((name, payload)) = parse_message(message) else {
+        return Ok(());
+    };
+
+    let identifier = browser.get_identifier()?;
+    let Some(scope) = RENDERER_SCOPES.with_borrow(|scopes| {
+        scopes
+            .get(&identifier)
+            .copied()
+            .flatten()
+    }) else {
+        return Ok(());
+    };
+
+    if !scope.contains(browser, frame)? {
+        return Ok(());
+    }
+
+    // The frame may have navigated away and lost its context.
+    let Some(context) = frame.get_v8_context()? else {
+        return Ok(());
+    };
+
+    let dispatch = context
+        .get_global()?
+        .get_value_by_key(DISPATCH_FUNCTION)?
+        .ok_or_else(|| anyhow!("The event bus is not installed in this context."))?;
+
+    dispatch.execute_function_with_context(
+        context,
+        None,
+        &[V8Value::new_string(&name)?, V8Value::new_string(&payload)?]
+    )?;
+
+    Ok(())
+}
+
+/// Defines cefEmit, cefOn and cefOff in |context| unless they already exist.
+fn install(context: &V8Context) -> Result<()> {
+    if !context.enter()? {
+        return Err(anyhow!("Failed to enter V8 context.").into());
+    }
+
+    let result = context
+        .get_global()
+        .and_then(|global| {
+            if global.has_value_by_key(DISPATCH_FUNCTION)? {
+                return Ok(());
+            }
+
+            let emit = V8Value::new_function(NATIVE_EMIT, V8Handler::new(NativeEmit))?;
+
+            global.set_value_by_key(NATIVE_EMIT, emit, V8PropertyAttribute::DontEnum)?;
+            context
+                .eval(BOOTSTRAP_SCRIPT, "cef-ui://event-bus.js", 1)
+                .map(|_| ())
+        });
+
+    context.exit()?;
+    result
+}
+
+/// The native function behind cefEmit. Forwards events to the browser process.
+struct NativeEmit;
+
+impl V8HandlerCallbacks for NativeEmit {
+    fn execute(
+        &mut self,
+        _name: CefStr<'_>,
+        _object: V8Value,
+        arguments: Vec<V8Value>
+    ) -> anyhow::Result<Option<V8Value>> {
+        let (Some(name), Some(payload)) = (arguments.first(), arguments.get(1)) else {
+            return Err(anyhow!("cefEmit expects an event name and a payload."));
+        };
+
+        let frame = V8Context::get_current_context()
+            .map(|context| context.get_frame())
+            .transpose()?
+            .flatten()
+            .ok_or_else(|| anyhow!("cefEmit must be called from a frame."))?;
+
+        frame.send_process_message(
+            ProcessId::Browser,
+            create_message(
+                EMIT_MESSAGE,
+                &name.get_string_value()?,
+                &payload.get_string_value()?
+            )?
+        )?;
+
+        Ok(None)
+    }
+}
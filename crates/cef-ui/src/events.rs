@@ -1,3 +1,4 @@
+use crate::Point;
 use bitflags::bitflags;
 use cef_ui_sys::{
     cef_event_flags_t, cef_event_flags_t_EVENTFLAG_ALTGR_DOWN,
@@ -11,12 +12,15 @@ use cef_ui_sys::{
     cef_key_event_t, cef_key_event_type_t, cef_mouse_button_type_t, cef_mouse_event_t,
     cef_pointer_type_t, cef_touch_event_t, cef_touch_event_type_t, char16_t
 };
-use std::ffi::c_int;
+use std::{
+    ffi::c_int,
+    time::{Duration, Instant}
+};
 
 bitflags! {
     /// Supported event bit flags.
     #[allow(non_upper_case_globals)]
-    #[derive(Default, Debug, Clone, Copy)]
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
     pub struct EventFlags: cef_event_flags_t {
         const None = cef_event_flags_t_EVENTFLAG_NONE;
         const CapsLockOn = cef_event_flags_t_EVENTFLAG_CAPS_LOCK_ON;
@@ -36,6 +40,28 @@ bitflags! {
     }
 }
 
+impl EventFlags {
+    /// Returns the modifier key that triggers application shortcuts: Command on
+    /// macOS and Control elsewhere.
+    pub fn shortcut_key() -> Self {
+        match cfg!(target_os = "macos") {
+            true => EventFlags::CommandDown,
+            false => EventFlags::ControlDown
+        }
+    }
+
+    /// Returns only the Shift, Control, Alt, AltGr and Command flags, dropping
+    /// lock states, mouse buttons and key location.
+    pub fn modifier_keys(&self) -> Self {
+        *self
+            & (EventFlags::ShiftDown
+                | EventFlags::ControlDown
+                | EventFlags::AltDown
+                | EventFlags::AltgrDown
+                | EventFlags::CommandDown)
+    }
+}
+
 #[cfg(all(target_os = "windows", target_arch = "x86_64"))]
 impl From<u32> for EventFlags {
     fn from(value: u32) -> Self {
@@ -134,7 +160,7 @@ impl From<&KeyEventType> for cef_key_event_type_t {
 
 /// Lifted from WebCore/platform/chromium/KeyboardCodes.h for the list of values.
 #[repr(transparent)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct WindowsKeyCode(i32);
 
 #[allow(non_upper_case_globals)]
@@ -317,12 +343,191 @@ impl WindowsKeyCode {
     pub const Unknown: Self = Self(0);
 }
 
+impl WindowsKeyCode {
+    /// Maps a DOM KeyboardEvent.code value ("KeyA", "Enter", "ArrowLeft", ...)
+    /// to the Windows key code for the same physical key on a US keyboard
+    /// layout. Returns None if the code is not known.
+    pub fn from_dom_code(code: &str) -> Option<Self> {
+        let key_code = match code {
+            "Backquote" => WindowsKeyCode::OEM3,
+            "Backslash" => WindowsKeyCode::OEM5,
+            "BracketLeft" => WindowsKeyCode::OEM4,
+            "BracketRight" => WindowsKeyCode::OEM6,
+            "Comma" => WindowsKeyCode::OEMComma,
+            "Digit0" => WindowsKeyCode::Key0,
+            "Digit1" => WindowsKeyCode::Key1,
+            "Digit2" => WindowsKeyCode::Key2,
+            "Digit3" => WindowsKeyCode::Key3,
+            "Digit4" => WindowsKeyCode::Key4,
+            "Digit5" => WindowsKeyCode::Key5,
+            "Digit6" => WindowsKeyCode::Key6,
+            "Digit7" => WindowsKeyCode::Key7,
+            "Digit8" => WindowsKeyCode::Key8,
+            "Digit9" => WindowsKeyCode::Key9,
+            "Equal" => WindowsKeyCode::OEMPlus,
+            "IntlBackslash" => WindowsKeyCode::OEM102,
+            "KeyA" => WindowsKeyCode::A,
+            "KeyB" => WindowsKeyCode::B,
+            "KeyC" => WindowsKeyCode::C,
+            "KeyD" => WindowsKeyCode::D,
+            "KeyE" => WindowsKeyCode::E,
+            "KeyF" => WindowsKeyCode::F,
+            "KeyG" => WindowsKeyCode::G,
+            "KeyH" => WindowsKeyCode::H,
+            "KeyI" => WindowsKeyCode::I,
+            "KeyJ" => WindowsKeyCode::J,
+            "KeyK" => WindowsKeyCode::K,
+            "KeyL" => WindowsKeyCode::L,
+            "KeyM" => WindowsKeyCode::M,
+            "KeyN" => WindowsKeyCode::N,
+            "KeyO" => WindowsKeyCode::O,
+            "KeyP" => WindowsKeyCode::P,
+            "KeyQ" => WindowsKeyCode::Q,
+            "KeyR" => WindowsKeyCode::R,
+            "KeyS" => WindowsKeyCode::S,
+            "KeyT" => WindowsKeyCode::T,
+            "KeyU" => WindowsKeyCode::U,
+            "KeyV" => WindowsKeyCode::V,
+            "KeyW" => WindowsKeyCode::W,
+            "KeyX" => WindowsKeyCode::X,
+            "KeyY" => WindowsKeyCode::Y,
+            "KeyZ" => WindowsKeyCode::Z,
+            "Minus" => WindowsKeyCode::OEMMinus,
+            "Period" => WindowsKeyCode::OEMPeriod,
+            "Quote" => WindowsKeyCode::OEM7,
+            "Semicolon" => WindowsKeyCode::OEM1,
+            "Slash" => WindowsKeyCode::OEM2,
+            "AltLeft" => WindowsKeyCode::LMenu,
+            "AltRight" => WindowsKeyCode::RMenu,
+            "Backspace" => WindowsKeyCode::Back,
+            "CapsLock" => WindowsKeyCode::Capital,
+            "ContextMenu" => WindowsKeyCode::Apps,
+            "ControlLeft" => WindowsKeyCode::LControl,
+            "ControlRight" => WindowsKeyCode::RControl,
+            "Enter" | "NumpadEnter" => WindowsKeyCode::Return,
+            "MetaLeft" | "OSLeft" => WindowsKeyCode::LWin,
+            "MetaRight" | "OSRight" => WindowsKeyCode::RWin,
+            "ShiftLeft" => WindowsKeyCode::LShift,
+            "ShiftRight" => WindowsKeyCode::RShift,
+            "Space" => WindowsKeyCode::Space,
+            "Tab" => WindowsKeyCode::Tab,
+            "Convert" => WindowsKeyCode::Convert,
+            "KanaMode" => WindowsKeyCode::Kana,
+            "NonConvert" => WindowsKeyCode::NonConvert,
+            "Delete" => WindowsKeyCode::Delete,
+            "End" => WindowsKeyCode::End,
+            "Help" => WindowsKeyCode::Help,
+            "Home" => WindowsKeyCode::Home,
+            "Insert" => WindowsKeyCode::Insert,
+            "PageDown" => WindowsKeyCode::Next,
+            "PageUp" => WindowsKeyCode::Prior,
+            "ArrowDown" => WindowsKeyCode::Down,
+            "ArrowLeft" => WindowsKeyCode::Left,
+            "ArrowRight" => WindowsKeyCode::Right,
+            "ArrowUp" => WindowsKeyCode::Up,
+            "NumLock" => WindowsKeyCode::NumLock,
+            "Numpad0" => WindowsKeyCode::Numpad0,
+            "Numpad1" => WindowsKeyCode::Numpad1,
+            "Numpad2" => WindowsKeyCode::Numpad2,
+            "Numpad3" => WindowsKeyCode::Numpad3,
+            "Numpad4" => WindowsKeyCode::Numpad4,
+            "Numpad5" => WindowsKeyCode::Numpad5,
+            "Numpad6" => WindowsKeyCode::Numpad6,
+            "Numpad7" => WindowsKeyCode::Numpad7,
+            "Numpad8" => WindowsKeyCode::Numpad8,
+            "Numpad9" => WindowsKeyCode::Numpad9,
+            "NumpadAdd" => WindowsKeyCode::Add,
+            "NumpadComma" => WindowsKeyCode::Separator,
+            "NumpadDecimal" => WindowsKeyCode::Decimal,
+            "NumpadDivide" => WindowsKeyCode::Divide,
+            "NumpadMultiply" | "NumpadStar" => WindowsKeyCode::Multiply,
+            "NumpadSubtract" => WindowsKeyCode::Subtract,
+            "NumpadClear" => WindowsKeyCode::Clear,
+            "Escape" => WindowsKeyCode::Escape,
+            "PrintScreen" => WindowsKeyCode::Snapshot,
+            "ScrollLock" => WindowsKeyCode::Scroll,
+            "Pause" => WindowsKeyCode::Pause,
+            "BrowserBack" => WindowsKeyCode::BrowserBack,
+            "BrowserFavorites" => WindowsKeyCode::BrowserFavorites,
+            "BrowserForward" => WindowsKeyCode::BrowserForward,
+            "BrowserHome" => WindowsKeyCode::BrowserHome,
+            "BrowserRefresh" => WindowsKeyCode::BrowserRefresh,
+            "BrowserSearch" => WindowsKeyCode::BrowserSearch,
+            "BrowserStop" => WindowsKeyCode::BrowserStop,
+            "LaunchApp1" => WindowsKeyCode::LaunchApp1,
+            "LaunchApp2" => WindowsKeyCode::LaunchApp2,
+            "LaunchMail" => WindowsKeyCode::LaunchMail,
+            "MediaPlayPause" => WindowsKeyCode::MediaPlayPause,
+            "MediaSelect" => WindowsKeyCode::LaunchMediaSelect,
+            "MediaStop" => WindowsKeyCode::MediaStop,
+            "MediaTrackNext" => WindowsKeyCode::MediaNextTrack,
+            "MediaTrackPrevious" => WindowsKeyCode::MediaPrevTrack,
+            "Sleep" => WindowsKeyCode::Sleep,
+            "AudioVolumeDown" => WindowsKeyCode::VolumeDown,
+            "AudioVolumeMute" => WindowsKeyCode::VolumeMute,
+            "AudioVolumeUp" => WindowsKeyCode::VolumeUp,
+            "Select" => WindowsKeyCode::Select,
+            "F1" => WindowsKeyCode::F1,
+            "F2" => WindowsKeyCode::F2,
+            "F3" => WindowsKeyCode::F3,
+            "F4" => WindowsKeyCode::F4,
+            "F5" => WindowsKeyCode::F5,
+            "F6" => WindowsKeyCode::F6,
+            "F7" => WindowsKeyCode::F7,
+            "F8" => WindowsKeyCode::F8,
+            "F9" => WindowsKeyCode::F9,
+            "F10" => WindowsKeyCode::F10,
+            "F11" => WindowsKeyCode::F11,
+            "F12" => WindowsKeyCode::F12,
+            "F13" => WindowsKeyCode::F13,
+            "F14" => WindowsKeyCode::F14,
+            "F15" => WindowsKeyCode::F15,
+            "F16" => WindowsKeyCode::F16,
+            "F17" => WindowsKeyCode::F17,
+            "F18" => WindowsKeyCode::F18,
+            "F19" => WindowsKeyCode::F19,
+            "F20" => WindowsKeyCode::F20,
+            "F21" => WindowsKeyCode::F21,
+            "F22" => WindowsKeyCode::F22,
+            "F23" => WindowsKeyCode::F23,
+            "F24" => WindowsKeyCode::F24,
+            _ => return None
+        };
+
+        Some(key_code)
+    }
+
+    /// Returns true for the Shift, Control, Alt and Windows/Command keys.
+    pub fn is_modifier(&self) -> bool {
+        matches!(
+            *self,
+            WindowsKeyCode::Shift
+                | WindowsKeyCode::LShift
+                | WindowsKeyCode::RShift
+                | WindowsKeyCode::Control
+                | WindowsKeyCode::LControl
+                | WindowsKeyCode::RControl
+                | WindowsKeyCode::Menu
+                | WindowsKeyCode::LMenu
+                | WindowsKeyCode::RMenu
+                | WindowsKeyCode::LWin
+                | WindowsKeyCode::RWin
+        )
+    }
+}
+
 impl From<i32> for WindowsKeyCode {
     fn from(value: i32) -> Self {
         Self(value)
     }
 }
 
+impl From<WindowsKeyCode> for i32 {
+    fn from(value: WindowsKeyCode) -> Self {
+        value.0
+    }
+}
+
 impl From<char> for WindowsKeyCode {
     fn from(value: char) -> Self {
         Self(value as i32)
@@ -372,6 +577,92 @@ pub struct KeyEvent {
 }
 
 impl KeyEvent {
+    /// A key event of |event_type| for |windows_key_code| without modifiers or
+    /// characters. Use the builder functions to fill in the rest.
+    pub fn new(event_type: KeyEventType, windows_key_code: WindowsKeyCode) -> Self {
+        Self {
+            event_type,
+            modifiers: EventFlags::None,
+            windows_key_code,
+            native_key_code: 0,
+            is_system_key: false,
+            character: 0,
+            unmodified_character: 0,
+            focus_on_editable_field: false
+        }
+    }
+
+    /// A key press, as passed to send_key_event before any character events.
+    pub fn key_down(windows_key_code: WindowsKeyCode) -> Self {
+        Self::new(KeyEventType::RawKeyDown, windows_key_code)
+    }
+
+    /// A key release.
+    pub fn key_up(windows_key_code: WindowsKeyCode) -> Self {
+        Self::new(KeyEventType::KeyUp, windows_key_code)
+    }
+
+    /// A character event for the UTF-16 code unit |character|. On Windows the
+    /// character is also passed as the key code, which is what CEF expects
+    /// there.
+    pub fn char(character: u16) -> Self {
+        let windows_key_code = match cfg!(target_os = "windows") {
+            true => WindowsKeyCode(character as i32),
+            false => WindowsKeyCode::Unknown
+        };
+
+        Self::new(KeyEventType::Char, windows_key_code).character(character)
+    }
+
+    /// Sets the pressed modifier keys.
+    pub fn modifiers(mut self, modifiers: EventFlags) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    /// Sets the platform key code. This is only needed when the page or CEF
+    /// relies on it, e.g. for key events on macOS.
+    pub fn native_key_code(mut self, native_key_code: i32) -> Self {
+        self.native_key_code = native_key_code;
+        self
+    }
+
+    /// Marks the event as a system key event (Windows only).
+    pub fn system_key(mut self, is_system_key: bool) -> Self {
+        self.is_system_key = is_system_key;
+        self
+    }
+
+    /// Sets both the character and the unmodified character.
+    pub fn character(mut self, character: u16) -> Self {
+        self.character = character;
+        self.unmodified_character = character;
+        self
+    }
+
+    /// Sets the character as unmodified by concurrently held modifiers.
+    pub fn unmodified_character(mut self, unmodified_character: u16) -> Self {
+        self.unmodified_character = unmodified_character;
+        self
+    }
+
+    /// Returns true for RawKeyDown and KeyDown events.
+    pub fn is_key_down(&self) -> bool {
+        matches!(
+            self.event_type,
+            KeyEventType::RawKeyDown | KeyEventType::KeyDown
+        )
+    }
+
+    /// Returns true if this is a key down event for |windows_key_code| with
+    /// exactly the |modifiers| modifier keys held, e.g. to match shortcuts in
+    /// KeyboardHandlerCallbacks::on_pre_key_event.
+    pub fn is_shortcut(&self, windows_key_code: WindowsKeyCode, modifiers: EventFlags) -> bool {
+        self.is_key_down()
+            && self.windows_key_code == windows_key_code
+            && self.modifiers.modifier_keys() == modifiers.modifier_keys()
+    }
+
     /// Convert from a pointer.
     pub fn from_ptr(ptr: *const cef_key_event_t) -> Option<KeyEvent> {
         unsafe { ptr.as_ref().map(|v| v.into()) }
@@ -433,6 +724,17 @@ pub enum MouseButtonType {
     Right
 }
 
+impl MouseButtonType {
+    /// Returns the event flag that is set while this button is held down.
+    pub fn event_flag(&self) -> EventFlags {
+        match self {
+            MouseButtonType::Left => EventFlags::LeftMouseButton,
+            MouseButtonType::Middle => EventFlags::MiddleMouseButton,
+            MouseButtonType::Right => EventFlags::RightMouseButton
+        }
+    }
+}
+
 impl From<cef_mouse_button_type_t> for MouseButtonType {
     fn from(value: cef_mouse_button_type_t) -> Self {
         Self::from(&value)
@@ -466,7 +768,7 @@ impl From<&MouseButtonType> for cef_mouse_button_type_t {
 }
 
 /// Structure representing mouse event information.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MouseEvent {
     /// X coordinate relative to the left side of the view.
     pub x: i32,
@@ -479,6 +781,39 @@ pub struct MouseEvent {
     pub modifiers: EventFlags
 }
 
+impl MouseEvent {
+    /// A mouse event at |x|, |y| without modifiers.
+    pub fn new(x: i32, y: i32) -> Self {
+        Self {
+            x,
+            y,
+            modifiers: EventFlags::None
+        }
+    }
+
+    /// A mouse event at |point| without modifiers.
+    pub fn at(point: Point) -> Self {
+        Self::new(point.x, point.y)
+    }
+
+    /// Sets the pressed modifier keys and mouse buttons.
+    pub fn modifiers(mut self, modifiers: EventFlags) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    /// Marks |button| as held down.
+    pub fn button(mut self, button: MouseButtonType) -> Self {
+        self.modifiers |= button.event_flag();
+        self
+    }
+
+    /// Returns the position of the event.
+    pub fn point(&self) -> Point {
+        Point::new(self.x, self.y)
+    }
+}
+
 impl From<MouseEvent> for cef_mouse_event_t {
     fn from(value: MouseEvent) -> Self {
         Self::from(&value)
@@ -495,6 +830,113 @@ impl From<&MouseEvent> for cef_mouse_event_t {
     }
 }
 
+/// Counts repeated clicks of the same button at the same place, so that they
+/// can be reported as double and triple clicks through the click_count of
+/// BrowserHost::send_mouse_click_event.
+#[derive(Debug, Clone)]
+pub struct ClickCounter {
+    interval: Duration,
+    distance: i32,
+    last:     Option<(MouseButtonType, Point, Instant, i32)>
+}
+
+impl ClickCounter {
+    /// Clicks closer together than this count as a multi-click by default.
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+
+    /// Clicks further apart than this many pixels do not count as a
+    /// multi-click by default.
+    pub const DEFAULT_DISTANCE: i32 = 4;
+
+    pub fn new() -> Self {
+        Self::with_limits(Self::DEFAULT_INTERVAL, Self::DEFAULT_DISTANCE)
+    }
+
+    /// A counter that counts clicks at most |interval| apart in time and
+    /// |distance| pixels apart in either direction as one multi-click.
+    pub fn with_limits(interval: Duration, distance: i32) -> Self {
+        Self {
+            interval,
+            distance,
+            last: None
+        }
+    }
+
+    /// Records a press of |button| at |point| and returns its click count,
+    /// starting at 1.
+    pub fn press(&mut self, button: MouseButtonType, point: Point) -> i32 {
+        let now = Instant::now();
+        let count = match self.last {
+            Some((last_button, last_point, time, count))
+                if last_button == button
+                    && now.duration_since(time) <= self.interval
+                    && (last_point.x - point.x).abs() <= self.distance
+                    && (last_point.y - point.y).abs() <= self.distance =>
+            {
+                count + 1
+            },
+            _ => 1
+        };
+
+        self.last = Some((button, point, now, count));
+
+        count
+    }
+
+    /// Returns the click count of the last press, or 0 if there was none.
+    pub fn count(&self) -> i32 {
+        self.last
+            .map(|(_, _, _, count)| count)
+            .unwrap_or(0)
+    }
+
+    /// Forgets the last press, so that the next press starts a new click.
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+impl Default for ClickCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Converts precise wheel deltas, e.g. from touchpads, into the whole pixel
+/// deltas that BrowserHost::send_mouse_wheel_event takes. Fractions of a pixel
+/// are carried over to the next delta so that slow scrolling is not lost.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WheelDelta {
+    remainder_x: f64,
+    remainder_y: f64
+}
+
+impl WheelDelta {
+    /// The number of pixels scrolled per line for line based deltas. This
+    /// matches the value Chromium uses for a single wheel tick.
+    pub const PIXELS_PER_LINE: f64 = 40.0;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a delta of |x|, |y| pixels and returns the whole pixels to scroll.
+    pub fn pixels(&mut self, x: f64, y: f64) -> (i32, i32) {
+        let x = self.remainder_x + x;
+        let y = self.remainder_y + y;
+
+        self.remainder_x = x.fract();
+        self.remainder_y = y.fract();
+
+        (x.trunc() as i32, y.trunc() as i32)
+    }
+
+    /// Adds a delta of |x|, |y| lines and returns the whole pixels to scroll.
+    pub fn lines(&mut self, x: f64, y: f64) -> (i32, i32) {
+        self.pixels(x * Self::PIXELS_PER_LINE, y * Self::PIXELS_PER_LINE)
+    }
+}
+
 /// Touch points states types.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum TouchEventType {
@@ -647,3 +1089,103 @@ impl From<&TouchEvent> for cef_touch_event_t {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_dom_code_keys() {
+        for (code, key_code) in [
+            ("KeyA", 0x41),
+            ("KeyZ", 0x5A),
+            ("Digit0", 0x30),
+            ("Digit9", 0x39),
+            ("F1", 0x70),
+            ("F24", 0x87),
+            ("Numpad0", 0x60),
+            ("Numpad9", 0x69)
+        ] {
+            assert_eq!(
+                WindowsKeyCode::from_dom_code(code),
+                Some(WindowsKeyCode::from(key_code))
+            );
+        }
+    }
+
+    #[test]
+    fn from_dom_code_named_keys() {
+        assert_eq!(
+            WindowsKeyCode::from_dom_code("Enter"),
+            Some(WindowsKeyCode::Return)
+        );
+        assert_eq!(
+            WindowsKeyCode::from_dom_code("NumpadEnter"),
+            Some(WindowsKeyCode::Return)
+        );
+        assert_eq!(
+            WindowsKeyCode::from_dom_code("ArrowLeft"),
+            Some(WindowsKeyCode::Left)
+        );
+        assert_eq!(
+            WindowsKeyCode::from_dom_code("PageUp"),
+            Some(WindowsKeyCode::Prior)
+        );
+        assert_eq!(
+            WindowsKeyCode::from_dom_code("Backquote"),
+            Some(WindowsKeyCode::OEM3)
+        );
+        assert_eq!(
+            WindowsKeyCode::from_dom_code("MetaLeft"),
+            Some(WindowsKeyCode::LWin)
+        );
+        assert_eq!(
+            WindowsKeyCode::from_dom_code("OSLeft"),
+            Some(WindowsKeyCode::LWin)
+        );
+    }
+
+    #[test]
+    fn from_dom_code_unknown() {
+        assert_eq!(WindowsKeyCode::from_dom_code(""), None);
+        assert_eq!(WindowsKeyCode::from_dom_code("keya"), None);
+        assert_eq!(WindowsKeyCode::from_dom_code("F25"), None);
+        assert_eq!(WindowsKeyCode::from_dom_code("Fn"), None);
+    }
+
+    #[test]
+    fn is_modifier() {
+        for code in ["ShiftLeft", "ControlRight", "AltLeft", "MetaRight"] {
+            assert!(
+                WindowsKeyCode::from_dom_code(code)
+                    .unwrap()
+                    .is_modifier()
+            );
+        }
+
+        assert!(WindowsKeyCode::Shift.is_modifier());
+        assert!(!WindowsKeyCode::A.is_modifier());
+        assert!(!WindowsKeyCode::Capital.is_modifier());
+    }
+
+    #[test]
+    fn from_char() {
+        assert_eq!(WindowsKeyCode::from('A'), WindowsKeyCode::A);
+        assert_eq!(WindowsKeyCode::from('0'), WindowsKeyCode::Key0);
+        assert_eq!(WindowsKeyCode::from(' '), WindowsKeyCode::Space);
+    }
+
+    #[test]
+    fn modifier_keys() {
+        let flags = EventFlags::ShiftDown
+            | EventFlags::AltgrDown
+            | EventFlags::CapsLockOn
+            | EventFlags::LeftMouseButton
+            | EventFlags::IsLeft;
+
+        assert_eq!(
+            flags.modifier_keys(),
+            EventFlags::ShiftDown | EventFlags::AltgrDown
+        );
+    }
+}
@@ -1,5 +1,6 @@
-use crate::{ref_counted_ptr, try_c, CefString, DictionaryValue, RequestContext};
-use anyhow::Result;
+use crate::{
+    ref_counted_ptr, try_c, CefString, DictionaryValue, ExtensionHandler, RequestContext, Result
+};
 use cef_ui_sys::cef_extension_t;
 
 // Object representing an extension. Methods may be called on any thread unless
@@ -48,15 +49,14 @@ impl Extension {
         })
     }
 
-    // TODO: Fix this!
-
-    //     ///
-    //     /// Returns the handler for this extension. Will return NULL for internal
-    //     /// extensions or if no handler was passed to
-    //     /// cef_request_context_t::LoadExtension.
-    //     ///
-    //     struct _cef_extension_handler_t*(CEF_CALLBACK* get_handler)(
-    //     struct _cef_extension_t* self);
+    /// Returns the handler for this extension. Will return NULL for internal
+    /// extensions or if no handler was passed to
+    /// cef_request_context_t::LoadExtension.
+    pub fn get_handler(&self) -> Result<Option<ExtensionHandler>> {
+        try_c!(self, get_handler, {
+            Ok(ExtensionHandler::from_ptr(get_handler(self.as_ptr())))
+        })
+    }
 
     /// Returns the request context that loaded this extension. Will return NULL
     /// for internal extensions or if the extension has been unloaded. See the
@@ -1,10 +1,38 @@
-use crate::{ref_counted_ptr, Browser, ErrorCode, Extension, RefCountedPtr, Wrappable, Wrapped};
+use crate::{
+    ref_counted_ptr, try_c, unwind::catch_unwind_or, Browser, BrowserSettings, CefString, Client,
+    ErrorCode, Extension, RefCountedPtr, Result, StreamReader, WindowInfo, Wrappable, Wrapped
+};
 use cef_ui_sys::{
     cef_browser_settings_t, cef_browser_t, cef_client_t, cef_errorcode_t, cef_extension_handler_t,
     cef_extension_t, cef_get_extension_resource_callback_t, cef_string_t, cef_window_info_t
 };
 use std::{ffi::c_int, mem::zeroed, ptr::null_mut};
 
+// Callback structure used for asynchronous continuation of
+// cef_extension_handler_t::GetExtensionResource.
+ref_counted_ptr!(
+    GetExtensionResourceCallback,
+    cef_get_extension_resource_callback_t
+);
+
+impl GetExtensionResourceCallback {
+    /// Continue the request. Read the resource contents from |stream|.
+    pub fn cont(&self, stream: Option<StreamReader>) -> Result<()> {
+        try_c!(self, cont, {
+            let stream = stream
+                .map(|stream| stream.into_raw())
+                .unwrap_or_else(null_mut);
+
+            Ok(cont(self.as_ptr(), stream))
+        })
+    }
+
+    /// Cancel the request.
+    pub fn cancel(&self) -> Result<()> {
+        try_c!(self, cancel, { Ok(cancel(self.as_ptr())) })
+    }
+}
+
 /// Implement this structure to handle events related to browser extensions. The
 /// functions of this structure will be called on the UI thread. See
 /// cef_request_context_t::LoadExtension for information about extension
@@ -12,64 +40,65 @@ use std::{ffi::c_int, mem::zeroed, ptr::null_mut};
 pub trait ExtensionHandlerCallbacks: Send + Sync + 'static {
     /// Called if the cef_request_context_t::LoadExtension request fails. |result|
     /// will be the error code.
-    fn on_extension_load_failed(&mut self, result: ErrorCode);
+    fn on_extension_load_failed(&mut self, _result: ErrorCode) {}
 
     /// Called if the cef_request_context_t::LoadExtension request succeeds.
     /// |extension| is the loaded extension.
-    fn on_extension_loaded(&mut self, extension: Extension);
+    fn on_extension_loaded(&mut self, _extension: Extension) {}
 
     /// Called after the cef_extension_t::Unload request has completed.
-    fn on_extension_unloaded(&mut self, extension: Extension);
-
-    // TODO: Fix this!
-
-    //     /// Called when an extension needs a browser to host a background script
-    //     /// specified via the "background" manifest key. The browser will have no
-    //     /// visible window and cannot be displayed. |extension| is the extension that
-    //     /// is loading the background script. |url| is an internally generated
-    //     /// reference to an HTML page that will be used to load the background script
-    //     /// via a "<script>" src attribute. To allow creation of the browser
-    //     /// optionally modify |client| and |settings| and return false (0). To cancel
-    //     /// creation of the browser (and consequently cancel load of the background
-    //     /// script) return true (1). Successful creation will be indicated by a call
-    //     /// to cef_life_span_handler_t::OnAfterCreated, and
-    //     /// cef_browser_host_t::IsBackgroundHost will return true (1) for the
-    //     /// resulting browser. See https://developer.chrome.com/extensions/event_pages
-    //     /// for more information about extension background script usage.
-    //     ///
-    //     int(CEF_CALLBACK* on_before_background_browser)(
-    //     struct _cef_extension_handler_t* self,
-    //     struct _cef_extension_t* extension,
-    //     const cef_string_t* url,
-    //     struct _cef_client_t** client,
-    //     struct _cef_browser_settings_t* settings);
-
-    //     ///
-    //     /// Called when an extension API (e.g. chrome.tabs.create) requests creation
-    //     /// of a new browser. |extension| and |browser| are the source of the API
-    //     /// call. |active_browser| may optionally be specified via the windowId
-    //     /// property or returned via the get_active_browser() callback and provides
-    //     /// the default |client| and |settings| values for the new browser. |index| is
-    //     /// the position value optionally specified via the index property. |url| is
-    //     /// the URL that will be loaded in the browser. |active| is true (1) if the
-    //     /// new browser should be active when opened.  To allow creation of the
-    //     /// browser optionally modify |windowInfo|, |client| and |settings| and return
-    //     /// false (0). To cancel creation of the browser return true (1). Successful
-    //     /// creation will be indicated by a call to
-    //     /// cef_life_span_handler_t::OnAfterCreated. Any modifications to |windowInfo|
-    //     /// will be ignored if |active_browser| is wrapped in a cef_browser_view_t.
-    //     ///
-    //     int(CEF_CALLBACK* on_before_browser)(
-    //     struct _cef_extension_handler_t* self,
-    //     struct _cef_extension_t* extension,
-    //     struct _cef_browser_t* browser,
-    //     struct _cef_browser_t* active_browser,
-    //     int index,
-    //     const cef_string_t* url,
-    //     int active,
-    //     struct _cef_window_info_t* windowInfo,
-    //     struct _cef_client_t** client,
-    //     struct _cef_browser_settings_t* settings);
+    fn on_extension_unloaded(&mut self, _extension: Extension) {}
+
+    /// Called when an extension needs a browser to host a background script
+    /// specified via the "background" manifest key. The browser will have no
+    /// visible window and cannot be displayed. |extension| is the extension that
+    /// is loading the background script. |url| is an internally generated
+    /// reference to an HTML page that will be used to load the background script
+    /// via a "<script>" src attribute. To allow creation of the browser
+    /// optionally modify |client| and |settings| and return false (0). To cancel
+    /// creation of the browser (and consequently cancel load of the background
+    /// script) return true (1). Successful creation will be indicated by a call
+    /// to cef_life_span_handler_t::OnAfterCreated, and
+    /// cef_browser_host_t::IsBackgroundHost will return true (1) for the
+    /// resulting browser. See https://developer.chrome.com/extensions/event_pages
+    /// for more information about extension background script usage.
+    fn on_before_background_browser(
+        &mut self,
+        _extension: Extension,
+        _url: String,
+        _client: &mut Option<Client>,
+        _settings: &mut BrowserSettings
+    ) -> bool {
+        false
+    }
+
+    /// Called when an extension API (e.g. chrome.tabs.create) requests creation
+    /// of a new browser. |extension| and |browser| are the source of the API
+    /// call. |active_browser| may optionally be specified via the windowId
+    /// property or returned via the get_active_browser() callback and provides
+    /// the default |client| and |settings| values for the new browser. |index| is
+    /// the position value optionally specified via the index property. |url| is
+    /// the URL that will be loaded in the browser. |active| is true (1) if the
+    /// new browser should be active when opened.  To allow creation of the
+    /// browser optionally modify |windowInfo|, |client| and |settings| and return
+    /// false (0). To cancel creation of the browser return true (1). Successful
+    /// creation will be indicated by a call to
+    /// cef_life_span_handler_t::OnAfterCreated. Any modifications to |windowInfo|
+    /// will be ignored if |active_browser| is wrapped in a cef_browser_view_t.
+    fn on_before_browser(
+        &mut self,
+        _extension: Extension,
+        _browser: Browser,
+        _active_browser: Option<Browser>,
+        _index: i32,
+        _url: String,
+        _active: bool,
+        _window_info: &mut WindowInfo,
+        _client: &mut Option<Client>,
+        _settings: &mut BrowserSettings
+    ) -> bool {
+        false
+    }
 
     /// Called when no tabId is specified to an extension API call that accepts a
     /// tabId parameter (e.g. chrome.tabs.*). |extension| and |browser| are the
@@ -80,10 +109,12 @@ pub trait ExtensionHandlerCallbacks: Send + Sync + 'static {
     /// enabled, in which case |include_incognito| will be true (1).
     fn get_active_browser(
         &mut self,
-        extension: Extension,
-        browser: Browser,
-        include_incognito: bool
-    ) -> Option<Browser>;
+        _extension: Extension,
+        _browser: Browser,
+        _include_incognito: bool
+    ) -> Option<Browser> {
+        None
+    }
 
     /// Called when the tabId associated with |target_browser| is specified to an
     /// extension API call that accepts a tabId parameter (e.g. chrome.tabs.*).
@@ -93,31 +124,32 @@ pub trait ExtensionHandlerCallbacks: Send + Sync + 'static {
     /// enabled, in which case |include_incognito| will be true (1).
     fn can_access_browser(
         &mut self,
-        extension: Extension,
-        browser: Browser,
-        include_incognito: bool,
-        target_browser: Browser
-    ) -> bool;
-
-    // TODO: Fix this!
-
-    //     ///
-    //     /// Called to retrieve an extension resource that would normally be loaded
-    //     /// from disk (e.g. if a file parameter is specified to
-    //     /// chrome.tabs.executeScript). |extension| and |browser| are the source of
-    //     /// the resource request. |file| is the requested relative file path. To
-    //     /// handle the resource request return true (1) and execute |callback| either
-    //     /// synchronously or asynchronously. For the default behavior which reads the
-    //     /// resource from the extension directory on disk return false (0).
-    //     /// Localization substitutions will not be applied to resources handled via
-    //     /// this function.
-    //     ///
-    //     int(CEF_CALLBACK* get_extension_resource)(
-    //     struct _cef_extension_handler_t* self,
-    //     struct _cef_extension_t* extension,
-    //     struct _cef_browser_t* browser,
-    //     const cef_string_t* file,
-    //     struct _cef_get_extension_resource_callback_t* callback);
+        _extension: Extension,
+        _browser: Browser,
+        _include_incognito: bool,
+        _target_browser: Browser
+    ) -> bool {
+        false
+    }
+
+    /// Called to retrieve an extension resource that would normally be loaded
+    /// from disk (e.g. if a file parameter is specified to
+    /// chrome.tabs.executeScript). |extension| and |browser| are the source of
+    /// the resource request. |file| is the requested relative file path. To
+    /// handle the resource request return true (1) and execute |callback| either
+    /// synchronously or asynchronously. For the default behavior which reads the
+    /// resource from the extension directory on disk return false (0).
+    /// Localization substitutions will not be applied to resources handled via
+    /// this function.
+    fn get_extension_resource(
+        &mut self,
+        _extension: Extension,
+        _browser: Browser,
+        _file: String,
+        _callback: GetExtensionResourceCallback
+    ) -> bool {
+        false
+    }
 }
 
 // Implement this structure to handle events related to browser extensions. The
@@ -133,15 +165,11 @@ impl ExtensionHandler {
 }
 
 /// Translates CEF -> Rust callbacks.
-struct ExtensionWrapper(Box<dyn ExtensionHandlerCallbacks>);
+struct ExtensionWrapper<C: ExtensionHandlerCallbacks>(C);
 
-// TODO: Remove this!
-
-#[allow(dead_code)]
-#[allow(unused_variables)]
-impl ExtensionWrapper {
-    pub fn new<C: ExtensionHandlerCallbacks>(delegate: C) -> Self {
-        Self(Box::new(delegate))
+impl<C: ExtensionHandlerCallbacks> ExtensionWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
     }
 
     /// Called if the cef_request_context_t::LoadExtension request fails. |result|
@@ -150,10 +178,12 @@ impl ExtensionWrapper {
         this: *mut cef_extension_handler_t,
         result: cef_errorcode_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
+        catch_unwind_or("ExtensionWrapper::c_on_extension_load_failed", (), || {
+            let this: &mut Self = Wrapped::wrappable(this);
 
-        this.0
-            .on_extension_load_failed(result.into())
+            this.0
+                .on_extension_load_failed(result.into())
+        })
     }
 
     /// Called if the cef_request_context_t::LoadExtension request succeeds.
@@ -162,11 +192,13 @@ impl ExtensionWrapper {
         this: *mut cef_extension_handler_t,
         extension: *mut cef_extension_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let extension = Extension::from_ptr_unchecked(extension);
+        catch_unwind_or("ExtensionWrapper::c_on_extension_loaded", (), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let extension = Extension::from_ptr_unchecked(extension);
 
-        this.0
-            .on_extension_loaded(extension);
+            this.0
+                .on_extension_loaded(extension);
+        })
     }
 
     /// Called after the cef_extension_t::Unload request has completed.
@@ -174,11 +206,13 @@ impl ExtensionWrapper {
         this: *mut cef_extension_handler_t,
         extension: *mut cef_extension_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let extension = Extension::from_ptr_unchecked(extension);
+        catch_unwind_or("ExtensionWrapper::c_on_extension_unloaded", (), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let extension = Extension::from_ptr_unchecked(extension);
 
-        this.0
-            .on_extension_unloaded(extension);
+            this.0
+                .on_extension_unloaded(extension);
+        })
     }
 
     /// Called when an extension needs a browser to host a background script
@@ -201,7 +235,36 @@ impl ExtensionWrapper {
         client: *mut *mut cef_client_t,
         settings: *mut cef_browser_settings_t
     ) -> c_int {
-        todo!()
+        catch_unwind_or(
+            "ExtensionWrapper::c_on_before_background_browser",
+            0,
+            || {
+                let this: &mut Self = Wrapped::wrappable(this);
+                let extension = Extension::from_ptr_unchecked(extension);
+                let url: String = CefString::from_ptr_unchecked(url).into();
+                let local_client_ptr = *client;
+                let mut local_client = Client::from_ptr(local_client_ptr);
+                let settings = BrowserSettings::from_ptr_mut_unchecked(settings);
+
+                let ret = this.0.on_before_background_browser(
+                    extension,
+                    url,
+                    &mut local_client,
+                    settings
+                );
+
+                // See LifeSpanHandlerWrapper::c_on_before_popup for why the client
+                // pointer has to be compared before handing it back to CEF.
+                *client = local_client
+                    .map(|c| match c.as_ptr() == local_client_ptr {
+                        true => local_client_ptr,
+                        false => c.into_raw()
+                    })
+                    .unwrap_or_else(null_mut);
+
+                ret as c_int
+            }
+        )
     }
 
     /// Called when an extension API (e.g. chrome.tabs.create) requests creation
@@ -229,7 +292,40 @@ impl ExtensionWrapper {
         client: *mut *mut cef_client_t,
         settings: *mut cef_browser_settings_t
     ) -> c_int {
-        todo!()
+        catch_unwind_or("ExtensionWrapper::c_on_before_browser", 0, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let extension = Extension::from_ptr_unchecked(extension);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let active_browser = Browser::from_ptr(active_browser);
+            let url: String = CefString::from_ptr_unchecked(url).into();
+            let window_info = WindowInfo::from_ptr_mut_unchecked(window_info);
+            let local_client_ptr = *client;
+            let mut local_client = Client::from_ptr(local_client_ptr);
+            let settings = BrowserSettings::from_ptr_mut_unchecked(settings);
+
+            let ret = this.0.on_before_browser(
+                extension,
+                browser,
+                active_browser,
+                index,
+                url,
+                active != 0,
+                window_info,
+                &mut local_client,
+                settings
+            );
+
+            // See LifeSpanHandlerWrapper::c_on_before_popup for why the client
+            // pointer has to be compared before handing it back to CEF.
+            *client = local_client
+                .map(|c| match c.as_ptr() == local_client_ptr {
+                    true => local_client_ptr,
+                    false => c.into_raw()
+                })
+                .unwrap_or_else(null_mut);
+
+            ret as c_int
+        })
     }
 
     /// Called when no tabId is specified to an extension API call that accepts a
@@ -245,14 +341,16 @@ impl ExtensionWrapper {
         browser: *mut cef_browser_t,
         include_incognito: c_int
     ) -> *mut cef_browser_t {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let extension = Extension::from_ptr_unchecked(extension);
-        let browser = Browser::from_ptr_unchecked(browser);
-
-        this.0
-            .get_active_browser(extension, browser, include_incognito != 0)
-            .map(|browser| browser.into_raw())
-            .unwrap_or_else(null_mut)
+        catch_unwind_or("ExtensionWrapper::c_get_active_browser", null_mut(), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let extension = Extension::from_ptr_unchecked(extension);
+            let browser = Browser::from_ptr_unchecked(browser);
+
+            this.0
+                .get_active_browser(extension, browser, include_incognito != 0)
+                .map(|browser| browser.into_raw())
+                .unwrap_or_else(null_mut)
+        })
     }
 
     /// Called when the tabId associated with |target_browser| is specified to an
@@ -268,14 +366,16 @@ impl ExtensionWrapper {
         include_incognito: c_int,
         target_browser: *mut cef_browser_t
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let extension = Extension::from_ptr_unchecked(extension);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let target_browser = Browser::from_ptr_unchecked(target_browser);
-
-        this.0
-            .can_access_browser(extension, browser, include_incognito != 0, target_browser)
-            as c_int
+        catch_unwind_or("ExtensionWrapper::c_can_access_browser", 0, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let extension = Extension::from_ptr_unchecked(extension);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let target_browser = Browser::from_ptr_unchecked(target_browser);
+
+            this.0
+                .can_access_browser(extension, browser, include_incognito != 0, target_browser)
+                as c_int
+        })
     }
 
     /// Called to retrieve an extension resource that would normally be loaded
@@ -294,11 +394,20 @@ impl ExtensionWrapper {
         file: *const cef_string_t,
         callback: *mut cef_get_extension_resource_callback_t
     ) -> c_int {
-        todo!()
+        catch_unwind_or("ExtensionWrapper::c_get_extension_resource", 0, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let extension = Extension::from_ptr_unchecked(extension);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let file: String = CefString::from_ptr_unchecked(file).into();
+            let callback = GetExtensionResourceCallback::from_ptr_unchecked(callback);
+
+            this.0
+                .get_extension_resource(extension, browser, file, callback) as c_int
+        })
     }
 }
 
-impl Wrappable for ExtensionWrapper {
+impl<C: ExtensionHandlerCallbacks> Wrappable for ExtensionWrapper<C> {
     type Cef = cef_extension_handler_t;
 
     /// Converts this to a smart pointer.
@@ -307,15 +416,14 @@ impl Wrappable for ExtensionWrapper {
             cef_extension_handler_t {
                 base: unsafe { zeroed() },
 
-                // TODO: Fix this!
                 on_extension_load_failed:     Some(Self::c_on_extension_load_failed),
                 on_extension_loaded:          Some(Self::c_on_extension_loaded),
                 on_extension_unloaded:        Some(Self::c_on_extension_unloaded),
-                on_before_background_browser: None,
-                on_before_browser:            None,
+                on_before_background_browser: Some(Self::c_on_before_background_browser),
+                on_before_browser:            Some(Self::c_on_before_browser),
                 get_active_browser:           Some(Self::c_get_active_browser),
                 can_access_browser:           Some(Self::c_can_access_browser),
-                get_extension_resource:       None
+                get_extension_resource:       Some(Self::c_get_extension_resource)
             },
             self
         )
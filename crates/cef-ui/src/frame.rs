@@ -1,8 +1,9 @@
+#[cfg(feature = "v8")]
+use crate::V8Context;
 use crate::{
-    ref_counted_ptr, try_c, Browser, CefString, ProcessId, ProcessMessage, Request, StringVisitor,
-    UrlRequest, UrlRequestClient
+    ref_counted_ptr, try_c, Browser, CefString, ProcessId, ProcessMessage, Request, Result,
+    StringVisitor, UrlRequest, UrlRequestClient
 };
-use anyhow::Result;
 use cef_ui_sys::cef_frame_t;
 use std::ffi::c_int;
 
@@ -168,13 +169,14 @@ impl Frame {
         })
     }
 
-    // TODO: Fix this!
-
-    // /// Get the V8 context associated with the frame. This function can only be
-    // /// called from the render process.
-    // ///
-    // struct _cef_v8context_t*(CEF_CALLBACK* get_v8context)(
-    // struct _cef_frame_t* self);
+    /// Get the V8 context associated with the frame. This function can only be
+    /// called from the render process.
+    #[cfg(feature = "v8")]
+    pub fn get_v8_context(&self) -> Result<Option<V8Context>> {
+        try_c!(self, get_v8context, {
+            Ok(V8Context::from_ptr(get_v8context(self.as_ptr())))
+        })
+    }
 
     // ///
     // /// Visit the DOM document. This function can only be called from the render
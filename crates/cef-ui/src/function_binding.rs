@@ -0,0 +1,682 @@
+use crate::{
+    Browser, CefStr, Frame, ProcessId, ProcessMessage, Result, V8Context, V8Handler,
+    V8HandlerCallbacks, V8PropertyAttribute, V8Value
+};
+use anyhow::anyhow;
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    mem::take,
+    sync::Arc
+};
+
+/// Sent from the renderer when a process first sees a browser, asking for the
+/// names of its bound functions.
+const SYNC_MESSAGE: &str = "cef_ui.bind_function.sync";
+
+/// Sent to the renderer with the name of a bound function.
+const BIND_MESSAGE: &str = "cef_ui.bind_function.bind";
+
+/// Sent from the renderer when JavaScript calls a bound function.
+const CALL_MESSAGE: &str = "cef_ui.bind_function.call";
+
+/// Sent to the renderer with the result of a call.
+const RESULT_MESSAGE: &str = "cef_ui.bind_function.result";
+
+/// The global native function used by the generated JavaScript functions.
+const NATIVE_INVOKE: &str = "__cefUiInvoke";
+
+type BoundFunction = Arc<dyn Fn(Value, FunctionReply) + Send + Sync + 'static>;
+
+/// The functions bound to a browser and their scopes, by name.
+type BrowserBindings = BTreeMap<String, (FunctionScope, BoundFunction)>;
+
+/// Bound functions in the browser process, by browser identifier.
+static BINDINGS: Mutex<BTreeMap<i32, BrowserBindings>> = Mutex::new(BTreeMap::new());
+
+/// The frames of a browser that can call a bound function, or use an EventBus
+/// or BinaryBridge attached to the browser.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FunctionScope {
+    /// Only the main frame.
+    MainFrame,
+
+    /// The main frame and frames with the same origin as the main frame.
+    #[default]
+    SameOrigin,
+
+    /// Every frame, including cross-origin iframes. Only use this if every
+    /// page that may be embedded is trusted.
+    AllFrames
+}
+
+impl FunctionScope {
+    /// Returns true if |frame| of |browser| is in this scope.
+    pub(crate) fn contains(&self, browser: &Browser, frame: &Frame) -> Result<bool> {
+        if frame.is_main()? {
+            return Ok(true);
+        }
+
+        match self {
+            FunctionScope::MainFrame => Ok(false),
+            FunctionScope::SameOrigin => match browser.get_main_frame()? {
+                Some(main_frame) => Ok(is_same_origin(&main_frame.get_url()?, &frame.get_url()?)),
+                None => Ok(false)
+            },
+            FunctionScope::AllFrames => Ok(true)
+        }
+    }
+}
+
+impl From<FunctionScope> for i32 {
+    fn from(value: FunctionScope) -> Self {
+        match value {
+            FunctionScope::MainFrame => 0,
+            FunctionScope::SameOrigin => 1,
+            FunctionScope::AllFrames => 2
+        }
+    }
+}
+
+impl From<i32> for FunctionScope {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => FunctionScope::MainFrame,
+            2 => FunctionScope::AllFrames,
+            _ => FunctionScope::SameOrigin
+        }
+    }
+}
+
+/// Returns true if |a| and |b| have the same scheme, host and port. URLs
+/// without a host, e.g. about:blank or data: URLs, have an opaque origin that
+/// is never the same as any other.
+fn is_same_origin(a: &str, b: &str) -> bool {
+    match (url_origin(a), url_origin(b)) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+        _ => false
+    }
+}
+
+/// Returns the scheme://host:port part of |url|.
+fn url_origin(url: &str) -> Option<&str> {
+    let (scheme, rest) = url.split_once("://")?;
+    let end = rest
+        .find(['/', '?', '#'])
+        .unwrap_or(rest.len());
+
+    match rest[..end].is_empty() {
+        true => None,
+        false => Some(&url[..scheme.len() + 3 + end])
+    }
+}
+
+impl Browser {
+    /// Exposes |f| to JavaScript as the global function |name| in the frames
+    /// of this browser selected by |scope|. Calling it from JavaScript returns
+    /// a Promise that resolves with the value returned by |f|, or rejects with
+    /// its error. The arguments are passed to |f| as a JSON array. |f| is
+    /// called on the browser process UI thread, so it must not block. Calls
+    /// from frames outside of |scope| are rejected.
+    ///
+    /// No render process code is needed beyond returning a
+    /// RenderProcessHandler from AppCallbacks::get_render_process_handler,
+    /// which the crate does by default. Bindings are released when the
+    /// browser is closed, provided the client has a LifeSpanHandler.
+    pub fn bind_function(
+        &self,
+        name: &str,
+        scope: FunctionScope,
+        f: impl Fn(Value) -> anyhow::Result<Value> + Send + Sync + 'static
+    ) -> Result<()> {
+        self.bind_function_with_reply(name, scope, move |arguments, reply| {
+            reply.send(f(arguments))
+        })
+    }
+
+    /// Like bind_function, but |f| completes the call through |reply|, which
+    /// may be sent from any thread and at any later time. This allows bound
+    /// functions to run asynchronously; the JavaScript promise stays pending
+    /// until the reply is sent. Dropping the reply rejects the promise.
+    pub fn bind_function_with_reply(
+        &self,
+        name: &str,
+        scope: FunctionScope,
+        f: impl Fn(Value, FunctionReply) + Send + Sync + 'static
+    ) -> Result<()> {
+        BINDINGS
+            .lock()
+            .entry(self.get_identifier()?)
+            .or_default()
+            .insert(name.to_string(), (scope, Arc::new(f)));
+
+        // Every render process of the browser learns about the function, but
+        // only defines it in the frames that are in scope.
+        for identifier in self.get_frame_identifiers()? {
+            if let Some(frame) = self.get_frame_by_identifier(identifier)? {
+                send_bind_message(&frame, name, scope)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes a function bound with bind_function. Frames that already define
+    /// it keep the JavaScript function, but calls to it are rejected.
+    pub fn unbind_function(&self, name: &str) -> Result<()> {
+        let identifier = self.get_identifier()?;
+        let mut bindings = BINDINGS.lock();
+
+        if let Some(functions) = bindings.get_mut(&identifier) {
+            functions.remove(name);
+
+            if functions.is_empty() {
+                bindings.remove(&identifier);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Drops all functions bound to |browser|.
+pub(crate) fn release_browser_bindings(browser: &Browser) {
+    if let Ok(identifier) = browser.get_identifier() {
+        BINDINGS.lock().remove(&identifier);
+    }
+}
+
+/// Handles binding messages sent to the browser process. Returns true if
+/// |message| was handled.
+pub(crate) fn on_browser_process_message(
+    browser: &Browser,
+    frame: &Frame,
+    source_process: ProcessId,
+    message: &ProcessMessage
+) -> bool {
+    if source_process != ProcessId::Renderer {
+        return false;
+    }
+
+    match message.get_name().ok().as_deref() {
+        Some(SYNC_MESSAGE) => {
+            for (name, scope) in bound_functions(browser) {
+                let _ = send_bind_message(frame, &name, scope);
+            }
+
+            true
+        },
+        Some(CALL_MESSAGE) => {
+            let _ = call_bound_function(browser, frame, message);
+
+            true
+        },
+        _ => false
+    }
+}
+
+/// Returns the names and scopes of the functions bound to |browser|.
+fn bound_functions(browser: &Browser) -> Vec<(String, FunctionScope)> {
+    let Ok(identifier) = browser.get_identifier() else {
+        return Vec::new();
+    };
+
+    BINDINGS
+        .lock()
+        .get(&identifier)
+        .map(|functions| {
+            functions
+                .iter()
+                .map(|(name, (scope, _))| (name.clone(), *scope))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Runs the bound function named in a call message and sends back its result.
+fn call_bound_function(browser: &Browser, frame: &Frame, message: &ProcessMessage) -> Result<()> {
+    let Some(arguments) = message.get_argument_list()? else {
+        return Ok(());
+    };
+
+    let call_id = arguments.get_int(0)?;
+    let name = arguments
+        .get_string(1)?
+        .unwrap_or_default();
+    let json = arguments
+        .get_string(2)?
+        .unwrap_or_default();
+
+    // The function is cloned so that it may bind or unbind functions.
+    let function = BINDINGS
+        .lock()
+        .get(&browser.get_identifier()?)
+        .and_then(|functions| functions.get(&name).cloned());
+    let reply = FunctionReply(Some((frame.clone(), call_id)));
+
+    // The render process only defines functions in frames that are in scope,
+    // but a compromised render process could still send the call.
+    let function = match function {
+        Some((scope, function)) => match scope.contains(browser, frame)? {
+            true => Some(function),
+            false => None
+        },
+        None => None
+    };
+
+    match function {
+        Some(function) => match serde_json::from_str(&json) {
+            Ok(arguments) => function(arguments, reply),
+            Err(e) => reply.reject(e)
+        },
+        None => reply.reject(format!("{} is not bound.", name))
+    }
+
+    Ok(())
+}
+
+/// Completes a call to a function bound with Browser::bind_function_with_reply.
+/// The promise returned to JavaScript is rejected if this is dropped without
+/// being sent.
+pub struct FunctionReply(Option<(Frame, i32)>);
+
+impl FunctionReply {
+    /// Resolves the promise with |value|.
+    pub fn resolve(self, value: Value) {
+        self.send(Ok(value));
+    }
+
+    /// Rejects the promise with |error| as the message.
+    pub fn reject(self, error: impl Display) {
+        self.send(Err(anyhow!("{}", error)));
+    }
+
+    /// Resolves or rejects the promise depending on |result|.
+    pub fn send(mut self, result: anyhow::Result<Value>) {
+        if let Some((frame, call_id)) = self.0.take() {
+            let result = result.and_then(|value| Ok(serde_json::to_string(&value)?));
+
+            // The frame may have been destroyed while the call was running.
+            let _ = send_result_message(&frame, call_id, result);
+        }
+    }
+}
+
+impl Drop for FunctionReply {
+    fn drop(&mut self) {
+        if let Some((frame, call_id)) = self.0.take() {
+            let _ = send_result_message(
+                &frame,
+                call_id,
+                Err(anyhow!("The bound function did not reply."))
+            );
+        }
+    }
+}
+
+/// Sends the result of a call to the render process for |frame|.
+fn send_result_message(frame: &Frame, call_id: i32, result: anyhow::Result<String>) -> Result<()> {
+    let message = ProcessMessage::new(RESULT_MESSAGE);
+
+    if let Some(arguments) = message.get_argument_list()? {
+        arguments.set_int(0, call_id)?;
+        arguments.set_bool(1, result.is_ok())?;
+
+        match result {
+            Ok(json) => arguments.set_string(2, &json)?,
+            Err(e) => arguments.set_string(2, &e.to_string())?
+        };
+    }
+
+    frame.send_process_message(ProcessId::Renderer, message)
+}
+
+/// Tells the render process for |frame| that |name| is bound in |scope|.
+fn send_bind_message(frame: &Frame, name: &str, scope: FunctionScope) -> Result<()> {
+    let message = ProcessMessage::new(BIND_MESSAGE);
+
+    if let Some(arguments) = message.get_argument_list()? {
+        arguments.set_string(0, name)?;
+        arguments.set_int(1, scope.into())?;
+    }
+
+    frame.send_process_message(ProcessId::Renderer, message)
+}
+
+thread_local! {
+    /// The bound function names and scopes known to this render process, by
+    /// browser identifier. V8 objects can only be used on the renderer thread,
+    /// so the render process state is thread local.
+    static RENDERER_BINDINGS: RefCell<HashMap<i32, BTreeMap<String, FunctionScope>>> =
+        RefCell::new(HashMap::new());
+
+    /// Promises waiting for a result, by call identifier.
+    static PENDING_CALLS: RefCell<HashMap<i32, (V8Context, V8Value)>> =
+        RefCell::new(HashMap::new());
+
+    /// The identifier of the next call.
+    static NEXT_CALL_ID: Cell<i32> = const { Cell::new(0) };
+}
+
+/// Defines the functions bound to |browser| in |context| whose scope contains
+/// |frame|. The first context of a browser in this process asks the browser
+/// process for the names.
+pub(crate) fn on_context_created(browser: &Browser, frame: &Frame, context: &V8Context) {
+    let Ok(identifier) = browser.get_identifier() else {
+        return;
+    };
+
+    let functions = RENDERER_BINDINGS.with_borrow_mut(|bindings| match bindings.get(&identifier) {
+        Some(functions) => Some(functions.clone()),
+        None => {
+            bindings.insert(identifier, BTreeMap::new());
+
+            None
+        }
+    });
+
+    match functions {
+        Some(functions) => {
+            let names: Vec<String> = functions
+                .into_iter()
+                .filter(|(_, scope)| {
+                    scope
+                        .contains(browser, frame)
+                        .unwrap_or(false)
+                })
+                .map(|(name, _)| name)
+                .collect();
+
+            if !names.is_empty() {
+                let _ = install_functions(context, &names);
+            }
+        },
+        None => {
+            let _ =
+                frame.send_process_message(ProcessId::Browser, ProcessMessage::new(SYNC_MESSAGE));
+        }
+    }
+}
+
+/// Rejects the calls that are still pending in |context|. This must be called
+/// before the context is released.
+pub(crate) fn on_context_released(context: &V8Context) {
+    let released: HashMap<i32, (V8Context, V8Value)> = PENDING_CALLS.with_borrow_mut(|pending| {
+        let (released, kept) = take(pending)
+            .into_iter()
+            .partition(|(_, (call_context, _))| {
+                call_context
+                    .is_same(context.clone())
+                    .unwrap_or(true)
+            });
+
+        *pending = kept;
+        released
+    });
+
+    if released.is_empty() || !context.enter().unwrap_or(false) {
+        return;
+    }
+
+    for (_, (_, promise)) in released {
+        let _ = promise.reject_promise("The V8 context was released.");
+    }
+
+    let _ = context.exit();
+}
+
+/// Forgets the functions bound to |browser|.
+pub(crate) fn on_browser_destroyed(browser: &Browser) {
+    if let Ok(identifier) = browser.get_identifier() {
+        RENDERER_BINDINGS.with_borrow_mut(|bindings| bindings.remove(&identifier));
+    }
+}
+
+/// Handles binding messages sent to the render process. Returns true if
+/// |message| was handled.
+pub(crate) fn on_renderer_process_message(
+    browser: &Browser,
+    source_process: ProcessId,
+    message: &ProcessMessage
+) -> bool {
+    if source_process != ProcessId::Browser {
+        return false;
+    }
+
+    match message.get_name().ok().as_deref() {
+        Some(BIND_MESSAGE) => {
+            let _ = bind_in_renderer(browser, message);
+
+            true
+        },
+        Some(RESULT_MESSAGE) => {
+            let _ = complete_call(message);
+
+            true
+        },
+        _ => false
+    }
+}
+
+/// Records a bound function and defines it in the frames of |browser| in this
+/// process that are in its scope.
+fn bind_in_renderer(browser: &Browser, message: &ProcessMessage) -> Result<()> {
+    let Some(arguments) = message.get_argument_list()? else {
+        return Ok(());
+    };
+
+    let Some(name) = arguments.get_string(0)? else {
+        return Ok(());
+    };
+
+    let scope = FunctionScope::from(arguments.get_int(1)?);
+    let identifier = browser.get_identifier()?;
+    let previous = RENDERER_BINDINGS.with_borrow_mut(|bindings| {
+        bindings
+            .entry(identifier)
+            .or_default()
+            .insert(name.clone(), scope)
+    });
+
+    if previous == Some(scope) {
+        return Ok(());
+    }
+
+    for frame_identifier in browser.get_frame_identifiers()? {
+        let Some(frame) = browser.get_frame_by_identifier(frame_identifier)? else {
+            continue;
+        };
+
+        if !scope.contains(browser, &frame)? {
+            continue;
+        }
+
+        if let Some(context) = frame.get_v8_context()? {
+            install_functions(&context, std::slice::from_ref(&name))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolves or rejects the promise for a call.
+fn complete_call(message: &ProcessMessage) -> Result<()> {
+    let Some(arguments) = message.get_argument_list()? else {
+        return Ok(());
+    };
+
+    let call_id = arguments.get_int(0)?;
+    let Some((context, promise)) =
+        PENDING_CALLS.with_borrow_mut(|pending| pending.remove(&call_id))
+    else {
+        return Ok(());
+    };
+
+    let success = arguments.get_bool(1)?;
+    let value = arguments
+        .get_string(2)?
+        .unwrap_or_default();
+
+    if !context.enter()? {
+        return Err(anyhow!("Failed to enter V8 context.").into());
+    }
+
+    // The result is parsed by the generated JavaScript function.
+    let result = match success {
+        true => V8Value::new_string(&value).and_then(|value| promise.resolve_promise(Some(value))),
+        false => promise.reject_promise(&value)
+    };
+
+    context.exit()?;
+    result.map(|_| ())
+}
+
+/// Defines each of |names| in |context| as a function that forwards its
+/// arguments to the browser process.
+fn install_functions(context: &V8Context, names: &[String]) -> Result<()> {
+    let mut script = String::new();
+
+    for name in names {
+        let name = serde_json::to_string(name).map_err(anyhow::Error::from)?;
+
+        script.push_str(&format!(
+            "globalThis[{name}] = (...args) => \
+             globalThis.{NATIVE_INVOKE}({name}, JSON.stringify(args)).then(JSON.parse);\n"
+        ));
+    }
+
+    if !context.enter()? {
+        return Err(anyhow!("Failed to enter V8 context.").into());
+    }
+
+    let result = context
+        .get_global()
+        .and_then(|global| {
+            let invoke = V8Value::new_function(NATIVE_INVOKE, V8Handler::new(NativeInvoke))?;
+
+            global.set_value_by_key(NATIVE_INVOKE, invoke, V8PropertyAttribute::DontEnum)
+        })
+        .and_then(|_| context.eval(&script, "cef-ui://bind-function.js", 1));
+
+    context.exit()?;
+    result.map(|_| ())
+}
+
+/// The native function behind every bound function. Sends the call to the
+/// browser process and returns a promise for its result.
+struct NativeInvoke;
+
+impl V8HandlerCallbacks for NativeInvoke {
+    fn execute(
+        &mut self,
+        _name: CefStr<'_>,
+        _object: V8Value,
+        arguments: Vec<V8Value>
+    ) -> anyhow::Result<Option<V8Value>> {
+        let (Some(name), Some(json)) = (arguments.first(), arguments.get(1)) else {
+            return Err(anyhow!("Expected a function name and arguments."));
+        };
+
+        let context = V8Context::get_current_context()
+            .ok_or_else(|| anyhow!("Bound functions must be called from a V8 context."))?;
+        let frame = context
+            .get_frame()?
+            .ok_or_else(|| anyhow!("Bound functions must be called from a frame."))?;
+
+        let call_id = NEXT_CALL_ID.replace(NEXT_CALL_ID.get().wrapping_add(1));
+        let message = ProcessMessage::new(CALL_MESSAGE);
+
+        if let Some(message_arguments) = message.get_argument_list()? {
+            message_arguments.set_int(0, call_id)?;
+            message_arguments.set_string(1, &name.get_string_value()?)?;
+            message_arguments.set_string(2, &json.get_string_value()?)?;
+        }
+
+        frame.send_process_message(ProcessId::Browser, message)?;
+
+        let promise = V8Value::new_promise()?;
+
+        PENDING_CALLS
+            .with_borrow_mut(|pending| pending.insert(call_id, (context, promise.clone())));
+
+        Ok(Some(promise))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_origin_of_urls() {
+        assert_eq!(
+            url_origin("https://example.com/path?query#hash"),
+            Some("https://example.com")
+        );
+        assert_eq!(
+            url_origin("http://localhost:8080"),
+            Some("http://localhost:8080")
+        );
+        assert_eq!(
+            url_origin("https://user@example.com:443?query"),
+            Some("https://user@example.com:443")
+        );
+        assert_eq!(url_origin("file://host/path#hash"), Some("file://host"));
+    }
+
+    #[test]
+    fn url_origin_opaque() {
+        assert_eq!(url_origin("about:blank"), None);
+        assert_eq!(url_origin("data:text/html,<p>hello</p>"), None);
+        assert_eq!(url_origin("file:///etc/hosts"), None);
+        assert_eq!(url_origin("https://"), None);
+        assert_eq!(url_origin(""), None);
+    }
+
+    #[test]
+    fn same_origin() {
+        assert!(is_same_origin(
+            "https://example.com/a",
+            "https://example.com/b?c#d"
+        ));
+        assert!(is_same_origin(
+            "HTTPS://Example.com/a",
+            "https://example.com"
+        ));
+    }
+
+    #[test]
+    fn different_origin() {
+        assert!(!is_same_origin("https://example.com", "http://example.com"));
+        assert!(!is_same_origin(
+            "https://example.com",
+            "https://example.com:8443"
+        ));
+        assert!(!is_same_origin(
+            "https://example.com",
+            "https://example.com.evil.net"
+        ));
+        assert!(!is_same_origin(
+            "https://example.com",
+            "https://sub.example.com"
+        ));
+        assert!(!is_same_origin("about:blank", "about:blank"));
+        assert!(!is_same_origin("data:,a", "data:,a"));
+    }
+
+    #[test]
+    fn scope_round_trip() {
+        for scope in [
+            FunctionScope::MainFrame,
+            FunctionScope::SameOrigin,
+            FunctionScope::AllFrames
+        ] {
+            assert_eq!(FunctionScope::from(i32::from(scope)), scope);
+        }
+
+        assert_eq!(FunctionScope::from(-1), FunctionScope::SameOrigin);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{
+    Browser, Callback, CommandLine, Frame, Request, RequestContextHandler,
+    RequestContextHandlerCallbacks, ResourceRequestHandler, ResourceRequestHandlerCallbacks,
+    Result, ReturnValue
+};
+#[cfg(feature = "devtools")]
+use crate::{BrowserHost, DictionaryValue};
+#[cfg(feature = "devtools")]
+use anyhow::anyhow;
+use parking_lot::RwLock;
+use std::sync::Arc;
+use tracing::error;
+
+/// The command line switch that overrides the default user agent.
+const USER_AGENT_SWITCH: &str = "user-agent";
+
+/// The header that carries the user agent.
+const USER_AGENT_HEADER: &str = "User-Agent";
+
+/// The headers applied by HeaderOverrides.
+#[derive(Debug, Default)]
+struct Headers {
+    user_agent: Option<String>,
+    headers:    Vec<(String, String, bool)>
+}
+
+/// Overrides the user agent and adds default headers to requests, e.g. to
+/// attach an API token to every request made by an embedded app. Clones share
+/// the same headers, so they can be changed at any time (for example when the
+/// token is refreshed) and the change applies to the next request.
+///
+/// To apply the overrides to a single browser, return resource_request_handler
+/// from RequestHandlerCallbacks::get_resource_request_handler. To apply them to
+/// every browser using a request context, pass request_context_handler to
+/// RequestContext::new_shared and create the browsers with that context.
+/// Requests that another handler already claimed are not modified.
+///
+/// Only the User-Agent request header is changed this way. Use
+/// apply_to_command_line or Settings::user_agent to change the default user
+/// agent of the whole process, and set_user_agent_override to also change
+/// navigator.userAgent for a single browser.
+#[derive(Debug, Clone, Default)]
+pub struct HeaderOverrides(Arc<RwLock<Headers>>);
+
+impl HeaderOverrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends |user_agent| as the User-Agent header. Pass None to keep the
+    /// default user agent.
+    pub fn set_user_agent(&self, user_agent: Option<&str>) {
+        self.0.write().user_agent = user_agent.map(String::from);
+    }
+
+    /// Returns the user agent override, if any.
+    pub fn get_user_agent(&self) -> Option<String> {
+        self.0.read().user_agent.clone()
+    }
+
+    /// Adds the header |name| to every request. If |overwrite| is true an
+    /// existing value is replaced, otherwise requests that already have the
+    /// header keep their value. Setting the same header again replaces the
+    /// previous override.
+    pub fn set_header(&self, name: &str, value: &str, overwrite: bool) {
+        let mut headers = self.0.write();
+
+        headers
+            .headers
+            .retain(|(existing, _, _)| !existing.eq_ignore_ascii_case(name));
+        headers
+            .headers
+            .push((name.to_string(), value.to_string(), overwrite));
+    }
+
+    /// Stops adding the header |name|.
+    pub fn remove_header(&self, name: &str) {
+        self.0
+            .write()
+            .headers
+            .retain(|(existing, _, _)| !existing.eq_ignore_ascii_case(name));
+    }
+
+    /// Removes the user agent override and all headers.
+    pub fn clear(&self) {
+        *self.0.write() = Headers::default();
+    }
+
+    /// Applies the overrides to |request|.
+    pub fn apply(&self, request: &Request) -> Result<()> {
+        let headers = self.0.read();
+
+        if let Some(user_agent) = &headers.user_agent {
+            request.set_header_by_name(USER_AGENT_HEADER, user_agent, true)?;
+        }
+
+        for (name, value, overwrite) in &headers.headers {
+            request.set_header_by_name(name, value, *overwrite)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns a resource request handler that applies the overrides.
+    pub fn resource_request_handler(&self) -> ResourceRequestHandler {
+        ResourceRequestHandler::new(self.clone())
+    }
+
+    /// Returns a request context handler that applies the overrides to every
+    /// request made through the request context.
+    pub fn request_context_handler(&self) -> RequestContextHandler {
+        RequestContextHandler::new(self.clone())
+    }
+
+    /// Sets the user agent switch on |command_line| so that the user agent
+    /// override becomes the default for the whole process. Call this from
+    /// AppCallbacks::on_before_command_line_processing.
+    pub fn apply_to_command_line(&self, command_line: &CommandLine) -> Result<()> {
+        match self.get_user_agent() {
+            Some(user_agent) => {
+                command_line.append_switch_with_value(USER_AGENT_SWITCH, Some(&user_agent))
+            },
+            None => Ok(())
+        }
+    }
+
+    /// Changes the user agent of |browser|, including navigator.userAgent, to
+    /// the user agent override. Call this after the browser is created.
+    #[cfg(feature = "devtools")]
+    pub fn set_user_agent_override(&self, browser: &BrowserHost) -> Result<()> {
+        let Some(user_agent) = self.get_user_agent() else {
+            return Ok(());
+        };
+
+        let params = DictionaryValue::new();
+
+        params.set_string("userAgent", &user_agent)?;
+
+        match browser.execute_dev_tools_method(0, "Emulation.setUserAgentOverride", Some(params))? {
+            0 => Err(anyhow!("Failed to override the user agent.").into()),
+            _ => Ok(())
+        }
+    }
+}
+
+impl ResourceRequestHandlerCallbacks for HeaderOverrides {
+    fn on_before_resource_load(
+        &mut self,
+        _browser: Option<Browser>,
+        _frame: Option<Frame>,
+        request: Request,
+        _callback: Callback
+    ) -> ReturnValue {
+        if let Err(e) = self.apply(&request) {
+            error!("Failed to apply header overrides: {}", e);
+        }
+
+        ReturnValue::Continue
+    }
+}
+
+impl RequestContextHandlerCallbacks for HeaderOverrides {
+    fn get_resource_request_handler(
+        &mut self,
+        _browser: Option<Browser>,
+        _frame: Option<Frame>,
+        _request: Request,
+        _is_navigation: bool,
+        _is_download: bool,
+        _request_initiator: &str,
+        _disable_default_handling: &mut bool
+    ) -> Option<ResourceRequestHandler> {
+        Some(self.resource_request_handler())
+    }
+}
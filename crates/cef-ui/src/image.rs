@@ -0,0 +1,672 @@
+use crate::{ref_counted_ptr, try_c, BinaryValue, Result};
+use anyhow::anyhow;
+use cef_ui_sys::{
+    cef_alpha_type_t, cef_binary_value_t, cef_color_type_t, cef_image_create, cef_image_t
+};
+use std::ffi::{c_int, c_void};
+
+/// Uncompressed BMP pixels.
+const BI_RGB: u32 = 0;
+
+/// Uncompressed BMP pixels with explicit channel masks.
+const BI_BITFIELDS: u32 = 3;
+
+/// The size of the smallest BMP info header that includes an alpha mask.
+const BITMAPV3INFOHEADER_SIZE: u32 = 56;
+
+/// Describes how to interpret the components of a pixel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ColorType {
+    /// RGBA with 8 bits per pixel (32bits total).
+    Rgba8888,
+
+    /// BGRA with 8 bits per pixel (32bits total).
+    Bgra8888
+}
+
+impl From<cef_color_type_t> for ColorType {
+    fn from(value: cef_color_type_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_color_type_t> for ColorType {
+    fn from(value: &cef_color_type_t) -> Self {
+        match value {
+            cef_color_type_t::CEF_COLOR_TYPE_RGBA_8888 => ColorType::Rgba8888,
+            cef_color_type_t::CEF_COLOR_TYPE_BGRA_8888 => ColorType::Bgra8888
+        }
+    }
+}
+
+impl From<ColorType> for cef_color_type_t {
+    fn from(value: ColorType) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&ColorType> for cef_color_type_t {
+    fn from(value: &ColorType) -> Self {
+        match value {
+            ColorType::Rgba8888 => cef_color_type_t::CEF_COLOR_TYPE_RGBA_8888,
+            ColorType::Bgra8888 => cef_color_type_t::CEF_COLOR_TYPE_BGRA_8888
+        }
+    }
+}
+
+/// Describes how to interpret the alpha component of a pixel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AlphaType {
+    /// No transparency. The alpha component is ignored.
+    Opaque,
+
+    /// Transparency with pre-multiplied alpha component.
+    Premultiplied,
+
+    /// Transparency with post-multiplied alpha component.
+    Postmultiplied
+}
+
+impl From<cef_alpha_type_t> for AlphaType {
+    fn from(value: cef_alpha_type_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_alpha_type_t> for AlphaType {
+    fn from(value: &cef_alpha_type_t) -> Self {
+        match value {
+            cef_alpha_type_t::CEF_ALPHA_TYPE_OPAQUE => AlphaType::Opaque,
+            cef_alpha_type_t::CEF_ALPHA_TYPE_PREMULTIPLIED => AlphaType::Premultiplied,
+            cef_alpha_type_t::CEF_ALPHA_TYPE_POSTMULTIPLIED => AlphaType::Postmultiplied
+        }
+    }
+}
+
+impl From<AlphaType> for cef_alpha_type_t {
+    fn from(value: AlphaType) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&AlphaType> for cef_alpha_type_t {
+    fn from(value: &AlphaType) -> Self {
+        match value {
+            AlphaType::Opaque => cef_alpha_type_t::CEF_ALPHA_TYPE_OPAQUE,
+            AlphaType::Premultiplied => cef_alpha_type_t::CEF_ALPHA_TYPE_PREMULTIPLIED,
+            AlphaType::Postmultiplied => cef_alpha_type_t::CEF_ALPHA_TYPE_POSTMULTIPLIED
+        }
+    }
+}
+
+/// Information about a single image representation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ImageRepresentation {
+    /// The actual scale factor of the representation.
+    pub actual_scale_factor: f32,
+
+    /// The width of the representation in pixel coordinates.
+    pub pixel_width: i32,
+
+    /// The height of the representation in pixel coordinates.
+    pub pixel_height: i32
+}
+
+/// An image representation exported as bitmap, PNG or JPEG data.
+pub struct EncodedImage {
+    /// The exported data.
+    pub data: BinaryValue,
+
+    /// The width of the exported data in pixel coordinates.
+    pub pixel_width: i32,
+
+    /// The height of the exported data in pixel coordinates.
+    pub pixel_height: i32
+}
+
+// Container for a single image represented at different scale factors. All
+// image representations should be the same size in density independent pixel
+// (DIP) units. For example, if the image at scale factor 1.0 is 100x100 pixels
+// then the image at scale factor 2.0 should be 200x200 pixels -- both images
+// will display with a DIP size of 100x100 units. The functions of this
+// structure can be called on any browser process thread.
+ref_counted_ptr!(Image, cef_image_t);
+
+impl Image {
+    /// Create a new cef_image_t. It will initially be NULL. Use the Add*()
+    /// functions to add representations at different scale factors.
+    pub fn new() -> Self {
+        unsafe { Self::from_ptr_unchecked(cef_image_create()) }
+    }
+
+    /// Create a new image from a single PNG representation.
+    pub fn from_png(scale_factor: f32, data: &[u8]) -> Result<Self> {
+        let image = Self::new();
+
+        match image.add_png(scale_factor, data)? {
+            true => Ok(image),
+            false => Err(anyhow!("Failed to decode PNG image data.").into())
+        }
+    }
+
+    /// Create a new image from a single JPEG representation.
+    pub fn from_jpeg(scale_factor: f32, data: &[u8]) -> Result<Self> {
+        let image = Self::new();
+
+        match image.add_jpeg(scale_factor, data)? {
+            true => Ok(image),
+            false => Err(anyhow!("Failed to decode JPEG image data.").into())
+        }
+    }
+
+    /// Create a new image from a single BMP representation. Only uncompressed
+    /// 24-bit and 32-bit bitmaps are supported.
+    pub fn from_bmp(scale_factor: f32, data: &[u8]) -> Result<Self> {
+        let image = Self::new();
+
+        match image.add_bmp(scale_factor, data)? {
+            true => Ok(image),
+            false => Err(anyhow!("Failed to add BMP image data.").into())
+        }
+    }
+
+    /// Create a new image from a single raw BGRA representation.
+    pub fn from_bgra(
+        scale_factor: f32,
+        pixel_width: i32,
+        pixel_height: i32,
+        alpha_type: AlphaType,
+        pixel_data: &[u8]
+    ) -> Result<Self> {
+        let image = Self::new();
+
+        match image.add_bitmap(
+            scale_factor,
+            pixel_width,
+            pixel_height,
+            ColorType::Bgra8888,
+            alpha_type,
+            pixel_data
+        )? {
+            true => Ok(image),
+            false => Err(anyhow!("Failed to add BGRA image data.").into())
+        }
+    }
+
+    /// Returns true (1) if this Image is NULL.
+    pub fn is_empty(&self) -> Result<bool> {
+        try_c!(self, is_empty, { Ok(is_empty(self.as_ptr()) != 0) })
+    }
+
+    /// Returns true (1) if this Image and |that| Image share the same underlying
+    /// storage. Will also return true (1) if both images are NULL.
+    pub fn is_same(&self, that: Image) -> Result<bool> {
+        try_c!(self, is_same, {
+            Ok(is_same(self.as_ptr(), that.into_raw()) != 0)
+        })
+    }
+
+    /// Add a bitmap image representation for |scale_factor|. Only 32-bit
+    /// RGBA/BGRA formats are supported. |pixel_width| and |pixel_height| are the
+    /// bitmap representation size in pixel coordinates. |pixel_data| is the
+    /// array of pixel data and should be |pixel_width| x |pixel_height| x 4
+    /// bytes in size. |color_type| and |alpha_type| values specify the pixel
+    /// format.
+    pub fn add_bitmap(
+        &self,
+        scale_factor: f32,
+        pixel_width: i32,
+        pixel_height: i32,
+        color_type: ColorType,
+        alpha_type: AlphaType,
+        pixel_data: &[u8]
+    ) -> Result<bool> {
+        if pixel_width <= 0
+            || pixel_height <= 0
+            || pixel_data.len() != pixel_width as usize * pixel_height as usize * 4
+        {
+            return Err(anyhow!(
+                "Pixel data must be {} x {} x 4 bytes, but is {} bytes.",
+                pixel_width,
+                pixel_height,
+                pixel_data.len()
+            )
+            .into());
+        }
+
+        try_c!(self, add_bitmap, {
+            Ok(add_bitmap(
+                self.as_ptr(),
+                scale_factor,
+                pixel_width as c_int,
+                pixel_height as c_int,
+                color_type.into(),
+                alpha_type.into(),
+                pixel_data.as_ptr() as *const c_void,
+                pixel_data.len()
+            ) != 0)
+        })
+    }
+
+    /// Add a PNG image representation for |scale_factor|. |png_data| is the
+    /// image data of size |png_data_size|. Any alpha transparency in the PNG
+    /// data will be maintained.
+    pub fn add_png(&self, scale_factor: f32, png_data: &[u8]) -> Result<bool> {
+        try_c!(self, add_png, {
+            Ok(add_png(
+                self.as_ptr(),
+                scale_factor,
+                png_data.as_ptr() as *const c_void,
+                png_data.len()
+            ) != 0)
+        })
+    }
+
+    /// Create a JPEG image representation for |scale_factor|. |jpeg_data| is
+    /// the image data of size |jpeg_data_size|. The JPEG format does not
+    /// support transparency so the alpha byte will be set to 0xFF for all
+    /// pixels.
+    pub fn add_jpeg(&self, scale_factor: f32, jpeg_data: &[u8]) -> Result<bool> {
+        try_c!(self, add_jpeg, {
+            Ok(add_jpeg(
+                self.as_ptr(),
+                scale_factor,
+                jpeg_data.as_ptr() as *const c_void,
+                jpeg_data.len()
+            ) != 0)
+        })
+    }
+
+    /// Add a BMP image representation for |scale_factor|. CEF cannot decode
+    /// BMP data itself, so uncompressed 24-bit and 32-bit bitmaps are decoded
+    /// to BGRA here and added as a bitmap representation.
+    pub fn add_bmp(&self, scale_factor: f32, bmp_data: &[u8]) -> Result<bool> {
+        let (pixel_width, pixel_height, alpha_type, pixel_data) = decode_bmp(bmp_data)?;
+
+        self.add_bitmap(
+            scale_factor,
+            pixel_width,
+            pixel_height,
+            ColorType::Bgra8888,
+            alpha_type,
+            &pixel_data
+        )
+    }
+
+    /// Returns the image width in density independent pixel (DIP) units.
+    pub fn get_width(&self) -> Result<usize> {
+        try_c!(self, get_width, { Ok(get_width(self.as_ptr())) })
+    }
+
+    /// Returns the image height in density independent pixel (DIP) units.
+    pub fn get_height(&self) -> Result<usize> {
+        try_c!(self, get_height, { Ok(get_height(self.as_ptr())) })
+    }
+
+    /// Returns true (1) if this image contains a representation for
+    /// |scale_factor|.
+    pub fn has_representation(&self, scale_factor: f32) -> Result<bool> {
+        try_c!(self, has_representation, {
+            Ok(has_representation(self.as_ptr(), scale_factor) != 0)
+        })
+    }
+
+    /// Removes the representation for |scale_factor|. Returns true (1) on
+    /// success.
+    pub fn remove_representation(&self, scale_factor: f32) -> Result<bool> {
+        try_c!(self, remove_representation, {
+            Ok(remove_representation(self.as_ptr(), scale_factor) != 0)
+        })
+    }
+
+    /// Returns information for the representation that most closely matches
+    /// |scale_factor|, or None if no representation exists.
+    pub fn get_representation_info(
+        &self,
+        scale_factor: f32
+    ) -> Result<Option<ImageRepresentation>> {
+        try_c!(self, get_representation_info, {
+            let mut actual_scale_factor: f32 = 0.0;
+            let mut pixel_width: c_int = 0;
+            let mut pixel_height: c_int = 0;
+
+            let ret = get_representation_info(
+                self.as_ptr(),
+                scale_factor,
+                &mut actual_scale_factor,
+                &mut pixel_width,
+                &mut pixel_height
+            );
+
+            Ok(match ret != 0 {
+                true => Some(ImageRepresentation {
+                    actual_scale_factor,
+                    pixel_width,
+                    pixel_height
+                }),
+                false => None
+            })
+        })
+    }
+
+    /// Returns the bitmap representation that most closely matches
+    /// |scale_factor|. Only 32-bit RGBA/BGRA formats are supported.
+    /// |color_type| and |alpha_type| values specify the desired output pixel
+    /// format. Returns None if no representation exists.
+    pub fn get_as_bitmap(
+        &self,
+        scale_factor: f32,
+        color_type: ColorType,
+        alpha_type: AlphaType
+    ) -> Result<Option<EncodedImage>> {
+        try_c!(self, get_as_bitmap, {
+            let mut pixel_width: c_int = 0;
+            let mut pixel_height: c_int = 0;
+
+            let data = get_as_bitmap(
+                self.as_ptr(),
+                scale_factor,
+                color_type.into(),
+                alpha_type.into(),
+                &mut pixel_width,
+                &mut pixel_height
+            );
+
+            Ok(encoded_image(data, pixel_width, pixel_height))
+        })
+    }
+
+    /// Returns the PNG representation that most closely matches
+    /// |scale_factor|. If |with_transparency| is true (1) any alpha
+    /// transparency in the image will be represented in the resulting PNG
+    /// data. Returns None if no representation exists.
+    pub fn get_as_png(
+        &self,
+        scale_factor: f32,
+        with_transparency: bool
+    ) -> Result<Option<EncodedImage>> {
+        try_c!(self, get_as_png, {
+            let mut pixel_width: c_int = 0;
+            let mut pixel_height: c_int = 0;
+
+            let data = get_as_png(
+                self.as_ptr(),
+                scale_factor,
+                with_transparency as c_int,
+                &mut pixel_width,
+                &mut pixel_height
+            );
+
+            Ok(encoded_image(data, pixel_width, pixel_height))
+        })
+    }
+
+    /// Returns the JPEG representation that most closely matches
+    /// |scale_factor|. |quality| determines the compression level with 0 ==
+    /// lowest and 100 == highest. The JPEG format does not support alpha
+    /// transparency and the alpha channel, if any, will be discarded. Returns
+    /// None if no representation exists.
+    pub fn get_as_jpeg(&self, scale_factor: f32, quality: i32) -> Result<Option<EncodedImage>> {
+        try_c!(self, get_as_jpeg, {
+            let mut pixel_width: c_int = 0;
+            let mut pixel_height: c_int = 0;
+
+            let data = get_as_jpeg(
+                self.as_ptr(),
+                scale_factor,
+                quality.clamp(0, 100) as c_int,
+                &mut pixel_width,
+                &mut pixel_height
+            );
+
+            Ok(encoded_image(data, pixel_width, pixel_height))
+        })
+    }
+}
+
+impl Default for Image {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wraps exported image data, if any.
+unsafe fn encoded_image(
+    data: *mut cef_binary_value_t,
+    pixel_width: c_int,
+    pixel_height: c_int
+) -> Option<EncodedImage> {
+    BinaryValue::from_ptr(data).map(|data| EncodedImage {
+        data,
+        pixel_width,
+        pixel_height
+    })
+}
+
+/// Decodes an uncompressed 24-bit or 32-bit BMP into top-down BGRA pixels.
+fn decode_bmp(data: &[u8]) -> Result<(i32, i32, AlphaType, Vec<u8>)> {
+    let u16_at = |offset: usize| -> Result<u16> {
+        data.get(offset..offset + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+            .ok_or_else(|| anyhow!("Truncated BMP header.").into())
+    };
+    let u32_at = |offset: usize| -> Result<u32> {
+        data.get(offset..offset + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .ok_or_else(|| anyhow!("Truncated BMP header.").into())
+    };
+    let too_large = || anyhow!("BMP dimensions are too large.");
+
+    if data.get(0..2) != Some(b"BM") {
+        return Err(anyhow!("Missing BMP signature.").into());
+    }
+
+    let pixel_offset = u32_at(10)? as usize;
+    let header_size = u32_at(14)?;
+    let width = u32_at(18)? as i32;
+    let height = u32_at(22)? as i32;
+    let bits_per_pixel = u16_at(28)?;
+    let compression = u32_at(30)?;
+
+    let bytes_per_pixel = match bits_per_pixel {
+        24 => 3,
+        32 => 4,
+        _ => return Err(anyhow!("Unsupported BMP bit depth: {}.", bits_per_pixel).into())
+    };
+
+    // The reserved fourth byte of 32-bit BI_RGB pixels is not alpha, so only
+    // BI_BITFIELDS bitmaps with an alpha mask can be transparent.
+    let has_alpha = match (compression, bytes_per_pixel) {
+        (BI_RGB, _) => false,
+        (BI_BITFIELDS, 4) => {
+            let masks = [u32_at(54)?, u32_at(58)?, u32_at(62)?];
+            let alpha_mask = match header_size >= BITMAPV3INFOHEADER_SIZE {
+                true => u32_at(66)?,
+                false => 0
+            };
+
+            if masks != [0x00ff0000, 0x0000ff00, 0x000000ff]
+                || (alpha_mask != 0 && alpha_mask != 0xff000000)
+            {
+                return Err(anyhow!("Only BGRA BMP channel masks are supported.").into());
+            }
+
+            alpha_mask != 0
+        },
+        _ => return Err(anyhow!("Compressed BMP images are not supported.").into())
+    };
+
+    if width <= 0 || height == 0 {
+        return Err(anyhow!("Invalid BMP dimensions: {} x {}.", width, height).into());
+    }
+
+    // A negative height denotes a top-down bitmap.
+    let top_down = height < 0;
+    let width = width as usize;
+    let height = height.unsigned_abs() as usize;
+    let row_size = width
+        .checked_mul(bytes_per_pixel)
+        .ok_or_else(too_large)?;
+    let stride = row_size
+        .checked_add(3)
+        .ok_or_else(too_large)?
+        & !3;
+    let pixels_end = stride
+        .checked_mul(height)
+        .and_then(|size| size.checked_add(pixel_offset))
+        .ok_or_else(too_large)?;
+
+    if data.len() < pixels_end {
+        return Err(anyhow!("Truncated BMP pixel data.").into());
+    }
+
+    let mut pixels = Vec::with_capacity(width * height * 4);
+
+    for y in 0..height {
+        let row = match top_down {
+            true => y,
+            false => height - 1 - y
+        };
+        let start = pixel_offset + row * stride;
+
+        for pixel in data[start..start + row_size].chunks_exact(bytes_per_pixel) {
+            let alpha = match has_alpha {
+                true => pixel[3],
+                false => 0xff
+            };
+
+            pixels.extend_from_slice(&[pixel[0], pixel[1], pixel[2], alpha]);
+        }
+    }
+
+    let alpha_type = match has_alpha {
+        true => AlphaType::Postmultiplied,
+        false => AlphaType::Opaque
+    };
+
+    Ok((width as i32, height as i32, alpha_type, pixels))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a BMP file with the channel |masks| after the info header and
+    /// the pixel |rows| in file order, each padded to four bytes.
+    fn bmp(
+        width: i32,
+        height: i32,
+        bits_per_pixel: u16,
+        compression: u32,
+        masks: &[u32],
+        rows: &[&[u8]]
+    ) -> Vec<u8> {
+        let header_size: u32 = match masks.len() {
+            4 => BITMAPV3INFOHEADER_SIZE,
+            _ => 40
+        };
+        let pixel_offset = 54 + 4 * masks.len() as u32;
+        let mut data = Vec::new();
+
+        data.extend_from_slice(b"BM");
+        data.extend_from_slice(&[0; 8]);
+        data.extend_from_slice(&pixel_offset.to_le_bytes());
+        data.extend_from_slice(&header_size.to_le_bytes());
+        data.extend_from_slice(&width.to_le_bytes());
+        data.extend_from_slice(&height.to_le_bytes());
+        data.extend_from_slice(&1u16.to_le_bytes());
+        data.extend_from_slice(&bits_per_pixel.to_le_bytes());
+        data.extend_from_slice(&compression.to_le_bytes());
+        data.extend_from_slice(&[0; 20]);
+
+        for mask in masks {
+            data.extend_from_slice(&mask.to_le_bytes());
+        }
+
+        for row in rows {
+            data.extend_from_slice(row);
+            data.resize(data.len() + (4 - row.len() % 4) % 4, 0);
+        }
+
+        data
+    }
+
+    #[test]
+    fn decode_24_bit_bottom_up() {
+        let data = bmp(
+            2,
+            2,
+            24,
+            BI_RGB,
+            &[],
+            &[&[1, 2, 3, 4, 5, 6], &[7, 8, 9, 10, 11, 12]]
+        );
+
+        assert_eq!(
+            decode_bmp(&data).unwrap(),
+            (
+                2,
+                2,
+                AlphaType::Opaque,
+                vec![
+                    7, 8, 9, 0xff, 10, 11, 12, 0xff, 1, 2, 3, 0xff, 4, 5, 6, 0xff
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn decode_32_bit_top_down() {
+        let data = bmp(1, -2, 32, BI_RGB, &[], &[&[1, 2, 3, 4], &[5, 6, 7, 8]]);
+
+        // The reserved byte of BI_RGB pixels is not alpha.
+        assert_eq!(
+            decode_bmp(&data).unwrap(),
+            (1, 2, AlphaType::Opaque, vec![1, 2, 3, 0xff, 5, 6, 7, 0xff])
+        );
+    }
+
+    #[test]
+    fn decode_bitfields() {
+        let masks = [0x00ff0000, 0x0000ff00, 0x000000ff];
+        let data = bmp(1, 1, 32, BI_BITFIELDS, &masks, &[&[1, 2, 3, 4]]);
+
+        assert_eq!(
+            decode_bmp(&data).unwrap(),
+            (1, 1, AlphaType::Opaque, vec![1, 2, 3, 0xff])
+        );
+    }
+
+    #[test]
+    fn decode_bitfields_alpha() {
+        let masks = [0x00ff0000, 0x0000ff00, 0x000000ff, 0xff000000];
+        let data = bmp(1, 1, 32, BI_BITFIELDS, &masks, &[&[1, 2, 3, 4]]);
+
+        assert_eq!(
+            decode_bmp(&data).unwrap(),
+            (1, 1, AlphaType::Postmultiplied, vec![1, 2, 3, 4])
+        );
+    }
+
+    #[test]
+    fn decode_unsupported() {
+        let rgba = [0x000000ff, 0x0000ff00, 0x00ff0000, 0xff000000];
+
+        assert!(decode_bmp(&bmp(1, 1, 8, BI_RGB, &[], &[&[0]])).is_err());
+        assert!(decode_bmp(&bmp(1, 1, 24, 1, &[], &[&[0, 0, 0]])).is_err());
+        assert!(decode_bmp(&bmp(1, 1, 32, BI_BITFIELDS, &rgba, &[&[0; 4]])).is_err());
+    }
+
+    #[test]
+    fn decode_invalid() {
+        let data = bmp(2, 2, 24, BI_RGB, &[], &[&[0; 6], &[0; 6]]);
+
+        assert!(decode_bmp(&[]).is_err());
+        assert!(decode_bmp(b"PNG").is_err());
+        assert!(decode_bmp(&data[..20]).is_err());
+        assert!(decode_bmp(&data[..data.len() - 1]).is_err());
+        assert!(decode_bmp(&bmp(0, 1, 24, BI_RGB, &[], &[])).is_err());
+        assert!(decode_bmp(&bmp(1, 0, 24, BI_RGB, &[], &[])).is_err());
+        assert!(decode_bmp(&bmp(i32::MAX, i32::MIN, 32, BI_RGB, &[], &[])).is_err());
+    }
+}
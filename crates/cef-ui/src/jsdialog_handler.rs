@@ -0,0 +1,317 @@
+use crate::{
+    callbacks_builder, debug_assert_currently_on, ref_counted_ptr, try_c, unwind::catch_unwind_or,
+    Browser, CefString, RefCountedPtr, Result, ThreadId, Wrappable, Wrapped
+};
+use cef_ui_sys::{
+    cef_browser_t, cef_jsdialog_callback_t, cef_jsdialog_handler_t, cef_jsdialog_type_t,
+    cef_string_t
+};
+use std::{ffi::c_int, mem::zeroed, ptr::null};
+
+/// Supported JavaScript dialog types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JsDialogType {
+    Alert,
+    Confirm,
+    Prompt
+}
+
+impl From<cef_jsdialog_type_t> for JsDialogType {
+    fn from(value: cef_jsdialog_type_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_jsdialog_type_t> for JsDialogType {
+    fn from(value: &cef_jsdialog_type_t) -> Self {
+        match value {
+            cef_jsdialog_type_t::JSDIALOGTYPE_ALERT => JsDialogType::Alert,
+            cef_jsdialog_type_t::JSDIALOGTYPE_CONFIRM => JsDialogType::Confirm,
+            cef_jsdialog_type_t::JSDIALOGTYPE_PROMPT => JsDialogType::Prompt
+        }
+    }
+}
+
+impl From<JsDialogType> for cef_jsdialog_type_t {
+    fn from(value: JsDialogType) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&JsDialogType> for cef_jsdialog_type_t {
+    fn from(value: &JsDialogType) -> Self {
+        match value {
+            JsDialogType::Alert => cef_jsdialog_type_t::JSDIALOGTYPE_ALERT,
+            JsDialogType::Confirm => cef_jsdialog_type_t::JSDIALOGTYPE_CONFIRM,
+            JsDialogType::Prompt => cef_jsdialog_type_t::JSDIALOGTYPE_PROMPT
+        }
+    }
+}
+
+// Callback structure used for asynchronous continuation of JavaScript dialog
+// requests.
+ref_counted_ptr!(JsDialogCallback, cef_jsdialog_callback_t);
+
+impl JsDialogCallback {
+    /// Continue the JS dialog request. Set |success| to true (1) if the OK button
+    /// was pressed. The |user_input| value should be specified for prompt
+    /// dialogs.
+    pub fn cont(&self, success: bool, user_input: Option<&str>) -> Result<()> {
+        try_c!(self, cont, {
+            let user_input = user_input.map(CefString::new);
+
+            Ok(cont(
+                self.as_ptr(),
+                success as c_int,
+                user_input
+                    .as_ref()
+                    .map_or(null(), |s| s.as_ptr())
+            ))
+        })
+    }
+}
+
+/// Implement this structure to handle events related to JavaScript dialogs. The
+/// functions of this structure will be called on the UI thread.
+pub trait JsDialogHandlerCallbacks: Send + Sync + 'static {
+    /// Called to run a JavaScript dialog. If |origin_url| is non-NULL it can be
+    /// passed to the CefFormatUrlForSecurityDisplay function to retrieve a secure
+    /// and user-friendly display string. The |default_prompt_text| value will be
+    /// specified for prompt dialogs only. Set |suppress_message| to true (1) and
+    /// return false (0) to suppress the message (suppressing messages is
+    /// preferable to immediately executing the callback as this is used to detect
+    /// presumably malicious behavior like spamming alert messages in
+    /// onbeforeunload). Set |suppress_message| to false (0) and return false (0)
+    /// to use the default implementation (the default implementation will show
+    /// one modal dialog at a time and suppress any additional dialog requests
+    /// until the displayed dialog is dismissed). Return true (1) if the
+    /// application will use a custom dialog or if the callback has been executed
+    /// immediately. Custom dialogs may be either modal or modeless. If a custom
+    /// dialog is used the application must execute |callback| once the custom
+    /// dialog is dismissed.
+    fn on_jsdialog(
+        &mut self,
+        _browser: Browser,
+        _origin_url: Option<&str>,
+        _dialog_type: JsDialogType,
+        _message_text: &str,
+        _default_prompt_text: Option<&str>,
+        _callback: JsDialogCallback,
+        _suppress_message: &mut bool
+    ) -> bool {
+        false
+    }
+
+    /// Called to run a dialog asking the user if they want to leave a page.
+    /// Return false (0) to use the default dialog implementation. Return true (1)
+    /// if the application will use a custom dialog or if the callback has been
+    /// executed immediately. Custom dialogs may be either modal or modeless. If a
+    /// custom dialog is used the application must execute |callback| once the
+    /// custom dialog is dismissed.
+    fn on_before_unload_dialog(
+        &mut self,
+        _browser: Browser,
+        _message_text: &str,
+        _is_reload: bool,
+        _callback: JsDialogCallback
+    ) -> bool {
+        false
+    }
+
+    /// Called to cancel any pending dialogs and reset any saved dialog state.
+    /// Will be called due to events like page navigation irregardless of whether
+    /// any dialogs are currently pending.
+    fn on_reset_dialog_state(&mut self, _browser: Browser) {}
+
+    /// Called when the dialog is closed.
+    fn on_dialog_closed(&mut self, _browser: Browser) {}
+}
+
+// Implement this structure to handle events related to JavaScript dialogs. The
+// functions of this structure will be called on the UI thread.
+ref_counted_ptr!(JsDialogHandler, cef_jsdialog_handler_t);
+
+impl JsDialogHandler {
+    pub fn new<C: JsDialogHandlerCallbacks>(delegate: C) -> Self {
+        Self(JsDialogHandlerWrapper::new(delegate).wrap())
+    }
+}
+
+callbacks_builder! {
+    /// Builds a JsDialogHandler from closures. Callbacks that are not set fall
+    /// back to the default JsDialogHandlerCallbacks behavior.
+    JsDialogHandlerBuilder: JsDialogHandlerCallbacks => JsDialogHandler {
+        fn on_jsdialog(
+            browser: Browser,
+            origin_url: Option<&str>,
+            dialog_type: JsDialogType,
+            message_text: &str,
+            default_prompt_text: Option<&str>,
+            callback: JsDialogCallback,
+            suppress_message: &mut bool
+        ) -> bool = false;
+        fn on_before_unload_dialog(
+            browser: Browser,
+            message_text: &str,
+            is_reload: bool,
+            callback: JsDialogCallback
+        ) -> bool = false;
+        fn on_reset_dialog_state(browser: Browser);
+        fn on_dialog_closed(browser: Browser);
+    }
+}
+
+/// Translates CEF -> Rust callbacks.
+struct JsDialogHandlerWrapper<C: JsDialogHandlerCallbacks>(C);
+
+impl<C: JsDialogHandlerCallbacks> JsDialogHandlerWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
+    }
+
+    /// Called to run a JavaScript dialog. If |origin_url| is non-NULL it can be
+    /// passed to the CefFormatUrlForSecurityDisplay function to retrieve a secure
+    /// and user-friendly display string. The |default_prompt_text| value will be
+    /// specified for prompt dialogs only. Set |suppress_message| to true (1) and
+    /// return false (0) to suppress the message (suppressing messages is
+    /// preferable to immediately executing the callback as this is used to detect
+    /// presumably malicious behavior like spamming alert messages in
+    /// onbeforeunload). Set |suppress_message| to false (0) and return false (0)
+    /// to use the default implementation (the default implementation will show
+    /// one modal dialog at a time and suppress any additional dialog requests
+    /// until the displayed dialog is dismissed). Return true (1) if the
+    /// application will use a custom dialog or if the callback has been executed
+    /// immediately. Custom dialogs may be either modal or modeless. If a custom
+    /// dialog is used the application must execute |callback| once the custom
+    /// dialog is dismissed.
+    unsafe extern "C" fn c_on_jsdialog(
+        this: *mut cef_jsdialog_handler_t,
+        browser: *mut cef_browser_t,
+        origin_url: *const cef_string_t,
+        dialog_type: cef_jsdialog_type_t,
+        message_text: *const cef_string_t,
+        default_prompt_text: *const cef_string_t,
+        callback: *mut cef_jsdialog_callback_t,
+        suppress_message: *mut c_int
+    ) -> c_int {
+        catch_unwind_or("JsDialogHandlerWrapper::c_on_jsdialog", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let origin_url: Option<String> = CefString::from_ptr(origin_url).map(|s| s.into());
+            let message_text: String = CefString::from_ptr(message_text)
+                .map(|s| s.into())
+                .unwrap_or_default();
+            let default_prompt_text: Option<String> =
+                CefString::from_ptr(default_prompt_text).map(|s| s.into());
+            let callback = JsDialogCallback::from_ptr_unchecked(callback);
+            let mut local_suppress_message = *suppress_message != 0;
+
+            let ret = this.0.on_jsdialog(
+                browser,
+                origin_url
+                    .as_deref()
+                    .filter(|s| !s.is_empty()),
+                dialog_type.into(),
+                &message_text,
+                default_prompt_text.as_deref(),
+                callback,
+                &mut local_suppress_message
+            );
+
+            *suppress_message = local_suppress_message as c_int;
+
+            ret as c_int
+        })
+    }
+
+    /// Called to run a dialog asking the user if they want to leave a page.
+    /// Return false (0) to use the default dialog implementation. Return true (1)
+    /// if the application will use a custom dialog or if the callback has been
+    /// executed immediately. Custom dialogs may be either modal or modeless. If a
+    /// custom dialog is used the application must execute |callback| once the
+    /// custom dialog is dismissed.
+    unsafe extern "C" fn c_on_before_unload_dialog(
+        this: *mut cef_jsdialog_handler_t,
+        browser: *mut cef_browser_t,
+        message_text: *const cef_string_t,
+        is_reload: c_int,
+        callback: *mut cef_jsdialog_callback_t
+    ) -> c_int {
+        catch_unwind_or(
+            "JsDialogHandlerWrapper::c_on_before_unload_dialog",
+            0,
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+                let message_text: String = CefString::from_ptr(message_text)
+                    .map(|s| s.into())
+                    .unwrap_or_default();
+                let callback = JsDialogCallback::from_ptr_unchecked(callback);
+
+                this.0
+                    .on_before_unload_dialog(browser, &message_text, is_reload != 0, callback)
+                    as c_int
+            }
+        )
+    }
+
+    /// Called to cancel any pending dialogs and reset any saved dialog state.
+    /// Will be called due to events like page navigation irregardless of whether
+    /// any dialogs are currently pending.
+    unsafe extern "C" fn c_on_reset_dialog_state(
+        this: *mut cef_jsdialog_handler_t,
+        browser: *mut cef_browser_t
+    ) {
+        catch_unwind_or(
+            "JsDialogHandlerWrapper::c_on_reset_dialog_state",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+
+                this.0
+                    .on_reset_dialog_state(browser);
+            }
+        )
+    }
+
+    /// Called when the dialog is closed.
+    unsafe extern "C" fn c_on_dialog_closed(
+        this: *mut cef_jsdialog_handler_t,
+        browser: *mut cef_browser_t
+    ) {
+        catch_unwind_or("JsDialogHandlerWrapper::c_on_dialog_closed", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+
+            this.0
+                .on_dialog_closed(browser);
+        })
+    }
+}
+
+impl<C: JsDialogHandlerCallbacks> Wrappable for JsDialogHandlerWrapper<C> {
+    type Cef = cef_jsdialog_handler_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<cef_jsdialog_handler_t> {
+        RefCountedPtr::wrap(
+            cef_jsdialog_handler_t {
+                base:                    unsafe { zeroed() },
+                on_jsdialog:             Some(Self::c_on_jsdialog),
+                on_before_unload_dialog: Some(Self::c_on_before_unload_dialog),
+                on_reset_dialog_state:   Some(Self::c_on_reset_dialog_state),
+                on_dialog_closed:        Some(Self::c_on_dialog_closed)
+            },
+            self
+        )
+    }
+}
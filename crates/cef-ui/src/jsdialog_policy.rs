@@ -0,0 +1,267 @@
+use crate::{Browser, JsDialogCallback, JsDialogHandler, JsDialogHandlerCallbacks, JsDialogType};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant}
+};
+use tracing::{error, warn};
+
+/// What JsDialogPolicy does with a JavaScript dialog.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum JsDialogAction {
+    /// Forward the dialog to the delegate, or show the default dialog if there
+    /// is no delegate.
+    #[default]
+    Default,
+
+    /// Drop the dialog without showing it. The page sees the dialog as
+    /// cancelled. CEF cannot suppress beforeunload dialogs, so for those this
+    /// is the same as Accept.
+    Suppress,
+
+    /// Close the dialog immediately as if OK was pressed. Prompts receive
+    /// their default text; beforeunload dialogs allow the page to be left.
+    Accept,
+
+    /// Close the dialog immediately as if Cancel was pressed. Beforeunload
+    /// dialogs keep the page open.
+    Dismiss
+}
+
+/// The JsDialogPolicy delegate that shows the default dialogs.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoDelegate;
+
+impl JsDialogHandlerCallbacks for NoDelegate {}
+
+/// A configurable JsDialogHandlerCallbacks implementation for deployments that
+/// need to keep hostile pages from spamming or blocking the user with dialogs,
+/// e.g. kiosks. Dialogs not handled by the policy are forwarded to the
+/// delegate |C|.
+pub struct JsDialogPolicy<C: JsDialogHandlerCallbacks = NoDelegate> {
+    alerts:        JsDialogAction,
+    confirms:      JsDialogAction,
+    prompts:       JsDialogAction,
+    before_unload: JsDialogAction,
+    rate_limit:    Option<(usize, Duration)>,
+    dialogs:       HashMap<String, VecDeque<Instant>>,
+    delegate:      C
+}
+
+impl JsDialogPolicy {
+    /// Uses the default action for every dialog, without rate limiting.
+    pub fn new() -> Self {
+        Self {
+            alerts:        JsDialogAction::Default,
+            confirms:      JsDialogAction::Default,
+            prompts:       JsDialogAction::Default,
+            before_unload: JsDialogAction::Default,
+            rate_limit:    None,
+            dialogs:       HashMap::new(),
+            delegate:      NoDelegate
+        }
+    }
+
+    /// Suppresses all alert, confirm and prompt dialogs and leaves pages
+    /// without asking.
+    pub fn kiosk() -> Self {
+        Self::new()
+            .alerts(JsDialogAction::Suppress)
+            .confirms(JsDialogAction::Suppress)
+            .prompts(JsDialogAction::Suppress)
+            .before_unload(JsDialogAction::Accept)
+    }
+}
+
+impl<C: JsDialogHandlerCallbacks> JsDialogPolicy<C> {
+    /// What to do with alert() dialogs.
+    pub fn alerts(mut self, action: JsDialogAction) -> Self {
+        self.alerts = action;
+        self
+    }
+
+    /// What to do with confirm() dialogs.
+    pub fn confirms(mut self, action: JsDialogAction) -> Self {
+        self.confirms = action;
+        self
+    }
+
+    /// What to do with prompt() dialogs.
+    pub fn prompts(mut self, action: JsDialogAction) -> Self {
+        self.prompts = action;
+        self
+    }
+
+    /// What to do with dialogs asking the user if they want to leave a page.
+    pub fn before_unload(mut self, action: JsDialogAction) -> Self {
+        self.before_unload = action;
+        self
+    }
+
+    /// Suppresses alert, confirm and prompt dialogs from an origin once it has
+    /// shown |max_dialogs| of them within |period|.
+    pub fn rate_limit(mut self, max_dialogs: usize, period: Duration) -> Self {
+        self.rate_limit = Some((max_dialogs, period));
+        self
+    }
+
+    /// Forwards dialogs whose action is Default to |delegate|.
+    pub fn delegate<D: JsDialogHandlerCallbacks>(self, delegate: D) -> JsDialogPolicy<D> {
+        JsDialogPolicy {
+            alerts:        self.alerts,
+            confirms:      self.confirms,
+            prompts:       self.prompts,
+            before_unload: self.before_unload,
+            rate_limit:    self.rate_limit,
+            dialogs:       self.dialogs,
+            delegate
+        }
+    }
+
+    /// Converts this to a JsDialogHandler.
+    pub fn build(self) -> JsDialogHandler {
+        JsDialogHandler::new(self)
+    }
+
+    /// Returns the action for |dialog_type|.
+    pub fn action(&self, dialog_type: JsDialogType) -> JsDialogAction {
+        match dialog_type {
+            JsDialogType::Alert => self.alerts,
+            JsDialogType::Confirm => self.confirms,
+            JsDialogType::Prompt => self.prompts
+        }
+    }
+
+    /// Records a dialog from |origin| and returns true if it exceeds the rate
+    /// limit. Suppressed dialogs are not counted.
+    fn is_rate_limited(&mut self, origin: &str) -> bool {
+        let (max_dialogs, period) = match self.rate_limit {
+            Some(rate_limit) => rate_limit,
+            None => return false
+        };
+
+        let now = Instant::now();
+
+        // Forget dialogs older than |period| and origins that have none left,
+        // so that pages cycling through origins cannot grow the map.
+        self.dialogs.retain(|_, dialogs| {
+            while let Some(dialog) = dialogs.front() {
+                match now.duration_since(*dialog) > period {
+                    true => dialogs.pop_front(),
+                    false => break
+                };
+            }
+
+            !dialogs.is_empty()
+        });
+
+        let count = self
+            .dialogs
+            .get(origin)
+            .map_or(0, |dialogs| dialogs.len());
+
+        if count >= max_dialogs {
+            return true;
+        }
+
+        self.dialogs
+            .entry(origin.to_string())
+            .or_default()
+            .push_back(now);
+
+        false
+    }
+}
+
+impl Default for JsDialogPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: JsDialogHandlerCallbacks> JsDialogHandlerCallbacks for JsDialogPolicy<C> {
+    fn on_jsdialog(
+        &mut self,
+        browser: Browser,
+        origin_url: Option<&str>,
+        dialog_type: JsDialogType,
+        message_text: &str,
+        default_prompt_text: Option<&str>,
+        callback: JsDialogCallback,
+        suppress_message: &mut bool
+    ) -> bool {
+        let action = self.action(dialog_type);
+
+        if action == JsDialogAction::Suppress {
+            *suppress_message = true;
+
+            return false;
+        }
+
+        if self.is_rate_limited(origin_url.unwrap_or_default()) {
+            warn!(
+                "Suppressing {:?} dialog from {:?}: too many dialogs.",
+                dialog_type, origin_url
+            );
+
+            *suppress_message = true;
+
+            return false;
+        }
+
+        let result = match action {
+            JsDialogAction::Accept => callback.cont(true, default_prompt_text),
+            JsDialogAction::Dismiss => callback.cont(false, None),
+            _ => {
+                return self.delegate.on_jsdialog(
+                    browser,
+                    origin_url,
+                    dialog_type,
+                    message_text,
+                    default_prompt_text,
+                    callback,
+                    suppress_message
+                );
+            }
+        };
+
+        if let Err(e) = result {
+            error!("Failed to close {:?} dialog: {}", dialog_type, e);
+        }
+
+        true
+    }
+
+    fn on_before_unload_dialog(
+        &mut self,
+        browser: Browser,
+        message_text: &str,
+        is_reload: bool,
+        callback: JsDialogCallback
+    ) -> bool {
+        let result = match self.before_unload {
+            JsDialogAction::Suppress | JsDialogAction::Accept => callback.cont(true, None),
+            JsDialogAction::Dismiss => callback.cont(false, None),
+            JsDialogAction::Default => {
+                return self
+                    .delegate
+                    .on_before_unload_dialog(browser, message_text, is_reload, callback);
+            }
+        };
+
+        if let Err(e) = result {
+            error!("Failed to close beforeunload dialog: {}", e);
+        }
+
+        true
+    }
+
+    fn on_reset_dialog_state(&mut self, browser: Browser) {
+        self.delegate
+            .on_reset_dialog_state(browser);
+    }
+
+    fn on_dialog_closed(&mut self, browser: Browser) {
+        self.delegate
+            .on_dialog_closed(browser);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
-    ref_counted_ptr, Browser, KeyEvent, NativeEventHandle, RefCountedPtr, Wrappable, Wrapped
+    callbacks_builder, debug_assert_currently_on, ref_counted_ptr, unwind::catch_unwind_or,
+    Browser, KeyEvent, NativeEventHandle, RefCountedPtr, ThreadId, Wrappable, Wrapped
 };
 use cef_ui_sys::{cef_browser_t, cef_event_handle_t, cef_key_event_t, cef_keyboard_handler_t};
 use std::{ffi::c_int, mem::zeroed};
@@ -15,11 +16,13 @@ pub trait KeyboardHandlerCallbacks: Send + Sync + 'static {
     /// (0).
     fn on_pre_key_event(
         &mut self,
-        browser: Browser,
-        event: KeyEvent,
-        os_event: Option<NativeEventHandle>,
-        is_keyboard_shortcut: &mut bool
-    ) -> bool;
+        _browser: Browser,
+        _event: KeyEvent,
+        _os_event: Option<NativeEventHandle>,
+        _is_keyboard_shortcut: &mut bool
+    ) -> bool {
+        false
+    }
 
     /// Called after the renderer and JavaScript in the page has had a chance to
     /// handle the event. |event| contains information about the keyboard event.
@@ -27,10 +30,12 @@ pub trait KeyboardHandlerCallbacks: Send + Sync + 'static {
     /// if the keyboard event was handled or false (0) otherwise.
     fn on_key_event(
         &mut self,
-        browser: Browser,
-        event: KeyEvent,
-        os_event: Option<NativeEventHandle>
-    ) -> bool;
+        _browser: Browser,
+        _event: KeyEvent,
+        _os_event: Option<NativeEventHandle>
+    ) -> bool {
+        false
+    }
 }
 
 // Implement this structure to handle events related to keyboard input. The
@@ -43,12 +48,30 @@ impl KeyboardHandler {
     }
 }
 
+callbacks_builder! {
+    /// Builds a KeyboardHandler from closures. Callbacks that are not set fall
+    /// back to the default KeyboardHandlerCallbacks behavior.
+    KeyboardHandlerBuilder: KeyboardHandlerCallbacks => KeyboardHandler {
+        fn on_pre_key_event(
+            browser: Browser,
+            event: KeyEvent,
+            os_event: Option<NativeEventHandle>,
+            is_keyboard_shortcut: &mut bool
+        ) -> bool = false;
+        fn on_key_event(
+            browser: Browser,
+            event: KeyEvent,
+            os_event: Option<NativeEventHandle>
+        ) -> bool = false;
+    }
+}
+
 /// Translates CEF -> Rust callbacks.
-struct KeyboardHandlerWrapper(Box<dyn KeyboardHandlerCallbacks>);
+struct KeyboardHandlerWrapper<C: KeyboardHandlerCallbacks>(C);
 
-impl KeyboardHandlerWrapper {
-    pub fn new<C: KeyboardHandlerCallbacks>(delegate: C) -> Self {
-        Self(Box::new(delegate))
+impl<C: KeyboardHandlerCallbacks> KeyboardHandlerWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
     }
 
     /// Called before a keyboard event is sent to the renderer. |event| contains
@@ -64,19 +87,23 @@ impl KeyboardHandlerWrapper {
         os_event: cef_event_handle_t,
         is_keyboard_shortcut: *mut c_int
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let event = KeyEvent::from_ptr_unchecked(event);
-        let os_event = NativeEventHandle::try_from(os_event).ok();
-        let mut local_is_keyboard_shortcut = *is_keyboard_shortcut != 0;
+        catch_unwind_or("KeyboardHandlerWrapper::c_on_pre_key_event", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
 
-        let ret =
-            this.0
-                .on_pre_key_event(browser, event, os_event, &mut local_is_keyboard_shortcut);
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let event = KeyEvent::from_ptr_unchecked(event);
+            let os_event = NativeEventHandle::try_from(os_event).ok();
+            let mut local_is_keyboard_shortcut = *is_keyboard_shortcut != 0;
+
+            let ret =
+                this.0
+                    .on_pre_key_event(browser, event, os_event, &mut local_is_keyboard_shortcut);
 
-        *is_keyboard_shortcut = local_is_keyboard_shortcut as c_int;
+            *is_keyboard_shortcut = local_is_keyboard_shortcut as c_int;
 
-        ret as c_int
+            ret as c_int
+        })
     }
 
     /// Called after the renderer and JavaScript in the page has had a chance to
@@ -89,17 +116,21 @@ impl KeyboardHandlerWrapper {
         event: *const cef_key_event_t,
         os_event: cef_event_handle_t
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let event = KeyEvent::from_ptr_unchecked(event);
-        let os_event = NativeEventHandle::try_from(os_event).ok();
+        catch_unwind_or("KeyboardHandlerWrapper::c_on_key_event", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
 
-        this.0
-            .on_key_event(browser, event, os_event) as c_int
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let event = KeyEvent::from_ptr_unchecked(event);
+            let os_event = NativeEventHandle::try_from(os_event).ok();
+
+            this.0
+                .on_key_event(browser, event, os_event) as c_int
+        })
     }
 }
 
-impl Wrappable for KeyboardHandlerWrapper {
+impl<C: KeyboardHandlerCallbacks> Wrappable for KeyboardHandlerWrapper<C> {
     type Cef = cef_keyboard_handler_t;
 
     /// Converts this to a smart pointer.
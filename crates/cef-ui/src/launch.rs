@@ -0,0 +1,274 @@
+use crate::{
+    quit_message_loop, App, Browser, BrowserSettings, Client, ClientCallbacks, Context,
+    LifeSpanHandler, LifeSpanHandlerCallbacks, MainArgs, Result, RuntimeStyle, Settings, Size
+};
+#[cfg(not(feature = "views"))]
+use crate::{BrowserHost, Rect, WindowInfo};
+#[cfg(feature = "views")]
+use crate::{
+    BrowserView, BrowserViewDelegate, BrowserViewDelegateCallbacks, ChromeToolbarType,
+    PanelDelegateCallbacks, ViewDelegateCallbacks, Window, WindowDelegate, WindowDelegateCallbacks
+};
+use anyhow::anyhow;
+use tracing::error;
+
+/// The distance of a native window from the top-left corner of the screen.
+#[cfg(not(feature = "views"))]
+const WINDOW_OFFSET: i32 = 100;
+
+/// Runs a browser window that shows |url| until it is closed, for applications
+/// that just need a webview. This is the equivalent of the cefsimple sample;
+/// see Launcher for the available options.
+pub fn launch(settings: Settings, url: &str) -> Result<()> {
+    Launcher::new(settings, url).run()
+}
+
+/// Performs the whole lifecycle of a single-window application:
+///
+/// 1. Runs the current process as a CEF subprocess and exits, if it is one.
+/// 2. Initializes CEF.
+/// 3. Creates a top-level window showing the URL, using the Views framework
+///    when the views feature is enabled and a native window otherwise.
+/// 4. Runs the CEF message loop until every browser has closed.
+/// 5. Shuts CEF down.
+///
+/// Popups opened by the page get windows of their own, and the application
+/// exits when the last window closes. The settings must not enable
+/// multi_threaded_message_loop.
+pub struct Launcher {
+    settings:         Settings,
+    url:              String,
+    app:              Option<App>,
+    browser_settings: BrowserSettings,
+    title:            String,
+    size:             Size,
+    #[cfg(feature = "views")]
+    toolbar_type:     ChromeToolbarType
+}
+
+impl Launcher {
+    pub fn new(settings: Settings, url: &str) -> Self {
+        Self {
+            settings,
+            url: url.to_string(),
+            app: None,
+            browser_settings: BrowserSettings::new(),
+            title: String::from("cef-ui"),
+            size: Size::new(1280, 800),
+            #[cfg(feature = "views")]
+            toolbar_type: ChromeToolbarType::None
+        }
+    }
+
+    /// Selects the Alloy or Chrome runtime. This overrides the runtime chosen
+    /// in the settings.
+    pub fn runtime_style(mut self, runtime_style: RuntimeStyle) -> Self {
+        self.settings = self
+            .settings
+            .runtime_style(runtime_style);
+        self
+    }
+
+    /// The Chrome toolbar shown above the browser. Only used with the Chrome
+    /// runtime. Defaults to no toolbar.
+    #[cfg(feature = "views")]
+    pub fn chrome_toolbar_type(mut self, toolbar_type: ChromeToolbarType) -> Self {
+        self.toolbar_type = toolbar_type;
+        self
+    }
+
+    /// The application callbacks, used in the browser process and subprocesses.
+    pub fn app(mut self, app: App) -> Self {
+        self.app = Some(app);
+        self
+    }
+
+    /// The settings of the browser.
+    pub fn browser_settings(mut self, browser_settings: BrowserSettings) -> Self {
+        self.browser_settings = browser_settings;
+        self
+    }
+
+    /// The title of the window.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    /// The initial size of the window in DIP, or in screen pixels for native
+    /// windows when the views feature is disabled.
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Runs the application. Returns once the last window has closed and CEF
+    /// has shut down. In a subprocess this never returns.
+    pub fn run(self) -> Result<()> {
+        self.browser_settings
+            .validate_for(self.settings.get_runtime_style())?;
+
+        let context = Context::new(MainArgs::new()?, self.settings, self.app);
+
+        context.exit_if_subprocess();
+        context.initialize()?;
+
+        let client = Client::new(LaunchClient {
+            life_span_handler: LifeSpanHandler::new(LaunchLifeSpanHandler { open: 0 })
+        });
+        let created = create_window(
+            client,
+            &self.url,
+            &self.browser_settings,
+            &self.title,
+            &self.size,
+            #[cfg(feature = "views")]
+            self.toolbar_type
+        );
+
+        let result = created.and_then(|_| context.run_message_loop());
+
+        context.shutdown();
+
+        result
+    }
+}
+
+/// The client of every browser created by Launcher.
+struct LaunchClient {
+    life_span_handler: LifeSpanHandler
+}
+
+impl ClientCallbacks for LaunchClient {
+    fn get_life_span_handler(&mut self) -> Option<LifeSpanHandler> {
+        Some(self.life_span_handler.clone())
+    }
+}
+
+/// Quits the message loop when the last browser closes.
+struct LaunchLifeSpanHandler {
+    open: usize
+}
+
+impl LifeSpanHandlerCallbacks for LaunchLifeSpanHandler {
+    fn on_after_created(&mut self, _browser: Browser) {
+        self.open += 1;
+    }
+
+    fn on_before_close(&mut self, _browser: Browser) {
+        self.open = self.open.saturating_sub(1);
+
+        if self.open == 0 {
+            if let Err(e) = quit_message_loop() {
+                error!("Failed to quit the message loop: {}", e);
+            }
+        }
+    }
+}
+
+/// Creates a Views window that hosts a BrowserView.
+#[cfg(feature = "views")]
+fn create_window(
+    client: Client,
+    url: &str,
+    settings: &BrowserSettings,
+    title: &str,
+    size: &Size,
+    toolbar_type: ChromeToolbarType
+) -> Result<()> {
+    let browser_view_delegate =
+        BrowserViewDelegate::new(LaunchBrowserViewDelegate { toolbar_type });
+    let browser_view =
+        BrowserView::create(client, url, settings, None, None, Some(browser_view_delegate))
+            .ok_or_else(|| anyhow!("Failed to create the browser view."))?;
+    let delegate = WindowDelegate::new(LaunchWindowDelegate {
+        browser_view,
+        title: title.to_string(),
+        size: *size
+    });
+
+    Window::create_top_level(Some(delegate))
+        .map(|_| ())
+        .ok_or_else(|| anyhow!("Failed to create the window.").into())
+}
+
+/// Creates a native window that hosts the browser.
+#[cfg(not(feature = "views"))]
+fn create_window(
+    client: Client,
+    url: &str,
+    settings: &BrowserSettings,
+    title: &str,
+    size: &Size
+) -> Result<()> {
+    let bounds = Rect::new(WINDOW_OFFSET, WINDOW_OFFSET, size.width, size.height);
+    let window_info = WindowInfo::as_top_level(title, &bounds);
+
+    BrowserHost::create_browser_sync(&window_info, client, url, settings, None, None)
+        .map(|_| ())
+        .ok_or_else(|| anyhow!("Failed to create the browser.").into())
+}
+
+/// Supplies the Chrome toolbar type of the BrowserView.
+#[cfg(feature = "views")]
+struct LaunchBrowserViewDelegate {
+    toolbar_type: ChromeToolbarType
+}
+
+#[cfg(feature = "views")]
+impl ViewDelegateCallbacks for LaunchBrowserViewDelegate {}
+
+#[cfg(feature = "views")]
+impl BrowserViewDelegateCallbacks for LaunchBrowserViewDelegate {
+    fn get_chrome_toolbar_type(&mut self, _browser_view: BrowserView) -> ChromeToolbarType {
+        self.toolbar_type
+    }
+}
+
+/// Lays out the BrowserView in the window and closes the browser along with
+/// the window.
+#[cfg(feature = "views")]
+struct LaunchWindowDelegate {
+    browser_view: BrowserView,
+    title:        String,
+    size:         Size
+}
+
+#[cfg(feature = "views")]
+impl LaunchWindowDelegate {
+    fn show(&self, window: &Window) -> Result<()> {
+        window.add_child_view(self.browser_view.as_view())?;
+        window.set_title(&self.title)?;
+        window.center_window(&self.size)?;
+        window.show()?;
+
+        self.browser_view.request_focus()
+    }
+}
+
+#[cfg(feature = "views")]
+impl ViewDelegateCallbacks for LaunchWindowDelegate {}
+
+#[cfg(feature = "views")]
+impl PanelDelegateCallbacks for LaunchWindowDelegate {}
+
+#[cfg(feature = "views")]
+impl WindowDelegateCallbacks for LaunchWindowDelegate {
+    fn on_window_created(&mut self, window: Window) {
+        if let Err(e) = self.show(&window) {
+            error!("Failed to show the window: {}", e);
+        }
+    }
+
+    fn can_close(&mut self, _window: Window) -> bool {
+        // The window may close once the browser has closed, which
+        // request_close starts if it is not already under way.
+        match self.browser_view.get_browser() {
+            Ok(Some(browser)) => browser
+                .get_host()
+                .and_then(|host| host.request_close())
+                .map_or(true, |status| status.can_close_window()),
+            _ => true
+        }
+    }
+}
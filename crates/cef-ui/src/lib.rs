@@ -1,82 +1,240 @@
+#[cfg(feature = "osr")]
+mod accelerated_paint;
+mod accelerator;
+#[cfg(feature = "osr")]
+mod accessibility;
+#[cfg(feature = "osr")]
 mod accessibility_handler;
 mod app;
+mod app_relaunch;
+mod audio_handler;
+mod automation;
+#[cfg(feature = "v8")]
+mod binary_bridge;
 mod browser;
 mod browser_process_handler;
+mod browser_registry;
+mod browsing_data;
 mod callbacks;
 mod client;
 mod color;
 mod command_line;
+mod console_logger;
 mod context;
 mod context_menu_handler;
+mod cookie;
+mod crash_reporting;
+mod dev_server_proxy;
+#[cfg(feature = "devtools")]
+mod dev_tools_host;
+#[cfg(feature = "devtools")]
+mod dev_tools_message_observer;
+#[cfg(feature = "osr")]
+mod device_scale;
+mod dialog_handler;
+mod display_handler;
+mod download;
+mod download_handler;
 mod drag;
+mod drag_handler;
+mod embedded_assets;
+mod error;
+#[cfg(feature = "v8")]
+mod event_bus;
 mod events;
+#[cfg(feature = "extensions")]
 mod extension;
+#[cfg(feature = "extensions")]
 mod extension_handler;
 mod frame;
+#[cfg(feature = "v8")]
+mod function_binding;
+mod header_overrides;
+mod image;
 mod ime;
+mod jsdialog_handler;
+mod jsdialog_policy;
 mod keyboard_handler;
+mod launch;
 mod life_span_handler;
+mod load_handler;
+mod log_file;
 mod macros;
+mod message_pump;
+mod mime;
+mod mock_network;
 mod navigation_entry;
+mod network_recorder;
+#[cfg(feature = "osr")]
+mod osr_surface;
+mod permission_handler;
 mod platform;
+mod popup_policy;
+mod preference_manager;
+mod print_settings;
 mod process;
 mod refcounted;
+mod registration;
+#[cfg(feature = "osr")]
 mod render_handler;
+mod render_process_handler;
+mod render_process_recovery;
 mod request;
 mod request_context;
 mod request_context_handler;
 mod request_handler;
+mod resource_bundle_handler;
+mod resource_handler;
 mod resource_request_handler;
 mod response;
+mod scheme;
+#[cfg(feature = "osr")]
+mod screen_layout;
+#[cfg(feature = "tokio")]
+mod screenshot;
 mod settings;
 mod shared_memory_region;
 mod ssl;
+mod stream;
+mod stream_resource_handler;
 mod string;
+mod subprocess;
+mod task;
 mod time;
+#[cfg(feature = "tokio")]
+mod tokio_interop;
+mod trace;
 mod types;
+mod unwind;
 mod url_request;
+#[cfg(feature = "v8")]
+mod v8;
 mod values;
+mod version;
+#[cfg(feature = "views")]
+mod views;
+#[cfg(feature = "winit")]
+mod winit_integration;
 mod x509_certificate;
+mod xml_reader;
 
+#[cfg(feature = "osr")]
+pub use accelerated_paint::*;
+pub use accelerator::*;
+#[cfg(feature = "osr")]
+pub use accessibility::*;
+#[cfg(feature = "osr")]
 pub use accessibility_handler::*;
 pub use app::*;
+pub use app_relaunch::*;
+pub use audio_handler::*;
+pub use automation::*;
+#[cfg(feature = "v8")]
+pub use binary_bridge::*;
 pub use browser::*;
 pub use browser_process_handler::*;
+pub use browser_registry::*;
+pub use browsing_data::*;
 pub use callbacks::*;
 pub use client::*;
 pub use color::*;
 pub use command_line::*;
+pub use console_logger::*;
 pub use context::*;
 pub use context_menu_handler::*;
+pub use cookie::*;
+pub use crash_reporting::*;
+pub use dev_server_proxy::*;
+#[cfg(all(feature = "devtools", feature = "views"))]
+pub use dev_tools_host::*;
+#[cfg(feature = "devtools")]
+pub use dev_tools_message_observer::*;
+#[cfg(feature = "osr")]
+pub use device_scale::*;
+pub use dialog_handler::*;
+pub use display_handler::*;
+pub use download::*;
+pub use download_handler::*;
 pub use drag::*;
+pub use drag_handler::*;
+pub use embedded_assets::*;
+pub use error::*;
+#[cfg(feature = "v8")]
+pub use event_bus::*;
 pub use events::*;
+#[cfg(feature = "extensions")]
 pub use extension::*;
+#[cfg(feature = "extensions")]
 pub use extension_handler::*;
 pub use frame::*;
+#[cfg(feature = "v8")]
+pub use function_binding::*;
+pub use header_overrides::*;
+pub use image::*;
 pub use ime::*;
+pub use jsdialog_handler::*;
+pub use jsdialog_policy::*;
 pub use keyboard_handler::*;
+pub use launch::*;
 pub use life_span_handler::*;
-pub use macros::*;
+pub use load_handler::*;
+pub use log_file::*;
+pub use message_pump::*;
+pub use mime::*;
+pub use mock_network::*;
 pub use navigation_entry::*;
+pub use network_recorder::*;
+#[cfg(feature = "osr")]
+pub use osr_surface::*;
+pub use permission_handler::*;
 pub use platform::*;
+pub use popup_policy::*;
+pub use preference_manager::*;
+pub use print_settings::*;
 pub use process::*;
 pub use refcounted::*;
+pub use registration::*;
+#[cfg(feature = "osr")]
 pub use render_handler::*;
+pub use render_process_handler::*;
+pub use render_process_recovery::*;
 pub use request::*;
 pub use request_context::*;
 pub use request_context_handler::*;
 pub use request_handler::*;
+pub use resource_bundle_handler::*;
+pub use resource_handler::*;
 pub use resource_request_handler::*;
 pub use response::*;
+pub use scheme::*;
+#[cfg(feature = "osr")]
+pub use screen_layout::*;
+#[cfg(feature = "tokio")]
+pub use screenshot::*;
 pub use settings::*;
 pub use shared_memory_region::*;
 pub use ssl::*;
+pub use stream::*;
+pub use stream_resource_handler::*;
 pub use string::*;
+pub use subprocess::*;
+pub use task::*;
 pub use time::*;
+#[cfg(feature = "tokio")]
+pub use tokio_interop::*;
+pub use trace::*;
 pub use types::*;
 pub use url_request::*;
+#[cfg(feature = "v8")]
+pub use v8::*;
 pub use values::*;
+pub use version::*;
+#[cfg(feature = "views")]
+pub use views::*;
+#[cfg(feature = "winit")]
+pub use winit_integration::*;
 pub use x509_certificate::*;
+pub use xml_reader::*;
 
 // TODO: Make a macro for all the pointer conversion functions.
 
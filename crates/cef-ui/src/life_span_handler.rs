@@ -1,6 +1,9 @@
+#[cfg(feature = "v8")]
+use crate::{binary_bridge, event_bus, function_binding};
 use crate::{
-    ref_counted_ptr, Browser, BrowserSettings, CefString, Client, DictionaryValue, Frame,
-    RefCountedPtr, WindowInfo, WindowOpenDisposition, Wrappable, Wrapped
+    browser_registry, callbacks_builder, debug_assert_currently_on, ref_counted_ptr,
+    unwind::catch_unwind_or, Browser, BrowserSettings, CefString, Client, DictionaryValue, Frame,
+    RefCountedPtr, ThreadId, WindowInfo, WindowOpenDisposition, Wrappable, Wrapped
 };
 use cef_ui_sys::{
     cef_browser_settings_t, cef_browser_t, cef_client_t, cef_dictionary_value_t, cef_frame_t,
@@ -111,19 +114,21 @@ pub trait LifeSpanHandlerCallbacks: Send + Sync + 'static {
     /// cef_render_process_handler_t::on_browser_created() in the render process.
     unsafe fn on_before_popup(
         &mut self,
-        browser: Browser,
-        frame: Frame,
-        target_url: Option<String>,
-        target_frame_name: Option<String>,
-        target_disposition: WindowOpenDisposition,
-        user_gesture: bool,
-        popup_features: PopupFeatures,
-        window_info: &mut WindowInfo,
-        client: &mut Option<Client>,
-        settings: &mut BrowserSettings,
-        extra_info: &mut Option<DictionaryValue>,
-        no_javascript_access: &mut bool
-    ) -> bool;
+        _browser: Browser,
+        _frame: Frame,
+        _target_url: Option<String>,
+        _target_frame_name: Option<String>,
+        _target_disposition: WindowOpenDisposition,
+        _user_gesture: bool,
+        _popup_features: PopupFeatures,
+        _window_info: &mut WindowInfo,
+        _client: &mut Option<Client>,
+        _settings: &mut BrowserSettings,
+        _extra_info: &mut Option<DictionaryValue>,
+        _no_javascript_access: &mut bool
+    ) -> bool {
+        false
+    }
 
     /// Called on the UI thread before a new DevTools popup browser is created.
     /// The |browser| value represents the source of the popup request. Optionally
@@ -144,19 +149,20 @@ pub trait LifeSpanHandlerCallbacks: Send + Sync + 'static {
     /// for IDC_DEV_TOOLS. Only used with the Chrome runtime.
     fn on_before_dev_tools_popup(
         &mut self,
-        browser: Browser,
-        window_info: &mut WindowInfo,
-        client: &mut Option<Client>,
-        settings: &mut BrowserSettings,
-        extra_info: &mut Option<DictionaryValue>,
-        use_default_window: &mut bool
-    );
+        _browser: Browser,
+        _window_info: &mut WindowInfo,
+        _client: &mut Option<Client>,
+        _settings: &mut BrowserSettings,
+        _extra_info: &mut Option<DictionaryValue>,
+        _use_default_window: &mut bool
+    ) {
+    }
 
     /// Called after a new browser is created. It is now safe to begin performing
     /// actions with |browser|. cef_frame_handler_t callbacks related to initial
     /// main frame creation will arrive before this callback. See
     /// cef_frame_handler_t documentation for additional usage information.
-    fn on_after_created(&mut self, browser: Browser);
+    fn on_after_created(&mut self, _browser: Browser) {}
 
     /// Called when a browser has received a request to close. This may result
     /// directly from a call to cef_browser_host_t::*close_browser() or indirectly
@@ -245,7 +251,9 @@ pub trait LifeSpanHandlerCallbacks: Send + Sync + 'static {
     ///     is destroyed.
     /// 11. Application exits by calling cef_quit_message_loop() if no other
     /// browsers exist.
-    fn do_close(&mut self, browser: Browser) -> bool;
+    fn do_close(&mut self, _browser: Browser) -> bool {
+        false
+    }
 
     /// Called just before a browser is destroyed. Release all references to the
     /// browser object and do not attempt to execute any functions on the browser
@@ -257,7 +265,7 @@ pub trait LifeSpanHandlerCallbacks: Send + Sync + 'static {
     /// and cef_resource_request_handler_t callbacks related to those requests may
     /// still arrive on the IO thread after this callback. See cef_frame_handler_t
     /// and do_close() documentation for additional usage information.
-    fn on_before_close(&mut self, browser: Browser);
+    fn on_before_close(&mut self, _browser: Browser) {}
 }
 
 // Implement this structure to handle events related to browser life span. The
@@ -271,12 +279,44 @@ impl LifeSpanHandler {
     }
 }
 
+callbacks_builder! {
+    /// Builds a LifeSpanHandler from closures. Callbacks that are not set fall
+    /// back to the default LifeSpanHandlerCallbacks behavior.
+    LifeSpanHandlerBuilder: LifeSpanHandlerCallbacks => LifeSpanHandler {
+        [unsafe] fn on_before_popup(
+            browser: Browser,
+            frame: Frame,
+            target_url: Option<String>,
+            target_frame_name: Option<String>,
+            target_disposition: WindowOpenDisposition,
+            user_gesture: bool,
+            popup_features: PopupFeatures,
+            window_info: &mut WindowInfo,
+            client: &mut Option<Client>,
+            settings: &mut BrowserSettings,
+            extra_info: &mut Option<DictionaryValue>,
+            no_javascript_access: &mut bool
+        ) -> bool = false;
+        fn on_before_dev_tools_popup(
+            browser: Browser,
+            window_info: &mut WindowInfo,
+            client: &mut Option<Client>,
+            settings: &mut BrowserSettings,
+            extra_info: &mut Option<DictionaryValue>,
+            use_default_window: &mut bool
+        );
+        fn on_after_created(browser: Browser);
+        fn do_close(browser: Browser) -> bool = false;
+        fn on_before_close(browser: Browser);
+    }
+}
+
 /// Translates CEF -> Rust callbacks.
-struct LifeSpanHandlerWrapper(Box<dyn LifeSpanHandlerCallbacks>);
+struct LifeSpanHandlerWrapper<C: LifeSpanHandlerCallbacks>(C);
 
-impl LifeSpanHandlerWrapper {
-    pub fn new<C: LifeSpanHandlerCallbacks>(delegate: C) -> Self {
-        Self(Box::new(delegate))
+impl<C: LifeSpanHandlerCallbacks> LifeSpanHandlerWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
     }
 
     /// Called on the UI thread before a new popup browser is created. The
@@ -317,65 +357,69 @@ impl LifeSpanHandlerWrapper {
         extra_info: *mut *mut cef_dictionary_value_t,
         no_javascript_access: *mut c_int
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let frame = Frame::from_ptr_unchecked(frame);
-        let target_url: Option<String> = CefString::from_ptr(target_url).map(|s| s.into());
-        let target_frame_name: Option<String> =
-            CefString::from_ptr(target_frame_name).map(|s| s.into());
-        let target_disposition = target_disposition.into();
-        let user_gesture = user_gesture != 0;
-        let popup_features = PopupFeatures::from_ptr_unchecked(popup_features);
-        let window_info = WindowInfo::from_ptr_mut_unchecked(window_info);
-        let local_client_ptr = *client;
-        let mut local_client = Client::from_ptr(local_client_ptr);
-        let settings = BrowserSettings::from_ptr_mut_unchecked(settings);
-        let local_extra_info_ptr = *extra_info;
-        let mut local_extra_info = DictionaryValue::from_ptr(local_extra_info_ptr);
-        let mut local_no_javascript_access = *no_javascript_access != 0;
-
-        let ret = this.0.on_before_popup(
-            browser,
-            frame,
-            target_url,
-            target_frame_name,
-            target_disposition,
-            user_gesture,
-            popup_features,
-            window_info,
-            &mut local_client,
-            settings,
-            &mut local_extra_info,
-            &mut local_no_javascript_access
-        );
-
-        // This is terribly gross and continues to increase my hatred for C.
-        // CEF passes the client and extra_info parameters as *mut *mut so you
-        // can either modify or replace the value. These are refcounted values
-        // though so we have to be very careful. In any case, our smart pointer
-        // wrapper will decrease the reference count when dropped as expected.
-        // However, if the value was replaced, we must "forget" the new smart
-        // pointer wrapper so that it doesn't get dropped before we hand it off
-        // to C. We can't forget the original smart pointer wrapper because it
-        // would prevent the reference count from being decreased, hence the
-        // pointer comparison here. :^(
-        *client = local_client
-            .map(|c| match c.as_ptr() == local_client_ptr {
-                true => local_client_ptr,
-                false => c.into_raw()
-            })
-            .unwrap_or_else(null_mut);
-
-        *extra_info = local_extra_info
-            .map(|e| match e.as_ptr() == local_extra_info_ptr {
-                true => local_extra_info_ptr,
-                false => e.into_raw()
-            })
-            .unwrap_or_else(null_mut);
-
-        *no_javascript_access = local_no_javascript_access as c_int;
-
-        ret as c_int
+        catch_unwind_or("LifeSpanHandlerWrapper::c_on_before_popup", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let frame = Frame::from_ptr_unchecked(frame);
+            let target_url: Option<String> = CefString::from_ptr(target_url).map(|s| s.into());
+            let target_frame_name: Option<String> =
+                CefString::from_ptr(target_frame_name).map(|s| s.into());
+            let target_disposition = target_disposition.into();
+            let user_gesture = user_gesture != 0;
+            let popup_features = PopupFeatures::from_ptr_unchecked(popup_features);
+            let window_info = WindowInfo::from_ptr_mut_unchecked(window_info);
+            let local_client_ptr = *client;
+            let mut local_client = Client::from_ptr(local_client_ptr);
+            let settings = BrowserSettings::from_ptr_mut_unchecked(settings);
+            let local_extra_info_ptr = *extra_info;
+            let mut local_extra_info = DictionaryValue::from_ptr(local_extra_info_ptr);
+            let mut local_no_javascript_access = *no_javascript_access != 0;
+
+            let ret = this.0.on_before_popup(
+                browser,
+                frame,
+                target_url,
+                target_frame_name,
+                target_disposition,
+                user_gesture,
+                popup_features,
+                window_info,
+                &mut local_client,
+                settings,
+                &mut local_extra_info,
+                &mut local_no_javascript_access
+            );
+
+            // This is terribly gross and continues to increase my hatred for C.
+            // CEF passes the client and extra_info parameters as *mut *mut so you
+            // can either modify or replace the value. These are refcounted values
+            // though so we have to be very careful. In any case, our smart pointer
+            // wrapper will decrease the reference count when dropped as expected.
+            // However, if the value was replaced, we must "forget" the new smart
+            // pointer wrapper so that it doesn't get dropped before we hand it off
+            // to C. We can't forget the original smart pointer wrapper because it
+            // would prevent the reference count from being decreased, hence the
+            // pointer comparison here. :^(
+            *client = local_client
+                .map(|c| match c.as_ptr() == local_client_ptr {
+                    true => local_client_ptr,
+                    false => c.into_raw()
+                })
+                .unwrap_or_else(null_mut);
+
+            *extra_info = local_extra_info
+                .map(|e| match e.as_ptr() == local_extra_info_ptr {
+                    true => local_extra_info_ptr,
+                    false => e.into_raw()
+                })
+                .unwrap_or_else(null_mut);
+
+            *no_javascript_access = local_no_javascript_access as c_int;
+
+            ret as c_int
+        })
     }
 
     /// Called on the UI thread before a new DevTools popup browser is created.
@@ -404,41 +448,49 @@ impl LifeSpanHandlerWrapper {
         extra_info: *mut *mut cef_dictionary_value_t,
         use_default_window: *mut c_int
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let window_info = WindowInfo::from_ptr_mut_unchecked(window_info);
-        let local_client_ptr = *client;
-        let mut local_client = Client::from_ptr(local_client_ptr);
-        let settings = BrowserSettings::from_ptr_mut_unchecked(settings);
-        let local_extra_info_ptr = *extra_info;
-        let mut local_extra_info = DictionaryValue::from_ptr(local_extra_info_ptr);
-        let mut local_use_default_window = *use_default_window != 0;
-
-        this.0.on_before_dev_tools_popup(
-            browser,
-            window_info,
-            &mut local_client,
-            settings,
-            &mut local_extra_info,
-            &mut local_use_default_window
-        );
-
-        // Same horrible pointer comparison dance here. :^(
-        *client = local_client
-            .map(|c| match c.as_ptr() == local_client_ptr {
-                true => local_client_ptr,
-                false => c.into_raw()
-            })
-            .unwrap_or_else(null_mut);
-
-        *extra_info = local_extra_info
-            .map(|e| match e.as_ptr() == local_extra_info_ptr {
-                true => local_extra_info_ptr,
-                false => e.into_raw()
-            })
-            .unwrap_or_else(null_mut);
-
-        *use_default_window = local_use_default_window as c_int;
+        catch_unwind_or(
+            "LifeSpanHandlerWrapper::c_on_before_dev_tools_popup",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+                let window_info = WindowInfo::from_ptr_mut_unchecked(window_info);
+                let local_client_ptr = *client;
+                let mut local_client = Client::from_ptr(local_client_ptr);
+                let settings = BrowserSettings::from_ptr_mut_unchecked(settings);
+                let local_extra_info_ptr = *extra_info;
+                let mut local_extra_info = DictionaryValue::from_ptr(local_extra_info_ptr);
+                let mut local_use_default_window = *use_default_window != 0;
+
+                this.0.on_before_dev_tools_popup(
+                    browser,
+                    window_info,
+                    &mut local_client,
+                    settings,
+                    &mut local_extra_info,
+                    &mut local_use_default_window
+                );
+
+                // Same horrible pointer comparison dance here. :^(
+                *client = local_client
+                    .map(|c| match c.as_ptr() == local_client_ptr {
+                        true => local_client_ptr,
+                        false => c.into_raw()
+                    })
+                    .unwrap_or_else(null_mut);
+
+                *extra_info = local_extra_info
+                    .map(|e| match e.as_ptr() == local_extra_info_ptr {
+                        true => local_extra_info_ptr,
+                        false => e.into_raw()
+                    })
+                    .unwrap_or_else(null_mut);
+
+                *use_default_window = local_use_default_window as c_int;
+            }
+        )
     }
 
     /// Called after a new browser is created. It is now safe to begin performing
@@ -449,10 +501,16 @@ impl LifeSpanHandlerWrapper {
         this: *mut cef_life_span_handler_t,
         browser: *mut cef_browser_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
+        catch_unwind_or("LifeSpanHandlerWrapper::c_on_after_created", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+
+            browser_registry::register_browser(&browser);
 
-        this.0.on_after_created(browser);
+            this.0.on_after_created(browser);
+        })
     }
 
     /// Called when a browser has received a request to close. This may result
@@ -547,10 +605,16 @@ impl LifeSpanHandlerWrapper {
         this: *mut cef_life_span_handler_t,
         browser: *mut cef_browser_t
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
+        catch_unwind_or("LifeSpanHandlerWrapper::c_do_close", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
 
-        this.0.do_close(browser) as c_int
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+
+            browser_registry::mark_browser_closing(&browser);
+
+            this.0.do_close(browser) as c_int
+        })
     }
 
     /// Called just before a browser is destroyed. Release all references to the
@@ -567,14 +631,28 @@ impl LifeSpanHandlerWrapper {
         this: *mut cef_life_span_handler_t,
         browser: *mut cef_browser_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
+        catch_unwind_or("LifeSpanHandlerWrapper::c_on_before_close", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+
+            #[cfg(feature = "v8")]
+            {
+                function_binding::release_browser_bindings(&browser);
+                event_bus::release_browser_event_bus(&browser);
+                binary_bridge::release_browser_binary_bridge(&browser);
+            }
+
+            this.0
+                .on_before_close(browser.clone());
 
-        this.0.on_before_close(browser);
+            browser_registry::unregister_browser(&browser);
+        })
     }
 }
 
-impl Wrappable for LifeSpanHandlerWrapper {
+impl<C: LifeSpanHandlerCallbacks> Wrappable for LifeSpanHandlerWrapper<C> {
     type Cef = cef_life_span_handler_t;
 
     /// Converts this to a smart pointer.
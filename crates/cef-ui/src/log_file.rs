@@ -0,0 +1,397 @@
+use crate::{LogSeverity, Result, Settings};
+use anyhow::anyhow;
+use std::{
+    env::current_exe,
+    fs::File,
+    io::{ErrorKind, Read, Seek, SeekFrom},
+    path::{Path, PathBuf}
+};
+use tracing::{debug, error, info, warn};
+
+/// A single entry from the CEF debug log. Each line is written as
+/// "[pid:tid:MMDD/HHMMSS.uuuuuu:SEVERITY:file.cc(line)] message" where the
+/// leading items depend on Settings::log_items. Items that were not written
+/// are None.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogRecord {
+    pub process_id: Option<u32>,
+    pub thread_id:  Option<u32>,
+    pub timestamp:  Option<String>,
+    pub tick_count: Option<u64>,
+    pub severity:   LogSeverity,
+    pub file:       String,
+    pub line:       u32,
+    pub message:    String
+}
+
+impl LogRecord {
+    /// Parses a single log line. Returns None if |line| does not start with a
+    /// log header, which is the case for continuation lines of multi-line
+    /// messages.
+    pub fn parse(line: &str) -> Option<Self> {
+        let rest = line.strip_prefix('[')?;
+        let end = rest.find(']')?;
+        let header = &rest[..end];
+        let message = rest[end + 1..]
+            .strip_prefix(' ')
+            .unwrap_or(&rest[end + 1..]);
+
+        // The last two items are always the severity and the source location.
+        let mut items: Vec<&str> = header.split(':').collect();
+        let location = items.pop()?;
+        let severity = parse_severity(items.pop()?)?;
+        let (file, line) = parse_location(location)?;
+
+        // The remaining items are, in order, the process id, thread id,
+        // timestamp and tick count, any of which may have been omitted. The
+        // timestamp is the only one that is not a plain number.
+        let mut record = Self {
+            process_id: None,
+            thread_id: None,
+            timestamp: None,
+            tick_count: None,
+            severity,
+            file: file.to_string(),
+            line,
+            message: message.to_string()
+        };
+
+        for item in items {
+            if item.contains('/') {
+                record.timestamp = Some(item.to_string());
+            } else if record.timestamp.is_some() {
+                record.tick_count = Some(item.parse().ok()?);
+            } else if record.process_id.is_none() {
+                record.process_id = Some(item.parse().ok()?);
+            } else if record.thread_id.is_none() {
+                record.thread_id = Some(item.parse().ok()?);
+            } else {
+                record.tick_count = Some(item.parse().ok()?);
+            }
+        }
+
+        Some(record)
+    }
+
+    /// Parses the full contents of a log. Lines without a log header are
+    /// appended to the message of the preceding record. Such lines at the start
+    /// of |text| are returned as a record with Default severity and no source
+    /// location.
+    pub fn parse_all(text: &str) -> Vec<Self> {
+        let mut records: Vec<Self> = Vec::new();
+
+        for line in text.lines() {
+            if line.is_empty() {
+                continue;
+            }
+
+            match (Self::parse(line), records.last_mut()) {
+                (Some(record), _) => records.push(record),
+                (None, Some(last)) => {
+                    last.message.push('\n');
+                    last.message.push_str(line);
+                },
+                (None, None) => records.push(Self::orphan(line))
+            }
+        }
+
+        records
+    }
+
+    /// Emits this record as a `tracing` event with the `cef_ui::log` target.
+    pub fn emit(&self) {
+        let pid = self.process_id.unwrap_or_default();
+        let file = self.file.as_str();
+        let line = self.line;
+        let message = self.message.as_str();
+
+        match self.severity {
+            LogSeverity::Verbose => debug!(target: "cef_ui::log", pid, file, line, "{}", message),
+            LogSeverity::Default | LogSeverity::Info => {
+                info!(target: "cef_ui::log", pid, file, line, "{}", message)
+            },
+            LogSeverity::Warning => warn!(target: "cef_ui::log", pid, file, line, "{}", message),
+            LogSeverity::Error | LogSeverity::Fatal => {
+                error!(target: "cef_ui::log", pid, file, line, "{}", message)
+            },
+            LogSeverity::Disable => {}
+        }
+    }
+
+    /// Creates a record for a line that has no log header.
+    fn orphan(line: &str) -> Self {
+        Self {
+            process_id: None,
+            thread_id:  None,
+            timestamp:  None,
+            tick_count: None,
+            severity:   LogSeverity::Default,
+            file:       String::new(),
+            line:       0,
+            message:    line.to_string()
+        }
+    }
+}
+
+/// Incrementally reads the CEF debug log, returning the records appended since
+/// the previous read. This is useful for surfacing the output of helper
+/// processes that crash before they can report anything themselves.
+#[derive(Debug)]
+pub struct LogTail {
+    path:    PathBuf,
+    offset:  u64,
+    partial: Vec<u8>
+}
+
+impl LogTail {
+    /// Tails the log at |path| from its beginning.
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path:    path.to_path_buf(),
+            offset:  0,
+            partial: Vec::new()
+        }
+    }
+
+    /// Tails the log at |path|, skipping anything that has already been written.
+    pub fn from_end(path: &Path) -> Result<Self> {
+        let mut tail = Self::new(path);
+
+        tail.offset = match path.metadata() {
+            Ok(metadata) => metadata.len(),
+            Err(e) if e.kind() == ErrorKind::NotFound => 0,
+            Err(e) => return Err(e.into())
+        };
+
+        Ok(tail)
+    }
+
+    /// Tails the log that CEF will write to for |settings|, skipping anything
+    /// that has already been written.
+    pub fn from_settings(settings: &Settings) -> Result<Self> {
+        Self::from_end(&log_file_path(settings)?)
+    }
+
+    /// Returns the path of the log being tailed.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the records appended to the log since the last call. A missing
+    /// log yields no records. If the log was truncated it is read again from the
+    /// beginning. Incomplete trailing lines are held back until they are
+    /// finished.
+    pub fn read(&mut self) -> Result<Vec<LogRecord>> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into())
+        };
+
+        if file.metadata()?.len() < self.offset {
+            self.offset = 0;
+            self.partial.clear();
+        }
+
+        file.seek(SeekFrom::Start(self.offset))?;
+
+        let read = file.read_to_end(&mut self.partial)?;
+
+        self.offset += read as u64;
+
+        let complete = match self
+            .partial
+            .iter()
+            .rposition(|&b| b == b'\n')
+        {
+            Some(index) => index + 1,
+            None => return Ok(Vec::new())
+        };
+        let lines: Vec<u8> = self
+            .partial
+            .drain(..complete)
+            .collect();
+
+        Ok(LogRecord::parse_all(&String::from_utf8_lossy(&lines)))
+    }
+}
+
+/// Returns the path that CEF writes its debug log to for |settings|. This is
+/// Settings::log_file if set, and otherwise the platform default.
+pub fn log_file_path(settings: &Settings) -> Result<PathBuf> {
+    if let Some(path) = settings.get_log_file() {
+        return Ok(path);
+    }
+
+    let exe = current_exe()?;
+
+    if cfg!(target_os = "macos") {
+        let home = std::env::var_os("HOME").ok_or_else(|| anyhow!("HOME is not set."))?;
+        let name = exe
+            .file_stem()
+            .ok_or_else(|| anyhow!("Failed to get the executable name."))?
+            .to_string_lossy();
+
+        Ok(PathBuf::from(home)
+            .join("Library/Logs")
+            .join(format!("{}_debug.log", name)))
+    } else {
+        let dir = exe
+            .parent()
+            .ok_or_else(|| anyhow!("Failed to get the executable directory."))?;
+
+        Ok(dir.join("debug.log"))
+    }
+}
+
+/// Parses a severity item such as "INFO" or "VERBOSE1".
+fn parse_severity(value: &str) -> Option<LogSeverity> {
+    match value {
+        "INFO" => Some(LogSeverity::Info),
+        "WARNING" => Some(LogSeverity::Warning),
+        "ERROR" => Some(LogSeverity::Error),
+        "FATAL" => Some(LogSeverity::Fatal),
+        value if value.starts_with("VERBOSE") => Some(LogSeverity::Verbose),
+        _ => None
+    }
+}
+
+/// Parses a source location such as "browser.cc(123)".
+fn parse_location(value: &str) -> Option<(&str, u32)> {
+    let (file, line) = value
+        .strip_suffix(')')?
+        .rsplit_once('(')?;
+
+    Some((file, line.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(
+        process_id: Option<u32>,
+        thread_id: Option<u32>,
+        timestamp: Option<&str>,
+        tick_count: Option<u64>,
+        severity: LogSeverity,
+        message: &str
+    ) -> LogRecord {
+        LogRecord {
+            process_id,
+            thread_id,
+            timestamp: timestamp.map(String::from),
+            tick_count,
+            severity,
+            file: "browser.cc".to_string(),
+            line: 42,
+            message: message.to_string()
+        }
+    }
+
+    #[test]
+    fn parse_all_items() {
+        assert_eq!(
+            LogRecord::parse("[123:456:0102/030405.678901:12345:ERROR:browser.cc(42)] Failed"),
+            Some(record(
+                Some(123),
+                Some(456),
+                Some("0102/030405.678901"),
+                Some(12345),
+                LogSeverity::Error,
+                "Failed"
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_optional_items() {
+        assert_eq!(
+            LogRecord::parse("[WARNING:browser.cc(42)] Slow"),
+            Some(record(None, None, None, None, LogSeverity::Warning, "Slow"))
+        );
+        assert_eq!(
+            LogRecord::parse("[123:456:INFO:browser.cc(42)] Started"),
+            Some(record(
+                Some(123),
+                Some(456),
+                None,
+                None,
+                LogSeverity::Info,
+                "Started"
+            ))
+        );
+        assert_eq!(
+            LogRecord::parse("[123:0102/030405.678901:INFO:browser.cc(42)] Started"),
+            Some(record(
+                Some(123),
+                None,
+                Some("0102/030405.678901"),
+                None,
+                LogSeverity::Info,
+                "Started"
+            ))
+        );
+        assert_eq!(
+            LogRecord::parse("[0102/030405.678901:12345:VERBOSE1:browser.cc(42)] Detail"),
+            Some(record(
+                None,
+                None,
+                Some("0102/030405.678901"),
+                Some(12345),
+                LogSeverity::Verbose,
+                "Detail"
+            ))
+        );
+        assert_eq!(
+            LogRecord::parse("[FATAL:browser.cc(42)]"),
+            Some(record(None, None, None, None, LogSeverity::Fatal, ""))
+        );
+    }
+
+    #[test]
+    fn parse_garbage() {
+        assert_eq!(LogRecord::parse(""), None);
+        assert_eq!(LogRecord::parse("Failed"), None);
+        assert_eq!(LogRecord::parse("[INFO:browser.cc(42) Unterminated"), None);
+        assert_eq!(LogRecord::parse("[]"), None);
+        assert_eq!(LogRecord::parse("[browser.cc(42)] Missing severity"), None);
+        assert_eq!(
+            LogRecord::parse("[NOTICE:browser.cc(42)] Unknown severity"),
+            None
+        );
+        assert_eq!(LogRecord::parse("[INFO:browser.cc] Missing line"), None);
+        assert_eq!(LogRecord::parse("[INFO:browser.cc(x)] Invalid line"), None);
+        assert_eq!(
+            LogRecord::parse("[abc:INFO:browser.cc(42)] Invalid pid"),
+            None
+        );
+        assert_eq!(LogRecord::parse(" [INFO:browser.cc(42)] Indented"), None);
+    }
+
+    #[test]
+    fn parse_all_multi_line() {
+        let text = "Orphan\n\
+                    [INFO:browser.cc(42)] First\n\
+                    continued\n\
+                    \n\
+                    [ERROR:browser.cc(42)] Second\n";
+
+        assert_eq!(
+            LogRecord::parse_all(text),
+            vec![
+                LogRecord::orphan("Orphan"),
+                record(
+                    None,
+                    None,
+                    None,
+                    None,
+                    LogSeverity::Info,
+                    "First\ncontinued"
+                ),
+                record(None, None, None, None, LogSeverity::Error, "Second")
+            ]
+        );
+        assert_eq!(LogRecord::parse_all(""), vec![]);
+    }
+}
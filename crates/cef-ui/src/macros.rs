@@ -1,34 +1,107 @@
-use std::fmt::{Display, Formatter};
-
-#[derive(Debug)]
-pub enum FfiError {
-    MissingFunctionPointer(&'static str)
-}
-
-impl Display for FfiError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match *self {
-            FfiError::MissingFunctionPointer(name) => {
-                write!(f, "Missing function pointer: {}", name)
-            }
-        }
-    }
-}
-
-impl std::error::Error for FfiError {}
-
 /// A macro to simplify the process of calling of calling CEF functions.
 /// This will check if the function pointer is available and return an
-/// error if it is not.
+/// Error::NullFunctionPointer if it is not.
 #[macro_export]
 macro_rules! try_c {
     ($self:ident, $name:ident, $body:block) => {{
-        use anyhow::anyhow;
-
         $self
             .0
             .$name
-            .ok_or_else(|| anyhow!($crate::FfiError::MissingFunctionPointer(stringify!($name))))
+            .ok_or_else(|| $crate::Error::NullFunctionPointer {
+                type_name: $crate::short_type_name(&$self),
+                method:    stringify!($name)
+            })
             .and_then(|$name| unsafe { $body })
     }};
 }
+
+/// A macro that asserts, in debug builds only, that the current thread is the
+/// specified CEF thread. Used by the handler wrappers to catch callbacks that
+/// are invoked on a different thread than the one CEF documents.
+#[macro_export]
+macro_rules! debug_assert_currently_on {
+    ($thread_id:expr) => {{
+        debug_assert!(
+            $crate::currently_on($thread_id),
+            "Expected to be called on the {:?} thread.",
+            $thread_id
+        );
+    }};
+}
+
+/// A macro that generates a closure-based builder for a callbacks trait. Each
+/// listed trait function gets a field holding an optional closure and a
+/// builder function of the same name that sets it. Functions without a
+/// closure fall back to the given default value (or do nothing), which must
+/// match the trait's own default implementation. Trait functions marked as
+/// unsafe are listed with an `[unsafe]` prefix. Attributes (such as #[cfg])
+/// placed on a listed function are applied to everything generated for it.
+#[macro_export]
+macro_rules! callbacks_builder {
+    (
+        $(#[$meta:meta])*
+        $name:ident: $callbacks:ident => $handler:ident {
+            $(
+                $(#[$attr:meta])*
+                $([$unsafe:ident])? fn $method:ident($($arg:ident: $ty:ty),* $(,)?)
+                    $(-> $ret:ty = $default:expr)?;
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Default)]
+        pub struct $name {
+            $($(#[$attr])* $method: Option<Box<dyn FnMut($($ty),*) $(-> $ret)? + Send + Sync + 'static>>),*
+        }
+
+        impl $name {
+            /// Creates a new builder with every callback set to its default.
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            $(
+                $(#[$attr])*
+                #[doc = concat!(
+                    "Sets the closure invoked for `",
+                    stringify!($callbacks),
+                    "::",
+                    stringify!($method),
+                    "`."
+                )]
+                pub fn $method(
+                    mut self,
+                    f: impl FnMut($($ty),*) $(-> $ret)? + Send + Sync + 'static
+                ) -> Self {
+                    self.$method = Some(Box::new(f));
+                    self
+                }
+            )*
+
+            #[doc = concat!("Builds the ", stringify!($handler), ".")]
+            pub fn build(self) -> $handler {
+                $handler::new(self)
+            }
+        }
+
+        impl $callbacks for $name {
+            $(
+                $(#[$attr])*
+                $($unsafe)? fn $method(&mut self, $($arg: $ty),*) $(-> $ret)? {
+                    match self.$method.as_mut() {
+                        Some(f) => f($($arg),*),
+                        None => $crate::callbacks_builder!(@default $($default)?)
+                    }
+                }
+            )*
+        }
+    };
+
+    (@default) => {
+        ()
+    };
+
+    (@default $default:expr) => {
+        $default
+    };
+}
@@ -0,0 +1,136 @@
+use crate::{Context, Result};
+use parking_lot::Mutex;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant}
+};
+
+/// The maximum amount of time to wait between calls to do_message_loop_work,
+/// even if CEF has not requested any work. This matches the 30fps cadence used
+/// by the cefclient sample application and prevents starvation when a
+/// scheduling request is lost.
+pub const MAX_MESSAGE_PUMP_DELAY: Duration = Duration::from_millis(1000 / 30);
+
+/// Implement this trait to wake up an existing event loop (winit, GTK, a custom
+/// game loop, etc.) when CEF requests message pump work. This may be called on
+/// any thread. The |delay| is the amount of time after which
+/// MessagePump::do_work_if_due should be called on the main thread.
+pub trait MessagePumpWaker: Send + Sync + 'static {
+    fn wake(&self, delay: Duration);
+}
+
+impl<F> MessagePumpWaker for F
+where
+    F: Fn(Duration) + Send + Sync + 'static
+{
+    fn wake(&self, delay: Duration) {
+        self(delay)
+    }
+}
+
+/// Scheduling state shared between the browser process handler and the
+/// application event loop.
+struct MessagePumpState {
+    deadline:  Option<Instant>,
+    last_work: Instant
+}
+
+/// Adapter for driving CEF from an existing event loop when
+/// cef_settings_t.external_message_pump is enabled. Forward
+/// BrowserProcessHandlerCallbacks::on_schedule_message_pump_work to
+/// on_schedule_message_pump_work and call do_work_if_due from the main
+/// application thread whenever the event loop wakes up. Cloning is cheap and
+/// all clones share the same schedule.
+#[derive(Clone)]
+pub struct MessagePump {
+    state: Arc<Mutex<MessagePumpState>>,
+    waker: Option<Arc<dyn MessagePumpWaker>>
+}
+
+impl MessagePump {
+    /// Create a message pump that relies on the event loop polling
+    /// time_until_next_work to decide when to wake up.
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(MessagePumpState {
+                deadline:  None,
+                last_work: Instant::now()
+            })),
+            waker: None
+        }
+    }
+
+    /// Create a message pump that notifies |waker| whenever CEF schedules work.
+    pub fn with_waker<W: MessagePumpWaker>(waker: W) -> Self {
+        Self {
+            waker: Some(Arc::new(waker)),
+            ..Self::new()
+        }
+    }
+
+    /// Call this from BrowserProcessHandlerCallbacks::on_schedule_message_pump_work.
+    /// If |delay_ms| is <= 0 work is requested as soon as possible, otherwise it
+    /// replaces any currently pending request.
+    pub fn on_schedule_message_pump_work(&self, delay_ms: i64) {
+        let delay = Duration::from_millis(delay_ms.max(0) as u64).min(MAX_MESSAGE_PUMP_DELAY);
+
+        self.state.lock().deadline = Some(Instant::now() + delay);
+
+        if let Some(waker) = &self.waker {
+            waker.wake(delay);
+        }
+    }
+
+    /// Returns the instant at which do_message_loop_work should next be called.
+    pub fn next_work(&self) -> Instant {
+        let state = self.state.lock();
+        let fallback = state.last_work + MAX_MESSAGE_PUMP_DELAY;
+
+        match state.deadline {
+            Some(deadline) => deadline.min(fallback),
+            None => fallback
+        }
+    }
+
+    /// Returns how long the event loop may sleep before calling do_work_if_due.
+    pub fn time_until_next_work(&self) -> Duration {
+        self.next_work()
+            .saturating_duration_since(Instant::now())
+    }
+
+    /// Returns true if CEF message pump work is due.
+    pub fn is_work_due(&self) -> bool {
+        self.next_work() <= Instant::now()
+    }
+
+    /// Performs a single iteration of CEF message loop processing if it is due.
+    /// Returns true if work was performed. This must be called on the main
+    /// application thread.
+    pub fn do_work_if_due(&self, context: &Context) -> Result<bool> {
+        match self.is_work_due() {
+            true => self.do_work(context).map(|_| true),
+            false => Ok(false)
+        }
+    }
+
+    /// Performs a single iteration of CEF message loop processing immediately.
+    /// This must be called on the main application thread.
+    pub fn do_work(&self, context: &Context) -> Result<()> {
+        {
+            let mut state = self.state.lock();
+
+            state.deadline = None;
+            state.last_work = Instant::now();
+        }
+
+        // The lock must not be held here because CEF may
+        // reentrantly call on_schedule_message_pump_work.
+        context.do_message_loop_work()
+    }
+}
+
+impl Default for MessagePump {
+    fn default() -> Self {
+        Self::new()
+    }
+}
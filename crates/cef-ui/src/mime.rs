@@ -0,0 +1,42 @@
+use crate::{CefString, CefStringList};
+use cef_ui_sys::{cef_get_extensions_for_mime_type, cef_get_mime_type};
+use std::path::Path;
+
+/// Returns the mime type for the specified file extension, without the leading
+/// dot (e.g. "html"), or None if unknown.
+pub fn get_mime_type(extension: &str) -> Option<String> {
+    let extension = CefString::new(extension.trim_start_matches('.'));
+    let mime_type = CefString::from_userfree_ptr(unsafe { cef_get_mime_type(extension.as_ptr()) })
+        .map(String::from)
+        .unwrap_or_default();
+
+    match mime_type.is_empty() {
+        true => None,
+        false => Some(mime_type)
+    }
+}
+
+/// Returns the mime type for the extension of |path|, or None if the path has
+/// no extension or the extension is unknown.
+pub fn get_mime_type_for_path<P: AsRef<Path>>(path: P) -> Option<String> {
+    let extension = path
+        .as_ref()
+        .extension()?
+        .to_str()?;
+
+    get_mime_type(extension)
+}
+
+/// Returns the extensions associated with the given mime type, without the
+/// leading dot. This should be passed in lower case. There could be multiple
+/// extensions for a given mime type, like "html,htm" for "text/html", or
+/// "txt,text,html,..." for "text/*". Any existing elements in the result are
+/// ignored.
+pub fn get_extensions_for_mime_type(mime_type: &str) -> Vec<String> {
+    let mime_type = CefString::new(mime_type);
+    let mut extensions = CefStringList::new();
+
+    unsafe { cef_get_extensions_for_mime_type(mime_type.as_ptr(), extensions.as_mut_ptr()) };
+
+    extensions.into()
+}
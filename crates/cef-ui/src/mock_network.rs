@@ -0,0 +1,359 @@
+use crate::{
+    post_delayed_task, Browser, Callback, ErrorCode, Frame, Request, RequestContextHandler,
+    RequestContextHandlerCallbacks, ResourceHandler, ResourceHandlerCallbacks, ResourceReadBuffer,
+    ResourceReadCallback, ResourceRequestHandler, ResourceRequestHandlerCallbacks, Response,
+    Result, ThreadId
+};
+use bytes::{Buf, Bytes};
+use parking_lot::Mutex;
+use std::{sync::Arc, time::Duration};
+use tracing::error;
+
+/// A canned response served by MockNetwork.
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    status:    i32,
+    mime_type: String,
+    headers:   Vec<(String, String)>,
+    body:      Bytes,
+    delay:     Option<Duration>,
+    error:     Option<ErrorCode>
+}
+
+impl MockResponse {
+    /// An empty response with |status|.
+    pub fn new(status: i32) -> Self {
+        Self {
+            status,
+            mime_type: String::from("text/plain"),
+            headers: Vec::new(),
+            body: Bytes::new(),
+            delay: None,
+            error: None
+        }
+    }
+
+    /// A 200 response with a JSON body.
+    pub fn json(body: &str) -> Self {
+        Self::new(200)
+            .mime_type("application/json")
+            .body(body.to_string())
+    }
+
+    /// A request that fails with |error|, e.g. ErrorCode::ConnectionRefused,
+    /// instead of receiving a response.
+    pub fn error(error: ErrorCode) -> Self {
+        Self {
+            error: Some(error),
+            ..Self::new(0)
+        }
+    }
+
+    /// Sets the MIME type, text/plain by default.
+    pub fn mime_type(mut self, mime_type: &str) -> Self {
+        self.mime_type = mime_type.to_string();
+        self
+    }
+
+    /// Adds a response header.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers
+            .push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sets the body.
+    pub fn body(mut self, body: impl Into<Bytes>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Delays the response (or the error) by |delay|, e.g. to test loading
+    /// states and timeouts.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+}
+
+/// A request answered by MockNetwork.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockedRequest {
+    pub method: String,
+    pub url:    String
+}
+
+type Responder = Arc<dyn Fn(&Request) -> MockResponse + Send + Sync + 'static>;
+
+/// A registered URL pattern.
+struct MockRoute {
+    method:    Option<String>,
+    pattern:   String,
+    responder: Responder
+}
+
+#[derive(Default)]
+struct MockState {
+    routes:          Vec<MockRoute>,
+    requests:        Vec<MockedRequest>,
+    block_unmatched: bool
+}
+
+/// Answers requests matching registered URL patterns with canned responses,
+/// delays or errors, so that integration tests can run pages against
+/// deterministic fake backends. Patterns match the whole URL and may contain *
+/// wildcards, e.g. "https://api.example.com/users/*". When several patterns
+/// match, the most recently registered one wins. Clones share the same routes.
+///
+/// Return resource_request_handler from
+/// RequestHandlerCallbacks::get_resource_request_handler to mock requests of a
+/// single browser, or use request_context_handler with
+/// RequestContext::new_shared to mock every request of a request context.
+#[derive(Clone, Default)]
+pub struct MockNetwork(Arc<Mutex<MockState>>);
+
+impl MockNetwork {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answers requests whose URL matches |pattern| with |response|.
+    pub fn mock(&self, pattern: &str, response: MockResponse) {
+        self.route(None, pattern, Arc::new(move |_| response.clone()));
+    }
+
+    /// Answers requests with |method| (e.g. "POST") whose URL matches |pattern|
+    /// with |response|.
+    pub fn mock_method(&self, method: &str, pattern: &str, response: MockResponse) {
+        self.route(
+            Some(method.to_ascii_uppercase()),
+            pattern,
+            Arc::new(move |_| response.clone())
+        );
+    }
+
+    /// Answers requests whose URL matches |pattern| with the response returned
+    /// by |f|. |f| is called on the IO thread.
+    pub fn mock_with(
+        &self,
+        pattern: &str,
+        f: impl Fn(&Request) -> MockResponse + Send + Sync + 'static
+    ) {
+        self.route(None, pattern, Arc::new(f));
+    }
+
+    /// If true, requests that match no pattern fail with
+    /// ErrorCode::BlockedByClient instead of reaching the network.
+    pub fn block_unmatched(&self, block_unmatched: bool) {
+        self.0.lock().block_unmatched = block_unmatched;
+    }
+
+    /// Removes all patterns.
+    pub fn clear(&self) {
+        self.0.lock().routes.clear();
+    }
+
+    /// Returns and forgets the requests answered so far.
+    pub fn take_requests(&self) -> Vec<MockedRequest> {
+        std::mem::take(&mut self.0.lock().requests)
+    }
+
+    /// Returns a resource request handler that answers mocked requests.
+    pub fn resource_request_handler(&self) -> ResourceRequestHandler {
+        ResourceRequestHandler::new(self.clone())
+    }
+
+    /// Returns a request context handler that answers mocked requests made
+    /// through the request context.
+    pub fn request_context_handler(&self) -> RequestContextHandler {
+        RequestContextHandler::new(self.clone())
+    }
+
+    /// Returns the response for |request|, if it is mocked.
+    pub fn respond(&self, request: &Request) -> Result<Option<MockResponse>> {
+        let url = request.get_url()?;
+        let method = request.get_method()?;
+        let (responder, block_unmatched) = {
+            let state = self.0.lock();
+            let responder = state
+                .routes
+                .iter()
+                .rev()
+                .find(|route| {
+                    route
+                        .method
+                        .as_ref()
+                        .is_none_or(|route_method| route_method.eq_ignore_ascii_case(&method))
+                        && matches_pattern(&route.pattern, &url)
+                })
+                .map(|route| route.responder.clone());
+
+            (responder, state.block_unmatched)
+        };
+
+        // The responder is called without the lock, so that it may change the
+        // routes.
+        let response = match responder {
+            Some(responder) => responder(request),
+            None => match block_unmatched {
+                true => MockResponse::error(ErrorCode::BlockedByClient),
+                false => return Ok(None)
+            }
+        };
+
+        self.0
+            .lock()
+            .requests
+            .push(MockedRequest { method, url });
+
+        Ok(Some(response))
+    }
+
+    /// Registers a route.
+    fn route(&self, method: Option<String>, pattern: &str, responder: Responder) {
+        self.0
+            .lock()
+            .routes
+            .push(MockRoute {
+                method,
+                pattern: pattern.to_string(),
+                responder
+            });
+    }
+}
+
+impl ResourceRequestHandlerCallbacks for MockNetwork {
+    fn get_resource_handler(
+        &mut self,
+        _browser: Option<Browser>,
+        _frame: Option<Frame>,
+        request: Request
+    ) -> Option<ResourceHandler> {
+        match self.respond(&request) {
+            Ok(response) => response.map(|response| ResourceHandler::new(MockBody::new(response))),
+            Err(e) => {
+                error!("Failed to mock request: {}", e);
+
+                None
+            }
+        }
+    }
+}
+
+impl RequestContextHandlerCallbacks for MockNetwork {
+    fn get_resource_request_handler(
+        &mut self,
+        _browser: Option<Browser>,
+        _frame: Option<Frame>,
+        _request: Request,
+        _is_navigation: bool,
+        _is_download: bool,
+        _request_initiator: &str,
+        _disable_default_handling: &mut bool
+    ) -> Option<ResourceRequestHandler> {
+        Some(self.resource_request_handler())
+    }
+}
+
+/// Serves a MockResponse.
+struct MockBody {
+    response: MockResponse,
+    body:     Bytes
+}
+
+impl MockBody {
+    fn new(response: MockResponse) -> Self {
+        let body = response.body.clone();
+
+        Self { response, body }
+    }
+
+    /// Sets the status and headers on |response|.
+    fn apply(&self, response: &Response) -> Result<()> {
+        if let Some(error) = self.response.error {
+            return response.set_error(error);
+        }
+
+        response.set_status(self.response.status)?;
+        response.set_mime_type(&self.response.mime_type)?;
+
+        for (name, value) in &self.response.headers {
+            response.set_header_by_name(name, value, true)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ResourceHandlerCallbacks for MockBody {
+    fn open(&mut self, _request: Request, handle_request: &mut bool, callback: Callback) -> bool {
+        match self.response.delay {
+            Some(delay) => {
+                post_delayed_task(ThreadId::Io, delay, move || {
+                    let _ = callback.cont();
+                });
+
+                *handle_request = false;
+            },
+            None => *handle_request = true
+        }
+
+        true
+    }
+
+    fn get_response_headers(
+        &mut self,
+        response: Response,
+        response_length: &mut i64,
+        _redirect_url: &mut Option<String>
+    ) {
+        if let Err(e) = self.apply(&response) {
+            error!("Failed to set mock response headers: {}", e);
+        }
+
+        *response_length = match self.response.error {
+            Some(_) => 0,
+            None => self.body.len() as i64
+        };
+    }
+
+    fn read(
+        &mut self,
+        mut data_out: ResourceReadBuffer,
+        bytes_read: &mut i32,
+        _callback: ResourceReadCallback
+    ) -> bool {
+        let count = data_out.write(&self.body);
+
+        self.body.advance(count);
+        *bytes_read = count as i32;
+
+        count > 0
+    }
+}
+
+/// Returns true if |url| matches |pattern|, where * matches any sequence of
+/// characters.
+fn matches_pattern(pattern: &str, url: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+
+    let Some(mut rest) = url.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false
+        }
+    }
+
+    rest.len() >= last.len() && rest.ends_with(last)
+}
@@ -1,7 +1,7 @@
 use crate::{
-    ref_counted_ptr, try_c, CefString, CefTime, RefCountedPtr, SslStatus, Wrappable, Wrapped
+    ref_counted_ptr, try_c, unwind::catch_unwind_or, CefString, CefTime, RefCountedPtr, Result,
+    SslStatus, TransitionType, Wrappable, Wrapped
 };
-use anyhow::Result;
 use cef_ui_sys::{cef_navigation_entry_t, cef_navigation_entry_visitor_t};
 use chrono::{DateTime, Utc};
 use parking_lot::Mutex;
@@ -55,14 +55,13 @@ impl NavigationEntry {
         })
     }
 
-    // TODO: Fix this!
-
-    //     ///
-    //     /// Returns the transition type which indicates what the user did to move to
-    //     /// this page from the previous page.
-    //     ///
-    //     cef_transition_type_t(CEF_CALLBACK* get_transition_type)(
-    //     struct _cef_navigation_entry_t* self);
+    /// Returns the transition type which indicates what the user did to move to
+    /// this page from the previous page.
+    pub fn get_transition_type(&self) -> Result<TransitionType> {
+        try_c!(self, get_transition_type, {
+            Ok(get_transition_type(self.as_ptr()).into())
+        })
+    }
 
     /// Returns true (1) if this navigation includes post data.
     pub fn has_post_data(&self) -> Result<bool> {
@@ -123,11 +122,11 @@ impl NavigationEntryVisitor {
 }
 
 // /// Translates CEF -> Rust callbacks.
-struct NavigationEntryVisitorWrapper(Mutex<Box<dyn NavigationEntryVisitorCallbacks>>);
+struct NavigationEntryVisitorWrapper<C: NavigationEntryVisitorCallbacks>(Mutex<C>);
 
-impl NavigationEntryVisitorWrapper {
-    pub fn new<C: NavigationEntryVisitorCallbacks>(callbacks: C) -> Self {
-        Self(Mutex::new(Box::new(callbacks)))
+impl<C: NavigationEntryVisitorCallbacks> NavigationEntryVisitorWrapper<C> {
+    pub fn new(callbacks: C) -> Self {
+        Self(Mutex::new(callbacks))
     }
 
     /// Method that will be executed. Do not keep a reference to |entry| outside
@@ -142,16 +141,18 @@ impl NavigationEntryVisitorWrapper {
         index: c_int,
         total: c_int
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let entry = NavigationEntry::from_ptr_unchecked(entry);
+        catch_unwind_or("NavigationEntryVisitorWrapper::c_visit", 0, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let entry = NavigationEntry::from_ptr_unchecked(entry);
 
-        this.0
-            .lock()
-            .visit(entry, current != 0, index as usize, total as usize) as c_int
+            this.0
+                .lock()
+                .visit(entry, current != 0, index as usize, total as usize) as c_int
+        })
     }
 }
 
-impl Wrappable for NavigationEntryVisitorWrapper {
+impl<C: NavigationEntryVisitorCallbacks> Wrappable for NavigationEntryVisitorWrapper<C> {
     type Cef = cef_navigation_entry_visitor_t;
 
     /// Converts this to a smart pointer.
@@ -159,7 +160,7 @@ impl Wrappable for NavigationEntryVisitorWrapper {
         RefCountedPtr::wrap(
             cef_navigation_entry_visitor_t {
                 base:  unsafe { zeroed() },
-                visit: Some(NavigationEntryVisitorWrapper::c_visit)
+                visit: Some(Self::c_visit)
             },
             self
         )
@@ -0,0 +1,677 @@
+use crate::{
+    Browser, Callback, ErrorCode, Frame, Request, RequestContextHandler,
+    RequestContextHandlerCallbacks, ResourceHandler, ResourceRequestHandler,
+    ResourceRequestHandlerCallbacks, Response, Result, ReturnValue, UrlRequestStatus
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    fs::write,
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant}
+};
+use tracing::error;
+
+/// A request captured by NetworkRecorder. A redirected request produces one
+/// entry per hop, each with the redirect_url of the next one.
+#[derive(Debug, Clone)]
+pub struct NetworkEntry {
+    /// When the request was started.
+    pub started: DateTime<Utc>,
+
+    pub method:          String,
+    pub url:             String,
+    pub request_headers: Vec<(String, String)>,
+
+    /// The size of the request body, not counting uploaded files.
+    pub request_body_size: i64,
+
+    pub status:           i32,
+    pub status_text:      String,
+    pub mime_type:        String,
+    pub response_headers: Vec<(String, String)>,
+
+    /// The URL the request was redirected to, if any.
+    pub redirect_url: Option<String>,
+
+    /// The load completion status.
+    pub load_status: UrlRequestStatus,
+
+    /// The network error, if the load failed.
+    pub error: Option<ErrorCode>,
+
+    /// The number of response bytes actually read, or -1 if it is unknown.
+    pub received_content_length: i64,
+
+    /// The time from the start of the request until the response headers
+    /// arrived.
+    pub wait: Duration,
+
+    /// The time spent reading the response body.
+    pub receive: Duration
+}
+
+impl NetworkEntry {
+    /// Creates an entry for a request that was just started.
+    fn new(request: &Request) -> Result<Self> {
+        let post_data = request.get_post_data()?;
+        let request_body_size = match post_data {
+            Some(post_data) => post_data
+                .get_elements()?
+                .iter()
+                .try_fold(0, |size, element| {
+                    element
+                        .get_bytes_count()
+                        .map(|count| size + count as i64)
+                })?,
+            None => 0
+        };
+
+        Ok(Self {
+            started: Utc::now(),
+            method: request.get_method()?,
+            url: request.get_url()?,
+            request_headers: flatten_headers(request.get_header_map()?),
+            request_body_size,
+            status: 0,
+            status_text: String::new(),
+            mime_type: String::new(),
+            response_headers: Vec::new(),
+            redirect_url: None,
+            load_status: UrlRequestStatus::Unknown,
+            error: None,
+            received_content_length: -1,
+            wait: Duration::ZERO,
+            receive: Duration::ZERO
+        })
+    }
+
+    /// Copies the status and headers of |response|.
+    fn set_response(&mut self, response: &Response) -> Result<()> {
+        self.status = response.get_status()?;
+        self.status_text = response.get_status_text()?;
+        self.mime_type = response.get_mime_type()?;
+        self.response_headers = flatten_headers(response.get_header_map()?);
+
+        Ok(())
+    }
+
+    /// Converts this to a HAR entry.
+    fn to_har(&self) -> Value {
+        let headers = |headers: &[(String, String)]| {
+            headers
+                .iter()
+                .map(|(name, value)| json!({ "name": name, "value": value }))
+                .collect::<Vec<_>>()
+        };
+        let millis = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        let query = self
+            .url
+            .split_once('?')
+            .map(|(_, query)| {
+                query
+                    .split('#')
+                    .next()
+                    .unwrap_or_default()
+                    .split('&')
+                    .filter(|pair| !pair.is_empty())
+                    .map(|pair| {
+                        let (name, value) = pair
+                            .split_once('=')
+                            .unwrap_or((pair, ""));
+
+                        json!({ "name": name, "value": value })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let mut entry = json!({
+            "startedDateTime": self.started.to_rfc3339_opts(SecondsFormat::Millis, true),
+            "time": millis(self.wait + self.receive),
+            "request": {
+                "method": self.method,
+                "url": self.url,
+                "httpVersion": "",
+                "cookies": [],
+                "headers": headers(&self.request_headers),
+                "queryString": query,
+                "headersSize": -1,
+                "bodySize": self.request_body_size
+            },
+            "response": {
+                "status": self.status,
+                "statusText": self.status_text,
+                "httpVersion": "",
+                "cookies": [],
+                "headers": headers(&self.response_headers),
+                "content": {
+                    "size": self.received_content_length.max(0),
+                    "mimeType": self.mime_type
+                },
+                "redirectURL": self.redirect_url.as_deref().unwrap_or_default(),
+                "headersSize": -1,
+                "bodySize": self.received_content_length
+            },
+            "cache": {},
+            "timings": {
+                "send": 0,
+                "wait": millis(self.wait),
+                "receive": millis(self.receive)
+            }
+        });
+
+        if let Some(error) = self.error {
+            entry["_error"] = Value::String(format!("{:?}", error));
+        }
+
+        entry
+    }
+}
+
+/// A request that has not completed yet.
+struct PendingEntry {
+    entry:    NetworkEntry,
+    started:  Instant,
+    response: Option<Instant>
+}
+
+impl PendingEntry {
+    fn new(request: &Request) -> Result<Self> {
+        Ok(Self {
+            entry:    NetworkEntry::new(request)?,
+            started:  Instant::now(),
+            response: None
+        })
+    }
+
+    /// Records the arrival of the response headers.
+    fn on_response(&mut self, response: &Response) -> Result<()> {
+        let now = Instant::now();
+
+        self.entry.set_response(response)?;
+        self.entry.wait = now - self.started;
+        self.response = Some(now);
+
+        Ok(())
+    }
+
+    /// Finishes the entry.
+    fn finish(mut self) -> NetworkEntry {
+        let now = Instant::now();
+
+        match self.response {
+            Some(response) => self.entry.receive = now - response,
+            None => self.entry.wait = now - self.started
+        }
+
+        self.entry
+    }
+}
+
+struct RecorderState {
+    recording: bool,
+    pending:   HashMap<u64, PendingEntry>,
+    entries:   Vec<NetworkEntry>
+}
+
+/// Records the requests, redirects and responses of the browsers it is
+/// installed on, with their timing and sizes, and exports them as a HAR file
+/// that can be inspected with browser devtools or other HAR viewers. Response
+/// bodies are not recorded. Clones share the same recording.
+///
+/// Return resource_request_handler (or a handler made by wrap) from
+/// RequestHandlerCallbacks::get_resource_request_handler to record a single
+/// browser, or use request_context_handler with RequestContext::new_shared to
+/// record every request of a request context.
+#[derive(Clone)]
+pub struct NetworkRecorder(Arc<Mutex<RecorderState>>);
+
+impl NetworkRecorder {
+    /// Creates a recorder that starts recording immediately.
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(RecorderState {
+            recording: true,
+            pending:   HashMap::new(),
+            entries:   Vec::new()
+        })))
+    }
+
+    /// Pauses or resumes recording. Requests in flight when recording is paused
+    /// are still completed.
+    pub fn set_recording(&self, recording: bool) {
+        self.0.lock().recording = recording;
+    }
+
+    /// Returns true if requests are being recorded.
+    pub fn is_recording(&self) -> bool {
+        self.0.lock().recording
+    }
+
+    /// Returns the completed entries in the order the requests completed.
+    pub fn entries(&self) -> Vec<NetworkEntry> {
+        self.0.lock().entries.clone()
+    }
+
+    /// Forgets the completed entries.
+    pub fn clear(&self) {
+        self.0.lock().entries.clear();
+    }
+
+    /// Returns the completed entries as a HAR 1.2 document.
+    pub fn to_har(&self) -> Value {
+        let entries: Vec<Value> = self
+            .0
+            .lock()
+            .entries
+            .iter()
+            .map(NetworkEntry::to_har)
+            .collect();
+
+        json!({
+            "log": {
+                "version": "1.2",
+                "creator": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION")
+                },
+                "pages": [],
+                "entries": entries
+            }
+        })
+    }
+
+    /// Writes the completed entries to |path| as a HAR file.
+    pub fn export_har(&self, path: &Path) -> Result<()> {
+        let har = serde_json::to_string_pretty(&self.to_har()).map_err(anyhow::Error::from)?;
+
+        write(path, har)?;
+
+        Ok(())
+    }
+
+    /// Returns a resource request handler that only records requests.
+    pub fn resource_request_handler(&self) -> ResourceRequestHandler {
+        self.wrap(NoopHandler)
+    }
+
+    /// Returns a resource request handler that records requests and forwards
+    /// every callback to |delegate|.
+    pub fn wrap<C: ResourceRequestHandlerCallbacks>(&self, delegate: C) -> ResourceRequestHandler {
+        ResourceRequestHandler::new(RecordingHandler {
+            recorder: self.clone(),
+            delegate
+        })
+    }
+
+    /// Returns a request context handler that records the requests made through
+    /// the request context.
+    pub fn request_context_handler(&self) -> RequestContextHandler {
+        RequestContextHandler::new(self.clone())
+    }
+
+    /// Starts an entry for |request|, if recording.
+    fn on_start(&self, request: &Request) -> Result<()> {
+        if !self.is_recording() {
+            return Ok(());
+        }
+
+        let id = request.get_identifier()?;
+        let pending = PendingEntry::new(request)?;
+
+        self.0
+            .lock()
+            .pending
+            .insert(id, pending);
+
+        Ok(())
+    }
+
+    /// Records the response headers of |request|.
+    fn on_response(&self, request: &Request, response: &Response) -> Result<()> {
+        let id = request.get_identifier()?;
+
+        match self.0.lock().pending.get_mut(&id) {
+            Some(pending) => pending.on_response(response),
+            None => Ok(())
+        }
+    }
+
+    /// Finishes the entry of |request| with the redirect to |new_url|. The new
+    /// hop is recorded by the next on_before_resource_load.
+    fn on_redirect(&self, request: &Request, response: &Response, new_url: &str) -> Result<()> {
+        let id = request.get_identifier()?;
+        let mut state = self.0.lock();
+
+        if let Some(mut pending) = state.pending.remove(&id) {
+            pending.on_response(response)?;
+            pending.entry.redirect_url = Some(new_url.to_string());
+            pending.entry.load_status = UrlRequestStatus::Success;
+
+            let entry = pending.finish();
+
+            state.entries.push(entry);
+        }
+
+        Ok(())
+    }
+
+    /// Finishes the entry of |request|.
+    fn on_complete(
+        &self,
+        request: &Request,
+        response: &Response,
+        status: UrlRequestStatus,
+        received_content_length: i64
+    ) -> Result<()> {
+        let id = request.get_identifier()?;
+        let mut state = self.0.lock();
+
+        if let Some(mut pending) = state.pending.remove(&id) {
+            if pending.response.is_none() {
+                pending.on_response(response)?;
+            }
+
+            pending.entry.load_status = status;
+            pending
+                .entry
+                .received_content_length = received_content_length;
+            pending.entry.error = match response.get_error()? {
+                ErrorCode::None => None,
+                error => Some(error)
+            };
+
+            let entry = pending.finish();
+
+            state.entries.push(entry);
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for NetworkRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RequestContextHandlerCallbacks for NetworkRecorder {
+    fn get_resource_request_handler(
+        &mut self,
+        _browser: Option<Browser>,
+        _frame: Option<Frame>,
+        _request: Request,
+        _is_navigation: bool,
+        _is_download: bool,
+        _request_initiator: &str,
+        _disable_default_handling: &mut bool
+    ) -> Option<ResourceRequestHandler> {
+        Some(self.resource_request_handler())
+    }
+}
+
+/// A resource request handler that does nothing.
+struct NoopHandler;
+
+impl ResourceRequestHandlerCallbacks for NoopHandler {}
+
+/// Records requests and forwards the callbacks to the delegate.
+struct RecordingHandler<C: ResourceRequestHandlerCallbacks> {
+    recorder: NetworkRecorder,
+    delegate: C
+}
+
+impl<C: ResourceRequestHandlerCallbacks> ResourceRequestHandlerCallbacks for RecordingHandler<C> {
+    fn on_before_resource_load(
+        &mut self,
+        browser: Option<Browser>,
+        frame: Option<Frame>,
+        request: Request,
+        callback: Callback
+    ) -> ReturnValue {
+        let value = self
+            .delegate
+            .on_before_resource_load(browser, frame, request.clone(), callback);
+
+        // The delegate may have changed the request, so it is recorded after.
+        if let Err(e) = self.recorder.on_start(&request) {
+            error!("Failed to record request: {}", e);
+        }
+
+        value
+    }
+
+    fn get_resource_handler(
+        &mut self,
+        browser: Option<Browser>,
+        frame: Option<Frame>,
+        request: Request
+    ) -> Option<ResourceHandler> {
+        self.delegate
+            .get_resource_handler(browser, frame, request)
+    }
+
+    fn on_resource_redirect(
+        &mut self,
+        browser: Option<Browser>,
+        frame: Option<Frame>,
+        request: Request,
+        response: Response,
+        new_url: &mut String
+    ) {
+        self.delegate.on_resource_redirect(
+            browser,
+            frame,
+            request.clone(),
+            response.clone(),
+            new_url
+        );
+
+        if let Err(e) = self
+            .recorder
+            .on_redirect(&request, &response, new_url)
+        {
+            error!("Failed to record redirect: {}", e);
+        }
+    }
+
+    fn on_resource_response(
+        &mut self,
+        browser: Option<Browser>,
+        frame: Option<Frame>,
+        request: Request,
+        response: Response
+    ) -> bool {
+        if let Err(e) = self
+            .recorder
+            .on_response(&request, &response)
+        {
+            error!("Failed to record response: {}", e);
+        }
+
+        self.delegate
+            .on_resource_response(browser, frame, request, response)
+    }
+
+    fn on_resource_load_complete(
+        &mut self,
+        browser: Option<Browser>,
+        frame: Option<Frame>,
+        request: Request,
+        response: Response,
+        status: UrlRequestStatus,
+        received_content_length: i64
+    ) {
+        if let Err(e) =
+            self.recorder
+                .on_complete(&request, &response, status, received_content_length)
+        {
+            error!("Failed to record response: {}", e);
+        }
+
+        self.delegate
+            .on_resource_load_complete(
+                browser,
+                frame,
+                request,
+                response,
+                status,
+                received_content_length
+            );
+    }
+}
+
+/// Flattens a header map into name and value pairs.
+fn flatten_headers(headers: HashMap<String, Vec<String>>) -> Vec<(String, String)> {
+    let mut headers: Vec<(String, String)> = headers
+        .into_iter()
+        .flat_map(|(name, values)| {
+            values
+                .into_iter()
+                .map(move |value| (name.clone(), value))
+        })
+        .collect();
+
+    headers.sort_by(|a, b| a.0.cmp(&b.0));
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> NetworkEntry {
+        NetworkEntry {
+            started:                 DateTime::from_timestamp(1_700_000_000, 123_000_000).unwrap(),
+            method:                  "POST".to_string(),
+            url:                     "https://example.com/api?a=1&b&&c=x=y#top".to_string(),
+            request_headers:         vec![("Accept".to_string(), "*/*".to_string())],
+            request_body_size:       12,
+            status:                  200,
+            status_text:             "OK".to_string(),
+            mime_type:               "application/json".to_string(),
+            response_headers:        vec![
+                ("Set-Cookie".to_string(), "a=1".to_string()),
+                ("Set-Cookie".to_string(), "b=2".to_string())
+            ],
+            redirect_url:            None,
+            load_status:             UrlRequestStatus::Success,
+            error:                   None,
+            received_content_length: 345,
+            wait:                    Duration::from_millis(20),
+            receive:                 Duration::from_millis(5)
+        }
+    }
+
+    #[test]
+    fn entry_to_har() {
+        assert_eq!(
+            entry().to_har(),
+            json!({
+                "startedDateTime": "2023-11-14T22:13:20.123Z",
+                "time": 25.0,
+                "request": {
+                    "method": "POST",
+                    "url": "https://example.com/api?a=1&b&&c=x=y#top",
+                    "httpVersion": "",
+                    "cookies": [],
+                    "headers": [{ "name": "Accept", "value": "*/*" }],
+                    "queryString": [
+                        { "name": "a", "value": "1" },
+                        { "name": "b", "value": "" },
+                        { "name": "c", "value": "x=y" }
+                    ],
+                    "headersSize": -1,
+                    "bodySize": 12
+                },
+                "response": {
+                    "status": 200,
+                    "statusText": "OK",
+                    "httpVersion": "",
+                    "cookies": [],
+                    "headers": [
+                        { "name": "Set-Cookie", "value": "a=1" },
+                        { "name": "Set-Cookie", "value": "b=2" }
+                    ],
+                    "content": { "size": 345, "mimeType": "application/json" },
+                    "redirectURL": "",
+                    "headersSize": -1,
+                    "bodySize": 345
+                },
+                "cache": {},
+                "timings": { "send": 0, "wait": 20.0, "receive": 5.0 }
+            })
+        );
+    }
+
+    #[test]
+    fn entry_to_har_redirect_and_error() {
+        let mut redirect = entry();
+
+        redirect.url = "https://example.com/".to_string();
+        redirect.redirect_url = Some("https://example.com/next".to_string());
+
+        let har = redirect.to_har();
+
+        assert_eq!(har["request"]["queryString"], json!([]));
+        assert_eq!(har["response"]["redirectURL"], "https://example.com/next");
+        assert_eq!(har.get("_error"), None);
+
+        let mut failed = entry();
+
+        failed.error = Some(ErrorCode::Failed);
+        failed.received_content_length = -1;
+
+        let har = failed.to_har();
+
+        assert_eq!(har["_error"], "Failed");
+        assert_eq!(har["response"]["content"]["size"], 0);
+        assert_eq!(har["response"]["bodySize"], -1);
+    }
+
+    #[test]
+    fn recorder_to_har() {
+        let recorder = NetworkRecorder::new();
+
+        recorder.0.lock().entries = vec![entry(), entry()];
+
+        let har = recorder.to_har();
+
+        assert_eq!(har["log"]["version"], "1.2");
+        assert_eq!(har["log"]["creator"]["name"], env!("CARGO_PKG_NAME"));
+        assert_eq!(har["log"]["pages"], json!([]));
+        assert_eq!(
+            har["log"]["entries"],
+            json!([entry().to_har(), entry().to_har()])
+        );
+
+        recorder.clear();
+
+        assert_eq!(recorder.to_har()["log"]["entries"], json!([]));
+    }
+
+    #[test]
+    fn flatten_headers_sorted() {
+        let headers = HashMap::from([
+            ("Vary".to_string(), vec!["Accept".to_string()]),
+            (
+                "Cache-Control".to_string(),
+                vec!["no-cache".to_string(), "no-store".to_string()]
+            ),
+            ("Empty".to_string(), vec![])
+        ]);
+
+        assert_eq!(
+            flatten_headers(headers),
+            vec![
+                ("Cache-Control".to_string(), "no-cache".to_string()),
+                ("Cache-Control".to_string(), "no-store".to_string()),
+                ("Vary".to_string(), "Accept".to_string())
+            ]
+        );
+    }
+}
@@ -0,0 +1,437 @@
+use crate::{AlphaType, PaintElementType, Rect};
+
+/// The number of bytes per BGRA pixel.
+const BYTES_PER_PIXEL: usize = 4;
+
+/// A borrowed BGRA frame. Rows are tightly packed, so the stride is always
+/// width * 4 bytes. Pixels use premultiplied alpha; a windowless browser with a
+/// transparent background color leaves the areas the page does not paint fully
+/// transparent.
+#[derive(Debug, Clone, Copy)]
+pub struct OsrFrame<'a> {
+    pub data:   &'a [u8],
+    pub width:  usize,
+    pub height: usize
+}
+
+impl OsrFrame<'_> {
+    /// The number of bytes per row.
+    pub fn stride(&self) -> usize {
+        self.width * BYTES_PER_PIXEL
+    }
+
+    /// Returns true if every pixel is fully opaque.
+    pub fn is_opaque(&self) -> bool {
+        self.data
+            .as_chunks::<BYTES_PER_PIXEL>()
+            .0
+            .iter()
+            .all(|pixel| pixel[3] == 0xFF)
+    }
+
+    /// Converts the frame to RGBA with |alpha_type|, e.g. for uploading to a
+    /// texture. Opaque sets every alpha component to 0xFF and Postmultiplied
+    /// divides the color components by the alpha component.
+    pub fn to_rgba(&self, alpha_type: AlphaType) -> Vec<u8> {
+        let mut rgba = Vec::with_capacity(self.data.len());
+
+        for &[b, g, r, a] in self
+            .data
+            .as_chunks::<BYTES_PER_PIXEL>()
+            .0
+        {
+            match alpha_type {
+                AlphaType::Opaque => rgba.extend_from_slice(&[r, g, b, 0xFF]),
+                AlphaType::Premultiplied => rgba.extend_from_slice(&[r, g, b, a]),
+                AlphaType::Postmultiplied => {
+                    let unmultiply = |value: u8| match a {
+                        0 => 0,
+                        a => ((value as u32 * 255 + a as u32 / 2) / a as u32).min(255) as u8
+                    };
+
+                    rgba.extend_from_slice(&[unmultiply(r), unmultiply(g), unmultiply(b), a]);
+                }
+            }
+        }
+
+        rgba
+    }
+}
+
+/// A single BGRA image.
+#[derive(Debug, Default)]
+struct Image {
+    data:   Vec<u8>,
+    width:  usize,
+    height: usize
+}
+
+impl Image {
+    /// Resizes the image, discarding its contents if the size changed.
+    fn resize(&mut self, width: usize, height: usize) -> bool {
+        if self.width == width && self.height == height {
+            return false;
+        }
+
+        self.data = vec![0; width * height * BYTES_PER_PIXEL];
+        self.width = width;
+        self.height = height;
+
+        true
+    }
+
+    /// Copies |rect| from |source|, which must have the same size.
+    fn copy_rect(&mut self, source: &[u8], rect: &Rect) {
+        let bounds = Rect::new(0, 0, self.width as i32, self.height as i32);
+        let rect = rect.intersect(&bounds);
+
+        if rect.is_empty() {
+            return;
+        }
+
+        let stride = self.width * BYTES_PER_PIXEL;
+        let start = rect.x as usize * BYTES_PER_PIXEL;
+        let end = start + rect.width as usize * BYTES_PER_PIXEL;
+
+        for row in rect.y as usize..(rect.y + rect.height) as usize {
+            let offset = row * stride;
+
+            self.data[offset + start..offset + end]
+                .copy_from_slice(&source[offset + start..offset + end]);
+        }
+    }
+
+    /// Borrows the image as a frame.
+    fn frame(&self) -> OsrFrame<'_> {
+        OsrFrame {
+            data:   &self.data,
+            width:  self.width,
+            height: self.height
+        }
+    }
+}
+
+/// Keeps a double-buffered copy of the view painted by
+/// RenderHandlerCallbacks::on_paint so it can be presented with softbuffer,
+/// pixels or a texture upload. Only the dirty rectangles are copied on each
+/// paint, and the damage accumulated since the last take_damage is reported so
+/// that partial uploads are possible. Popup widgets are kept separately and can
+/// be drawn on top of the view at popup_rect.
+#[derive(Debug, Default)]
+pub struct OsrSurface {
+    buffers:    [Image; 2],
+    front:      usize,
+    stale:      Vec<Rect>,
+    damage:     Vec<Rect>,
+    popup:      Image,
+    popup_rect: Option<Rect>
+}
+
+impl OsrSurface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call this from RenderHandlerCallbacks::on_paint. |buffer| contains the
+    /// whole element in BGRA format.
+    pub fn on_paint(
+        &mut self,
+        paint_element_type: PaintElementType,
+        dirty_rects: &[Rect],
+        buffer: &[u8],
+        width: usize,
+        height: usize
+    ) {
+        if buffer.len() < width * height * BYTES_PER_PIXEL {
+            return;
+        }
+
+        match paint_element_type {
+            PaintElementType::View => self.paint_view(dirty_rects, buffer, width, height),
+            PaintElementType::Popup => {
+                self.popup.resize(width, height);
+                self.popup
+                    .data
+                    .copy_from_slice(&buffer[..width * height * BYTES_PER_PIXEL]);
+
+                if let Some(rect) = self.popup_rect {
+                    self.damage.push(rect);
+                }
+            }
+        }
+    }
+
+    /// Call this from RenderHandlerCallbacks::on_popup_size. |rect| is in view
+    /// coordinates.
+    pub fn on_popup_size(&mut self, rect: &Rect) {
+        if let Some(old) = self.popup_rect.replace(*rect) {
+            self.damage.push(old);
+        }
+
+        self.damage.push(*rect);
+    }
+
+    /// Call this from RenderHandlerCallbacks::on_popup_show.
+    pub fn on_popup_show(&mut self, show: bool) {
+        if !show {
+            if let Some(rect) = self.popup_rect.take() {
+                self.damage.push(rect);
+            }
+
+            self.popup = Image::default();
+        }
+    }
+
+    /// Returns the most recently painted view.
+    pub fn frame(&self) -> OsrFrame<'_> {
+        self.buffers[self.front].frame()
+    }
+
+    /// Returns the view with the popup widget, if one is showing, blended on top
+    /// of it. Transparent areas of the popup keep the view visible.
+    pub fn composite(&self) -> Vec<u8> {
+        let view = self.frame();
+        let mut data = view.data.to_vec();
+
+        if let Some((rect, popup)) = self.popup() {
+            let bounds = Rect::new(0, 0, view.width as i32, view.height as i32);
+            let clipped = rect.intersect(&bounds);
+
+            for y in clipped.y..clipped.y + clipped.height {
+                for x in clipped.x..clipped.x + clipped.width {
+                    let (px, py) = ((x - rect.x) as usize, (y - rect.y) as usize);
+
+                    if px >= popup.width || py >= popup.height {
+                        continue;
+                    }
+
+                    let source = (py * popup.width + px) * BYTES_PER_PIXEL;
+                    let target = (y as usize * view.width + x as usize) * BYTES_PER_PIXEL;
+                    let source = &popup.data[source..source + BYTES_PER_PIXEL];
+                    let inverse = 255 - source[3] as u32;
+
+                    // Source-over blending of premultiplied pixels.
+                    for (target, source) in data[target..target + BYTES_PER_PIXEL]
+                        .iter_mut()
+                        .zip(source)
+                    {
+                        *target = (*source as u32 + (*target as u32 * inverse + 127) / 255) as u8;
+                    }
+                }
+            }
+        }
+
+        data
+    }
+
+    /// Returns the popup widget and where to draw it, if one is showing.
+    pub fn popup(&self) -> Option<(Rect, OsrFrame<'_>)> {
+        match (self.popup_rect, self.popup.data.is_empty()) {
+            (Some(rect), false) => Some((rect, self.popup.frame())),
+            _ => None
+        }
+    }
+
+    /// Returns the regions that changed since the last call, clipped to the
+    /// view. The whole view is reported after a resize.
+    pub fn take_damage(&mut self) -> Vec<Rect> {
+        let front = &self.buffers[self.front];
+        let bounds = Rect::new(0, 0, front.width as i32, front.height as i32);
+
+        self.damage
+            .drain(..)
+            .map(|rect| rect.intersect(&bounds))
+            .filter(|rect| !rect.is_empty())
+            .collect()
+    }
+
+    /// Updates the back buffer and swaps it to the front.
+    fn paint_view(&mut self, dirty_rects: &[Rect], buffer: &[u8], width: usize, height: usize) {
+        let full = Rect::new(0, 0, width as i32, height as i32);
+        let back = &mut self.buffers[1 - self.front];
+
+        // The back buffer is one frame behind, so it also needs the regions
+        // that were copied into the front buffer on the previous paint.
+        if back.resize(width, height) {
+            back.copy_rect(buffer, &full);
+        } else {
+            for rect in self.stale.iter().chain(dirty_rects) {
+                back.copy_rect(buffer, rect);
+            }
+        }
+
+        let resized =
+            self.buffers[self.front].width != width || self.buffers[self.front].height != height;
+
+        self.front = 1 - self.front;
+        self.stale = match resized {
+            true => vec![full],
+            false => dirty_rects.to_vec()
+        };
+
+        match resized {
+            true => {
+                self.damage.clear();
+                self.damage.push(full);
+            },
+            false => self
+                .damage
+                .extend_from_slice(dirty_rects)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A BGRA buffer of |width| x |height| pixels with every byte set to |value|.
+    fn buffer(width: usize, height: usize, value: u8) -> Vec<u8> {
+        vec![value; width * height * BYTES_PER_PIXEL]
+    }
+
+    fn paint(surface: &mut OsrSurface, dirty_rects: &[Rect], buffer: &[u8], width: usize) {
+        let height = buffer.len() / (width * BYTES_PER_PIXEL);
+
+        surface.on_paint(PaintElementType::View, dirty_rects, buffer, width, height);
+    }
+
+    #[test]
+    fn first_paint_damages_view() {
+        let mut surface = OsrSurface::new();
+
+        paint(&mut surface, &[Rect::new(0, 0, 1, 1)], &buffer(4, 2, 1), 4);
+
+        assert_eq!(surface.take_damage(), vec![Rect::new(0, 0, 4, 2)]);
+        assert_eq!(surface.frame().data, &buffer(4, 2, 1)[..]);
+        assert!(surface.take_damage().is_empty());
+    }
+
+    #[test]
+    fn damage_accumulates_and_clips() {
+        let mut surface = OsrSurface::new();
+        let first = Rect::new(0, 0, 1, 1);
+        let second = Rect::new(3, 1, 5, 5);
+
+        paint(&mut surface, &[], &buffer(4, 2, 1), 4);
+        surface.take_damage();
+        paint(&mut surface, &[first], &buffer(4, 2, 2), 4);
+        paint(&mut surface, &[second], &buffer(4, 2, 3), 4);
+
+        assert_eq!(surface.take_damage(), vec![first, Rect::new(3, 1, 1, 1)]);
+    }
+
+    #[test]
+    fn resize_replaces_damage() {
+        let mut surface = OsrSurface::new();
+
+        paint(&mut surface, &[], &buffer(4, 2, 1), 4);
+        paint(&mut surface, &[Rect::new(0, 0, 1, 1)], &buffer(4, 2, 1), 4);
+        paint(&mut surface, &[Rect::new(0, 0, 1, 1)], &buffer(2, 2, 1), 2);
+
+        assert_eq!(surface.take_damage(), vec![Rect::new(0, 0, 2, 2)]);
+        assert_eq!(surface.frame().width, 2);
+        assert_eq!(surface.frame().data, &buffer(2, 2, 1)[..]);
+    }
+
+    #[test]
+    fn partial_paints_keep_both_buffers_current() {
+        let mut surface = OsrSurface::new();
+        let mut view = buffer(2, 1, 0);
+
+        paint(&mut surface, &[], &view, 2);
+        paint(&mut surface, &[], &view, 2);
+
+        // Each paint only updates one of the two pixels.
+        view[..4].fill(1);
+        paint(&mut surface, &[Rect::new(0, 0, 1, 1)], &view, 2);
+        view[4..].fill(2);
+        paint(&mut surface, &[Rect::new(1, 0, 1, 1)], &view, 2);
+
+        assert_eq!(surface.frame().data, &[1, 1, 1, 1, 2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn popup_damage() {
+        let mut surface = OsrSurface::new();
+        let old = Rect::new(0, 0, 1, 1);
+        let new = Rect::new(1, 0, 1, 1);
+
+        paint(&mut surface, &[], &buffer(2, 1, 0), 2);
+        surface.take_damage();
+
+        surface.on_popup_show(true);
+        surface.on_popup_size(&old);
+        surface.on_popup_size(&new);
+        surface.on_paint(PaintElementType::Popup, &[], &buffer(1, 1, 9), 1, 1);
+
+        // Moving the popup damages both its old and its new position.
+        assert_eq!(surface.take_damage(), vec![old, old, new, new]);
+        assert_eq!(
+            surface
+                .popup()
+                .map(|(rect, _)| rect),
+            Some(new)
+        );
+
+        surface.on_popup_show(false);
+
+        assert_eq!(surface.take_damage(), vec![new]);
+        assert!(surface.popup().is_none());
+    }
+
+    #[test]
+    fn composite_blends_popup() {
+        let mut surface = OsrSurface::new();
+
+        paint(&mut surface, &[], &[200, 100, 0, 255, 200, 100, 0, 255], 2);
+        surface.on_popup_size(&Rect::new(1, 0, 4, 4));
+        surface.on_paint(
+            PaintElementType::Popup,
+            &[],
+            &[0, 0, 128, 128, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            2,
+            2
+        );
+
+        assert_eq!(
+            surface.composite(),
+            vec![200, 100, 0, 255, 100, 50, 128, 255]
+        );
+    }
+
+    #[test]
+    fn short_buffer_is_ignored() {
+        let mut surface = OsrSurface::new();
+
+        surface.on_paint(PaintElementType::View, &[], &buffer(2, 1, 1), 2, 2);
+
+        assert!(surface.take_damage().is_empty());
+        assert!(surface.frame().data.is_empty());
+    }
+
+    #[test]
+    fn frame_to_rgba() {
+        let data = [10, 20, 30, 255, 32, 64, 96, 128, 1, 2, 3, 0];
+        let frame = OsrFrame {
+            data:   &data,
+            width:  3,
+            height: 1
+        };
+
+        assert!(!frame.is_opaque());
+        assert_eq!(frame.stride(), 12);
+        assert_eq!(
+            frame.to_rgba(AlphaType::Opaque),
+            vec![30, 20, 10, 255, 96, 64, 32, 255, 3, 2, 1, 255]
+        );
+        assert_eq!(
+            frame.to_rgba(AlphaType::Premultiplied),
+            vec![30, 20, 10, 255, 96, 64, 32, 128, 3, 2, 1, 0]
+        );
+        assert_eq!(
+            frame.to_rgba(AlphaType::Postmultiplied),
+            vec![30, 20, 10, 255, 191, 128, 64, 128, 0, 0, 0, 0]
+        );
+    }
+}
@@ -0,0 +1,448 @@
+use crate::{
+    callbacks_builder, debug_assert_currently_on, ref_counted_ptr, try_c, unwind::catch_unwind_or,
+    Browser, CefString, Frame, RefCountedPtr, Result, ThreadId, Wrappable, Wrapped
+};
+use bitflags::bitflags;
+use cef_ui_sys::{
+    cef_browser_t, cef_frame_t, cef_media_access_callback_t, cef_media_access_permission_types_t,
+    cef_permission_handler_t, cef_permission_prompt_callback_t, cef_permission_request_result_t,
+    cef_permission_request_types_t, cef_string_t
+};
+use std::{ffi::c_int, mem::zeroed};
+
+bitflags! {
+    /// Media access permissions used by OnRequestMediaAccessPermission.
+    #[allow(non_upper_case_globals)]
+    #[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct MediaAccessPermissionTypes: u32 {
+        /// No permission.
+        const None = cef_media_access_permission_types_t::CEF_MEDIA_PERMISSION_NONE as u32;
+
+        /// Device audio capture permission.
+        const DeviceAudioCapture =
+            cef_media_access_permission_types_t::CEF_MEDIA_PERMISSION_DEVICE_AUDIO_CAPTURE as u32;
+
+        /// Device video capture permission.
+        const DeviceVideoCapture =
+            cef_media_access_permission_types_t::CEF_MEDIA_PERMISSION_DEVICE_VIDEO_CAPTURE as u32;
+
+        /// Desktop audio capture permission.
+        const DesktopAudioCapture =
+            cef_media_access_permission_types_t::CEF_MEDIA_PERMISSION_DESKTOP_AUDIO_CAPTURE as u32;
+
+        /// Desktop video capture permission.
+        const DesktopVideoCapture =
+            cef_media_access_permission_types_t::CEF_MEDIA_PERMISSION_DESKTOP_VIDEO_CAPTURE as u32;
+    }
+}
+
+impl From<u32> for MediaAccessPermissionTypes {
+    fn from(value: u32) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&u32> for MediaAccessPermissionTypes {
+    fn from(value: &u32) -> Self {
+        Self::from_bits_truncate(*value)
+    }
+}
+
+impl From<MediaAccessPermissionTypes> for u32 {
+    fn from(value: MediaAccessPermissionTypes) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&MediaAccessPermissionTypes> for u32 {
+    fn from(value: &MediaAccessPermissionTypes) -> Self {
+        value.bits()
+    }
+}
+
+bitflags! {
+    /// Permission types used with OnShowPermissionPrompt. Some types are
+    /// platform-specific or only supported with the Chrome runtime. Should be
+    /// kept in sync with Chromium's permissions::RequestType type.
+    #[allow(non_upper_case_globals)]
+    #[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct PermissionRequestTypes: u32 {
+        const None = cef_permission_request_types_t::CEF_PERMISSION_TYPE_NONE as u32;
+        const AccessibilityEvents =
+            cef_permission_request_types_t::CEF_PERMISSION_TYPE_ACCESSIBILITY_EVENTS as u32;
+        const ArSession = cef_permission_request_types_t::CEF_PERMISSION_TYPE_AR_SESSION as u32;
+        const CameraPanTiltZoom =
+            cef_permission_request_types_t::CEF_PERMISSION_TYPE_CAMERA_PAN_TILT_ZOOM as u32;
+        const CameraStream =
+            cef_permission_request_types_t::CEF_PERMISSION_TYPE_CAMERA_STREAM as u32;
+        const Clipboard = cef_permission_request_types_t::CEF_PERMISSION_TYPE_CLIPBOARD as u32;
+        const TopLevelStorageAccess =
+            cef_permission_request_types_t::CEF_PERMISSION_TYPE_TOP_LEVEL_STORAGE_ACCESS as u32;
+        const DiskQuota = cef_permission_request_types_t::CEF_PERMISSION_TYPE_DISK_QUOTA as u32;
+        const LocalFonts = cef_permission_request_types_t::CEF_PERMISSION_TYPE_LOCAL_FONTS as u32;
+        const Geolocation =
+            cef_permission_request_types_t::CEF_PERMISSION_TYPE_GEOLOCATION as u32;
+        const IdleDetection =
+            cef_permission_request_types_t::CEF_PERMISSION_TYPE_IDLE_DETECTION as u32;
+        const MicStream = cef_permission_request_types_t::CEF_PERMISSION_TYPE_MIC_STREAM as u32;
+        const Midi = cef_permission_request_types_t::CEF_PERMISSION_TYPE_MIDI as u32;
+        const MidiSysex = cef_permission_request_types_t::CEF_PERMISSION_TYPE_MIDI_SYSEX as u32;
+        const MultipleDownloads =
+            cef_permission_request_types_t::CEF_PERMISSION_TYPE_MULTIPLE_DOWNLOADS as u32;
+        const Notifications =
+            cef_permission_request_types_t::CEF_PERMISSION_TYPE_NOTIFICATIONS as u32;
+        const ProtectedMediaIdentifier =
+            cef_permission_request_types_t::CEF_PERMISSION_TYPE_PROTECTED_MEDIA_IDENTIFIER as u32;
+        const RegisterProtocolHandler =
+            cef_permission_request_types_t::CEF_PERMISSION_TYPE_REGISTER_PROTOCOL_HANDLER as u32;
+        const StorageAccess =
+            cef_permission_request_types_t::CEF_PERMISSION_TYPE_STORAGE_ACCESS as u32;
+        const VrSession = cef_permission_request_types_t::CEF_PERMISSION_TYPE_VR_SESSION as u32;
+        const WindowManagement =
+            cef_permission_request_types_t::CEF_PERMISSION_TYPE_WINDOW_MANAGEMENT as u32;
+        const FileSystemAccess =
+            cef_permission_request_types_t::CEF_PERMISSION_TYPE_FILE_SYSTEM_ACCESS as u32;
+    }
+}
+
+impl From<u32> for PermissionRequestTypes {
+    fn from(value: u32) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&u32> for PermissionRequestTypes {
+    fn from(value: &u32) -> Self {
+        Self::from_bits_truncate(*value)
+    }
+}
+
+impl From<PermissionRequestTypes> for u32 {
+    fn from(value: PermissionRequestTypes) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&PermissionRequestTypes> for u32 {
+    fn from(value: &PermissionRequestTypes) -> Self {
+        value.bits()
+    }
+}
+
+/// Permission request results.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PermissionRequestResult {
+    /// Accept the permission request as an explicit user action.
+    Accept,
+
+    /// Deny the permission request as an explicit user action.
+    Deny,
+
+    /// Dismiss the permission request as an explicit user action.
+    Dismiss,
+
+    /// Ignore the permission request. If the prompt remains unhandled (e.g.
+    /// OnShowPermissionPrompt returns false and there is no default permissions
+    /// UI) then any related promises may remain unresolved.
+    Ignore
+}
+
+impl From<cef_permission_request_result_t> for PermissionRequestResult {
+    fn from(value: cef_permission_request_result_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_permission_request_result_t> for PermissionRequestResult {
+    fn from(value: &cef_permission_request_result_t) -> Self {
+        match value {
+            cef_permission_request_result_t::CEF_PERMISSION_RESULT_ACCEPT => Self::Accept,
+            cef_permission_request_result_t::CEF_PERMISSION_RESULT_DENY => Self::Deny,
+            cef_permission_request_result_t::CEF_PERMISSION_RESULT_DISMISS => Self::Dismiss,
+            cef_permission_request_result_t::CEF_PERMISSION_RESULT_IGNORE => Self::Ignore
+        }
+    }
+}
+
+impl From<PermissionRequestResult> for cef_permission_request_result_t {
+    fn from(value: PermissionRequestResult) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&PermissionRequestResult> for cef_permission_request_result_t {
+    fn from(value: &PermissionRequestResult) -> Self {
+        match value {
+            PermissionRequestResult::Accept => Self::CEF_PERMISSION_RESULT_ACCEPT,
+            PermissionRequestResult::Deny => Self::CEF_PERMISSION_RESULT_DENY,
+            PermissionRequestResult::Dismiss => Self::CEF_PERMISSION_RESULT_DISMISS,
+            PermissionRequestResult::Ignore => Self::CEF_PERMISSION_RESULT_IGNORE
+        }
+    }
+}
+
+// Callback structure used for asynchronous continuation of media access
+// permission requests.
+ref_counted_ptr!(MediaAccessCallback, cef_media_access_callback_t);
+
+impl MediaAccessCallback {
+    /// Call to allow or deny media access. If this callback was initiated in
+    /// response to a getUserMedia (indicated by
+    /// CEF_MEDIA_PERMISSION_DEVICE_AUDIO_CAPTURE and/or
+    /// CEF_MEDIA_PERMISSION_DEVICE_VIDEO_CAPTURE being set) then
+    /// |allowed_permissions| must match |required_permissions| passed to
+    /// OnRequestMediaAccessPermission.
+    pub fn cont(&self, allowed_permissions: MediaAccessPermissionTypes) -> Result<()> {
+        try_c!(self, cont, {
+            Ok(cont(self.as_ptr(), allowed_permissions.into()))
+        })
+    }
+
+    /// Cancel the media access request.
+    pub fn cancel(&self) -> Result<()> {
+        try_c!(self, cancel, { Ok(cancel(self.as_ptr())) })
+    }
+}
+
+// Callback structure used for asynchronous continuation of permission prompts.
+ref_counted_ptr!(PermissionPromptCallback, cef_permission_prompt_callback_t);
+
+impl PermissionPromptCallback {
+    /// Complete the permissions request with the specified |result|.
+    pub fn cont(&self, result: PermissionRequestResult) -> Result<()> {
+        try_c!(self, cont, { Ok(cont(self.as_ptr(), result.into())) })
+    }
+}
+
+/// Implement this structure to handle events related to permission requests.
+/// The functions of this structure will be called on the browser process UI
+/// thread.
+pub trait PermissionHandlerCallbacks: Send + Sync + 'static {
+    /// Called when a page requests permission to access media.
+    /// |requesting_origin| is the URL origin requesting permission.
+    /// |requested_permissions| is a combination of values that represent the
+    /// requested permissions. Return true (1) and call
+    /// cef_media_access_callback_t functions either in this function or at a
+    /// later time to continue or cancel the request. Return false (0) to proceed
+    /// with default handling. With the Chrome runtime, default handling will
+    /// display the permission request UI. With the Alloy runtime, default
+    /// handling will deny the request. This function will not be called if the
+    /// "--enable-media-stream" command-line switch is used to grant all
+    /// permissions.
+    fn on_request_media_access_permission(
+        &mut self,
+        _browser: Browser,
+        _frame: Frame,
+        _requesting_origin: String,
+        _requested_permissions: MediaAccessPermissionTypes,
+        _callback: MediaAccessCallback
+    ) -> bool {
+        false
+    }
+
+    /// Called when a page should show a permission prompt. |prompt_id| uniquely
+    /// identifies the prompt. |requesting_origin| is the URL origin requesting
+    /// permission. |requested_permissions| is a combination of values that
+    /// represent the requested permissions. Return true (1) and call
+    /// cef_permission_prompt_callback_t::Continue either in this function or at
+    /// a later time to continue or cancel the request. Return false (0) to
+    /// proceed with default handling. With the Chrome runtime, default handling
+    /// will display the permission prompt UI. With the Alloy runtime, default
+    /// handling is CEF_PERMISSION_RESULT_IGNORE.
+    fn on_show_permission_prompt(
+        &mut self,
+        _browser: Browser,
+        _prompt_id: u64,
+        _requesting_origin: String,
+        _requested_permissions: PermissionRequestTypes,
+        _callback: PermissionPromptCallback
+    ) -> bool {
+        false
+    }
+
+    /// Called when a permission prompt handled via OnShowPermissionPrompt is
+    /// dismissed. |prompt_id| will match the value that was passed to
+    /// OnShowPermissionPrompt. |result| will be the value passed to
+    /// cef_permission_prompt_callback_t::Continue or CEF_PERMISSION_RESULT_IGNORE
+    /// if the dialog was dismissed for other reasons such as navigation, browser
+    /// closure, etc. This function will not be called if OnShowPermissionPrompt
+    /// returned false (0) for |prompt_id|.
+    fn on_dismiss_permission_prompt(
+        &mut self,
+        _browser: Browser,
+        _prompt_id: u64,
+        _result: PermissionRequestResult
+    ) {
+    }
+}
+
+// Implement this structure to handle events related to permission requests.
+// The functions of this structure will be called on the browser process UI
+// thread.
+ref_counted_ptr!(PermissionHandler, cef_permission_handler_t);
+
+impl PermissionHandler {
+    pub fn new<C: PermissionHandlerCallbacks>(delegate: C) -> Self {
+        Self(PermissionHandlerWrapper::new(delegate).wrap())
+    }
+}
+
+callbacks_builder! {
+    /// Builds a PermissionHandler from closures. Callbacks that are not set
+    /// fall back to the default PermissionHandlerCallbacks behavior.
+    PermissionHandlerBuilder: PermissionHandlerCallbacks => PermissionHandler {
+        fn on_request_media_access_permission(
+            browser: Browser,
+            frame: Frame,
+            requesting_origin: String,
+            requested_permissions: MediaAccessPermissionTypes,
+            callback: MediaAccessCallback
+        ) -> bool = false;
+        fn on_show_permission_prompt(
+            browser: Browser,
+            prompt_id: u64,
+            requesting_origin: String,
+            requested_permissions: PermissionRequestTypes,
+            callback: PermissionPromptCallback
+        ) -> bool = false;
+        fn on_dismiss_permission_prompt(
+            browser: Browser,
+            prompt_id: u64,
+            result: PermissionRequestResult
+        );
+    }
+}
+
+/// Translates CEF -> Rust callbacks.
+struct PermissionHandlerWrapper<C: PermissionHandlerCallbacks>(C);
+
+impl<C: PermissionHandlerCallbacks> PermissionHandlerWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
+    }
+
+    /// Called when a page requests permission to access media.
+    /// |requesting_origin| is the URL origin requesting permission.
+    /// |requested_permissions| is a combination of values from
+    /// cef_media_access_permission_types_t that represent the requested
+    /// permissions. Return true (1) and call cef_media_access_callback_t
+    /// functions either in this function or at a later time to continue or
+    /// cancel the request. Return false (0) to proceed with default handling.
+    unsafe extern "C" fn c_on_request_media_access_permission(
+        this: *mut cef_permission_handler_t,
+        browser: *mut cef_browser_t,
+        frame: *mut cef_frame_t,
+        requesting_origin: *const cef_string_t,
+        requested_permissions: u32,
+        callback: *mut cef_media_access_callback_t
+    ) -> c_int {
+        catch_unwind_or(
+            "PermissionHandlerWrapper::c_on_request_media_access_permission",
+            0,
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+                let frame = Frame::from_ptr_unchecked(frame);
+                let requesting_origin: String =
+                    CefString::from_ptr_unchecked(requesting_origin).into();
+                let callback = MediaAccessCallback::from_ptr_unchecked(callback);
+
+                this.0
+                    .on_request_media_access_permission(
+                        browser,
+                        frame,
+                        requesting_origin,
+                        requested_permissions.into(),
+                        callback
+                    ) as c_int
+            }
+        )
+    }
+
+    /// Called when a page should show a permission prompt. |prompt_id| uniquely
+    /// identifies the prompt. |requesting_origin| is the URL origin requesting
+    /// permission. |requested_permissions| is a combination of values from
+    /// cef_permission_request_types_t that represent the requested permissions.
+    /// Return true (1) and call cef_permission_prompt_callback_t::Continue either
+    /// in this function or at a later time to continue or cancel the request.
+    /// Return false (0) to proceed with default handling.
+    unsafe extern "C" fn c_on_show_permission_prompt(
+        this: *mut cef_permission_handler_t,
+        browser: *mut cef_browser_t,
+        prompt_id: u64,
+        requesting_origin: *const cef_string_t,
+        requested_permissions: u32,
+        callback: *mut cef_permission_prompt_callback_t
+    ) -> c_int {
+        catch_unwind_or(
+            "PermissionHandlerWrapper::c_on_show_permission_prompt",
+            0,
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+                let requesting_origin: String =
+                    CefString::from_ptr_unchecked(requesting_origin).into();
+                let callback = PermissionPromptCallback::from_ptr_unchecked(callback);
+
+                this.0.on_show_permission_prompt(
+                    browser,
+                    prompt_id,
+                    requesting_origin,
+                    requested_permissions.into(),
+                    callback
+                ) as c_int
+            }
+        )
+    }
+
+    /// Called when a permission prompt handled via OnShowPermissionPrompt is
+    /// dismissed. |prompt_id| will match the value that was passed to
+    /// OnShowPermissionPrompt. |result| will be the value passed to
+    /// cef_permission_prompt_callback_t::Continue or CEF_PERMISSION_RESULT_IGNORE
+    /// if the dialog was dismissed for other reasons such as navigation, browser
+    /// closure, etc.
+    unsafe extern "C" fn c_on_dismiss_permission_prompt(
+        this: *mut cef_permission_handler_t,
+        browser: *mut cef_browser_t,
+        prompt_id: u64,
+        result: cef_permission_request_result_t
+    ) {
+        catch_unwind_or(
+            "PermissionHandlerWrapper::c_on_dismiss_permission_prompt",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+
+                this.0
+                    .on_dismiss_permission_prompt(browser, prompt_id, result.into());
+            }
+        )
+    }
+}
+
+impl<C: PermissionHandlerCallbacks> Wrappable for PermissionHandlerWrapper<C> {
+    type Cef = cef_permission_handler_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<cef_permission_handler_t> {
+        RefCountedPtr::wrap(
+            cef_permission_handler_t {
+                base: unsafe { zeroed() },
+
+                on_request_media_access_permission: Some(
+                    Self::c_on_request_media_access_permission
+                ),
+                on_show_permission_prompt:          Some(Self::c_on_show_permission_prompt),
+                on_dismiss_permission_prompt:       Some(Self::c_on_dismiss_permission_prompt)
+            },
+            self
+        )
+    }
+}
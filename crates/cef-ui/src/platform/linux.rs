@@ -1,14 +1,17 @@
-use crate::{free_cef_string, CefString, Rect};
-use anyhow::{anyhow, Error, Result};
+use crate::{free_cef_string, CefString, CommandLine, Error, Rect, Result};
 use cef_ui_sys::{
-    cef_event_handle_t, cef_main_args_t, cef_string_t, cef_window_handle_t, cef_window_info_t
+    cef_cursor_handle_t, cef_event_handle_t, cef_main_args_t, cef_string_t, cef_window_handle_t,
+    cef_window_info_t
 };
 use std::{
-    env::args,
+    env::{args, var_os},
     ffi::{c_char, c_int, CString},
     mem::zeroed
 };
 
+/// The command-line switch that selects the Ozone platform.
+const OZONE_PLATFORM_SWITCH: &str = "ozone-platform";
+
 /// Structure representing CefExecuteProcess arguments.
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -74,7 +77,7 @@ impl TryFrom<cef_event_handle_t> for NativeEventHandle {
 
     fn try_from(handle: cef_event_handle_t) -> Result<Self> {
         match handle.is_null() {
-            true => Err(anyhow!("Native event handle is null!")),
+            true => Err(Error::InvalidHandle("NativeEventHandle")),
             false => Ok(Self(handle))
         }
     }
@@ -88,6 +91,103 @@ impl TryFrom<NativeEventHandle> for cef_event_handle_t {
     }
 }
 
+/// Native cursor handle.
+#[derive(Clone)]
+pub struct NativeCursorHandle(cef_cursor_handle_t);
+
+impl TryFrom<cef_cursor_handle_t> for NativeCursorHandle {
+    type Error = Error;
+
+    fn try_from(handle: cef_cursor_handle_t) -> Result<Self> {
+        match handle == 0 {
+            true => Err(Error::InvalidHandle("NativeCursorHandle")),
+            false => Ok(Self(handle))
+        }
+    }
+}
+
+impl TryFrom<NativeCursorHandle> for cef_cursor_handle_t {
+    type Error = Error;
+
+    fn try_from(handle: NativeCursorHandle) -> Result<Self> {
+        Ok(handle.0)
+    }
+}
+
+/// The Ozone platform, i.e. the display server backend, that Chromium uses on
+/// Linux. It is selected with the "--ozone-platform" command-line switch and
+/// defaults to X11, which fails on Wayland-only systems that lack XWayland.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OzonePlatform {
+    /// An X11 server, including XWayland.
+    X11,
+
+    /// A Wayland compositor.
+    Wayland,
+
+    /// No display server. Only windowless browsers can be used.
+    Headless
+}
+
+impl OzonePlatform {
+    /// Returns the platform for the current session based on the DISPLAY and
+    /// WAYLAND_DISPLAY environment variables. X11 is preferred when both are
+    /// available because only X11 supports embedding browsers as child windows.
+    pub fn detect() -> Self {
+        match (var_os("DISPLAY"), var_os("WAYLAND_DISPLAY")) {
+            (Some(_), _) => OzonePlatform::X11,
+            (None, Some(_)) => OzonePlatform::Wayland,
+            (None, None) => OzonePlatform::Headless
+        }
+    }
+
+    /// Returns the platform selected by |command_line|, if any.
+    pub fn from_command_line(command_line: &CommandLine) -> Result<Option<Self>> {
+        let platform = command_line
+            .get_switch_value(OZONE_PLATFORM_SWITCH)?
+            .and_then(|value| Self::from_switch_value(&value));
+
+        Ok(platform)
+    }
+
+    /// Returns the platform with the "--ozone-platform" switch value |value|.
+    pub fn from_switch_value(value: &str) -> Option<Self> {
+        match value {
+            "x11" => Some(OzonePlatform::X11),
+            "wayland" => Some(OzonePlatform::Wayland),
+            "headless" => Some(OzonePlatform::Headless),
+            _ => None
+        }
+    }
+
+    /// Returns the "--ozone-platform" switch value.
+    pub fn as_switch_value(&self) -> &'static str {
+        match self {
+            OzonePlatform::X11 => "x11",
+            OzonePlatform::Wayland => "wayland",
+            OzonePlatform::Headless => "headless"
+        }
+    }
+
+    /// Returns true if browsers can be embedded in native windows with
+    /// WindowInfo::as_child. On Wayland and headless, browsers must be
+    /// created with Views or windowless rendering instead.
+    pub fn supports_child_windows(&self) -> bool {
+        *self == OzonePlatform::X11
+    }
+
+    /// Selects this platform on |command_line| unless the user already chose
+    /// one. Call this from AppCallbacks::on_before_command_line_processing for
+    /// the browser process; secondary processes inherit the switch.
+    pub fn apply_to_command_line(&self, command_line: &CommandLine) -> Result<()> {
+        match command_line.has_switch(OZONE_PLATFORM_SWITCH)? {
+            true => Ok(()),
+            false => command_line
+                .append_switch_with_value(OZONE_PLATFORM_SWITCH, Some(self.as_switch_value()))
+        }
+    }
+}
+
 /// Represents window information.
 #[repr(transparent)]
 pub struct WindowInfo(cef_window_info_t);
@@ -117,6 +217,53 @@ impl WindowInfo {
         unsafe { &mut *(ptr as *mut Self) }
     }
 
+    /// Create the browser as a child window embedded in |parent| (an X11
+    /// window) at the specified |bounds|.
+    pub fn as_child(parent: NativeWindowHandle, bounds: &Rect) -> Self {
+        Self::new()
+            .parent_window(parent)
+            .bounds(bounds)
+    }
+
+    /// Create the browser as a top-level X11 window with the specified
+    /// |window_name| at |bounds|. Window managers may ignore the requested
+    /// position.
+    pub fn as_top_level(window_name: &str, bounds: &Rect) -> Self {
+        Self::new()
+            .window_name(&window_name.to_string())
+            .bounds(bounds)
+    }
+
+    /// Create the browser using windowless (off-screen) rendering. The |parent|
+    /// window, if any, is used to identify monitor info and to act as the parent
+    /// window for dialogs, context menus, etc. Transparent painting is enabled by
+    /// default and can be disabled by setting CefBrowserSettings.background_color
+    /// to an opaque value.
+    pub fn as_windowless(parent: Option<NativeWindowHandle>) -> Self {
+        let info = Self::new().windowless_rendering_enabled(true);
+
+        match parent {
+            Some(parent) => info.parent_window(parent),
+            None => info
+        }
+    }
+
+    /// Create the browser as a child window embedded in |parent| if |platform|
+    /// supports child windows, and windowless otherwise. Windowless browsers
+    /// must be painted by the application through a RenderHandler and require
+    /// Settings::windowless_rendering_enabled, so applications that target
+    /// Wayland should be prepared for both.
+    pub fn as_child_or_windowless(
+        platform: OzonePlatform,
+        parent: Option<NativeWindowHandle>,
+        bounds: &Rect
+    ) -> Self {
+        match (platform.supports_child_windows(), parent) {
+            (true, Some(parent)) => Self::as_child(parent, bounds),
+            _ => Self::as_windowless(None)
+        }
+    }
+
     /// The initial title of the window, to be set when the window is created.
     /// Some layout managers (e.g., Compiz) can look at the window title
     /// in order to decide where to place the window when it is
@@ -182,8 +329,11 @@ impl WindowInfo {
         &self.0
     }
 
-    /// Tries to assign a String to a cef_string_t.
+    /// Tries to assign a String to a cef_string_t, freeing any
+    /// previously assigned value.
     fn set_string(s: &String, cef: &mut cef_string_t) {
+        free_cef_string(cef);
+
         *cef = CefString::new(s.as_str()).into_raw();
     }
 }
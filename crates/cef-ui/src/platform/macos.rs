@@ -1,7 +1,7 @@
-use crate::{free_cef_string, CefString, Rect};
-use anyhow::{anyhow, Error, Result};
+use crate::{free_cef_string, CefString, Error, Rect, Result};
 use cef_ui_sys::{
-    cef_event_handle_t, cef_main_args_t, cef_string_t, cef_window_handle_t, cef_window_info_t
+    cef_cursor_handle_t, cef_event_handle_t, cef_main_args_t, cef_string_t, cef_window_handle_t,
+    cef_window_info_t
 };
 use std::{
     env::args,
@@ -74,7 +74,7 @@ impl TryFrom<cef_event_handle_t> for NativeEventHandle {
 
     fn try_from(handle: cef_event_handle_t) -> Result<Self> {
         match handle.is_null() {
-            true => Err(anyhow!("Native event handle is null!")),
+            true => Err(Error::InvalidHandle("NativeEventHandle")),
             false => Ok(Self(handle))
         }
     }
@@ -88,6 +88,29 @@ impl TryFrom<NativeEventHandle> for cef_event_handle_t {
     }
 }
 
+/// Native cursor handle.
+#[derive(Clone)]
+pub struct NativeCursorHandle(cef_cursor_handle_t);
+
+impl TryFrom<cef_cursor_handle_t> for NativeCursorHandle {
+    type Error = Error;
+
+    fn try_from(handle: cef_cursor_handle_t) -> Result<Self> {
+        match handle.is_null() {
+            true => Err(Error::InvalidHandle("NativeCursorHandle")),
+            false => Ok(Self(handle))
+        }
+    }
+}
+
+impl TryFrom<NativeCursorHandle> for cef_cursor_handle_t {
+    type Error = Error;
+
+    fn try_from(handle: NativeCursorHandle) -> Result<Self> {
+        Ok(handle.0)
+    }
+}
+
 /// Represents window information.
 #[repr(transparent)]
 pub struct WindowInfo(cef_window_info_t);
@@ -117,6 +140,37 @@ impl WindowInfo {
         unsafe { &mut *(ptr as *mut Self) }
     }
 
+    /// Create the browser as a child view embedded in |parent| (an NSView) at
+    /// the specified |bounds|.
+    pub fn as_child(parent: NativeWindowHandle, bounds: &Rect) -> Self {
+        Self::new()
+            .parent_view(parent)
+            .bounds(bounds)
+    }
+
+    /// Create the browser in a new, visible top-level NSWindow with the
+    /// specified |window_name| at |bounds|.
+    pub fn as_top_level(window_name: &str, bounds: &Rect) -> Self {
+        Self::new()
+            .window_name(&window_name.to_string())
+            .bounds(bounds)
+            .hidden(false)
+    }
+
+    /// Create the browser using windowless (off-screen) rendering. The |parent|
+    /// view, if any, is used to identify monitor info and to act as the parent
+    /// view for dialogs, context menus, etc. Transparent painting is enabled by
+    /// default and can be disabled by setting CefBrowserSettings.background_color
+    /// to an opaque value.
+    pub fn as_windowless(parent: Option<NativeWindowHandle>) -> Self {
+        let info = Self::new().windowless_rendering_enabled(true);
+
+        match parent {
+            Some(parent) => info.parent_view(parent),
+            None => info
+        }
+    }
+
     /// Sets the window name.
     pub fn window_name(mut self, value: &String) -> Self {
         Self::set_string(value, &mut self.0.window_name);
@@ -184,8 +238,11 @@ impl WindowInfo {
         &self.0
     }
 
-    /// Tries to assign a String to a cef_string_t.
+    /// Tries to assign a String to a cef_string_t, freeing any
+    /// previously assigned value.
     fn set_string(s: &String, cef: &mut cef_string_t) {
+        free_cef_string(cef);
+
         *cef = CefString::new(s.as_str()).into_raw();
     }
 }
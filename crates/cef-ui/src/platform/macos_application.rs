@@ -0,0 +1,507 @@
+use crate::{unwind::catch_unwind_or, Result};
+use anyhow::anyhow;
+use parking_lot::Mutex;
+use std::{
+    ffi::{c_char, c_void, CStr, CString},
+    mem::transmute,
+    sync::atomic::{AtomicBool, Ordering}
+};
+
+type Id = *mut c_void;
+type Class = *mut c_void;
+type Sel = *mut c_void;
+type Protocol = *mut c_void;
+
+/// The name of the NSApplication subclass that is registered with the
+/// Objective-C runtime.
+const APPLICATION_CLASS: &[u8] = b"CefUiApplication\0";
+
+/// The protocols that CEF expects the shared application to conform to.
+const APPLICATION_PROTOCOLS: [&[u8]; 3] =
+    [b"CrAppProtocol\0", b"CrAppControlProtocol\0", b"CefAppProtocol\0"];
+
+/// NSEventModifierFlagOption.
+const MODIFIER_OPTION: usize = 1 << 19;
+
+/// NSEventModifierFlagCommand.
+const MODIFIER_COMMAND: usize = 1 << 20;
+
+/// Set while the shared application is dispatching an event in sendEvent:.
+static HANDLING_SEND_EVENT: AtomicBool = AtomicBool::new(false);
+
+/// Called instead of the default terminate: implementation, if set.
+static TERMINATE_HANDLER: Mutex<Option<Box<dyn FnMut() + Send + 'static>>> = Mutex::new(None);
+
+#[repr(C)]
+struct ObjcSuper {
+    receiver:    Id,
+    super_class: Class
+}
+
+#[link(name = "objc")]
+extern "C" {
+    fn objc_getClass(name: *const c_char) -> Class;
+    fn objc_allocateClassPair(superclass: Class, name: *const c_char, extra_bytes: usize)
+        -> Class;
+    fn objc_registerClassPair(class: Class);
+    fn objc_getProtocol(name: *const c_char) -> Protocol;
+    fn objc_allocateProtocol(name: *const c_char) -> Protocol;
+    fn objc_registerProtocol(protocol: Protocol);
+    fn class_addMethod(class: Class, name: Sel, imp: *const c_void, types: *const c_char)
+        -> bool;
+    fn class_addProtocol(class: Class, protocol: Protocol) -> bool;
+    fn sel_registerName(name: *const c_char) -> Sel;
+    fn objc_msgSend();
+    fn objc_msgSendSuper();
+}
+
+#[link(name = "AppKit", kind = "framework")]
+extern "C" {
+    static NSApp: Id;
+}
+
+/// How the application appears in the Dock and whether it can be activated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ActivationPolicy {
+    /// An ordinary application with a Dock icon and a menu bar.
+    Regular,
+
+    /// An application without a Dock icon that can still show windows, e.g. a
+    /// menu bar extra.
+    Accessory,
+
+    /// An application that cannot show windows or be activated.
+    Prohibited
+}
+
+impl From<ActivationPolicy> for isize {
+    fn from(value: ActivationPolicy) -> Self {
+        match value {
+            ActivationPolicy::Regular => 0,
+            ActivationPolicy::Accessory => 1,
+            ActivationPolicy::Prohibited => 2
+        }
+    }
+}
+
+/// Creates the shared NSApplication as an instance of an NSApplication
+/// subclass that implements CefAppProtocol. CEF requires this on macOS for
+/// key events, menus and nested message loops to work correctly. This must be
+/// called on the main thread before Context::initialize and before anything
+/// else creates the shared application, e.g. winit's event loop. Calling it
+/// again after it succeeded does nothing.
+pub fn init_application() -> Result<()> {
+    ensure_main_thread()?;
+
+    unsafe {
+        let class = application_class()?;
+
+        if !NSApp.is_null() {
+            return match send::<bool>(NSApp, "isKindOfClass:", class) {
+                true => Ok(()),
+                false => Err(anyhow!(
+                    "The shared NSApplication was created before init_application was called."
+                )
+                .into())
+            };
+        }
+
+        send0(class, "sharedApplication");
+    }
+
+    Ok(())
+}
+
+/// Sets how the application appears in the Dock. Applications that are not
+/// launched from an app bundle start without a Dock icon or menu bar until
+/// this is set to ActivationPolicy::Regular. Returns an error if the policy
+/// could not be changed.
+pub fn set_activation_policy(policy: ActivationPolicy) -> Result<()> {
+    let app = shared_application()?;
+    let changed: bool = unsafe { send(app, "setActivationPolicy:", isize::from(policy)) };
+
+    match changed {
+        true => Ok(()),
+        false => Err(anyhow!("Failed to set the activation policy to {:?}.", policy).into())
+    }
+}
+
+/// Brings the application to the front, e.g. after creating its first window
+/// when it was launched from a terminal.
+pub fn activate_application() -> Result<()> {
+    let app = shared_application()?;
+
+    unsafe { send::<()>(app, "activateIgnoringOtherApps:", true) };
+
+    Ok(())
+}
+
+/// Installs a main menu with the standard application, Edit and Window menus.
+/// Without a menu the key equivalents for copy, paste, select all, undo and
+/// quit do not reach the browser. |app_name| is shown in the Hide and Quit
+/// items.
+pub fn set_default_main_menu(app_name: &str) -> Result<()> {
+    let app = shared_application()?;
+
+    unsafe {
+        let main_menu = new_menu("")?;
+
+        let app_menu = new_menu(app_name)?;
+        add_item(app_menu, &format!("Hide {}", app_name), "hide:", "h", 0)?;
+        add_item(
+            app_menu,
+            "Hide Others",
+            "hideOtherApplications:",
+            "h",
+            MODIFIER_OPTION
+        )?;
+        add_item(app_menu, "Show All", "unhideAllApplications:", "", 0)?;
+        add_separator(app_menu);
+        add_item(app_menu, &format!("Quit {}", app_name), "terminate:", "q", 0)?;
+        add_submenu(main_menu, app_menu)?;
+
+        let edit_menu = new_menu("Edit")?;
+        add_item(edit_menu, "Undo", "undo:", "z", 0)?;
+        add_item(edit_menu, "Redo", "redo:", "Z", 0)?;
+        add_separator(edit_menu);
+        add_item(edit_menu, "Cut", "cut:", "x", 0)?;
+        add_item(edit_menu, "Copy", "copy:", "c", 0)?;
+        add_item(edit_menu, "Paste", "paste:", "v", 0)?;
+        add_item(edit_menu, "Delete", "delete:", "", 0)?;
+        add_item(edit_menu, "Select All", "selectAll:", "a", 0)?;
+        add_submenu(main_menu, edit_menu)?;
+
+        let window_menu = new_menu("Window")?;
+        add_item(window_menu, "Minimize", "performMiniaturize:", "m", 0)?;
+        add_item(window_menu, "Zoom", "performZoom:", "", 0)?;
+        add_item(window_menu, "Close", "performClose:", "w", 0)?;
+        add_submenu(main_menu, window_menu)?;
+
+        send::<()>(app, "setMainMenu:", main_menu);
+        send::<()>(app, "setWindowsMenu:", window_menu);
+
+        release(app_menu);
+        release(edit_menu);
+        release(window_menu);
+        release(main_menu);
+    }
+
+    Ok(())
+}
+
+/// Calls |f| instead of terminating the process when the user quits the
+/// application, e.g. with Cmd+Q or from the Dock. Use it to close all browsers
+/// and quit the message loop so that CEF can shut down cleanly.
+pub fn set_terminate_handler(f: impl FnMut() + Send + 'static) {
+    *TERMINATE_HANDLER.lock() = Some(Box::new(f));
+}
+
+/// Returns true while the shared application is dispatching an event. This is
+/// the state CEF queries through CrAppProtocol::isHandlingSendEvent.
+pub fn is_handling_send_event() -> bool {
+    HANDLING_SEND_EVENT.load(Ordering::SeqCst)
+}
+
+/// Returns the shared application, which must have been created with
+/// init_application.
+fn shared_application() -> Result<Id> {
+    ensure_main_thread()?;
+
+    match unsafe { NSApp.is_null() } {
+        true => Err(anyhow!("init_application must be called first.").into()),
+        false => Ok(unsafe { NSApp })
+    }
+}
+
+/// AppKit may only be used from the main thread.
+fn ensure_main_thread() -> Result<()> {
+    let is_main_thread: bool = unsafe { send(class(c"NSThread")?, "isMainThread", ()) };
+
+    match is_main_thread {
+        true => Ok(()),
+        false => Err(anyhow!("AppKit must be used from the main thread.").into())
+    }
+}
+
+/// Returns the application class, registering it on first use.
+unsafe fn application_class() -> Result<Class> {
+    let existing = objc_getClass(APPLICATION_CLASS.as_ptr() as *const c_char);
+
+    if !existing.is_null() {
+        return Ok(existing);
+    }
+
+    let class = objc_allocateClassPair(
+        class(c"NSApplication")?,
+        APPLICATION_CLASS.as_ptr() as *const c_char,
+        0
+    );
+
+    if class.is_null() {
+        return Err(anyhow!("Failed to register the application class.").into());
+    }
+
+    class_addMethod(
+        class,
+        selector("isHandlingSendEvent"),
+        is_handling_send_event_imp as *const c_void,
+        b"B@:\0".as_ptr() as *const c_char
+    );
+    class_addMethod(
+        class,
+        selector("setHandlingSendEvent:"),
+        set_handling_send_event_imp as *const c_void,
+        b"v@:B\0".as_ptr() as *const c_char
+    );
+    class_addMethod(
+        class,
+        selector("sendEvent:"),
+        send_event_imp as *const c_void,
+        b"v@:@\0".as_ptr() as *const c_char
+    );
+    class_addMethod(
+        class,
+        selector("terminate:"),
+        terminate_imp as *const c_void,
+        b"v@:@\0".as_ptr() as *const c_char
+    );
+
+    for name in APPLICATION_PROTOCOLS {
+        class_addProtocol(class, protocol(name));
+    }
+
+    objc_registerClassPair(class);
+
+    Ok(class)
+}
+
+/// Returns the protocol named |name|, registering an empty one if no loaded
+/// image declares it. The runtime matches protocols by name, so CEF's
+/// conformance checks succeed either way.
+unsafe fn protocol(name: &[u8]) -> Protocol {
+    let existing = objc_getProtocol(name.as_ptr() as *const c_char);
+
+    if !existing.is_null() {
+        return existing;
+    }
+
+    let protocol = objc_allocateProtocol(name.as_ptr() as *const c_char);
+
+    objc_registerProtocol(protocol);
+
+    protocol
+}
+
+extern "C" fn is_handling_send_event_imp(_this: Id, _cmd: Sel) -> bool {
+    is_handling_send_event()
+}
+
+extern "C" fn set_handling_send_event_imp(_this: Id, _cmd: Sel, value: bool) {
+    HANDLING_SEND_EVENT.store(value, Ordering::SeqCst);
+}
+
+extern "C" fn send_event_imp(this: Id, cmd: Sel, event: Id) {
+    catch_unwind_or("CefUiApplication::sendEvent", (), || {
+        let _guard = HandlingSendEvent::enter();
+
+        unsafe { send_super(this, cmd, event) };
+    })
+}
+
+extern "C" fn terminate_imp(this: Id, cmd: Sel, sender: Id) {
+    catch_unwind_or("CefUiApplication::terminate", (), || {
+        // The handler is called without holding the lock, so that it can
+        // replace itself or re-enter terminate: without deadlocking.
+        let handler = TERMINATE_HANDLER.lock().take();
+
+        match handler {
+            Some(handler) => {
+                let mut restore = RestoreTerminateHandler(Some(handler));
+
+                if let Some(handler) = restore.0.as_mut() {
+                    handler();
+                }
+            },
+            None => unsafe { send_super(this, cmd, sender) }
+        }
+    })
+}
+
+/// Sets HANDLING_SEND_EVENT for the duration of sendEvent: and restores the
+/// previous value when dropped, even if dispatching the event unwinds.
+struct HandlingSendEvent(bool);
+
+impl HandlingSendEvent {
+    fn enter() -> Self {
+        Self(HANDLING_SEND_EVENT.swap(true, Ordering::SeqCst))
+    }
+}
+
+impl Drop for HandlingSendEvent {
+    fn drop(&mut self) {
+        HANDLING_SEND_EVENT.store(self.0, Ordering::SeqCst);
+    }
+}
+
+/// Puts the terminate handler back when dropped, even if it panicked, unless
+/// it installed a replacement while it ran.
+struct RestoreTerminateHandler(Option<Box<dyn FnMut() + Send + 'static>>);
+
+impl Drop for RestoreTerminateHandler {
+    fn drop(&mut self) {
+        let mut current = TERMINATE_HANDLER.lock();
+
+        if current.is_none() {
+            *current = self.0.take();
+        }
+    }
+}
+
+/// Sends |cmd| with a single object argument to the NSApplication
+/// implementation of |this|.
+unsafe fn send_super(this: Id, cmd: Sel, argument: Id) {
+    let mut receiver = ObjcSuper {
+        receiver:    this,
+        super_class: objc_getClass(b"NSApplication\0".as_ptr() as *const c_char)
+    };
+    let send: unsafe extern "C" fn(*mut ObjcSuper, Sel, Id) =
+        transmute(objc_msgSendSuper as *const c_void);
+
+    send(&mut receiver, cmd, argument);
+}
+
+/// A value that can be passed as the single argument of a message.
+trait Argument {
+    unsafe fn send<R>(self, receiver: Id, selector: Sel) -> R;
+}
+
+impl Argument for () {
+    unsafe fn send<R>(self, receiver: Id, selector: Sel) -> R {
+        let send: unsafe extern "C" fn(Id, Sel) -> R = transmute(objc_msgSend as *const c_void);
+
+        send(receiver, selector)
+    }
+}
+
+macro_rules! impl_argument {
+    ($($type:ty),*) => {
+        $(
+            impl Argument for $type {
+                unsafe fn send<R>(self, receiver: Id, selector: Sel) -> R {
+                    let send: unsafe extern "C" fn(Id, Sel, $type) -> R =
+                        transmute(objc_msgSend as *const c_void);
+
+                    send(receiver, selector, self)
+                }
+            }
+        )*
+    };
+}
+
+impl_argument!(Id, bool, isize, usize);
+
+/// Sends |name| with |argument| to |receiver|. The return type must match the
+/// method signature.
+unsafe fn send<R>(receiver: Id, name: &str, argument: impl Argument) -> R {
+    argument.send(receiver, selector(name))
+}
+
+/// Sends |name| without arguments to |receiver| and returns the object result.
+unsafe fn send0(receiver: Id, name: &str) -> Id {
+    send(receiver, name, ())
+}
+
+/// Releases |object|.
+unsafe fn release(object: Id) {
+    send::<()>(object, "release", ());
+}
+
+/// Returns the class named |name|.
+fn class(name: &CStr) -> Result<Class> {
+    let class = unsafe { objc_getClass(name.as_ptr()) };
+
+    match class.is_null() {
+        true => Err(anyhow!("The Objective-C class {:?} does not exist.", name).into()),
+        false => Ok(class)
+    }
+}
+
+/// Returns the selector named |name|.
+fn selector(name: &str) -> Sel {
+    let name = CString::new(name).unwrap_or_default();
+
+    unsafe { sel_registerName(name.as_ptr()) }
+}
+
+/// Returns a new NSString with the contents of |value|. The caller owns it.
+unsafe fn new_string(value: &str) -> Result<Id> {
+    let value = CString::new(value)?;
+    let string = send0(class(c"NSString")?, "alloc");
+
+    Ok(send(string, "initWithUTF8String:", value.as_ptr() as Id))
+}
+
+/// Returns a new menu titled |title|. The caller owns it.
+unsafe fn new_menu(title: &str) -> Result<Id> {
+    let title = new_string(title)?;
+    let menu = send0(class(c"NSMenu")?, "alloc");
+    let menu: Id = send(menu, "initWithTitle:", title);
+
+    release(title);
+
+    Ok(menu)
+}
+
+/// Adds an item titled |title| to |menu| that sends |action| to the first
+/// responder when chosen or when Command, |modifiers| and |key| are pressed.
+unsafe fn add_item(
+    menu: Id,
+    title: &str,
+    action: &str,
+    key: &str,
+    modifiers: usize
+) -> Result<()> {
+    let title = new_string(title)?;
+    let key = new_string(key)?;
+    let item = send0(class(c"NSMenuItem")?, "alloc");
+    let init: unsafe extern "C" fn(Id, Sel, Id, Sel, Id) -> Id =
+        transmute(objc_msgSend as *const c_void);
+    let item = init(
+        item,
+        selector("initWithTitle:action:keyEquivalent:"),
+        title,
+        selector(action),
+        key
+    );
+
+    if modifiers != 0 {
+        send::<()>(item, "setKeyEquivalentModifierMask:", MODIFIER_COMMAND | modifiers);
+    }
+
+    send::<()>(menu, "addItem:", item);
+
+    release(item);
+    release(key);
+    release(title);
+
+    Ok(())
+}
+
+/// Adds a separator to |menu|.
+unsafe fn add_separator(menu: Id) {
+    if let Ok(class) = class(c"NSMenuItem") {
+        send::<()>(menu, "addItem:", send0(class, "separatorItem"));
+    }
+}
+
+/// Adds |submenu| to |menu| as an item with the same title.
+unsafe fn add_submenu(menu: Id, submenu: Id) -> Result<()> {
+    let item = send0(send0(class(c"NSMenuItem")?, "alloc"), "init");
+
+    send::<()>(item, "setSubmenu:", submenu);
+    send::<()>(menu, "addItem:", item);
+
+    release(item);
+
+    Ok(())
+}
@@ -15,3 +15,15 @@ mod windows;
 
 #[cfg(all(target_os = "windows"))]
 pub use windows::*;
+
+#[cfg(all(target_os = "macos"))]
+mod macos_application;
+
+#[cfg(all(target_os = "macos"))]
+pub use macos_application::*;
+
+#[cfg(all(target_os = "windows"))]
+mod windows_dpi;
+
+#[cfg(all(target_os = "windows"))]
+pub use windows_dpi::*;
@@ -1,8 +1,8 @@
-use crate::{free_cef_string, CefString, Rect};
-use anyhow::{anyhow, Error, Result};
+use crate::{free_cef_string, CefString, Error, Rect, Result};
 use cef_ui_sys::{
-    cef_event_handle_t, cef_main_args_t, cef_string_t, cef_window_handle_t, cef_window_info_t,
-    GetModuleHandleA, HMENU
+    cef_cursor_handle_t, cef_event_handle_t, cef_main_args_t, cef_set_osmodal_loop, cef_string_t,
+    cef_window_handle_t, cef_window_info_t, GetModuleHandleA, HMENU, WS_CHILD, WS_CLIPCHILDREN,
+    WS_CLIPSIBLINGS, WS_OVERLAPPEDWINDOW, WS_TABSTOP, WS_VISIBLE
 };
 use std::{
     ffi::{c_int, c_ulong},
@@ -62,7 +62,7 @@ impl TryFrom<cef_event_handle_t> for NativeEventHandle {
 
     fn try_from(handle: cef_event_handle_t) -> Result<Self> {
         match handle.is_null() {
-            true => Err(anyhow!("Native event handle is null!")),
+            true => Err(Error::InvalidHandle("NativeEventHandle")),
             false => Ok(Self(handle))
         }
     }
@@ -76,6 +76,32 @@ impl TryFrom<NativeEventHandle> for cef_event_handle_t {
     }
 }
 
+/// Native cursor handle.
+#[derive(Clone)]
+pub struct NativeCursorHandle(cef_cursor_handle_t);
+
+impl TryFrom<cef_cursor_handle_t> for NativeCursorHandle {
+    type Error = Error;
+
+    fn try_from(handle: cef_cursor_handle_t) -> Result<Self> {
+        match handle.is_null() {
+            true => Err(Error::InvalidHandle("NativeCursorHandle")),
+            false => Ok(Self(handle))
+        }
+    }
+}
+
+impl TryFrom<NativeCursorHandle> for cef_cursor_handle_t {
+    type Error = Error;
+
+    fn try_from(handle: NativeCursorHandle) -> Result<Self> {
+        Ok(handle.0)
+    }
+}
+
+/// Lets Windows pick the default position and size for a new window.
+const CW_USEDEFAULT: i32 = 0x80000000u32 as i32;
+
 /// Represents window information.
 #[repr(transparent)]
 pub struct WindowInfo(cef_window_info_t);
@@ -105,6 +131,55 @@ impl WindowInfo {
         unsafe { &mut *(ptr as *mut Self) }
     }
 
+    /// Create the browser as a child window of |parent| at the specified
+    /// |bounds|.
+    pub fn as_child(parent: NativeWindowHandle, bounds: &Rect) -> Self {
+        Self::new()
+            .style((WS_CHILD | WS_CLIPCHILDREN | WS_CLIPSIBLINGS | WS_TABSTOP | WS_VISIBLE) as u64)
+            .parent_window(parent)
+            .bounds(bounds)
+    }
+
+    /// Create the browser as a popup window with the specified |window_name|.
+    /// The |parent| window, if any, will be the owner of the popup. The popup
+    /// uses the default position and size chosen by Windows.
+    pub fn as_popup(parent: Option<NativeWindowHandle>, window_name: &str) -> Self {
+        let info = Self::new()
+            .style((WS_OVERLAPPEDWINDOW | WS_CLIPCHILDREN | WS_CLIPSIBLINGS | WS_VISIBLE) as u64)
+            .window_name(&window_name.to_string())
+            .bounds(&Rect {
+                x:      CW_USEDEFAULT,
+                y:      CW_USEDEFAULT,
+                width:  CW_USEDEFAULT,
+                height: CW_USEDEFAULT
+            });
+
+        match parent {
+            Some(parent) => info.parent_window(parent),
+            None => info
+        }
+    }
+
+    /// Create the browser as a visible top-level window with the specified
+    /// |window_name| at |bounds|, which are in screen pixels.
+    pub fn as_top_level(window_name: &str, bounds: &Rect) -> Self {
+        Self::as_popup(None, window_name).bounds(bounds)
+    }
+
+    /// Create the browser using windowless (off-screen) rendering. The |parent|
+    /// window, if any, is used to identify monitor info and to act as the parent
+    /// window for dialogs, context menus, etc. Transparent painting is enabled by
+    /// default and can be disabled by setting CefBrowserSettings.background_color
+    /// to an opaque value.
+    pub fn as_windowless(parent: Option<NativeWindowHandle>) -> Self {
+        let info = Self::new().windowless_rendering_enabled(true);
+
+        match parent {
+            Some(parent) => info.parent_window(parent),
+            None => info
+        }
+    }
+
     /// Set the ex_style for CreateWindowEx.
     pub fn ex_style(mut self, value: u64) -> Self {
         self.0.ex_style = value as c_ulong;
@@ -183,8 +258,11 @@ impl WindowInfo {
         &self.0
     }
 
-    /// Tries to assign a String to a cef_string_t.
+    /// Tries to assign a String to a cef_string_t, freeing any
+    /// previously assigned value.
     fn set_string(s: &String, cef: &mut cef_string_t) {
+        free_cef_string(cef);
+
         *cef = CefString::new(s.as_str()).into_raw();
     }
 }
@@ -194,3 +272,31 @@ impl Drop for WindowInfo {
         free_cef_string(&mut self.0.window_name);
     }
 }
+
+/// Set to true before calling Windows APIs like TrackPopupMenu that enter a
+/// modal message loop. Set to false after exiting the modal message loop.
+/// This keeps CEF processing its work while the modal loop runs, e.g. when an
+/// external message pump or the multi-threaded message loop is used.
+pub fn set_os_modal_loop(os_modal_loop: bool) {
+    unsafe { cef_set_osmodal_loop(os_modal_loop as c_int) };
+}
+
+/// Tells CEF that a native modal message loop is running for as long as it is
+/// alive. Create one before showing a native modal dialog or tracking a popup
+/// menu, and drop it once the call returns.
+#[derive(Debug)]
+pub struct OsModalLoop(());
+
+impl OsModalLoop {
+    pub fn enter() -> Self {
+        set_os_modal_loop(true);
+
+        Self(())
+    }
+}
+
+impl Drop for OsModalLoop {
+    fn drop(&mut self) {
+        set_os_modal_loop(false);
+    }
+}
@@ -0,0 +1,171 @@
+#[cfg(feature = "osr")]
+use crate::{BrowserHost, DeviceScaleFactor};
+use crate::{NativeWindowHandle, Rect, Result, WindowInfo};
+use cef_ui_sys::{
+    cef_window_handle_t, GetProcAddress, LoadLibraryA, SetProcessDPIAware, BOOL,
+    DPI_AWARENESS_CONTEXT, FARPROC, UINT, USER_DEFAULT_SCREEN_DPI, WPARAM
+};
+use std::{ffi::CStr, mem::transmute};
+
+/// DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2, which is a #define'd handle and
+/// therefore not generated by bindgen.
+const DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2: DPI_AWARENESS_CONTEXT =
+    -4isize as DPI_AWARENESS_CONTEXT;
+
+type SetProcessDpiAwarenessContextFn = unsafe extern "system" fn(DPI_AWARENESS_CONTEXT) -> BOOL;
+type GetDpiForWindowFn = unsafe extern "system" fn(cef_window_handle_t) -> UINT;
+type GetDpiForSystemFn = unsafe extern "system" fn() -> UINT;
+
+/// The DPI awareness that the process ended up with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DpiAwareness {
+    /// Windows are scaled per monitor, including their non-client area, and
+    /// receive WM_DPICHANGED when moved to a monitor with a different DPI.
+    PerMonitorV2,
+
+    /// Windows are scaled by the DPI of the primary monitor at login and are
+    /// bitmap-stretched by Windows on other monitors.
+    System,
+
+    /// The awareness was already set, e.g. by the application manifest, and
+    /// could not be changed.
+    Unchanged
+}
+
+/// Makes the process per-monitor (v2) DPI aware, falling back to system DPI
+/// awareness on Windows versions before 10 1703. The newer functions are
+/// looked up at runtime, so this also works on older versions of Windows that
+/// do not export them. Chromium assumes that the
+/// browser process is DPI aware; without it windowed browsers are blurry and
+/// OSR coordinates are off on scaled displays. Call this at the top of main,
+/// before any window is created and before Context::initialize. Declaring the
+/// awareness in the application manifest instead is equivalent.
+pub fn enable_high_dpi_support() -> DpiAwareness {
+    unsafe {
+        if let Some(function) = user32_function(c"SetProcessDpiAwarenessContext") {
+            let set_awareness: SetProcessDpiAwarenessContextFn = transmute(function);
+
+            if set_awareness(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2) != 0 {
+                return DpiAwareness::PerMonitorV2;
+            }
+        }
+
+        match SetProcessDPIAware() != 0 {
+            true => DpiAwareness::System,
+            false => DpiAwareness::Unchanged
+        }
+    }
+}
+
+/// Returns the scale factor of the monitor that |window| is on, e.g. 1.5 for
+/// 144 DPI. Without per-monitor DPI awareness this is the system scale factor.
+/// Windows versions before 10 1607 cannot report the DPI, so this is 1.0.
+pub fn window_scale_factor(window: &NativeWindowHandle) -> Result<f32> {
+    let window: cef_window_handle_t = window.clone().try_into()?;
+    let dpi = match dpi_for_window(window) {
+        0 => dpi_for_system(),
+        dpi => dpi
+    };
+
+    Ok(dpi_to_scale_factor(dpi))
+}
+
+/// Returns the scale factor carried by the wParam of a WM_DPICHANGED message,
+/// which a window receives when it is dragged to a monitor with a different
+/// DPI.
+pub fn dpi_changed_scale_factor(wparam: WPARAM) -> f32 {
+    dpi_to_scale_factor((wparam & 0xffff) as u32)
+}
+
+/// Converts |rect| from logical coordinates (device-independent pixels) to
+/// physical pixels at |scale_factor|.
+pub fn to_physical_rect(rect: &Rect, scale_factor: f32) -> Rect {
+    scale_rect(rect, scale_factor)
+}
+
+/// Converts |rect| from physical pixels to logical coordinates
+/// (device-independent pixels) at |scale_factor|.
+pub fn to_logical_rect(rect: &Rect, scale_factor: f32) -> Rect {
+    scale_rect(rect, 1.0 / scale_factor)
+}
+
+impl WindowInfo {
+    /// Create the browser as a child window embedded in |parent| at |bounds|,
+    /// which are given in logical coordinates and converted to the physical
+    /// pixels that Windows expects using the DPI of the monitor |parent| is
+    /// on.
+    pub fn as_child_logical(parent: NativeWindowHandle, bounds: &Rect) -> Result<Self> {
+        let bounds = to_physical_rect(bounds, window_scale_factor(&parent)?);
+
+        Ok(Self::as_child(parent, &bounds))
+    }
+}
+
+/// Updates |scale_factor| to the DPI of the monitor that |window| is on and
+/// tells the browser of |host| to repaint if it changed. Call this for
+/// WM_DPICHANGED on the window that displays a windowless browser.
+#[cfg(feature = "osr")]
+pub fn sync_device_scale_factor(
+    scale_factor: &DeviceScaleFactor,
+    host: &BrowserHost,
+    window: &NativeWindowHandle
+) -> Result<()> {
+    scale_factor.set(host, window_scale_factor(window)?)
+}
+
+/// Returns the address of |name| in user32.dll, or None if this version of
+/// Windows does not export it.
+fn user32_function(name: &CStr) -> FARPROC {
+    unsafe {
+        let user32 = LoadLibraryA(c"user32.dll".as_ptr());
+
+        match user32.is_null() {
+            true => None,
+            false => GetProcAddress(user32, name.as_ptr())
+        }
+    }
+}
+
+/// Calls GetDpiForWindow, which requires Windows 10 1607. Returns 0 if it is
+/// not available or fails.
+fn dpi_for_window(window: cef_window_handle_t) -> u32 {
+    match user32_function(c"GetDpiForWindow") {
+        Some(function) => unsafe {
+            let get_dpi: GetDpiForWindowFn = transmute(function);
+
+            get_dpi(window)
+        },
+        None => 0
+    }
+}
+
+/// Calls GetDpiForSystem, which requires Windows 10 1607. Returns 0 if it is
+/// not available.
+fn dpi_for_system() -> u32 {
+    match user32_function(c"GetDpiForSystem") {
+        Some(function) => unsafe {
+            let get_dpi: GetDpiForSystemFn = transmute(function);
+
+            get_dpi()
+        },
+        None => 0
+    }
+}
+
+/// Converts a DPI value to a scale factor.
+fn dpi_to_scale_factor(dpi: u32) -> f32 {
+    match dpi {
+        0 => 1.0,
+        dpi => dpi as f32 / USER_DEFAULT_SCREEN_DPI as f32
+    }
+}
+
+/// Scales |rect| by |factor|, rounding to the nearest pixel.
+fn scale_rect(rect: &Rect, factor: f32) -> Rect {
+    Rect::new(
+        (rect.x as f32 * factor).round() as i32,
+        (rect.y as f32 * factor).round() as i32,
+        (rect.width as f32 * factor).round() as i32,
+        (rect.height as f32 * factor).round() as i32
+    )
+}
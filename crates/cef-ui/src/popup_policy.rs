@@ -0,0 +1,314 @@
+use crate::{
+    Browser, BrowserSettings, Client, DictionaryValue, Frame, LifeSpanHandler,
+    LifeSpanHandlerCallbacks, NativeWindowHandle, PopupFeatures, Rect, Result, WindowInfo,
+    WindowOpenDisposition
+};
+use anyhow::anyhow;
+use std::process::Command;
+use tracing::{error, warn};
+
+/// The URL schemes that PopupPolicy::OpenExternally hands to the operating
+/// system. Anything else (file:, javascript:, custom protocol handlers, ...)
+/// could run local programs on behalf of the page, so it is denied.
+const EXTERNAL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Describes a popup requested with window.open or a target=_blank link. See
+/// LifeSpanHandlerCallbacks::on_before_popup.
+pub struct PopupRequest {
+    /// The browser that requested the popup.
+    pub browser: Browser,
+
+    /// The frame that requested the popup.
+    pub frame: Frame,
+
+    /// The URL the popup should navigate to, if specified.
+    pub target_url: Option<String>,
+
+    /// The name of the target frame, if specified.
+    pub target_frame_name: Option<String>,
+
+    /// Where the user intended to open the popup (e.g. current tab, new tab).
+    pub target_disposition: WindowOpenDisposition,
+
+    /// True if the popup was opened via an explicit user gesture.
+    pub user_gesture: bool,
+
+    /// Additional information about the requested popup window.
+    pub popup_features: PopupFeatures
+}
+
+/// What to do with a popup request.
+pub enum PopupPolicy {
+    /// Navigate the requesting browser's main frame to the target URL instead
+    /// of opening a popup.
+    OpenInSameBrowser,
+
+    /// Open the target URL with the default application of the operating
+    /// system, usually the system web browser. Only http, https and mailto
+    /// URLs are opened; popups to any other URL are blocked.
+    OpenExternally,
+
+    /// Let CEF create the popup browser. If |window_info| is set it replaces the
+    /// window information CEF would use, e.g. to create the popup windowless or
+    /// as a child of an application window.
+    AllowNewWindow { window_info: Option<WindowInfo> },
+
+    /// Let CEF create the popup browser as a child of |parent|, a native window
+    /// (HWND, NSView or X11 window) created by the application, at |bounds| in
+    /// the coordinates of |parent|. The application owns the window, so the
+    /// popup is moved, resized and closed with the rest of its windows.
+    Reparent {
+        parent: NativeWindowHandle,
+        bounds: Rect
+    },
+
+    /// Block the popup.
+    Deny
+}
+
+impl PopupPolicy {
+    /// Carries out the policy for |request|. |window_info| is the window
+    /// information passed to on_before_popup. Returns the value on_before_popup
+    /// should return, i.e. true if CEF should not create the popup browser.
+    pub fn apply(self, request: &PopupRequest, window_info: &mut WindowInfo) -> bool {
+        let result = match self {
+            PopupPolicy::OpenInSameBrowser => Self::open_in_same_browser(request),
+            PopupPolicy::OpenExternally => Self::open_externally(request),
+            PopupPolicy::AllowNewWindow {
+                window_info: replacement
+            } => {
+                if let Some(replacement) = replacement {
+                    *window_info = replacement;
+                }
+
+                return false;
+            },
+            PopupPolicy::Reparent { parent, bounds } => {
+                *window_info = WindowInfo::as_child(parent, &bounds);
+
+                return false;
+            },
+            PopupPolicy::Deny => Ok(())
+        };
+
+        if let Err(e) = result {
+            error!("Failed to route popup: {}", e);
+        }
+
+        true
+    }
+
+    /// Loads the target URL in the main frame of the requesting browser.
+    fn open_in_same_browser(request: &PopupRequest) -> Result<()> {
+        let Some(url) = &request.target_url else {
+            return Ok(());
+        };
+
+        match request.browser.get_main_frame()? {
+            Some(frame) => frame.load_url(url),
+            None => request.frame.load_url(url)
+        }
+    }
+
+    /// Opens the target URL with the default application of the operating
+    /// system. Only http, https and mailto URLs are opened; others are denied.
+    fn open_externally(request: &PopupRequest) -> Result<()> {
+        let Some(url) = &request.target_url else {
+            return Ok(());
+        };
+
+        if !is_external_url(url) {
+            warn!(
+                "Denied opening {} externally; only http, https and mailto are allowed.",
+                url
+            );
+
+            return Ok(());
+        }
+
+        let mut command = Self::open_command();
+
+        command
+            .arg(url)
+            .spawn()
+            .map_err(|e| anyhow!("Failed to open {} externally: {}", url, e))?;
+
+        Ok(())
+    }
+
+    /// Returns the command that opens a URL with the default application.
+    #[cfg(target_os = "windows")]
+    fn open_command() -> Command {
+        let mut command = Command::new("rundll32");
+
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    }
+
+    /// Returns the command that opens a URL with the default application.
+    #[cfg(target_os = "macos")]
+    fn open_command() -> Command {
+        Command::new("open")
+    }
+
+    /// Returns the command that opens a URL with the default application.
+    #[cfg(target_os = "linux")]
+    fn open_command() -> Command {
+        Command::new("xdg-open")
+    }
+}
+
+/// Returns true if |url| is a well-formed URL with one of EXTERNAL_SCHEMES.
+fn is_external_url(url: &str) -> bool {
+    // Whitespace and control characters could be interpreted by the opener.
+    if url
+        .chars()
+        .any(|c| c.is_whitespace() || c.is_control())
+    {
+        return false;
+    }
+
+    let Some((scheme, rest)) = url.split_once(':') else {
+        return false;
+    };
+
+    let scheme = scheme.to_ascii_lowercase();
+
+    if !EXTERNAL_SCHEMES.contains(&scheme.as_str()) {
+        return false;
+    }
+
+    // http and https URLs must have a host, mailto URLs an address.
+    match scheme.as_str() {
+        "mailto" => !rest.is_empty(),
+        _ => rest
+            .strip_prefix("//")
+            .is_some_and(|authority| {
+                !authority.is_empty() && !authority.starts_with(['/', '?', '#'])
+            })
+    }
+}
+
+/// A LifeSpanHandlerCallbacks implementation that routes popups with a
+/// PopupPolicy returned by a closure, so that window.open and target=_blank
+/// links can be handled without implementing on_before_popup. All other
+/// callbacks are forwarded to |delegate|.
+pub struct PopupRouter<C: LifeSpanHandlerCallbacks, F> {
+    delegate:  C,
+    policy:    F,
+    dev_tools: Option<DevToolsWindow>
+}
+
+type DevToolsWindow = Box<dyn FnMut(&Browser) -> Option<WindowInfo> + Send + Sync + 'static>;
+
+impl<C, F> PopupRouter<C, F>
+where
+    C: LifeSpanHandlerCallbacks,
+    F: FnMut(&PopupRequest) -> PopupPolicy + Send + Sync + 'static
+{
+    pub fn new(delegate: C, policy: F) -> Self {
+        Self {
+            delegate,
+            policy,
+            dev_tools: None
+        }
+    }
+
+    /// Calls |f| with the inspected browser whenever DevTools opens as a popup
+    /// (e.g. from the context menu or a keyboard shortcut). If |f| returns
+    /// window information, e.g. WindowInfo::as_child with a native window
+    /// created by the application, DevTools is created with it instead of in a
+    /// window of its own.
+    pub fn dev_tools_window(
+        mut self,
+        f: impl FnMut(&Browser) -> Option<WindowInfo> + Send + Sync + 'static
+    ) -> Self {
+        self.dev_tools = Some(Box::new(f));
+        self
+    }
+
+    /// Converts this to a LifeSpanHandler.
+    pub fn build(self) -> LifeSpanHandler {
+        LifeSpanHandler::new(self)
+    }
+}
+
+impl<C, F> LifeSpanHandlerCallbacks for PopupRouter<C, F>
+where
+    C: LifeSpanHandlerCallbacks,
+    F: FnMut(&PopupRequest) -> PopupPolicy + Send + Sync + 'static
+{
+    unsafe fn on_before_popup(
+        &mut self,
+        browser: Browser,
+        frame: Frame,
+        target_url: Option<String>,
+        target_frame_name: Option<String>,
+        target_disposition: WindowOpenDisposition,
+        user_gesture: bool,
+        popup_features: PopupFeatures,
+        window_info: &mut WindowInfo,
+        _client: &mut Option<Client>,
+        _settings: &mut BrowserSettings,
+        _extra_info: &mut Option<DictionaryValue>,
+        _no_javascript_access: &mut bool
+    ) -> bool {
+        let request = PopupRequest {
+            browser,
+            frame,
+            target_url,
+            target_frame_name,
+            target_disposition,
+            user_gesture,
+            popup_features
+        };
+
+        (self.policy)(&request).apply(&request, window_info)
+    }
+
+    fn on_before_dev_tools_popup(
+        &mut self,
+        browser: Browser,
+        window_info: &mut WindowInfo,
+        client: &mut Option<Client>,
+        settings: &mut BrowserSettings,
+        extra_info: &mut Option<DictionaryValue>,
+        use_default_window: &mut bool
+    ) {
+        let replacement = self
+            .dev_tools
+            .as_mut()
+            .and_then(|f| f(&browser));
+
+        self.delegate
+            .on_before_dev_tools_popup(
+                browser,
+                window_info,
+                client,
+                settings,
+                extra_info,
+                use_default_window
+            );
+
+        // Views-hosted browsers would otherwise create a Views-hosted DevTools
+        // window and ignore |window_info|.
+        if let Some(replacement) = replacement {
+            *window_info = replacement;
+            *use_default_window = true;
+        }
+    }
+
+    fn on_after_created(&mut self, browser: Browser) {
+        self.delegate
+            .on_after_created(browser);
+    }
+
+    fn do_close(&mut self, browser: Browser) -> bool {
+        self.delegate.do_close(browser)
+    }
+
+    fn on_before_close(&mut self, browser: Browser) {
+        self.delegate
+            .on_before_close(browser);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{ref_counted_ptr, try_c, CefString, DictionaryValue, Result, Value};
+use anyhow::anyhow;
+use cef_ui_sys::{cef_preference_manager_get_global, cef_preference_manager_t};
+use std::ptr::null_mut;
+
+// Manage access to preferences. Many built-in preferences are registered by
+// Chromium. Custom preferences can be registered in
+// cef_browser_process_handler_t::OnRegisterCustomPreferences.
+ref_counted_ptr!(PreferenceManager, cef_preference_manager_t);
+
+impl PreferenceManager {
+    /// Returns the global preference manager object.
+    pub fn get_global() -> Option<Self> {
+        unsafe { Self::from_ptr(cef_preference_manager_get_global()) }
+    }
+
+    /// Returns true (1) if a preference with the specified |name| exists. This
+    /// function must be called on the browser process UI thread.
+    pub fn has_preference(&self, name: &str) -> Result<bool> {
+        try_c!(self, has_preference, {
+            let name = CefString::new(name);
+
+            Ok(has_preference(self.as_ptr(), name.as_ptr()) != 0)
+        })
+    }
+
+    /// Returns the value for the preference with the specified |name|. Returns
+    /// NULL if the preference does not exist. The returned object contains a copy
+    /// of the underlying preference value and modifications to the returned
+    /// object will not modify the underlying preference value. This function must
+    /// be called on the browser process UI thread.
+    pub fn get_preference(&self, name: &str) -> Result<Option<Value>> {
+        try_c!(self, get_preference, {
+            let name = CefString::new(name);
+
+            Ok(Value::from_ptr(get_preference(
+                self.as_ptr(),
+                name.as_ptr()
+            )))
+        })
+    }
+
+    /// Returns all preferences as a dictionary. If |include_defaults| is true (1)
+    /// then preferences currently at their default value will be included. The
+    /// returned object contains a copy of the underlying preference values and
+    /// modifications to the returned object will not modify the underlying
+    /// preference values. This function must be called on the browser process UI
+    /// thread.
+    pub fn get_all_preferences(&self, include_defaults: bool) -> Result<Option<DictionaryValue>> {
+        try_c!(self, get_all_preferences, {
+            Ok(DictionaryValue::from_ptr(get_all_preferences(
+                self.as_ptr(),
+                include_defaults as i32
+            )))
+        })
+    }
+
+    /// Returns true (1) if the preference with the specified |name| can be
+    /// modified using SetPreference. As one example preferences set via the
+    /// command-line usually cannot be modified. This function must be called on
+    /// the browser process UI thread.
+    pub fn can_set_preference(&self, name: &str) -> Result<bool> {
+        try_c!(self, can_set_preference, {
+            let name = CefString::new(name);
+
+            Ok(can_set_preference(self.as_ptr(), name.as_ptr()) != 0)
+        })
+    }
+
+    /// Set the |value| associated with preference |name|. If |value| is None
+    /// the preference will be restored to its default value. If setting the
+    /// preference fails then the returned error will contain a detailed
+    /// description of the problem. This function must be called on the browser
+    /// process UI thread.
+    pub fn set_preference(&self, name: &str, value: Option<Value>) -> Result<()> {
+        try_c!(self, set_preference, {
+            let cef_name = CefString::new(name);
+            let value = value
+                .map(|value| value.into_raw())
+                .unwrap_or_else(null_mut);
+            let mut error = CefString::default();
+
+            match set_preference(self.as_ptr(), cef_name.as_ptr(), value, error.as_mut_ptr()) {
+                0 => Err(anyhow!(
+                    "Failed to set preference {}: {}",
+                    name,
+                    String::from(&error)
+                )
+                .into()),
+                _ => Ok(())
+            }
+        })
+    }
+}
@@ -0,0 +1,352 @@
+use crate::{ref_counted_ptr, try_c, CefString, Range, Rect, Result, Size};
+use cef_ui_sys::{
+    cef_color_model_t, cef_duplex_mode_t, cef_print_settings_create, cef_print_settings_t,
+    cef_range_t, cef_rect_t, cef_size_t
+};
+use std::ffi::c_int;
+
+/// Print job color mode values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ColorModel {
+    Unknown,
+    Gray,
+    Color,
+    Cmyk,
+    Cmy,
+    Kcmy,
+    CmyPlusK,
+    Black,
+    Grayscale,
+    Rgb,
+    Rgb16,
+    Rgba,
+    ColorModeColor,
+    ColorModeMonochrome,
+    HpColorColor,
+    HpColorBlack,
+    PrintoutModeNormal,
+    PrintoutModeNormalGray,
+    ProcessColorModelCmyk,
+    ProcessColorModelGreyscale,
+    ProcessColorModelRgb
+}
+
+impl From<cef_color_model_t> for ColorModel {
+    fn from(value: cef_color_model_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_color_model_t> for ColorModel {
+    fn from(value: &cef_color_model_t) -> Self {
+        match value {
+            cef_color_model_t::COLOR_MODEL_UNKNOWN => ColorModel::Unknown,
+            cef_color_model_t::COLOR_MODEL_GRAY => ColorModel::Gray,
+            cef_color_model_t::COLOR_MODEL_COLOR => ColorModel::Color,
+            cef_color_model_t::COLOR_MODEL_CMYK => ColorModel::Cmyk,
+            cef_color_model_t::COLOR_MODEL_CMY => ColorModel::Cmy,
+            cef_color_model_t::COLOR_MODEL_KCMY => ColorModel::Kcmy,
+            cef_color_model_t::COLOR_MODEL_CMY_K => ColorModel::CmyPlusK,
+            cef_color_model_t::COLOR_MODEL_BLACK => ColorModel::Black,
+            cef_color_model_t::COLOR_MODEL_GRAYSCALE => ColorModel::Grayscale,
+            cef_color_model_t::COLOR_MODEL_RGB => ColorModel::Rgb,
+            cef_color_model_t::COLOR_MODEL_RGB16 => ColorModel::Rgb16,
+            cef_color_model_t::COLOR_MODEL_RGBA => ColorModel::Rgba,
+            cef_color_model_t::COLOR_MODEL_COLORMODE_COLOR => ColorModel::ColorModeColor,
+            cef_color_model_t::COLOR_MODEL_COLORMODE_MONOCHROME => ColorModel::ColorModeMonochrome,
+            cef_color_model_t::COLOR_MODEL_HP_COLOR_COLOR => ColorModel::HpColorColor,
+            cef_color_model_t::COLOR_MODEL_HP_COLOR_BLACK => ColorModel::HpColorBlack,
+            cef_color_model_t::COLOR_MODEL_PRINTOUTMODE_NORMAL => ColorModel::PrintoutModeNormal,
+            cef_color_model_t::COLOR_MODEL_PRINTOUTMODE_NORMAL_GRAY => {
+                ColorModel::PrintoutModeNormalGray
+            },
+            cef_color_model_t::COLOR_MODEL_PROCESSCOLORMODEL_CMYK => {
+                ColorModel::ProcessColorModelCmyk
+            },
+            cef_color_model_t::COLOR_MODEL_PROCESSCOLORMODEL_GREYSCALE => {
+                ColorModel::ProcessColorModelGreyscale
+            },
+            cef_color_model_t::COLOR_MODEL_PROCESSCOLORMODEL_RGB => ColorModel::ProcessColorModelRgb
+        }
+    }
+}
+
+impl From<ColorModel> for cef_color_model_t {
+    fn from(value: ColorModel) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&ColorModel> for cef_color_model_t {
+    fn from(value: &ColorModel) -> Self {
+        match value {
+            ColorModel::Unknown => cef_color_model_t::COLOR_MODEL_UNKNOWN,
+            ColorModel::Gray => cef_color_model_t::COLOR_MODEL_GRAY,
+            ColorModel::Color => cef_color_model_t::COLOR_MODEL_COLOR,
+            ColorModel::Cmyk => cef_color_model_t::COLOR_MODEL_CMYK,
+            ColorModel::Cmy => cef_color_model_t::COLOR_MODEL_CMY,
+            ColorModel::Kcmy => cef_color_model_t::COLOR_MODEL_KCMY,
+            ColorModel::CmyPlusK => cef_color_model_t::COLOR_MODEL_CMY_K,
+            ColorModel::Black => cef_color_model_t::COLOR_MODEL_BLACK,
+            ColorModel::Grayscale => cef_color_model_t::COLOR_MODEL_GRAYSCALE,
+            ColorModel::Rgb => cef_color_model_t::COLOR_MODEL_RGB,
+            ColorModel::Rgb16 => cef_color_model_t::COLOR_MODEL_RGB16,
+            ColorModel::Rgba => cef_color_model_t::COLOR_MODEL_RGBA,
+            ColorModel::ColorModeColor => cef_color_model_t::COLOR_MODEL_COLORMODE_COLOR,
+            ColorModel::ColorModeMonochrome => cef_color_model_t::COLOR_MODEL_COLORMODE_MONOCHROME,
+            ColorModel::HpColorColor => cef_color_model_t::COLOR_MODEL_HP_COLOR_COLOR,
+            ColorModel::HpColorBlack => cef_color_model_t::COLOR_MODEL_HP_COLOR_BLACK,
+            ColorModel::PrintoutModeNormal => cef_color_model_t::COLOR_MODEL_PRINTOUTMODE_NORMAL,
+            ColorModel::PrintoutModeNormalGray => {
+                cef_color_model_t::COLOR_MODEL_PRINTOUTMODE_NORMAL_GRAY
+            },
+            ColorModel::ProcessColorModelCmyk => {
+                cef_color_model_t::COLOR_MODEL_PROCESSCOLORMODEL_CMYK
+            },
+            ColorModel::ProcessColorModelGreyscale => {
+                cef_color_model_t::COLOR_MODEL_PROCESSCOLORMODEL_GREYSCALE
+            },
+            ColorModel::ProcessColorModelRgb => cef_color_model_t::COLOR_MODEL_PROCESSCOLORMODEL_RGB
+        }
+    }
+}
+
+/// Print job duplex mode values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DuplexMode {
+    Unknown,
+    Simplex,
+    LongEdge,
+    ShortEdge
+}
+
+impl From<cef_duplex_mode_t> for DuplexMode {
+    fn from(value: cef_duplex_mode_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_duplex_mode_t> for DuplexMode {
+    fn from(value: &cef_duplex_mode_t) -> Self {
+        match value {
+            cef_duplex_mode_t::DUPLEX_MODE_UNKNOWN => DuplexMode::Unknown,
+            cef_duplex_mode_t::DUPLEX_MODE_SIMPLEX => DuplexMode::Simplex,
+            cef_duplex_mode_t::DUPLEX_MODE_LONG_EDGE => DuplexMode::LongEdge,
+            cef_duplex_mode_t::DUPLEX_MODE_SHORT_EDGE => DuplexMode::ShortEdge
+        }
+    }
+}
+
+impl From<DuplexMode> for cef_duplex_mode_t {
+    fn from(value: DuplexMode) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&DuplexMode> for cef_duplex_mode_t {
+    fn from(value: &DuplexMode) -> Self {
+        match value {
+            DuplexMode::Unknown => cef_duplex_mode_t::DUPLEX_MODE_UNKNOWN,
+            DuplexMode::Simplex => cef_duplex_mode_t::DUPLEX_MODE_SIMPLEX,
+            DuplexMode::LongEdge => cef_duplex_mode_t::DUPLEX_MODE_LONG_EDGE,
+            DuplexMode::ShortEdge => cef_duplex_mode_t::DUPLEX_MODE_SHORT_EDGE
+        }
+    }
+}
+
+// Structure representing print settings.
+ref_counted_ptr!(PrintSettings, cef_print_settings_t);
+
+impl PrintSettings {
+    /// Create a new cef_print_settings_t object.
+    pub fn new() -> Self {
+        unsafe { Self::from_ptr_unchecked(cef_print_settings_create()) }
+    }
+
+    /// Returns true (1) if this object is valid. Do not call any other functions
+    /// if this function returns false (0).
+    pub fn is_valid(&self) -> Result<bool> {
+        try_c!(self, is_valid, { Ok(is_valid(self.as_ptr()) != 0) })
+    }
+
+    /// Returns true (1) if the values of this object are read-only. Some APIs may
+    /// expose read-only objects.
+    pub fn is_read_only(&self) -> Result<bool> {
+        try_c!(self, is_read_only, { Ok(is_read_only(self.as_ptr()) != 0) })
+    }
+
+    /// Set the page orientation.
+    pub fn set_orientation(&self, landscape: bool) -> Result<()> {
+        try_c!(self, set_orientation, {
+            Ok(set_orientation(self.as_ptr(), landscape as c_int))
+        })
+    }
+
+    /// Returns true (1) if the orientation is landscape.
+    pub fn is_landscape(&self) -> Result<bool> {
+        try_c!(self, is_landscape, { Ok(is_landscape(self.as_ptr()) != 0) })
+    }
+
+    /// Set the printer printable area in device units. Some platforms already
+    /// provide flipped area. Set |landscape_needs_flip| to false (0) on those
+    /// platforms to avoid double flipping.
+    pub fn set_printer_printable_area(
+        &self,
+        physical_size_device_units: &Size,
+        printable_area_device_units: &Rect,
+        landscape_needs_flip: bool
+    ) -> Result<()> {
+        try_c!(self, set_printer_printable_area, {
+            let physical_size_device_units: cef_size_t = physical_size_device_units.into();
+            let printable_area_device_units: cef_rect_t = printable_area_device_units.into();
+
+            Ok(set_printer_printable_area(
+                self.as_ptr(),
+                &physical_size_device_units,
+                &printable_area_device_units,
+                landscape_needs_flip as c_int
+            ))
+        })
+    }
+
+    /// Set the device name.
+    pub fn set_device_name(&self, name: &str) -> Result<()> {
+        try_c!(self, set_device_name, {
+            let name = CefString::new(name);
+
+            Ok(set_device_name(self.as_ptr(), name.as_ptr()))
+        })
+    }
+
+    /// Get the device name.
+    pub fn get_device_name(&self) -> Result<Option<String>> {
+        try_c!(self, get_device_name, {
+            let s = get_device_name(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s).map(|s| s.into()))
+        })
+    }
+
+    /// Set the DPI (dots per inch).
+    pub fn set_dpi(&self, dpi: i32) -> Result<()> {
+        try_c!(self, set_dpi, { Ok(set_dpi(self.as_ptr(), dpi as c_int)) })
+    }
+
+    /// Get the DPI (dots per inch).
+    pub fn get_dpi(&self) -> Result<i32> {
+        try_c!(self, get_dpi, { Ok(get_dpi(self.as_ptr())) })
+    }
+
+    /// Set the page ranges.
+    pub fn set_page_ranges(&self, ranges: &[Range]) -> Result<()> {
+        try_c!(self, set_page_ranges, {
+            let ranges: Vec<cef_range_t> = ranges
+                .iter()
+                .map(|range| range.into())
+                .collect();
+
+            Ok(set_page_ranges(
+                self.as_ptr(),
+                ranges.len(),
+                ranges.as_ptr()
+            ))
+        })
+    }
+
+    /// Returns the number of page ranges that currently exist.
+    pub fn get_page_ranges_count(&self) -> Result<usize> {
+        try_c!(self, get_page_ranges_count, {
+            Ok(get_page_ranges_count(self.as_ptr()))
+        })
+    }
+
+    /// Retrieve the page ranges.
+    pub fn get_page_ranges(&self) -> Result<Vec<Range>> {
+        let count = self.get_page_ranges_count()?;
+
+        try_c!(self, get_page_ranges, {
+            let mut ranges = vec![cef_range_t { from: 0, to: 0 }; count];
+            let mut count = ranges.len();
+
+            get_page_ranges(self.as_ptr(), &mut count, ranges.as_mut_ptr());
+
+            Ok(ranges
+                .iter()
+                .take(count)
+                .map(Range::from)
+                .collect())
+        })
+    }
+
+    /// Set whether only the selection will be printed.
+    pub fn set_selection_only(&self, selection_only: bool) -> Result<()> {
+        try_c!(self, set_selection_only, {
+            Ok(set_selection_only(self.as_ptr(), selection_only as c_int))
+        })
+    }
+
+    /// Returns true (1) if only the selection will be printed.
+    pub fn is_selection_only(&self) -> Result<bool> {
+        try_c!(self, is_selection_only, {
+            Ok(is_selection_only(self.as_ptr()) != 0)
+        })
+    }
+
+    /// Set whether pages will be collated.
+    pub fn set_collate(&self, collate: bool) -> Result<()> {
+        try_c!(self, set_collate, {
+            Ok(set_collate(self.as_ptr(), collate as c_int))
+        })
+    }
+
+    /// Returns true (1) if pages will be collated.
+    pub fn will_collate(&self) -> Result<bool> {
+        try_c!(self, will_collate, { Ok(will_collate(self.as_ptr()) != 0) })
+    }
+
+    /// Set the color model.
+    pub fn set_color_model(&self, model: ColorModel) -> Result<()> {
+        try_c!(self, set_color_model, {
+            Ok(set_color_model(self.as_ptr(), model.into()))
+        })
+    }
+
+    /// Get the color model.
+    pub fn get_color_model(&self) -> Result<ColorModel> {
+        try_c!(self, get_color_model, {
+            Ok(get_color_model(self.as_ptr()).into())
+        })
+    }
+
+    /// Set the number of copies.
+    pub fn set_copies(&self, copies: i32) -> Result<()> {
+        try_c!(self, set_copies, {
+            Ok(set_copies(self.as_ptr(), copies as c_int))
+        })
+    }
+
+    /// Get the number of copies.
+    pub fn get_copies(&self) -> Result<i32> {
+        try_c!(self, get_copies, { Ok(get_copies(self.as_ptr())) })
+    }
+
+    /// Set the duplex mode.
+    pub fn set_duplex_mode(&self, mode: DuplexMode) -> Result<()> {
+        try_c!(self, set_duplex_mode, {
+            Ok(set_duplex_mode(self.as_ptr(), mode.into()))
+        })
+    }
+
+    /// Get the duplex mode.
+    pub fn get_duplex_mode(&self) -> Result<DuplexMode> {
+        try_c!(self, get_duplex_mode, {
+            Ok(get_duplex_mode(self.as_ptr()).into())
+        })
+    }
+}
+
+impl Default for PrintSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
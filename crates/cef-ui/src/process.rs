@@ -1,6 +1,9 @@
-use crate::{ref_counted_ptr, try_c, CefString, ListValue, SharedMemoryRegion};
-use anyhow::Result;
-use cef_ui_sys::{cef_process_id_t, cef_process_message_create, cef_process_message_t};
+use crate::{ref_counted_ptr, try_c, CefString, ListValue, Result, SharedMemoryRegion};
+use cef_ui_sys::{
+    cef_process_id_t, cef_process_message_create, cef_process_message_t,
+    cef_shared_process_message_builder_create, cef_shared_process_message_builder_t
+};
+use std::ffi::c_void;
 
 /// Existing process IDs.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -102,3 +105,51 @@ impl ProcessMessage {
         })
     }
 }
+
+// Structure that builds a cef_process_message_t containing a shared memory
+// region. This structure is not thread-safe but may be used exclusively on a
+// different thread from the one which constructed it.
+ref_counted_ptr!(
+    SharedProcessMessageBuilder,
+    cef_shared_process_message_builder_t
+);
+
+impl SharedProcessMessageBuilder {
+    /// Creates a new cef_shared_process_message_builder_t with the specified
+    /// |name| and shared memory region of specified |byte_size|.
+    pub fn new(name: &str, byte_size: usize) -> Self {
+        unsafe {
+            let name = CefString::new(name);
+
+            Self::from_ptr_unchecked(cef_shared_process_message_builder_create(
+                name.as_ptr(),
+                byte_size
+            ))
+        }
+    }
+
+    /// Returns true (1) if the builder is valid.
+    pub fn is_valid(&self) -> Result<bool> {
+        try_c!(self, is_valid, { Ok(is_valid(self.as_ptr()) != 0) })
+    }
+
+    /// Returns the size of the shared memory region.
+    pub fn size(&self) -> Result<usize> {
+        try_c!(self, size, { Ok(size(self.as_ptr())) })
+    }
+
+    /// Returns the pointer to the writable memory. Returns nullptr for invalid
+    /// instances. The returned pointer is only valid for the life span of this
+    /// object.
+    pub fn memory(&self) -> Result<*mut c_void> {
+        try_c!(self, memory, { Ok(memory(self.as_ptr())) })
+    }
+
+    /// Creates a new cef_process_message_t from the data provided to the builder.
+    /// Returns nullptr for invalid instances. Invalidates the builder instance.
+    pub fn build(&self) -> Result<Option<ProcessMessage>> {
+        try_c!(self, build, {
+            Ok(ProcessMessage::from_ptr(build(self.as_ptr())))
+        })
+    }
+}
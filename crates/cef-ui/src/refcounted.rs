@@ -1,3 +1,4 @@
+use crate::unwind::catch_unwind_or;
 use cef_ui_sys::cef_base_ref_counted_t;
 use std::{
     ffi::c_int,
@@ -34,6 +35,7 @@ macro_rules! ref_counted {
 }
 
 /// A smart pointer that wraps RefCounted objects.
+#[repr(transparent)]
 pub struct RefCountedPtr<T: RefCounted> {
     pub value: NonNull<T>
 }
@@ -281,8 +283,12 @@ impl<W: Wrappable> Wrapped<W> {
     /// Decrements the reference count. If the reference count
     /// reaches 0, then the object is deallocated (c version).
     unsafe extern "C" fn c_release(this: *mut cef_base_ref_counted_t) -> c_int {
-        let this = &mut *(this as *mut Self);
+        // Only dropping the Rust object can panic, and by then the object has
+        // been released, so that is what is reported to CEF.
+        catch_unwind_or("Wrapped::c_release", 1, || {
+            let this = &mut *(this as *mut Self);
 
-        this.release() as c_int
+            this.release() as c_int
+        })
     }
 }
@@ -1,7 +1,8 @@
 use crate::{
-    ref_counted_ptr, AccessibilityHandler, Browser, CefString, DragData, DragOperations,
-    HorizontalAlignment, PaintElementType, Point, Range, Rect, RefCountedPtr, ScreenInfo, Size,
-    TextInputMode, TouchHandleState, Wrappable, Wrapped
+    debug_assert_currently_on, ref_counted_ptr, unwind::catch_unwind_or, AccessibilityHandler,
+    Browser, CefString, DragData, DragOperations, HorizontalAlignment, PaintElementType, Point,
+    Range, Rect, RefCountedPtr, ScreenInfo, SharedTextureHandle, Size, TextInputMode, ThreadId,
+    TouchHandleState, Wrappable, Wrapped
 };
 use cef_ui_sys::{
     cef_accessibility_handler_t, cef_browser_t, cef_drag_data_t, cef_drag_operations_mask_t,
@@ -9,6 +10,7 @@ use cef_ui_sys::{
     cef_render_handler_t, cef_screen_info_t, cef_size_t, cef_string_t, cef_text_input_mode_t,
     cef_touch_handle_state_t
 };
+use parking_lot::Mutex;
 use std::{
     ffi::{c_int, c_void},
     mem::zeroed,
@@ -16,17 +18,37 @@ use std::{
     slice::from_raw_parts
 };
 
+/// Called with the BGRA buffer, its width and height, and the view rectangle
+/// of the next view paint of a browser.
+pub(crate) type PaintWaiter = Box<dyn FnOnce(&[u8], usize, usize, &Rect) + Send + 'static>;
+
+/// The paint waiters by browser identifier.
+static PAINT_WAITERS: Mutex<Vec<(i32, PaintWaiter)>> = Mutex::new(Vec::new());
+
+/// Calls |waiter| on the UI thread with the next view paint of the windowless
+/// browser |browser_id|.
+#[cfg(feature = "tokio")]
+pub(crate) fn on_next_paint(browser_id: i32, waiter: PaintWaiter) {
+    PAINT_WAITERS
+        .lock()
+        .push((browser_id, waiter));
+}
+
 /// Implement this structure to handle events when window rendering is disabled.
 /// The functions of this structure will be called on the UI thread.
 pub trait RenderHandlerCallbacks: Send + Sync + 'static {
     /// Return the handler for accessibility notifications. If no handler is
     /// provided the default implementation will be used.
-    fn get_accessibility_handler(&mut self) -> Option<AccessibilityHandler>;
+    fn get_accessibility_handler(&mut self) -> Option<AccessibilityHandler> {
+        None
+    }
 
     // /// Called to retrieve the root window rectangle in screen DIP coordinates.
     // /// Return true (1) if the rectangle was provided. If this function returns
     // /// false (0) the rectangle from GetViewRect will be used.
-    fn get_root_screen_rect(&mut self, browser: Browser) -> Option<Rect>;
+    fn get_root_screen_rect(&mut self, _browser: Browser) -> Option<Rect> {
+        None
+    }
 
     /// Called to retrieve the view rectangle in screen DIP coordinates. This
     /// function must always provide a non-NULL rectangle.
@@ -36,7 +58,9 @@ pub trait RenderHandlerCallbacks: Send + Sync + 'static {
     /// coordinates. Windows/Linux should provide screen device (pixel)
     /// coordinates and MacOS should provide screen DIP coordinates. Return true
     /// (1) if the requested coordinates were provided.
-    fn get_screen_point(&mut self, browser: Browser, view: &Point) -> Option<Point>;
+    fn get_screen_point(&mut self, _browser: Browser, _view: &Point) -> Option<Point> {
+        None
+    }
 
     /// Called to allow the client to fill in the CefScreenInfo object with
     /// appropriate values. Return true (1) if the |screen_info| structure has
@@ -44,16 +68,19 @@ pub trait RenderHandlerCallbacks: Send + Sync + 'static {
     ///
     /// If the screen info rectangle is left NULL the rectangle from GetViewRect
     /// will be used. If the rectangle is still NULL or invalid popups may not be
-    /// drawn correctly.
-    fn get_screen_info(&mut self, browser: Browser) -> Option<ScreenInfo>;
+    /// drawn correctly. ScreenInfo::new fills in sensible defaults for a color
+    /// screen, which can then be adjusted with its builder methods.
+    fn get_screen_info(&mut self, _browser: Browser) -> Option<ScreenInfo> {
+        None
+    }
 
     /// Called when the browser wants to show or hide the popup widget. The popup
     /// should be shown if |show| is true (1) and hidden if |show| is false (0).
-    fn on_popup_show(&mut self, browser: Browser, show: bool);
+    fn on_popup_show(&mut self, _browser: Browser, _show: bool) {}
 
     /// Called when the browser wants to move or resize the popup widget. |rect|
     /// contains the new location and size in view coordinates.
-    fn on_popup_size(&mut self, browser: Browser, rect: &Rect);
+    fn on_popup_size(&mut self, _browser: Browser, _rect: &Rect) {}
 
     /// Called when an element should be painted. Pixel values passed to this
     /// function are scaled relative to view coordinates based on the value of
@@ -63,16 +90,19 @@ pub trait RenderHandlerCallbacks: Send + Sync + 'static {
     /// of rectangles in pixel coordinates that need to be repainted. |buffer|
     /// will be |width|*|height|*4 bytes in size and represents a BGRA image with
     /// an upper-left origin. This function is only called when
-    /// cef_window_tInfo::shared_texture_enabled is set to false (0).
+    /// cef_window_tInfo::shared_texture_enabled is set to false (0). Pixels use
+    /// premultiplied alpha, which is only meaningful when the browser was created
+    /// with a fully transparent BrowserSettings::background_color.
     fn on_paint(
         &mut self,
-        browser: Browser,
-        paint_element_type: PaintElementType,
-        dirty_rects: &[Rect],
-        buffer: &[u8],
-        width: usize,
-        height: usize
-    );
+        _browser: Browser,
+        _paint_element_type: PaintElementType,
+        _dirty_rects: &[Rect],
+        _buffer: &[u8],
+        _width: usize,
+        _height: usize
+    ) {
+    }
 
     /// Called when an element has been rendered to the shared texture handle.
     /// |type| indicates whether the element is the view or the popup widget.
@@ -80,23 +110,28 @@ pub trait RenderHandlerCallbacks: Send + Sync + 'static {
     /// to be repainted. |shared_handle| is the handle for a D3D11 Texture2D that
     /// can be accessed via ID3D11Device using the OpenSharedResource function.
     /// This function is only called when cef_window_tInfo::shared_texture_enabled
-    /// is set to true (1), and is currently only supported on Windows.
+    /// is set to true (1), and is currently only supported on Windows. With the
+    /// wgpu feature, SharedTextureHandle::copy_to_texture copies the texture
+    /// into a wgpu texture.
     fn on_accelerated_paint(
         &mut self,
-        browser: Browser,
-        paint_element_type: PaintElementType,
-        dirty_rects: &[Rect],
-        shared_handle: *mut c_void
-    );
+        _browser: Browser,
+        _paint_element_type: PaintElementType,
+        _dirty_rects: &[Rect],
+        _shared_handle: SharedTextureHandle
+    ) {
+    }
 
     /// Called to retrieve the size of the touch handle for the specified
     /// |orientation|.
-    fn get_touch_handle_size(&mut self, browser: Browser, orientation: HorizontalAlignment)
-    -> Size;
+    fn get_touch_handle_size(&mut self, _browser: Browser, _orientation: HorizontalAlignment)
+    -> Size {
+        Size::default()
+    }
 
     /// Called when touch handle state is updated. The client is responsible for
     /// rendering the touch handles.
-    fn on_touch_handle_state_changed(&mut self, browser: Browser, state: &TouchHandleState);
+    fn on_touch_handle_state_changed(&mut self, _browser: Browser, _state: &TouchHandleState) {}
 
     /// Called when the user starts dragging content in the web view. Contextual
     /// information about the dragged content is supplied by |drag_data|. (|x|,
@@ -112,45 +147,49 @@ pub trait RenderHandlerCallbacks: Send + Sync + 'static {
     /// operation has ended.
     fn start_dragging(
         &mut self,
-        browser: Browser,
-        drag_data: DragData,
-        allowed_ops: DragOperations,
-        drag_start: &Point
-    ) -> bool;
+        _browser: Browser,
+        _drag_data: DragData,
+        _allowed_ops: DragOperations,
+        _drag_start: &Point
+    ) -> bool {
+        false
+    }
 
     /// Called when the web view wants to update the mouse cursor during a drag &
     /// drop operation. |operation| describes the allowed operation (none, move,
     /// copy, link).
-    fn update_drag_cursor(&mut self, browser: Browser, operation: DragOperations);
+    fn update_drag_cursor(&mut self, _browser: Browser, _operation: DragOperations) {}
 
     /// Called when the scroll offset has changed.
-    fn on_scroll_offset_changed(&mut self, browser: Browser, x: f64, y: f64);
+    fn on_scroll_offset_changed(&mut self, _browser: Browser, _x: f64, _y: f64) {}
 
     /// Called when the IME composition range has changed. |selected_range| is the
     /// range of characters that have been selected. |character_bounds| is the
     /// bounds of each character in view coordinates.
     fn on_ime_composition_range_changed(
         &mut self,
-        browser: Browser,
-        selected_range: &Range,
-        character_bounds: &[Rect]
-    );
+        _browser: Browser,
+        _selected_range: &Range,
+        _character_bounds: &[Rect]
+    ) {
+    }
 
     /// Called when text selection has changed for the specified |browser|.
     /// |selected_text| is the currently selected text and |selected_range| is the
     /// character range.
     fn on_text_selection_changed(
         &mut self,
-        browser: Browser,
-        selected_text: Option<String>,
-        selected_range: &Range
-    );
+        _browser: Browser,
+        _selected_text: Option<String>,
+        _selected_range: &Range
+    ) {
+    }
 
     /// Called when an on-screen keyboard should be shown or hidden for the
     /// specified |browser|. |input_mode| specifies what kind of keyboard should
     /// be opened. If |input_mode| is CEF_TEXT_INPUT_MODE_NONE, any existing
     /// keyboard for this browser should be hidden.
-    fn on_virtual_keyboard_requested(&mut self, browser: Browser, input_mode: TextInputMode);
+    fn on_virtual_keyboard_requested(&mut self, _browser: Browser, _input_mode: TextInputMode) {}
 }
 
 // Implement this structure to handle events when window rendering is disabled.
@@ -164,11 +203,11 @@ impl RenderHandler {
 }
 
 /// Translates CEF -> Rust callbacks.
-struct RenderHandlerWrapper(Box<dyn RenderHandlerCallbacks>);
+struct RenderHandlerWrapper<C: RenderHandlerCallbacks>(C);
 
-impl RenderHandlerWrapper {
-    pub fn new<C: RenderHandlerCallbacks>(delegate: C) -> Self {
-        Self(Box::new(delegate))
+impl<C: RenderHandlerCallbacks> RenderHandlerWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
     }
 
     /// Return the handler for accessibility notifications. If no handler is
@@ -176,12 +215,20 @@ impl RenderHandlerWrapper {
     unsafe extern "C" fn c_get_accessibility_handler(
         this: *mut cef_render_handler_t
     ) -> *mut cef_accessibility_handler_t {
-        let this: &mut Self = Wrapped::wrappable(this);
-
-        this.0
-            .get_accessibility_handler()
-            .map(|handler| handler.into_raw())
-            .unwrap_or(null_mut())
+        catch_unwind_or(
+            "RenderHandlerWrapper::c_get_accessibility_handler",
+            null_mut(),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+
+                this.0
+                    .get_accessibility_handler()
+                    .map(|handler| handler.into_raw())
+                    .unwrap_or(null_mut())
+            }
+        )
     }
 
     /// Called to retrieve the root window rectangle in screen DIP coordinates.
@@ -192,15 +239,19 @@ impl RenderHandlerWrapper {
         browser: *mut cef_browser_t,
         rect: *mut cef_rect_t
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let local_rect = this.0.get_root_screen_rect(browser);
+        catch_unwind_or("RenderHandlerWrapper::c_get_root_screen_rect", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
 
-        if let Some(local_rect) = &local_rect {
-            *rect = local_rect.into();
-        }
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let local_rect = this.0.get_root_screen_rect(browser);
 
-        local_rect.is_some() as c_int
+            if let Some(local_rect) = &local_rect {
+                *rect = local_rect.into();
+            }
+
+            local_rect.is_some() as c_int
+        })
     }
 
     /// Called to retrieve the view rectangle in screen DIP coordinates. This
@@ -210,10 +261,14 @@ impl RenderHandlerWrapper {
         browser: *mut cef_browser_t,
         rect: *mut cef_rect_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
+        catch_unwind_or("RenderHandlerWrapper::c_get_view_rect", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
 
-        *rect = this.0.get_view_rect(browser).into();
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+
+            *rect = this.0.get_view_rect(browser).into();
+        })
     }
 
     /// Called to retrieve the translation from view DIP coordinates to screen
@@ -228,22 +283,26 @@ impl RenderHandlerWrapper {
         screen_x: *mut c_int,
         screen_y: *mut c_int
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let local_screen = this.0.get_screen_point(
-            browser,
-            &Point {
-                x: view_x,
-                y: view_y
+        catch_unwind_or("RenderHandlerWrapper::c_get_screen_point", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let local_screen = this.0.get_screen_point(
+                browser,
+                &Point {
+                    x: view_x,
+                    y: view_y
+                }
+            );
+
+            if let Some(local_screen) = &local_screen {
+                *screen_x = local_screen.x;
+                *screen_y = local_screen.y;
             }
-        );
-
-        if let Some(local_screen) = &local_screen {
-            *screen_x = local_screen.x;
-            *screen_y = local_screen.y;
-        }
 
-        local_screen.is_some() as c_int
+            local_screen.is_some() as c_int
+        })
     }
 
     /// Called to allow the client to fill in the CefScreenInfo object with
@@ -258,15 +317,19 @@ impl RenderHandlerWrapper {
         browser: *mut cef_browser_t,
         screen_info: *mut cef_screen_info_t
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let local_screen_info = this.0.get_screen_info(browser);
+        catch_unwind_or("RenderHandlerWrapper::c_get_screen_info", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
 
-        if let Some(local_screen_info) = &local_screen_info {
-            *screen_info = local_screen_info.into();
-        }
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let local_screen_info = this.0.get_screen_info(browser);
+
+            if let Some(local_screen_info) = &local_screen_info {
+                *screen_info = local_screen_info.into();
+            }
 
-        local_screen_info.is_some() as c_int
+            local_screen_info.is_some() as c_int
+        })
     }
 
     /// Called when the browser wants to show or hide the popup widget. The popup
@@ -276,11 +339,15 @@ impl RenderHandlerWrapper {
         browser: *mut cef_browser_t,
         show: c_int
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
+        catch_unwind_or("RenderHandlerWrapper::c_on_popup_show", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
 
-        this.0
-            .on_popup_show(browser, show != 0);
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+
+            this.0
+                .on_popup_show(browser, show != 0);
+        })
     }
 
     /// Called when the browser wants to move or resize the popup widget. |rect|
@@ -290,11 +357,15 @@ impl RenderHandlerWrapper {
         browser: *mut cef_browser_t,
         rect: *const cef_rect_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
+        catch_unwind_or("RenderHandlerWrapper::c_on_popup_size", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
 
-        this.0
-            .on_popup_size(browser, &(*rect).into());
+            this.0
+                .on_popup_size(browser, &(*rect).into());
+        })
     }
 
     /// Called when an element should be painted. Pixel values passed to this
@@ -316,15 +387,23 @@ impl RenderHandlerWrapper {
         width: c_int,
         height: c_int
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let dirty_rects = from_raw_parts(dirty_rects as *const Rect, dirty_rects_count);
-        let width = width as usize;
-        let height = height as usize;
-        let buffer = from_raw_parts(buffer as *const u8, width * height * 4);
+        catch_unwind_or("RenderHandlerWrapper::c_on_paint", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let dirty_rects = from_raw_parts(dirty_rects as *const Rect, dirty_rects_count);
+            let width = width as usize;
+            let height = height as usize;
+            let buffer = from_raw_parts(buffer as *const u8, width * height * 4);
+
+            if type_ == cef_paint_element_type_t::PET_VIEW {
+                notify_paint_waiters(&mut this.0, &browser, buffer, width, height);
+            }
 
-        this.0
-            .on_paint(browser, type_.into(), dirty_rects, buffer, width, height);
+            this.0
+                .on_paint(browser, type_.into(), dirty_rects, buffer, width, height);
+        })
     }
 
     /// Called when an element has been rendered to the shared texture handle.
@@ -342,12 +421,21 @@ impl RenderHandlerWrapper {
         dirty_rects: *const cef_rect_t,
         shared_handle: *mut c_void
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let dirty_rects = from_raw_parts(dirty_rects as *const Rect, dirty_rects_count);
-
-        this.0
-            .on_accelerated_paint(browser, type_.into(), dirty_rects, shared_handle);
+        catch_unwind_or("RenderHandlerWrapper::c_on_accelerated_paint", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let dirty_rects = from_raw_parts(dirty_rects as *const Rect, dirty_rects_count);
+
+            this.0
+                .on_accelerated_paint(
+                    browser,
+                    type_.into(),
+                    dirty_rects,
+                    SharedTextureHandle::new(shared_handle)
+                );
+        })
     }
 
     /// Called to retrieve the size of the touch handle for the specified
@@ -358,13 +446,17 @@ impl RenderHandlerWrapper {
         orientation: cef_horizontal_alignment_t,
         size: *mut cef_size_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
+        catch_unwind_or("RenderHandlerWrapper::c_get_touch_handle_size", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
 
-        *size = this
-            .0
-            .get_touch_handle_size(browser, orientation.into())
-            .into();
+            *size = this
+                .0
+                .get_touch_handle_size(browser, orientation.into())
+                .into();
+        })
     }
 
     /// Called when touch handle state is updated. The client is responsible for
@@ -374,11 +466,19 @@ impl RenderHandlerWrapper {
         browser: *mut cef_browser_t,
         state: *const cef_touch_handle_state_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
+        catch_unwind_or(
+            "RenderHandlerWrapper::c_on_touch_handle_state_changed",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
 
-        this.0
-            .on_touch_handle_state_changed(browser, &(*state).into());
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+
+                this.0
+                    .on_touch_handle_state_changed(browser, &(*state).into());
+            }
+        )
     }
 
     /// Called when the user starts dragging content in the web view. Contextual
@@ -401,13 +501,17 @@ impl RenderHandlerWrapper {
         x: c_int,
         y: c_int
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let drag_data = DragData::from_ptr_unchecked(drag_data);
+        catch_unwind_or("RenderHandlerWrapper::c_start_dragging", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let drag_data = DragData::from_ptr_unchecked(drag_data);
 
-        this.0
-            .start_dragging(browser, drag_data, allowed_ops.into(), &Point { x, y })
-            as c_int
+            this.0
+                .start_dragging(browser, drag_data, allowed_ops.into(), &Point { x, y })
+                as c_int
+        })
     }
 
     /// Called when the web view wants to update the mouse cursor during a drag &
@@ -418,11 +522,15 @@ impl RenderHandlerWrapper {
         browser: *mut cef_browser_t,
         operation: cef_drag_operations_mask_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
+        catch_unwind_or("RenderHandlerWrapper::c_update_drag_cursor", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
 
-        this.0
-            .update_drag_cursor(browser, operation.into());
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+
+            this.0
+                .update_drag_cursor(browser, operation.into());
+        })
     }
 
     /// Called when the scroll offset has changed.
@@ -432,11 +540,19 @@ impl RenderHandlerWrapper {
         x: f64,
         y: f64
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
+        catch_unwind_or(
+            "RenderHandlerWrapper::c_on_scroll_offset_changed",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
 
-        this.0
-            .on_scroll_offset_changed(browser, x, y);
+                this.0
+                    .on_scroll_offset_changed(browser, x, y);
+            }
+        )
     }
 
     /// Called when the IME composition range has changed. |selected_range| is the
@@ -449,13 +565,25 @@ impl RenderHandlerWrapper {
         character_bounds_count: usize,
         character_bounds: *const cef_rect_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let character_bounds =
-            from_raw_parts(character_bounds as *const Rect, character_bounds_count);
-
-        this.0
-            .on_ime_composition_range_changed(browser, &(*selected_range).into(), character_bounds);
+        catch_unwind_or(
+            "RenderHandlerWrapper::c_on_ime_composition_range_changed",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+                let character_bounds =
+                    from_raw_parts(character_bounds as *const Rect, character_bounds_count);
+
+                this.0
+                    .on_ime_composition_range_changed(
+                        browser,
+                        &(*selected_range).into(),
+                        character_bounds
+                    );
+            }
+        )
     }
 
     /// Called when text selection has changed for the specified |browser|.
@@ -467,12 +595,21 @@ impl RenderHandlerWrapper {
         selected_text: *const cef_string_t,
         selected_range: *const cef_range_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let selected_text: Option<String> = CefString::from_ptr(selected_text).map(|s| s.into());
-
-        this.0
-            .on_text_selection_changed(browser, selected_text, &(*selected_range).into());
+        catch_unwind_or(
+            "RenderHandlerWrapper::c_on_text_selection_changed",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+                let selected_text: Option<String> =
+                    CefString::from_ptr(selected_text).map(|s| s.into());
+
+                this.0
+                    .on_text_selection_changed(browser, selected_text, &(*selected_range).into());
+            }
+        )
     }
 
     /// Called when an on-screen keyboard should be shown or hidden for the
@@ -484,15 +621,23 @@ impl RenderHandlerWrapper {
         browser: *mut cef_browser_t,
         input_mode: cef_text_input_mode_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
+        catch_unwind_or(
+            "RenderHandlerWrapper::c_on_virtual_keyboard_requested",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
 
-        this.0
-            .on_virtual_keyboard_requested(browser, input_mode.into());
+                this.0
+                    .on_virtual_keyboard_requested(browser, input_mode.into());
+            }
+        )
     }
 }
 
-impl Wrappable for RenderHandlerWrapper {
+impl<C: RenderHandlerCallbacks> Wrappable for RenderHandlerWrapper<C> {
     type Cef = cef_render_handler_t;
 
     /// Converts this to a smart pointer.
@@ -522,3 +667,42 @@ impl Wrappable for RenderHandlerWrapper {
         )
     }
 }
+
+/// Calls the paint waiters of |browser|, if there are any.
+fn notify_paint_waiters<C: RenderHandlerCallbacks>(
+    callbacks: &mut C,
+    browser: &Browser,
+    buffer: &[u8],
+    width: usize,
+    height: usize
+) {
+    let waiters = {
+        let mut waiters = PAINT_WAITERS.lock();
+
+        if waiters.is_empty() {
+            return;
+        }
+
+        let Ok(browser_id) = browser.get_identifier() else {
+            return;
+        };
+
+        let (ready, pending) = waiters
+            .drain(..)
+            .partition(|(id, _)| *id == browser_id);
+
+        *waiters = pending;
+
+        ready
+    };
+
+    if waiters.is_empty() {
+        return;
+    }
+
+    let view_rect = callbacks.get_view_rect(browser.clone());
+
+    for (_, waiter) in waiters {
+        waiter(buffer, width, height, &view_rect);
+    }
+}
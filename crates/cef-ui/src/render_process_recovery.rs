@@ -0,0 +1,300 @@
+use crate::{
+    AuthCallback, Browser, Callback, ErrorCode, Frame, Request, RequestHandler,
+    RequestHandlerCallbacks, ResourceRequestHandler, Result, SelectClientCertificateCallback,
+    SslInfo, TerminationStatus, WindowOpenDisposition, X509Certificate
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant}
+};
+use tracing::{error, warn};
+
+impl TerminationStatus {
+    /// Returns true if the process died on its own (crashed, ran out of memory
+    /// or exited with an error) rather than being killed from the outside.
+    pub fn is_crash(&self) -> bool {
+        match self {
+            TerminationStatus::AbnormalTermination => true,
+            TerminationStatus::ProcessWasKilled => false,
+            TerminationStatus::ProcessCrashed => true,
+            TerminationStatus::ProcessOom => true
+        }
+    }
+}
+
+/// What AutoReloadPolicy decided to do about a terminated render process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RecoveryAction {
+    /// The browser was reloaded, which starts a new render process.
+    Reload,
+
+    /// The termination status is not one the policy reloads on.
+    Ignore,
+
+    /// The browser has been reloaded too often recently and is left as is.
+    GiveUp
+}
+
+/// Decides whether to reload a browser whose render process terminated. To
+/// avoid reloading a page that reliably crashes its renderer forever, a
+/// browser is only reloaded |max_reloads| times within |period|.
+#[derive(Clone, Debug)]
+pub struct AutoReloadPolicy {
+    statuses:     Vec<TerminationStatus>,
+    max_reloads:  usize,
+    period:       Duration,
+    ignore_cache: bool,
+    reloads:      HashMap<i32, VecDeque<Instant>>
+}
+
+impl AutoReloadPolicy {
+    /// Default number of reloads allowed within DEFAULT_PERIOD.
+    pub const DEFAULT_MAX_RELOADS: usize = 3;
+
+    /// Default period over which reloads are counted.
+    pub const DEFAULT_PERIOD: Duration = Duration::from_secs(60);
+
+    /// Reloads after crashes, out of memory errors and abnormal exits, but not
+    /// after the process was killed (e.g. from the task manager).
+    pub fn new() -> Self {
+        Self {
+            statuses:     vec![
+                TerminationStatus::AbnormalTermination,
+                TerminationStatus::ProcessCrashed,
+                TerminationStatus::ProcessOom
+            ],
+            max_reloads:  Self::DEFAULT_MAX_RELOADS,
+            period:       Self::DEFAULT_PERIOD,
+            ignore_cache: false,
+            reloads:      HashMap::new()
+        }
+    }
+
+    /// Whether to reload after the render process terminated with |status|.
+    pub fn reload_on(mut self, status: TerminationStatus, reload: bool) -> Self {
+        self.statuses
+            .retain(|s| *s != status);
+
+        if reload {
+            self.statuses.push(status);
+        }
+
+        self
+    }
+
+    /// Allow at most |max_reloads| reloads of a browser within |period|.
+    pub fn limit(mut self, max_reloads: usize, period: Duration) -> Self {
+        self.max_reloads = max_reloads;
+        self.period = period;
+        self
+    }
+
+    /// Whether to bypass the cache when reloading.
+    pub fn ignore_cache(mut self, ignore_cache: bool) -> Self {
+        self.ignore_cache = ignore_cache;
+        self
+    }
+
+    /// Applies the policy to |browser|, whose render process terminated with
+    /// |status|. Call this from on_render_process_terminated.
+    pub fn apply(
+        &mut self,
+        browser: &Browser,
+        status: TerminationStatus
+    ) -> Result<RecoveryAction> {
+        if !self.statuses.contains(&status) {
+            return Ok(RecoveryAction::Ignore);
+        }
+
+        let now = Instant::now();
+        let reloads = self
+            .reloads
+            .entry(browser.get_identifier()?)
+            .or_default();
+
+        while let Some(reload) = reloads.front() {
+            match now.duration_since(*reload) > self.period {
+                true => reloads.pop_front(),
+                false => break
+            };
+        }
+
+        if reloads.len() >= self.max_reloads {
+            return Ok(RecoveryAction::GiveUp);
+        }
+
+        reloads.push_back(now);
+
+        match self.ignore_cache {
+            true => browser.reload_ignore_cache()?,
+            false => browser.reload()?
+        }
+
+        Ok(RecoveryAction::Reload)
+    }
+}
+
+impl Default for AutoReloadPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+type GiveUpHandler = Box<dyn FnMut(&Browser, TerminationStatus) + Send + Sync + 'static>;
+
+/// A RequestHandlerCallbacks implementation that recovers from render process
+/// crashes with an AutoReloadPolicy. All callbacks are forwarded to |delegate|,
+/// which sees on_render_process_terminated before the policy is applied.
+pub struct RenderProcessRecovery<C: RequestHandlerCallbacks> {
+    delegate: C,
+    policy:   AutoReloadPolicy,
+    give_up:  Option<GiveUpHandler>
+}
+
+impl<C: RequestHandlerCallbacks> RenderProcessRecovery<C> {
+    pub fn new(delegate: C, policy: AutoReloadPolicy) -> Self {
+        Self {
+            delegate,
+            policy,
+            give_up: None
+        }
+    }
+
+    /// Calls |f| when the policy gives up on a browser, e.g. to load an error
+    /// page explaining that the page keeps crashing.
+    pub fn on_give_up(
+        mut self,
+        f: impl FnMut(&Browser, TerminationStatus) + Send + Sync + 'static
+    ) -> Self {
+        self.give_up = Some(Box::new(f));
+        self
+    }
+
+    /// Converts this to a RequestHandler.
+    pub fn build(self) -> RequestHandler {
+        RequestHandler::new(self)
+    }
+}
+
+impl<C: RequestHandlerCallbacks> RequestHandlerCallbacks for RenderProcessRecovery<C> {
+    fn on_before_browse(
+        &mut self,
+        browser: Browser,
+        frame: Frame,
+        request: Request,
+        user_gesture: bool,
+        is_redirect: bool
+    ) -> bool {
+        self.delegate
+            .on_before_browse(browser, frame, request, user_gesture, is_redirect)
+    }
+
+    fn on_open_urlfrom_tab(
+        &mut self,
+        browser: Browser,
+        frame: Frame,
+        target_url: &str,
+        target_disposition: WindowOpenDisposition,
+        user_gesture: bool
+    ) -> bool {
+        self.delegate
+            .on_open_urlfrom_tab(
+                browser,
+                frame,
+                target_url,
+                target_disposition,
+                user_gesture
+            )
+    }
+
+    fn get_resource_request_handler(
+        &mut self,
+        browser: Browser,
+        frame: Frame,
+        request: Request,
+        is_navigation: bool,
+        is_download: bool,
+        request_initiator: &str,
+        disable_default_handling: &mut bool
+    ) -> Option<ResourceRequestHandler> {
+        self.delegate
+            .get_resource_request_handler(
+                browser,
+                frame,
+                request,
+                is_navigation,
+                is_download,
+                request_initiator,
+                disable_default_handling
+            )
+    }
+
+    fn get_auth_credentials(
+        &mut self,
+        browser: Browser,
+        origin_url: &str,
+        is_proxy: bool,
+        host: &str,
+        port: u16,
+        realm: Option<&str>,
+        scheme: Option<&str>,
+        callback: AuthCallback
+    ) -> bool {
+        self.delegate
+            .get_auth_credentials(
+                browser, origin_url, is_proxy, host, port, realm, scheme, callback
+            )
+    }
+
+    fn on_certificate_error(
+        &mut self,
+        browser: Browser,
+        cert_error: ErrorCode,
+        request_url: &str,
+        ssl_info: SslInfo,
+        callback: Callback
+    ) -> bool {
+        self.delegate
+            .on_certificate_error(browser, cert_error, request_url, ssl_info, callback)
+    }
+
+    fn on_select_client_certificate(
+        &mut self,
+        browser: Browser,
+        is_proxy: bool,
+        host: &str,
+        port: u16,
+        certificates: &[X509Certificate],
+        callback: SelectClientCertificateCallback
+    ) -> bool {
+        self.delegate
+            .on_select_client_certificate(browser, is_proxy, host, port, certificates, callback)
+    }
+
+    fn on_render_view_ready(&mut self, browser: Browser) {
+        self.delegate
+            .on_render_view_ready(browser);
+    }
+
+    fn on_render_process_terminated(&mut self, browser: Browser, status: TerminationStatus) {
+        self.delegate
+            .on_render_process_terminated(browser.clone(), status);
+
+        match self.policy.apply(&browser, status) {
+            Ok(RecoveryAction::GiveUp) => {
+                warn!("Render process terminated ({:?}) too often, not reloading.", status);
+
+                if let Some(give_up) = &mut self.give_up {
+                    give_up(&browser, status);
+                }
+            },
+            Ok(_) => {},
+            Err(e) => error!("Failed to reload after render process termination: {}", e)
+        }
+    }
+
+    fn on_document_available_in_main_frame(&mut self, browser: Browser) {
+        self.delegate
+            .on_document_available_in_main_frame(browser);
+    }
+}
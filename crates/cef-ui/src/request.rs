@@ -1,8 +1,7 @@
 use crate::{
-    ref_counted_ptr, try_c, CefString, CefStringMultiMap, ReferrerPolicy, ResourceType,
-    UrlRequestFlags
+    ref_counted_ptr, try_c, CefStr, CefString, CefStringMultiMap, ReferrerPolicy, ResourceType,
+    Result, TransitionType, UrlRequestFlags
 };
-use anyhow::Result;
 use cef_ui_sys::{
     cef_post_data_create, cef_post_data_element_create, cef_post_data_element_t, cef_post_data_t,
     cef_postdataelement_type_t, cef_request_create, cef_request_t, cef_urlrequest_flags_t
@@ -282,10 +281,10 @@ impl Request {
         })
     }
 
-    /// Get the post data.
-    pub fn get_post_data(&self) -> Result<PostData> {
+    /// Get the post data, if any.
+    pub fn get_post_data(&self) -> Result<Option<PostData>> {
         try_c!(self, get_post_data, {
-            Ok(PostData::from_ptr_unchecked(get_post_data(self.as_ptr())))
+            Ok(PostData::from_ptr(get_post_data(self.as_ptr())))
         })
     }
 
@@ -307,6 +306,20 @@ impl Request {
         })
     }
 
+    /// Calls |f| with each header name and value, without converting them to
+    /// Rust strings; use this on hot paths instead of get_header_map. Will not
+    /// include the Referer value if any.
+    pub fn visit_headers(&self, f: impl FnMut(CefStr<'_>, CefStr<'_>)) -> Result<()> {
+        try_c!(self, get_header_map, {
+            let mut headers = CefStringMultiMap::new();
+
+            get_header_map(self.as_ptr(), headers.as_mut_ptr());
+            headers.for_each(f);
+
+            Ok(())
+        })
+    }
+
     /// Set the header values. If a Referer value exists in the header map it will
     /// be removed and ignored.
     pub fn set_header_map(&self, headers: &HashMap<String, Vec<String>>) -> Result<()> {
@@ -418,15 +431,14 @@ impl Request {
         })
     }
 
-    // TODO: Fix this!
-
-    //     ///
-    //     /// Get the transition type for this request. Only available in the browser
-    //     /// process and only applies to requests that represent a main frame or sub-
-    //     /// frame navigation.
-    //     ///
-    //     cef_transition_type_t(CEF_CALLBACK* get_transition_type)(
-    //     struct _cef_request_t* self);
+    /// Get the transition type for this request. Only available in the browser
+    /// process and only applies to requests that represent a main frame or sub-
+    /// frame navigation.
+    pub fn get_transition_type(&self) -> Result<TransitionType> {
+        try_c!(self, get_transition_type, {
+            Ok(get_transition_type(self.as_ptr()).into())
+        })
+    }
 
     /// Returns the globally unique identifier for this request or 0 if not
     /// specified. Can be used by cef_resource_request_handler_t implementations
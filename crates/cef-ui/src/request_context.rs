@@ -1,13 +1,20 @@
 use crate::{
-    ref_counted_ptr, try_c, CefString, CefStringList, CompletionCallback, ErrorCode, RefCountedPtr,
-    RequestContextHandler, Wrappable, Wrapped
+    ref_counted_ptr, try_c, unwind::catch_unwind_or, CefString, CefStringList, CompletionCallback,
+    CookieManager, DictionaryValue, ErrorCode, PreferenceManager, RefCountedPtr,
+    RequestContextHandler, Result, SchemeHandlerFactory, Value, Wrappable, Wrapped
 };
-use anyhow::Result;
+#[cfg(feature = "extensions")]
+use crate::{Extension, ExtensionHandler};
 use cef_ui_sys::{
-    cef_errorcode_t, cef_request_context_t, cef_resolve_callback_t, cef_string_list_t
+    cef_create_context_shared, cef_errorcode_t, cef_preference_manager_t,
+    cef_request_context_get_global_context, cef_request_context_t, cef_resolve_callback_t,
+    cef_string_list_t
 };
 use parking_lot::Mutex;
-use std::mem::zeroed;
+use std::{
+    mem::zeroed,
+    ptr::{null, null_mut}
+};
 
 // Callback structure for cef_request_context_t::ResolveHost.
 // Called on the UI thread after the ResolveHost request has completed.
@@ -36,13 +43,16 @@ impl ResolveCallbackWrapper {
         result: cef_errorcode_t,
         resolved_ips: cef_string_list_t
     ) {
-        let this: &Self = Wrapped::wrappable(this);
-        let result = result.into();
-        let resolved_ips = CefStringList::from_ptr(resolved_ips).map_or(Vec::new(), |s| s.into());
-
-        if let Some(f) = this.0.lock().take() {
-            f(result, resolved_ips);
-        }
+        catch_unwind_or("ResolveCallbackWrapper::c_on_resolve_completed", (), || {
+            let this: &Self = Wrapped::wrappable(this);
+            let result = result.into();
+            let resolved_ips =
+                CefStringList::from_ptr(resolved_ips).map_or(Vec::new(), |s| s.into());
+
+            if let Some(f) = this.0.lock().take() {
+                f(result, resolved_ips);
+            }
+        })
     }
 }
 
@@ -78,6 +88,25 @@ impl Wrappable for ResolveCallbackWrapper {
 ref_counted_ptr!(RequestContext, cef_request_context_t);
 
 impl RequestContext {
+    /// Returns the global context object. The global context is used by default
+    /// when creating a browser or URL request with a NULL context argument.
+    pub fn get_global() -> Option<Self> {
+        unsafe { Self::from_ptr(cef_request_context_get_global_context()) }
+    }
+
+    /// Creates a new context object that shares storage with |other| and uses an
+    /// optional |handler|.
+    pub fn new_shared(other: &RequestContext, handler: Option<RequestContextHandler>) -> Self {
+        unsafe {
+            Self::from_ptr_unchecked(cef_create_context_shared(
+                other.clone().into_raw(),
+                handler
+                    .map(|handler| handler.into_raw())
+                    .unwrap_or(null_mut())
+            ))
+        }
+    }
+
     /// Returns true (1) if this object is pointing to the same context as |that|
     /// object.
     pub fn is_same(&self, other: RequestContext) -> Result<bool> {
@@ -108,6 +137,63 @@ impl RequestContext {
         })
     }
 
+    /// Returns this as a PreferenceManager. The request context preferences
+    /// apply to all browsers and URL requests that use this context.
+    pub fn as_preference_manager(&self) -> PreferenceManager {
+        unsafe {
+            PreferenceManager::from_ptr_unchecked(
+                self.clone().into_raw() as *mut cef_preference_manager_t
+            )
+        }
+    }
+
+    /// Returns true (1) if a preference with the specified |name| exists. This
+    /// function must be called on the browser process UI thread.
+    pub fn has_preference(&self, name: &str) -> Result<bool> {
+        self.as_preference_manager()
+            .has_preference(name)
+    }
+
+    /// Returns the value for the preference with the specified |name|. Returns
+    /// NULL if the preference does not exist. The returned object contains a copy
+    /// of the underlying preference value and modifications to the returned
+    /// object will not modify the underlying preference value. This function must
+    /// be called on the browser process UI thread.
+    pub fn get_preference(&self, name: &str) -> Result<Option<Value>> {
+        self.as_preference_manager()
+            .get_preference(name)
+    }
+
+    /// Returns all preferences as a dictionary. If |include_defaults| is true (1)
+    /// then preferences currently at their default value will be included. The
+    /// returned object contains a copy of the underlying preference values and
+    /// modifications to the returned object will not modify the underlying
+    /// preference values. This function must be called on the browser process UI
+    /// thread.
+    pub fn get_all_preferences(&self, include_defaults: bool) -> Result<Option<DictionaryValue>> {
+        self.as_preference_manager()
+            .get_all_preferences(include_defaults)
+    }
+
+    /// Returns true (1) if the preference with the specified |name| can be
+    /// modified using SetPreference. As one example preferences set via the
+    /// command-line usually cannot be modified. This function must be called on
+    /// the browser process UI thread.
+    pub fn can_set_preference(&self, name: &str) -> Result<bool> {
+        self.as_preference_manager()
+            .can_set_preference(name)
+    }
+
+    /// Set the |value| associated with preference |name|. If |value| is None
+    /// the preference will be restored to its default value. If setting the
+    /// preference fails then the returned error will contain a detailed
+    /// description of the problem. This function must be called on the browser
+    /// process UI thread.
+    pub fn set_preference(&self, name: &str, value: Option<Value>) -> Result<()> {
+        self.as_preference_manager()
+            .set_preference(name, value)
+    }
+
     /// Returns the cache path for this object. If NULL an "incognito mode" in-
     /// memory cache is being used.
     pub fn get_cache_path(&self) -> Result<String> {
@@ -118,35 +204,58 @@ impl RequestContext {
         })
     }
 
-    // TODO: Fix this!
+    /// Returns the cookie manager for this object. If |callback| is non-NULL it
+    /// will be executed asnychronously on the UI thread after the manager's
+    /// storage has been initialized.
+    pub fn get_cookie_manager(
+        &self,
+        callback: Option<CompletionCallback>
+    ) -> Result<Option<CookieManager>> {
+        try_c!(self, get_cookie_manager, {
+            Ok(CookieManager::from_ptr(get_cookie_manager(
+                self.as_ptr(),
+                callback
+                    .map(|callback| callback.into_raw())
+                    .unwrap_or_else(null_mut)
+            )))
+        })
+    }
 
-    //     ///
-    //     /// Returns the cookie manager for this object. If |callback| is non-NULL it
-    //     /// will be executed asnychronously on the UI thread after the manager's
-    //     /// storage has been initialized.
-    //     ///
-    //     struct _cef_cookie_manager_t*(CEF_CALLBACK* get_cookie_manager)(
-    //     struct _cef_request_context_t* self,
-    //     struct _cef_completion_callback_t* callback);
-    //
-    //     ///
-    //     /// Register a scheme handler factory for the specified |scheme_name| and
-    //     /// optional |domain_name|. An NULL |domain_name| value for a standard scheme
-    //     /// will cause the factory to match all domain names. The |domain_name| value
-    //     /// will be ignored for non-standard schemes. If |scheme_name| is a built-in
-    //     /// scheme and no handler is returned by |factory| then the built-in scheme
-    //     /// handler factory will be called. If |scheme_name| is a custom scheme then
-    //     /// you must also implement the cef_app_t::on_register_custom_schemes()
-    //     /// function in all processes. This function may be called multiple times to
-    //     /// change or remove the factory that matches the specified |scheme_name| and
-    //     /// optional |domain_name|. Returns false (0) if an error occurs. This
-    //     /// function may be called on any thread in the browser process.
-    //     ///
-    //     int(CEF_CALLBACK* register_scheme_handler_factory)(
-    //     struct _cef_request_context_t* self,
-    //     const cef_string_t* scheme_name,
-    //     const cef_string_t* domain_name,
-    //     struct _cef_scheme_handler_factory_t* factory);
+    /// Register a scheme handler factory for the specified |scheme_name| and
+    /// optional |domain_name|. An NULL |domain_name| value for a standard scheme
+    /// will cause the factory to match all domain names. The |domain_name| value
+    /// will be ignored for non-standard schemes. If |scheme_name| is a built-in
+    /// scheme and no handler is returned by |factory| then the built-in scheme
+    /// handler factory will be called. If |scheme_name| is a custom scheme then
+    /// you must also implement the cef_app_t::on_register_custom_schemes()
+    /// function in all processes. This function may be called multiple times to
+    /// change or remove the factory that matches the specified |scheme_name| and
+    /// optional |domain_name|. Returns false (0) if an error occurs. This
+    /// function may be called on any thread in the browser process.
+    pub fn register_scheme_handler_factory(
+        &self,
+        scheme_name: &str,
+        domain_name: Option<&str>,
+        factory: Option<SchemeHandlerFactory>
+    ) -> Result<bool> {
+        try_c!(self, register_scheme_handler_factory, {
+            let scheme_name = CefString::new(scheme_name);
+            let domain_name = domain_name.map(CefString::new);
+            let factory = factory
+                .map(|factory| factory.into_raw())
+                .unwrap_or_else(null_mut);
+
+            Ok(register_scheme_handler_factory(
+                self.as_ptr(),
+                scheme_name.as_ptr(),
+                domain_name
+                    .as_ref()
+                    .map(|domain_name| domain_name.as_ptr())
+                    .unwrap_or_else(null),
+                factory
+            ) != 0)
+        })
+    }
 
     /// Clear all registered scheme handler factories. Returns false (0) on error.
     /// This function may be called on any thread in the browser process.
@@ -162,11 +271,13 @@ impl RequestContext {
     /// being prompted again for server certificates if you reconnect quickly. If
     /// |callback| is non-NULL it will be executed on the UI thread after
     /// completion.
-    pub fn clear_certificate_exceptions(&self, callback: CompletionCallback) -> Result<()> {
+    pub fn clear_certificate_exceptions(&self, callback: Option<CompletionCallback>) -> Result<()> {
         try_c!(self, clear_certificate_exceptions, {
             Ok(clear_certificate_exceptions(
                 self.as_ptr(),
-                callback.into_raw()
+                callback
+                    .map(|callback| callback.into_raw())
+                    .unwrap_or_else(null_mut)
             ))
         })
     }
@@ -174,11 +285,13 @@ impl RequestContext {
     /// Clears all HTTP authentication credentials that were added as part of
     /// handling GetAuthCredentials. If |callback| is non-NULL it will be executed
     /// on the UI thread after completion.
-    pub fn clear_http_auth_credentials(&self, callback: CompletionCallback) -> Result<()> {
+    pub fn clear_http_auth_credentials(&self, callback: Option<CompletionCallback>) -> Result<()> {
         try_c!(self, clear_http_auth_credentials, {
             Ok(clear_http_auth_credentials(
                 self.as_ptr(),
-                callback.into_raw()
+                callback
+                    .map(|callback| callback.into_raw())
+                    .unwrap_or_else(null_mut)
             ))
         })
     }
@@ -187,9 +300,14 @@ impl RequestContext {
     /// is only recommended if you have released all other CEF objects but don't
     /// yet want to call cef_shutdown(). If |callback| is non-NULL it will be
     /// executed on the UI thread after completion.
-    pub fn close_all_connections(&self, callback: CompletionCallback) -> Result<()> {
+    pub fn close_all_connections(&self, callback: Option<CompletionCallback>) -> Result<()> {
         try_c!(self, close_all_connections, {
-            Ok(close_all_connections(self.as_ptr(), callback.into_raw()))
+            Ok(close_all_connections(
+                self.as_ptr(),
+                callback
+                    .map(|callback| callback.into_raw())
+                    .unwrap_or_else(null_mut)
+            ))
         })
     }
 
@@ -207,65 +325,82 @@ impl RequestContext {
         })
     }
 
-    // TODO: Fix this!
-
-    //     ///
-    //     /// Load an extension.
-    //     ///
-    //     /// If extension resources will be read from disk using the default load
-    //     /// implementation then |root_directory| should be the absolute path to the
-    //     /// extension resources directory and |manifest| should be NULL. If extension
-    //     /// resources will be provided by the client (e.g. via cef_request_handler_t
-    //     /// and/or cef_extension_handler_t) then |root_directory| should be a path
-    //     /// component unique to the extension (if not absolute this will be internally
-    //     /// prefixed with the PK_DIR_RESOURCES path) and |manifest| should contain the
-    //     /// contents that would otherwise be read from the "manifest.json" file on
-    //     /// disk.
-    //     ///
-    //     /// The loaded extension will be accessible in all contexts sharing the same
-    //     /// storage (HasExtension returns true (1)). However, only the context on
-    //     /// which this function was called is considered the loader (DidLoadExtension
-    //     /// returns true (1)) and only the loader will receive
-    //     /// cef_request_context_handler_t callbacks for the extension.
-    //     ///
-    //     /// cef_extension_handler_t::OnExtensionLoaded will be called on load success
-    //     /// or cef_extension_handler_t::OnExtensionLoadFailed will be called on load
-    //     /// failure.
-    //     ///
-    //     /// If the extension specifies a background script via the "background"
-    //     /// manifest key then cef_extension_handler_t::OnBeforeBackgroundBrowser will
-    //     /// be called to create the background browser. See that function for
-    //     /// additional information about background scripts.
-    //     ///
-    //     /// For visible extension views the client application should evaluate the
-    //     /// manifest to determine the correct extension URL to load and then pass that
-    //     /// URL to the cef_browser_host_t::CreateBrowser* function after the extension
-    //     /// has loaded. For example, the client can look for the "browser_action"
-    //     /// manifest key as documented at
-    //     /// https://developer.chrome.com/extensions/browserAction. Extension URLs take
-    //     /// the form "chrome-extension://<extension_id>/<path>".
-    //     ///
-    //     /// Browsers that host extensions differ from normal browsers as follows:
-    //     ///  - Can access chrome.* JavaScript APIs if allowed by the manifest. Visit
-    //     ///    chrome://extensions-support for the list of extension APIs currently
-    //     ///    supported by CEF.
-    //     ///  - Main frame navigation to non-extension content is blocked.
-    //     ///  - Pinch-zooming is disabled.
-    //     ///  - CefBrowserHost::GetExtension returns the hosted extension.
-    //     ///  - CefBrowserHost::IsBackgroundHost returns true for background hosts.
-    //     ///
-    //     /// See https://developer.chrome.com/extensions for extension implementation
-    //     /// and usage documentation.
-    //     ///
-    //     void(CEF_CALLBACK* load_extension)(struct _cef_request_context_t* self,
-    //     const cef_string_t* root_directory,
-    //     struct _cef_dictionary_value_t* manifest,
-    //     struct _cef_extension_handler_t* handler);
+    /// Load an extension.
+    ///
+    /// If extension resources will be read from disk using the default load
+    /// implementation then |root_directory| should be the absolute path to the
+    /// extension resources directory and |manifest| should be NULL. If extension
+    /// resources will be provided by the client (e.g. via cef_request_handler_t
+    /// and/or cef_extension_handler_t) then |root_directory| should be a path
+    /// component unique to the extension (if not absolute this will be internally
+    /// prefixed with the PK_DIR_RESOURCES path) and |manifest| should contain the
+    /// contents that would otherwise be read from the "manifest.json" file on
+    /// disk.
+    ///
+    /// The loaded extension will be accessible in all contexts sharing the same
+    /// storage (HasExtension returns true (1)). However, only the context on
+    /// which this function was called is considered the loader (DidLoadExtension
+    /// returns true (1)) and only the loader will receive
+    /// cef_request_context_handler_t callbacks for the extension.
+    ///
+    /// cef_extension_handler_t::OnExtensionLoaded will be called on load success
+    /// or cef_extension_handler_t::OnExtensionLoadFailed will be called on load
+    /// failure.
+    ///
+    /// If the extension specifies a background script via the "background"
+    /// manifest key then cef_extension_handler_t::OnBeforeBackgroundBrowser will
+    /// be called to create the background browser. See that function for
+    /// additional information about background scripts.
+    ///
+    /// For visible extension views the client application should evaluate the
+    /// manifest to determine the correct extension URL to load and then pass that
+    /// URL to the cef_browser_host_t::CreateBrowser* function after the extension
+    /// has loaded. For example, the client can look for the "browser_action"
+    /// manifest key as documented at
+    /// https://developer.chrome.com/extensions/browserAction. Extension URLs take
+    /// the form "chrome-extension://<extension_id>/<path>".
+    ///
+    /// Browsers that host extensions differ from normal browsers as follows:
+    ///  - Can access chrome.* JavaScript APIs if allowed by the manifest. Visit
+    ///    chrome://extensions-support for the list of extension APIs currently
+    ///    supported by CEF.
+    ///  - Main frame navigation to non-extension content is blocked.
+    ///  - Pinch-zooming is disabled.
+    ///  - CefBrowserHost::GetExtension returns the hosted extension.
+    ///  - CefBrowserHost::IsBackgroundHost returns true for background hosts.
+    ///
+    /// See https://developer.chrome.com/extensions for extension implementation
+    /// and usage documentation.
+    #[cfg(feature = "extensions")]
+    pub fn load_extension(
+        &self,
+        root_directory: &str,
+        manifest: Option<DictionaryValue>,
+        handler: Option<ExtensionHandler>
+    ) -> Result<()> {
+        try_c!(self, load_extension, {
+            let root_directory = CefString::new(root_directory);
+            let manifest = manifest
+                .map(|manifest| manifest.into_raw())
+                .unwrap_or_else(null_mut);
+            let handler = handler
+                .map(|handler| handler.into_raw())
+                .unwrap_or_else(null_mut);
+
+            Ok(load_extension(
+                self.as_ptr(),
+                root_directory.as_ptr(),
+                manifest,
+                handler
+            ))
+        })
+    }
 
     /// Returns true (1) if this context was used to load the extension identified
     /// by |extension_id|. Other contexts sharing the same storage will also have
     /// access to the extension (see HasExtension). This function must be called
     /// on the browser process UI thread.
+    #[cfg(feature = "extensions")]
     pub fn did_load_extension(&self, extension_id: &str) -> Result<bool> {
         try_c!(self, did_load_extension, {
             let extension_id = CefString::new(extension_id);
@@ -278,6 +413,7 @@ impl RequestContext {
     /// |extension_id|. This may not be the context that was used to load the
     /// extension (see DidLoadExtension). This function must be called on the
     /// browser process UI thread.
+    #[cfg(feature = "extensions")]
     pub fn has_extension(&self, extension_id: &str) -> Result<bool> {
         try_c!(self, has_extension, {
             let extension_id = CefString::new(extension_id);
@@ -290,6 +426,7 @@ impl RequestContext {
     /// HasExtension). |extension_ids| will be populated with the list of
     /// extension ID values. Returns true (1) on success. This function must be
     /// called on the browser process UI thread.
+    #[cfg(feature = "extensions")]
     pub fn get_extensions(&self) -> Result<Option<Vec<String>>> {
         try_c!(self, get_extensions, {
             let mut extension_ids = CefStringList::new();
@@ -303,17 +440,23 @@ impl RequestContext {
         })
     }
 
+    /// Returns the extension matching |extension_id| or NULL if no matching
+    /// extension is accessible in this context (see HasExtension). This function
+    /// must be called on the browser process UI thread.
+    #[cfg(feature = "extensions")]
+    pub fn get_extension(&self, extension_id: &str) -> Result<Option<Extension>> {
+        try_c!(self, get_extension, {
+            let extension_id = CefString::new(extension_id);
+
+            Ok(Extension::from_ptr(get_extension(
+                self.as_ptr(),
+                extension_id.as_ptr()
+            )))
+        })
+    }
+
     // TODO: Fix this!
 
-    //
-    //     ///
-    //     /// Returns the extension matching |extension_id| or NULL if no matching
-    //     /// extension is accessible in this context (see HasExtension). This function
-    //     /// must be called on the browser process UI thread.
-    //     ///
-    //     struct _cef_extension_t*(CEF_CALLBACK* get_extension)(
-    //     struct _cef_request_context_t* self,
-    //     const cef_string_t* extension_id);
     //
     //     ///
     //     /// Returns the MediaRouter object associated with this context.  If
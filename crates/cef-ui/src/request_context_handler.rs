@@ -1,6 +1,6 @@
 use crate::{
-    ref_counted_ptr, Browser, CefString, Frame, RefCountedPtr, Request, RequestContext,
-    ResourceRequestHandler, Wrappable, Wrapped
+    debug_assert_currently_on, ref_counted_ptr, unwind::catch_unwind_or, Browser, CefString, Frame,
+    RefCountedPtr, Request, RequestContext, ResourceRequestHandler, ThreadId, Wrappable, Wrapped
 };
 use cef_ui_sys::{
     cef_browser_t, cef_frame_t, cef_request_context_handler_t, cef_request_context_t,
@@ -14,7 +14,7 @@ use std::{ffi::c_int, mem::zeroed, ptr::null_mut};
 pub trait RequestContextHandlerCallbacks: Send + Sync + 'static {
     /// Called on the browser process UI thread immediately after the request
     /// context has been initialized.
-    fn on_request_context_initialized(&mut self, request_context: RequestContext);
+    fn on_request_context_initialized(&mut self, _request_context: RequestContext) {}
 
     /// Called on the browser process IO thread before a resource request is
     /// initiated. The |browser| and |frame| values represent the source of the
@@ -35,14 +35,16 @@ pub trait RequestContextHandlerCallbacks: Send + Sync + 'static {
     /// (identified by cef_request_t::GetIdentifier).
     fn get_resource_request_handler(
         &mut self,
-        browser: Option<Browser>,
-        frame: Option<Frame>,
-        request: Request,
-        is_navigation: bool,
-        is_download: bool,
-        request_initiator: &str,
-        disable_default_handling: &mut bool
-    ) -> Option<ResourceRequestHandler>;
+        _browser: Option<Browser>,
+        _frame: Option<Frame>,
+        _request: Request,
+        _is_navigation: bool,
+        _is_download: bool,
+        _request_initiator: &str,
+        _disable_default_handling: &mut bool
+    ) -> Option<ResourceRequestHandler> {
+        None
+    }
 }
 
 // Implement this structure to provide handler implementations. The handler
@@ -57,11 +59,11 @@ impl RequestContextHandler {
 }
 
 /// Translates CEF -> Rust callbacks.
-struct RequestContextHandlerWrapper(Box<dyn RequestContextHandlerCallbacks>);
+struct RequestContextHandlerWrapper<C: RequestContextHandlerCallbacks>(C);
 
-impl RequestContextHandlerWrapper {
-    pub fn new(delegate: impl RequestContextHandlerCallbacks) -> Self {
-        Self(Box::new(delegate))
+impl<C: RequestContextHandlerCallbacks> RequestContextHandlerWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
     }
 
     /// Called on the browser process UI thread immediately after the request
@@ -70,11 +72,19 @@ impl RequestContextHandlerWrapper {
         this: *mut cef_request_context_handler_t,
         request_context: *mut cef_request_context_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let request_context = RequestContext::from_ptr_unchecked(request_context);
+        catch_unwind_or(
+            "RequestContextHandlerWrapper::c_on_request_context_initialized",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let request_context = RequestContext::from_ptr_unchecked(request_context);
 
-        this.0
-            .on_request_context_initialized(request_context);
+                this.0
+                    .on_request_context_initialized(request_context);
+            }
+        )
     }
 
     /// Called on the browser process IO thread before a resource request is
@@ -104,34 +114,43 @@ impl RequestContextHandlerWrapper {
         request_initiator: *const cef_string_t,
         disable_default_handling: *mut c_int
     ) -> *mut cef_resource_request_handler_t {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr(browser);
-        let frame = Frame::from_ptr(frame);
-        let request = Request::from_ptr_unchecked(request);
-        let is_navigation = is_navigation != 0;
-        let is_download = is_download != 0;
-        let request_initiator: String = CefString::from_ptr_unchecked(request_initiator).into();
-        let mut local_disable_default_handling = *disable_default_handling != 0;
-
-        let resource_request_handler = this.0.get_resource_request_handler(
-            browser,
-            frame,
-            request,
-            is_navigation,
-            is_download,
-            &request_initiator,
-            &mut local_disable_default_handling
-        );
-
-        *disable_default_handling = local_disable_default_handling as c_int;
-
-        resource_request_handler
-            .map(|resource_request_handler| resource_request_handler.into_raw())
-            .unwrap_or(null_mut())
+        catch_unwind_or(
+            "RequestContextHandlerWrapper::c_get_resource_request_handler",
+            null_mut(),
+            || {
+                debug_assert_currently_on!(ThreadId::Io);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr(browser);
+                let frame = Frame::from_ptr(frame);
+                let request = Request::from_ptr_unchecked(request);
+                let is_navigation = is_navigation != 0;
+                let is_download = is_download != 0;
+                let request_initiator: String =
+                    CefString::from_ptr_unchecked(request_initiator).into();
+                let mut local_disable_default_handling = *disable_default_handling != 0;
+
+                let resource_request_handler = this.0.get_resource_request_handler(
+                    browser,
+                    frame,
+                    request,
+                    is_navigation,
+                    is_download,
+                    &request_initiator,
+                    &mut local_disable_default_handling
+                );
+
+                *disable_default_handling = local_disable_default_handling as c_int;
+
+                resource_request_handler
+                    .map(|resource_request_handler| resource_request_handler.into_raw())
+                    .unwrap_or(null_mut())
+            }
+        )
     }
 }
 
-impl Wrappable for RequestContextHandlerWrapper {
+impl<C: RequestContextHandlerCallbacks> Wrappable for RequestContextHandlerWrapper<C> {
     type Cef = cef_request_context_handler_t;
 
     /// Converts this to a smart pointer.
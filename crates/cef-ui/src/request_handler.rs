@@ -1,9 +1,9 @@
 use crate::{
-    ref_counted_ptr, try_c, AuthCallback, Browser, Callback, CefString, ErrorCode, Frame,
-    RefCountedPtr, Request, ResourceRequestHandler, SslInfo, TerminationStatus,
-    WindowOpenDisposition, Wrappable, Wrapped, X509Certificate
+    callbacks_builder, debug_assert_currently_on, ref_counted_ptr, try_c, unwind::catch_unwind_or,
+    AuthCallback, Browser, Callback, CefString, ErrorCode, Frame, RefCountedPtr, Request,
+    ResourceRequestHandler, Result, SslInfo, TerminationStatus, ThreadId, WindowOpenDisposition,
+    Wrappable, Wrapped, X509Certificate
 };
-use anyhow::Result;
 use cef_ui_sys::{
     cef_auth_callback_t, cef_browser_t, cef_callback_t, cef_errorcode_t, cef_frame_t,
     cef_request_handler_t, cef_request_t, cef_resource_request_handler_t,
@@ -46,12 +46,14 @@ pub trait RequestHandlerCallbacks: Send + Sync + 'static {
     /// DomContentLoaded event).
     fn on_before_browse(
         &mut self,
-        browser: Browser,
-        frame: Frame,
-        request: Request,
-        user_gesture: bool,
-        is_redirect: bool
-    ) -> bool;
+        _browser: Browser,
+        _frame: Frame,
+        _request: Request,
+        _user_gesture: bool,
+        _is_redirect: bool
+    ) -> bool {
+        false
+    }
 
     /// Called on the UI thread before OnBeforeBrowse in certain limited cases
     /// where navigating a new or different browser might be desirable. This
@@ -69,12 +71,14 @@ pub trait RequestHandlerCallbacks: Send + Sync + 'static {
     /// proceed in the source browser's top-level frame.
     fn on_open_urlfrom_tab(
         &mut self,
-        browser: Browser,
-        frame: Frame,
-        target_url: &str,
-        target_disposition: WindowOpenDisposition,
-        user_gesture: bool
-    ) -> bool;
+        _browser: Browser,
+        _frame: Frame,
+        _target_url: &str,
+        _target_disposition: WindowOpenDisposition,
+        _user_gesture: bool
+    ) -> bool {
+        false
+    }
 
     /// Called on the browser process IO thread before a resource request is
     /// initiated. The |browser| and |frame| values represent the source of the
@@ -92,14 +96,16 @@ pub trait RequestHandlerCallbacks: Send + Sync + 'static {
     /// cef_request_context_handler_t, if any.
     fn get_resource_request_handler(
         &mut self,
-        browser: Browser,
-        frame: Frame,
-        request: Request,
-        is_navigation: bool,
-        is_download: bool,
-        request_initiator: &str,
-        disable_default_handling: &mut bool
-    ) -> Option<ResourceRequestHandler>;
+        _browser: Browser,
+        _frame: Frame,
+        _request: Request,
+        _is_navigation: bool,
+        _is_download: bool,
+        _request_initiator: &str,
+        _disable_default_handling: &mut bool
+    ) -> Option<ResourceRequestHandler> {
+        None
+    }
 
     /// Called on the IO thread when the browser needs credentials from the user.
     /// |origin_url| is the origin making this authentication request. |isProxy|
@@ -113,15 +119,17 @@ pub trait RequestHandlerCallbacks: Send + Sync + 'static {
     /// cancel the request immediately.
     fn get_auth_credentials(
         &mut self,
-        browser: Browser,
-        origin_url: &str,
-        is_proxy: bool,
-        host: &str,
-        port: u16,
-        realm: Option<&str>,
-        scheme: Option<&str>,
-        callback: AuthCallback
-    ) -> bool;
+        _browser: Browser,
+        _origin_url: &str,
+        _is_proxy: bool,
+        _host: &str,
+        _port: u16,
+        _realm: Option<&str>,
+        _scheme: Option<&str>,
+        _callback: AuthCallback
+    ) -> bool {
+        false
+    }
 
     /// Called on the UI thread to handle requests for URLs with an invalid SSL
     /// certificate. Return true (1) and call cef_callback_t functions either in
@@ -131,12 +139,14 @@ pub trait RequestHandlerCallbacks: Send + Sync + 'static {
     /// will be accepted without calling this function.
     fn on_certificate_error(
         &mut self,
-        browser: Browser,
-        cert_error: ErrorCode,
-        request_url: &str,
-        ssl_info: SslInfo,
-        callback: Callback
-    ) -> bool;
+        _browser: Browser,
+        _cert_error: ErrorCode,
+        _request_url: &str,
+        _ssl_info: SslInfo,
+        _callback: Callback
+    ) -> bool {
+        false
+    }
 
     /// Called on the UI thread when a client certificate is being requested for
     /// authentication. Return false (0) to use the default behavior and
@@ -151,26 +161,28 @@ pub trait RequestHandlerCallbacks: Send + Sync + 'static {
     /// issuers that the server trusts.
     fn on_select_client_certificate(
         &mut self,
-        browser: Browser,
-        is_proxy: bool,
-        host: &str,
-        port: u16,
-        certificates: &[X509Certificate],
-        callback: SelectClientCertificateCallback
-    ) -> bool;
+        _browser: Browser,
+        _is_proxy: bool,
+        _host: &str,
+        _port: u16,
+        _certificates: &[X509Certificate],
+        _callback: SelectClientCertificateCallback
+    ) -> bool {
+        false
+    }
 
     /// Called on the browser process UI thread when the render view associated
     /// with |browser| is ready to receive/handle IPC messages in the render
     /// process.
-    fn on_render_view_ready(&mut self, browser: Browser);
+    fn on_render_view_ready(&mut self, _browser: Browser) {}
 
     /// Called on the browser process UI thread when the render process terminates
     /// unexpectedly. |status| indicates how the process terminated.
-    fn on_render_process_terminated(&mut self, browser: Browser, status: TerminationStatus);
+    fn on_render_process_terminated(&mut self, _browser: Browser, _status: TerminationStatus) {}
 
     /// Called on the browser process UI thread when the window.document object of
     /// the main frame has been created.
-    fn on_document_available_in_main_frame(&mut self, browser: Browser);
+    fn on_document_available_in_main_frame(&mut self, _browser: Browser) {}
 }
 
 // Implement this structure to handle events related to browser requests. The
@@ -183,12 +195,70 @@ impl RequestHandler {
     }
 }
 
+callbacks_builder! {
+    /// Builds a RequestHandler from closures. Callbacks that are not set fall
+    /// back to the default RequestHandlerCallbacks behavior.
+    RequestHandlerBuilder: RequestHandlerCallbacks => RequestHandler {
+        fn on_before_browse(
+            browser: Browser,
+            frame: Frame,
+            request: Request,
+            user_gesture: bool,
+            is_redirect: bool
+        ) -> bool = false;
+        fn on_open_urlfrom_tab(
+            browser: Browser,
+            frame: Frame,
+            target_url: &str,
+            target_disposition: WindowOpenDisposition,
+            user_gesture: bool
+        ) -> bool = false;
+        fn get_resource_request_handler(
+            browser: Browser,
+            frame: Frame,
+            request: Request,
+            is_navigation: bool,
+            is_download: bool,
+            request_initiator: &str,
+            disable_default_handling: &mut bool
+        ) -> Option<ResourceRequestHandler> = None;
+        fn get_auth_credentials(
+            browser: Browser,
+            origin_url: &str,
+            is_proxy: bool,
+            host: &str,
+            port: u16,
+            realm: Option<&str>,
+            scheme: Option<&str>,
+            callback: AuthCallback
+        ) -> bool = false;
+        fn on_certificate_error(
+            browser: Browser,
+            cert_error: ErrorCode,
+            request_url: &str,
+            ssl_info: SslInfo,
+            callback: Callback
+        ) -> bool = false;
+        fn on_select_client_certificate(
+            browser: Browser,
+            is_proxy: bool,
+            host: &str,
+            port: u16,
+            certificates: &[X509Certificate],
+            callback: SelectClientCertificateCallback
+        ) -> bool = false;
+        fn on_render_view_ready(browser: Browser);
+        fn on_render_process_terminated(browser: Browser, status: TerminationStatus);
+        fn on_document_available_in_main_frame(browser: Browser);
+    }
+}
+
 /// Translates CEF -> Rust callbacks.
-struct RequestHandlerWrapper(Box<dyn RequestHandlerCallbacks>);
+struct RequestHandlerWrapper<C: RequestHandlerCallbacks>(C);
 
-impl RequestHandlerWrapper {
-    pub fn new<C: RequestHandlerCallbacks>(delegate: C) -> Self {
-        Self(Box::new(delegate))
+impl<C: RequestHandlerCallbacks> RequestHandlerWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
     }
 
     /// Called on the UI thread before browser navigation. Return true (1) to
@@ -210,14 +280,18 @@ impl RequestHandlerWrapper {
         user_gesture: c_int,
         is_redirect: c_int
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let frame = Frame::from_ptr_unchecked(frame);
-        let request = Request::from_ptr_unchecked(request);
-
-        this.0
-            .on_before_browse(browser, frame, request, user_gesture != 0, is_redirect != 0)
-            as c_int
+        catch_unwind_or("RequestHandlerWrapper::c_on_before_browse", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let frame = Frame::from_ptr_unchecked(frame);
+            let request = Request::from_ptr_unchecked(request);
+
+            this.0
+                .on_before_browse(browser, frame, request, user_gesture != 0, is_redirect != 0)
+                as c_int
+        })
     }
 
     /// Called on the UI thread before OnBeforeBrowse in certain limited cases
@@ -242,18 +316,22 @@ impl RequestHandlerWrapper {
         target_disposition: cef_window_open_disposition_t,
         user_gesture: c_int
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let frame = Frame::from_ptr_unchecked(frame);
-        let target_url: String = CefString::from_ptr_unchecked(target_url).into();
-
-        this.0.on_open_urlfrom_tab(
-            browser,
-            frame,
-            &target_url,
-            target_disposition.into(),
-            user_gesture != 0
-        ) as c_int
+        catch_unwind_or("RequestHandlerWrapper::c_on_open_urlfrom_tab", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let frame = Frame::from_ptr_unchecked(frame);
+            let target_url: String = CefString::from_ptr_unchecked(target_url).into();
+
+            this.0.on_open_urlfrom_tab(
+                browser,
+                frame,
+                &target_url,
+                target_disposition.into(),
+                user_gesture != 0
+            ) as c_int
+        })
     }
 
     /// Called on the browser process IO thread before a resource request is
@@ -280,28 +358,37 @@ impl RequestHandlerWrapper {
         request_initiator: *const cef_string_t,
         disable_default_handling: *mut c_int
     ) -> *mut cef_resource_request_handler_t {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let frame = Frame::from_ptr_unchecked(frame);
-        let request = Request::from_ptr_unchecked(request);
-        let request_initiator: String = CefString::from_ptr_unchecked(request_initiator).into();
-        let mut local_disable_default_handling = *disable_default_handling != 0;
-
-        let resource_request_handler = this.0.get_resource_request_handler(
-            browser,
-            frame,
-            request,
-            is_navigation != 0,
-            is_download != 0,
-            &request_initiator,
-            &mut local_disable_default_handling
-        );
-
-        *disable_default_handling = local_disable_default_handling as c_int;
-
-        resource_request_handler
-            .map(|resource_request_handler| resource_request_handler.into_raw())
-            .unwrap_or(null_mut())
+        catch_unwind_or(
+            "RequestHandlerWrapper::c_get_resource_request_handler",
+            null_mut(),
+            || {
+                debug_assert_currently_on!(ThreadId::Io);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+                let frame = Frame::from_ptr_unchecked(frame);
+                let request = Request::from_ptr_unchecked(request);
+                let request_initiator: String =
+                    CefString::from_ptr_unchecked(request_initiator).into();
+                let mut local_disable_default_handling = *disable_default_handling != 0;
+
+                let resource_request_handler = this.0.get_resource_request_handler(
+                    browser,
+                    frame,
+                    request,
+                    is_navigation != 0,
+                    is_download != 0,
+                    &request_initiator,
+                    &mut local_disable_default_handling
+                );
+
+                *disable_default_handling = local_disable_default_handling as c_int;
+
+                resource_request_handler
+                    .map(|resource_request_handler| resource_request_handler.into_raw())
+                    .unwrap_or(null_mut())
+            }
+        )
     }
 
     /// Called on the IO thread when the browser needs credentials from the user.
@@ -325,26 +412,30 @@ impl RequestHandlerWrapper {
         scheme: *const cef_string_t,
         callback: *mut cef_auth_callback_t
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let origin_url: String = CefString::from_ptr_unchecked(origin_url).into();
-        let host: String = CefString::from_ptr_unchecked(host).into();
-        let realm: Option<String> = CefString::from_ptr(realm).map(|s| s.into());
-        let realm = realm.as_ref().map(|s| s.as_str());
-        let scheme: Option<String> = CefString::from_ptr(scheme).map(|s| s.into());
-        let scheme = scheme.as_ref().map(|s| s.as_str());
-        let callback = AuthCallback::from_ptr_unchecked(callback);
-
-        this.0.get_auth_credentials(
-            browser,
-            &origin_url,
-            is_proxy != 0,
-            &host,
-            port as u16,
-            realm,
-            scheme,
-            callback
-        ) as c_int
+        catch_unwind_or("RequestHandlerWrapper::c_get_auth_credentials", 0, || {
+            debug_assert_currently_on!(ThreadId::Io);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let origin_url: String = CefString::from_ptr_unchecked(origin_url).into();
+            let host: String = CefString::from_ptr_unchecked(host).into();
+            let realm: Option<String> = CefString::from_ptr(realm).map(|s| s.into());
+            let realm = realm.as_ref().map(|s| s.as_str());
+            let scheme: Option<String> = CefString::from_ptr(scheme).map(|s| s.into());
+            let scheme = scheme.as_ref().map(|s| s.as_str());
+            let callback = AuthCallback::from_ptr_unchecked(callback);
+
+            this.0.get_auth_credentials(
+                browser,
+                &origin_url,
+                is_proxy != 0,
+                &host,
+                port as u16,
+                realm,
+                scheme,
+                callback
+            ) as c_int
+        })
     }
 
     /// Called on the UI thread to handle requests for URLs with an invalid SSL
@@ -361,15 +452,23 @@ impl RequestHandlerWrapper {
         ssl_info: *mut cef_sslinfo_t,
         callback: *mut cef_callback_t
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let request_url: String = CefString::from_ptr_unchecked(request_url).into();
-        let ssl_info = SslInfo::from_ptr_unchecked(ssl_info);
-        let callback = Callback::from_ptr_unchecked(callback);
-
-        this.0
-            .on_certificate_error(browser, cert_error.into(), &request_url, ssl_info, callback)
-            as c_int
+        catch_unwind_or("RequestHandlerWrapper::c_on_certificate_error", 0, || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+            let request_url: String = CefString::from_ptr_unchecked(request_url).into();
+            let ssl_info = SslInfo::from_ptr_unchecked(ssl_info);
+            let callback = Callback::from_ptr_unchecked(callback);
+
+            this.0.on_certificate_error(
+                browser,
+                cert_error.into(),
+                &request_url,
+                ssl_info,
+                callback
+            ) as c_int
+        })
     }
 
     /// Called on the UI thread when a client certificate is being requested for
@@ -393,23 +492,31 @@ impl RequestHandlerWrapper {
         certificates: *const *mut cef_x509certificate_t,
         callback: *mut cef_select_client_certificate_callback_t
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-        let host: String = CefString::from_ptr_unchecked(host).into();
-        let certificates = from_raw_parts(certificates, certificates_count)
-            .iter()
-            .map(|&ptr| X509Certificate::from_ptr_unchecked(ptr))
-            .collect::<Vec<X509Certificate>>();
-        let callback = SelectClientCertificateCallback::from_ptr_unchecked(callback);
-
-        this.0.on_select_client_certificate(
-            browser,
-            is_proxy != 0,
-            &host,
-            port as u16,
-            &certificates,
-            callback
-        ) as c_int
+        catch_unwind_or(
+            "RequestHandlerWrapper::c_on_select_client_certificate",
+            0,
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+                let host: String = CefString::from_ptr_unchecked(host).into();
+                let certificates = from_raw_parts(certificates, certificates_count)
+                    .iter()
+                    .map(|&ptr| X509Certificate::from_ptr_unchecked(ptr))
+                    .collect::<Vec<X509Certificate>>();
+                let callback = SelectClientCertificateCallback::from_ptr_unchecked(callback);
+
+                this.0.on_select_client_certificate(
+                    browser,
+                    is_proxy != 0,
+                    &host,
+                    port as u16,
+                    &certificates,
+                    callback
+                ) as c_int
+            }
+        )
     }
 
     /// Called on the browser process UI thread when the render view associated
@@ -419,10 +526,14 @@ impl RequestHandlerWrapper {
         this: *mut cef_request_handler_t,
         browser: *mut cef_browser_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
+        catch_unwind_or("RequestHandlerWrapper::c_on_render_view_ready", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
 
-        this.0.on_render_view_ready(browser)
+            let this: &mut Self = Wrapped::wrappable(this);
+            let browser = Browser::from_ptr_unchecked(browser);
+
+            this.0.on_render_view_ready(browser)
+        })
     }
 
     /// Called on the browser process UI thread when the render process terminates
@@ -432,11 +543,19 @@ impl RequestHandlerWrapper {
         browser: *mut cef_browser_t,
         status: cef_termination_status_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-
-        this.0
-            .on_render_process_terminated(browser, status.into())
+        catch_unwind_or(
+            "RequestHandlerWrapper::c_on_render_process_terminated",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+
+                this.0
+                    .on_render_process_terminated(browser, status.into())
+            }
+        )
     }
 
     /// Called on the browser process UI thread when the window.document object of
@@ -445,15 +564,23 @@ impl RequestHandlerWrapper {
         this: *mut cef_request_handler_t,
         browser: *mut cef_browser_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let browser = Browser::from_ptr_unchecked(browser);
-
-        this.0
-            .on_document_available_in_main_frame(browser)
+        catch_unwind_or(
+            "RequestHandlerWrapper::c_on_document_available_in_main_frame",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr_unchecked(browser);
+
+                this.0
+                    .on_document_available_in_main_frame(browser)
+            }
+        )
     }
 }
 
-impl Wrappable for RequestHandlerWrapper {
+impl<C: RequestHandlerCallbacks> Wrappable for RequestHandlerWrapper<C> {
     type Cef = cef_request_handler_t;
 
     /// Converts this to a smart pointer.
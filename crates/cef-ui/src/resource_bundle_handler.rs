@@ -0,0 +1,248 @@
+use crate::{
+    ref_counted_ptr, unwind::catch_unwind_or, CefString, RefCountedPtr, Wrappable, Wrapped
+};
+use cef_ui_sys::{cef_resource_bundle_handler_t, cef_scale_factor_t, cef_string_t};
+use std::{
+    ffi::{c_int, c_void},
+    mem::zeroed
+};
+
+/// Supported UI scale factors for the platform. SCALE_FACTOR_NONE is used for
+/// density independent resources such as string, html/js files or an image
+/// that can be used for any scale factors (such as wallpapers).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ScaleFactor {
+    None,
+    P100,
+    P125,
+    P133,
+    P140,
+    P150,
+    P180,
+    P200,
+    P250,
+    P300
+}
+
+impl From<cef_scale_factor_t> for ScaleFactor {
+    fn from(value: cef_scale_factor_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_scale_factor_t> for ScaleFactor {
+    fn from(value: &cef_scale_factor_t) -> Self {
+        match value {
+            cef_scale_factor_t::SCALE_FACTOR_NONE => ScaleFactor::None,
+            cef_scale_factor_t::SCALE_FACTOR_100P => ScaleFactor::P100,
+            cef_scale_factor_t::SCALE_FACTOR_125P => ScaleFactor::P125,
+            cef_scale_factor_t::SCALE_FACTOR_133P => ScaleFactor::P133,
+            cef_scale_factor_t::SCALE_FACTOR_140P => ScaleFactor::P140,
+            cef_scale_factor_t::SCALE_FACTOR_150P => ScaleFactor::P150,
+            cef_scale_factor_t::SCALE_FACTOR_180P => ScaleFactor::P180,
+            cef_scale_factor_t::SCALE_FACTOR_200P => ScaleFactor::P200,
+            cef_scale_factor_t::SCALE_FACTOR_250P => ScaleFactor::P250,
+            cef_scale_factor_t::SCALE_FACTOR_300P => ScaleFactor::P300
+        }
+    }
+}
+
+impl From<ScaleFactor> for cef_scale_factor_t {
+    fn from(value: ScaleFactor) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&ScaleFactor> for cef_scale_factor_t {
+    fn from(value: &ScaleFactor) -> Self {
+        match value {
+            ScaleFactor::None => cef_scale_factor_t::SCALE_FACTOR_NONE,
+            ScaleFactor::P100 => cef_scale_factor_t::SCALE_FACTOR_100P,
+            ScaleFactor::P125 => cef_scale_factor_t::SCALE_FACTOR_125P,
+            ScaleFactor::P133 => cef_scale_factor_t::SCALE_FACTOR_133P,
+            ScaleFactor::P140 => cef_scale_factor_t::SCALE_FACTOR_140P,
+            ScaleFactor::P150 => cef_scale_factor_t::SCALE_FACTOR_150P,
+            ScaleFactor::P180 => cef_scale_factor_t::SCALE_FACTOR_180P,
+            ScaleFactor::P200 => cef_scale_factor_t::SCALE_FACTOR_200P,
+            ScaleFactor::P250 => cef_scale_factor_t::SCALE_FACTOR_250P,
+            ScaleFactor::P300 => cef_scale_factor_t::SCALE_FACTOR_300P
+        }
+    }
+}
+
+/// Structure used to implement a custom resource bundle structure. See
+/// CefSettings for additional options related to resource bundle loading. The
+/// functions of this structure may be called on multiple threads.
+pub trait ResourceBundleHandlerCallbacks: Send + Sync + 'static {
+    /// Called to retrieve a localized translation for the specified |string_id|.
+    /// To provide the translation return Some(string). To use the default
+    /// translation return None. Include cef_pack_strings.h for a listing of
+    /// valid string ID values.
+    fn get_localized_string(&mut self, _string_id: i32) -> Option<String> {
+        None
+    }
+
+    /// Called to retrieve data for the specified scale independent
+    /// |resource_id|. To provide the resource data return Some(data). To use
+    /// the default resource data return None. The resource data will not be
+    /// copied and must remain resident in memory, hence the 'static lifetime.
+    /// Include cef_pack_resources.h for a listing of valid resource ID values.
+    fn get_data_resource(&mut self, _resource_id: i32) -> Option<&'static [u8]> {
+        None
+    }
+
+    /// Called to retrieve data for the specified |resource_id| nearest the
+    /// scale factor |scale_factor|. To provide the resource data return
+    /// Some(data). To use the default resource data return None. The resource
+    /// data will not be copied and must remain resident in memory, hence the
+    /// 'static lifetime. Include cef_pack_resources.h for a listing of valid
+    /// resource ID values.
+    fn get_data_resource_for_scale(
+        &mut self,
+        _resource_id: i32,
+        _scale_factor: ScaleFactor
+    ) -> Option<&'static [u8]> {
+        None
+    }
+}
+
+// Structure used to implement a custom resource bundle structure. See
+// CefSettings for additional options related to resource bundle loading. The
+// functions of this structure may be called on multiple threads.
+ref_counted_ptr!(ResourceBundleHandler, cef_resource_bundle_handler_t);
+
+impl ResourceBundleHandler {
+    pub fn new<C: ResourceBundleHandlerCallbacks>(delegate: C) -> Self {
+        Self(ResourceBundleHandlerWrapper::new(delegate).wrap())
+    }
+}
+
+/// Translates CEF -> Rust callbacks.
+struct ResourceBundleHandlerWrapper<C: ResourceBundleHandlerCallbacks>(C);
+
+impl<C: ResourceBundleHandlerCallbacks> ResourceBundleHandlerWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
+    }
+
+    /// Hands a resident resource back to CEF.
+    unsafe fn set_data(
+        resource: Option<&'static [u8]>,
+        data: *mut *mut c_void,
+        data_size: *mut usize
+    ) -> c_int {
+        match resource {
+            Some(resource) => {
+                *data = resource.as_ptr() as *mut c_void;
+                *data_size = resource.len();
+
+                1
+            },
+            None => 0
+        }
+    }
+
+    /// Called to retrieve a localized translation for the specified |string_id|.
+    /// To provide the translation set |string| to the translation string and
+    /// return true (1). To use the default translation return false (0). Include
+    /// cef_pack_strings.h for a listing of valid string ID values.
+    unsafe extern "C" fn c_get_localized_string(
+        this: *mut cef_resource_bundle_handler_t,
+        string_id: c_int,
+        string: *mut cef_string_t
+    ) -> c_int {
+        catch_unwind_or(
+            "ResourceBundleHandlerWrapper::c_get_localized_string",
+            0,
+            || {
+                let this: &mut Self = Wrapped::wrappable(this);
+
+                match this
+                    .0
+                    .get_localized_string(string_id)
+                {
+                    Some(value) => match CefString::from_ptr_mut(string) {
+                        Some(string) => {
+                            string.set(&value);
+
+                            1
+                        },
+                        None => 0
+                    },
+                    None => 0
+                }
+            }
+        )
+    }
+
+    /// Called to retrieve data for the specified scale independent
+    /// |resource_id|. To provide the resource data set |data| and |data_size| to
+    /// the data pointer and size respectively and return true (1). To use the
+    /// default resource data return false (0). The resource data will not be
+    /// copied and must remain resident in memory. Include cef_pack_resources.h
+    /// for a listing of valid resource ID values.
+    unsafe extern "C" fn c_get_data_resource(
+        this: *mut cef_resource_bundle_handler_t,
+        resource_id: c_int,
+        data: *mut *mut c_void,
+        data_size: *mut usize
+    ) -> c_int {
+        catch_unwind_or(
+            "ResourceBundleHandlerWrapper::c_get_data_resource",
+            0,
+            || {
+                let this: &mut Self = Wrapped::wrappable(this);
+                let resource = this
+                    .0
+                    .get_data_resource(resource_id);
+
+                Self::set_data(resource, data, data_size)
+            }
+        )
+    }
+
+    /// Called to retrieve data for the specified |resource_id| nearest the scale
+    /// factor |scale_factor|. To provide the resource data set |data| and
+    /// |data_size| to the data pointer and size respectively and return true
+    /// (1). To use the default resource data return false (0). The resource data
+    /// will not be copied and must remain resident in memory. Include
+    /// cef_pack_resources.h for a listing of valid resource ID values.
+    unsafe extern "C" fn c_get_data_resource_for_scale(
+        this: *mut cef_resource_bundle_handler_t,
+        resource_id: c_int,
+        scale_factor: cef_scale_factor_t,
+        data: *mut *mut c_void,
+        data_size: *mut usize
+    ) -> c_int {
+        catch_unwind_or(
+            "ResourceBundleHandlerWrapper::c_get_data_resource_for_scale",
+            0,
+            || {
+                let this: &mut Self = Wrapped::wrappable(this);
+                let resource = this
+                    .0
+                    .get_data_resource_for_scale(resource_id, scale_factor.into());
+
+                Self::set_data(resource, data, data_size)
+            }
+        )
+    }
+}
+
+impl<C: ResourceBundleHandlerCallbacks> Wrappable for ResourceBundleHandlerWrapper<C> {
+    type Cef = cef_resource_bundle_handler_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<cef_resource_bundle_handler_t> {
+        RefCountedPtr::wrap(
+            cef_resource_bundle_handler_t {
+                base: unsafe { zeroed() },
+
+                get_localized_string:        Some(Self::c_get_localized_string),
+                get_data_resource:           Some(Self::c_get_data_resource),
+                get_data_resource_for_scale: Some(Self::c_get_data_resource_for_scale)
+            },
+            self
+        )
+    }
+}
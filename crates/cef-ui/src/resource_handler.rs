@@ -0,0 +1,348 @@
+use crate::{
+    ref_counted_ptr, try_c, unwind::catch_unwind_or, Callback, CefString, RefCountedPtr, Request,
+    Response, Result, Wrappable, Wrapped
+};
+use cef_ui_sys::{
+    cef_callback_t, cef_request_t, cef_resource_handler_t, cef_resource_read_callback_t,
+    cef_resource_skip_callback_t, cef_response_t, cef_string_t
+};
+use std::{
+    ffi::{c_int, c_void},
+    mem::zeroed,
+    slice::from_raw_parts_mut
+};
+
+// Callback for asynchronous continuation of cef_resource_handler_t::skip().
+ref_counted_ptr!(ResourceSkipCallback, cef_resource_skip_callback_t);
+
+impl ResourceSkipCallback {
+    /// Callback for asynchronous continuation of skip(). If |bytes_skipped| > 0
+    /// then either skip() will be called again until the requested number of
+    /// bytes have been skipped or the request will proceed. If |bytes_skipped|
+    /// <= 0 the request will fail with ERR_REQUEST_RANGE_NOT_SATISFIABLE.
+    pub fn cont(&self, bytes_skipped: i64) -> Result<()> {
+        try_c!(self, cont, { Ok(cont(self.as_ptr(), bytes_skipped)) })
+    }
+}
+
+// Callback for asynchronous continuation of cef_resource_handler_t::read().
+ref_counted_ptr!(ResourceReadCallback, cef_resource_read_callback_t);
+
+impl ResourceReadCallback {
+    /// Callback for asynchronous continuation of read(). If |bytes_read| == 0
+    /// the response will be considered complete. If |bytes_read| > 0 then read()
+    /// will be called again until the request is complete (based on either the
+    /// result or the expected content length). If |bytes_read| < 0 then the
+    /// request will fail and the |bytes_read| value will be treated as the error
+    /// code.
+    pub fn cont(&self, bytes_read: i32) -> Result<()> {
+        try_c!(self, cont, { Ok(cont(self.as_ptr(), bytes_read)) })
+    }
+}
+
+/// The buffer passed to ResourceHandlerCallbacks::read. The buffer is owned by
+/// CEF and remains valid until read returns or, if the data is provided later,
+/// until ResourceReadCallback::cont is called. It must not be used after that.
+#[derive(Debug)]
+pub struct ResourceReadBuffer {
+    data: *mut u8,
+    len:  usize
+}
+
+// The buffer may be filled from any thread before the read callback is
+// executed.
+unsafe impl Send for ResourceReadBuffer {}
+
+impl ResourceReadBuffer {
+    /// The number of bytes that may be written.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if no bytes may be written.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Copies as much of |data| as fits and returns the number of bytes copied.
+    pub fn write(&mut self, data: &[u8]) -> usize {
+        let count = data.len().min(self.len);
+        let buffer = unsafe { self.as_mut_slice() };
+
+        buffer[..count].copy_from_slice(&data[..count]);
+
+        count
+    }
+
+    /// Returns the buffer as a slice.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the buffer is still valid; see
+    /// ResourceReadBuffer.
+    pub unsafe fn as_mut_slice(&mut self) -> &mut [u8] {
+        match self.data.is_null() {
+            true => &mut [],
+            false => from_raw_parts_mut(self.data, self.len)
+        }
+    }
+}
+
+/// Structure used to implement a custom request handler structure. The
+/// functions of this structure will be called on the IO thread unless
+/// otherwise indicated.
+pub trait ResourceHandlerCallbacks: Send + Sync + 'static {
+    /// Open the response stream. To handle the request immediately set
+    /// |handle_request| to true (1) and return true (1). To decide at a later
+    /// time set |handle_request| to false (0), return true (1), and execute
+    /// |callback| to continue or cancel the request. To cancel the request
+    /// immediately set |handle_request| to true (1) and return false (0). This
+    /// function will be called in sequence but not from a dedicated thread.
+    fn open(&mut self, request: Request, handle_request: &mut bool, callback: Callback) -> bool;
+
+    /// Retrieve response header information. If the response length is not
+    /// known set |response_length| to -1 and read() will be called until it
+    /// returns false (0). If the response length is known set |response_length|
+    /// to a positive value and read() will be called until it returns false (0)
+    /// or the specified number of bytes have been read. Use the |response|
+    /// object to set the mime type, http status code and other optional header
+    /// values. To redirect the request to a new URL set |redirect_url| to the new
+    /// URL. |redirect_url| can be either a relative or fully qualified URL. It is
+    /// also possible to set |response| to a redirect http status code and pass
+    /// the new URL via a Location header. Likewise with |redirect_url| it is
+    /// valid to set a relative or fully qualified URL as the Location header
+    /// value. If an error occured while setting up the request you can call
+    /// set_error() on |response| to indicate the error condition.
+    fn get_response_headers(
+        &mut self,
+        response: Response,
+        response_length: &mut i64,
+        redirect_url: &mut Option<String>
+    );
+
+    /// Skip response data when requested by a Range header. Skip over and
+    /// discard |bytes_to_skip| bytes of response data. If data is available
+    /// immediately set |bytes_skipped| to the number of bytes skipped and return
+    /// true (1). To read the data at a later time set |bytes_skipped| to 0,
+    /// return true (1) and execute |callback| when the data is available. To
+    /// indicate failure set |bytes_skipped| to < 0 (e.g. -2 for ERR_FAILED) and
+    /// return false (0). This function will be called in sequence but not from a
+    /// dedicated thread.
+    fn skip(
+        &mut self,
+        _bytes_to_skip: i64,
+        bytes_skipped: &mut i64,
+        _callback: ResourceSkipCallback
+    ) -> bool {
+        *bytes_skipped = -2;
+
+        false
+    }
+
+    /// Read response data. If data is available immediately copy up to
+    /// |data_out|.len() bytes into |data_out|, set |bytes_read| to the number of
+    /// bytes copied, and return true (1). To read the data at a later time keep
+    /// |data_out|, set |bytes_read| to 0, return true (1) and execute |callback|
+    /// when the data is available (|data_out| will remain valid until the
+    /// callback is executed). To indicate response completion set |bytes_read|
+    /// to 0 and return false (0). To indicate failure set |bytes_read| to < 0
+    /// (e.g. -2 for ERR_FAILED) and return false (0). This function will be
+    /// called in sequence but not from a dedicated thread.
+    fn read(
+        &mut self,
+        data_out: ResourceReadBuffer,
+        bytes_read: &mut i32,
+        callback: ResourceReadCallback
+    ) -> bool;
+
+    /// Request processing has been canceled.
+    fn cancel(&mut self) {}
+}
+
+// Structure used to implement a custom request handler structure. The
+// functions of this structure will be called on the IO thread unless otherwise
+// indicated.
+ref_counted_ptr!(ResourceHandler, cef_resource_handler_t);
+
+impl ResourceHandler {
+    pub fn new<C: ResourceHandlerCallbacks>(delegate: C) -> Self {
+        Self(ResourceHandlerWrapper::new(delegate).wrap())
+    }
+}
+
+/// Translates CEF -> Rust callbacks.
+struct ResourceHandlerWrapper<C: ResourceHandlerCallbacks>(C);
+
+impl<C: ResourceHandlerCallbacks> ResourceHandlerWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
+    }
+
+    /// Open the response stream. To handle the request immediately set
+    /// |handle_request| to true (1) and return true (1). To decide at a later
+    /// time set |handle_request| to false (0), return true (1), and execute
+    /// |callback| to continue or cancel the request. To cancel the request
+    /// immediately set |handle_request| to true (1) and return false (0). This
+    /// function will be called in sequence but not from a dedicated thread. For
+    /// backwards compatibility set |handle_request| to false (0) and return false
+    /// (0) and the ProcessRequest function will be called.
+    unsafe extern "C" fn c_open(
+        this: *mut cef_resource_handler_t,
+        request: *mut cef_request_t,
+        handle_request: *mut c_int,
+        callback: *mut cef_callback_t
+    ) -> c_int {
+        catch_unwind_or("ResourceHandlerWrapper::c_open", 0, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let request = Request::from_ptr_unchecked(request);
+            let callback = Callback::from_ptr_unchecked(callback);
+            let mut local_handle_request = false;
+
+            let result = this
+                .0
+                .open(request, &mut local_handle_request, callback);
+
+            *handle_request = local_handle_request as c_int;
+
+            result as c_int
+        })
+    }
+
+    /// Retrieve response header information. If the response length is not
+    /// known set |response_length| to -1 and read_response() will be called
+    /// until it returns false (0). If the response length is known set
+    /// |response_length| to a positive value and read_response() will be called
+    /// until it returns false (0) or the specified number of bytes have been
+    /// read. Use the |response| object to set the mime type, http status code
+    /// and other optional header values. To redirect the request to a new URL
+    /// set |redirectUrl| to the new URL. |redirectUrl| can be either a relative
+    /// or fully qualified URL. It is also possible to set |response| to a
+    /// redirect http status code and pass the new URL via a Location header.
+    /// Likewise with |redirectUrl| it is valid to set a relative or fully
+    /// qualified URL as the Location header value. If an error occured while
+    /// setting up the request you can call set_error() on |response| to
+    /// indicate the error condition.
+    unsafe extern "C" fn c_get_response_headers(
+        this: *mut cef_resource_handler_t,
+        response: *mut cef_response_t,
+        response_length: *mut i64,
+        redirect_url: *mut cef_string_t
+    ) {
+        catch_unwind_or("ResourceHandlerWrapper::c_get_response_headers", (), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let response = Response::from_ptr_unchecked(response);
+            let mut local_response_length = -1;
+            let mut local_redirect_url = None;
+
+            this.0.get_response_headers(
+                response,
+                &mut local_response_length,
+                &mut local_redirect_url
+            );
+
+            *response_length = local_response_length;
+
+            if let (Some(value), Some(redirect_url)) =
+                (local_redirect_url, CefString::from_ptr_mut(redirect_url))
+            {
+                redirect_url.set(&value);
+            }
+        })
+    }
+
+    /// Skip response data when requested by a Range header. Skip over and
+    /// discard |bytes_to_skip| bytes of response data. If data is available
+    /// immediately set |bytes_skipped| to the number of bytes skipped and return
+    /// true (1). To read the data at a later time set |bytes_skipped| to 0,
+    /// return true (1) and execute |callback| when the data is available. To
+    /// indicate failure set |bytes_skipped| to < 0 (e.g. -2 for ERR_FAILED) and
+    /// return false (0). This function will be called in sequence but not from a
+    /// dedicated thread.
+    unsafe extern "C" fn c_skip(
+        this: *mut cef_resource_handler_t,
+        bytes_to_skip: i64,
+        bytes_skipped: *mut i64,
+        callback: *mut cef_resource_skip_callback_t
+    ) -> c_int {
+        catch_unwind_or("ResourceHandlerWrapper::c_skip", 0, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let callback = ResourceSkipCallback::from_ptr_unchecked(callback);
+            let mut local_bytes_skipped = 0;
+
+            let result = this
+                .0
+                .skip(bytes_to_skip, &mut local_bytes_skipped, callback);
+
+            *bytes_skipped = local_bytes_skipped;
+
+            result as c_int
+        })
+    }
+
+    /// Read response data. If data is available immediately copy up to
+    /// |bytes_to_read| bytes into |data_out|, set |bytes_read| to the number of
+    /// bytes copied, and return true (1). To read the data at a later time keep a
+    /// pointer to |data_out|, set |bytes_read| to 0, return true (1) and execute
+    /// |callback| when the data is available (|data_out| will remain valid until
+    /// the callback is executed). To indicate response completion set
+    /// |bytes_read| to 0 and return false (0). To indicate failure set
+    /// |bytes_read| to < 0 (e.g. -2 for ERR_FAILED) and return false (0). This
+    /// function will be called in sequence but not from a dedicated thread. For
+    /// backwards compatibility set |bytes_read| to -1 and return false (0) and
+    /// the ReadResponse function will be called.
+    unsafe extern "C" fn c_read(
+        this: *mut cef_resource_handler_t,
+        data_out: *mut c_void,
+        bytes_to_read: c_int,
+        bytes_read: *mut c_int,
+        callback: *mut cef_resource_read_callback_t
+    ) -> c_int {
+        catch_unwind_or("ResourceHandlerWrapper::c_read", 0, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let data_out = ResourceReadBuffer {
+                data: data_out as *mut u8,
+                len:  bytes_to_read.max(0) as usize
+            };
+            let callback = ResourceReadCallback::from_ptr_unchecked(callback);
+            let mut local_bytes_read = 0;
+
+            let result = this
+                .0
+                .read(data_out, &mut local_bytes_read, callback);
+
+            *bytes_read = local_bytes_read;
+
+            result as c_int
+        })
+    }
+
+    /// Request processing has been canceled.
+    unsafe extern "C" fn c_cancel(this: *mut cef_resource_handler_t) {
+        catch_unwind_or("ResourceHandlerWrapper::c_cancel", (), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+
+            this.0.cancel();
+        })
+    }
+}
+
+impl<C: ResourceHandlerCallbacks> Wrappable for ResourceHandlerWrapper<C> {
+    type Cef = cef_resource_handler_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<Self::Cef> {
+        RefCountedPtr::wrap(
+            cef_resource_handler_t {
+                base:                 unsafe { zeroed() },
+                open:                 Some(Self::c_open),
+                process_request:      None,
+                get_response_headers: Some(Self::c_get_response_headers),
+                skip:                 Some(Self::c_skip),
+                read:                 Some(Self::c_read),
+                read_response:        None,
+                cancel:               Some(Self::c_cancel)
+            },
+            self
+        )
+    }
+}
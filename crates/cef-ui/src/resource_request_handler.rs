@@ -1,15 +1,163 @@
-use crate::{ref_counted_ptr, RefCountedPtr, Wrappable};
+use crate::{
+    debug_assert_currently_on, ref_counted_ptr, unwind::catch_unwind_or, Browser, Callback,
+    CefString, Frame, RefCountedPtr, Request, ResourceHandler, Response, ThreadId,
+    UrlRequestStatus, Wrappable, Wrapped
+};
 use cef_ui_sys::{
-    cef_browser_t, cef_callback_t, cef_cookie_access_filter_t, cef_frame_t, cef_request_t,
-    cef_resource_handler_t, cef_resource_request_handler_t, cef_response_filter_t, cef_response_t,
-    cef_return_value_t, cef_string_t, cef_urlrequest_status_t
+    cef_browser_t, cef_callback_t, cef_frame_t, cef_request_t, cef_resource_handler_t,
+    cef_resource_request_handler_t, cef_response_t, cef_return_value_t, cef_string_t,
+    cef_urlrequest_status_t
 };
-use std::{ffi::c_int, mem::zeroed};
+use std::{ffi::c_int, mem::zeroed, ptr::null_mut};
+
+/// Return value types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ReturnValue {
+    /// Cancel immediately.
+    Cancel,
+
+    /// Continue immediately.
+    Continue,
+
+    /// Continue asynchronously (usually via a callback).
+    ContinueAsync
+}
+
+impl From<cef_return_value_t> for ReturnValue {
+    fn from(value: cef_return_value_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_return_value_t> for ReturnValue {
+    fn from(value: &cef_return_value_t) -> Self {
+        match value {
+            cef_return_value_t::RV_CANCEL => Self::Cancel,
+            cef_return_value_t::RV_CONTINUE => Self::Continue,
+            cef_return_value_t::RV_CONTINUE_ASYNC => Self::ContinueAsync
+        }
+    }
+}
+
+impl From<ReturnValue> for cef_return_value_t {
+    fn from(value: ReturnValue) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&ReturnValue> for cef_return_value_t {
+    fn from(value: &ReturnValue) -> Self {
+        match value {
+            ReturnValue::Cancel => cef_return_value_t::RV_CANCEL,
+            ReturnValue::Continue => cef_return_value_t::RV_CONTINUE,
+            ReturnValue::ContinueAsync => cef_return_value_t::RV_CONTINUE_ASYNC
+        }
+    }
+}
 
 /// Implement this structure to handle events related to browser requests. The
 /// functions of this structure will be called on the IO thread unless otherwise
 /// indicated.
 pub trait ResourceRequestHandlerCallbacks: Send + Sync + 'static {
+    /// Called on the IO thread before a resource request is loaded. The |browser|
+    /// and |frame| values represent the source of the request, and may be NULL
+    /// for requests originating from service workers or cef_urlrequest_t. To
+    /// redirect or change the resource load optionally modify |request|.
+    /// Modification of the request URL will be treated as a redirect. Return
+    /// RV_CONTINUE to continue the request immediately. Return RV_CONTINUE_ASYNC
+    /// and call cef_callback_t functions at a later time to continue or cancel
+    /// the request asynchronously. Return RV_CANCEL to cancel the request
+    /// immediately.
+    fn on_before_resource_load(
+        &mut self,
+        _browser: Option<Browser>,
+        _frame: Option<Frame>,
+        _request: Request,
+        _callback: Callback
+    ) -> ReturnValue {
+        ReturnValue::Continue
+    }
+
+    /// Called on the IO thread before a resource is loaded. The |browser| and
+    /// |frame| values represent the source of the request, and may be NULL for
+    /// requests originating from service workers or cef_urlrequest_t. To allow
+    /// the resource to load using the default network loader return NULL. To
+    /// specify a handler for the resource return a cef_resource_handler_t object.
+    /// The |request| object cannot not be modified in this callback.
+    fn get_resource_handler(
+        &mut self,
+        _browser: Option<Browser>,
+        _frame: Option<Frame>,
+        _request: Request
+    ) -> Option<ResourceHandler> {
+        None
+    }
+
+    /// Called on the IO thread when a resource load is redirected. The |browser|
+    /// and |frame| values represent the source of the request, and may be NULL
+    /// for requests originating from service workers or cef_urlrequest_t. The
+    /// |request| parameter will contain the old URL and other request-related
+    /// information. The |response| parameter will contain the response that
+    /// resulted in the redirect. The |new_url| parameter will contain the new URL
+    /// and can be changed if desired. The |request| and |response| objects cannot
+    /// be modified in this callback.
+    fn on_resource_redirect(
+        &mut self,
+        _browser: Option<Browser>,
+        _frame: Option<Frame>,
+        _request: Request,
+        _response: Response,
+        _new_url: &mut String
+    ) {
+    }
+
+    /// Called on the IO thread when a resource response is received. The
+    /// |browser| and |frame| values represent the source of the request, and may
+    /// be NULL for requests originating from service workers or cef_urlrequest_t.
+    /// To allow the resource load to proceed without modification return false
+    /// (0). To redirect or retry the resource load optionally modify |request|
+    /// and return true (1). Modification of the request URL will be treated as a
+    /// redirect. Requests handled using the default network loader cannot be
+    /// redirected in this callback. The |response| object cannot be modified in
+    /// this callback.
+    ///
+    /// WARNING: Redirecting using this function is deprecated. Use
+    /// OnBeforeResourceLoad or GetResourceHandler to perform redirects.
+    fn on_resource_response(
+        &mut self,
+        _browser: Option<Browser>,
+        _frame: Option<Frame>,
+        _request: Request,
+        _response: Response
+    ) -> bool {
+        false
+    }
+
+    /// Called on the IO thread when a resource load has completed. The |browser|
+    /// and |frame| values represent the source of the request, and may be NULL
+    /// for requests originating from service workers or cef_urlrequest_t.
+    /// |request| and |response| represent the request and response respectively
+    /// and cannot be modified in this callback. |status| indicates the load
+    /// completion status. |received_content_length| is the number of response
+    /// bytes actually read. This function will be called for all requests,
+    /// including requests that are aborted due to CEF shutdown or destruction of
+    /// the associated browser. In cases where the associated browser is destroyed
+    /// this callback may arrive after the cef_life_span_handler_t::OnBeforeClose
+    /// callback for that browser. The cef_frame_t::IsValid function can be used
+    /// to test for this situation, and care should be taken not to call |browser|
+    /// or |frame| functions that modify state (like LoadURL, SendProcessMessage,
+    /// etc.) if the frame is invalid.
+    fn on_resource_load_complete(
+        &mut self,
+        _browser: Option<Browser>,
+        _frame: Option<Frame>,
+        _request: Request,
+        _response: Response,
+        _status: UrlRequestStatus,
+        _received_content_length: i64
+    ) {
+    }
+
     // TODO: Fix this!
 
     // /// Called on the IO thread before a resource request is loaded. The |browser|
@@ -24,69 +172,6 @@ pub trait ResourceRequestHandlerCallbacks: Send + Sync + 'static {
     // // struct _cef_frame_t* frame,
     // // struct _cef_request_t* request);
     //
-    // /// Called on the IO thread before a resource request is loaded. The |browser|
-    // /// and |frame| values represent the source of the request, and may be NULL
-    // /// for requests originating from service workers or cef_urlrequest_t. To
-    // /// redirect or change the resource load optionally modify |request|.
-    // /// Modification of the request URL will be treated as a redirect. Return
-    // /// RV_CONTINUE to continue the request immediately. Return RV_CONTINUE_ASYNC
-    // /// and call cef_callback_t functions at a later time to continue or cancel
-    // /// the request asynchronously. Return RV_CANCEL to cancel the request
-    // /// immediately.
-    // // cef_return_value_t(CEF_CALLBACK* on_before_resource_load)(
-    // // struct _cef_resource_request_handler_t* self,
-    // // struct _cef_browser_t* browser,
-    // // struct _cef_frame_t* frame,
-    // // struct _cef_request_t* request,
-    // // struct _cef_callback_t* callback);
-    //
-    // /// Called on the IO thread before a resource is loaded. The |browser| and
-    // /// |frame| values represent the source of the request, and may be NULL for
-    // /// requests originating from service workers or cef_urlrequest_t. To allow
-    // /// the resource to load using the default network loader return NULL. To
-    // /// specify a handler for the resource return a cef_resource_handler_t object.
-    // /// The |request| object cannot not be modified in this callback.
-    // // struct _cef_resource_handler_t*(CEF_CALLBACK* get_resource_handler)(
-    // // struct _cef_resource_request_handler_t* self,
-    // // struct _cef_browser_t* browser,
-    // // struct _cef_frame_t* frame,
-    // // struct _cef_request_t* request);
-    //
-    // /// Called on the IO thread when a resource load is redirected. The |browser|
-    // /// and |frame| values represent the source of the request, and may be NULL
-    // /// for requests originating from service workers or cef_urlrequest_t. The
-    // /// |request| parameter will contain the old URL and other request-related
-    // /// information. The |response| parameter will contain the response that
-    // /// resulted in the redirect. The |new_url| parameter will contain the new URL
-    // /// and can be changed if desired. The |request| and |response| objects cannot
-    // /// be modified in this callback.
-    // // void(CEF_CALLBACK* on_resource_redirect)(
-    // // struct _cef_resource_request_handler_t* self,
-    // // struct _cef_browser_t* browser,
-    // // struct _cef_frame_t* frame,
-    // // struct _cef_request_t* request,
-    // // struct _cef_response_t* response,
-    // // cef_string_t* new_url);
-    //
-    // /// Called on the IO thread when a resource response is received. The
-    // /// |browser| and |frame| values represent the source of the request, and may
-    // /// be NULL for requests originating from service workers or cef_urlrequest_t.
-    // /// To allow the resource load to proceed without modification return false
-    // /// (0). To redirect or retry the resource load optionally modify |request|
-    // /// and return true (1). Modification of the request URL will be treated as a
-    // /// redirect. Requests handled using the default network loader cannot be
-    // /// redirected in this callback. The |response| object cannot be modified in
-    // /// this callback.
-    // ///
-    // /// WARNING: Redirecting using this function is deprecated. Use
-    // /// OnBeforeResourceLoad or GetResourceHandler to perform redirects.
-    // // int(CEF_CALLBACK* on_resource_response)(
-    // // struct _cef_resource_request_handler_t* self,
-    // // struct _cef_browser_t* browser,
-    // // struct _cef_frame_t* frame,
-    // // struct _cef_request_t* request,
-    // // struct _cef_response_t* response);
-    //
     // /// Called on the IO thread to optionally filter resource response content.
     // /// The |browser| and |frame| values represent the source of the request, and
     // /// may be NULL for requests originating from service workers or
@@ -99,29 +184,6 @@ pub trait ResourceRequestHandlerCallbacks: Send + Sync + 'static {
     // // struct _cef_request_t* request,
     // // struct _cef_response_t* response);
     //
-    // /// Called on the IO thread when a resource load has completed. The |browser|
-    // /// and |frame| values represent the source of the request, and may be NULL
-    // /// for requests originating from service workers or cef_urlrequest_t.
-    // /// |request| and |response| represent the request and response respectively
-    // /// and cannot be modified in this callback. |status| indicates the load
-    // /// completion status. |received_content_length| is the number of response
-    // /// bytes actually read. This function will be called for all requests,
-    // /// including requests that are aborted due to CEF shutdown or destruction of
-    // /// the associated browser. In cases where the associated browser is destroyed
-    // /// this callback may arrive after the cef_life_span_handler_t::OnBeforeClose
-    // /// callback for that browser. The cef_frame_t::IsValid function can be used
-    // /// to test for this situation, and care should be taken not to call |browser|
-    // /// or |frame| functions that modify state (like LoadURL, SendProcessMessage,
-    // /// etc.) if the frame is invalid.
-    // // void(CEF_CALLBACK* on_resource_load_complete)(
-    // // struct _cef_resource_request_handler_t* self,
-    // // struct _cef_browser_t* browser,
-    // // struct _cef_frame_t* frame,
-    // // struct _cef_request_t* request,
-    // // struct _cef_response_t* response,
-    // // cef_urlrequest_status_t status,
-    // // int64_t received_content_length);
-    //
     // /// Called on the IO thread to handle requests for URLs with an unknown
     // /// protocol component. The |browser| and |frame| values represent the source
     // /// of the request, and may be NULL for requests originating from service
@@ -153,32 +215,15 @@ impl ResourceRequestHandler {
 
 /// Translates CEF -> Rust callbacks.
 #[allow(dead_code)]
-struct ResourceRequestHandlerWrapper(Box<dyn ResourceRequestHandlerCallbacks>);
+struct ResourceRequestHandlerWrapper<C: ResourceRequestHandlerCallbacks>(C);
 
 // TODO: Remove this!
 
 #[allow(dead_code)]
 #[allow(unused_variables)]
-impl ResourceRequestHandlerWrapper {
-    pub fn new(delegate: impl ResourceRequestHandlerCallbacks) -> Self {
-        Self(Box::new(delegate))
-    }
-
-    // TODO: Fix this!
-
-    /// Called on the IO thread before a resource request is loaded. The |browser|
-    /// and |frame| values represent the source of the request, and may be NULL
-    /// for requests originating from service workers or cef_urlrequest_t. To
-    /// optionally filter cookies for the request return a
-    /// cef_cookie_access_filter_t object. The |request| object cannot not be
-    /// modified in this callback.
-    unsafe extern "C" fn c_get_cookie_access_filter(
-        this: *mut cef_resource_request_handler_t,
-        browser: *mut cef_browser_t,
-        frame: *mut cef_frame_t,
-        request: *mut cef_request_t
-    ) -> *mut cef_cookie_access_filter_t {
-        todo!()
+impl<C: ResourceRequestHandlerCallbacks> ResourceRequestHandlerWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
     }
 
     /// Called on the IO thread before a resource request is loaded. The |browser|
@@ -197,7 +242,23 @@ impl ResourceRequestHandlerWrapper {
         request: *mut cef_request_t,
         callback: *mut cef_callback_t
     ) -> cef_return_value_t {
-        todo!()
+        catch_unwind_or(
+            "ResourceRequestHandlerWrapper::c_on_before_resource_load",
+            cef_return_value_t::RV_CONTINUE,
+            || {
+                debug_assert_currently_on!(ThreadId::Io);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr(browser);
+                let frame = Frame::from_ptr(frame);
+                let request = Request::from_ptr_unchecked(request);
+                let callback = Callback::from_ptr_unchecked(callback);
+
+                this.0
+                    .on_before_resource_load(browser, frame, request, callback)
+                    .into()
+            }
+        )
     }
 
     /// Called on the IO thread before a resource is loaded. The |browser| and
@@ -212,7 +273,23 @@ impl ResourceRequestHandlerWrapper {
         frame: *mut cef_frame_t,
         request: *mut cef_request_t
     ) -> *mut cef_resource_handler_t {
-        todo!()
+        catch_unwind_or(
+            "ResourceRequestHandlerWrapper::c_get_resource_handler",
+            null_mut(),
+            || {
+                debug_assert_currently_on!(ThreadId::Io);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr(browser);
+                let frame = Frame::from_ptr(frame);
+                let request = Request::from_ptr_unchecked(request);
+
+                this.0
+                    .get_resource_handler(browser, frame, request)
+                    .map(|resource_handler| resource_handler.into_raw())
+                    .unwrap_or(null_mut())
+            }
+        )
     }
 
     /// Called on the IO thread when a resource load is redirected. The |browser|
@@ -231,7 +308,39 @@ impl ResourceRequestHandlerWrapper {
         response: *mut cef_response_t,
         new_url: *mut cef_string_t
     ) {
-        todo!()
+        catch_unwind_or(
+            "ResourceRequestHandlerWrapper::c_on_resource_redirect",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Io);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr(browser);
+                let frame = Frame::from_ptr(frame);
+                let request = Request::from_ptr_unchecked(request);
+                let response = Response::from_ptr_unchecked(response);
+
+                match CefString::from_ptr_mut(new_url) {
+                    Some(new_url) => {
+                        let current: String = (&*new_url).into();
+                        let mut value = current.clone();
+
+                        this.0
+                            .on_resource_redirect(browser, frame, request, response, &mut value);
+
+                        if value != current {
+                            new_url.set(&value);
+                        }
+                    },
+                    None => {
+                        let mut value = String::new();
+
+                        this.0
+                            .on_resource_redirect(browser, frame, request, response, &mut value);
+                    }
+                }
+            }
+        )
     }
 
     /// Called on the IO thread when a resource response is received. The
@@ -253,22 +362,23 @@ impl ResourceRequestHandlerWrapper {
         request: *mut cef_request_t,
         response: *mut cef_response_t
     ) -> c_int {
-        todo!()
-    }
+        catch_unwind_or(
+            "ResourceRequestHandlerWrapper::c_on_resource_response",
+            0,
+            || {
+                debug_assert_currently_on!(ThreadId::Io);
 
-    /// Called on the IO thread to optionally filter resource response content.
-    /// The |browser| and |frame| values represent the source of the request, and
-    /// may be NULL for requests originating from service workers or
-    /// cef_urlrequest_t. |request| and |response| represent the request and
-    /// response respectively and cannot be modified in this callback.
-    unsafe extern "C" fn c_get_resource_response_filter(
-        this: *mut cef_resource_request_handler_t,
-        browser: *mut cef_browser_t,
-        frame: *mut cef_frame_t,
-        request: *mut cef_request_t,
-        response: *mut cef_response_t
-    ) -> *mut cef_response_filter_t {
-        todo!()
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr(browser);
+                let frame = Frame::from_ptr(frame);
+                let request = Request::from_ptr_unchecked(request);
+                let response = Response::from_ptr_unchecked(response);
+
+                this.0
+                    .on_resource_response(browser, frame, request, response)
+                    as c_int
+            }
+        )
     }
 
     /// Called on the IO thread when a resource load has completed. The |browser|
@@ -294,29 +404,32 @@ impl ResourceRequestHandlerWrapper {
         status: cef_urlrequest_status_t,
         received_content_length: i64
     ) {
-        todo!()
-    }
+        catch_unwind_or(
+            "ResourceRequestHandlerWrapper::c_on_resource_load_complete",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Io);
 
-    /// Called on the IO thread to handle requests for URLs with an unknown
-    /// protocol component. The |browser| and |frame| values represent the source
-    /// of the request, and may be NULL for requests originating from service
-    /// workers or cef_urlrequest_t. |request| cannot be modified in this
-    /// callback. Set |allow_os_execution| to true (1) to attempt execution via
-    /// the registered OS protocol handler, if any. SECURITY WARNING: YOU SHOULD
-    /// USE THIS METHOD TO ENFORCE RESTRICTIONS BASED ON SCHEME, HOST OR OTHER URL
-    /// ANALYSIS BEFORE ALLOWING OS EXECUTION.
-    unsafe extern "C" fn c_on_protocol_execution(
-        this: *mut cef_resource_request_handler_t,
-        browser: *mut cef_browser_t,
-        frame: *mut cef_frame_t,
-        request: *mut cef_request_t,
-        allow_os_execution: *mut c_int
-    ) {
-        todo!()
+                let this: &mut Self = Wrapped::wrappable(this);
+                let browser = Browser::from_ptr(browser);
+                let frame = Frame::from_ptr(frame);
+                let request = Request::from_ptr_unchecked(request);
+                let response = Response::from_ptr_unchecked(response);
+
+                this.0.on_resource_load_complete(
+                    browser,
+                    frame,
+                    request,
+                    response,
+                    status.into(),
+                    received_content_length
+                );
+            }
+        )
     }
 }
 
-impl Wrappable for ResourceRequestHandlerWrapper {
+impl<C: ResourceRequestHandlerCallbacks> Wrappable for ResourceRequestHandlerWrapper<C> {
     type Cef = cef_resource_request_handler_t;
 
     fn wrap(self) -> RefCountedPtr<Self::Cef> {
@@ -324,14 +437,15 @@ impl Wrappable for ResourceRequestHandlerWrapper {
             cef_resource_request_handler_t {
                 base: unsafe { zeroed() },
 
+                on_before_resource_load:   Some(Self::c_on_before_resource_load),
+                get_resource_handler:      Some(Self::c_get_resource_handler),
+                on_resource_redirect:      Some(Self::c_on_resource_redirect),
+                on_resource_response:      Some(Self::c_on_resource_response),
+                on_resource_load_complete: Some(Self::c_on_resource_load_complete),
+
                 // TODO: Fix this!
                 get_cookie_access_filter:     None,
-                on_before_resource_load:      None,
-                get_resource_handler:         None,
-                on_resource_redirect:         None,
-                on_resource_response:         None,
                 get_resource_response_filter: None,
-                on_resource_load_complete:    None,
                 on_protocol_execution:        None
             },
             self
@@ -1,5 +1,4 @@
-use crate::{ref_counted_ptr, try_c, CefString, CefStringMultiMap, ErrorCode};
-use anyhow::Result;
+use crate::{ref_counted_ptr, try_c, CefStr, CefString, CefStringMultiMap, ErrorCode, Result};
 use cef_ui_sys::{cef_response_create, cef_response_t};
 use std::{collections::HashMap, ffi::c_int};
 
@@ -135,6 +134,19 @@ impl Response {
         })
     }
 
+    /// Calls |f| with each response header name and value, without converting
+    /// them to Rust strings; use this on hot paths instead of get_header_map.
+    pub fn visit_headers(&self, f: impl FnMut(CefStr<'_>, CefStr<'_>)) -> Result<()> {
+        try_c!(self, get_header_map, {
+            let mut headers = CefStringMultiMap::new();
+
+            get_header_map(self.as_ptr(), headers.as_mut_ptr());
+            headers.for_each(f);
+
+            Ok(())
+        })
+    }
+
     /// Set all response header fields.
     pub fn set_header_map(&self, headers: &HashMap<String, Vec<String>>) -> Result<()> {
         try_c!(self, set_header_map, {
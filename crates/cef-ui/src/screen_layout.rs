@@ -0,0 +1,136 @@
+#[cfg(feature = "views")]
+use crate::Display;
+use crate::{DeviceScaleFactor, Point, Rect, ScreenInfo};
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+#[derive(Debug)]
+struct LayoutState {
+    view_bounds: Rect,
+    root_bounds: Option<Rect>
+}
+
+/// Tracks where a windowless view sits on the screen, so that the
+/// RenderHandlerCallbacks functions that CEF uses to place popup menus, select
+/// dropdowns and tooltips can be answered consistently across monitors. Call
+/// set_view_bounds whenever the native window that hosts the view moves or is
+/// resized, then return:
+///
+/// - view_rect from get_view_rect,
+/// - root_screen_rect from get_root_screen_rect,
+/// - screen_point from get_screen_point,
+/// - screen_info from get_screen_info.
+///
+/// With the views feature the monitor is looked up with Display, otherwise the
+/// shared DeviceScaleFactor is used for every monitor. Clones share the same
+/// layout.
+#[derive(Debug, Clone)]
+pub struct ScreenLayout {
+    state: Arc<Mutex<LayoutState>>,
+    scale: DeviceScaleFactor
+}
+
+impl ScreenLayout {
+    /// A layout whose view is painted with |scale|.
+    pub fn new(scale: DeviceScaleFactor) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(LayoutState {
+                view_bounds: Rect::default(),
+                root_bounds: None
+            })),
+            scale
+        }
+    }
+
+    /// Returns the scale factor the view is painted with.
+    pub fn scale(&self) -> &DeviceScaleFactor {
+        &self.scale
+    }
+
+    /// Sets the position and size of the view in screen DIP coordinates.
+    pub fn set_view_bounds(&self, bounds: Rect) {
+        self.state.lock().view_bounds = bounds;
+    }
+
+    /// Sets the bounds of the top-level window that contains the view in screen
+    /// DIP coordinates, or None if the view is the whole window.
+    pub fn set_root_screen_rect(&self, bounds: Option<Rect>) {
+        self.state.lock().root_bounds = bounds;
+    }
+
+    /// Returns the position and size of the view in screen DIP coordinates.
+    pub fn view_bounds(&self) -> Rect {
+        self.state.lock().view_bounds
+    }
+
+    /// Returns the view rectangle for RenderHandlerCallbacks::get_view_rect. Its
+    /// origin is always (0, 0), as screen positions are provided by
+    /// screen_point.
+    pub fn view_rect(&self) -> Rect {
+        let bounds = self.view_bounds();
+
+        Rect::new(0, 0, bounds.width.max(1), bounds.height.max(1))
+    }
+
+    /// Returns the root window rectangle for
+    /// RenderHandlerCallbacks::get_root_screen_rect.
+    pub fn root_screen_rect(&self) -> Option<Rect> {
+        self.state.lock().root_bounds
+    }
+
+    /// Translates |view| from view DIP coordinates to screen coordinates for
+    /// RenderHandlerCallbacks::get_screen_point. The result is in screen pixels
+    /// on Windows and Linux, and in screen DIP coordinates on macOS.
+    pub fn screen_point(&self, view: &Point) -> Point {
+        let bounds = self.view_bounds();
+        let point = Point::new(bounds.x + view.x, bounds.y + view.y);
+
+        match cfg!(target_os = "macos") {
+            true => point,
+            false => self.to_screen_pixels(&point)
+        }
+    }
+
+    /// Returns screen info for RenderHandlerCallbacks::get_screen_info,
+    /// describing the monitor that shows most of the view.
+    pub fn screen_info(&self) -> ScreenInfo {
+        let bounds = self.view_bounds();
+
+        #[cfg(feature = "views")]
+        if let Some(display) = Display::get_matching_bounds(&bounds, false) {
+            if let (Ok(rect), Ok(available_rect)) = (display.get_bounds(), display.get_work_area())
+            {
+                return self
+                    .scale
+                    .screen_info(rect)
+                    .available_rect(available_rect);
+            }
+        }
+
+        self.scale.screen_info(bounds)
+    }
+
+    /// Converts |point| from screen DIP coordinates to screen pixels.
+    fn to_screen_pixels(&self, point: &Point) -> Point {
+        #[cfg(all(feature = "views", target_os = "windows"))]
+        return Display::convert_screen_point_to_pixels(point);
+
+        #[cfg(all(feature = "views", not(target_os = "windows")))]
+        if let Some(pixels) = Display::get_nearest_point(point, false).and_then(|display| {
+            display
+                .convert_point_to_pixels(point)
+                .ok()
+        }) {
+            return pixels;
+        }
+
+        #[allow(unreachable_code)]
+        self.scale.to_pixel_point(point)
+    }
+}
+
+impl Default for ScreenLayout {
+    fn default() -> Self {
+        Self::new(DeviceScaleFactor::default())
+    }
+}
@@ -0,0 +1,315 @@
+#[cfg(any(feature = "osr", feature = "devtools"))]
+use crate::BinaryValue;
+#[cfg(feature = "osr")]
+use crate::{on_next_paint, AlphaType, Image, PaintElementType};
+#[cfg(feature = "devtools")]
+use crate::{
+    post_task, CefString, DevToolsMessageObserver, DevToolsMessageObserverCallbacks,
+    DictionaryValue, Registration
+};
+use crate::{run_on, Browser, CefFuture, Rect, Result, ThreadId};
+use anyhow::anyhow;
+#[cfg(feature = "devtools")]
+use cef_ui_sys::cef_base64decode;
+#[cfg(feature = "devtools")]
+use parking_lot::Mutex;
+#[cfg(feature = "devtools")]
+use std::sync::Arc;
+
+/// The image format of a screenshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenshotFormat {
+    /// Lossless, with transparency.
+    Png,
+
+    /// Lossy with |quality| from 0 (lowest) to 100 (highest).
+    Jpeg { quality: i32 }
+}
+
+impl Browser {
+    /// Captures the visible area of the page, or only |clip| (in view
+    /// coordinates) if it is set, and resolves with the encoded image.
+    ///
+    /// Windowed browsers are captured with the DevTools protocol
+    /// (Page.captureScreenshot). Windowless browsers are repainted and the
+    /// next frame passed to RenderHandlerCallbacks::on_paint is encoded, so
+    /// the result matches what the application presents; a hidden windowless
+    /// browser does not paint, and the future does not resolve until it is
+    /// shown again.
+    pub async fn capture_screenshot(
+        &self,
+        format: ScreenshotFormat,
+        clip: Option<Rect>
+    ) -> Result<Vec<u8>> {
+        let browser = self.clone();
+
+        run_on(ThreadId::Ui, move || start_capture(&browser, format, clip))?
+            .await??
+            .await?
+    }
+}
+
+/// Starts capturing a screenshot of |browser|. Must be called on the UI thread.
+fn start_capture(
+    browser: &Browser,
+    format: ScreenshotFormat,
+    clip: Option<Rect>
+) -> Result<CefFuture<Result<Vec<u8>>>> {
+    let host = browser.get_host()?;
+
+    match host.is_window_rendering_disabled()? {
+        true => capture_paint(browser, format, clip),
+        false => capture_dev_tools(browser, format, clip)
+    }
+}
+
+/// Encodes the next view paint of the windowless |browser|.
+#[cfg(feature = "osr")]
+fn capture_paint(
+    browser: &Browser,
+    format: ScreenshotFormat,
+    clip: Option<Rect>
+) -> Result<CefFuture<Result<Vec<u8>>>> {
+    let (complete, future) = CefFuture::new();
+
+    on_next_paint(
+        browser.get_identifier()?,
+        Box::new(move |buffer, width, height, view_rect| {
+            complete(encode_frame(buffer, width, height, view_rect, format, clip));
+        })
+    );
+
+    browser
+        .get_host()?
+        .invalidate(PaintElementType::View)?;
+
+    Ok(future)
+}
+
+/// Encodes the next view paint of the windowless |browser|.
+#[cfg(not(feature = "osr"))]
+fn capture_paint(
+    _browser: &Browser,
+    _format: ScreenshotFormat,
+    _clip: Option<Rect>
+) -> Result<CefFuture<Result<Vec<u8>>>> {
+    Err(anyhow!("Capturing windowless browsers requires the osr feature.").into())
+}
+
+/// Crops and encodes a BGRA frame. |clip| is in view coordinates, which are
+/// scaled to the frame's pixels by the ratio of its width to |view_rect|.
+#[cfg(feature = "osr")]
+fn encode_frame(
+    buffer: &[u8],
+    width: usize,
+    height: usize,
+    view_rect: &Rect,
+    format: ScreenshotFormat,
+    clip: Option<Rect>
+) -> Result<Vec<u8>> {
+    let bounds = Rect::new(0, 0, width as i32, height as i32);
+    let rect = match clip {
+        Some(clip) => {
+            let scale = match view_rect.width > 0 {
+                true => width as f32 / view_rect.width as f32,
+                false => 1.0
+            };
+            let scaled = |value: i32| (value as f32 * scale).round() as i32;
+
+            Rect::new(
+                scaled(clip.x),
+                scaled(clip.y),
+                scaled(clip.width),
+                scaled(clip.height)
+            )
+            .intersect(&bounds)
+        },
+        None => bounds
+    };
+
+    if rect.is_empty() {
+        return Err(anyhow!("The screenshot area is empty.").into());
+    }
+
+    let stride = width * 4;
+    let start = rect.x as usize * 4;
+    let end = start + rect.width as usize * 4;
+    let pixels: Vec<u8> = (rect.y as usize..(rect.y + rect.height) as usize)
+        .flat_map(|row| &buffer[row * stride + start..row * stride + end])
+        .copied()
+        .collect();
+    let image = Image::from_bgra(
+        1.0,
+        rect.width,
+        rect.height,
+        AlphaType::Premultiplied,
+        &pixels
+    )?;
+    let encoded = match format {
+        ScreenshotFormat::Png => image.get_as_png(1.0, true)?,
+        ScreenshotFormat::Jpeg { quality } => image.get_as_jpeg(1.0, quality)?
+    };
+
+    match encoded {
+        Some(encoded) => binary_to_vec(&encoded.data),
+        None => Err(anyhow!("Failed to encode the screenshot.").into())
+    }
+}
+
+/// Captures the windowed |browser| with Page.captureScreenshot.
+#[cfg(feature = "devtools")]
+fn capture_dev_tools(
+    browser: &Browser,
+    format: ScreenshotFormat,
+    clip: Option<Rect>
+) -> Result<CefFuture<Result<Vec<u8>>>> {
+    let host = browser.get_host()?;
+    let (complete, future) = CefFuture::new();
+    let capture = Arc::new(Mutex::new(PendingCapture {
+        message_id:   None,
+        complete:     Some(Box::new(complete)),
+        registration: None
+    }));
+    let registration = host.add_dev_tools_message_observer(DevToolsMessageObserver::new(
+        ScreenshotObserver(capture.clone())
+    ))?;
+    let params = DictionaryValue::new();
+
+    match format {
+        ScreenshotFormat::Png => {
+            params.set_string("format", "png")?;
+        },
+        ScreenshotFormat::Jpeg { quality } => {
+            params.set_string("format", "jpeg")?;
+            params.set_int("quality", quality.clamp(0, 100))?;
+        }
+    }
+
+    if let Some(clip) = clip {
+        let viewport = DictionaryValue::new();
+
+        viewport.set_double("x", clip.x as f64)?;
+        viewport.set_double("y", clip.y as f64)?;
+        viewport.set_double("width", clip.width as f64)?;
+        viewport.set_double("height", clip.height as f64)?;
+        viewport.set_double("scale", 1.0)?;
+        params.set_dictionary("clip", viewport)?;
+    }
+
+    // Results are delivered asynchronously on this thread, so the observer
+    // cannot see the result before the message ID is known.
+    let message_id = host.execute_dev_tools_method(0, "Page.captureScreenshot", Some(params))?;
+
+    if message_id == 0 {
+        return Err(anyhow!("Failed to execute Page.captureScreenshot.").into());
+    }
+
+    let mut capture = capture.lock();
+
+    capture.message_id = Some(message_id);
+    capture.registration = registration;
+
+    Ok(future)
+}
+
+/// Captures the windowed |browser| with Page.captureScreenshot.
+#[cfg(not(feature = "devtools"))]
+fn capture_dev_tools(
+    _browser: &Browser,
+    _format: ScreenshotFormat,
+    _clip: Option<Rect>
+) -> Result<CefFuture<Result<Vec<u8>>>> {
+    Err(anyhow!("Capturing windowed browsers requires the devtools feature.").into())
+}
+
+/// Completes a screenshot future.
+#[cfg(feature = "devtools")]
+type CompleteCapture = Box<dyn FnOnce(Result<Vec<u8>>) + Send + 'static>;
+
+/// A Page.captureScreenshot call waiting for its result.
+#[cfg(feature = "devtools")]
+struct PendingCapture {
+    message_id:   Option<i32>,
+    complete:     Option<CompleteCapture>,
+    registration: Option<Registration>
+}
+
+#[cfg(feature = "devtools")]
+impl PendingCapture {
+    /// Resolves the capture and removes the observer.
+    fn finish(&mut self, result: Result<Vec<u8>>) {
+        if let Some(complete) = self.complete.take() {
+            complete(result);
+        }
+
+        // The observer is being called, so it is removed from a task instead.
+        if let Some(registration) = self.registration.take() {
+            post_task(ThreadId::Ui, move || drop(registration));
+        }
+    }
+}
+
+/// Waits for the result of a Page.captureScreenshot call.
+#[cfg(feature = "devtools")]
+struct ScreenshotObserver(Arc<Mutex<PendingCapture>>);
+
+#[cfg(feature = "devtools")]
+impl DevToolsMessageObserverCallbacks for ScreenshotObserver {
+    fn on_dev_tools_method_result(
+        &mut self,
+        _browser: Browser,
+        message_id: i32,
+        success: bool,
+        result: &[u8]
+    ) {
+        let mut capture = self.0.lock();
+
+        if capture.message_id != Some(message_id) {
+            return;
+        }
+
+        let result = match success {
+            true => decode_screenshot(result),
+            false => Err(anyhow!(
+                "Page.captureScreenshot failed: {}",
+                String::from_utf8_lossy(result)
+            )
+            .into())
+        };
+
+        capture.finish(result);
+    }
+
+    fn on_dev_tools_agent_detached(&mut self, _browser: Browser) {
+        self.0
+            .lock()
+            .finish(Err(anyhow!("The DevTools agent detached.").into()));
+    }
+}
+
+/// Decodes the image data of a Page.captureScreenshot result.
+#[cfg(feature = "devtools")]
+fn decode_screenshot(result: &[u8]) -> Result<Vec<u8>> {
+    let result: serde_json::Value = serde_json::from_slice(result).map_err(anyhow::Error::from)?;
+    let data = result
+        .get("data")
+        .and_then(|data| data.as_str())
+        .ok_or_else(|| anyhow!("The screenshot result has no data."))?;
+    let data = CefString::new(data);
+
+    match unsafe { BinaryValue::from_ptr(cef_base64decode(data.as_ptr())) } {
+        Some(binary) => binary_to_vec(&binary),
+        None => Err(anyhow!("Failed to decode the screenshot data.").into())
+    }
+}
+
+/// Copies the contents of |binary|.
+#[cfg(any(feature = "osr", feature = "devtools"))]
+fn binary_to_vec(binary: &BinaryValue) -> Result<Vec<u8>> {
+    let mut data = vec![0; binary.get_size()?];
+    let size = binary.get_data(&mut data, 0)?;
+
+    data.truncate(size);
+
+    Ok(data)
+}
@@ -1,13 +1,35 @@
-use crate::{free_cef_string, types::LogItems, CefString, Color, LogSeverity};
-use anyhow::{anyhow, Result};
+use crate::{free_cef_string, types::LogItems, CefString, Color, LogSeverity, Result};
+use anyhow::anyhow;
 use cef_ui_sys::{cef_settings_t, cef_string_t};
 use dunce::canonicalize;
 use std::{
     ffi::c_int,
     mem::{size_of, zeroed},
-    path::PathBuf
+    path::{Path, PathBuf}
 };
 
+/// Selects how CEF bootstraps the browser. Alloy is the CEF-specific runtime
+/// that supports windowed browsers, windowless (off-screen) rendering and the
+/// Views framework. Chrome is the Chrome runtime, which supports Chrome UI such
+/// as the toolbar (see ChromeToolbarType), the status and zoom bubbles, Chrome
+/// policies and extensions, but not windowless rendering.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RuntimeStyle {
+    #[default]
+    Alloy,
+    Chrome
+}
+
+impl RuntimeStyle {
+    /// Returns true if browsers can be created with windowless rendering.
+    pub fn supports_windowless_rendering(&self) -> bool {
+        match self {
+            RuntimeStyle::Alloy => true,
+            RuntimeStyle::Chrome => false
+        }
+    }
+}
+
 /// Initialization settings. Specify NULL or 0 to get the recommended default
 /// values. Many of these and other settings can also configured using command-
 /// line switches.
@@ -79,15 +101,40 @@ impl Settings {
         self
     }
 
+    /// Selects the runtime used by all browsers. This is a typed alternative to
+    /// chrome_runtime.
+    pub fn runtime_style(self, style: RuntimeStyle) -> Self {
+        self.chrome_runtime(style == RuntimeStyle::Chrome)
+    }
+
+    /// Returns the runtime used by all browsers.
+    pub fn get_runtime_style(&self) -> RuntimeStyle {
+        match self.0.chrome_runtime != 0 {
+            true => RuntimeStyle::Chrome,
+            false => RuntimeStyle::Alloy
+        }
+    }
+
     /// Set to true (1) to have the browser process message loop run in a separate
     /// thread. If false (0) then the CefDoMessageLoopWork() function must be
     /// called from your application message loop. This option is only supported
     /// on Windows and Linux.
+    ///
+    /// When enabled, CEF runs its own UI thread and every callback documented
+    /// as running on the browser process UI thread will arrive on that thread
+    /// rather than on the application main thread. The application is then free
+    /// to run its own UI message loop and must not call run_message_loop or
+    /// do_message_loop_work on the Context.
     pub fn multi_threaded_message_loop(mut self, value: bool) -> Self {
         self.0.multi_threaded_message_loop = value as c_int;
         self
     }
 
+    /// Returns true if the browser process message loop runs in a separate thread.
+    pub fn is_multi_threaded_message_loop(&self) -> bool {
+        self.0.multi_threaded_message_loop != 0
+    }
+
     /// Set to true (1) to control browser process main (UI) thread message pump
     /// scheduling via the CefBrowserProcessHandler::OnScheduleMessagePumpWork()
     /// callback. This option is recommended for use in combination with the
@@ -102,6 +149,11 @@ impl Settings {
         self
     }
 
+    /// Returns true if message pump scheduling is controlled by the application.
+    pub fn is_external_message_pump(&self) -> bool {
+        self.0.external_message_pump != 0
+    }
+
     /// Set to true (1) to enable windowless (off-screen) rendering support. Do
     /// not enable this value if the application does not use windowless rendering
     /// as it may reduce rendering performance on some systems.
@@ -227,12 +279,22 @@ impl Settings {
     /// MacOS a "~/Library/Logs/[app name]_debug.log" file will be written where
     /// [app name] is the name of the main app executable. Also configurable using
     /// the "log-file" command-line switch.
-    pub fn log_file(mut self, value: &PathBuf) -> Result<Self> {
-        Self::set_path(value, &mut self.0.log_file)?;
+    pub fn log_file(mut self, value: &Path) -> Result<Self> {
+        Self::set_file_path(value, &mut self.0.log_file)?;
 
         Ok(self)
     }
 
+    /// Returns the debug log path, or None if the default location is used.
+    pub fn get_log_file(&self) -> Option<PathBuf> {
+        let log_file = Self::get_string(&self.0.log_file);
+
+        match log_file.is_empty() {
+            true => None,
+            false => Some(PathBuf::from(log_file))
+        }
+    }
+
     /// The log severity. Only messages of this severity level or higher will be
     /// logged. When set to DISABLE no messages will be written to the log file,
     /// but FATAL messages will still be output to stderr. Also configurable using
@@ -243,6 +305,11 @@ impl Settings {
         self
     }
 
+    /// Returns the log severity.
+    pub fn get_log_severity(&self) -> LogSeverity {
+        self.0.log_severity.into()
+    }
+
     /// The log items prepended to each log line. If not set the default log items
     /// will be used. Also configurable using the "log-items" command-line switch
     /// with a value of "none" for no log items, or a comma-delimited list of
@@ -252,6 +319,11 @@ impl Settings {
         self
     }
 
+    /// Returns the log items prepended to each log line.
+    pub fn get_log_items(&self) -> LogItems {
+        self.0.log_items.into()
+    }
+
     /// Custom flags that will be used when initializing the V8 JavaScript engine.
     /// The consequences of using custom flags may not be well tested. Also
     /// configurable using the "js-flags" command-line switch.
@@ -300,9 +372,18 @@ impl Settings {
     /// chrome://inspect page in Google Chrome. Port numbers 9222 and 9229 are
     /// discoverable by default. Other port numbers may need to be configured via
     /// "Discover network targets" on the Devices tab.
-    pub fn remote_debugging_port(mut self, value: u16) -> Self {
+    pub fn remote_debugging_port(mut self, value: u16) -> Result<Self> {
+        if value < 1024 {
+            return Err(anyhow!(
+                "Remote debugging port must be between 1024 and 65535, got {}.",
+                value
+            )
+            .into());
+        }
+
         self.0.remote_debugging_port = value as c_int;
-        self
+
+        Ok(self)
     }
 
     /// The number of stack trace frames to capture for uncaught exceptions.
@@ -324,9 +405,18 @@ impl Settings {
     /// default value of opaque white be used. If the alpha component is fully
     /// transparent for a windowless (off-screen) browser then transparent
     /// painting will be enabled.
-    pub fn background_color(mut self, value: Color) -> Self {
+    pub fn background_color(mut self, value: Color) -> Result<Self> {
+        if value.a != 0x00 && value.a != 0xFF {
+            return Err(anyhow!(
+                "Background color alpha must be fully opaque or fully transparent, got {:?}.",
+                value
+            )
+            .into());
+        }
+
         self.0.background_color = value.into();
-        self
+
+        Ok(self)
     }
 
     /// Comma delimited ordered list of language codes without any whitespace that
@@ -355,6 +445,9 @@ impl Settings {
         self
     }
 
+    /// If |cookieable_schemes_exclude_defaults| is true (1) the default schemes
+    /// ("http", "https", "ws" and "wss") will not be supported by the associated
+    /// CefCookieManager. See |cookieable_schemes_list| for details.
     pub fn cookieable_schemes_exclude_defaults(mut self, value: bool) -> Self {
         self.0
             .cookieable_schemes_exclude_defaults = value as c_int;
@@ -388,11 +481,91 @@ impl Settings {
         self
     }
 
+    /// Checks the settings for combinations that CEF would silently ignore or
+    /// that would cause startup failures. This is called automatically by
+    /// Context::initialize.
+    pub fn validate(&self) -> Result<()> {
+        let cache_path = Self::get_string(&self.0.cache_path);
+        let root_cache_path = Self::get_string(&self.0.root_cache_path);
+
+        // The cache path must be equal to or a child of the root cache path.
+        if !cache_path.is_empty()
+            && !root_cache_path.is_empty()
+            && !Path::new(&cache_path).starts_with(&root_cache_path)
+        {
+            return Err(anyhow!(
+                "Cache path {} must be equal to or a child of root cache path {}.",
+                cache_path,
+                root_cache_path
+            )
+            .into());
+        }
+
+        // Persisting cookies and preferences requires an on-disk cache.
+        if cache_path.is_empty() {
+            if self.0.persist_session_cookies != 0 {
+                return Err(anyhow!("Persisting session cookies requires a cache path.").into());
+            }
+
+            if self.0.persist_user_preferences != 0 {
+                return Err(anyhow!("Persisting user preferences requires a cache path.").into());
+            }
+        }
+
+        // The multi-threaded message loop is only supported on Windows and Linux
+        // and cannot be combined with an externally pumped message loop.
+        if self.is_multi_threaded_message_loop() {
+            if cfg!(target_os = "macos") {
+                return Err(
+                    anyhow!("The multi-threaded message loop is not supported on macOS.").into()
+                );
+            }
+
+            if self.is_external_message_pump() {
+                return Err(anyhow!(
+                    "The multi-threaded message loop cannot be used with an external message pump."
+                )
+                .into());
+            }
+        }
+
+        let runtime_style = self.get_runtime_style();
+
+        // The Chrome policy id is only supported with the Chrome runtime.
+        if runtime_style == RuntimeStyle::Alloy
+            && !Self::get_string(&self.0.chrome_policy_id).is_empty()
+        {
+            return Err(
+                anyhow!("Chrome policy id is only supported with the Chrome runtime.").into()
+            );
+        }
+
+        // The Chrome runtime cannot create windowless browsers.
+        if !runtime_style.supports_windowless_rendering()
+            && self.0.windowless_rendering_enabled != 0
+        {
+            return Err(anyhow!(
+                "Windowless rendering is not supported with the {:?} runtime.",
+                runtime_style
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
     /// Converts to the raw cef type.
     pub fn as_raw(&self) -> &cef_settings_t {
         &self.0
     }
 
+    /// Reads a cef_string_t back into a String.
+    fn get_string(cef: &cef_string_t) -> String {
+        CefString::from_ptr(cef)
+            .map(String::from)
+            .unwrap_or_default()
+    }
+
     /// Tries to assign a PathBuf to a cef_string_t.
     fn set_path(path: &PathBuf, cef: &mut cef_string_t) -> Result<()> {
         let path = canonicalize(path)?;
@@ -405,12 +578,39 @@ impl Settings {
         Ok(())
     }
 
+    /// Tries to assign a PathBuf naming a file that may not exist yet to a
+    /// cef_string_t. Only the parent directory must exist.
+    fn set_file_path(path: &Path, cef: &mut cef_string_t) -> Result<()> {
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| anyhow!("Path {} does not name a file.", path.display()))?;
+        let parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new(".")
+        };
+
+        let path = canonicalize(parent)?.join(file_name);
+        let path = path
+            .to_str()
+            .ok_or_else(|| anyhow!("Failed to convert path to utf8."))?;
+
+        *cef = CefString::new(path).into_raw();
+
+        Ok(())
+    }
+
     /// Tries to assign a String to a cef_string_t.
     fn set_string(s: &String, cef: &mut cef_string_t) {
         *cef = CefString::new(s.as_str()).into_raw();
     }
 }
 
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Drop for Settings {
     fn drop(&mut self) {
         free_cef_string(&mut self.0.browser_subprocess_path);
@@ -1,5 +1,4 @@
-use crate::{ref_counted_ptr, try_c};
-use anyhow::Result;
+use crate::{ref_counted_ptr, try_c, Result};
 use cef_ui_sys::cef_shared_memory_region_t;
 use std::ffi::c_void;
 
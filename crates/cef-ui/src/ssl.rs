@@ -1,5 +1,4 @@
-use crate::{ref_counted_ptr, try_c, X509Certificate};
-use anyhow::Result;
+use crate::{ref_counted_ptr, try_c, Result, X509Certificate};
 use bitflags::bitflags;
 use cef_ui_sys::{
     cef_cert_status_t, cef_cert_status_t_CERT_STATUS_AUTHORITY_INVALID,
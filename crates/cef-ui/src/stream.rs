@@ -0,0 +1,453 @@
+use crate::{
+    ref_counted_ptr, try_c, unwind::catch_unwind_or, CefString, RefCountedPtr, Result, Wrappable,
+    Wrapped
+};
+use cef_ui_sys::{
+    cef_read_handler_t, cef_stream_reader_create_for_data, cef_stream_reader_create_for_file,
+    cef_stream_reader_create_for_handler, cef_stream_reader_t, cef_stream_writer_create_for_file,
+    cef_stream_writer_create_for_handler, cef_stream_writer_t, cef_write_handler_t
+};
+use parking_lot::Mutex;
+use std::{
+    ffi::{c_int, c_void},
+    io::{ErrorKind, Read, Seek, SeekFrom, Write},
+    mem::zeroed,
+    path::Path,
+    slice::{from_raw_parts, from_raw_parts_mut}
+};
+
+/// The C stdio whence values used by CEF stream seek functions.
+const SEEK_SET: c_int = 0;
+const SEEK_CUR: c_int = 1;
+const SEEK_END: c_int = 2;
+
+/// Converts a CEF offset and whence value to a SeekFrom.
+fn to_seek_from(offset: i64, whence: c_int) -> Option<SeekFrom> {
+    match whence {
+        SEEK_SET => u64::try_from(offset)
+            .ok()
+            .map(SeekFrom::Start),
+        SEEK_CUR => Some(SeekFrom::Current(offset)),
+        SEEK_END => Some(SeekFrom::End(offset)),
+        _ => None
+    }
+}
+
+/// Converts a SeekFrom to a CEF offset and whence value.
+fn from_seek_from(pos: SeekFrom) -> (i64, c_int) {
+    match pos {
+        SeekFrom::Start(offset) => (offset as i64, SEEK_SET),
+        SeekFrom::Current(offset) => (offset, SEEK_CUR),
+        SeekFrom::End(offset) => (offset, SEEK_END)
+    }
+}
+
+/// Any type that can back a ReadHandler.
+pub trait ReadSeek: Read + Seek + Send + 'static {}
+
+impl<T: Read + Seek + Send + 'static> ReadSeek for T {}
+
+/// Any type that can back a WriteHandler.
+pub trait WriteSeek: Write + Seek + Send + 'static {}
+
+impl<T: Write + Seek + Send + 'static> WriteSeek for T {}
+
+// Structure the client can implement to provide a custom stream reader. The
+// functions of this structure may be called on any thread.
+ref_counted_ptr!(ReadHandler, cef_read_handler_t);
+
+impl ReadHandler {
+    /// Create a read handler backed by any Read + Seek type. Set |may_block| to
+    /// true if reading may block, e.g. when accessing the file system.
+    pub fn new<R: ReadSeek>(reader: R, may_block: bool) -> Self {
+        Self(ReadHandlerWrapper::new(reader, may_block).wrap())
+    }
+}
+
+/// The state of a read handler.
+struct ReadHandlerState {
+    reader: Box<dyn ReadSeek>,
+    eof:    bool
+}
+
+/// Translates CEF -> Rust callbacks.
+struct ReadHandlerWrapper {
+    state:     Mutex<ReadHandlerState>,
+    may_block: bool
+}
+
+impl ReadHandlerWrapper {
+    pub fn new<R: ReadSeek>(reader: R, may_block: bool) -> Self {
+        Self {
+            state: Mutex::new(ReadHandlerState {
+                reader: Box::new(reader),
+                eof:    false
+            }),
+            may_block
+        }
+    }
+
+    /// Read raw binary data.
+    unsafe extern "C" fn c_read(
+        this: *mut cef_read_handler_t,
+        ptr: *mut c_void,
+        size: usize,
+        n: usize
+    ) -> usize {
+        catch_unwind_or("ReadHandlerWrapper::c_read", 0, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let mut state = this.state.lock();
+            let len = size.saturating_mul(n);
+
+            if ptr.is_null() || len == 0 {
+                return 0;
+            }
+
+            let buffer = from_raw_parts_mut(ptr as *mut u8, len);
+            let mut read = 0;
+
+            while read < len {
+                match state
+                    .reader
+                    .read(&mut buffer[read..])
+                {
+                    Ok(0) => {
+                        state.eof = true;
+                        break;
+                    },
+                    Ok(count) => read += count,
+                    Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                    Err(_) => break
+                }
+            }
+
+            read / size
+        })
+    }
+
+    /// Seek to the specified offset position. |whence| may be any one of
+    /// SEEK_CUR, SEEK_END or SEEK_SET. Return zero on success and non-zero on
+    /// failure.
+    unsafe extern "C" fn c_seek(
+        this: *mut cef_read_handler_t,
+        offset: i64,
+        whence: c_int
+    ) -> c_int {
+        catch_unwind_or("ReadHandlerWrapper::c_seek", -1, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let mut state = this.state.lock();
+
+            match to_seek_from(offset, whence).map(|pos| state.reader.seek(pos)) {
+                Some(Ok(_)) => {
+                    state.eof = false;
+
+                    0
+                },
+                _ => 1
+            }
+        })
+    }
+
+    /// Return the current offset position.
+    unsafe extern "C" fn c_tell(this: *mut cef_read_handler_t) -> i64 {
+        catch_unwind_or("ReadHandlerWrapper::c_tell", -1, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let mut state = this.state.lock();
+
+            state
+                .reader
+                .stream_position()
+                .map(|pos| pos as i64)
+                .unwrap_or(-1)
+        })
+    }
+
+    /// Return non-zero if at end of file.
+    unsafe extern "C" fn c_eof(this: *mut cef_read_handler_t) -> c_int {
+        catch_unwind_or("ReadHandlerWrapper::c_eof", 0, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+
+            this.state.lock().eof as c_int
+        })
+    }
+
+    /// Return true (1) if this handler performs work like accessing the file
+    /// system which may block. Used as a hint for determining the thread to
+    /// access the handler from.
+    unsafe extern "C" fn c_may_block(this: *mut cef_read_handler_t) -> c_int {
+        catch_unwind_or("ReadHandlerWrapper::c_may_block", 0, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+
+            this.may_block as c_int
+        })
+    }
+}
+
+impl Wrappable for ReadHandlerWrapper {
+    type Cef = cef_read_handler_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<cef_read_handler_t> {
+        RefCountedPtr::wrap(
+            cef_read_handler_t {
+                base: unsafe { zeroed() },
+
+                read:      Some(Self::c_read),
+                seek:      Some(Self::c_seek),
+                tell:      Some(Self::c_tell),
+                eof:       Some(Self::c_eof),
+                may_block: Some(Self::c_may_block)
+            },
+            self
+        )
+    }
+}
+
+// Structure the client can implement to provide a custom stream writer. The
+// functions of this structure may be called on any thread.
+ref_counted_ptr!(WriteHandler, cef_write_handler_t);
+
+impl WriteHandler {
+    /// Create a write handler backed by any Write + Seek type. Set |may_block|
+    /// to true if writing may block, e.g. when accessing the file system.
+    pub fn new<W: WriteSeek>(writer: W, may_block: bool) -> Self {
+        Self(WriteHandlerWrapper::new(writer, may_block).wrap())
+    }
+}
+
+/// Translates CEF -> Rust callbacks.
+struct WriteHandlerWrapper {
+    writer:    Mutex<Box<dyn WriteSeek>>,
+    may_block: bool
+}
+
+impl WriteHandlerWrapper {
+    pub fn new<W: WriteSeek>(writer: W, may_block: bool) -> Self {
+        Self {
+            writer: Mutex::new(Box::new(writer)),
+            may_block
+        }
+    }
+
+    /// Write raw binary data.
+    unsafe extern "C" fn c_write(
+        this: *mut cef_write_handler_t,
+        ptr: *const c_void,
+        size: usize,
+        n: usize
+    ) -> usize {
+        catch_unwind_or("WriteHandlerWrapper::c_write", 0, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let len = size.saturating_mul(n);
+
+            if ptr.is_null() || len == 0 {
+                return 0;
+            }
+
+            let buffer = from_raw_parts(ptr as *const u8, len);
+
+            match this.writer.lock().write_all(buffer) {
+                Ok(_) => n,
+                Err(_) => 0
+            }
+        })
+    }
+
+    /// Seek to the specified offset position. |whence| may be any one of
+    /// SEEK_CUR, SEEK_END or SEEK_SET. Return zero on success and non-zero on
+    /// failure.
+    unsafe extern "C" fn c_seek(
+        this: *mut cef_write_handler_t,
+        offset: i64,
+        whence: c_int
+    ) -> c_int {
+        catch_unwind_or("WriteHandlerWrapper::c_seek", -1, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let mut writer = this.writer.lock();
+
+            match to_seek_from(offset, whence).map(|pos| writer.seek(pos)) {
+                Some(Ok(_)) => 0,
+                _ => 1
+            }
+        })
+    }
+
+    /// Return the current offset position.
+    unsafe extern "C" fn c_tell(this: *mut cef_write_handler_t) -> i64 {
+        catch_unwind_or("WriteHandlerWrapper::c_tell", -1, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+
+            this.writer
+                .lock()
+                .stream_position()
+                .map(|pos| pos as i64)
+                .unwrap_or(-1)
+        })
+    }
+
+    /// Flush the stream.
+    unsafe extern "C" fn c_flush(this: *mut cef_write_handler_t) -> c_int {
+        catch_unwind_or("WriteHandlerWrapper::c_flush", -1, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+
+            match this.writer.lock().flush() {
+                Ok(_) => 0,
+                Err(_) => 1
+            }
+        })
+    }
+
+    /// Return true (1) if this handler performs work like accessing the file
+    /// system which may block. Used as a hint for determining the thread to
+    /// access the handler from.
+    unsafe extern "C" fn c_may_block(this: *mut cef_write_handler_t) -> c_int {
+        catch_unwind_or("WriteHandlerWrapper::c_may_block", 0, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+
+            this.may_block as c_int
+        })
+    }
+}
+
+impl Wrappable for WriteHandlerWrapper {
+    type Cef = cef_write_handler_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<cef_write_handler_t> {
+        RefCountedPtr::wrap(
+            cef_write_handler_t {
+                base: unsafe { zeroed() },
+
+                write:     Some(Self::c_write),
+                seek:      Some(Self::c_seek),
+                tell:      Some(Self::c_tell),
+                flush:     Some(Self::c_flush),
+                may_block: Some(Self::c_may_block)
+            },
+            self
+        )
+    }
+}
+
+// Structure used to read data from a stream. The functions of this structure
+// may be called on any thread.
+ref_counted_ptr!(StreamReader, cef_stream_reader_t);
+
+impl StreamReader {
+    /// Create a new cef_stream_reader_t object from a file.
+    pub fn create_for_file(file_name: &Path) -> Option<Self> {
+        let file_name = CefString::new(&file_name.to_string_lossy());
+
+        unsafe { Self::from_ptr(cef_stream_reader_create_for_file(file_name.as_ptr())) }
+    }
+
+    /// Create a new cef_stream_reader_t object from data. The data is copied.
+    pub fn create_for_data(data: &[u8]) -> Option<Self> {
+        unsafe {
+            Self::from_ptr(cef_stream_reader_create_for_data(
+                data.as_ptr() as *mut c_void,
+                data.len()
+            ))
+        }
+    }
+
+    /// Create a new cef_stream_reader_t object from a custom handler.
+    pub fn create_for_handler(handler: ReadHandler) -> Option<Self> {
+        unsafe { Self::from_ptr(cef_stream_reader_create_for_handler(handler.into_raw())) }
+    }
+
+    /// Read raw binary data into |buffer|, returning the number of bytes read.
+    pub fn read(&self, buffer: &mut [u8]) -> Result<usize> {
+        try_c!(self, read, {
+            Ok(read(
+                self.as_ptr(),
+                buffer.as_mut_ptr() as *mut c_void,
+                1,
+                buffer.len()
+            ))
+        })
+    }
+
+    /// Seek to the specified position. Returns true on success.
+    pub fn seek(&self, pos: SeekFrom) -> Result<bool> {
+        try_c!(self, seek, {
+            let (offset, whence) = from_seek_from(pos);
+
+            Ok(seek(self.as_ptr(), offset, whence) == 0)
+        })
+    }
+
+    /// Return the current offset position.
+    pub fn tell(&self) -> Result<i64> {
+        try_c!(self, tell, { Ok(tell(self.as_ptr())) })
+    }
+
+    /// Return true if at end of file.
+    pub fn eof(&self) -> Result<bool> {
+        try_c!(self, eof, { Ok(eof(self.as_ptr()) != 0) })
+    }
+
+    /// Returns true (1) if this reader performs work like accessing the file
+    /// system which may block. Used as a hint for determining the thread to
+    /// access the reader from.
+    pub fn may_block(&self) -> Result<bool> {
+        try_c!(self, may_block, { Ok(may_block(self.as_ptr()) != 0) })
+    }
+}
+
+// Structure used to write data to a stream. The functions of this structure
+// may be called on any thread.
+ref_counted_ptr!(StreamWriter, cef_stream_writer_t);
+
+impl StreamWriter {
+    /// Create a new cef_stream_writer_t object for a file.
+    pub fn create_for_file(file_name: &Path) -> Option<Self> {
+        let file_name = CefString::new(&file_name.to_string_lossy());
+
+        unsafe { Self::from_ptr(cef_stream_writer_create_for_file(file_name.as_ptr())) }
+    }
+
+    /// Create a new cef_stream_writer_t object for a custom handler.
+    pub fn create_for_handler(handler: WriteHandler) -> Option<Self> {
+        unsafe { Self::from_ptr(cef_stream_writer_create_for_handler(handler.into_raw())) }
+    }
+
+    /// Write raw binary data from |buffer|, returning the number of bytes
+    /// written.
+    pub fn write(&self, buffer: &[u8]) -> Result<usize> {
+        try_c!(self, write, {
+            Ok(write(
+                self.as_ptr(),
+                buffer.as_ptr() as *const c_void,
+                1,
+                buffer.len()
+            ))
+        })
+    }
+
+    /// Seek to the specified position. Returns true on success.
+    pub fn seek(&self, pos: SeekFrom) -> Result<bool> {
+        try_c!(self, seek, {
+            let (offset, whence) = from_seek_from(pos);
+
+            Ok(seek(self.as_ptr(), offset, whence) == 0)
+        })
+    }
+
+    /// Return the current offset position.
+    pub fn tell(&self) -> Result<i64> {
+        try_c!(self, tell, { Ok(tell(self.as_ptr())) })
+    }
+
+    /// Flush the stream. Returns true on success.
+    pub fn flush(&self) -> Result<bool> {
+        try_c!(self, flush, { Ok(flush(self.as_ptr()) == 0) })
+    }
+
+    /// Returns true (1) if this writer performs work like accessing the file
+    /// system which may block. Used as a hint for determining the thread to
+    /// access the writer from.
+    pub fn may_block(&self) -> Result<bool> {
+        try_c!(self, may_block, { Ok(may_block(self.as_ptr()) != 0) })
+    }
+}
@@ -0,0 +1,386 @@
+use crate::{
+    post_task, Callback, ErrorCode, Request, ResourceHandler, ResourceHandlerCallbacks,
+    ResourceReadBuffer, ResourceReadCallback, Response, Result, ThreadId
+};
+use bytes::{Buf, Bytes};
+use futures_core::Stream;
+use parking_lot::Mutex;
+use std::{
+    io,
+    pin::Pin,
+    sync::{Arc, Weak},
+    task::{Context, Poll, Wake, Waker}
+};
+#[cfg(feature = "tokio")]
+use tokio::io::{AsyncRead, ReadBuf};
+use tracing::error;
+
+/// The size of the chunks read from an AsyncRead.
+#[cfg(feature = "tokio")]
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+type BodyStream = Pin<Box<dyn Stream<Item = io::Result<Bytes>> + Send + 'static>>;
+
+/// Serves a response whose body is produced by a Stream, without buffering it.
+/// Each chunk is copied into CEF's read buffer as soon as the stream yields
+/// it, so large or slowly produced responses (e.g. generated on demand or
+/// proxied from another source) start loading immediately.
+///
+/// The stream is polled from CEF's read calls. When it is not ready the read
+/// completes asynchronously once the stream wakes its waker, so the stream may
+/// be driven by any runtime, e.g. a tokio channel fed from a spawned task.
+///
+/// Return the handler built by build from
+/// ResourceRequestHandlerCallbacks::get_resource_handler or a scheme handler
+/// factory.
+pub struct StreamResourceHandler {
+    response: StreamResponse,
+    stream:   BodyStream
+}
+
+impl StreamResourceHandler {
+    /// Serves the chunks yielded by |stream| with a 200 status.
+    pub fn new(stream: impl Stream<Item = Bytes> + Send + 'static) -> Self {
+        Self::try_new(InfallibleStream(Box::pin(stream)))
+    }
+
+    /// Serves the chunks yielded by |stream| with a 200 status. If the stream
+    /// yields an error the response fails with ERR_FAILED, so the page sees a
+    /// network error rather than a truncated body.
+    pub fn try_new(stream: impl Stream<Item = io::Result<Bytes>> + Send + 'static) -> Self {
+        Self {
+            response: StreamResponse {
+                status:         200,
+                status_text:    None,
+                mime_type:      None,
+                charset:        None,
+                headers:        Vec::new(),
+                content_length: None
+            },
+            stream:   Box::pin(stream)
+        }
+    }
+
+    /// Serves |data| with a 200 status and its length as the content length.
+    pub fn from_bytes(data: impl Into<Bytes>) -> Self {
+        let data = data.into();
+        let length = data.len() as u64;
+
+        Self::new(OnceStream(Some(data))).content_length(length)
+    }
+
+    /// Serves the bytes read from |reader|. The response fails if reading
+    /// fails.
+    #[cfg(feature = "tokio")]
+    pub fn from_async_read(reader: impl AsyncRead + Send + 'static) -> Self {
+        Self::try_new(AsyncReadStream {
+            reader: Box::pin(reader),
+            buffer: vec![0; READ_CHUNK_SIZE]
+        })
+    }
+
+    /// Sets the HTTP status code.
+    pub fn status(mut self, status: i32) -> Self {
+        self.response.status = status;
+        self
+    }
+
+    /// Sets the HTTP status text.
+    pub fn status_text(mut self, status_text: &str) -> Self {
+        self.response.status_text = Some(status_text.to_string());
+        self
+    }
+
+    /// Sets the MIME type.
+    pub fn mime_type(mut self, mime_type: &str) -> Self {
+        self.response.mime_type = Some(mime_type.to_string());
+        self
+    }
+
+    /// Sets the charset.
+    pub fn charset(mut self, charset: &str) -> Self {
+        self.response.charset = Some(charset.to_string());
+        self
+    }
+
+    /// Adds a response header.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.response
+            .headers
+            .push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sets the length of the body, if known. Without it CEF reads until the
+    /// stream ends.
+    pub fn content_length(mut self, content_length: u64) -> Self {
+        self.response.content_length = Some(content_length);
+        self
+    }
+
+    /// Converts this to a ResourceHandler.
+    pub fn build(self) -> ResourceHandler {
+        ResourceHandler::new(StreamBody {
+            response: self.response,
+            state:    Arc::new(Mutex::new(StreamState {
+                stream:  Some(self.stream),
+                chunk:   Bytes::new(),
+                pending: None
+            }))
+        })
+    }
+}
+
+/// The response headers of a StreamResourceHandler.
+struct StreamResponse {
+    status:         i32,
+    status_text:    Option<String>,
+    mime_type:      Option<String>,
+    charset:        Option<String>,
+    headers:        Vec<(String, String)>,
+    content_length: Option<u64>
+}
+
+impl StreamResponse {
+    /// Sets the response headers.
+    fn apply(&self, response: &Response) -> Result<()> {
+        response.set_status(self.status)?;
+
+        if let Some(status_text) = &self.status_text {
+            response.set_status_text(status_text)?;
+        }
+
+        if let Some(mime_type) = &self.mime_type {
+            response.set_mime_type(mime_type)?;
+        }
+
+        if let Some(charset) = &self.charset {
+            response.set_charset(charset)?;
+        }
+
+        for (name, value) in &self.headers {
+            response.set_header_by_name(name, value, true)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Implements the resource handler protocol for a StreamResourceHandler.
+struct StreamBody {
+    response: StreamResponse,
+    state:    Arc<Mutex<StreamState>>
+}
+
+impl ResourceHandlerCallbacks for StreamBody {
+    fn open(&mut self, _request: Request, handle_request: &mut bool, _callback: Callback) -> bool {
+        *handle_request = true;
+
+        true
+    }
+
+    fn get_response_headers(
+        &mut self,
+        response: Response,
+        response_length: &mut i64,
+        _redirect_url: &mut Option<String>
+    ) {
+        if let Err(e) = self.response.apply(&response) {
+            error!("Failed to set stream response headers: {}", e);
+        }
+
+        *response_length = self
+            .response
+            .content_length
+            .map(|length| length as i64)
+            .unwrap_or(-1);
+    }
+
+    fn read(
+        &mut self,
+        mut data_out: ResourceReadBuffer,
+        bytes_read: &mut i32,
+        callback: ResourceReadCallback
+    ) -> bool {
+        let waker = StreamWaker::waker(&self.state);
+        let mut state = self.state.lock();
+
+        *bytes_read = 0;
+
+        match state.poll_into(&mut data_out, &waker) {
+            Poll::Ready(count) => {
+                *bytes_read = count;
+
+                count > 0
+            },
+            Poll::Pending => {
+                state.pending = Some((data_out, callback));
+
+                true
+            }
+        }
+    }
+
+    fn cancel(&mut self) {
+        let mut state = self.state.lock();
+
+        state.stream = None;
+        state.pending = None;
+    }
+}
+
+/// The state shared between the handler and the stream's waker.
+struct StreamState {
+    stream:  Option<BodyStream>,
+    chunk:   Bytes,
+    pending: Option<(ResourceReadBuffer, ResourceReadCallback)>
+}
+
+impl StreamState {
+    /// Copies as much data as is ready into |buffer|. Returns Ready(0) once the
+    /// stream has ended and a negative error code if it failed.
+    fn poll_into(&mut self, buffer: &mut ResourceReadBuffer, waker: &Waker) -> Poll<i32> {
+        let mut context = Context::from_waker(waker);
+
+        while self.chunk.is_empty() {
+            let Some(stream) = self.stream.as_mut() else {
+                return Poll::Ready(0);
+            };
+
+            match stream
+                .as_mut()
+                .poll_next(&mut context)
+            {
+                Poll::Ready(Some(Ok(chunk))) => self.chunk = chunk,
+                Poll::Ready(Some(Err(e))) => {
+                    error!("Failed to read stream response body: {}", e);
+
+                    self.stream = None;
+
+                    return Poll::Ready(ErrorCode::Failed.code());
+                },
+                Poll::Ready(None) => {
+                    self.stream = None;
+
+                    return Poll::Ready(0);
+                },
+                Poll::Pending => return Poll::Pending
+            }
+        }
+
+        let count = buffer.write(&self.chunk);
+
+        self.chunk.advance(count);
+
+        Poll::Ready(count as i32)
+    }
+}
+
+/// Resumes a pending read when the stream becomes ready.
+struct StreamWaker(Weak<Mutex<StreamState>>);
+
+impl StreamWaker {
+    fn waker(state: &Arc<Mutex<StreamState>>) -> Waker {
+        Waker::from(Arc::new(Self(Arc::downgrade(state))))
+    }
+
+    /// Completes the pending read, if the stream has data for it.
+    fn resume(state: Arc<Mutex<StreamState>>) {
+        let waker = Self::waker(&state);
+        let mut locked = state.lock();
+
+        let Some((mut buffer, callback)) = locked.pending.take() else {
+            return;
+        };
+
+        match locked.poll_into(&mut buffer, &waker) {
+            Poll::Ready(count) => {
+                drop(locked);
+
+                if let Err(e) = callback.cont(count) {
+                    error!("Failed to continue stream read: {}", e);
+                }
+            },
+            Poll::Pending => locked.pending = Some((buffer, callback))
+        }
+    }
+}
+
+impl Wake for StreamWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        // The stream may wake from inside poll_next, while the state is locked,
+        // so the read is resumed from a task instead.
+        let state = self.0.clone();
+
+        post_task(ThreadId::Io, move || {
+            if let Some(state) = state.upgrade() {
+                StreamWaker::resume(state);
+            }
+        });
+    }
+}
+
+/// A stream that yields a single chunk.
+struct OnceStream(Option<Bytes>);
+
+impl Stream for OnceStream {
+    type Item = Bytes;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Bytes>> {
+        Poll::Ready(self.0.take())
+    }
+}
+
+/// Adapts a stream of chunks to a stream of results that never fails.
+struct InfallibleStream<S: Stream<Item = Bytes>>(Pin<Box<S>>);
+
+impl<S: Stream<Item = Bytes>> Stream for InfallibleStream<S> {
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>
+    ) -> Poll<Option<io::Result<Bytes>>> {
+        self.0
+            .as_mut()
+            .poll_next(cx)
+            .map(|chunk| chunk.map(Ok))
+    }
+}
+
+/// Adapts an AsyncRead to a Stream of chunks.
+#[cfg(feature = "tokio")]
+struct AsyncReadStream<R: AsyncRead> {
+    reader: Pin<Box<R>>,
+    buffer: Vec<u8>
+}
+
+#[cfg(feature = "tokio")]
+impl<R: AsyncRead> Stream for AsyncReadStream<R> {
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>
+    ) -> Poll<Option<io::Result<Bytes>>> {
+        let this = &mut *self;
+        let mut buffer = ReadBuf::new(&mut this.buffer);
+
+        match this
+            .reader
+            .as_mut()
+            .poll_read(cx, &mut buffer)
+        {
+            Poll::Ready(Ok(())) => match buffer.filled() {
+                [] => Poll::Ready(None),
+                filled => Poll::Ready(Some(Ok(Bytes::copy_from_slice(filled))))
+            },
+            Poll::Ready(Err(e)) => Poll::Ready(Some(Err(e))),
+            Poll::Pending => Poll::Pending
+        }
+    }
+}
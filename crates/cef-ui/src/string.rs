@@ -1,4 +1,4 @@
-use crate::{ref_counted_ptr, RefCountedPtr, Wrappable, Wrapped};
+use crate::{ref_counted_ptr, unwind::catch_unwind_or, RefCountedPtr, Wrappable, Wrapped};
 use cef_ui_sys::{
     cef_string_list_alloc, cef_string_list_append, cef_string_list_clear, cef_string_list_copy,
     cef_string_list_free, cef_string_list_size, cef_string_list_t, cef_string_list_value,
@@ -15,7 +15,7 @@ use parking_lot::Mutex;
 use std::{
     collections::HashMap,
     ffi::c_char,
-    fmt::Debug,
+    fmt::{Debug, Display, Write},
     mem::{forget, zeroed},
     slice::from_raw_parts
 };
@@ -171,6 +171,163 @@ impl<'a> From<&'a CefString> for String {
     }
 }
 
+impl CefString {
+    /// Borrows the string without converting it.
+    pub fn as_cef_str(&self) -> CefStr<'_> {
+        CefStr::from(self)
+    }
+}
+
+/// A borrowed cef_string_t. Unlike CefString it never owns or converts the
+/// underlying UTF-16 buffer, so incoming strings can be inspected and compared
+/// without allocating. Convert to a String only when the value must be kept.
+#[derive(Clone, Copy, Default)]
+pub struct CefStr<'a>(&'a [u16]);
+
+impl<'a> CefStr<'a> {
+    /// Borrows |ptr|, or returns None if it is null.
+    pub fn from_ptr(ptr: *const cef_string_t) -> Option<Self> {
+        CefString::from_ptr(ptr).map(Self::from)
+    }
+
+    /// Borrows |ptr| without checking if the pointer is null.
+    pub fn from_ptr_unchecked(ptr: *const cef_string_t) -> Self {
+        Self::from(CefString::from_ptr_unchecked(ptr))
+    }
+
+    /// Returns the UTF-16 code units.
+    pub fn as_utf16(&self) -> &'a [u16] {
+        self.0
+    }
+
+    /// Returns the length in UTF-16 code units.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if the string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the characters, replacing invalid UTF-16 with
+    /// U+FFFD.
+    pub fn chars(&self) -> impl Iterator<Item = char> + 'a {
+        char::decode_utf16(self.0.iter().copied()).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    /// Returns true if the string equals |s|, without allocating.
+    pub fn eq_str(&self, s: &str) -> bool {
+        self.0
+            .iter()
+            .copied()
+            .eq(s.encode_utf16())
+    }
+
+    /// Returns true if the string equals |s| ignoring ASCII case, without
+    /// allocating. Useful for header names.
+    pub fn eq_ignore_ascii_case(&self, s: &str) -> bool {
+        let lower = |c: u16| match c {
+            0x41..=0x5a => c + 0x20,
+            _ => c
+        };
+
+        self.0
+            .iter()
+            .copied()
+            .map(lower)
+            .eq(s.encode_utf16().map(lower))
+    }
+
+    /// Returns true if the string starts with |prefix|, without allocating.
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        let mut units = self.0.iter().copied();
+
+        prefix
+            .encode_utf16()
+            .all(|c| units.next() == Some(c))
+    }
+
+    /// Converts the string to an owned String.
+    pub fn to_string_lossy(&self) -> String {
+        String::from_utf16_lossy(self.0)
+    }
+
+    /// Returns a cef_string_t that borrows the string. It has no destructor, so
+    /// CEF copies it when it needs to keep the value, and it must not outlive
+    /// the borrowed buffer.
+    #[cfg(feature = "v8")]
+    pub(crate) fn as_raw(&self) -> cef_string_t {
+        cef_string_t {
+            str_:   self.0.as_ptr() as *mut u16,
+            length: self.0.len(),
+            dtor:   None
+        }
+    }
+}
+
+impl<'a> From<&'a [u16]> for CefStr<'a> {
+    fn from(value: &'a [u16]) -> Self {
+        Self(value)
+    }
+}
+
+impl<'a> From<&'a CefString> for CefStr<'a> {
+    fn from(value: &'a CefString) -> Self {
+        match value.is_empty() {
+            true => Self::default(),
+            false => Self(unsafe { from_raw_parts(value.0.str_, value.0.length) })
+        }
+    }
+}
+
+impl From<CefStr<'_>> for String {
+    fn from(value: CefStr<'_>) -> Self {
+        value.to_string_lossy()
+    }
+}
+
+impl PartialEq<str> for CefStr<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.eq_str(other)
+    }
+}
+
+impl PartialEq<&str> for CefStr<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.eq_str(other)
+    }
+}
+
+impl PartialEq for CefStr<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for CefStr<'_> {}
+
+impl Display for CefStr<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.chars()
+            .try_for_each(|c| f.write_char(c))
+    }
+}
+
+impl Debug for CefStr<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_char('"')?;
+
+        for c in self.chars() {
+            for c in c.escape_debug() {
+                f.write_char(c)?;
+            }
+        }
+
+        f.write_char('"')
+    }
+}
+
 /// Try and free a cef_string_t. Don't call this unless you
 /// are sure that you know what you are doing or you could
 /// potentially cause a double free.
@@ -224,6 +381,11 @@ impl CefStringList {
         unsafe { cef_string_list_size(self.0) }
     }
 
+    /// Returns true if the string list contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Retrieve the value at the specified zero-based string list index. Returns
     /// true (1) if the value was successfully retrieved.
     pub fn get(&self, index: usize) -> Option<CefString> {
@@ -262,6 +424,50 @@ impl Drop for CefStringList {
     }
 }
 
+impl Default for CefStringList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> IntoIterator for &'a CefStringList {
+    type Item = CefString;
+    type IntoIter = CefStringListIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for CefStringList {
+    type Item = CefString;
+    type IntoIter = CefStringListIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        CefStringListIntoIter {
+            list:  self,
+            index: 0
+        }
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for CefStringList {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut result = CefStringList::new();
+
+        result.extend(iter);
+        result
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for CefStringList {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for s in iter {
+            self.push(&CefString::new(s.as_ref()));
+        }
+    }
+}
+
 /// An iterator for CefStringList.
 pub struct CefStringListIter<'a> {
     list:  &'a CefStringList,
@@ -286,6 +492,24 @@ impl<'a> Iterator for CefStringListIter<'a> {
     }
 }
 
+/// An owning iterator for CefStringList.
+pub struct CefStringListIntoIter {
+    list:  CefStringList,
+    index: usize
+}
+
+impl Iterator for CefStringListIntoIter {
+    type Item = CefString;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.list.get(self.index);
+
+        self.index += 1;
+
+        value
+    }
+}
+
 impl From<CefStringList> for Vec<String> {
     fn from(value: CefStringList) -> Self {
         Vec::<String>::from(&value)
@@ -359,6 +583,11 @@ impl CefStringMap {
         unsafe { cef_string_map_size(self.0) }
     }
 
+    /// Returns true if the string map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Return the value assigned to the specified key.
     pub fn get(&self, key: &CefString) -> Option<CefString> {
         let mut cef = CefString::default();
@@ -412,6 +641,50 @@ impl Drop for CefStringMap {
     }
 }
 
+impl Default for CefStringMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> IntoIterator for &'a CefStringMap {
+    type Item = (CefString, CefString);
+    type IntoIter = CefStringMapIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for CefStringMap {
+    type Item = (CefString, CefString);
+    type IntoIter = CefStringMapIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        CefStringMapIntoIter {
+            map:   self,
+            index: 0
+        }
+    }
+}
+
+impl<K: AsRef<str>, V: AsRef<str>> FromIterator<(K, V)> for CefStringMap {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut result = CefStringMap::new();
+
+        result.extend(iter);
+        result
+    }
+}
+
+impl<K: AsRef<str>, V: AsRef<str>> Extend<(K, V)> for CefStringMap {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.push(&CefString::new(k.as_ref()), &CefString::new(v.as_ref()));
+        }
+    }
+}
+
 /// An iterator for CefStringMap.
 pub struct CefStringMapIter<'a> {
     map:   &'a CefStringMap,
@@ -440,6 +713,28 @@ impl<'a> Iterator for CefStringMapIter<'a> {
     }
 }
 
+/// An owning iterator for CefStringMap.
+pub struct CefStringMapIntoIter {
+    map:   CefStringMap,
+    index: usize
+}
+
+impl Iterator for CefStringMapIntoIter {
+    type Item = (CefString, CefString);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.map.key(self.index);
+        let value = self.map.value(self.index);
+
+        self.index += 1;
+
+        match (key, value) {
+            (Some(key), Some(value)) => Some((key, value)),
+            _ => None
+        }
+    }
+}
+
 impl From<CefStringMap> for HashMap<String, String> {
     fn from(value: CefStringMap) -> Self {
         HashMap::<String, String>::from(&value)
@@ -514,6 +809,11 @@ impl CefStringMultiMap {
         unsafe { cef_string_multimap_size(self.0) }
     }
 
+    /// Returns true if the string multimap contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Return the number of values with the specified key.
     pub fn value_count(&self, key: &CefString) -> usize {
         unsafe { cef_string_multimap_find_count(self.0, key.as_ptr()) }
@@ -561,7 +861,28 @@ impl CefStringMultiMap {
         unsafe { cef_string_multimap_clear(self.0) }
     }
 
-    /// Returns an iterator for the string multimap.
+    /// Calls |f| with each key/value pair in order, borrowing the strings
+    /// instead of converting them. Keys with several values are visited once
+    /// per value.
+    pub fn for_each(&self, mut f: impl FnMut(CefStr<'_>, CefStr<'_>)) {
+        let mut key = CefString::default();
+        let mut value = CefString::default();
+
+        for index in 0..self.len() {
+            unsafe {
+                if cef_string_multimap_key(self.0, index, key.as_mut_ptr()) == 0
+                    || cef_string_multimap_value(self.0, index, value.as_mut_ptr()) == 0
+                {
+                    break;
+                }
+            }
+
+            f(key.as_cef_str(), value.as_cef_str());
+        }
+    }
+
+    /// Returns an iterator over the key/value pairs of the string multimap, in
+    /// order. Keys with several values are returned once per value.
     pub fn iter(&self) -> CefStringMultiMapIter {
         CefStringMultiMapIter::new(self)
     }
@@ -573,6 +894,50 @@ impl Drop for CefStringMultiMap {
     }
 }
 
+impl Default for CefStringMultiMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> IntoIterator for &'a CefStringMultiMap {
+    type Item = (CefString, CefString);
+    type IntoIter = CefStringMultiMapIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for CefStringMultiMap {
+    type Item = (CefString, CefString);
+    type IntoIter = CefStringMultiMapIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        CefStringMultiMapIntoIter {
+            map:   self,
+            index: 0
+        }
+    }
+}
+
+impl<K: AsRef<str>, V: AsRef<str>> FromIterator<(K, V)> for CefStringMultiMap {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut result = CefStringMultiMap::new();
+
+        result.extend(iter);
+        result
+    }
+}
+
+impl<K: AsRef<str>, V: AsRef<str>> Extend<(K, V)> for CefStringMultiMap {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.push(&CefString::new(k.as_ref()), &CefString::new(v.as_ref()));
+        }
+    }
+}
+
 /// An iterator for CefStringMultiMap.
 pub struct CefStringMultiMapIter<'a> {
     map:   &'a CefStringMultiMap,
@@ -586,21 +951,40 @@ impl<'a> CefStringMultiMapIter<'a> {
 }
 
 impl<'a> Iterator for CefStringMultiMapIter<'a> {
-    type Item = (CefString, Vec<CefString>);
+    type Item = (CefString, CefString);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let Some(key) = self.map.key(self.index) else {
-            return None;
-        };
+        let key = self.map.key(self.index);
+        let value = self.map.value(self.index);
 
-        let value_count = self.map.value_count(&key);
-        let values = (0..value_count)
-            .filter_map(|i| self.map.value_at_index(&key, i))
-            .collect::<Vec<CefString>>();
+        self.index += 1;
+
+        match (key, value) {
+            (Some(key), Some(value)) => Some((key, value)),
+            _ => None
+        }
+    }
+}
+
+/// An owning iterator for CefStringMultiMap.
+pub struct CefStringMultiMapIntoIter {
+    map:   CefStringMultiMap,
+    index: usize
+}
+
+impl Iterator for CefStringMultiMapIntoIter {
+    type Item = (CefString, CefString);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.map.key(self.index);
+        let value = self.map.value(self.index);
 
         self.index += 1;
 
-        Some((key, values))
+        match (key, value) {
+            (Some(key), Some(value)) => Some((key, value)),
+            _ => None
+        }
     }
 }
 
@@ -612,17 +996,16 @@ impl From<CefStringMultiMap> for HashMap<String, Vec<String>> {
 
 impl From<&CefStringMultiMap> for HashMap<String, Vec<String>> {
     fn from(value: &CefStringMultiMap) -> Self {
-        value
-            .iter()
-            .map(|(k, v)| {
-                (
-                    k.into(),
-                    v.into_iter()
-                        .map(|s| s.into())
-                        .collect()
-                )
-            })
-            .collect()
+        let mut result = HashMap::<String, Vec<String>>::new();
+
+        for (k, v) in value.iter() {
+            result
+                .entry(k.into())
+                .or_default()
+                .push(v.into());
+        }
+
+        result
     }
 }
 
@@ -662,23 +1045,25 @@ impl StringVisitor {
 }
 
 /// Translates CEF -> Rust callbacks.
-struct StringVisitorWrapper(Mutex<Box<dyn StringVisitorCallbacks>>);
+struct StringVisitorWrapper<C: StringVisitorCallbacks>(Mutex<C>);
 
-impl StringVisitorWrapper {
-    pub fn new<C: StringVisitorCallbacks>(callbacks: C) -> Self {
-        Self(Mutex::new(Box::new(callbacks)))
+impl<C: StringVisitorCallbacks> StringVisitorWrapper<C> {
+    pub fn new(callbacks: C) -> Self {
+        Self(Mutex::new(callbacks))
     }
 
     /// Method that will be executed.
     unsafe extern "C" fn c_visit(this: *mut cef_string_visitor_t, s: *const cef_string_t) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let s: String = CefString::from_ptr_unchecked(s).into();
+        catch_unwind_or("StringVisitorWrapper::c_visit", (), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let s: String = CefString::from_ptr_unchecked(s).into();
 
-        this.0.lock().visit(s.as_str());
+            this.0.lock().visit(s.as_str());
+        })
     }
 }
 
-impl Wrappable for StringVisitorWrapper {
+impl<C: StringVisitorCallbacks> Wrappable for StringVisitorWrapper<C> {
     type Cef = cef_string_visitor_t;
 
     /// Converts this to a smart pointer.
@@ -686,7 +1071,7 @@ impl Wrappable for StringVisitorWrapper {
         RefCountedPtr::wrap(
             cef_string_visitor_t {
                 base:  unsafe { zeroed() },
-                visit: Some(StringVisitorWrapper::c_visit)
+                visit: Some(Self::c_visit)
             },
             self
         )
@@ -0,0 +1,166 @@
+use crate::{App, Context, MainArgs, Result, Settings};
+use anyhow::anyhow;
+use std::{env::args_os, ffi::OsStr};
+
+/// The command-line switch that Chromium uses to select a secondary process.
+const TYPE_SWITCH: &str = "--type=";
+
+/// The kind of secondary process, as given by the "--type=" command-line switch.
+/// The browser process has no such switch.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SubprocessKind {
+    /// A render process, which hosts Blink and V8.
+    Renderer,
+
+    /// The GPU process.
+    Gpu,
+
+    /// A utility process, e.g. the network or audio service.
+    Utility,
+
+    /// The Linux zygote, which forks render processes.
+    Zygote,
+
+    /// The Crashpad handler used for crash reporting.
+    CrashpadHandler,
+
+    /// Any other kind of process.
+    Other(String)
+}
+
+impl SubprocessKind {
+    /// Returns the kind of the current process, or None for the browser process.
+    pub fn current() -> Option<Self> {
+        Self::from_args(args_os())
+    }
+
+    /// Returns the kind of process selected by |args|, or None if there is no
+    /// "--type=" switch. Arguments that are not valid UTF-8 are converted
+    /// lossily rather than rejected.
+    pub fn from_args<I, S>(args: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>
+    {
+        args.into_iter().find_map(|arg| {
+            arg.as_ref()
+                .to_string_lossy()
+                .strip_prefix(TYPE_SWITCH)
+                .map(Self::from_type)
+        })
+    }
+
+    /// Returns the kind with the "--type=" switch value |value|.
+    pub fn from_type(value: &str) -> Self {
+        match value {
+            "renderer" => Self::Renderer,
+            "gpu-process" => Self::Gpu,
+            "utility" => Self::Utility,
+            "zygote" => Self::Zygote,
+            "crashpad-handler" => Self::CrashpadHandler,
+            other => Self::Other(other.to_string())
+        }
+    }
+
+    /// Returns the "--type=" switch value.
+    pub fn as_type(&self) -> &str {
+        match self {
+            Self::Renderer => "renderer",
+            Self::Gpu => "gpu-process",
+            Self::Utility => "utility",
+            Self::Zygote => "zygote",
+            Self::CrashpadHandler => "crashpad-handler",
+            Self::Other(other) => other
+        }
+    }
+}
+
+/// Returns true if the current process is a CEF secondary process rather than
+/// the browser process. Use this in single-binary applications to branch
+/// between the browser and helper roles at the top of main.
+pub fn is_subprocess() -> bool {
+    SubprocessKind::current().is_some()
+}
+
+/// Runs the current secondary process and returns its exit code once it should
+/// exit. |app| should provide the same render process handler as the browser
+/// process, if any. This fails if called in the browser process. The sandbox
+/// uses the default settings; use Context::is_cef_subprocess to customize them.
+pub fn run_subprocess(main_args: MainArgs, app: Option<App>) -> Result<i32> {
+    if !is_subprocess() {
+        return Err(anyhow!("run_subprocess must not be called in the browser process.").into());
+    }
+
+    Context::new(main_args, Settings::new(), app)
+        .is_cef_subprocess()
+        .ok_or_else(|| anyhow!("CEF did not recognize this process as a subprocess.").into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_args_browser_process() {
+        assert_eq!(SubprocessKind::from_args(["app"]), None);
+        assert_eq!(SubprocessKind::from_args(["app", "--type"]), None);
+        assert_eq!(SubprocessKind::from_args(["app", "--kind=renderer"]), None);
+        assert_eq!(SubprocessKind::from_args(Vec::<String>::new()), None);
+    }
+
+    #[test]
+    fn from_args_subprocess() {
+        assert_eq!(
+            SubprocessKind::from_args(["app", "--type=renderer", "--lang=en-US"]),
+            Some(SubprocessKind::Renderer)
+        );
+        assert_eq!(
+            SubprocessKind::from_args(["app", "--type=gpu-process"]),
+            Some(SubprocessKind::Gpu)
+        );
+        assert_eq!(
+            SubprocessKind::from_args(["app", "--type=utility"]),
+            Some(SubprocessKind::Utility)
+        );
+        assert_eq!(
+            SubprocessKind::from_args(["app", "--type=zygote"]),
+            Some(SubprocessKind::Zygote)
+        );
+        assert_eq!(
+            SubprocessKind::from_args(["app", "--type=crashpad-handler"]),
+            Some(SubprocessKind::CrashpadHandler)
+        );
+        assert_eq!(
+            SubprocessKind::from_args(["app", "--type=ppapi"]),
+            Some(SubprocessKind::Other("ppapi".to_string()))
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn from_args_non_utf8() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let invalid = OsStr::from_bytes(b"--user-data-dir=/tmp/\xff");
+
+        assert_eq!(SubprocessKind::from_args([invalid]), None);
+        assert_eq!(
+            SubprocessKind::from_args([invalid, OsStr::new("--type=renderer")]),
+            Some(SubprocessKind::Renderer)
+        );
+    }
+
+    #[test]
+    fn type_round_trip() {
+        for kind in [
+            SubprocessKind::Renderer,
+            SubprocessKind::Gpu,
+            SubprocessKind::Utility,
+            SubprocessKind::Zygote,
+            SubprocessKind::CrashpadHandler,
+            SubprocessKind::Other("ppapi".to_string())
+        ] {
+            assert_eq!(SubprocessKind::from_type(kind.as_type()), kind);
+        }
+    }
+}
@@ -1,10 +1,62 @@
 use cef_ui_sys::{cef_basetime_t, cef_time_from_basetime, cef_time_t};
-use chrono::{DateTime, NaiveDateTime, Utc};
-use std::mem::zeroed;
+use chrono::{DateTime, Datelike, NaiveDateTime, Timelike, Utc};
+use std::{mem::zeroed, time::SystemTime};
+
+/// The number of microseconds between the Windows epoch (1601-01-01 00:00:00
+/// UTC), which cef_basetime_t is relative to, and the Unix epoch.
+const WINDOWS_TO_UNIX_EPOCH_MICROS: i64 = 11_644_473_600_000_000;
 
 /// A wrapper for DateTime<Utc> for interacting with CEF.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct CefTime(DateTime<Utc>);
 
+impl CefTime {
+    /// Returns the current time.
+    pub fn now() -> Self {
+        Self(Utc::now())
+    }
+
+    /// Converts from a cef_basetime_t without calling into CEF. Returns None
+    /// for a null (zero) time or a time that is out of range.
+    pub fn from_basetime(value: cef_basetime_t) -> Option<Self> {
+        match value.val {
+            0 => None,
+            val => {
+                DateTime::<Utc>::from_timestamp_micros(val - WINDOWS_TO_UNIX_EPOCH_MICROS).map(Self)
+            },
+        }
+    }
+
+    /// Converts to a cef_basetime_t without calling into CEF.
+    pub fn to_basetime(&self) -> cef_basetime_t {
+        cef_basetime_t {
+            val: self.0.timestamp_micros() + WINDOWS_TO_UNIX_EPOCH_MICROS
+        }
+    }
+
+    /// Converts to a cef_time_t.
+    pub fn to_time(&self) -> cef_time_t {
+        cef_time_t {
+            year:         self.0.year(),
+            month:        self.0.month() as i32,
+            day_of_week:  self
+                .0
+                .weekday()
+                .num_days_from_sunday() as i32,
+            day_of_month: self.0.day() as i32,
+            hour:         self.0.hour() as i32,
+            minute:       self.0.minute() as i32,
+            second:       self.0.second() as i32,
+            millisecond:  (self.0.timestamp_subsec_millis() % 1000) as i32
+        }
+    }
+
+    /// Converts to a SystemTime.
+    pub fn to_system_time(&self) -> SystemTime {
+        self.0.into()
+    }
+}
+
 impl From<CefTime> for DateTime<Utc> {
     fn from(value: CefTime) -> Self {
         value.0
@@ -17,17 +69,80 @@ impl From<&CefTime> for DateTime<Utc> {
     }
 }
 
+impl From<DateTime<Utc>> for CefTime {
+    fn from(value: DateTime<Utc>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&DateTime<Utc>> for CefTime {
+    fn from(value: &DateTime<Utc>) -> Self {
+        Self(*value)
+    }
+}
+
+impl From<CefTime> for SystemTime {
+    fn from(value: CefTime) -> Self {
+        value.to_system_time()
+    }
+}
+
+impl From<&CefTime> for SystemTime {
+    fn from(value: &CefTime) -> Self {
+        value.to_system_time()
+    }
+}
+
+impl From<SystemTime> for CefTime {
+    fn from(value: SystemTime) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<&SystemTime> for CefTime {
+    fn from(value: &SystemTime) -> Self {
+        Self((*value).into())
+    }
+}
+
+impl From<CefTime> for cef_basetime_t {
+    fn from(value: CefTime) -> Self {
+        value.to_basetime()
+    }
+}
+
+impl From<&CefTime> for cef_basetime_t {
+    fn from(value: &CefTime) -> Self {
+        value.to_basetime()
+    }
+}
+
+impl From<CefTime> for cef_time_t {
+    fn from(value: CefTime) -> Self {
+        value.to_time()
+    }
+}
+
+impl From<&CefTime> for cef_time_t {
+    fn from(value: &CefTime) -> Self {
+        value.to_time()
+    }
+}
+
 impl TryFrom<cef_basetime_t> for CefTime {
     type Error = &'static str;
 
     fn try_from(value: cef_basetime_t) -> Result<Self, Self::Error> {
+        if value.val == 0 {
+            return Err("Null time!");
+        }
+
         let mut time: cef_time_t = unsafe { zeroed() };
 
-        unsafe {
-            cef_time_from_basetime(value, &mut time);
+        match unsafe { cef_time_from_basetime(value, &mut time) } {
+            0 => Err("Invalid time!"),
+            _ => CefTime::try_from(time)
         }
-
-        CefTime::try_from(time)
     }
 }
 
@@ -0,0 +1,200 @@
+#[cfg(feature = "v8")]
+use crate::{Browser, FunctionScope};
+use crate::{
+    post_task, CompletionCallback, EndTracingCallback, ErrorCode, RequestContext, ResolveCallback,
+    Result, TaskRunner, ThreadId
+};
+use anyhow::anyhow;
+#[cfg(feature = "v8")]
+use serde_json::Value;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll}
+};
+#[cfg(feature = "v8")]
+use tokio::runtime::Handle;
+use tokio::sync::oneshot::{channel, Receiver, Sender};
+
+/// A future that resolves with a value produced on a CEF thread. It fails if
+/// the producing task or callback is destroyed without running, which happens
+/// when CEF shuts down or rejects the request.
+#[derive(Debug)]
+pub struct CefFuture<T>(Receiver<T>);
+
+impl<T: Send + 'static> CefFuture<T> {
+    /// Creates a future together with the function that completes it. The
+    /// function may be called on any thread.
+    pub fn new() -> (impl FnOnce(T) + Send + 'static, Self) {
+        let (tx, rx): (Sender<T>, Receiver<T>) = channel();
+
+        (
+            move |value| {
+                // The future may already have been dropped.
+                let _ = tx.send(value);
+            },
+            Self(rx)
+        )
+    }
+}
+
+impl<T> Future for CefFuture<T> {
+    type Output = Result<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0)
+            .poll(cx)
+            .map(|value| {
+                value.map_err(|_| anyhow!("The CEF callback was dropped without running.").into())
+            })
+    }
+}
+
+/// Extends TaskRunner with functions that return futures.
+pub trait CefTaskRunnerExt {
+    /// Runs |f| on the thread associated with this task runner and resolves with
+    /// its return value.
+    fn run<R, F>(&self, f: F) -> Result<CefFuture<R>>
+    where
+        R: Send + 'static,
+        F: FnOnce() -> R + Send + 'static;
+}
+
+impl CefTaskRunnerExt for TaskRunner {
+    fn run<R, F>(&self, f: F) -> Result<CefFuture<R>>
+    where
+        R: Send + 'static,
+        F: FnOnce() -> R + Send + 'static
+    {
+        let (complete, future) = CefFuture::new();
+
+        match self.post_task(move || complete(f()))? {
+            true => Ok(future),
+            false => Err(anyhow!("Failed to post task.").into())
+        }
+    }
+}
+
+/// Runs |f| on the CEF thread |thread_id| and resolves with its return value.
+pub fn run_on<R, F>(thread_id: ThreadId, f: F) -> Result<CefFuture<R>>
+where
+    R: Send + 'static,
+    F: FnOnce() -> R + Send + 'static
+{
+    let (complete, future) = CefFuture::new();
+
+    match post_task(thread_id, move || complete(f())) {
+        true => Ok(future),
+        false => Err(anyhow!("Failed to post task to the {:?} thread.", thread_id).into())
+    }
+}
+
+impl CompletionCallback {
+    /// Creates a callback together with a future that resolves when it runs.
+    pub fn future() -> (Self, CefFuture<()>) {
+        let (complete, future) = CefFuture::new();
+
+        (Self::new(move || complete(())), future)
+    }
+}
+
+impl ResolveCallback {
+    /// Creates a callback together with a future that resolves with the result
+    /// code and the resolved IP addresses.
+    pub fn future() -> (Self, CefFuture<(ErrorCode, Vec<String>)>) {
+        let (complete, future) = CefFuture::new();
+
+        (
+            Self::new(move |result, resolved_ips| complete((result, resolved_ips))),
+            future
+        )
+    }
+}
+
+impl EndTracingCallback {
+    /// Creates a callback together with a future that resolves with the path at
+    /// which tracing data was written.
+    pub fn future() -> (Self, CefFuture<String>) {
+        let (complete, future) = CefFuture::new();
+
+        (Self::new(complete), future)
+    }
+}
+
+impl RequestContext {
+    /// Clears all certificate exceptions that were added as part of handling
+    /// cef_request_handler_t::on_certificate_error(), resolving once complete.
+    pub async fn clear_certificate_exceptions_async(&self) -> Result<()> {
+        let (callback, future) = CompletionCallback::future();
+
+        self.clear_certificate_exceptions(Some(callback))?;
+
+        future.await
+    }
+
+    /// Clears all HTTP authentication credentials that were added as part of
+    /// handling GetAuthCredentials, resolving once complete.
+    pub async fn clear_http_auth_credentials_async(&self) -> Result<()> {
+        let (callback, future) = CompletionCallback::future();
+
+        self.clear_http_auth_credentials(Some(callback))?;
+
+        future.await
+    }
+
+    /// Clears all active and idle connections that Chromium currently has, and
+    /// resolves once complete.
+    pub async fn close_all_connections_async(&self) -> Result<()> {
+        let (callback, future) = CompletionCallback::future();
+
+        self.close_all_connections(Some(callback))?;
+
+        future.await
+    }
+
+    /// Forgets HTTP authentication credentials and certificate exceptions and
+    /// closes all connections, resolving once complete. See
+    /// RequestContext::reset_network_state.
+    pub async fn reset_network_state_async(&self) -> Result<()> {
+        let (complete, future) = CefFuture::new();
+
+        self.reset_network_state(move || complete(()))?;
+
+        future.await
+    }
+
+    /// Attempts to resolve |origin| to a list of associated IP addresses,
+    /// resolving with the result code and the addresses.
+    pub async fn resolve_host_async(&self, origin: &str) -> Result<(ErrorCode, Vec<String>)> {
+        let (callback, future) = ResolveCallback::future();
+
+        self.resolve_host(origin, callback)?;
+
+        future.await
+    }
+}
+
+#[cfg(feature = "v8")]
+impl Browser {
+    /// Exposes the async function |f| to JavaScript as the global function
+    /// |name| in the frames selected by |scope|. Each call runs |f| on
+    /// |runtime| and the Promise returned to JavaScript settles with its
+    /// result. See Browser::bind_function.
+    pub fn bind_async_function<F, Fut>(
+        &self,
+        runtime: Handle,
+        name: &str,
+        scope: FunctionScope,
+        f: F
+    ) -> Result<()>
+    where
+        F: Fn(Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = anyhow::Result<Value>> + Send + 'static
+    {
+        self.bind_function_with_reply(name, scope, move |arguments, reply| {
+            let future = f(arguments);
+
+            runtime.spawn(async move { reply.send(future.await) });
+        })
+    }
+}
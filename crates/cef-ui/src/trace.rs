@@ -0,0 +1,130 @@
+use crate::{
+    ref_counted_ptr, unwind::catch_unwind_or, CefString, CompletionCallback, RefCountedPtr,
+    Wrappable, Wrapped
+};
+use cef_ui_sys::{
+    cef_begin_tracing, cef_end_tracing, cef_end_tracing_callback_t, cef_now_from_system_trace_time,
+    cef_string_t
+};
+use parking_lot::Mutex;
+use std::{
+    mem::zeroed,
+    ptr::{null, null_mut}
+};
+
+// Implement this structure to receive notification when tracing has completed.
+// The functions of this structure will be called on the browser process UI
+// thread.
+ref_counted_ptr!(EndTracingCallback, cef_end_tracing_callback_t);
+
+impl EndTracingCallback {
+    pub fn new(f: impl FnOnce(String) + Send + 'static) -> Self {
+        Self(EndTracingCallbackWrapper::new(f).wrap())
+    }
+}
+
+/// Translates CEF -> Rust callbacks.
+struct EndTracingCallbackWrapper(Mutex<Option<Box<dyn FnOnce(String) + Send + 'static>>>);
+
+impl EndTracingCallbackWrapper {
+    pub fn new(f: impl FnOnce(String) + Send + 'static) -> Self {
+        Self(Mutex::new(Some(Box::new(f))))
+    }
+
+    /// Called after all processes have sent their trace data. |tracing_file| is
+    /// the path at which tracing data was written. The client is responsible for
+    /// deleting |tracing_file|.
+    unsafe extern "C" fn c_on_end_tracing_complete(
+        this: *mut cef_end_tracing_callback_t,
+        tracing_file: *const cef_string_t
+    ) {
+        catch_unwind_or(
+            "EndTracingCallbackWrapper::c_on_end_tracing_complete",
+            (),
+            || {
+                let this: &Self = Wrapped::wrappable(this);
+                let tracing_file: String = CefString::from_ptr_unchecked(tracing_file).into();
+
+                if let Some(f) = this.0.lock().take() {
+                    f(tracing_file);
+                }
+            }
+        )
+    }
+}
+
+impl Wrappable for EndTracingCallbackWrapper {
+    type Cef = cef_end_tracing_callback_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<Self::Cef> {
+        RefCountedPtr::wrap(
+            cef_end_tracing_callback_t {
+                base:                    unsafe { zeroed() },
+                on_end_tracing_complete: Some(Self::c_on_end_tracing_complete)
+            },
+            self
+        )
+    }
+}
+
+/// Start tracing events on all processes. Tracing is initialized asynchronously
+/// and |callback| will be executed on the UI thread after initialization is
+/// complete.
+///
+/// If CefBeginTracing was called previously, or if a CefEndTracingAsync call is
+/// pending, CefBeginTracing will fail and return false (0).
+///
+/// |categories| is a comma-delimited list of category wildcards. A category can
+/// have an optional '-' prefix to make it an excluded category. Having both
+/// included and excluded categories in the same list is not supported.
+///
+/// Examples:
+/// - "test_MyTest*"
+/// - "test_MyTest*,test_OtherStuff"
+/// - "-excluded_category1,-excluded_category2"
+///
+/// This function must be called on the browser process UI thread.
+pub fn begin_tracing(categories: &str, callback: Option<CompletionCallback>) -> bool {
+    unsafe {
+        let categories = CefString::new(categories);
+        let callback = callback
+            .map(|callback| callback.into_raw())
+            .unwrap_or_else(null_mut);
+
+        cef_begin_tracing(categories.as_ptr(), callback) != 0
+    }
+}
+
+/// Stop tracing events on all processes.
+///
+/// This function will fail and return false (0) if a previous call to
+/// CefEndTracingAsync is already pending or if CefBeginTracing was not called.
+///
+/// |tracing_file| is the path at which tracing data will be written and
+/// |callback| is the callback that will be executed once all processes have
+/// sent their trace data. If |tracing_file| is None a new temporary file path
+/// will be used. If |callback| is None no trace data will be written.
+///
+/// This function must be called on the browser process UI thread.
+pub fn end_tracing(tracing_file: Option<&str>, callback: Option<EndTracingCallback>) -> bool {
+    unsafe {
+        let tracing_file = tracing_file.map(CefString::new);
+        let tracing_file = tracing_file
+            .as_ref()
+            .map(|tracing_file| tracing_file.as_ptr())
+            .unwrap_or_else(null);
+        let callback = callback
+            .map(|callback| callback.into_raw())
+            .unwrap_or_else(null_mut);
+
+        cef_end_tracing(tracing_file, callback) != 0
+    }
+}
+
+/// Returns the current system trace time or, if none is defined, the current
+/// high-res time. Can be used by clients to synchronize with the time
+/// information in trace events.
+pub fn now_from_system_trace_time() -> i64 {
+    unsafe { cef_now_from_system_trace_time() }
+}
@@ -1,3 +1,5 @@
+use crate::{Error, Result};
+use anyhow::anyhow;
 use bitflags::bitflags;
 use cef_ui_sys::{
     cef_errorcode_t, cef_horizontal_alignment_t, cef_insets_t, cef_log_items_t, cef_log_severity_t,
@@ -9,9 +11,13 @@ use cef_ui_sys::{
     cef_touch_handle_state_flags_t_CEF_THS_FLAG_NONE,
     cef_touch_handle_state_flags_t_CEF_THS_FLAG_ORIENTATION,
     cef_touch_handle_state_flags_t_CEF_THS_FLAG_ORIGIN, cef_touch_handle_state_t,
-    cef_window_open_disposition_t, cef_zoom_command_t
+    cef_transition_type_t, cef_window_open_disposition_t, cef_zoom_command_t
+};
+use std::{
+    ffi::c_int,
+    fmt::{Display, Formatter},
+    str::FromStr
 };
-use std::ffi::c_int;
 
 /// These are #define's and cannot be generated by bindgen.
 /// They are lifted from include/chrome_command_ids.h.
@@ -1937,6 +1943,252 @@ impl From<&ErrorCode> for cef_errorcode_t {
     }
 }
 
+impl ErrorCode {
+    /// Returns the numeric net error code. Errors are negative and None is 0.
+    pub fn code(&self) -> i32 {
+        cef_errorcode_t::from(self) as i32
+    }
+
+    /// Returns true if the load was aborted rather than failed, e.g. because the
+    /// user navigated away, stop_load was called or a download was started
+    /// instead. Aborted loads are usually not reported to the user.
+    pub fn is_aborted(&self) -> bool {
+        *self == ErrorCode::Aborted
+    }
+
+    /// Returns true if this is a certificate error, in the range -200 to -299.
+    pub fn is_certificate_error(&self) -> bool {
+        (-299..=-200).contains(&self.code())
+    }
+
+    /// Returns the Chromium name of the error, e.g. "net::ERR_ABORTED".
+    pub fn description(&self) -> String {
+        match self {
+            ErrorCode::None => String::from("net::OK"),
+            _ => format!("net::{:?}", cef_errorcode_t::from(self))
+        }
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.description(), self.code())
+    }
+}
+
+/// Transition source. What the user did to move to the page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransitionSource {
+    /// Source is a link click or the JavaScript window.open function. This is
+    /// also the default value for requests like sub-resource loads that are not
+    /// navigations.
+    Link,
+
+    /// Source is some other "explicit" navigation. This is the default value for
+    /// navigations where the actual type is unknown. See also
+    /// TransitionQualifiers::DirectLoad.
+    Explicit,
+
+    /// User got to this page through a suggestion in the UI (for example, via
+    /// the destinations page). Chrome runtime only.
+    AutoBookmark,
+
+    /// Source is a subframe navigation. This is any content that is
+    /// automatically loaded in a non-toplevel frame. For example, if a page
+    /// consists of several frames containing ads, those ad URLs will have this
+    /// transition type. The user may not even realize the content in these
+    /// pages is a separate frame, so may not care about the URL.
+    AutoSubframe,
+
+    /// Source is a subframe navigation explicitly requested by the user that
+    /// will generate new navigation entries in the back/forward list. These are
+    /// probably more important than frames that were automatically loaded in
+    /// the background because the user probably cares about the fact that this
+    /// link was loaded.
+    ManualSubframe,
+
+    /// User got to this page by typing in the URL bar and selecting an entry
+    /// that did not look like a URL. For example, a match might have the URL
+    /// of a Google search result page, but appear like "Search Google for ...".
+    /// These are not quite the same as EXPLICIT navigations because the user
+    /// didn't type or see the destination URL. Chrome runtime only.
+    Generated,
+
+    /// This is a toplevel navigation. This is any content that is automatically
+    /// loaded in a toplevel frame. For example, opening a tab to show the ASH
+    /// screen saver, opening the devtools window, opening the NTP after the safe
+    /// browsing warning, opening web-based dialog boxes are examples of
+    /// AUTO_TOPLEVEL navigations. Chrome runtime only.
+    AutoToplevel,
+
+    /// Source is a form submission by the user. NOTE: In some situations
+    /// submitting a form does not result in this transition type. This can
+    /// happen if the form uses a script to submit the contents.
+    FormSubmit,
+
+    /// Source is a "reload" of the page via the Reload function or by re-visiting
+    /// the same URL. NOTE: This is distinct from the concept of whether a
+    /// particular load uses "reload semantics" (i.e. bypasses cached data).
+    Reload,
+
+    /// The url was generated from a replaceable keyword other than the default
+    /// search provider. If the user types a keyword (which also applies to
+    /// tab-to-search) in the omnibox this qualifier is applied to the transition
+    /// type of the generated url. Chrome runtime only.
+    Keyword,
+
+    /// Corresponds to a visit generated for a keyword. See description of
+    /// Keyword for more details. Chrome runtime only.
+    KeywordGenerated
+}
+
+impl From<u32> for TransitionSource {
+    fn from(value: u32) -> Self {
+        match value & cef_transition_type_t::TT_SOURCE_MASK as u32 {
+            1 => TransitionSource::Explicit,
+            2 => TransitionSource::AutoBookmark,
+            3 => TransitionSource::AutoSubframe,
+            4 => TransitionSource::ManualSubframe,
+            5 => TransitionSource::Generated,
+            6 => TransitionSource::AutoToplevel,
+            7 => TransitionSource::FormSubmit,
+            8 => TransitionSource::Reload,
+            9 => TransitionSource::Keyword,
+            10 => TransitionSource::KeywordGenerated,
+            _ => TransitionSource::Link
+        }
+    }
+}
+
+impl From<TransitionSource> for u32 {
+    fn from(value: TransitionSource) -> Self {
+        let value = match value {
+            TransitionSource::Link => cef_transition_type_t::TT_LINK,
+            TransitionSource::Explicit => cef_transition_type_t::TT_EXPLICIT,
+            TransitionSource::AutoBookmark => cef_transition_type_t::TT_AUTO_BOOKMARK,
+            TransitionSource::AutoSubframe => cef_transition_type_t::TT_AUTO_SUBFRAME,
+            TransitionSource::ManualSubframe => cef_transition_type_t::TT_MANUAL_SUBFRAME,
+            TransitionSource::Generated => cef_transition_type_t::TT_GENERATED,
+            TransitionSource::AutoToplevel => cef_transition_type_t::TT_AUTO_TOPLEVEL,
+            TransitionSource::FormSubmit => cef_transition_type_t::TT_FORM_SUBMIT,
+            TransitionSource::Reload => cef_transition_type_t::TT_RELOAD,
+            TransitionSource::Keyword => cef_transition_type_t::TT_KEYWORD,
+            TransitionSource::KeywordGenerated => cef_transition_type_t::TT_KEYWORD_GENERATED
+        };
+
+        value as u32
+    }
+}
+
+bitflags! {
+    /// Transition qualifiers. Any of the core values above can be augmented by
+    /// one or more qualifiers.
+    #[allow(non_upper_case_globals)]
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct TransitionQualifiers: u32 {
+        /// Attempted to visit a URL but was blocked.
+        const Blocked = cef_transition_type_t::TT_BLOCKED_FLAG as u32;
+
+        /// Used the Forward or Back function to navigate among browsing history.
+        /// Will be ORed to the transition type for the original load.
+        const ForwardBack = cef_transition_type_t::TT_FORWARD_BACK_FLAG as u32;
+
+        /// Loaded a URL directly via CreateBrowser, LoadURL or LoadRequest.
+        const DirectLoad = cef_transition_type_t::TT_DIRECT_LOAD_FLAG as u32;
+
+        /// User is navigating to the home page. Chrome runtime only.
+        const HomePage = cef_transition_type_t::TT_HOME_PAGE_FLAG as u32;
+
+        /// The transition originated from an external application; the exact
+        /// definition of this is embedder dependent. Chrome runtime and
+        /// extension system only.
+        const FromApi = cef_transition_type_t::TT_FROM_API_FLAG as u32;
+
+        /// The beginning of a navigation chain.
+        const ChainStart = cef_transition_type_t::TT_CHAIN_START_FLAG as u32;
+
+        /// The last transition in a redirect chain.
+        const ChainEnd = cef_transition_type_t::TT_CHAIN_END_FLAG as u32;
+
+        /// Redirects caused by JavaScript or a meta refresh tag on the page.
+        const ClientRedirect = cef_transition_type_t::TT_CLIENT_REDIRECT_FLAG as u32;
+
+        /// Redirects sent from the server by HTTP headers.
+        const ServerRedirect = cef_transition_type_t::TT_SERVER_REDIRECT_FLAG as u32;
+    }
+}
+
+/// Transition type for a request. Made up of one source value and zero or more
+/// qualifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TransitionType {
+    /// What the user did to move to the page.
+    pub source: TransitionSource,
+
+    /// Additional information about the navigation.
+    pub qualifiers: TransitionQualifiers
+}
+
+impl TransitionType {
+    /// Returns true if the navigation is a client or server redirect.
+    pub fn is_redirect(&self) -> bool {
+        self.qualifiers
+            .intersects(TransitionQualifiers::ClientRedirect | TransitionQualifiers::ServerRedirect)
+    }
+
+    /// Returns true if the navigation went back or forward in the history.
+    pub fn is_forward_back(&self) -> bool {
+        self.qualifiers
+            .contains(TransitionQualifiers::ForwardBack)
+    }
+
+    /// Returns true if the page was reloaded.
+    pub fn is_reload(&self) -> bool {
+        self.source == TransitionSource::Reload
+    }
+
+    /// Returns true if the navigation was started by the user through a link,
+    /// a typed URL or a form, rather than by a subframe, redirect or reload.
+    pub fn is_user_initiated(&self) -> bool {
+        matches!(
+            self.source,
+            TransitionSource::Link
+                | TransitionSource::Explicit
+                | TransitionSource::ManualSubframe
+                | TransitionSource::FormSubmit
+        ) && !self.is_redirect()
+    }
+}
+
+impl From<u32> for TransitionType {
+    fn from(value: u32) -> Self {
+        Self {
+            source:     value.into(),
+            qualifiers: TransitionQualifiers::from_bits_truncate(value)
+        }
+    }
+}
+
+impl From<cef_transition_type_t> for TransitionType {
+    fn from(value: cef_transition_type_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_transition_type_t> for TransitionType {
+    fn from(value: &cef_transition_type_t) -> Self {
+        // CEF combines a source value with qualifier bits, so the value is
+        // decoded from its bits rather than matched against the variants.
+        Self::from(*value as u32)
+    }
+}
+
+impl From<TransitionType> for u32 {
+    fn from(value: TransitionType) -> Self {
+        u32::from(value.source) | value.qualifiers.bits()
+    }
+}
+
 /// Represents the state of a setting.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum State {
@@ -2323,22 +2575,70 @@ impl From<&TextInputMode> for cef_text_input_mode_t {
 /// The manner in which a link click should be opened. These constants match
 /// their equivalents in Chromium's window_open_disposition.h and should not be
 /// renumbered.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub enum WindowOpenDisposition {
+    /// The disposition is not known.
+    #[default]
     Unknown,
+
+    /// Open in the current tab.
     CurrentTab,
+
+    /// Switch to an existing tab showing the URL, or open it in a new tab.
     SingletonTab,
+
+    /// Open in a new tab that becomes active, e.g. a middle click with shift.
     NewForegroundTab,
+
+    /// Open in a new tab in the background, e.g. a middle click.
     NewBackgroundTab,
+
+    /// Open in a new popup window, e.g. window.open with window features.
     NewPopup,
+
+    /// Open in a new window, e.g. a shift click.
     NewWindow,
+
+    /// Save the target to disk, e.g. an alt click.
     SaveToDisk,
+
+    /// Open in an off-the-record (incognito) window.
     OffTheRecord,
+
+    /// Ignore the request.
     IgnoreAction,
+
+    /// Activate an existing tab containing the URL, rather than navigating.
+    /// Chrome runtime only.
     SwitchToTab,
+
+    /// Open in a Document picture-in-picture window.
     NewPictureInPicture
 }
 
+impl WindowOpenDisposition {
+    /// Returns true if the target should be shown in a new tab or window
+    /// rather than replacing the current page.
+    pub fn opens_new_browser(&self) -> bool {
+        matches!(
+            self,
+            WindowOpenDisposition::SingletonTab
+                | WindowOpenDisposition::NewForegroundTab
+                | WindowOpenDisposition::NewBackgroundTab
+                | WindowOpenDisposition::NewPopup
+                | WindowOpenDisposition::NewWindow
+                | WindowOpenDisposition::OffTheRecord
+                | WindowOpenDisposition::NewPictureInPicture
+        )
+    }
+
+    /// Returns true if the target should be opened without taking focus from
+    /// the current page.
+    pub fn is_background(&self) -> bool {
+        *self == WindowOpenDisposition::NewBackgroundTab
+    }
+}
+
 impl From<cef_window_open_disposition_t> for WindowOpenDisposition {
     fn from(value: cef_window_open_disposition_t) -> Self {
         Self::from(&value)
@@ -2514,12 +2814,66 @@ pub enum ReferrerPolicy {
     NoReferrer
 }
 
+impl ReferrerPolicy {
+    /// Returns the equivalent value of the Referrer-Policy HTTP header, e.g.
+    /// "strict-origin-when-cross-origin".
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReferrerPolicy::ClearReferrerOnTransitionFromSecureToInsecure => {
+                "no-referrer-when-downgrade"
+            },
+            ReferrerPolicy::ReduceReferrerGranularityOnTransitionCrossOrigin => {
+                "strict-origin-when-cross-origin"
+            },
+            ReferrerPolicy::OriginOnlyOnTransitionCrossOrigin => "origin-when-cross-origin",
+            ReferrerPolicy::NeverClearReferrer => "unsafe-url",
+            ReferrerPolicy::Origin => "origin",
+            ReferrerPolicy::ClearReferrerOnTransitionCrossOrigin => "same-origin",
+            ReferrerPolicy::OriginClearOnTransitionFromSecureToInsecure => "strict-origin",
+            ReferrerPolicy::NoReferrer => "no-referrer"
+        }
+    }
+}
+
 impl Default for ReferrerPolicy {
     fn default() -> Self {
         ReferrerPolicy::ClearReferrerOnTransitionFromSecureToInsecure
     }
 }
 
+impl Display for ReferrerPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ReferrerPolicy {
+    type Err = Error;
+
+    /// Parses a value of the Referrer-Policy HTTP header.
+    fn from_str(value: &str) -> Result<Self> {
+        match value
+            .trim()
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "no-referrer-when-downgrade" => {
+                Ok(ReferrerPolicy::ClearReferrerOnTransitionFromSecureToInsecure)
+            },
+            "strict-origin-when-cross-origin" => {
+                Ok(ReferrerPolicy::ReduceReferrerGranularityOnTransitionCrossOrigin)
+            },
+            "origin-when-cross-origin" => Ok(ReferrerPolicy::OriginOnlyOnTransitionCrossOrigin),
+            "unsafe-url" => Ok(ReferrerPolicy::NeverClearReferrer),
+            "origin" => Ok(ReferrerPolicy::Origin),
+            "same-origin" => Ok(ReferrerPolicy::ClearReferrerOnTransitionCrossOrigin),
+            "strict-origin" => Ok(ReferrerPolicy::OriginClearOnTransitionFromSecureToInsecure),
+            "no-referrer" => Ok(ReferrerPolicy::NoReferrer),
+            _ => Err(anyhow!("Invalid referrer policy: {}", value).into())
+        }
+    }
+}
+
 impl From<cef_referrer_policy_t> for ReferrerPolicy {
     fn from(value: cef_referrer_policy_t) -> Self {
         Self::from(&value)
@@ -2652,6 +3006,43 @@ pub enum ResourceType {
     NavigationPreloadSubFrame
 }
 
+impl ResourceType {
+    /// Returns true for requests that load a document into a frame, including
+    /// service worker navigation preload requests.
+    pub fn is_navigation(&self) -> bool {
+        matches!(
+            self,
+            ResourceType::MainFrame
+                | ResourceType::SubFrame
+                | ResourceType::NavigationPreloadMainFrame
+                | ResourceType::NavigationPreloadSubFrame
+        )
+    }
+
+    /// Returns true for requests that load a document into the main frame.
+    pub fn is_main_frame(&self) -> bool {
+        matches!(
+            self,
+            ResourceType::MainFrame | ResourceType::NavigationPreloadMainFrame
+        )
+    }
+
+    /// Returns true for the main resource of a dedicated, shared or service
+    /// worker.
+    pub fn is_worker(&self) -> bool {
+        matches!(
+            self,
+            ResourceType::Worker | ResourceType::SharedWorker | ResourceType::ServiceWorker
+        )
+    }
+
+    /// Returns true for requests that are issued by a document rather than
+    /// loading one, such as stylesheets, scripts, images and XMLHttpRequest.
+    pub fn is_subresource(&self) -> bool {
+        !self.is_navigation() && !self.is_worker()
+    }
+}
+
 impl From<cef_resource_type_t> for ResourceType {
     fn from(value: cef_resource_type_t) -> Self {
         Self::from(&value)
@@ -2727,13 +3118,19 @@ impl From<&ResourceType> for cef_resource_type_t {
 }
 
 /// Structure representing a point.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[repr(C)]
 pub struct Point {
     pub x: i32,
     pub y: i32
 }
 
+impl Point {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+
 impl From<cef_point_t> for Point {
     fn from(value: cef_point_t) -> Self {
         Self::from(&value)
@@ -2765,7 +3162,7 @@ impl From<&Point> for cef_point_t {
 }
 
 /// Structure representing a rectangle.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[repr(C)]
 pub struct Rect {
     pub x:      i32,
@@ -2774,6 +3171,66 @@ pub struct Rect {
     pub height: i32
 }
 
+impl Rect {
+    pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height
+        }
+    }
+
+    /// Create a rectangle from an origin and a size.
+    pub fn from_origin_size(origin: Point, size: Size) -> Self {
+        Self::new(origin.x, origin.y, size.width, size.height)
+    }
+
+    /// The top-left corner of the rectangle.
+    pub fn origin(&self) -> Point {
+        Point::new(self.x, self.y)
+    }
+
+    /// The size of the rectangle.
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+
+    /// Returns true if the rectangle has no area.
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0 || self.height <= 0
+    }
+
+    /// Returns true if the point lies within the rectangle.
+    pub fn contains(&self, point: &Point) -> bool {
+        point.x >= self.x
+            && point.y >= self.y
+            && point.x < self.x + self.width
+            && point.y < self.y + self.height
+    }
+
+    /// Shrink the rectangle by the given insets.
+    pub fn inset(&self, insets: &Insets) -> Self {
+        Self::new(
+            self.x + insets.left,
+            self.y + insets.top,
+            (self.width - insets.left - insets.right).max(0),
+            (self.height - insets.top - insets.bottom).max(0)
+        )
+    }
+
+    /// Returns the overlap of both rectangles, which is empty if they do not
+    /// intersect.
+    pub fn intersect(&self, other: &Rect) -> Self {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = (self.x + self.width).min(other.x + other.width);
+        let bottom = (self.y + self.height).min(other.y + other.height);
+
+        Self::new(x, y, (right - x).max(0), (bottom - y).max(0))
+    }
+}
+
 impl From<cef_rect_t> for Rect {
     fn from(value: cef_rect_t) -> Self {
         Self::from(&value)
@@ -2809,13 +3266,24 @@ impl From<&Rect> for cef_rect_t {
 }
 
 /// Structure representing a size.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[repr(C)]
 pub struct Size {
     pub width:  i32,
     pub height: i32
 }
 
+impl Size {
+    pub fn new(width: i32, height: i32) -> Self {
+        Self { width, height }
+    }
+
+    /// Returns true if the size has no area.
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0 || self.height <= 0
+    }
+}
+
 impl From<cef_size_t> for Size {
     fn from(value: cef_size_t) -> Self {
         Self::from(&value)
@@ -2847,7 +3315,7 @@ impl From<&Size> for cef_size_t {
 }
 
 /// Structure representing insets.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[repr(C)]
 pub struct Insets {
     pub top:    i32,
@@ -2856,6 +3324,22 @@ pub struct Insets {
     pub right:  i32
 }
 
+impl Insets {
+    pub fn new(top: i32, left: i32, bottom: i32, right: i32) -> Self {
+        Self {
+            top,
+            left,
+            bottom,
+            right
+        }
+    }
+
+    /// Create insets with the same value on every side.
+    pub fn uniform(value: i32) -> Self {
+        Self::new(value, value, value, value)
+    }
+}
+
 impl From<cef_insets_t> for Insets {
     fn from(value: cef_insets_t) -> Self {
         Self::from(&value)
@@ -2891,13 +3375,41 @@ impl From<&Insets> for cef_insets_t {
 }
 
 /// Structure representing a range.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[repr(C)]
 pub struct Range {
     pub from: u32,
     pub to:   u32
 }
 
+impl Range {
+    pub fn new(from: u32, to: u32) -> Self {
+        Self { from, to }
+    }
+
+    /// Returns true if the range contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.from == self.to
+    }
+
+    /// The number of elements in the range.
+    pub fn len(&self) -> u32 {
+        self.from.abs_diff(self.to)
+    }
+}
+
+impl From<std::ops::Range<u32>> for Range {
+    fn from(value: std::ops::Range<u32>) -> Self {
+        Self::new(value.start, value.end)
+    }
+}
+
+impl From<Range> for std::ops::Range<u32> {
+    fn from(value: Range) -> Self {
+        value.from..value.to
+    }
+}
+
 impl From<cef_range_t> for Range {
     fn from(value: cef_range_t) -> Self {
         Self::from(&value)
@@ -2928,9 +3440,70 @@ impl From<&Range> for cef_range_t {
     }
 }
 
+#[cfg(feature = "winit")]
+mod winit_conversions {
+    use super::{Point, Size};
+    use winit::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
+
+    impl From<PhysicalPosition<i32>> for Point {
+        fn from(value: PhysicalPosition<i32>) -> Self {
+            Self::new(value.x, value.y)
+        }
+    }
+
+    impl From<Point> for PhysicalPosition<i32> {
+        fn from(value: Point) -> Self {
+            Self::new(value.x, value.y)
+        }
+    }
+
+    impl From<LogicalPosition<i32>> for Point {
+        fn from(value: LogicalPosition<i32>) -> Self {
+            Self::new(value.x, value.y)
+        }
+    }
+
+    impl From<Point> for LogicalPosition<i32> {
+        fn from(value: Point) -> Self {
+            Self::new(value.x, value.y)
+        }
+    }
+
+    impl From<PhysicalSize<u32>> for Size {
+        fn from(value: PhysicalSize<u32>) -> Self {
+            Self::new(
+                value.width.min(i32::MAX as u32) as i32,
+                value.height.min(i32::MAX as u32) as i32
+            )
+        }
+    }
+
+    impl From<Size> for PhysicalSize<u32> {
+        fn from(value: Size) -> Self {
+            Self::new(value.width.max(0) as u32, value.height.max(0) as u32)
+        }
+    }
+
+    impl From<LogicalSize<u32>> for Size {
+        fn from(value: LogicalSize<u32>) -> Self {
+            Self::new(
+                value.width.min(i32::MAX as u32) as i32,
+                value.height.min(i32::MAX as u32) as i32
+            )
+        }
+    }
+
+    impl From<Size> for LogicalSize<u32> {
+        fn from(value: Size) -> Self {
+            Self::new(value.width.max(0) as u32, value.height.max(0) as u32)
+        }
+    }
+}
+
 /// Screen information used when window rendering is disabled. This structure is
 /// passed as a parameter to CefRenderHandler::GetScreenInfo and should be
 /// filled in by the client.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ScreenInfo {
     /// Device scale factor. Specifies the ratio between physical and logical
     /// pixels.
@@ -2970,6 +3543,78 @@ pub struct ScreenInfo {
     pub available_rect: Rect
 }
 
+impl ScreenInfo {
+    /// Describes a 24-bit color screen covering |rect| at a device scale factor
+    /// of 1, with all of |rect| available.
+    pub fn new(rect: Rect) -> Self {
+        Self {
+            device_scale_factor: 1.0,
+            depth: 24,
+            depth_per_component: 8,
+            is_monochrome: false,
+            rect,
+            available_rect: rect
+        }
+    }
+
+    /// Set the ratio between physical and logical pixels.
+    pub fn device_scale_factor(mut self, device_scale_factor: f32) -> Self {
+        self.device_scale_factor = device_scale_factor;
+        self
+    }
+
+    /// Set the screen depth in bits per pixel and the bits per color component.
+    pub fn depth(mut self, depth: i32, depth_per_component: i32) -> Self {
+        self.depth = depth;
+        self.depth_per_component = depth_per_component;
+        self
+    }
+
+    /// Set whether the screen is black and white.
+    pub fn monochrome(mut self, is_monochrome: bool) -> Self {
+        self.is_monochrome = is_monochrome;
+        self
+    }
+
+    /// Set the screen rectangle. The available rectangle is left unchanged.
+    pub fn rect(mut self, rect: Rect) -> Self {
+        self.rect = rect;
+        self
+    }
+
+    /// Set the work area of the screen, i.e. the screen rectangle without
+    /// system windows such as the task bar.
+    pub fn available_rect(mut self, available_rect: Rect) -> Self {
+        self.available_rect = available_rect;
+        self
+    }
+}
+
+impl Default for ScreenInfo {
+    fn default() -> Self {
+        Self::new(Rect::default())
+    }
+}
+
+impl From<cef_screen_info_t> for ScreenInfo {
+    fn from(value: cef_screen_info_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_screen_info_t> for ScreenInfo {
+    fn from(value: &cef_screen_info_t) -> Self {
+        Self {
+            device_scale_factor: value.device_scale_factor,
+            depth:               value.depth as i32,
+            depth_per_component: value.depth_per_component as i32,
+            is_monochrome:       value.is_monochrome != 0,
+            rect:                value.rect.into(),
+            available_rect:      value.available_rect.into()
+        }
+    }
+}
+
 impl From<ScreenInfo> for cef_screen_info_t {
     fn from(value: ScreenInfo) -> Self {
         Self::from(&value)
@@ -0,0 +1,31 @@
+use std::{
+    any::Any,
+    panic::{catch_unwind, AssertUnwindSafe}
+};
+use tracing::error;
+
+/// Runs the body of a CEF -> Rust callback shim, catching any panic so that
+/// it never unwinds across the C boundary. If the callback panics the panic
+/// is logged and |default| is handed back to CEF instead, which should be
+/// the value that asks CEF to proceed with its default behavior.
+pub(crate) fn catch_unwind_or<R>(name: &'static str, default: R, f: impl FnOnce() -> R) -> R {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(ret) => ret,
+        Err(payload) => {
+            error!("Panic in {}: {}", name, panic_message(payload.as_ref()));
+
+            default
+        }
+    }
+}
+
+/// Extracts a human-readable message from a panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "<unknown>"
+    }
+}
@@ -1,8 +1,7 @@
 use crate::{
-    ref_counted_ptr, try_c, AuthCallback, CefString, ErrorCode, RefCountedPtr, Request,
-    RequestContext, Response, Wrappable, Wrapped
+    ref_counted_ptr, try_c, unwind::catch_unwind_or, AuthCallback, CefString, ErrorCode,
+    RefCountedPtr, Request, RequestContext, Response, Result, Wrappable, Wrapped
 };
-use anyhow::Result;
 use bitflags::bitflags;
 use cef_ui_sys::{
     cef_auth_callback_t, cef_string_t, cef_urlrequest_client_t, cef_urlrequest_create,
@@ -23,6 +22,7 @@ use std::{
 
 bitflags! {
     /// Flags used to customize the behavior of CefURLRequest.
+    #[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
     pub struct UrlRequestFlags: cef_urlrequest_flags_t {
         /// Default behavior.
         const None = cef_urlrequest_flags_t_UR_FLAG_NONE;
@@ -248,23 +248,23 @@ pub trait UrlRequestClientCallbacks: Send + Sync + 'static {
     /// Notifies the client that the request has completed. Use the
     /// cef_urlrequest_t::GetRequestStatus function to determine if the request
     /// was successful or not.
-    fn on_request_complete(&mut self, request: UrlRequest);
+    fn on_request_complete(&mut self, _request: UrlRequest) {}
 
     /// Notifies the client of upload progress. |current| denotes the number of
     /// bytes sent so far and |total| is the total size of uploading data (or -1
     /// if chunked upload is enabled). This function will only be called if the
     /// UR_FLAG_REPORT_UPLOAD_PROGRESS flag is set on the request.
-    fn on_upload_progress(&mut self, request: UrlRequest, current: i64, total: i64);
+    fn on_upload_progress(&mut self, _request: UrlRequest, _current: i64, _total: i64) {}
 
     /// Notifies the client of download progress. |current| denotes the number of
     /// bytes received up to the call and |total| is the expected total size of
     /// the response (or -1 if not determined).
-    fn on_download_progress(&mut self, request: UrlRequest, current: i64, total: i64);
+    fn on_download_progress(&mut self, _request: UrlRequest, _current: i64, _total: i64) {}
 
     /// Called when some part of the response is read. |data| contains the current
     /// bytes received since the last call. This function will not be called if
     /// the UR_FLAG_NO_DOWNLOAD_DATA flag is set on the request.
-    fn on_download_data(&mut self, request: UrlRequest, data: &[u8]);
+    fn on_download_data(&mut self, _request: UrlRequest, _data: &[u8]) {}
 
     /// Called on the IO thread when the browser needs credentials from the user.
     /// |isProxy| indicates whether the host is a proxy server. |host| contains
@@ -278,13 +278,15 @@ pub trait UrlRequestClientCallbacks: Send + Sync + 'static {
     /// the browser process.
     fn get_auth_credentials(
         &mut self,
-        is_proxy: bool,
-        host: &str,
-        port: u16,
-        realm: &str,
-        scheme: &str,
-        callback: AuthCallback
-    ) -> bool;
+        _is_proxy: bool,
+        _host: &str,
+        _port: u16,
+        _realm: &str,
+        _scheme: &str,
+        _callback: AuthCallback
+    ) -> bool {
+        false
+    }
 }
 
 // Structure that should be implemented by the cef_urlrequest_t client. The
@@ -299,11 +301,11 @@ impl UrlRequestClient {
 }
 
 /// Translates CEF -> Rust callbacks.
-struct UrlRequestClientWrapper(Box<dyn UrlRequestClientCallbacks>);
+struct UrlRequestClientWrapper<C: UrlRequestClientCallbacks>(C);
 
-impl UrlRequestClientWrapper {
-    pub fn new<C: UrlRequestClientCallbacks>(delegate: C) -> Self {
-        Self(Box::new(delegate))
+impl<C: UrlRequestClientCallbacks> UrlRequestClientWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
     }
 
     /// Notifies the client that the request has completed. Use the
@@ -313,10 +315,12 @@ impl UrlRequestClientWrapper {
         this: *mut cef_urlrequest_client_t,
         request: *mut cef_urlrequest_t
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let request = UrlRequest::from_ptr_unchecked(request);
+        catch_unwind_or("UrlRequestClientWrapper::c_on_request_complete", (), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let request = UrlRequest::from_ptr_unchecked(request);
 
-        this.0.on_request_complete(request);
+            this.0.on_request_complete(request);
+        })
     }
 
     /// Notifies the client of upload progress. |current| denotes the number of
@@ -329,11 +333,13 @@ impl UrlRequestClientWrapper {
         current: i64,
         total: i64
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let request = UrlRequest::from_ptr_unchecked(request);
+        catch_unwind_or("UrlRequestClientWrapper::c_on_upload_progress", (), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let request = UrlRequest::from_ptr_unchecked(request);
 
-        this.0
-            .on_upload_progress(request, current, total);
+            this.0
+                .on_upload_progress(request, current, total);
+        })
     }
 
     /// Notifies the client of download progress. |current| denotes the number of
@@ -345,11 +351,17 @@ impl UrlRequestClientWrapper {
         current: i64,
         total: i64
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let request = UrlRequest::from_ptr_unchecked(request);
-
-        this.0
-            .on_download_progress(request, current, total);
+        catch_unwind_or(
+            "UrlRequestClientWrapper::c_on_download_progress",
+            (),
+            || {
+                let this: &mut Self = Wrapped::wrappable(this);
+                let request = UrlRequest::from_ptr_unchecked(request);
+
+                this.0
+                    .on_download_progress(request, current, total);
+            }
+        )
     }
 
     /// Called when some part of the response is read. |data| contains the current
@@ -361,12 +373,14 @@ impl UrlRequestClientWrapper {
         data: *const c_void,
         data_length: usize
     ) {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let request = UrlRequest::from_ptr_unchecked(request);
-        let data = from_raw_parts(data as *const u8, data_length);
+        catch_unwind_or("UrlRequestClientWrapper::c_on_download_data", (), || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let request = UrlRequest::from_ptr_unchecked(request);
+            let data = from_raw_parts(data as *const u8, data_length);
 
-        this.0
-            .on_download_data(request, data);
+            this.0
+                .on_download_data(request, data);
+        })
     }
 
     /// Called on the IO thread when the browser needs credentials from the user.
@@ -388,19 +402,26 @@ impl UrlRequestClientWrapper {
         scheme: *const cef_string_t,
         callback: *mut cef_auth_callback_t
     ) -> c_int {
-        let this: &mut Self = Wrapped::wrappable(this);
-        let host: String = CefString::from_ptr_unchecked(host).into();
-        let realm: String = CefString::from_ptr_unchecked(realm).into();
-        let scheme: String = CefString::from_ptr_unchecked(scheme).into();
-        let callback = AuthCallback::from_ptr_unchecked(callback);
-
-        this.0
-            .get_auth_credentials(is_proxy != 0, &host, port as u16, &realm, &scheme, callback)
-            as c_int
+        catch_unwind_or("UrlRequestClientWrapper::c_get_auth_credentials", 0, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let host: String = CefString::from_ptr_unchecked(host).into();
+            let realm: String = CefString::from_ptr_unchecked(realm).into();
+            let scheme: String = CefString::from_ptr_unchecked(scheme).into();
+            let callback = AuthCallback::from_ptr_unchecked(callback);
+
+            this.0.get_auth_credentials(
+                is_proxy != 0,
+                &host,
+                port as u16,
+                &realm,
+                &scheme,
+                callback
+            ) as c_int
+        })
     }
 }
 
-impl Wrappable for UrlRequestClientWrapper {
+impl<C: UrlRequestClientCallbacks> Wrappable for UrlRequestClientWrapper<C> {
     type Cef = cef_urlrequest_client_t;
 
     /// Converts this to a smart pointer.
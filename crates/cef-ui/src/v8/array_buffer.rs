@@ -0,0 +1,60 @@
+use crate::{ref_counted_ptr, unwind::catch_unwind_or, RefCountedPtr, Wrappable, Wrapped};
+use cef_ui_sys::cef_v8array_buffer_release_callback_t;
+use parking_lot::Mutex;
+use std::{any::Any, ffi::c_void, mem::zeroed};
+
+// Callback interface that is passed to cef_v8value_t::CreateArrayBuffer.
+ref_counted_ptr!(
+    V8ArrayBufferReleaseCallback,
+    cef_v8array_buffer_release_callback_t
+);
+
+impl V8ArrayBufferReleaseCallback {
+    /// Creates a callback that keeps |owner| alive until the ArrayBuffer is
+    /// released, then drops it.
+    pub fn new(owner: impl Send + 'static) -> Self {
+        Self(V8ArrayBufferReleaseCallbackWrapper::new(owner).wrap())
+    }
+}
+
+/// Translates CEF -> Rust callbacks.
+struct V8ArrayBufferReleaseCallbackWrapper(Mutex<Option<Box<dyn Any + Send + 'static>>>);
+
+impl V8ArrayBufferReleaseCallbackWrapper {
+    pub fn new(owner: impl Send + 'static) -> Self {
+        Self(Mutex::new(Some(Box::new(owner))))
+    }
+
+    /// Called to release |buffer| when the ArrayBuffer JS object is garbage
+    /// collected. |buffer| is the value that was passed to CreateArrayBuffer
+    /// along with this object.
+    unsafe extern "C" fn c_release_buffer(
+        this: *mut cef_v8array_buffer_release_callback_t,
+        _buffer: *mut c_void
+    ) {
+        catch_unwind_or(
+            "V8ArrayBufferReleaseCallbackWrapper::c_release_buffer",
+            (),
+            || {
+                let this: &Self = Wrapped::wrappable(this);
+
+                this.0.lock().take();
+            }
+        )
+    }
+}
+
+impl Wrappable for V8ArrayBufferReleaseCallbackWrapper {
+    type Cef = cef_v8array_buffer_release_callback_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<Self::Cef> {
+        RefCountedPtr::wrap(
+            cef_v8array_buffer_release_callback_t {
+                base:           unsafe { zeroed() },
+                release_buffer: Some(Self::c_release_buffer)
+            },
+            self
+        )
+    }
+}
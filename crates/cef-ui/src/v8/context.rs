@@ -0,0 +1,128 @@
+use crate::{
+    ref_counted_ptr, try_c, Browser, CefString, Frame, Result, TaskRunner, V8Exception, V8Value
+};
+use anyhow::anyhow;
+use cef_ui_sys::{
+    cef_v8context_get_current_context, cef_v8context_get_entered_context, cef_v8context_in_context,
+    cef_v8context_t
+};
+use std::{ffi::c_int, ptr::null_mut};
+
+// Structure representing a V8 context handle. V8 handles can only be accessed
+// from the thread on which they are created. Valid threads for creating a V8
+// handle include the render process main thread (TID_RENDERER) and WebWorker
+// threads. A task runner for posting tasks on the associated thread can be
+// retrieved via the cef_v8context_t::get_task_runner() function.
+ref_counted_ptr!(V8Context, cef_v8context_t);
+
+impl V8Context {
+    /// Returns the current (top) context object in the V8 context stack.
+    pub fn get_current_context() -> Option<Self> {
+        unsafe { Self::from_ptr(cef_v8context_get_current_context()) }
+    }
+
+    /// Returns the entered (bottom) context object in the V8 context stack.
+    pub fn get_entered_context() -> Option<Self> {
+        unsafe { Self::from_ptr(cef_v8context_get_entered_context()) }
+    }
+
+    /// Returns true (1) if V8 is currently inside a context.
+    pub fn in_context() -> bool {
+        unsafe { cef_v8context_in_context() != 0 }
+    }
+
+    /// Returns the task runner associated with this context. V8 handles can only
+    /// be accessed from the thread on which they are created. This function can
+    /// be called on any render process thread.
+    pub fn get_task_runner(&self) -> Result<TaskRunner> {
+        try_c!(self, get_task_runner, {
+            TaskRunner::from_ptr(get_task_runner(self.as_ptr()))
+                .ok_or_else(|| anyhow!("The V8 context has no task runner.").into())
+        })
+    }
+
+    /// Returns true (1) if the underlying handle is valid and it can be accessed
+    /// on the current thread. Do not call any other functions if this function
+    /// returns false (0).
+    pub fn is_valid(&self) -> Result<bool> {
+        try_c!(self, is_valid, { Ok(is_valid(self.as_ptr()) != 0) })
+    }
+
+    /// Returns the browser for this context. This function will return an NULL
+    /// reference for WebWorker contexts.
+    pub fn get_browser(&self) -> Result<Option<Browser>> {
+        try_c!(self, get_browser, {
+            Ok(Browser::from_ptr(get_browser(self.as_ptr())))
+        })
+    }
+
+    /// Returns the frame for this context. This function will return an NULL
+    /// reference for WebWorker contexts.
+    pub fn get_frame(&self) -> Result<Option<Frame>> {
+        try_c!(self, get_frame, {
+            Ok(Frame::from_ptr(get_frame(self.as_ptr())))
+        })
+    }
+
+    /// Returns the global object for this context. The context must be entered
+    /// before calling this function.
+    pub fn get_global(&self) -> Result<V8Value> {
+        try_c!(self, get_global, {
+            V8Value::from_ptr(get_global(self.as_ptr())).ok_or_else(|| {
+                anyhow!("Failed to get the global object; enter the context first.").into()
+            })
+        })
+    }
+
+    /// Enter this context. A context must be explicitly entered before creating a
+    /// V8 Object, Array, Function or Date asynchronously. exit() must be called
+    /// the same number of times as enter() before releasing this context. V8
+    /// objects belong to the context in which they are created. Returns true (1)
+    /// if the scope was entered successfully.
+    pub fn enter(&self) -> Result<bool> {
+        try_c!(self, enter, { Ok(enter(self.as_ptr()) != 0) })
+    }
+
+    /// Exit this context. Call this function only after calling enter(). Returns
+    /// true (1) if the scope was exited successfully.
+    pub fn exit(&self) -> Result<bool> {
+        try_c!(self, exit, { Ok(exit(self.as_ptr()) != 0) })
+    }
+
+    /// Returns true (1) if this object is pointing to the same handle as |that|
+    /// object.
+    pub fn is_same(&self, that: V8Context) -> Result<bool> {
+        try_c!(self, is_same, {
+            Ok(is_same(self.as_ptr(), that.into_raw()) != 0)
+        })
+    }
+
+    /// Execute a string of JavaScript code in this V8 context. The |script_url|
+    /// parameter is the URL where the script in question can be found, if any.
+    /// The |start_line| parameter is the base line number to use for error
+    /// reporting. On success the return value is returned. On failure an
+    /// Error::V8Exception describing the exception that was thrown is returned.
+    pub fn eval(&self, code: &str, script_url: &str, start_line: i32) -> Result<V8Value> {
+        try_c!(self, eval, {
+            let code = CefString::new(code);
+            let script_url = CefString::new(script_url);
+            let mut retval = null_mut();
+            let mut exception = null_mut();
+
+            eval(
+                self.as_ptr(),
+                code.as_ptr(),
+                script_url.as_ptr(),
+                start_line as c_int,
+                &mut retval,
+                &mut exception
+            );
+
+            match (V8Value::from_ptr(retval), V8Exception::from_ptr(exception)) {
+                (Some(retval), None) => Ok(retval),
+                (_, Some(exception)) => Err(exception.to_error()),
+                (None, None) => Err(anyhow!("Failed to evaluate JavaScript code.").into())
+            }
+        })
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{ref_counted_ptr, try_c, CefString, Error, Result};
+use cef_ui_sys::cef_v8exception_t;
+
+// Structure representing a V8 exception. The functions of this structure may be
+// called on any render process thread.
+ref_counted_ptr!(V8Exception, cef_v8exception_t);
+
+impl V8Exception {
+    /// Returns the exception message.
+    pub fn get_message(&self) -> Result<String> {
+        try_c!(self, get_message, {
+            let s = get_message(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
+        })
+    }
+
+    /// Returns the line of source code that the exception occurred within.
+    pub fn get_source_line(&self) -> Result<String> {
+        try_c!(self, get_source_line, {
+            let s = get_source_line(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
+        })
+    }
+
+    /// Returns the resource name for the script from where the function causing
+    /// the error originates.
+    pub fn get_script_resource_name(&self) -> Result<String> {
+        try_c!(self, get_script_resource_name, {
+            let s = get_script_resource_name(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
+        })
+    }
+
+    /// Returns the 1-based number of the line where the error occurred or 0 if
+    /// the line number is unknown.
+    pub fn get_line_number(&self) -> Result<i32> {
+        try_c!(self, get_line_number, {
+            Ok(get_line_number(self.as_ptr()))
+        })
+    }
+
+    /// Returns the index within the script of the first character where the
+    /// error occurred.
+    pub fn get_start_position(&self) -> Result<i32> {
+        try_c!(self, get_start_position, {
+            Ok(get_start_position(self.as_ptr()))
+        })
+    }
+
+    /// Returns the index within the script of the last character where the error
+    /// occurred.
+    pub fn get_end_position(&self) -> Result<i32> {
+        try_c!(self, get_end_position, {
+            Ok(get_end_position(self.as_ptr()))
+        })
+    }
+
+    /// Returns the index within the line of the first character where the error
+    /// occurred.
+    pub fn get_start_column(&self) -> Result<i32> {
+        try_c!(self, get_start_column, {
+            Ok(get_start_column(self.as_ptr()))
+        })
+    }
+
+    /// Returns the index within the line of the last character where the error
+    /// occurred.
+    pub fn get_end_column(&self) -> Result<i32> {
+        try_c!(self, get_end_column, { Ok(get_end_column(self.as_ptr())) })
+    }
+
+    /// Converts this exception into an Error::V8Exception.
+    pub fn to_error(&self) -> Error {
+        Error::V8Exception {
+            message:              self
+                .get_message()
+                .unwrap_or_default(),
+            script_resource_name: self
+                .get_script_resource_name()
+                .unwrap_or_default(),
+            line_number:          self
+                .get_line_number()
+                .unwrap_or_default()
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{
+    ref_counted_ptr, unwind::catch_unwind_or, CefStr, CefString, RefCountedPtr, V8Value, Wrappable,
+    Wrapped
+};
+use anyhow::Result;
+use cef_ui_sys::{cef_string_t, cef_v8handler_t, cef_v8value_t};
+use std::{ffi::c_int, mem::zeroed, slice::from_raw_parts};
+
+/// Structure that should be implemented to handle V8 function calls. The
+/// functions of this structure will be called on the thread associated with the
+/// V8 function.
+pub trait V8HandlerCallbacks: Send + Sync + 'static {
+    /// Handle execution of the function identified by |name|. |object| is the
+    /// receiver ('this' object) of the function. |arguments| is the list of
+    /// arguments passed to the function. Return the value to hand back to
+    /// JavaScript, or None to return undefined. If an error is returned its
+    /// message will be thrown as a JavaScript exception.
+    fn execute(
+        &mut self,
+        name: CefStr<'_>,
+        object: V8Value,
+        arguments: Vec<V8Value>
+    ) -> Result<Option<V8Value>>;
+}
+
+// Structure that should be implemented to handle V8 function calls. The
+// functions of this structure will be called on the thread associated with the
+// V8 function.
+ref_counted_ptr!(V8Handler, cef_v8handler_t);
+
+impl V8Handler {
+    pub fn new<C: V8HandlerCallbacks>(delegate: C) -> Self {
+        Self(V8HandlerWrapper::new(delegate).wrap())
+    }
+}
+
+/// Translates CEF -> Rust callbacks.
+struct V8HandlerWrapper<C: V8HandlerCallbacks>(C);
+
+impl<C: V8HandlerCallbacks> V8HandlerWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
+    }
+
+    /// Handle execution of the function identified by |name|. |object| is the
+    /// receiver ('this' object) of the function. |arguments| is the list of
+    /// arguments passed to the function. If execution succeeds set |retval| to
+    /// the function return value. If execution fails set |exception| to the
+    /// exception that will be thrown. Return true (1) if execution was handled.
+    unsafe extern "C" fn c_execute(
+        this: *mut cef_v8handler_t,
+        name: *const cef_string_t,
+        object: *mut cef_v8value_t,
+        arguments_count: usize,
+        arguments: *const *mut cef_v8value_t,
+        retval: *mut *mut cef_v8value_t,
+        exception: *mut cef_string_t
+    ) -> c_int {
+        catch_unwind_or("V8HandlerWrapper::c_execute", 0, || {
+            let this: &mut Self = Wrapped::wrappable(this);
+            let name = CefStr::from_ptr(name).unwrap_or_default();
+            let object = V8Value::from_ptr_unchecked(object);
+            let arguments = match arguments.is_null() {
+                true => Vec::new(),
+                false => from_raw_parts(arguments, arguments_count)
+                    .iter()
+                    .map(|&ptr| V8Value::from_ptr_unchecked(ptr))
+                    .collect()
+            };
+
+            match this
+                .0
+                .execute(name, object, arguments)
+            {
+                Ok(value) => {
+                    if let Some(value) = value {
+                        *retval = value.into_raw();
+                    }
+                },
+                Err(e) => {
+                    if let Some(exception) = CefString::from_ptr_mut(exception) {
+                        exception.set(&e.to_string());
+                    }
+                },
+            }
+
+            1
+        })
+    }
+}
+
+impl<C: V8HandlerCallbacks> Wrappable for V8HandlerWrapper<C> {
+    type Cef = cef_v8handler_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<cef_v8handler_t> {
+        RefCountedPtr::wrap(
+            cef_v8handler_t {
+                base:    unsafe { zeroed() },
+                execute: Some(Self::c_execute)
+            },
+            self
+        )
+    }
+}
@@ -0,0 +1,15 @@
+mod array_buffer;
+mod context;
+mod exception;
+mod handler;
+mod object_builder;
+mod stack_trace;
+mod value;
+
+pub use array_buffer::*;
+pub use context::*;
+pub use exception::*;
+pub use handler::*;
+pub use object_builder::*;
+pub use stack_trace::*;
+pub use value::*;
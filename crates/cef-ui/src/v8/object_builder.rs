@@ -0,0 +1,200 @@
+use crate::{CefStr, Result, V8Context, V8Handler, V8PropertyAttribute, V8Value};
+use anyhow::anyhow;
+
+/// A property value stored by V8ObjectBuilder. Strings are kept as UTF-16 so
+/// that they are not converted again each time the object is built.
+enum V8ObjectValue {
+    Undefined,
+    Null,
+    Bool(bool),
+    Int(i32),
+    UInt(u32),
+    Double(f64),
+    String(Vec<u16>),
+    Function(V8Handler),
+    Object(V8ObjectBuilder)
+}
+
+/// Describes a JavaScript object with many properties and functions, so that
+/// it can be created in one pass. The context is entered once per build rather
+/// than once per property, and property names and string values are converted
+/// to UTF-16 once, when they are added, rather than on every build. A single
+/// builder can therefore be kept and used to install the same binding surface
+/// into every new context from RenderProcessHandlerCallbacks::on_context_created.
+#[derive(Default)]
+pub struct V8ObjectBuilder {
+    properties: Vec<(Vec<u16>, V8PropertyAttribute, V8ObjectValue)>
+}
+
+impl V8ObjectBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a property that is undefined.
+    pub fn undefined(self, key: &str) -> Self {
+        self.property(key, V8PropertyAttribute::None, V8ObjectValue::Undefined)
+    }
+
+    /// Adds a property that is null.
+    pub fn null(self, key: &str) -> Self {
+        self.property(key, V8PropertyAttribute::None, V8ObjectValue::Null)
+    }
+
+    /// Adds a bool property.
+    pub fn bool(self, key: &str, value: bool) -> Self {
+        self.property(key, V8PropertyAttribute::None, V8ObjectValue::Bool(value))
+    }
+
+    /// Adds an int property.
+    pub fn int(self, key: &str, value: i32) -> Self {
+        self.property(key, V8PropertyAttribute::None, V8ObjectValue::Int(value))
+    }
+
+    /// Adds an unsigned int property.
+    pub fn uint(self, key: &str, value: u32) -> Self {
+        self.property(key, V8PropertyAttribute::None, V8ObjectValue::UInt(value))
+    }
+
+    /// Adds a double property.
+    pub fn double(self, key: &str, value: f64) -> Self {
+        self.property(key, V8PropertyAttribute::None, V8ObjectValue::Double(value))
+    }
+
+    /// Adds a string property.
+    pub fn string(self, key: &str, value: &str) -> Self {
+        self.property(
+            key,
+            V8PropertyAttribute::None,
+            V8ObjectValue::String(value.encode_utf16().collect())
+        )
+    }
+
+    /// Adds a function property named |key| whose calls are handled by
+    /// |handler|. The same handler may be shared by several functions, in which
+    /// case it can tell them apart by the name passed to execute.
+    pub fn function(self, key: &str, handler: V8Handler) -> Self {
+        self.property(
+            key,
+            V8PropertyAttribute::None,
+            V8ObjectValue::Function(handler)
+        )
+    }
+
+    /// Adds a property holding the object described by |object|.
+    pub fn object(self, key: &str, object: V8ObjectBuilder) -> Self {
+        self.property(
+            key,
+            V8PropertyAttribute::None,
+            V8ObjectValue::Object(object)
+        )
+    }
+
+    /// Sets the attribute of the most recently added property.
+    pub fn attribute(mut self, attribute: V8PropertyAttribute) -> Self {
+        if let Some(property) = self.properties.last_mut() {
+            property.1 = attribute;
+        }
+
+        self
+    }
+
+    /// Returns the number of properties.
+    pub fn len(&self) -> usize {
+        self.properties.len()
+    }
+
+    /// Returns true if there are no properties.
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
+
+    /// Enters |context|, creates the object and exits.
+    pub fn build(&self, context: &V8Context) -> Result<V8Value> {
+        Self::with_context(context, || self.create())
+    }
+
+    /// Enters |context|, sets the properties on |object| and exits. Pass the
+    /// context's global object to define the properties as globals.
+    pub fn apply(&self, context: &V8Context, object: &V8Value) -> Result<()> {
+        Self::with_context(context, || self.set_properties(object))
+    }
+
+    /// Enters |context|, sets the object as the global |name| and exits.
+    pub fn install(&self, context: &V8Context, name: &str) -> Result<()> {
+        let name: Vec<u16> = name.encode_utf16().collect();
+
+        Self::with_context(context, || {
+            let global = context.get_global()?;
+
+            Self::set(&global, &name, self.create()?, V8PropertyAttribute::None)
+        })
+    }
+
+    /// Creates the object. The context must already be entered.
+    fn create(&self) -> Result<V8Value> {
+        let object = V8Value::new_object()?;
+
+        self.set_properties(&object)?;
+
+        Ok(object)
+    }
+
+    /// Sets the properties on |object|. The context must already be entered.
+    fn set_properties(&self, object: &V8Value) -> Result<()> {
+        for (key, attribute, value) in &self.properties {
+            let value = match value {
+                V8ObjectValue::Undefined => V8Value::new_undefined(),
+                V8ObjectValue::Null => V8Value::new_null(),
+                V8ObjectValue::Bool(value) => V8Value::new_bool(*value),
+                V8ObjectValue::Int(value) => V8Value::new_int(*value),
+                V8ObjectValue::UInt(value) => V8Value::new_uint(*value),
+                V8ObjectValue::Double(value) => V8Value::new_double(*value),
+                V8ObjectValue::String(value) => V8Value::new_cef_string(CefStr::from(&value[..]))?,
+                V8ObjectValue::Function(handler) => {
+                    V8Value::new_cef_function(CefStr::from(&key[..]), handler.clone())?
+                },
+                V8ObjectValue::Object(builder) => builder.create()?
+            };
+
+            Self::set(object, key, value, *attribute)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets a single property.
+    fn set(
+        object: &V8Value,
+        key: &[u16],
+        value: V8Value,
+        attribute: V8PropertyAttribute
+    ) -> Result<()> {
+        match object.set_value_by_cef_key(CefStr::from(key), value, attribute)? {
+            true => Ok(()),
+            false => {
+                Err(anyhow!("Failed to set property {}.", String::from_utf16_lossy(key)).into())
+            },
+        }
+    }
+
+    /// Runs |f| with |context| entered.
+    fn with_context<T>(context: &V8Context, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        if !context.enter()? {
+            return Err(anyhow!("Failed to enter V8 context.").into());
+        }
+
+        let result = f();
+
+        context.exit()?;
+        result
+    }
+
+    /// Adds a property.
+    fn property(mut self, key: &str, attribute: V8PropertyAttribute, value: V8ObjectValue) -> Self {
+        self.properties
+            .push((key.encode_utf16().collect(), attribute, value));
+
+        self
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{ref_counted_ptr, try_c, CefString, Result};
+use cef_ui_sys::{cef_v8stack_frame_t, cef_v8stack_trace_get_current, cef_v8stack_trace_t};
+
+// Structure representing a V8 stack trace handle. V8 handles can only be
+// accessed from the thread on which they are created. Valid threads for
+// creating a V8 handle include the render process main thread (TID_RENDERER)
+// and WebWorker threads. A task runner for posting tasks on the associated
+// thread can be retrieved via the cef_v8context_t::get_task_runner() function.
+ref_counted_ptr!(V8StackTrace, cef_v8stack_trace_t);
+
+impl V8StackTrace {
+    /// Returns the stack trace for the currently active context. |frame_limit| is
+    /// the maximum number of frames that will be captured.
+    pub fn get_current(frame_limit: i32) -> Option<Self> {
+        unsafe { Self::from_ptr(cef_v8stack_trace_get_current(frame_limit)) }
+    }
+
+    /// Returns true (1) if the underlying handle is valid and it can be accessed
+    /// on the current thread. Do not call any other functions if this function
+    /// returns false (0).
+    pub fn is_valid(&self) -> Result<bool> {
+        try_c!(self, is_valid, { Ok(is_valid(self.as_ptr()) != 0) })
+    }
+
+    /// Returns the number of stack frames.
+    pub fn get_frame_count(&self) -> Result<usize> {
+        try_c!(self, get_frame_count, {
+            Ok(get_frame_count(self.as_ptr()).max(0) as usize)
+        })
+    }
+
+    /// Returns the stack frame at the specified 0-based index.
+    pub fn get_frame(&self, index: usize) -> Result<Option<V8StackFrame>> {
+        try_c!(self, get_frame, {
+            Ok(V8StackFrame::from_ptr(get_frame(
+                self.as_ptr(),
+                index as i32
+            )))
+        })
+    }
+}
+
+// Structure representing a V8 stack frame handle. V8 handles can only be
+// accessed from the thread on which they are created. Valid threads for
+// creating a V8 handle include the render process main thread (TID_RENDERER)
+// and WebWorker threads. A task runner for posting tasks on the associated
+// thread can be retrieved via the cef_v8context_t::get_task_runner() function.
+ref_counted_ptr!(V8StackFrame, cef_v8stack_frame_t);
+
+impl V8StackFrame {
+    /// Returns true (1) if the underlying handle is valid and it can be accessed
+    /// on the current thread. Do not call any other functions if this function
+    /// returns false (0).
+    pub fn is_valid(&self) -> Result<bool> {
+        try_c!(self, is_valid, { Ok(is_valid(self.as_ptr()) != 0) })
+    }
+
+    /// Returns the name of the resource script that contains the function.
+    pub fn get_script_name(&self) -> Result<Option<String>> {
+        try_c!(self, get_script_name, {
+            let s = get_script_name(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s).map(|s| s.into()))
+        })
+    }
+
+    /// Returns the name of the resource script that contains the function or the
+    /// sourceURL value if the script name is undefined and its source ends with
+    /// a "//@ sourceURL=..." string.
+    pub fn get_script_name_or_source_url(&self) -> Result<Option<String>> {
+        try_c!(self, get_script_name_or_source_url, {
+            let s = get_script_name_or_source_url(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s).map(|s| s.into()))
+        })
+    }
+
+    /// Returns the name of the function.
+    pub fn get_function_name(&self) -> Result<Option<String>> {
+        try_c!(self, get_function_name, {
+            let s = get_function_name(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s).map(|s| s.into()))
+        })
+    }
+
+    /// Returns the 1-based line number for the function call or 0 if unknown.
+    pub fn get_line_number(&self) -> Result<i32> {
+        try_c!(self, get_line_number, {
+            Ok(get_line_number(self.as_ptr()))
+        })
+    }
+
+    /// Returns the 1-based column offset on the line for the function call or 0
+    /// if unknown.
+    pub fn get_column(&self) -> Result<i32> {
+        try_c!(self, get_column, { Ok(get_column(self.as_ptr())) })
+    }
+
+    /// Returns true (1) if the function was compiled using eval().
+    pub fn is_eval(&self) -> Result<bool> {
+        try_c!(self, is_eval, { Ok(is_eval(self.as_ptr()) != 0) })
+    }
+
+    /// Returns true (1) if the function was called as a constructor via "new".
+    pub fn is_constructor(&self) -> Result<bool> {
+        try_c!(self, is_constructor, {
+            Ok(is_constructor(self.as_ptr()) != 0)
+        })
+    }
+}
@@ -0,0 +1,704 @@
+use crate::{
+    ref_counted_ptr, try_c, CefStr, CefString, CefStringList, CefTime, Result,
+    V8ArrayBufferReleaseCallback, V8Context, V8Exception, V8Handler
+};
+use anyhow::anyhow;
+use cef_ui_sys::{
+    cef_v8_propertyattribute_t, cef_v8value_create_array, cef_v8value_create_array_buffer,
+    cef_v8value_create_bool, cef_v8value_create_date, cef_v8value_create_double,
+    cef_v8value_create_function, cef_v8value_create_int, cef_v8value_create_null,
+    cef_v8value_create_object, cef_v8value_create_promise, cef_v8value_create_string,
+    cef_v8value_create_uint, cef_v8value_create_undefined, cef_v8value_t
+};
+use std::{
+    ffi::{c_int, c_void},
+    ptr::null_mut,
+    slice::from_raw_parts
+};
+
+/// V8 property attribute values.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum V8PropertyAttribute {
+    /// Writeable, Enumerable, Configurable
+    None,
+
+    /// Not writeable
+    ReadOnly,
+
+    /// Not enumerable
+    DontEnum,
+
+    /// Not configurable
+    DontDelete
+}
+
+impl From<cef_v8_propertyattribute_t> for V8PropertyAttribute {
+    fn from(value: cef_v8_propertyattribute_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_v8_propertyattribute_t> for V8PropertyAttribute {
+    fn from(value: &cef_v8_propertyattribute_t) -> Self {
+        match value {
+            cef_v8_propertyattribute_t::V8_PROPERTY_ATTRIBUTE_NONE => Self::None,
+            cef_v8_propertyattribute_t::V8_PROPERTY_ATTRIBUTE_READONLY => Self::ReadOnly,
+            cef_v8_propertyattribute_t::V8_PROPERTY_ATTRIBUTE_DONTENUM => Self::DontEnum,
+            cef_v8_propertyattribute_t::V8_PROPERTY_ATTRIBUTE_DONTDELETE => Self::DontDelete
+        }
+    }
+}
+
+impl From<V8PropertyAttribute> for cef_v8_propertyattribute_t {
+    fn from(value: V8PropertyAttribute) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&V8PropertyAttribute> for cef_v8_propertyattribute_t {
+    fn from(value: &V8PropertyAttribute) -> Self {
+        match value {
+            V8PropertyAttribute::None => Self::V8_PROPERTY_ATTRIBUTE_NONE,
+            V8PropertyAttribute::ReadOnly => Self::V8_PROPERTY_ATTRIBUTE_READONLY,
+            V8PropertyAttribute::DontEnum => Self::V8_PROPERTY_ATTRIBUTE_DONTENUM,
+            V8PropertyAttribute::DontDelete => Self::V8_PROPERTY_ATTRIBUTE_DONTDELETE
+        }
+    }
+}
+
+// Structure representing a V8 value handle. V8 handles can only be accessed
+// from the thread on which they are created. Valid threads for creating a V8
+// handle include the render process main thread (TID_RENDERER) and WebWorker
+// threads. A task runner for posting tasks on the associated thread can be
+// retrieved via the cef_v8context_t::get_task_runner() function.
+ref_counted_ptr!(V8Value, cef_v8value_t);
+
+impl V8Value {
+    /// Create a new cef_v8value_t object of type undefined.
+    pub fn new_undefined() -> Self {
+        unsafe { Self::from_ptr_unchecked(cef_v8value_create_undefined()) }
+    }
+
+    /// Create a new cef_v8value_t object of type null.
+    pub fn new_null() -> Self {
+        unsafe { Self::from_ptr_unchecked(cef_v8value_create_null()) }
+    }
+
+    /// Create a new cef_v8value_t object of type bool.
+    pub fn new_bool(value: bool) -> Self {
+        unsafe { Self::from_ptr_unchecked(cef_v8value_create_bool(value as c_int)) }
+    }
+
+    /// Create a new cef_v8value_t object of type int.
+    pub fn new_int(value: i32) -> Self {
+        unsafe { Self::from_ptr_unchecked(cef_v8value_create_int(value)) }
+    }
+
+    /// Create a new cef_v8value_t object of type unsigned int.
+    pub fn new_uint(value: u32) -> Self {
+        unsafe { Self::from_ptr_unchecked(cef_v8value_create_uint(value)) }
+    }
+
+    /// Create a new cef_v8value_t object of type double.
+    pub fn new_double(value: f64) -> Self {
+        unsafe { Self::from_ptr_unchecked(cef_v8value_create_double(value)) }
+    }
+
+    /// Create a new cef_v8value_t object of type Date. This function should only
+    /// be called from within the scope of a cef_render_process_handler_t,
+    /// cef_v8handler_t or cef_v8accessor_t callback, or in combination with
+    /// calling enter() and exit() on a stored cef_v8context_t reference.
+    pub fn new_date(date: &CefTime) -> Result<Self> {
+        unsafe { Self::created("Date", cef_v8value_create_date(date.into())) }
+    }
+
+    /// Create a new cef_v8value_t object of type string.
+    pub fn new_string(value: &str) -> Result<Self> {
+        let value = CefString::new(value);
+
+        unsafe { Self::created("string", cef_v8value_create_string(value.as_ptr())) }
+    }
+
+    /// Same as new_string, but borrows |value| instead of converting it.
+    pub(crate) fn new_cef_string(value: CefStr<'_>) -> Result<Self> {
+        let value = value.as_raw();
+
+        unsafe { Self::created("string", cef_v8value_create_string(&value)) }
+    }
+
+    /// Same as new_function, but borrows |name| instead of converting it.
+    pub(crate) fn new_cef_function(name: CefStr<'_>, handler: V8Handler) -> Result<Self> {
+        let name = name.as_raw();
+
+        unsafe {
+            Self::created(
+                "function",
+                cef_v8value_create_function(&name, handler.into_raw())
+            )
+        }
+    }
+
+    /// Create a new cef_v8value_t object of type object. This function should
+    /// only be called from within the scope of a cef_render_process_handler_t,
+    /// cef_v8handler_t or cef_v8accessor_t callback, or in combination with
+    /// calling enter() and exit() on a stored cef_v8context_t reference.
+    pub fn new_object() -> Result<Self> {
+        unsafe { Self::created("object", cef_v8value_create_object(null_mut(), null_mut())) }
+    }
+
+    /// Create a new cef_v8value_t object of type array with the specified
+    /// |length|. If |length| is negative the returned array will have length 0.
+    /// This function should only be called from within the scope of a
+    /// cef_render_process_handler_t, cef_v8handler_t or cef_v8accessor_t
+    /// callback, or in combination with calling enter() and exit() on a stored
+    /// cef_v8context_t reference.
+    pub fn new_array(length: usize) -> Result<Self> {
+        unsafe { Self::created("array", cef_v8value_create_array(length as c_int)) }
+    }
+
+    /// Create a new cef_v8value_t object of type function. This function should
+    /// only be called from within the scope of a cef_render_process_handler_t,
+    /// cef_v8handler_t or cef_v8accessor_t callback, or in combination with
+    /// calling enter() and exit() on a stored cef_v8context_t reference.
+    pub fn new_function(name: &str, handler: V8Handler) -> Result<Self> {
+        let name = CefString::new(name);
+
+        unsafe {
+            Self::created(
+                "function",
+                cef_v8value_create_function(name.as_ptr(), handler.into_raw())
+            )
+        }
+    }
+
+    /// Create a new cef_v8value_t object of type Promise. This function should
+    /// only be called from within the scope of a cef_render_process_handler_t,
+    /// cef_v8handler_t or cef_v8accessor_t callback, or in combination with
+    /// calling enter() and exit() on a stored cef_v8context_t reference.
+    pub fn new_promise() -> Result<Self> {
+        unsafe { Self::created("Promise", cef_v8value_create_promise()) }
+    }
+
+    /// Create a new cef_v8value_t object of type ArrayBuffer which wraps the
+    /// provided |buffer| of size |length| bytes. The ArrayBuffer is externalized,
+    /// meaning that it does not own |buffer|. The caller is responsible for
+    /// freeing |buffer| when requested via a call to
+    /// cef_v8array_buffer_release_callback_t::ReleaseBuffer. This function
+    /// should only be called from within the scope of a
+    /// cef_render_process_handler_t, cef_v8handler_t or cef_v8accessor_t
+    /// callback, or in combination with calling enter() and exit() on a stored
+    /// cef_v8context_t reference.
+    ///
+    /// # Safety
+    ///
+    /// |buffer| must point to |length| bytes that remain valid until
+    /// |release_callback| is called.
+    pub unsafe fn new_array_buffer_raw(
+        buffer: *mut c_void,
+        length: usize,
+        release_callback: V8ArrayBufferReleaseCallback
+    ) -> Result<Self> {
+        Self::created(
+            "ArrayBuffer",
+            cef_v8value_create_array_buffer(buffer, length, release_callback.into_raw())
+        )
+    }
+
+    /// Create a new cef_v8value_t object of type ArrayBuffer that wraps the
+    /// bytes of |buffer| without copying them. |buffer| is dropped when the
+    /// ArrayBuffer is garbage collected. See new_array_buffer_raw.
+    pub fn new_array_buffer<T: AsRef<[u8]> + Send + 'static>(buffer: T) -> Result<Self> {
+        // Boxing keeps the bytes at a stable address while the owner moves.
+        let buffer = Box::new(buffer);
+        let bytes = (*buffer).as_ref();
+        let (data, length) = (bytes.as_ptr() as *mut c_void, bytes.len());
+
+        unsafe {
+            Self::new_array_buffer_raw(data, length, V8ArrayBufferReleaseCallback::new(buffer))
+        }
+    }
+
+    /// Wraps a value returned by one of the cef_v8value_create functions, which
+    /// return NULL when called outside of a V8 context or on the wrong thread.
+    unsafe fn created(kind: &str, ptr: *mut cef_v8value_t) -> Result<Self> {
+        Self::from_ptr(ptr).ok_or_else(|| {
+            anyhow!("Failed to create a V8 {}; no V8 context is entered.", kind).into()
+        })
+    }
+
+    /// Returns true (1) if the underlying handle is valid and it can be accessed
+    /// on the current thread. Do not call any other functions if this function
+    /// returns false (0).
+    pub fn is_valid(&self) -> Result<bool> {
+        try_c!(self, is_valid, { Ok(is_valid(self.as_ptr()) != 0) })
+    }
+
+    /// True if the value type is undefined.
+    pub fn is_undefined(&self) -> Result<bool> {
+        try_c!(self, is_undefined, { Ok(is_undefined(self.as_ptr()) != 0) })
+    }
+
+    /// True if the value type is null.
+    pub fn is_null(&self) -> Result<bool> {
+        try_c!(self, is_null, { Ok(is_null(self.as_ptr()) != 0) })
+    }
+
+    /// True if the value type is bool.
+    pub fn is_bool(&self) -> Result<bool> {
+        try_c!(self, is_bool, { Ok(is_bool(self.as_ptr()) != 0) })
+    }
+
+    /// True if the value type is int.
+    pub fn is_int(&self) -> Result<bool> {
+        try_c!(self, is_int, { Ok(is_int(self.as_ptr()) != 0) })
+    }
+
+    /// True if the value type is unsigned int.
+    pub fn is_uint(&self) -> Result<bool> {
+        try_c!(self, is_uint, { Ok(is_uint(self.as_ptr()) != 0) })
+    }
+
+    /// True if the value type is double.
+    pub fn is_double(&self) -> Result<bool> {
+        try_c!(self, is_double, { Ok(is_double(self.as_ptr()) != 0) })
+    }
+
+    /// True if the value type is Date.
+    pub fn is_date(&self) -> Result<bool> {
+        try_c!(self, is_date, { Ok(is_date(self.as_ptr()) != 0) })
+    }
+
+    /// True if the value type is string.
+    pub fn is_string(&self) -> Result<bool> {
+        try_c!(self, is_string, { Ok(is_string(self.as_ptr()) != 0) })
+    }
+
+    /// True if the value type is object.
+    pub fn is_object(&self) -> Result<bool> {
+        try_c!(self, is_object, { Ok(is_object(self.as_ptr()) != 0) })
+    }
+
+    /// True if the value type is array.
+    pub fn is_array(&self) -> Result<bool> {
+        try_c!(self, is_array, { Ok(is_array(self.as_ptr()) != 0) })
+    }
+
+    /// True if the value type is an ArrayBuffer.
+    pub fn is_array_buffer(&self) -> Result<bool> {
+        try_c!(self, is_array_buffer, {
+            Ok(is_array_buffer(self.as_ptr()) != 0)
+        })
+    }
+
+    /// True if the value type is function.
+    pub fn is_function(&self) -> Result<bool> {
+        try_c!(self, is_function, { Ok(is_function(self.as_ptr()) != 0) })
+    }
+
+    /// True if the value type is a Promise.
+    pub fn is_promise(&self) -> Result<bool> {
+        try_c!(self, is_promise, { Ok(is_promise(self.as_ptr()) != 0) })
+    }
+
+    /// Returns true (1) if this object is pointing to the same handle as |that|
+    /// object.
+    pub fn is_same(&self, that: V8Value) -> Result<bool> {
+        try_c!(self, is_same, {
+            Ok(is_same(self.as_ptr(), that.into_raw()) != 0)
+        })
+    }
+
+    /// Return a bool value.
+    pub fn get_bool_value(&self) -> Result<bool> {
+        try_c!(self, get_bool_value, {
+            Ok(get_bool_value(self.as_ptr()) != 0)
+        })
+    }
+
+    /// Return an int value.
+    pub fn get_int_value(&self) -> Result<i32> {
+        try_c!(self, get_int_value, { Ok(get_int_value(self.as_ptr())) })
+    }
+
+    /// Return an unsigned int value.
+    pub fn get_uint_value(&self) -> Result<u32> {
+        try_c!(self, get_uint_value, { Ok(get_uint_value(self.as_ptr())) })
+    }
+
+    /// Return a double value.
+    pub fn get_double_value(&self) -> Result<f64> {
+        try_c!(self, get_double_value, {
+            Ok(get_double_value(self.as_ptr()))
+        })
+    }
+
+    /// Return a Date value.
+    pub fn get_date_value(&self) -> Result<Option<CefTime>> {
+        try_c!(self, get_date_value, {
+            Ok(CefTime::from_basetime(get_date_value(self.as_ptr())))
+        })
+    }
+
+    /// Return a string value.
+    pub fn get_string_value(&self) -> Result<String> {
+        try_c!(self, get_string_value, {
+            let s = get_string_value(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
+        })
+    }
+
+    /// Returns true (1) if this is a user created object.
+    pub fn is_user_created(&self) -> Result<bool> {
+        try_c!(self, is_user_created, {
+            Ok(is_user_created(self.as_ptr()) != 0)
+        })
+    }
+
+    /// Returns true (1) if the last function call resulted in an exception. This
+    /// attribute exists only in the scope of the current CEF value object.
+    pub fn has_exception(&self) -> Result<bool> {
+        try_c!(self, has_exception, {
+            Ok(has_exception(self.as_ptr()) != 0)
+        })
+    }
+
+    /// Returns the exception resulting from the last function call. This
+    /// attribute exists only in the scope of the current CEF value object.
+    pub fn get_exception(&self) -> Result<Option<V8Exception>> {
+        try_c!(self, get_exception, {
+            Ok(V8Exception::from_ptr(get_exception(self.as_ptr())))
+        })
+    }
+
+    /// Clears the last exception and returns true (1) on success.
+    pub fn clear_exception(&self) -> Result<bool> {
+        try_c!(self, clear_exception, {
+            Ok(clear_exception(self.as_ptr()) != 0)
+        })
+    }
+
+    /// Returns true (1) if this object will re-throw future exceptions. This
+    /// attribute exists only in the scope of the current CEF value object.
+    pub fn will_rethrow_exceptions(&self) -> Result<bool> {
+        try_c!(self, will_rethrow_exceptions, {
+            Ok(will_rethrow_exceptions(self.as_ptr()) != 0)
+        })
+    }
+
+    /// Set whether this object will re-throw future exceptions. By default
+    /// exceptions are not re-thrown. If a exception is re-thrown the current
+    /// context should not be accessed again until after the exception has been
+    /// caught and not re-thrown. Returns true (1) on success. This attribute
+    /// exists only in the scope of the current CEF value object.
+    pub fn set_rethrow_exceptions(&self, rethrow: bool) -> Result<bool> {
+        try_c!(self, set_rethrow_exceptions, {
+            Ok(set_rethrow_exceptions(self.as_ptr(), rethrow as c_int) != 0)
+        })
+    }
+
+    /// Returns true (1) if the object has a value with the specified identifier.
+    pub fn has_value_by_key(&self, key: &str) -> Result<bool> {
+        try_c!(self, has_value_bykey, {
+            let key = CefString::new(key);
+
+            Ok(has_value_bykey(self.as_ptr(), key.as_ptr()) != 0)
+        })
+    }
+
+    /// Returns true (1) if the object has a value with the specified identifier.
+    pub fn has_value_by_index(&self, index: usize) -> Result<bool> {
+        try_c!(self, has_value_byindex, {
+            Ok(has_value_byindex(self.as_ptr(), index as c_int) != 0)
+        })
+    }
+
+    /// Deletes the value with the specified identifier and returns true (1) on
+    /// success. Returns false (0) if this function is called incorrectly or an
+    /// exception is thrown. For read-only and don't-delete values this function
+    /// will return true (1) even though deletion failed.
+    pub fn delete_value_by_key(&self, key: &str) -> Result<bool> {
+        try_c!(self, delete_value_bykey, {
+            let key = CefString::new(key);
+
+            Ok(delete_value_bykey(self.as_ptr(), key.as_ptr()) != 0)
+        })
+    }
+
+    /// Deletes the value with the specified identifier and returns true (1) on
+    /// success. Returns false (0) if this function is called incorrectly,
+    /// deletion fails or an exception is thrown. For read-only and don't-delete
+    /// values this function will return true (1) even though deletion failed.
+    pub fn delete_value_by_index(&self, index: usize) -> Result<bool> {
+        try_c!(self, delete_value_byindex, {
+            Ok(delete_value_byindex(self.as_ptr(), index as c_int) != 0)
+        })
+    }
+
+    /// Returns the value with the specified identifier on success. Returns NULL
+    /// if this function is called incorrectly or an exception is thrown.
+    pub fn get_value_by_key(&self, key: &str) -> Result<Option<V8Value>> {
+        try_c!(self, get_value_bykey, {
+            let key = CefString::new(key);
+
+            Ok(V8Value::from_ptr(get_value_bykey(
+                self.as_ptr(),
+                key.as_ptr()
+            )))
+        })
+    }
+
+    /// Returns the value with the specified identifier on success. Returns NULL
+    /// if this function is called incorrectly or an exception is thrown.
+    pub fn get_value_by_index(&self, index: usize) -> Result<Option<V8Value>> {
+        try_c!(self, get_value_byindex, {
+            Ok(V8Value::from_ptr(get_value_byindex(
+                self.as_ptr(),
+                index as c_int
+            )))
+        })
+    }
+
+    /// Associates a value with the specified identifier and returns true (1) on
+    /// success. Returns false (0) if this function is called incorrectly or an
+    /// exception is thrown. For read-only values this function will return true
+    /// (1) even though assignment failed.
+    pub fn set_value_by_key(
+        &self,
+        key: &str,
+        value: V8Value,
+        attribute: V8PropertyAttribute
+    ) -> Result<bool> {
+        try_c!(self, set_value_bykey, {
+            let key = CefString::new(key);
+
+            Ok(set_value_bykey(
+                self.as_ptr(),
+                key.as_ptr(),
+                value.into_raw(),
+                attribute.into()
+            ) != 0)
+        })
+    }
+
+    /// Same as set_value_by_key, but borrows |key| instead of converting it.
+    pub(crate) fn set_value_by_cef_key(
+        &self,
+        key: CefStr<'_>,
+        value: V8Value,
+        attribute: V8PropertyAttribute
+    ) -> Result<bool> {
+        try_c!(self, set_value_bykey, {
+            let key = key.as_raw();
+
+            Ok(set_value_bykey(self.as_ptr(), &key, value.into_raw(), attribute.into()) != 0)
+        })
+    }
+
+    /// Associates a value with the specified identifier and returns true (1) on
+    /// success. Returns false (0) if this function is called incorrectly or an
+    /// exception is thrown. For read-only values this function will return true
+    /// (1) even though assignment failed.
+    pub fn set_value_by_index(&self, index: usize, value: V8Value) -> Result<bool> {
+        try_c!(self, set_value_byindex, {
+            Ok(set_value_byindex(self.as_ptr(), index as c_int, value.into_raw()) != 0)
+        })
+    }
+
+    /// Read the keys for the object's values into the specified vector. Integer-
+    /// based keys will also be returned as strings.
+    pub fn get_keys(&self) -> Result<Vec<String>> {
+        try_c!(self, get_keys, {
+            let mut list = CefStringList::new();
+
+            get_keys(self.as_ptr(), list.as_mut_ptr());
+
+            Ok(list.into())
+        })
+    }
+
+    /// Returns the amount of externally allocated memory registered for the
+    /// object.
+    pub fn get_externally_allocated_memory(&self) -> Result<i32> {
+        try_c!(self, get_externally_allocated_memory, {
+            Ok(get_externally_allocated_memory(self.as_ptr()))
+        })
+    }
+
+    /// Adjusts the amount of registered external memory for the object. Used to
+    /// give V8 an indication of the amount of externally allocated memory that
+    /// is kept alive by JavaScript objects. V8 uses this information to decide
+    /// when to perform global garbage collection. Each cef_v8value_t tracks the
+    /// amount of external memory associated with it and automatically decreases
+    /// the global total by the appropriate amount on its destruction.
+    /// |change_in_bytes| specifies the number of bytes to adjust by. This
+    /// function returns the number of bytes associated with the object after the
+    /// adjustment. This function can only be called on user created objects.
+    pub fn adjust_externally_allocated_memory(&self, change_in_bytes: i32) -> Result<i32> {
+        try_c!(self, adjust_externally_allocated_memory, {
+            Ok(adjust_externally_allocated_memory(
+                self.as_ptr(),
+                change_in_bytes
+            ))
+        })
+    }
+
+    /// Returns the number of elements in the array.
+    pub fn get_array_length(&self) -> Result<usize> {
+        try_c!(self, get_array_length, {
+            Ok(get_array_length(self.as_ptr()).max(0) as usize)
+        })
+    }
+
+    /// Prevent the ArrayBuffer from using it's memory block by setting the length
+    /// to zero. This operation cannot be undone. If the ArrayBuffer was created
+    /// with CreateArrayBuffer then
+    /// cef_v8array_buffer_release_callback_t::ReleaseBuffer will be called to
+    /// release the underlying buffer.
+    pub fn neuter_array_buffer(&self) -> Result<bool> {
+        try_c!(self, neuter_array_buffer, {
+            Ok(neuter_array_buffer(self.as_ptr()) != 0)
+        })
+    }
+
+    /// Returns the length (in bytes) of the ArrayBuffer.
+    pub fn get_array_buffer_byte_length(&self) -> Result<usize> {
+        try_c!(self, get_array_buffer_byte_length, {
+            Ok(get_array_buffer_byte_length(self.as_ptr()))
+        })
+    }
+
+    /// Returns a pointer to the beginning of the memory block for this
+    /// ArrayBuffer backing store. The returned pointer is valid as long as the
+    /// cef_v8value_t is alive.
+    pub fn get_array_buffer_data(&self) -> Result<*mut c_void> {
+        try_c!(self, get_array_buffer_data, {
+            Ok(get_array_buffer_data(self.as_ptr()))
+        })
+    }
+
+    /// Returns a copy of the bytes of the ArrayBuffer.
+    pub fn get_array_buffer_bytes(&self) -> Result<Vec<u8>> {
+        let length = self.get_array_buffer_byte_length()?;
+        let data = self.get_array_buffer_data()?;
+
+        match data.is_null() || length == 0 {
+            true => Ok(Vec::new()),
+            false => Ok(unsafe { from_raw_parts(data as *const u8, length) }.to_vec())
+        }
+    }
+
+    /// Returns the function name.
+    pub fn get_function_name(&self) -> Result<String> {
+        try_c!(self, get_function_name, {
+            let s = get_function_name(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
+        })
+    }
+
+    /// Returns the function handler or NULL if not a CEF-created function.
+    pub fn get_function_handler(&self) -> Result<Option<V8Handler>> {
+        try_c!(self, get_function_handler, {
+            Ok(V8Handler::from_ptr(get_function_handler(self.as_ptr())))
+        })
+    }
+
+    /// Execute the function using the current V8 context. This function should
+    /// only be called from within the scope of a cef_v8handler_t or
+    /// cef_v8accessor_t callback, or in combination with calling enter() and
+    /// exit() on a stored cef_v8context_t reference. |object| is the receiver
+    /// ('this' object) of the function. If |object| is None the current
+    /// context's global object will be used. |arguments| is the list of
+    /// arguments that will be passed to the function. Returns the function
+    /// return value on success. Returns NULL if this function is called
+    /// incorrectly or an exception is thrown.
+    pub fn execute_function(
+        &self,
+        object: Option<V8Value>,
+        arguments: &[V8Value]
+    ) -> Result<Option<V8Value>> {
+        try_c!(self, execute_function, {
+            let object = object
+                .map(|object| object.into_raw())
+                .unwrap_or_else(null_mut);
+            let arguments = arguments
+                .iter()
+                .map(|argument| argument.clone().into_raw())
+                .collect::<Vec<_>>();
+
+            Ok(V8Value::from_ptr(execute_function(
+                self.as_ptr(),
+                object,
+                arguments.len(),
+                arguments.as_ptr()
+            )))
+        })
+    }
+
+    /// Execute the function using the specified V8 context. |object| is the
+    /// receiver ('this' object) of the function. If |object| is None the
+    /// specified context's global object will be used. |arguments| is the list
+    /// of arguments that will be passed to the function. Returns the function
+    /// return value on success. Returns NULL if this function is called
+    /// incorrectly or an exception is thrown.
+    pub fn execute_function_with_context(
+        &self,
+        context: V8Context,
+        object: Option<V8Value>,
+        arguments: &[V8Value]
+    ) -> Result<Option<V8Value>> {
+        try_c!(self, execute_function_with_context, {
+            let object = object
+                .map(|object| object.into_raw())
+                .unwrap_or_else(null_mut);
+            let arguments = arguments
+                .iter()
+                .map(|argument| argument.clone().into_raw())
+                .collect::<Vec<_>>();
+
+            Ok(V8Value::from_ptr(execute_function_with_context(
+                self.as_ptr(),
+                context.into_raw(),
+                object,
+                arguments.len(),
+                arguments.as_ptr()
+            )))
+        })
+    }
+
+    /// Resolve the Promise using the current V8 context. This function should
+    /// only be called from within the scope of a cef_v8handler_t or
+    /// cef_v8accessor_t callback, or in combination with calling enter() and
+    /// exit() on a stored cef_v8context_t reference. |arg| is the argument
+    /// passed to the resolved promise. Returns true (1) on success. Returns
+    /// false (0) if this function is called incorrectly or an exception is
+    /// thrown.
+    pub fn resolve_promise(&self, arg: Option<V8Value>) -> Result<bool> {
+        try_c!(self, resolve_promise, {
+            let arg = arg
+                .map(|arg| arg.into_raw())
+                .unwrap_or_else(null_mut);
+
+            Ok(resolve_promise(self.as_ptr(), arg) != 0)
+        })
+    }
+
+    /// Reject the Promise using the current V8 context. This function should
+    /// only be called from within the scope of a cef_v8handler_t or
+    /// cef_v8accessor_t callback, or in combination with calling enter() and
+    /// exit() on a stored cef_v8context_t reference. Returns true (1) on
+    /// success. Returns false (0) if this function is called incorrectly or an
+    /// exception is thrown.
+    pub fn reject_promise(&self, error_msg: &str) -> Result<bool> {
+        try_c!(self, reject_promise, {
+            let error_msg = CefString::new(error_msg);
+
+            Ok(reject_promise(self.as_ptr(), error_msg.as_ptr()) != 0)
+        })
+    }
+}
@@ -0,0 +1,138 @@
+use crate::Result;
+use anyhow::anyhow;
+use cef_ui_sys::{
+    cef_api_hash, cef_version_info, CEF_COMMIT_HASH, CEF_COMMIT_NUMBER, CEF_VERSION_MAJOR,
+    CEF_VERSION_MINOR, CEF_VERSION_PATCH, CHROME_VERSION_BUILD, CHROME_VERSION_MAJOR,
+    CHROME_VERSION_MINOR, CHROME_VERSION_PATCH
+};
+use std::{
+    ffi::{c_int, CStr},
+    fmt::{Display, Formatter}
+};
+
+/// Which API hash to retrieve from the loaded libcef library.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ApiHash {
+    /// CEF_API_HASH_PLATFORM.
+    Platform,
+
+    /// CEF_API_HASH_UNIVERSAL.
+    Universal,
+
+    /// CEF_COMMIT_HASH (from cef_version.h).
+    Commit
+}
+
+/// Returns the requested API hash of the loaded libcef library.
+pub fn api_hash(entry: ApiHash) -> Option<String> {
+    let entry: c_int = match entry {
+        ApiHash::Platform => 0,
+        ApiHash::Universal => 1,
+        ApiHash::Commit => 2
+    };
+
+    unsafe {
+        let hash = cef_api_hash(entry);
+
+        match hash.is_null() {
+            true => None,
+            false => Some(
+                CStr::from_ptr(hash)
+                    .to_string_lossy()
+                    .into_owned()
+            )
+        }
+    }
+}
+
+/// CEF and Chromium version numbers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VersionInfo {
+    pub cef_major:         u32,
+    pub cef_minor:         u32,
+    pub cef_patch:         u32,
+    pub cef_commit_number: u32,
+    pub chrome_major:      u32,
+    pub chrome_minor:      u32,
+    pub chrome_build:      u32,
+    pub chrome_patch:      u32
+}
+
+impl VersionInfo {
+    /// The version these bindings were generated against.
+    pub fn bindings() -> Self {
+        Self {
+            cef_major:         CEF_VERSION_MAJOR,
+            cef_minor:         CEF_VERSION_MINOR,
+            cef_patch:         CEF_VERSION_PATCH,
+            cef_commit_number: CEF_COMMIT_NUMBER,
+            chrome_major:      CHROME_VERSION_MAJOR,
+            chrome_minor:      CHROME_VERSION_MINOR,
+            chrome_build:      CHROME_VERSION_BUILD,
+            chrome_patch:      CHROME_VERSION_PATCH
+        }
+    }
+
+    /// The version of the loaded libcef library.
+    pub fn runtime() -> Self {
+        let entry = |entry: c_int| unsafe { cef_version_info(entry) as u32 };
+
+        Self {
+            cef_major:         entry(0),
+            cef_minor:         entry(1),
+            cef_patch:         entry(2),
+            cef_commit_number: entry(3),
+            chrome_major:      entry(4),
+            chrome_minor:      entry(5),
+            chrome_build:      entry(6),
+            chrome_patch:      entry(7)
+        }
+    }
+}
+
+impl Display for VersionInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}.{} (commit {}) / Chromium {}.{}.{}.{}",
+            self.cef_major,
+            self.cef_minor,
+            self.cef_patch,
+            self.cef_commit_number,
+            self.chrome_major,
+            self.chrome_minor,
+            self.chrome_build,
+            self.chrome_patch
+        )
+    }
+}
+
+/// Checks that the loaded libcef library matches the version and commit that
+/// these bindings were generated against. A mismatch means the structure
+/// layouts may differ, which would otherwise surface as memory corruption or
+/// crashes deep inside CEF.
+pub fn verify_version() -> Result<()> {
+    let bindings = VersionInfo::bindings();
+    let runtime = VersionInfo::runtime();
+
+    if bindings != runtime {
+        return Err(anyhow!(
+            "The loaded libcef version {} does not match the bindings version {}.",
+            runtime,
+            bindings
+        )
+        .into());
+    }
+
+    let expected = String::from_utf8_lossy(&CEF_COMMIT_HASH[..CEF_COMMIT_HASH.len() - 1]);
+
+    match api_hash(ApiHash::Commit) {
+        Some(hash) if hash == expected => Ok(()),
+        hash => Err(anyhow!(
+            "The loaded libcef commit hash {} does not match the bindings commit hash {}.",
+            hash.unwrap_or_else(|| String::from("<none>")),
+            expected
+        )
+        .into())
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{
+    debug_assert_currently_on, ref_counted_ptr, try_c, unwind::catch_unwind_or, view_delegate_base,
+    CefString, LabelButton, RefCountedPtr, Result, ThreadId, View, ViewDelegateCallbacks,
+    ViewDelegateWrappable, Wrappable, Wrapped
+};
+use cef_ui_sys::{cef_button_delegate_t, cef_button_state_t, cef_button_t, cef_view_t};
+use std::ffi::c_int;
+
+/// Specifies the button display state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ButtonState {
+    Normal,
+    Hovered,
+    Pressed,
+    Disabled
+}
+
+impl From<cef_button_state_t> for ButtonState {
+    fn from(value: cef_button_state_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_button_state_t> for ButtonState {
+    fn from(value: &cef_button_state_t) -> Self {
+        match value {
+            cef_button_state_t::CEF_BUTTON_STATE_NORMAL => ButtonState::Normal,
+            cef_button_state_t::CEF_BUTTON_STATE_HOVERED => ButtonState::Hovered,
+            cef_button_state_t::CEF_BUTTON_STATE_PRESSED => ButtonState::Pressed,
+            cef_button_state_t::CEF_BUTTON_STATE_DISABLED => ButtonState::Disabled
+        }
+    }
+}
+
+impl From<ButtonState> for cef_button_state_t {
+    fn from(value: ButtonState) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&ButtonState> for cef_button_state_t {
+    fn from(value: &ButtonState) -> Self {
+        match value {
+            ButtonState::Normal => cef_button_state_t::CEF_BUTTON_STATE_NORMAL,
+            ButtonState::Hovered => cef_button_state_t::CEF_BUTTON_STATE_HOVERED,
+            ButtonState::Pressed => cef_button_state_t::CEF_BUTTON_STATE_PRESSED,
+            ButtonState::Disabled => cef_button_state_t::CEF_BUTTON_STATE_DISABLED
+        }
+    }
+}
+
+// A View representing a button. Depending on the specific type, the button
+// could be implemented by a native control or custom rendered. Methods must be
+// called on the browser process UI thread unless otherwise indicated.
+ref_counted_ptr!(Button, cef_button_t);
+view_deref!(Button, View);
+
+impl Button {
+    /// Returns this Button as a View.
+    pub fn as_view(&self) -> View {
+        unsafe { View::from_ptr_unchecked(self.clone().into_raw() as *mut cef_view_t) }
+    }
+
+    /// Returns this Button as a LabelButton or NULL if this is not a LabelButton.
+    pub fn as_label_button(&self) -> Result<Option<LabelButton>> {
+        try_c!(self, as_label_button, {
+            Ok(LabelButton::from_ptr(as_label_button(self.as_ptr())))
+        })
+    }
+
+    /// Sets the current display state of the Button.
+    pub fn set_state(&self, state: ButtonState) -> Result<()> {
+        try_c!(self, set_state, {
+            Ok(set_state(self.as_ptr(), state.into()))
+        })
+    }
+
+    /// Returns the current display state of the Button.
+    pub fn get_state(&self) -> Result<ButtonState> {
+        try_c!(self, get_state, { Ok(get_state(self.as_ptr()).into()) })
+    }
+
+    /// Sets the Button will use an ink drop effect for displaying state changes.
+    pub fn set_ink_drop_enabled(&self, enabled: bool) -> Result<()> {
+        try_c!(self, set_ink_drop_enabled, {
+            Ok(set_ink_drop_enabled(self.as_ptr(), enabled as c_int))
+        })
+    }
+
+    /// Sets the tooltip text that will be displayed when the user hovers the
+    /// mouse cursor over the Button.
+    pub fn set_tooltip_text(&self, tooltip_text: &str) -> Result<()> {
+        try_c!(self, set_tooltip_text, {
+            let tooltip_text = CefString::new(tooltip_text);
+
+            Ok(set_tooltip_text(self.as_ptr(), tooltip_text.as_ptr()))
+        })
+    }
+
+    /// Sets the accessible name that will be exposed to assistive technology
+    /// (AT).
+    pub fn set_accessible_name(&self, name: &str) -> Result<()> {
+        try_c!(self, set_accessible_name, {
+            let name = CefString::new(name);
+
+            Ok(set_accessible_name(self.as_ptr(), name.as_ptr()))
+        })
+    }
+}
+
+/// Implement this structure to handle Button events. The functions of this
+/// structure will be called on the browser process UI thread unless otherwise
+/// indicated.
+pub trait ButtonDelegateCallbacks: ViewDelegateCallbacks {
+    /// Called when |button| is pressed.
+    fn on_button_pressed(&mut self, _button: Button) {}
+
+    /// Called when the state of |button| changes.
+    fn on_button_state_changed(&mut self, _button: Button) {}
+}
+
+// Implement this structure to handle Button events. The functions of this
+// structure will be called on the browser process UI thread unless otherwise
+// indicated.
+ref_counted_ptr!(ButtonDelegate, cef_button_delegate_t);
+
+impl ButtonDelegate {
+    pub fn new<C: ButtonDelegateCallbacks>(delegate: C) -> Self {
+        Self(ButtonDelegateWrapper::new(delegate).wrap())
+    }
+}
+
+/// Translates CEF -> Rust callbacks.
+struct ButtonDelegateWrapper<C: ButtonDelegateCallbacks>(C);
+
+impl<C: ButtonDelegateCallbacks> ButtonDelegateWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
+    }
+
+    /// Called when |button| is pressed.
+    unsafe extern "C" fn c_on_button_pressed(
+        this: *mut cef_button_delegate_t,
+        button: *mut cef_button_t
+    ) {
+        catch_unwind_or("ButtonDelegateWrapper::c_on_button_pressed", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this);
+            let button = Button::from_ptr_unchecked(button);
+
+            this.0.on_button_pressed(button);
+        })
+    }
+
+    /// Called when the state of |button| changes.
+    unsafe extern "C" fn c_on_button_state_changed(
+        this: *mut cef_button_delegate_t,
+        button: *mut cef_button_t
+    ) {
+        catch_unwind_or(
+            "ButtonDelegateWrapper::c_on_button_state_changed",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let button = Button::from_ptr_unchecked(button);
+
+                this.0
+                    .on_button_state_changed(button);
+            }
+        )
+    }
+}
+
+impl<C: ButtonDelegateCallbacks> ViewDelegateWrappable for ButtonDelegateWrapper<C> {
+    type Callbacks = C;
+
+    fn view_delegate(&mut self) -> &mut Self::Callbacks {
+        &mut self.0
+    }
+}
+
+impl<C: ButtonDelegateCallbacks> Wrappable for ButtonDelegateWrapper<C> {
+    type Cef = cef_button_delegate_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<cef_button_delegate_t> {
+        RefCountedPtr::wrap(
+            cef_button_delegate_t {
+                base: view_delegate_base::<Self>(),
+
+                on_button_pressed:       Some(Self::c_on_button_pressed),
+                on_button_state_changed: Some(Self::c_on_button_state_changed)
+            },
+            self
+        )
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{ref_counted_ptr, try_c, Point, Rect, Result};
+use cef_ui_sys::{
+    cef_display_convert_screen_point_from_pixels, cef_display_convert_screen_point_to_pixels,
+    cef_display_convert_screen_rect_from_pixels, cef_display_convert_screen_rect_to_pixels,
+    cef_display_get_alls, cef_display_get_count, cef_display_get_matching_bounds,
+    cef_display_get_nearest_point, cef_display_get_primary, cef_display_t
+};
+use std::{ffi::c_int, ptr::null_mut};
+
+// This structure typically, but not always, corresponds to a physical display
+// connected to the system. A fake Display may exist on a headless system, or a
+// Display may correspond to a remote, virtual display. All size and position
+// values are in density independent pixel (DIP) coordinates unless otherwise
+// indicated. Methods must be called on the browser process UI thread unless
+// otherwise indicated.
+//
+// For details on coordinate systems and usage see
+// https://bitbucket.org/chromiumembedded/cef/wiki/GeneralUsage#markdown-header-
+// coordinate-systems.
+ref_counted_ptr!(Display, cef_display_t);
+
+impl Display {
+    /// Returns the primary Display.
+    pub fn get_primary() -> Option<Self> {
+        unsafe { Self::from_ptr(cef_display_get_primary()) }
+    }
+
+    /// Returns the Display nearest |point|. Set |input_pixel_coords| to true (1)
+    /// if |point| is in pixel screen coordinates instead of DIP screen
+    /// coordinates.
+    pub fn get_nearest_point(point: &Point, input_pixel_coords: bool) -> Option<Self> {
+        unsafe {
+            let point = point.into();
+
+            Self::from_ptr(cef_display_get_nearest_point(
+                &point,
+                input_pixel_coords as c_int
+            ))
+        }
+    }
+
+    /// Returns the Display that most closely intersects |bounds|.  Set
+    /// |input_pixel_coords| to true (1) if |bounds| is in pixel screen
+    /// coordinates instead of DIP screen coordinates.
+    pub fn get_matching_bounds(bounds: &Rect, input_pixel_coords: bool) -> Option<Self> {
+        unsafe {
+            let bounds = bounds.into();
+
+            Self::from_ptr(cef_display_get_matching_bounds(
+                &bounds,
+                input_pixel_coords as c_int
+            ))
+        }
+    }
+
+    /// Returns the total number of Displays. Mirrored displays are excluded; this
+    /// function is intended to return the number of distinct, usable displays.
+    pub fn get_count() -> usize {
+        unsafe { cef_display_get_count() }
+    }
+
+    /// Returns all Displays. Mirrored displays are excluded; this function is
+    /// intended to return distinct, usable displays.
+    pub fn get_all() -> Vec<Self> {
+        unsafe {
+            let mut count = cef_display_get_count();
+            let mut displays: Vec<*mut cef_display_t> = vec![null_mut(); count];
+
+            cef_display_get_alls(&mut count, displays.as_mut_ptr());
+
+            displays
+                .into_iter()
+                .take(count)
+                .filter_map(|display| Self::from_ptr(display))
+                .collect()
+        }
+    }
+
+    /// Convert |point| from DIP screen coordinates to pixel screen coordinates.
+    /// This function is only used on Windows.
+    pub fn convert_screen_point_to_pixels(point: &Point) -> Point {
+        unsafe {
+            let point = point.into();
+
+            cef_display_convert_screen_point_to_pixels(&point).into()
+        }
+    }
+
+    /// Convert |point| from pixel screen coordinates to DIP screen coordinates.
+    /// This function is only used on Windows.
+    pub fn convert_screen_point_from_pixels(point: &Point) -> Point {
+        unsafe {
+            let point = point.into();
+
+            cef_display_convert_screen_point_from_pixels(&point).into()
+        }
+    }
+
+    /// Convert |rect| from DIP screen coordinates to pixel screen coordinates.
+    /// This function is only used on Windows.
+    pub fn convert_screen_rect_to_pixels(rect: &Rect) -> Rect {
+        unsafe {
+            let rect = rect.into();
+
+            cef_display_convert_screen_rect_to_pixels(&rect).into()
+        }
+    }
+
+    /// Convert |rect| from pixel screen coordinates to DIP screen coordinates.
+    /// This function is only used on Windows.
+    pub fn convert_screen_rect_from_pixels(rect: &Rect) -> Rect {
+        unsafe {
+            let rect = rect.into();
+
+            cef_display_convert_screen_rect_from_pixels(&rect).into()
+        }
+    }
+
+    /// Returns the unique identifier for this Display.
+    pub fn get_id(&self) -> Result<i64> {
+        try_c!(self, get_id, { Ok(get_id(self.as_ptr())) })
+    }
+
+    /// Returns this Display's device pixel scale factor. This specifies how much
+    /// the UI should be scaled when the actual output has more pixels than
+    /// standard displays (which is around 100~120dpi). The potential return
+    /// values differ by platform.
+    pub fn get_device_scale_factor(&self) -> Result<f32> {
+        try_c!(self, get_device_scale_factor, {
+            Ok(get_device_scale_factor(self.as_ptr()))
+        })
+    }
+
+    /// Convert |point| from density independent pixels (DIP) to pixel
+    /// coordinates using this Display's device scale factor.
+    pub fn convert_point_to_pixels(&self, point: &Point) -> Result<Point> {
+        try_c!(self, convert_point_to_pixels, {
+            let mut point = point.into();
+
+            convert_point_to_pixels(self.as_ptr(), &mut point);
+
+            Ok(point.into())
+        })
+    }
+
+    /// Convert |point| from pixel coordinates to density independent pixels
+    /// (DIP) using this Display's device scale factor.
+    pub fn convert_point_from_pixels(&self, point: &Point) -> Result<Point> {
+        try_c!(self, convert_point_from_pixels, {
+            let mut point = point.into();
+
+            convert_point_from_pixels(self.as_ptr(), &mut point);
+
+            Ok(point.into())
+        })
+    }
+
+    /// Returns this Display's bounds in DIP screen coordinates. This is the full
+    /// size of the display.
+    pub fn get_bounds(&self) -> Result<Rect> {
+        try_c!(self, get_bounds, { Ok(get_bounds(self.as_ptr()).into()) })
+    }
+
+    /// Returns this Display's work area in DIP screen coordinates. This excludes
+    /// areas of the display that are occupied with window manager toolbars, etc.
+    pub fn get_work_area(&self) -> Result<Rect> {
+        try_c!(self, get_work_area, {
+            Ok(get_work_area(self.as_ptr()).into())
+        })
+    }
+
+    /// Returns this Display's rotation in degrees.
+    pub fn get_rotation(&self) -> Result<i32> {
+        try_c!(self, get_rotation, { Ok(get_rotation(self.as_ptr())) })
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{
+    ref_counted_ptr, try_c, Button, ButtonDelegate, ButtonState, CefString, Color,
+    HorizontalAlignment, Image, MenuButton, Result, Size, View
+};
+use cef_ui_sys::{cef_button_t, cef_label_button_create, cef_label_button_t, cef_view_t};
+use std::ptr::null_mut;
+
+// LabelButton is a button with optional text and/or icon. Methods must be
+// called on the browser process UI thread unless otherwise indicated.
+ref_counted_ptr!(LabelButton, cef_label_button_t);
+view_deref!(LabelButton, Button);
+
+impl LabelButton {
+    /// Create a new LabelButton. A |delegate| must be provided to handle the
+    /// button click. |text| will be shown on the LabelButton and used as the
+    /// default accessible name.
+    pub fn create(delegate: ButtonDelegate, text: &str) -> Option<Self> {
+        unsafe {
+            let text = CefString::new(text);
+
+            Self::from_ptr(cef_label_button_create(delegate.into_raw(), text.as_ptr()))
+        }
+    }
+
+    /// Returns this LabelButton as a Button.
+    pub fn as_button(&self) -> Button {
+        unsafe { Button::from_ptr_unchecked(self.clone().into_raw() as *mut cef_button_t) }
+    }
+
+    /// Returns this LabelButton as a View.
+    pub fn as_view(&self) -> View {
+        unsafe { View::from_ptr_unchecked(self.clone().into_raw() as *mut cef_view_t) }
+    }
+
+    /// Returns this LabelButton as a MenuButton or NULL if this is not a
+    /// MenuButton.
+    pub fn as_menu_button(&self) -> Result<Option<MenuButton>> {
+        try_c!(self, as_menu_button, {
+            Ok(MenuButton::from_ptr(as_menu_button(self.as_ptr())))
+        })
+    }
+
+    /// Sets the text shown on the LabelButton. By default |text| will also be
+    /// used as the accessible name.
+    pub fn set_text(&self, text: &str) -> Result<()> {
+        try_c!(self, set_text, {
+            let text = CefString::new(text);
+
+            Ok(set_text(self.as_ptr(), text.as_ptr()))
+        })
+    }
+
+    /// Returns the text shown on the LabelButton.
+    pub fn get_text(&self) -> Result<String> {
+        try_c!(self, get_text, {
+            let s = get_text(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s)
+                .map(|s| s.into())
+                .unwrap_or_default())
+        })
+    }
+
+    /// Sets the image shown for |button_state|. When this Button is drawn if no
+    /// image exists for the current state then the image for
+    /// CEF_BUTTON_STATE_NORMAL, if any, will be shown.
+    pub fn set_image(&self, button_state: ButtonState, image: Option<Image>) -> Result<()> {
+        try_c!(self, set_image, {
+            let image = image
+                .map(|image| image.into_raw())
+                .unwrap_or_else(null_mut);
+
+            Ok(set_image(self.as_ptr(), button_state.into(), image))
+        })
+    }
+
+    /// Returns the image shown for |button_state|. If no image exists for that
+    /// state then the image for CEF_BUTTON_STATE_NORMAL will be returned.
+    pub fn get_image(&self, button_state: ButtonState) -> Result<Option<Image>> {
+        try_c!(self, get_image, {
+            Ok(Image::from_ptr(get_image(
+                self.as_ptr(),
+                button_state.into()
+            )))
+        })
+    }
+
+    /// Sets the text color shown for the specified button |for_state| to
+    /// |color|.
+    pub fn set_text_color(&self, for_state: ButtonState, color: Color) -> Result<()> {
+        try_c!(self, set_text_color, {
+            Ok(set_text_color(
+                self.as_ptr(),
+                for_state.into(),
+                color.into()
+            ))
+        })
+    }
+
+    /// Sets the text colors shown for the non-disabled states to |color|.
+    pub fn set_enabled_text_colors(&self, color: Color) -> Result<()> {
+        try_c!(self, set_enabled_text_colors, {
+            Ok(set_enabled_text_colors(self.as_ptr(), color.into()))
+        })
+    }
+
+    /// Sets the font list. The format is "<FONT_FAMILY_LIST>,[STYLES] <SIZE>",
+    /// where:
+    /// - FONT_FAMILY_LIST is a comma-separated list of font family names,
+    /// - STYLES is an optional space-separated list of style names (case-
+    ///   sensitive "Bold" and "Italic" are supported), and
+    /// - SIZE is an integer font size in pixels with the suffix "px".
+    ///
+    /// Here are examples of valid font description strings:
+    /// - "Arial, Helvetica, Bold Italic 14px"
+    /// - "Arial, 14px"
+    pub fn set_font_list(&self, font_list: &str) -> Result<()> {
+        try_c!(self, set_font_list, {
+            let font_list = CefString::new(font_list);
+
+            Ok(set_font_list(self.as_ptr(), font_list.as_ptr()))
+        })
+    }
+
+    /// Sets the horizontal alignment; reversed in RTL. Default is
+    /// CEF_HORIZONTAL_ALIGNMENT_CENTER.
+    pub fn set_horizontal_alignment(&self, alignment: HorizontalAlignment) -> Result<()> {
+        try_c!(self, set_horizontal_alignment, {
+            Ok(set_horizontal_alignment(self.as_ptr(), alignment.into()))
+        })
+    }
+
+    /// Reset the minimum size of this LabelButton to |size|.
+    pub fn set_minimum_size(&self, size: &Size) -> Result<()> {
+        try_c!(self, set_minimum_size, {
+            let size = size.into();
+
+            Ok(set_minimum_size(self.as_ptr(), &size))
+        })
+    }
+
+    /// Reset the maximum size of this LabelButton to |size|.
+    pub fn set_maximum_size(&self, size: &Size) -> Result<()> {
+        try_c!(self, set_maximum_size, {
+            let size = size.into();
+
+            Ok(set_maximum_size(self.as_ptr(), &size))
+        })
+    }
+}
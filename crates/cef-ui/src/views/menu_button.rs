@@ -0,0 +1,253 @@
+use crate::{
+    debug_assert_currently_on, ref_counted_ptr, try_c, unwind::catch_unwind_or, view_delegate_base,
+    Button, ButtonDelegateCallbacks, CefString, LabelButton, MenuModel, Point, RefCountedPtr,
+    Result, ThreadId, View, ViewDelegateWrappable, Wrappable, Wrapped
+};
+use cef_ui_sys::{
+    cef_button_delegate_t, cef_button_t, cef_label_button_t, cef_menu_anchor_position_t,
+    cef_menu_button_create, cef_menu_button_delegate_t, cef_menu_button_pressed_lock_t,
+    cef_menu_button_t, cef_point_t, cef_view_t
+};
+
+/// Specifies how a menu will be anchored for non-RTL languages. The opposite
+/// position will be used for RTL languages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MenuAnchorPosition {
+    TopLeft,
+    TopRight,
+    BottomCenter
+}
+
+impl From<cef_menu_anchor_position_t> for MenuAnchorPosition {
+    fn from(value: cef_menu_anchor_position_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_menu_anchor_position_t> for MenuAnchorPosition {
+    fn from(value: &cef_menu_anchor_position_t) -> Self {
+        match value {
+            cef_menu_anchor_position_t::CEF_MENU_ANCHOR_TOPLEFT => MenuAnchorPosition::TopLeft,
+            cef_menu_anchor_position_t::CEF_MENU_ANCHOR_TOPRIGHT => MenuAnchorPosition::TopRight,
+            cef_menu_anchor_position_t::CEF_MENU_ANCHOR_BOTTOMCENTER => {
+                MenuAnchorPosition::BottomCenter
+            },
+        }
+    }
+}
+
+impl From<MenuAnchorPosition> for cef_menu_anchor_position_t {
+    fn from(value: MenuAnchorPosition) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&MenuAnchorPosition> for cef_menu_anchor_position_t {
+    fn from(value: &MenuAnchorPosition) -> Self {
+        match value {
+            MenuAnchorPosition::TopLeft => cef_menu_anchor_position_t::CEF_MENU_ANCHOR_TOPLEFT,
+            MenuAnchorPosition::TopRight => cef_menu_anchor_position_t::CEF_MENU_ANCHOR_TOPRIGHT,
+            MenuAnchorPosition::BottomCenter => {
+                cef_menu_anchor_position_t::CEF_MENU_ANCHOR_BOTTOMCENTER
+            },
+        }
+    }
+}
+
+// MenuButton is a button with optional text, icon and/or menu marker that
+// shows a menu when clicked with the left mouse button. All size and position
+// values are in density independent pixels (DIP) unless otherwise indicated.
+// Methods must be called on the browser process UI thread unless otherwise
+// indicated.
+ref_counted_ptr!(MenuButton, cef_menu_button_t);
+view_deref!(MenuButton, LabelButton);
+
+impl MenuButton {
+    /// Create a new MenuButton. A |delegate| must be provided to call show_menu()
+    /// when the button is clicked. |text| will be shown on the MenuButton and
+    /// used as the default accessible name.
+    pub fn create(delegate: MenuButtonDelegate, text: &str) -> Option<Self> {
+        unsafe {
+            let text = CefString::new(text);
+
+            Self::from_ptr(cef_menu_button_create(delegate.into_raw(), text.as_ptr()))
+        }
+    }
+
+    /// Returns this MenuButton as a LabelButton.
+    pub fn as_label_button(&self) -> LabelButton {
+        unsafe {
+            LabelButton::from_ptr_unchecked(self.clone().into_raw() as *mut cef_label_button_t)
+        }
+    }
+
+    /// Returns this MenuButton as a Button.
+    pub fn as_button(&self) -> Button {
+        unsafe { Button::from_ptr_unchecked(self.clone().into_raw() as *mut cef_button_t) }
+    }
+
+    /// Returns this MenuButton as a View.
+    pub fn as_view(&self) -> View {
+        unsafe { View::from_ptr_unchecked(self.clone().into_raw() as *mut cef_view_t) }
+    }
+
+    /// Show a menu with contents |menu_model|. |screen_point| specifies the menu
+    /// position in screen coordinates. |anchor_position| specifies how the menu
+    /// will be anchored relative to |screen_point|. This function should be
+    /// called from cef_menu_button_delegate_t::on_menu_button_pressed().
+    pub fn show_menu(
+        &self,
+        menu_model: MenuModel,
+        screen_point: &Point,
+        anchor_position: MenuAnchorPosition
+    ) -> Result<()> {
+        try_c!(self, show_menu, {
+            let screen_point = screen_point.into();
+
+            Ok(show_menu(
+                self.as_ptr(),
+                menu_model.into_raw(),
+                &screen_point,
+                anchor_position.into()
+            ))
+        })
+    }
+
+    /// Show the menu for this button. Results in a call to
+    /// cef_menu_button_delegate_t::on_menu_button_pressed().
+    pub fn trigger_menu(&self) -> Result<()> {
+        try_c!(self, trigger_menu, { Ok(trigger_menu(self.as_ptr())) })
+    }
+}
+
+// MenuButton pressed lock is released when this object is destroyed.
+ref_counted_ptr!(MenuButtonPressedLock, cef_menu_button_pressed_lock_t);
+
+/// Implement this structure to handle MenuButton events. The functions of this
+/// structure will be called on the browser process UI thread unless otherwise
+/// indicated.
+pub trait MenuButtonDelegateCallbacks: ButtonDelegateCallbacks {
+    /// Called when |button| is pressed. Call cef_menu_button_t::show_menu() to
+    /// show a popup menu at |screen_point|. When showing a custom popup such as a
+    /// window keep a reference to |button_pressed_lock| until the popup is hidden
+    /// to maintain the pressed button state.
+    fn on_menu_button_pressed(
+        &mut self,
+        _menu_button: MenuButton,
+        _screen_point: &Point,
+        _button_pressed_lock: MenuButtonPressedLock
+    ) {
+    }
+}
+
+// Implement this structure to handle MenuButton events. The functions of this
+// structure will be called on the browser process UI thread unless otherwise
+// indicated.
+ref_counted_ptr!(MenuButtonDelegate, cef_menu_button_delegate_t);
+
+impl MenuButtonDelegate {
+    pub fn new<C: MenuButtonDelegateCallbacks>(delegate: C) -> Self {
+        Self(MenuButtonDelegateWrapper::new(delegate).wrap())
+    }
+}
+
+/// Translates CEF -> Rust callbacks.
+struct MenuButtonDelegateWrapper<C: MenuButtonDelegateCallbacks>(C);
+
+impl<C: MenuButtonDelegateCallbacks> MenuButtonDelegateWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
+    }
+
+    /// Called when |button| is pressed.
+    unsafe extern "C" fn c_on_button_pressed(
+        this: *mut cef_button_delegate_t,
+        button: *mut cef_button_t
+    ) {
+        catch_unwind_or("MenuButtonDelegateWrapper::c_on_button_pressed", (), || {
+            debug_assert_currently_on!(ThreadId::Ui);
+
+            let this: &mut Self = Wrapped::wrappable(this as *mut cef_menu_button_delegate_t);
+            let button = Button::from_ptr_unchecked(button);
+
+            this.0.on_button_pressed(button);
+        })
+    }
+
+    /// Called when the state of |button| changes.
+    unsafe extern "C" fn c_on_button_state_changed(
+        this: *mut cef_button_delegate_t,
+        button: *mut cef_button_t
+    ) {
+        catch_unwind_or(
+            "MenuButtonDelegateWrapper::c_on_button_state_changed",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this as *mut cef_menu_button_delegate_t);
+                let button = Button::from_ptr_unchecked(button);
+
+                this.0
+                    .on_button_state_changed(button);
+            }
+        )
+    }
+
+    /// Called when |button| is pressed. Call cef_menu_button_t::show_menu() to
+    /// show a popup menu at |screen_point|. When showing a custom popup such as a
+    /// window keep a reference to |button_pressed_lock| until the popup is hidden
+    /// to maintain the pressed button state.
+    unsafe extern "C" fn c_on_menu_button_pressed(
+        this: *mut cef_menu_button_delegate_t,
+        menu_button: *mut cef_menu_button_t,
+        screen_point: *const cef_point_t,
+        button_pressed_lock: *mut cef_menu_button_pressed_lock_t
+    ) {
+        catch_unwind_or(
+            "MenuButtonDelegateWrapper::c_on_menu_button_pressed",
+            (),
+            || {
+                debug_assert_currently_on!(ThreadId::Ui);
+
+                let this: &mut Self = Wrapped::wrappable(this);
+                let menu_button = MenuButton::from_ptr_unchecked(menu_button);
+                let screen_point: Point = (&*screen_point).into();
+                let button_pressed_lock =
+                    MenuButtonPressedLock::from_ptr_unchecked(button_pressed_lock);
+
+                this.0
+                    .on_menu_button_pressed(menu_button, &screen_point, button_pressed_lock);
+            }
+        )
+    }
+}
+
+impl<C: MenuButtonDelegateCallbacks> ViewDelegateWrappable for MenuButtonDelegateWrapper<C> {
+    type Callbacks = C;
+
+    fn view_delegate(&mut self) -> &mut Self::Callbacks {
+        &mut self.0
+    }
+}
+
+impl<C: MenuButtonDelegateCallbacks> Wrappable for MenuButtonDelegateWrapper<C> {
+    type Cef = cef_menu_button_delegate_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<cef_menu_button_delegate_t> {
+        RefCountedPtr::wrap(
+            cef_menu_button_delegate_t {
+                base: cef_button_delegate_t {
+                    base: view_delegate_base::<Self>(),
+
+                    on_button_pressed:       Some(Self::c_on_button_pressed),
+                    on_button_state_changed: Some(Self::c_on_button_state_changed)
+                },
+
+                on_menu_button_pressed: Some(Self::c_on_menu_button_pressed)
+            },
+            self
+        )
+    }
+}
@@ -0,0 +1,38 @@
+// Implements Deref from a views type to the type that it extends, which gives
+// every views type the API of its base types (ultimately View). The extending
+// CEF structure has the extended structure as its first member and all views
+// types are #[repr(transparent)] wrappers around a RefCountedPtr, so the
+// reference can be reinterpreted.
+macro_rules! view_deref {
+    ($rust:ident, $target:ident) => {
+        impl std::ops::Deref for $rust {
+            type Target = $target;
+
+            fn deref(&self) -> &Self::Target {
+                unsafe { &*(self as *const Self as *const $target) }
+            }
+        }
+    };
+}
+
+mod browser_view;
+mod button;
+mod display;
+mod label_button;
+mod layout;
+mod menu_button;
+mod overlay_controller;
+mod panel;
+mod view;
+mod window;
+
+pub use browser_view::*;
+pub use button::*;
+pub use display::*;
+pub use label_button::*;
+pub use layout::*;
+pub use menu_button::*;
+pub use overlay_controller::*;
+pub use panel::*;
+pub use view::*;
+pub use window::*;
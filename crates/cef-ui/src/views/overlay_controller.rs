@@ -0,0 +1,224 @@
+use crate::{ref_counted_ptr, try_c, Insets, Point, Rect, Result, Size, View, Window};
+use cef_ui_sys::{cef_docking_mode_t, cef_overlay_controller_t};
+use std::ffi::c_int;
+
+/// Docking modes supported by cef_window_t::add_overlay().
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DockingMode {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Custom
+}
+
+impl From<cef_docking_mode_t> for DockingMode {
+    fn from(value: cef_docking_mode_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_docking_mode_t> for DockingMode {
+    fn from(value: &cef_docking_mode_t) -> Self {
+        match value {
+            cef_docking_mode_t::CEF_DOCKING_MODE_TOP_LEFT => DockingMode::TopLeft,
+            cef_docking_mode_t::CEF_DOCKING_MODE_TOP_RIGHT => DockingMode::TopRight,
+            cef_docking_mode_t::CEF_DOCKING_MODE_BOTTOM_LEFT => DockingMode::BottomLeft,
+            cef_docking_mode_t::CEF_DOCKING_MODE_BOTTOM_RIGHT => DockingMode::BottomRight,
+            cef_docking_mode_t::CEF_DOCKING_MODE_CUSTOM => DockingMode::Custom
+        }
+    }
+}
+
+impl From<DockingMode> for cef_docking_mode_t {
+    fn from(value: DockingMode) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&DockingMode> for cef_docking_mode_t {
+    fn from(value: &DockingMode) -> Self {
+        match value {
+            DockingMode::TopLeft => cef_docking_mode_t::CEF_DOCKING_MODE_TOP_LEFT,
+            DockingMode::TopRight => cef_docking_mode_t::CEF_DOCKING_MODE_TOP_RIGHT,
+            DockingMode::BottomLeft => cef_docking_mode_t::CEF_DOCKING_MODE_BOTTOM_LEFT,
+            DockingMode::BottomRight => cef_docking_mode_t::CEF_DOCKING_MODE_BOTTOM_RIGHT,
+            DockingMode::Custom => cef_docking_mode_t::CEF_DOCKING_MODE_CUSTOM
+        }
+    }
+}
+
+// Controller for an overlay that contains a contents View added via
+// cef_window_t::add_overlay_view. Methods exposed by this controller should be
+// called in preference to functions of the same name exposed by the contents
+// View unless otherwise indicated. Methods must be called on the browser
+// process UI thread unless otherwise indicated.
+ref_counted_ptr!(OverlayController, cef_overlay_controller_t);
+
+impl OverlayController {
+    /// Returns true (1) if this control is valid.
+    pub fn is_valid(&self) -> Result<bool> {
+        try_c!(self, is_valid, { Ok(is_valid(self.as_ptr()) != 0) })
+    }
+
+    /// Returns true (1) if this object is the same as |that| object.
+    pub fn is_same(&self, that: OverlayController) -> Result<bool> {
+        try_c!(self, is_same, {
+            Ok(is_same(self.as_ptr(), that.into_raw()) != 0)
+        })
+    }
+
+    /// Returns the contents View for this overlay.
+    pub fn get_contents_view(&self) -> Result<Option<View>> {
+        try_c!(self, get_contents_view, {
+            Ok(View::from_ptr(get_contents_view(self.as_ptr())))
+        })
+    }
+
+    /// Returns the top-level Window hosting this overlay. Use this function
+    /// instead of calling get_window() on the contents View.
+    pub fn get_window(&self) -> Result<Option<Window>> {
+        try_c!(self, get_window, {
+            Ok(Window::from_ptr(get_window(self.as_ptr())))
+        })
+    }
+
+    /// Returns the docking mode for this overlay.
+    pub fn get_docking_mode(&self) -> Result<DockingMode> {
+        try_c!(self, get_docking_mode, {
+            Ok(get_docking_mode(self.as_ptr()).into())
+        })
+    }
+
+    /// Destroy this overlay.
+    pub fn destroy(&self) -> Result<()> {
+        try_c!(self, destroy, { Ok(destroy(self.as_ptr())) })
+    }
+
+    /// Sets the bounds (size and position) of this overlay. This will set the
+    /// bounds of the contents View to match and trigger a re-layout if
+    /// necessary. |bounds| is in parent coordinates and any insets configured on
+    /// this overlay will be ignored. Use this function only for overlays created
+    /// with a docking mode value of CEF_DOCKING_MODE_CUSTOM. With other docking
+    /// modes modify the insets of this overlay and/or layout of the contents View
+    /// and call size_to_preferred_size() instead to calculate the new size and
+    /// re-position the overlay if necessary.
+    pub fn set_bounds(&self, bounds: &Rect) -> Result<()> {
+        try_c!(self, set_bounds, {
+            let bounds = bounds.into();
+
+            Ok(set_bounds(self.as_ptr(), &bounds))
+        })
+    }
+
+    /// Returns the bounds (size and position) of this overlay in parent
+    /// coordinates.
+    pub fn get_bounds(&self) -> Result<Rect> {
+        try_c!(self, get_bounds, { Ok(get_bounds(self.as_ptr()).into()) })
+    }
+
+    /// Returns the bounds (size and position) of this overlay in DIP screen
+    /// coordinates.
+    pub fn get_bounds_in_screen(&self) -> Result<Rect> {
+        try_c!(self, get_bounds_in_screen, {
+            Ok(get_bounds_in_screen(self.as_ptr()).into())
+        })
+    }
+
+    /// Sets the size of this overlay without changing the position. This will set
+    /// the size of the contents View to match and trigger a re-layout if
+    /// necessary. |size| is in parent coordinates and any insets configured on
+    /// this overlay will be ignored. Use this function only for overlays created
+    /// with a docking mode value of CEF_DOCKING_MODE_CUSTOM. With other docking
+    /// modes modify the insets of this overlay and/or layout of the contents View
+    /// and call size_to_preferred_size() instead to calculate the new size and
+    /// re-position the overlay if necessary.
+    pub fn set_size(&self, size: &Size) -> Result<()> {
+        try_c!(self, set_size, {
+            let size = size.into();
+
+            Ok(set_size(self.as_ptr(), &size))
+        })
+    }
+
+    /// Returns the size of this overlay in parent coordinates.
+    pub fn get_size(&self) -> Result<Size> {
+        try_c!(self, get_size, { Ok(get_size(self.as_ptr()).into()) })
+    }
+
+    /// Sets the position of this overlay without changing the size. |position| is
+    /// in parent coordinates and any insets configured on this overlay will be
+    /// ignored. Use this function only for overlays created with a docking mode
+    /// value of CEF_DOCKING_MODE_CUSTOM. With other docking modes modify the
+    /// insets of this overlay and/or layout of the contents View and call
+    /// size_to_preferred_size() instead to calculate the new location and
+    /// re-position the overlay if necessary.
+    pub fn set_position(&self, position: &Point) -> Result<()> {
+        try_c!(self, set_position, {
+            let position = position.into();
+
+            Ok(set_position(self.as_ptr(), &position))
+        })
+    }
+
+    /// Returns the position of this overlay in parent coordinates.
+    pub fn get_position(&self) -> Result<Point> {
+        try_c!(self, get_position, {
+            Ok(get_position(self.as_ptr()).into())
+        })
+    }
+
+    /// Sets the insets for this overlay. |insets| is in parent coordinates. Use
+    /// this function only for overlays created with a docking mode value other
+    /// than CEF_DOCKING_MODE_CUSTOM.
+    pub fn set_insets(&self, insets: &Insets) -> Result<()> {
+        try_c!(self, set_insets, {
+            let insets = insets.into();
+
+            Ok(set_insets(self.as_ptr(), &insets))
+        })
+    }
+
+    /// Returns the insets for this overlay in parent coordinates.
+    pub fn get_insets(&self) -> Result<Insets> {
+        try_c!(self, get_insets, { Ok(get_insets(self.as_ptr()).into()) })
+    }
+
+    /// Size this overlay to its preferred size and trigger a re-layout if
+    /// necessary. The position of overlays created with a docking mode value of
+    /// CEF_DOCKING_MODE_CUSTOM will not be modified by calling this function.
+    /// With other docking modes this function may re-position the overlay if
+    /// necessary to accommodate the new size and any insets configured on the
+    /// contents View.
+    pub fn size_to_preferred_size(&self) -> Result<()> {
+        try_c!(self, size_to_preferred_size, {
+            Ok(size_to_preferred_size(self.as_ptr()))
+        })
+    }
+
+    /// Sets whether this overlay is visible. Overlays are hidden by default. If
+    /// this overlay is hidden then it and any child Views will not be drawn and,
+    /// if any of those Views currently have focus, then focus will also be
+    /// cleared. Painting is scheduled as needed.
+    pub fn set_visible(&self, visible: bool) -> Result<()> {
+        try_c!(self, set_visible, {
+            Ok(set_visible(self.as_ptr(), visible as c_int))
+        })
+    }
+
+    /// Returns whether this overlay is visible. A View may be visible but still
+    /// not drawn in a Window if any parent Views are hidden. Call is_drawn() to
+    /// determine whether this overlay and all parent Views are visible and will
+    /// be drawn.
+    pub fn is_visible(&self) -> Result<bool> {
+        try_c!(self, is_visible, { Ok(is_visible(self.as_ptr()) != 0) })
+    }
+
+    /// Returns whether this overlay is visible and drawn in a Window. A View is
+    /// drawn if it and all parent Views are visible. To determine if the
+    /// containing Window is visible to the user on-screen call is_visible() on
+    /// the Window.
+    pub fn is_drawn(&self) -> Result<bool> {
+        try_c!(self, is_drawn, { Ok(is_drawn(self.as_ptr()) != 0) })
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{
+    ref_counted_ptr, try_c, view_delegate_base, BoxLayout, BoxLayoutSettings, FillLayout, Layout,
+    RefCountedPtr, Result, View, ViewDelegateCallbacks, ViewDelegateWrappable, Window, Wrappable
+};
+use cef_ui_sys::{cef_panel_create, cef_panel_delegate_t, cef_panel_t, cef_view_t};
+use std::{ffi::c_int, ptr::null_mut};
+
+// A Panel is a container in the views hierarchy that can contain other Views
+// as children. Methods must be called on the browser process UI thread unless
+// otherwise indicated.
+ref_counted_ptr!(Panel, cef_panel_t);
+view_deref!(Panel, View);
+
+impl Panel {
+    /// Create a new Panel.
+    pub fn create(delegate: Option<PanelDelegate>) -> Option<Self> {
+        unsafe {
+            let delegate = delegate
+                .map(|delegate| delegate.into_raw())
+                .unwrap_or_else(null_mut);
+
+            Self::from_ptr(cef_panel_create(delegate))
+        }
+    }
+
+    /// Returns this Panel as a View.
+    pub fn as_view(&self) -> View {
+        unsafe { View::from_ptr_unchecked(self.clone().into_raw() as *mut cef_view_t) }
+    }
+
+    /// Returns this Panel as a Window or NULL if this is not a Window.
+    pub fn as_window(&self) -> Result<Option<Window>> {
+        try_c!(self, as_window, {
+            Ok(Window::from_ptr(as_window(self.as_ptr())))
+        })
+    }
+
+    /// Set this Panel's Layout to FillLayout and return the FillLayout object.
+    pub fn set_to_fill_layout(&self) -> Result<Option<FillLayout>> {
+        try_c!(self, set_to_fill_layout, {
+            Ok(FillLayout::from_ptr(set_to_fill_layout(self.as_ptr())))
+        })
+    }
+
+    /// Set this Panel's Layout to BoxLayout and return the BoxLayout object.
+    pub fn set_to_box_layout(&self, settings: &BoxLayoutSettings) -> Result<Option<BoxLayout>> {
+        try_c!(self, set_to_box_layout, {
+            Ok(BoxLayout::from_ptr(set_to_box_layout(
+                self.as_ptr(),
+                settings.as_raw()
+            )))
+        })
+    }
+
+    /// Get the Layout.
+    pub fn get_layout(&self) -> Result<Option<Layout>> {
+        try_c!(self, get_layout, {
+            Ok(Layout::from_ptr(get_layout(self.as_ptr())))
+        })
+    }
+
+    /// Lay out the child Views (set their bounds based on sizing heuristics
+    /// specific to the current Layout).
+    pub fn layout(&self) -> Result<()> {
+        try_c!(self, layout, { Ok(layout(self.as_ptr())) })
+    }
+
+    /// Add a child View.
+    pub fn add_child_view(&self, view: View) -> Result<()> {
+        try_c!(self, add_child_view, {
+            Ok(add_child_view(self.as_ptr(), view.into_raw()))
+        })
+    }
+
+    /// Add a child View at the specified |index|. If |index| matches the result
+    /// of GetChildCount() then the View will be added at the end.
+    pub fn add_child_view_at(&self, view: View, index: usize) -> Result<()> {
+        try_c!(self, add_child_view_at, {
+            Ok(add_child_view_at(
+                self.as_ptr(),
+                view.into_raw(),
+                index as c_int
+            ))
+        })
+    }
+
+    /// Move the child View to the specified |index|. A negative value for |index|
+    /// will move the View to the end.
+    pub fn reorder_child_view(&self, view: View, index: Option<usize>) -> Result<()> {
+        try_c!(self, reorder_child_view, {
+            Ok(reorder_child_view(
+                self.as_ptr(),
+                view.into_raw(),
+                index.map_or(-1, |index| index as c_int)
+            ))
+        })
+    }
+
+    /// Remove a child View. The View can then be added to another Panel.
+    pub fn remove_child_view(&self, view: View) -> Result<()> {
+        try_c!(self, remove_child_view, {
+            Ok(remove_child_view(self.as_ptr(), view.into_raw()))
+        })
+    }
+
+    /// Remove all child Views. The removed Views will be deleted if the client
+    /// holds no references to them.
+    pub fn remove_all_child_views(&self) -> Result<()> {
+        try_c!(self, remove_all_child_views, {
+            Ok(remove_all_child_views(self.as_ptr()))
+        })
+    }
+
+    /// Returns the number of child Views.
+    pub fn get_child_view_count(&self) -> Result<usize> {
+        try_c!(self, get_child_view_count, {
+            Ok(get_child_view_count(self.as_ptr()))
+        })
+    }
+
+    /// Returns the child View at the specified |index|.
+    pub fn get_child_view_at(&self, index: usize) -> Result<Option<View>> {
+        try_c!(self, get_child_view_at, {
+            Ok(View::from_ptr(get_child_view_at(
+                self.as_ptr(),
+                index as c_int
+            )))
+        })
+    }
+
+    /// Returns all child Views.
+    pub fn get_child_views(&self) -> Result<Vec<View>> {
+        let count = self.get_child_view_count()?;
+        let mut views = Vec::with_capacity(count);
+
+        for index in 0..count {
+            if let Some(view) = self.get_child_view_at(index)? {
+                views.push(view);
+            }
+        }
+
+        Ok(views)
+    }
+}
+
+/// Implement this structure to handle Panel events. The functions of this
+/// structure will be called on the browser process UI thread unless otherwise
+/// indicated.
+pub trait PanelDelegateCallbacks: ViewDelegateCallbacks {}
+
+// Implement this structure to handle Panel events. The functions of this
+// structure will be called on the browser process UI thread unless otherwise
+// indicated.
+ref_counted_ptr!(PanelDelegate, cef_panel_delegate_t);
+
+impl PanelDelegate {
+    pub fn new<C: PanelDelegateCallbacks>(delegate: C) -> Self {
+        Self(PanelDelegateWrapper::new(delegate).wrap())
+    }
+}
+
+/// Translates CEF -> Rust callbacks.
+struct PanelDelegateWrapper<C: PanelDelegateCallbacks>(C);
+
+impl<C: PanelDelegateCallbacks> PanelDelegateWrapper<C> {
+    pub fn new(delegate: C) -> Self {
+        Self(delegate)
+    }
+}
+
+impl<C: PanelDelegateCallbacks> ViewDelegateWrappable for PanelDelegateWrapper<C> {
+    type Callbacks = C;
+
+    fn view_delegate(&mut self) -> &mut Self::Callbacks {
+        &mut self.0
+    }
+}
+
+impl<C: PanelDelegateCallbacks> Wrappable for PanelDelegateWrapper<C> {
+    type Cef = cef_panel_delegate_t;
+
+    /// Converts this to a smart pointer.
+    fn wrap(self) -> RefCountedPtr<cef_panel_delegate_t> {
+        RefCountedPtr::wrap(
+            cef_panel_delegate_t {
+                base: view_delegate_base::<Self>()
+            },
+            self
+        )
+    }
+}
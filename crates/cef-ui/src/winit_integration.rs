@@ -0,0 +1,758 @@
+use crate::{
+    BrowserHost, ClickCounter, Context, CursorType, Error, EventFlags, KeyEvent, KeyEventType,
+    MessagePump, MessagePumpWaker, MouseButtonType, MouseEvent, NativeWindowHandle, Point, Rect,
+    Result, ScreenInfo, WheelDelta, WindowInfo, WindowsKeyCode
+};
+#[cfg(target_os = "linux")]
+use crate::OzonePlatform;
+use anyhow::anyhow;
+use cef_ui_sys::cef_window_handle_t;
+use parking_lot::Mutex;
+use std::time::Duration;
+use winit::{
+    dpi::PhysicalPosition,
+    event::{ElementState, MouseButton, MouseScrollDelta, WindowEvent},
+    event_loop::{ControlFlow, EventLoopProxy, EventLoopWindowTarget},
+    keyboard::{KeyCode, KeyLocation, ModifiersState, PhysicalKey},
+    platform::scancode::PhysicalKeyExtScancode,
+    raw_window_handle::{HasWindowHandle, RawWindowHandle},
+    window::{CursorIcon, Window}
+};
+#[cfg(target_os = "linux")]
+use winit::raw_window_handle::{HasDisplayHandle, RawDisplayHandle};
+
+/// Returns the native handle of a winit window, suitable for use as the parent
+/// of a CEF browser. Wayland surfaces cannot host CEF child windows and are
+/// rejected; use windowless_window_info there instead.
+pub fn native_window_handle(window: &Window) -> Result<NativeWindowHandle> {
+    let handle = window
+        .window_handle()
+        .map_err(|_| Error::InvalidHandle("winit::window::Window"))?;
+
+    let handle: cef_window_handle_t = match handle.as_raw() {
+        #[cfg(target_os = "linux")]
+        RawWindowHandle::Xlib(handle) => handle.window,
+        #[cfg(target_os = "linux")]
+        RawWindowHandle::Xcb(handle) => handle.window.get() as cef_window_handle_t,
+        #[cfg(target_os = "windows")]
+        RawWindowHandle::Win32(handle) => handle.hwnd.get() as cef_window_handle_t,
+        #[cfg(target_os = "macos")]
+        RawWindowHandle::AppKit(handle) => handle.ns_view.as_ptr(),
+        _ => return Err(Error::InvalidHandle("winit::window::Window"))
+    };
+
+    NativeWindowHandle::try_from(handle)
+}
+
+/// Returns window info that creates a windowed browser filling the client area
+/// of |window|. The browser does not follow the size of |window| by itself;
+/// WinitInputHandler resizes it when |window| is resized.
+pub fn child_window_info(window: &Window) -> Result<WindowInfo> {
+    let size = window.inner_size();
+    let bounds = Rect::new(0, 0, size.width as i32, size.height as i32);
+
+    Ok(WindowInfo::as_child(native_window_handle(window)?, &bounds))
+}
+
+/// Resizes the child window of a windowed browser to |width| x |height|
+/// physical pixels, keeping it at the top left of its parent.
+#[cfg(target_os = "windows")]
+fn resize_child_window(window: NativeWindowHandle, width: u32, height: u32) -> Result<()> {
+    use cef_ui_sys::{SetWindowPos, SWP_NOACTIVATE, SWP_NOZORDER};
+    use std::ptr::null_mut;
+
+    let window: cef_window_handle_t = window.try_into()?;
+    let flags = SWP_NOZORDER | SWP_NOACTIVATE;
+
+    match unsafe { SetWindowPos(window, null_mut(), 0, 0, width as i32, height as i32, flags) } {
+        0 => Err(anyhow!("Failed to resize the browser window.").into()),
+        _ => Ok(())
+    }
+}
+
+/// Resizes the child window of a windowed browser to |width| x |height|
+/// physical pixels, keeping it at the top left of its parent.
+#[cfg(target_os = "linux")]
+fn resize_child_window(window: NativeWindowHandle, width: u32, height: u32) -> Result<()> {
+    use cef_ui_sys::{cef_get_xdisplay, XDisplay};
+    use std::ffi::{c_int, c_uint, c_ulong};
+
+    #[link(name = "X11")]
+    extern "C" {
+        fn XMoveResizeWindow(
+            display: *mut XDisplay,
+            window: c_ulong,
+            x: c_int,
+            y: c_int,
+            width: c_uint,
+            height: c_uint
+        ) -> c_int;
+        fn XFlush(display: *mut XDisplay) -> c_int;
+    }
+
+    let window: cef_window_handle_t = window.try_into()?;
+    let display = unsafe { cef_get_xdisplay() };
+
+    if display.is_null() {
+        return Err(anyhow!("Failed to resize the browser window; there is no X11 display.").into());
+    }
+
+    // X11 rejects windows with a zero dimension, e.g. while minimized.
+    unsafe {
+        XMoveResizeWindow(display, window, 0, 0, width.max(1), height.max(1));
+        XFlush(display);
+    }
+
+    Ok(())
+}
+
+/// On macOS the browser view is created with an autoresizing mask and follows
+/// the size of its parent view.
+#[cfg(target_os = "macos")]
+fn resize_child_window(_window: NativeWindowHandle, _width: u32, _height: u32) -> Result<()> {
+    Ok(())
+}
+
+/// Returns window info that creates an off-screen browser whose dialogs and
+/// context menus are parented to |window|. Use WinitInputHandler to forward
+/// input to it.
+/// Wayland surfaces cannot be used as parents, so on Wayland the browser has
+/// no parent window and uses the main screen for monitor info.
+pub fn windowless_window_info(window: &Window) -> Result<WindowInfo> {
+    let is_wayland = matches!(
+        window
+            .window_handle()
+            .map(|handle| handle.as_raw()),
+        Ok(RawWindowHandle::Wayland(_))
+    );
+
+    match is_wayland {
+        true => Ok(WindowInfo::as_windowless(None)),
+        false => Ok(WindowInfo::as_windowless(Some(native_window_handle(
+            window
+        )?)))
+    }
+}
+
+/// Returns the Ozone platform that matches the display server of
+/// |event_loop|. Pass it to OzonePlatform::apply_to_command_line so that CEF
+/// connects to the same display server as winit.
+#[cfg(target_os = "linux")]
+pub fn ozone_platform(event_loop: &impl HasDisplayHandle) -> OzonePlatform {
+    match event_loop
+        .display_handle()
+        .map(|handle| handle.as_raw())
+    {
+        Ok(RawDisplayHandle::Xlib(_) | RawDisplayHandle::Xcb(_)) => OzonePlatform::X11,
+        Ok(RawDisplayHandle::Wayland(_)) => OzonePlatform::Wayland,
+        _ => OzonePlatform::detect()
+    }
+}
+
+/// Returns screen info for the monitor that |window| is on, with its scale
+/// factor as the device scale factor. Return it from
+/// RenderHandlerCallbacks::get_screen_info so that windowless browsers paint
+/// at the monitor's native resolution, and call
+/// BrowserHost::notify_screen_info_changed on WindowEvent::ScaleFactorChanged.
+/// winit does not report work areas, so the available area is the whole
+/// monitor.
+pub fn screen_info(window: &Window) -> ScreenInfo {
+    let scale_factor = window.scale_factor();
+    let rect = match window.current_monitor() {
+        Some(monitor) => {
+            let position = monitor
+                .position()
+                .to_logical::<f64>(scale_factor);
+            let size = monitor
+                .size()
+                .to_logical::<f64>(scale_factor);
+
+            Rect::new(
+                position.x.round() as i32,
+                position.y.round() as i32,
+                size.width.round() as i32,
+                size.height.round() as i32
+            )
+        },
+        None => {
+            let size = window
+                .inner_size()
+                .to_logical::<f64>(scale_factor);
+
+            Rect::new(0, 0, size.width.round() as i32, size.height.round() as i32)
+        }
+    };
+
+    ScreenInfo::new(rect).device_scale_factor(scale_factor as f32)
+}
+
+/// Returns the winit cursor icon that matches |cursor_type|, or None if the
+/// cursor should be hidden. winit cannot show page-defined cursors, so
+/// CursorType::Custom falls back to the default arrow.
+pub fn cursor_icon(cursor_type: CursorType) -> Option<CursorIcon> {
+    let icon = match cursor_type {
+        CursorType::None => return None,
+        CursorType::Pointer | CursorType::Custom | CursorType::DndNone => CursorIcon::Default,
+        CursorType::Cross => CursorIcon::Crosshair,
+        CursorType::Hand => CursorIcon::Pointer,
+        CursorType::IBeam => CursorIcon::Text,
+        CursorType::Wait => CursorIcon::Wait,
+        CursorType::Help => CursorIcon::Help,
+        CursorType::EastResize | CursorType::EastPanning => CursorIcon::EResize,
+        CursorType::NorthResize | CursorType::NorthPanning => CursorIcon::NResize,
+        CursorType::NorthEastResize | CursorType::NorthEastPanning => CursorIcon::NeResize,
+        CursorType::NorthWestResize | CursorType::NorthWestPanning => CursorIcon::NwResize,
+        CursorType::SouthResize | CursorType::SouthPanning => CursorIcon::SResize,
+        CursorType::SouthEastResize | CursorType::SouthEastPanning => CursorIcon::SeResize,
+        CursorType::SouthWestResize | CursorType::SouthWestPanning => CursorIcon::SwResize,
+        CursorType::WestResize | CursorType::WestPanning => CursorIcon::WResize,
+        CursorType::NorthSouthResize | CursorType::MiddlePanningVertical => CursorIcon::NsResize,
+        CursorType::EastWestResize | CursorType::MiddlePanningHorizontal => CursorIcon::EwResize,
+        CursorType::NorthEastSouthWestResize => CursorIcon::NeswResize,
+        CursorType::NorthWestSouthEastResize => CursorIcon::NwseResize,
+        CursorType::ColumnResize => CursorIcon::ColResize,
+        CursorType::RowResize => CursorIcon::RowResize,
+        CursorType::MiddlePanning => CursorIcon::AllScroll,
+        CursorType::Move | CursorType::DndMove => CursorIcon::Move,
+        CursorType::VerticalText => CursorIcon::VerticalText,
+        CursorType::Cell => CursorIcon::Cell,
+        CursorType::ContextMenu => CursorIcon::ContextMenu,
+        CursorType::Alias | CursorType::DndLink => CursorIcon::Alias,
+        CursorType::Progress => CursorIcon::Progress,
+        CursorType::NoDrop => CursorIcon::NoDrop,
+        CursorType::Copy | CursorType::DndCopy => CursorIcon::Copy,
+        CursorType::NotAllowed => CursorIcon::NotAllowed,
+        CursorType::ZoomIn => CursorIcon::ZoomIn,
+        CursorType::ZoomOut => CursorIcon::ZoomOut,
+        CursorType::Grab => CursorIcon::Grab,
+        CursorType::Grabbing => CursorIcon::Grabbing
+    };
+
+    Some(icon)
+}
+
+/// Shows the cursor that matches |cursor_type| over |window|. Call this from
+/// DisplayHandlerCallbacks::on_cursor_change for windowless browsers.
+pub fn set_cursor(window: &Window, cursor_type: CursorType) {
+    match cursor_icon(cursor_type) {
+        Some(icon) => {
+            window.set_cursor_icon(icon);
+            window.set_cursor_visible(true);
+        },
+        None => window.set_cursor_visible(false)
+    }
+}
+
+/// Wakes a winit event loop when CEF schedules message pump work by sending
+/// |event| through an EventLoopProxy. Handle the event by calling
+/// pump_message_loop.
+pub struct WinitMessagePumpWaker<T: 'static> {
+    proxy: Mutex<(EventLoopProxy<T>, T)>
+}
+
+impl<T: Clone + Send + 'static> WinitMessagePumpWaker<T> {
+    pub fn new(proxy: EventLoopProxy<T>, event: T) -> Self {
+        Self {
+            proxy: Mutex::new((proxy, event))
+        }
+    }
+}
+
+impl<T: Clone + Send + 'static> MessagePumpWaker for WinitMessagePumpWaker<T> {
+    fn wake(&self, _delay: Duration) {
+        let proxy = self.proxy.lock();
+
+        // The event loop may already be gone during shutdown.
+        let _ = proxy.0.send_event(proxy.1.clone());
+    }
+}
+
+/// Performs CEF message loop work if it is due and schedules the next wake up
+/// of the winit event loop accordingly. Call this from Event::AboutToWait and
+/// when the event sent by WinitMessagePumpWaker is received. This must be
+/// called on the main application thread.
+pub fn pump_message_loop<T>(
+    pump: &MessagePump,
+    context: &Context,
+    target: &EventLoopWindowTarget<T>
+) -> Result<()> {
+    pump.do_work_if_due(context)?;
+
+    target.set_control_flow(ControlFlow::WaitUntil(pump.next_work()));
+
+    Ok(())
+}
+
+/// Translates winit window events into CEF browser events. For windowless
+/// browsers keyboard, mouse, focus and resize events are forwarded. For
+/// windowed browsers CEF receives input directly from the native window, so
+/// only focus and move/resize notifications are forwarded.
+#[derive(Debug)]
+pub struct WinitInputHandler {
+    modifiers: ModifiersState,
+    buttons:   EventFlags,
+    cursor:    Point,
+    clicks:    ClickCounter,
+    wheel:     WheelDelta
+}
+
+impl WinitInputHandler {
+    pub fn new() -> Self {
+        Self {
+            modifiers: ModifiersState::empty(),
+            buttons:   EventFlags::None,
+            cursor:    Point::new(0, 0),
+            clicks:    ClickCounter::new(),
+            wheel:     WheelDelta::new()
+        }
+    }
+
+    /// Forwards |event|, which was received for |window|, to the browser owned
+    /// by |host|. Returns true if the event was forwarded.
+    pub fn handle_window_event(
+        &mut self,
+        host: &BrowserHost,
+        window: &Window,
+        event: &WindowEvent
+    ) -> Result<bool> {
+        if let WindowEvent::ModifiersChanged(modifiers) = event {
+            self.modifiers = modifiers.state();
+        }
+
+        match host.is_window_rendering_disabled()? {
+            true => self.handle_windowless_event(host, window, event),
+            false => Self::handle_windowed_event(host, event)
+        }
+    }
+
+    /// Forwards an event to a windowed browser.
+    fn handle_windowed_event(host: &BrowserHost, event: &WindowEvent) -> Result<bool> {
+        match event {
+            WindowEvent::Focused(focused) => host.set_focus(*focused)?,
+            WindowEvent::Moved(_) => host.notify_move_or_resize_started()?,
+            WindowEvent::Resized(size) => {
+                resize_child_window(host.get_window_handle()?, size.width, size.height)?;
+                host.notify_move_or_resize_started()?;
+            },
+            _ => return Ok(false)
+        }
+
+        Ok(true)
+    }
+
+    /// Forwards an event to a windowless browser.
+    fn handle_windowless_event(
+        &mut self,
+        host: &BrowserHost,
+        window: &Window,
+        event: &WindowEvent
+    ) -> Result<bool> {
+        match event {
+            WindowEvent::Resized(_) => host.was_resized()?,
+            WindowEvent::ScaleFactorChanged { .. } => {
+                host.notify_screen_info_changed()?;
+                host.was_resized()?;
+            },
+            WindowEvent::Focused(focused) => host.set_focus(*focused)?,
+            WindowEvent::CursorMoved { position, .. } => {
+                self.cursor = Self::to_view(window, *position);
+
+                host.send_mouse_move_event(&self.mouse_event(), false)?;
+            },
+            WindowEvent::CursorLeft { .. } => {
+                host.send_mouse_move_event(&self.mouse_event(), true)?;
+            },
+            WindowEvent::MouseInput { state, button, .. } => {
+                let Some(button) = Self::mouse_button(*button) else {
+                    return Ok(false);
+                };
+                let mouse_up = *state == ElementState::Released;
+
+                if mouse_up {
+                    self.buttons.remove(button.event_flag());
+                } else {
+                    self.clicks.press(button, self.cursor);
+                    self.buttons.insert(button.event_flag());
+                }
+
+                host.send_mouse_click_event(
+                    &self.mouse_event(),
+                    button,
+                    mouse_up,
+                    self.clicks.count()
+                )?;
+            },
+            WindowEvent::MouseWheel { delta, .. } => {
+                let (delta_x, delta_y) = match delta {
+                    MouseScrollDelta::LineDelta(x, y) => self
+                        .wheel
+                        .lines(*x as f64, *y as f64),
+                    MouseScrollDelta::PixelDelta(delta) => {
+                        let delta = delta.to_logical::<f64>(window.scale_factor());
+
+                        self.wheel
+                            .pixels(delta.x, delta.y)
+                    }
+                };
+
+                host.send_mouse_wheel_event(&self.mouse_event(), delta_x, delta_y)?;
+            },
+            WindowEvent::KeyboardInput { event, .. } => {
+                let mut modifiers = self.event_flags();
+
+                if event.repeat {
+                    modifiers.insert(EventFlags::IsRepeat);
+                }
+
+                match event.location {
+                    KeyLocation::Left => modifiers.insert(EventFlags::IsLeft),
+                    KeyLocation::Right => modifiers.insert(EventFlags::IsRight),
+                    KeyLocation::Numpad => modifiers.insert(EventFlags::IsKeyPad),
+                    KeyLocation::Standard => {}
+                }
+
+                let text: Vec<u16> = event
+                    .text
+                    .as_ref()
+                    .map(|text| text.encode_utf16().collect())
+                    .unwrap_or_default();
+                let character = text.first().copied().unwrap_or(0);
+                let key_event = KeyEvent {
+                    event_type: match event.state {
+                        ElementState::Pressed => KeyEventType::RawKeyDown,
+                        ElementState::Released => KeyEventType::KeyUp
+                    },
+                    modifiers,
+                    windows_key_code: event.physical_key.into(),
+                    native_key_code: Self::native_key_code(event.physical_key),
+                    is_system_key: false,
+                    character,
+                    unmodified_character: character,
+                    focus_on_editable_field: false
+                };
+
+                host.send_key_event(key_event.clone())?;
+
+                // Text input is delivered as separate character events.
+                if event.state == ElementState::Pressed {
+                    for character in text {
+                        host.send_key_event(KeyEvent {
+                            event_type: KeyEventType::Char,
+                            character,
+                            unmodified_character: character,
+                            ..key_event.clone()
+                        })?;
+                    }
+                }
+            },
+            _ => return Ok(false)
+        }
+
+        Ok(true)
+    }
+
+    /// Converts a physical window position into view coordinates.
+    fn to_view(window: &Window, position: PhysicalPosition<f64>) -> Point {
+        let position = position.to_logical::<f64>(window.scale_factor());
+
+        Point::new(position.x as i32, position.y as i32)
+    }
+
+    /// Returns a mouse event at the current cursor position.
+    fn mouse_event(&self) -> MouseEvent {
+        MouseEvent::at(self.cursor).modifiers(self.event_flags())
+    }
+
+    /// Returns the flags for the currently pressed modifier keys and buttons.
+    fn event_flags(&self) -> EventFlags {
+        self.buttons | EventFlags::from(self.modifiers)
+    }
+
+    /// Maps a winit mouse button to a CEF button.
+    fn mouse_button(button: MouseButton) -> Option<MouseButtonType> {
+        match button {
+            MouseButton::Left => Some(MouseButtonType::Left),
+            MouseButton::Middle => Some(MouseButtonType::Middle),
+            MouseButton::Right => Some(MouseButtonType::Right),
+            _ => None
+        }
+    }
+
+    /// Returns the platform key code that CEF expects in native_key_code.
+    fn native_key_code(key: PhysicalKey) -> i32 {
+        let scancode = key.to_scancode().unwrap_or(0) as i32;
+
+        // X11 key codes are offset from the Linux scancode.
+        match cfg!(target_os = "linux") && scancode != 0 {
+            true => scancode + 8,
+            false => scancode
+        }
+    }
+}
+
+impl Default for WinitInputHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Maps a physical key to the Windows virtual key code used by the DOM.
+impl From<PhysicalKey> for WindowsKeyCode {
+    fn from(key: PhysicalKey) -> Self {
+        let PhysicalKey::Code(code) = key else {
+            return WindowsKeyCode::Unknown;
+        };
+
+        match code {
+            KeyCode::Backquote => WindowsKeyCode::OEM3,
+            KeyCode::Backslash => WindowsKeyCode::OEM5,
+            KeyCode::BracketLeft => WindowsKeyCode::OEM4,
+            KeyCode::BracketRight => WindowsKeyCode::OEM6,
+            KeyCode::Comma => WindowsKeyCode::OEMComma,
+            KeyCode::Digit0 => WindowsKeyCode::Key0,
+            KeyCode::Digit1 => WindowsKeyCode::Key1,
+            KeyCode::Digit2 => WindowsKeyCode::Key2,
+            KeyCode::Digit3 => WindowsKeyCode::Key3,
+            KeyCode::Digit4 => WindowsKeyCode::Key4,
+            KeyCode::Digit5 => WindowsKeyCode::Key5,
+            KeyCode::Digit6 => WindowsKeyCode::Key6,
+            KeyCode::Digit7 => WindowsKeyCode::Key7,
+            KeyCode::Digit8 => WindowsKeyCode::Key8,
+            KeyCode::Digit9 => WindowsKeyCode::Key9,
+            KeyCode::Equal => WindowsKeyCode::OEMPlus,
+            KeyCode::IntlBackslash => WindowsKeyCode::OEM102,
+            KeyCode::KeyA => WindowsKeyCode::A,
+            KeyCode::KeyB => WindowsKeyCode::B,
+            KeyCode::KeyC => WindowsKeyCode::C,
+            KeyCode::KeyD => WindowsKeyCode::D,
+            KeyCode::KeyE => WindowsKeyCode::E,
+            KeyCode::KeyF => WindowsKeyCode::F,
+            KeyCode::KeyG => WindowsKeyCode::G,
+            KeyCode::KeyH => WindowsKeyCode::H,
+            KeyCode::KeyI => WindowsKeyCode::I,
+            KeyCode::KeyJ => WindowsKeyCode::J,
+            KeyCode::KeyK => WindowsKeyCode::K,
+            KeyCode::KeyL => WindowsKeyCode::L,
+            KeyCode::KeyM => WindowsKeyCode::M,
+            KeyCode::KeyN => WindowsKeyCode::N,
+            KeyCode::KeyO => WindowsKeyCode::O,
+            KeyCode::KeyP => WindowsKeyCode::P,
+            KeyCode::KeyQ => WindowsKeyCode::Q,
+            KeyCode::KeyR => WindowsKeyCode::R,
+            KeyCode::KeyS => WindowsKeyCode::S,
+            KeyCode::KeyT => WindowsKeyCode::T,
+            KeyCode::KeyU => WindowsKeyCode::U,
+            KeyCode::KeyV => WindowsKeyCode::V,
+            KeyCode::KeyW => WindowsKeyCode::W,
+            KeyCode::KeyX => WindowsKeyCode::X,
+            KeyCode::KeyY => WindowsKeyCode::Y,
+            KeyCode::KeyZ => WindowsKeyCode::Z,
+            KeyCode::Minus => WindowsKeyCode::OEMMinus,
+            KeyCode::Period => WindowsKeyCode::OEMPeriod,
+            KeyCode::Quote => WindowsKeyCode::OEM7,
+            KeyCode::Semicolon => WindowsKeyCode::OEM1,
+            KeyCode::Slash => WindowsKeyCode::OEM2,
+            KeyCode::AltLeft => WindowsKeyCode::LMenu,
+            KeyCode::AltRight => WindowsKeyCode::RMenu,
+            KeyCode::Backspace => WindowsKeyCode::Back,
+            KeyCode::CapsLock => WindowsKeyCode::Capital,
+            KeyCode::ContextMenu => WindowsKeyCode::Apps,
+            KeyCode::ControlLeft => WindowsKeyCode::LControl,
+            KeyCode::ControlRight => WindowsKeyCode::RControl,
+            KeyCode::Enter | KeyCode::NumpadEnter => WindowsKeyCode::Return,
+            KeyCode::SuperLeft => WindowsKeyCode::LWin,
+            KeyCode::SuperRight => WindowsKeyCode::RWin,
+            KeyCode::ShiftLeft => WindowsKeyCode::LShift,
+            KeyCode::ShiftRight => WindowsKeyCode::RShift,
+            KeyCode::Space => WindowsKeyCode::Space,
+            KeyCode::Tab => WindowsKeyCode::Tab,
+            KeyCode::Convert => WindowsKeyCode::Convert,
+            KeyCode::KanaMode => WindowsKeyCode::Kana,
+            KeyCode::NonConvert => WindowsKeyCode::NonConvert,
+            KeyCode::Delete => WindowsKeyCode::Delete,
+            KeyCode::End => WindowsKeyCode::End,
+            KeyCode::Help => WindowsKeyCode::Help,
+            KeyCode::Home => WindowsKeyCode::Home,
+            KeyCode::Insert => WindowsKeyCode::Insert,
+            KeyCode::PageDown => WindowsKeyCode::Next,
+            KeyCode::PageUp => WindowsKeyCode::Prior,
+            KeyCode::ArrowDown => WindowsKeyCode::Down,
+            KeyCode::ArrowLeft => WindowsKeyCode::Left,
+            KeyCode::ArrowRight => WindowsKeyCode::Right,
+            KeyCode::ArrowUp => WindowsKeyCode::Up,
+            KeyCode::NumLock => WindowsKeyCode::NumLock,
+            KeyCode::Numpad0 => WindowsKeyCode::Numpad0,
+            KeyCode::Numpad1 => WindowsKeyCode::Numpad1,
+            KeyCode::Numpad2 => WindowsKeyCode::Numpad2,
+            KeyCode::Numpad3 => WindowsKeyCode::Numpad3,
+            KeyCode::Numpad4 => WindowsKeyCode::Numpad4,
+            KeyCode::Numpad5 => WindowsKeyCode::Numpad5,
+            KeyCode::Numpad6 => WindowsKeyCode::Numpad6,
+            KeyCode::Numpad7 => WindowsKeyCode::Numpad7,
+            KeyCode::Numpad8 => WindowsKeyCode::Numpad8,
+            KeyCode::Numpad9 => WindowsKeyCode::Numpad9,
+            KeyCode::NumpadAdd => WindowsKeyCode::Add,
+            KeyCode::NumpadComma => WindowsKeyCode::Separator,
+            KeyCode::NumpadDecimal => WindowsKeyCode::Decimal,
+            KeyCode::NumpadDivide => WindowsKeyCode::Divide,
+            KeyCode::NumpadMultiply | KeyCode::NumpadStar => WindowsKeyCode::Multiply,
+            KeyCode::NumpadSubtract => WindowsKeyCode::Subtract,
+            KeyCode::NumpadClear => WindowsKeyCode::Clear,
+            KeyCode::Escape => WindowsKeyCode::Escape,
+            KeyCode::PrintScreen => WindowsKeyCode::Snapshot,
+            KeyCode::ScrollLock => WindowsKeyCode::Scroll,
+            KeyCode::Pause => WindowsKeyCode::Pause,
+            KeyCode::BrowserBack => WindowsKeyCode::BrowserBack,
+            KeyCode::BrowserFavorites => WindowsKeyCode::BrowserFavorites,
+            KeyCode::BrowserForward => WindowsKeyCode::BrowserForward,
+            KeyCode::BrowserHome => WindowsKeyCode::BrowserHome,
+            KeyCode::BrowserRefresh => WindowsKeyCode::BrowserRefresh,
+            KeyCode::BrowserSearch => WindowsKeyCode::BrowserSearch,
+            KeyCode::BrowserStop => WindowsKeyCode::BrowserStop,
+            KeyCode::LaunchApp1 => WindowsKeyCode::LaunchApp1,
+            KeyCode::LaunchApp2 => WindowsKeyCode::LaunchApp2,
+            KeyCode::LaunchMail => WindowsKeyCode::LaunchMail,
+            KeyCode::MediaPlayPause => WindowsKeyCode::MediaPlayPause,
+            KeyCode::MediaSelect => WindowsKeyCode::LaunchMediaSelect,
+            KeyCode::MediaStop => WindowsKeyCode::MediaStop,
+            KeyCode::MediaTrackNext => WindowsKeyCode::MediaNextTrack,
+            KeyCode::MediaTrackPrevious => WindowsKeyCode::MediaPrevTrack,
+            KeyCode::Sleep => WindowsKeyCode::Sleep,
+            KeyCode::AudioVolumeDown => WindowsKeyCode::VolumeDown,
+            KeyCode::AudioVolumeMute => WindowsKeyCode::VolumeMute,
+            KeyCode::AudioVolumeUp => WindowsKeyCode::VolumeUp,
+            KeyCode::Select => WindowsKeyCode::Select,
+            KeyCode::F1 => WindowsKeyCode::F1,
+            KeyCode::F2 => WindowsKeyCode::F2,
+            KeyCode::F3 => WindowsKeyCode::F3,
+            KeyCode::F4 => WindowsKeyCode::F4,
+            KeyCode::F5 => WindowsKeyCode::F5,
+            KeyCode::F6 => WindowsKeyCode::F6,
+            KeyCode::F7 => WindowsKeyCode::F7,
+            KeyCode::F8 => WindowsKeyCode::F8,
+            KeyCode::F9 => WindowsKeyCode::F9,
+            KeyCode::F10 => WindowsKeyCode::F10,
+            KeyCode::F11 => WindowsKeyCode::F11,
+            KeyCode::F12 => WindowsKeyCode::F12,
+            KeyCode::F13 => WindowsKeyCode::F13,
+            KeyCode::F14 => WindowsKeyCode::F14,
+            KeyCode::F15 => WindowsKeyCode::F15,
+            KeyCode::F16 => WindowsKeyCode::F16,
+            KeyCode::F17 => WindowsKeyCode::F17,
+            KeyCode::F18 => WindowsKeyCode::F18,
+            KeyCode::F19 => WindowsKeyCode::F19,
+            KeyCode::F20 => WindowsKeyCode::F20,
+            KeyCode::F21 => WindowsKeyCode::F21,
+            KeyCode::F22 => WindowsKeyCode::F22,
+            KeyCode::F23 => WindowsKeyCode::F23,
+            KeyCode::F24 => WindowsKeyCode::F24,
+            _ => WindowsKeyCode::Unknown
+        }
+    }
+}
+
+/// Maps the winit modifier state to the Shift, Control, Alt and Command flags.
+impl From<ModifiersState> for EventFlags {
+    fn from(modifiers: ModifiersState) -> Self {
+        let mut flags = EventFlags::None;
+
+        if modifiers.shift_key() {
+            flags.insert(EventFlags::ShiftDown);
+        }
+
+        if modifiers.control_key() {
+            flags.insert(EventFlags::ControlDown);
+        }
+
+        if modifiers.alt_key() {
+            flags.insert(EventFlags::AltDown);
+        }
+
+        if modifiers.super_key() {
+            flags.insert(EventFlags::CommandDown);
+        }
+
+        flags
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn physical_key_matches_dom_code() {
+        // winit names key codes after the DOM KeyboardEvent.code values.
+        for code in [
+            KeyCode::Backquote,
+            KeyCode::Digit0,
+            KeyCode::Digit9,
+            KeyCode::Equal,
+            KeyCode::IntlBackslash,
+            KeyCode::KeyA,
+            KeyCode::KeyZ,
+            KeyCode::Slash,
+            KeyCode::AltRight,
+            KeyCode::Backspace,
+            KeyCode::ControlLeft,
+            KeyCode::Enter,
+            KeyCode::NumpadEnter,
+            KeyCode::ShiftRight,
+            KeyCode::PageDown,
+            KeyCode::ArrowUp,
+            KeyCode::Numpad5,
+            KeyCode::NumpadMultiply,
+            KeyCode::Escape,
+            KeyCode::MediaSelect,
+            KeyCode::AudioVolumeMute,
+            KeyCode::F1,
+            KeyCode::F24
+        ] {
+            assert_eq!(
+                Some(WindowsKeyCode::from(PhysicalKey::Code(code))),
+                WindowsKeyCode::from_dom_code(&format!("{:?}", code)),
+                "{:?}",
+                code
+            );
+        }
+    }
+
+    #[test]
+    fn physical_key_super() {
+        assert_eq!(
+            WindowsKeyCode::from(PhysicalKey::Code(KeyCode::SuperLeft)),
+            WindowsKeyCode::LWin
+        );
+        assert_eq!(
+            WindowsKeyCode::from(PhysicalKey::Code(KeyCode::SuperRight)),
+            WindowsKeyCode::RWin
+        );
+    }
+
+    #[test]
+    fn physical_key_unknown() {
+        assert_eq!(
+            WindowsKeyCode::from(PhysicalKey::Code(KeyCode::Fn)),
+            WindowsKeyCode::Unknown
+        );
+        assert_eq!(
+            WindowsKeyCode::from(PhysicalKey::Unidentified(
+                winit::keyboard::NativeKeyCode::Unidentified
+            )),
+            WindowsKeyCode::Unknown
+        );
+    }
+
+    #[test]
+    fn modifiers_state() {
+        assert_eq!(EventFlags::from(ModifiersState::empty()), EventFlags::None);
+        assert_eq!(
+            EventFlags::from(ModifiersState::SHIFT | ModifiersState::SUPER),
+            EventFlags::ShiftDown | EventFlags::CommandDown
+        );
+        assert_eq!(
+            EventFlags::from(ModifiersState::CONTROL | ModifiersState::ALT),
+            EventFlags::ControlDown | EventFlags::AltDown
+        );
+    }
+}
@@ -1,5 +1,4 @@
-use crate::{ref_counted_ptr, try_c, BinaryValue, CefString, CefStringList, CefTime};
-use anyhow::Result;
+use crate::{ref_counted_ptr, try_c, BinaryValue, CefString, CefStringList, CefTime, Result};
 use cef_ui_sys::{cef_x509cert_principal_t, cef_x509certificate_t};
 use chrono::{DateTime, Utc};
 
@@ -0,0 +1,408 @@
+use crate::{ref_counted_ptr, try_c, CefString, Result, StreamReader};
+use cef_ui_sys::{
+    cef_xml_encoding_type_t, cef_xml_node_type_t, cef_xml_reader_create, cef_xml_reader_t
+};
+use std::ffi::c_int;
+
+/// Supported XML encoding types. The parser supports ASCII, ISO-8859-1, and
+/// UTF16 (LE and BE) by default. All other types must be translated to UTF8
+/// before being passed to the parser. If a BOM is detected and the correct
+/// decoder is available then that decoder will be used automatically.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum XmlEncodingType {
+    None,
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Ascii
+}
+
+impl From<cef_xml_encoding_type_t> for XmlEncodingType {
+    fn from(value: cef_xml_encoding_type_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_xml_encoding_type_t> for XmlEncodingType {
+    fn from(value: &cef_xml_encoding_type_t) -> Self {
+        match value {
+            cef_xml_encoding_type_t::XML_ENCODING_NONE => XmlEncodingType::None,
+            cef_xml_encoding_type_t::XML_ENCODING_UTF8 => XmlEncodingType::Utf8,
+            cef_xml_encoding_type_t::XML_ENCODING_UTF16LE => XmlEncodingType::Utf16Le,
+            cef_xml_encoding_type_t::XML_ENCODING_UTF16BE => XmlEncodingType::Utf16Be,
+            cef_xml_encoding_type_t::XML_ENCODING_ASCII => XmlEncodingType::Ascii
+        }
+    }
+}
+
+impl From<XmlEncodingType> for cef_xml_encoding_type_t {
+    fn from(value: XmlEncodingType) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&XmlEncodingType> for cef_xml_encoding_type_t {
+    fn from(value: &XmlEncodingType) -> Self {
+        match value {
+            XmlEncodingType::None => cef_xml_encoding_type_t::XML_ENCODING_NONE,
+            XmlEncodingType::Utf8 => cef_xml_encoding_type_t::XML_ENCODING_UTF8,
+            XmlEncodingType::Utf16Le => cef_xml_encoding_type_t::XML_ENCODING_UTF16LE,
+            XmlEncodingType::Utf16Be => cef_xml_encoding_type_t::XML_ENCODING_UTF16BE,
+            XmlEncodingType::Ascii => cef_xml_encoding_type_t::XML_ENCODING_ASCII
+        }
+    }
+}
+
+/// XML node types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum XmlNodeType {
+    Unsupported,
+    ProcessingInstruction,
+    DocumentType,
+    ElementStart,
+    ElementEnd,
+    Attribute,
+    Text,
+    Cdata,
+    EntityReference,
+    Whitespace,
+    Comment
+}
+
+impl From<cef_xml_node_type_t> for XmlNodeType {
+    fn from(value: cef_xml_node_type_t) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&cef_xml_node_type_t> for XmlNodeType {
+    fn from(value: &cef_xml_node_type_t) -> Self {
+        match value {
+            cef_xml_node_type_t::XML_NODE_UNSUPPORTED => XmlNodeType::Unsupported,
+            cef_xml_node_type_t::XML_NODE_PROCESSING_INSTRUCTION => {
+                XmlNodeType::ProcessingInstruction
+            },
+            cef_xml_node_type_t::XML_NODE_DOCUMENT_TYPE => XmlNodeType::DocumentType,
+            cef_xml_node_type_t::XML_NODE_ELEMENT_START => XmlNodeType::ElementStart,
+            cef_xml_node_type_t::XML_NODE_ELEMENT_END => XmlNodeType::ElementEnd,
+            cef_xml_node_type_t::XML_NODE_ATTRIBUTE => XmlNodeType::Attribute,
+            cef_xml_node_type_t::XML_NODE_TEXT => XmlNodeType::Text,
+            cef_xml_node_type_t::XML_NODE_CDATA => XmlNodeType::Cdata,
+            cef_xml_node_type_t::XML_NODE_ENTITY_REFERENCE => XmlNodeType::EntityReference,
+            cef_xml_node_type_t::XML_NODE_WHITESPACE => XmlNodeType::Whitespace,
+            cef_xml_node_type_t::XML_NODE_COMMENT => XmlNodeType::Comment
+        }
+    }
+}
+
+impl From<XmlNodeType> for cef_xml_node_type_t {
+    fn from(value: XmlNodeType) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&XmlNodeType> for cef_xml_node_type_t {
+    fn from(value: &XmlNodeType) -> Self {
+        match value {
+            XmlNodeType::Unsupported => cef_xml_node_type_t::XML_NODE_UNSUPPORTED,
+            XmlNodeType::ProcessingInstruction => {
+                cef_xml_node_type_t::XML_NODE_PROCESSING_INSTRUCTION
+            },
+            XmlNodeType::DocumentType => cef_xml_node_type_t::XML_NODE_DOCUMENT_TYPE,
+            XmlNodeType::ElementStart => cef_xml_node_type_t::XML_NODE_ELEMENT_START,
+            XmlNodeType::ElementEnd => cef_xml_node_type_t::XML_NODE_ELEMENT_END,
+            XmlNodeType::Attribute => cef_xml_node_type_t::XML_NODE_ATTRIBUTE,
+            XmlNodeType::Text => cef_xml_node_type_t::XML_NODE_TEXT,
+            XmlNodeType::Cdata => cef_xml_node_type_t::XML_NODE_CDATA,
+            XmlNodeType::EntityReference => cef_xml_node_type_t::XML_NODE_ENTITY_REFERENCE,
+            XmlNodeType::Whitespace => cef_xml_node_type_t::XML_NODE_WHITESPACE,
+            XmlNodeType::Comment => cef_xml_node_type_t::XML_NODE_COMMENT
+        }
+    }
+}
+
+// Structure that supports the reading of XML data via the libxml streaming
+// API. The functions of this structure should only be called on the thread
+// that creates the object.
+ref_counted_ptr!(XmlReader, cef_xml_reader_t);
+
+impl XmlReader {
+    /// Create a new cef_xml_reader_t object. The returned object's functions can
+    /// only be called from the thread that created the object.
+    pub fn create(stream: StreamReader, encoding_type: XmlEncodingType, uri: &str) -> Option<Self> {
+        unsafe {
+            let uri = CefString::new(uri);
+
+            Self::from_ptr(cef_xml_reader_create(
+                stream.into_raw(),
+                encoding_type.into(),
+                uri.as_ptr()
+            ))
+        }
+    }
+
+    /// Moves the cursor to the next node and returns its type, or None if there
+    /// are no more nodes. This is a convenience for pull-style parsing loops.
+    pub fn next_node(&self) -> Result<Option<XmlNodeType>> {
+        match self.move_to_next_node()? {
+            true => self.get_type().map(Some),
+            false => Ok(None)
+        }
+    }
+
+    /// Moves the cursor to the next node in the document. This function must be
+    /// called at least once to set the current cursor position. Returns true (1)
+    /// if the cursor position was set successfully.
+    pub fn move_to_next_node(&self) -> Result<bool> {
+        try_c!(self, move_to_next_node, {
+            Ok(move_to_next_node(self.as_ptr()) != 0)
+        })
+    }
+
+    /// Close the document. This should be called directly to ensure that cleanup
+    /// occurs on the correct thread.
+    pub fn close(&self) -> Result<bool> {
+        try_c!(self, close, { Ok(close(self.as_ptr()) != 0) })
+    }
+
+    /// Returns true (1) if an error has been reported by the XML parser.
+    pub fn has_error(&self) -> Result<bool> {
+        try_c!(self, has_error, { Ok(has_error(self.as_ptr()) != 0) })
+    }
+
+    /// Returns the error string.
+    pub fn get_error(&self) -> Result<Option<String>> {
+        try_c!(self, get_error, {
+            let s = get_error(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s).map(|s| s.into()))
+        })
+    }
+
+    /// Returns the node type.
+    pub fn get_type(&self) -> Result<XmlNodeType> {
+        try_c!(self, get_type, { Ok(get_type(self.as_ptr()).into()) })
+    }
+
+    /// Returns the node depth. Depth starts at 0 for the root node.
+    pub fn get_depth(&self) -> Result<i32> {
+        try_c!(self, get_depth, { Ok(get_depth(self.as_ptr())) })
+    }
+
+    /// Returns the local name. See http://www.w3.org/TR/REC-xml-names/#NT-
+    /// LocalPart for additional details.
+    pub fn get_local_name(&self) -> Result<Option<String>> {
+        try_c!(self, get_local_name, {
+            let s = get_local_name(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s).map(|s| s.into()))
+        })
+    }
+
+    /// Returns the namespace prefix. See http://www.w3.org/TR/REC-xml-names/ for
+    /// additional details.
+    pub fn get_prefix(&self) -> Result<Option<String>> {
+        try_c!(self, get_prefix, {
+            let s = get_prefix(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s).map(|s| s.into()))
+        })
+    }
+
+    /// Returns the qualified name, equal to (Prefix:)LocalName. See
+    /// http://www.w3.org/TR/REC-xml-names/#ns-qualnames for additional details.
+    pub fn get_qualified_name(&self) -> Result<Option<String>> {
+        try_c!(self, get_qualified_name, {
+            let s = get_qualified_name(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s).map(|s| s.into()))
+        })
+    }
+
+    /// Returns the URI defining the namespace associated with the node. See
+    /// http://www.w3.org/TR/REC-xml-names/ for additional details.
+    pub fn get_namespace_uri(&self) -> Result<Option<String>> {
+        try_c!(self, get_namespace_uri, {
+            let s = get_namespace_uri(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s).map(|s| s.into()))
+        })
+    }
+
+    /// Returns the base URI of the node. See http://www.w3.org/TR/xmlbase/ for
+    /// additional details.
+    pub fn get_base_uri(&self) -> Result<Option<String>> {
+        try_c!(self, get_base_uri, {
+            let s = get_base_uri(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s).map(|s| s.into()))
+        })
+    }
+
+    /// Returns the xml:lang scope within which the node resides. See
+    /// http://www.w3.org/TR/REC-xml/#sec-lang-tag for additional details.
+    pub fn get_xml_lang(&self) -> Result<Option<String>> {
+        try_c!(self, get_xml_lang, {
+            let s = get_xml_lang(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s).map(|s| s.into()))
+        })
+    }
+
+    /// Returns true (1) if the node represents an NULL element. "<a/>" is an NULL
+    /// element but "<a></a>" is not.
+    pub fn is_empty_element(&self) -> Result<bool> {
+        try_c!(self, is_empty_element, {
+            Ok(is_empty_element(self.as_ptr()) != 0)
+        })
+    }
+
+    /// Returns true (1) if the node has a text value.
+    pub fn has_value(&self) -> Result<bool> {
+        try_c!(self, has_value, { Ok(has_value(self.as_ptr()) != 0) })
+    }
+
+    /// Returns the text value.
+    pub fn get_value(&self) -> Result<Option<String>> {
+        try_c!(self, get_value, {
+            let s = get_value(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s).map(|s| s.into()))
+        })
+    }
+
+    /// Returns true (1) if the node has attributes.
+    pub fn has_attributes(&self) -> Result<bool> {
+        try_c!(self, has_attributes, {
+            Ok(has_attributes(self.as_ptr()) != 0)
+        })
+    }
+
+    /// Returns the number of attributes.
+    pub fn get_attribute_count(&self) -> Result<usize> {
+        try_c!(self, get_attribute_count, {
+            Ok(get_attribute_count(self.as_ptr()))
+        })
+    }
+
+    /// Returns the value of the attribute at the specified 0-based index.
+    pub fn get_attribute_byindex(&self, index: i32) -> Result<Option<String>> {
+        try_c!(self, get_attribute_byindex, {
+            let s = get_attribute_byindex(self.as_ptr(), index as c_int);
+
+            Ok(CefString::from_userfree_ptr(s).map(|s| s.into()))
+        })
+    }
+
+    /// Returns the value of the attribute with the specified qualified name.
+    pub fn get_attribute_byqname(&self, qualified_name: &str) -> Result<Option<String>> {
+        try_c!(self, get_attribute_byqname, {
+            let qualified_name = CefString::new(qualified_name);
+
+            let s = get_attribute_byqname(self.as_ptr(), qualified_name.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s).map(|s| s.into()))
+        })
+    }
+
+    /// Returns the value of the attribute with the specified local name and
+    /// namespace URI.
+    pub fn get_attribute_bylname(
+        &self,
+        local_name: &str,
+        namespace_uri: &str
+    ) -> Result<Option<String>> {
+        try_c!(self, get_attribute_bylname, {
+            let local_name = CefString::new(local_name);
+            let namespace_uri = CefString::new(namespace_uri);
+
+            let s =
+                get_attribute_bylname(self.as_ptr(), local_name.as_ptr(), namespace_uri.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s).map(|s| s.into()))
+        })
+    }
+
+    /// Returns an XML representation of the current node's children.
+    pub fn get_inner_xml(&self) -> Result<Option<String>> {
+        try_c!(self, get_inner_xml, {
+            let s = get_inner_xml(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s).map(|s| s.into()))
+        })
+    }
+
+    /// Returns an XML representation of the current node including its children.
+    pub fn get_outer_xml(&self) -> Result<Option<String>> {
+        try_c!(self, get_outer_xml, {
+            let s = get_outer_xml(self.as_ptr());
+
+            Ok(CefString::from_userfree_ptr(s).map(|s| s.into()))
+        })
+    }
+
+    /// Returns the line number for the current node.
+    pub fn get_line_number(&self) -> Result<i32> {
+        try_c!(self, get_line_number, {
+            Ok(get_line_number(self.as_ptr()))
+        })
+    }
+
+    /// Moves the cursor to the attribute at the specified 0-based index. Returns
+    /// true (1) if the cursor position was set successfully.
+    pub fn move_to_attribute_byindex(&self, index: i32) -> Result<bool> {
+        try_c!(self, move_to_attribute_byindex, {
+            Ok(move_to_attribute_byindex(self.as_ptr(), index as c_int) != 0)
+        })
+    }
+
+    /// Moves the cursor to the attribute with the specified qualified name.
+    /// Returns true (1) if the cursor position was set successfully.
+    pub fn move_to_attribute_byqname(&self, qualified_name: &str) -> Result<bool> {
+        try_c!(self, move_to_attribute_byqname, {
+            let qualified_name = CefString::new(qualified_name);
+
+            Ok(move_to_attribute_byqname(self.as_ptr(), qualified_name.as_ptr()) != 0)
+        })
+    }
+
+    /// Moves the cursor to the attribute with the specified local name and
+    /// namespace URI. Returns true (1) if the cursor position was set
+    /// successfully.
+    pub fn move_to_attribute_bylname(&self, local_name: &str, namespace_uri: &str) -> Result<bool> {
+        try_c!(self, move_to_attribute_bylname, {
+            let local_name = CefString::new(local_name);
+            let namespace_uri = CefString::new(namespace_uri);
+
+            Ok(move_to_attribute_bylname(
+                self.as_ptr(),
+                local_name.as_ptr(),
+                namespace_uri.as_ptr()
+            ) != 0)
+        })
+    }
+
+    /// Moves the cursor to the first attribute in the current element. Returns
+    /// true (1) if the cursor position was set successfully.
+    pub fn move_to_first_attribute(&self) -> Result<bool> {
+        try_c!(self, move_to_first_attribute, {
+            Ok(move_to_first_attribute(self.as_ptr()) != 0)
+        })
+    }
+
+    /// Moves the cursor to the next attribute in the current element. Returns
+    /// true (1) if the cursor position was set successfully.
+    pub fn move_to_next_attribute(&self) -> Result<bool> {
+        try_c!(self, move_to_next_attribute, {
+            Ok(move_to_next_attribute(self.as_ptr()) != 0)
+        })
+    }
+
+    /// Moves the cursor back to the carrying element. Returns true (1) if the
+    /// cursor position was set successfully.
+    pub fn move_to_carrying_element(&self) -> Result<bool> {
+        try_c!(self, move_to_carrying_element, {
+            Ok(move_to_carrying_element(self.as_ptr()) != 0)
+        })
+    }
+}